@@ -2,9 +2,11 @@ use core::fmt;
 use std::sync::Arc;
 
 use anyhow::{Result, bail, ensure};
+use reblessive::TreeStack;
 use reblessive::tree::Stk;
 
-use super::store::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::store::{ListOptions, ObjectKey, ObjectMeta, ObjectStore, ObjectStream};
+use super::upload::Upload;
 use crate::catalog::providers::BucketProvider;
 use crate::catalog::{BucketDefinition, Permission};
 use crate::ctx::{Context, FrozenContext};
@@ -113,6 +115,24 @@ impl<'a> BucketController<'a> {
 		Ok(())
 	}
 
+	/// Starts a streaming upload into the specified key.
+	///
+	/// Permissions are checked when the upload is started, and the data only
+	/// replaces the contents of the key once the upload has been committed.
+	pub(crate) async fn upload(&mut self, key: ObjectKey) -> Result<Upload> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(&key), None).await?;
+
+		Upload::open(self.bucket.name.clone(), key, self.store.clone()).await
+	}
+
+	/// Checks that a file can be uploaded into the specified key, without
+	/// starting the upload.
+	pub(crate) async fn check_upload(&mut self, key: &ObjectKey) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await
+	}
+
 	/// Retrieves metadata for an object without fetching its contents.
 	///
 	/// Returns `None` if the object does not exist.
@@ -145,6 +165,19 @@ impl<'a> BucketController<'a> {
 		Ok(Some(Bytes(bytes)))
 	}
 
+	/// Retrieves the contents of an object as a stream of chunks.
+	///
+	/// Returns `None` if the object does not exist.
+	pub(crate) async fn stream(&mut self, key: &ObjectKey) -> Result<Option<ObjectStream>> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		self.store
+			.get_stream(key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			.map_err(anyhow::Error::new)
+	}
+
 	/// Deletes an object from the bucket.
 	///
 	/// This operation is idempotent - deleting a non-existent object is not an error.
@@ -336,6 +369,59 @@ impl<'a> BucketController<'a> {
 	}
 }
 
+/// Opens a streaming upload into a bucket, checking the permissions of the
+/// bucket within the specified context.
+pub(crate) async fn open_upload(
+	ctx: &FrozenContext,
+	opt: &Options,
+	bucket: &str,
+	key: ObjectKey,
+) -> Result<Upload> {
+	let mut stack = TreeStack::new();
+	stack
+		.enter(|stk| async move {
+			BucketController::new(stk, ctx, opt, None, bucket).await?.upload(key).await
+		})
+		.finish()
+		.await
+}
+
+/// Checks that a file can be uploaded into a bucket, checking the permissions
+/// of the bucket within the specified context.
+pub(crate) async fn check_upload(
+	ctx: &FrozenContext,
+	opt: &Options,
+	bucket: &str,
+	key: ObjectKey,
+) -> Result<()> {
+	let mut stack = TreeStack::new();
+	stack
+		.enter(|stk| async move {
+			BucketController::new(stk, ctx, opt, None, bucket).await?.check_upload(&key).await
+		})
+		.finish()
+		.await
+}
+
+/// Streams the contents of an object from a bucket, checking the permissions
+/// of the bucket within the specified context.
+///
+/// Returns `None` if the object does not exist.
+pub(crate) async fn open_stream(
+	ctx: &FrozenContext,
+	opt: &Options,
+	bucket: &str,
+	key: ObjectKey,
+) -> Result<Option<ObjectStream>> {
+	let mut stack = TreeStack::new();
+	stack
+		.enter(|stk| async move {
+			BucketController::new(stk, ctx, opt, None, bucket).await?.stream(&key).await
+		})
+		.finish()
+		.await
+}
+
 /// Represents the different operations that can be performed on a bucket.
 ///
 /// These operations are used for permission checking and action logging.
@@ -2,29 +2,49 @@
 //!
 //! This module provides abstractions for object storage backends (buckets) that can be used
 //! to store and retrieve binary data (files). It supports multiple storage backends including
-//! in-memory storage and local filesystem storage.
+//! in-memory storage, local filesystem storage, and S3-compatible object storage.
 //!
 //! The module is organized into:
 //! - `BucketController` - Controls bucket operations with permission checking
 //! - `BucketsManager` - Manages bucket connections and caching
+//! - `Upload` - Streaming and resumable multipart uploads
 //! - [`store`] - Object store trait and implementations
 
 use std::sync::Arc;
 
 mod controller;
 use anyhow::{Result, bail};
-pub(crate) use controller::BucketController;
 pub use controller::BucketOperation;
+pub(crate) use controller::{BucketController, check_upload, open_stream, open_upload};
 
 use crate::CommunityComposer;
 use crate::buc::store::ObjectStore;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::buc::store::file::FileStore;
 use crate::buc::store::memory::MemoryStore;
+#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+use crate::buc::store::s3::S3Store;
+#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+use crate::ctx::Context;
 use crate::err::Error;
 
 pub(crate) mod manager;
 pub mod store;
+pub(crate) mod upload;
+
+/// Checks that the endpoint of a bucket backend, which the server connects
+/// to, is allowed by the network capabilities. An S3 bucket without an
+/// `endpoint` option is checked against the endpoint of its region.
+#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+pub(crate) async fn check_backend_net(ctx: &Context, backend: &str) -> Result<()> {
+	if let Some(endpoint) = S3Store::endpoint(backend)? {
+		#[cfg(feature = "http")]
+		ctx.check_allowed_net(&endpoint).await?;
+		#[cfg(not(feature = "http"))]
+		bail!(Error::NetTargetNotAllowed(endpoint.to_string()));
+	}
+	Ok(())
+}
 
 /// Marker trait for bucket store provider requirements.
 ///
@@ -45,9 +65,9 @@ pub trait BucketStoreProviderRequirements: Send + Sync + 'static {}
 /// Trait for creating connections to bucket storage backends.
 ///
 /// Implementors of this trait can parse storage URLs and create appropriate
-/// [`ObjectStore`] instances. The community edition supports `memory://` and
-/// `file://` backends, while enterprise editions may support additional backends
-/// like S3, GCS, or Azure Blob Storage.
+/// [`ObjectStore`] instances. The community edition supports `memory://`,
+/// `file://` and `s3://` backends, while enterprise editions may support
+/// additional backends like GCS, or Azure Blob Storage.
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
 pub trait BucketStoreProvider: BucketStoreProviderRequirements {
@@ -88,6 +108,11 @@ impl BucketStoreProvider for CommunityComposer {
 			return Ok(Arc::new(FileStore::new(opts)));
 		}
 
+		#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+		if let Some(store) = S3Store::parse_url(url)? {
+			return Ok(Arc::new(store));
+		}
+
 		bail!(Error::UnsupportedBackend)
 	}
 }
@@ -3,13 +3,18 @@ use std::path::{Path as OsPath, PathBuf};
 use std::pin::Pin;
 
 use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
 use path_clean::PathClean;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
 use url::Url;
+use uuid::Uuid;
 use web_time::SystemTime;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{
+	BoxedWriter, ListOptions, ObjectKey, ObjectMeta, ObjectStore, ObjectStream, ObjectWriter,
+};
 use crate::cnf::BUCKET_FOLDER_ALLOWLIST;
 use crate::err::Error;
 
@@ -196,6 +201,48 @@ impl FileStore {
 	}
 }
 
+/// Streams data into a temporary file, which is moved into place on commit
+struct FileWriter {
+	file: File,
+	temp: PathBuf,
+	path: PathBuf,
+}
+
+impl ObjectWriter for FileWriter {
+	fn write<'a>(
+		&'a mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.file.write_all(&data).await.map_err(|e| format!("Failed to write to file: {}", e))
+		})
+	}
+
+	fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> {
+		Box::pin(async move {
+			let mut file = self.file;
+			file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
+			drop(file);
+
+			FileStore::ensure_parent_dirs(&self.path).await?;
+
+			tokio::fs::rename(&self.temp, &self.path)
+				.await
+				.map_err(|e| format!("Failed to rename file: {}", e))
+		})
+	}
+
+	fn abort(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> {
+		Box::pin(async move {
+			drop(self.file);
+
+			tokio::fs::remove_file(&self.temp)
+				.await
+				.map_err(|e| format!("Failed to delete file: {}", e))
+		})
+	}
+}
+
 /// Check if a path is allowed according to the allowlist
 fn is_path_allowed(path_to_check: &std::path::Path, lowercase_paths: bool) -> bool {
 	// If the allowlist is empty, nothing is allowed
@@ -575,4 +622,50 @@ impl ObjectStore for FileStore {
 			Ok(objects)
 		})
 	}
+
+	fn writer<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<BoxedWriter>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let path = self.to_os_path(key).await?;
+
+			// Uploads are written into a directory at the root of the bucket,
+			// which is never listed, so that they are moved into place atomically
+			let upload = ObjectKey::new(format!("/.uploads/{}", Uuid::new_v4().simple()));
+			let temp = self.to_os_path(&upload).await?;
+			Self::ensure_parent_dirs(&temp).await?;
+
+			let file =
+				File::create(&temp).await.map_err(|e| format!("Failed to create file: {}", e))?;
+
+			Ok(Some(Box::new(FileWriter {
+				file,
+				temp,
+				path,
+			}) as BoxedWriter))
+		})
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+
+			// Check if the file exists
+			if !Self::path_exists(&os_path).await? {
+				return Ok(None);
+			}
+
+			let file =
+				File::open(&os_path).await.map_err(|e| format!("Failed to read file: {}", e))?;
+
+			let stream =
+				ReaderStream::new(file).map_err(|e| format!("Failed to read file: {}", e)).boxed();
+
+			Ok(Some(stream as ObjectStream))
+		})
+	}
 }
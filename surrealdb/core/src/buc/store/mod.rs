@@ -5,6 +5,8 @@
 //! - [`ObjectKey`] - Normalized path representation for object keys
 //! - [`ObjectMeta`] - Metadata about stored objects
 //! - [`ListOptions`] - Options for listing objects in a bucket
+//! - [`ObjectWriter`] - Streaming writes into a single object
+//! - [`ObjectStream`] - Streaming reads from a single object
 
 use std::future::Future;
 use std::pin::Pin;
@@ -12,6 +14,7 @@ use std::sync::Arc;
 
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, stream};
 
 use crate::err::Error;
 use crate::val::{Datetime, File, Object, Value};
@@ -21,6 +24,8 @@ pub(crate) mod file;
 pub(crate) mod memory;
 pub(crate) mod path;
 pub(crate) mod prefixed;
+#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+pub(crate) mod s3;
 
 // Expose type for external composers
 pub use path::ObjectKey;
@@ -88,6 +93,30 @@ impl TryFrom<Object> for ListOptions {
 	}
 }
 
+/// A stream of chunks of data read from an object.
+pub type ObjectStream = Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>;
+
+/// A boxed [`ObjectWriter`] for a specific backend.
+pub type BoxedWriter = Box<dyn ObjectWriter>;
+
+/// A streaming write of data into a single object.
+///
+/// Data which is written only becomes visible at the key once the writer has
+/// been committed, at which point it replaces any existing data at the key.
+pub trait ObjectWriter: Send + 'static {
+	/// Appends data to the object.
+	fn write<'a>(
+		&'a mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+
+	/// Completes the write, storing the data which was written at the key.
+	fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+	/// Discards the data which was written so far.
+	fn abort(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+}
+
 /// Trait for object storage backends.
 ///
 /// This trait defines the core operations that all object storage implementations
@@ -175,6 +204,30 @@ pub trait ObjectStore: Send + Sync + 'static {
 		&'a self,
 		prefix: &'a ListOptions,
 	) -> Pin<Box<dyn Future<Output = Result<Vec<ObjectMeta>, String>> + Send + 'a>>;
+
+	/// Opens a streaming writer for the specified key.
+	///
+	/// Returns `Ok(None)` if the backend does not support streaming writes, in
+	/// which case the data is buffered and stored with [`put`](Self::put).
+	fn writer<'a>(
+		&'a self,
+		_key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<BoxedWriter>, String>> + Send + 'a>> {
+		Box::pin(async { Ok(None) })
+	}
+
+	/// Retrieves data from the specified key as a stream of chunks.
+	///
+	/// Returns `Ok(None)` if the key does not exist.
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let data = self.get(key).await?;
+			Ok(data.map(|data| stream::once(async { Ok(data) }).boxed()))
+		})
+	}
 }
 
 impl ObjectStore for Arc<dyn ObjectStore> {
@@ -260,4 +313,18 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 	) -> Pin<Box<dyn Future<Output = Result<Vec<ObjectMeta>, String>> + Send + 'a>> {
 		(**self).list(opts)
 	}
+
+	fn writer<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<BoxedWriter>, String>> + Send + 'a>> {
+		(**self).writer(key)
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectStream>, String>> + Send + 'a>> {
+		(**self).get_stream(key)
+	}
 }
@@ -8,7 +8,7 @@ use std::pin::Pin;
 
 use bytes::Bytes;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{BoxedWriter, ListOptions, ObjectKey, ObjectMeta, ObjectStore, ObjectStream};
 
 /// A wrapper that adds a prefix to all keys in an underlying [`ObjectStore`].
 ///
@@ -181,4 +181,22 @@ impl<T: ObjectStore> ObjectStore for PrefixedStore<T> {
 			Ok(mapped_objects)
 		})
 	}
+
+	fn writer<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<BoxedWriter>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.writer(&full_key).await })
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectStream>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.get_stream(&full_key).await })
+	}
 }
@@ -0,0 +1,672 @@
+//! S3-compatible object store implementation.
+//!
+//! This module provides an implementation of the [`ObjectStore`] trait which
+//! stores objects in an Amazon S3 bucket, or in any service which implements
+//! the S3 API, such as MinIO, Ceph, Cloudflare R2 or Garage.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::aws::{AmazonS3, AmazonS3Builder, AmazonS3ConfigKey, S3CopyIfNotExists};
+use object_store::path::Path;
+use object_store::{
+	Error as StoreError, ObjectMeta as StoreMeta, ObjectStore as _, ObjectStoreExt as _, PutMode,
+	PutOptions, WriteMultipart,
+};
+use url::Url;
+
+use super::{
+	BoxedWriter, ListOptions, ObjectKey, ObjectMeta, ObjectStore, ObjectStream, ObjectWriter,
+};
+use crate::cnf::BUCKET_S3_FROM_ENV;
+use crate::err::Error;
+
+/// The region of a bucket which is not configured with a region
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// An S3-compatible implementation of [`ObjectStore`].
+///
+/// # URL Format
+/// - `s3://bucket`
+/// - `s3://bucket?endpoint=http://localhost:9000&region=us-east-1&path_style=true`
+///
+/// The following query options are supported:
+/// - `endpoint` - The endpoint of an S3-compatible service
+/// - `region` - The region of the bucket
+/// - `access_key_id` and `secret_access_key` - The credentials of the bucket
+/// - `session_token` - A session token for temporary credentials
+/// - `path_style` - Whether the bucket is addressed in the path of requests,
+///   rather than in the host name, which most self-hosted services require
+///   (default: `false`)
+/// - `allow_http` - Whether unencrypted connections are allowed (default:
+///   `false`)
+///
+/// Options which are not specified in the URL are only read from the standard
+/// `AWS_*` environment variables when `SURREAL_BUCKET_S3_FROM_ENV` is enabled,
+/// so that the credentials of the server are not available to every bucket.
+/// Otherwise, a bucket without credentials in its URL is accessed anonymously.
+///
+/// Conditional copies and renames are made server-side, and require a service
+/// which supports conditional writes when completing a multipart upload.
+#[derive(Debug)]
+pub struct S3Store {
+	store: AmazonS3,
+}
+
+impl S3Store {
+	/// Parses a URL into an S3 store.
+	///
+	/// Returns `Ok(None)` if the URL does not refer to an S3 store.
+	pub fn parse_url(url: &str) -> Result<Option<Self>, Error> {
+		let Some(builder) = Self::builder(url)? else {
+			return Ok(None);
+		};
+		let store = builder.build().map_err(|e| Error::InvalidBucketUrl(e.to_string()))?;
+		Ok(Some(Self {
+			store,
+		}))
+	}
+
+	/// Returns the endpoint which the server connects to for an S3 URL.
+	///
+	/// This is the `endpoint` option of the URL, or the endpoint read from the
+	/// environment when `SURREAL_BUCKET_S3_FROM_ENV` is enabled, or otherwise
+	/// the Amazon S3 endpoint of the region of the bucket.
+	pub fn endpoint(url: &str) -> Result<Option<Url>, Error> {
+		let Some(builder) = Self::builder(url)? else {
+			return Ok(None);
+		};
+		let config = |key| builder.get_config_value(&key);
+		let endpoint = match config(AmazonS3ConfigKey::S3Endpoint)
+			.or_else(|| config(AmazonS3ConfigKey::Endpoint))
+		{
+			Some(v) => v,
+			None => {
+				let bucket = config(AmazonS3ConfigKey::Bucket).unwrap_or_default();
+				let region =
+					config(AmazonS3ConfigKey::Region).unwrap_or_else(|| DEFAULT_REGION.to_string());
+				let virtual_hosted = config(AmazonS3ConfigKey::VirtualHostedStyleRequest)
+					.is_some_and(|v| v.eq_ignore_ascii_case("true"));
+				match virtual_hosted {
+					true => format!("https://{bucket}.s3.{region}.amazonaws.com"),
+					false => format!("https://s3.{region}.amazonaws.com"),
+				}
+			}
+		};
+		Url::parse(&endpoint).map(Some).map_err(|_| {
+			Error::InvalidBucketUrl(format!("Invalid endpoint `{endpoint}` in the S3 url"))
+		})
+	}
+
+	/// Configures an S3 store from a URL.
+	///
+	/// Returns `Ok(None)` if the URL does not refer to an S3 store.
+	fn builder(url: &str) -> Result<Option<AmazonS3Builder>, Error> {
+		let Ok(url) = Url::parse(url) else {
+			return Ok(None);
+		};
+
+		if url.scheme() != "s3" {
+			return Ok(None);
+		}
+
+		let Some(bucket) = url.host_str().filter(|v| !v.is_empty()) else {
+			return Err(Error::InvalidBucketUrl(
+				"Expected a bucket name in the S3 url".to_string(),
+			));
+		};
+
+		let builder = if *BUCKET_S3_FROM_ENV {
+			AmazonS3Builder::from_env()
+		} else {
+			AmazonS3Builder::new()
+		};
+		// Conditional copies are made server-side, with a single part multipart
+		// upload which is only completed if the target does not exist
+		let mut builder =
+			builder.with_bucket_name(bucket).with_copy_if_not_exists(S3CopyIfNotExists::Multipart);
+		let mut credentials = false;
+		for (key, value) in url.query_pairs() {
+			credentials |= matches!(key.as_ref(), "access_key_id" | "secret_access_key");
+			builder = match key.as_ref() {
+				"endpoint" => builder.with_endpoint(value),
+				"region" => builder.with_region(value),
+				"access_key_id" => builder.with_access_key_id(value),
+				"secret_access_key" => builder.with_secret_access_key(value),
+				"session_token" => builder.with_token(value),
+				"path_style" => {
+					builder.with_virtual_hosted_style_request(!parse_bool(&key, &value)?)
+				}
+				"allow_http" => builder.with_allow_http(parse_bool(&key, &value)?),
+				_ => {
+					return Err(Error::InvalidBucketUrl(format!(
+						"Unexpected query option `{key}` in the S3 url"
+					)));
+				}
+			};
+		}
+
+		// Never fall back to the credentials of the server, or of its instance
+		if !*BUCKET_S3_FROM_ENV && !credentials {
+			builder = builder.with_skip_signature(true);
+		}
+
+		Ok(Some(builder))
+	}
+
+	/// Writes data to a key, unless the key already exists. Returns whether
+	/// the data was written.
+	async fn create(&self, key: &ObjectKey, data: Bytes) -> Result<bool, String> {
+		let opts = PutOptions {
+			mode: PutMode::Create,
+			..Default::default()
+		};
+		match self.store.put_opts(&path(key), data.into(), opts).await {
+			Ok(_) => Ok(true),
+			Err(StoreError::AlreadyExists {
+				..
+			}) => Ok(false),
+			Err(e) => Err(e.to_string()),
+		}
+	}
+
+	/// Copies a key to a target, unless the target already exists. Returns
+	/// whether the key was copied, or `None` if the key does not exist.
+	async fn copy_new(&self, key: &ObjectKey, target: &ObjectKey) -> Result<Option<bool>, String> {
+		match self.store.copy_if_not_exists(&path(key), &path(target)).await {
+			Ok(_) => Ok(Some(true)),
+			Err(StoreError::AlreadyExists {
+				..
+			}) => Ok(Some(false)),
+			Err(StoreError::NotFound {
+				..
+			}) => Ok(None),
+			Err(e) => Err(e.to_string()),
+		}
+	}
+}
+
+/// The number of parts of a multipart upload which are sent concurrently
+const MAX_CONCURRENT_PARTS: usize = 8;
+
+/// Streams data into a multipart upload, which is completed on commit
+struct S3Writer(WriteMultipart);
+
+impl ObjectWriter for S3Writer {
+	fn write<'a>(
+		&'a mut self,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.0.wait_for_capacity(MAX_CONCURRENT_PARTS).await.map_err(|e| e.to_string())?;
+			self.0.put(data);
+			Ok(())
+		})
+	}
+
+	fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> {
+		Box::pin(async move {
+			self.0.finish().await.map_err(|e| e.to_string())?;
+			Ok(())
+		})
+	}
+
+	fn abort(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> {
+		Box::pin(async move { self.0.abort().await.map_err(|e| e.to_string()) })
+	}
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, Error> {
+	if value.is_empty() {
+		return Ok(true);
+	}
+	value.parse().map_err(|_| {
+		Error::InvalidBucketUrl(format!("Expected to find a bool for query option `{key}`"))
+	})
+}
+
+/// Converts an object key into an S3 object path
+fn path(key: &ObjectKey) -> Path {
+	Path::from(key.as_str())
+}
+
+/// Converts S3 object metadata into object metadata
+fn meta(meta: StoreMeta) -> ObjectMeta {
+	ObjectMeta {
+		size: meta.size,
+		updated: meta.last_modified,
+		key: ObjectKey::new(meta.location.to_string()),
+	}
+}
+
+impl ObjectStore for S3Store {
+	fn put<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.store.put(&path(key), data.into()).await.map_err(|e| e.to_string())?;
+			Ok(())
+		})
+	}
+
+	fn put_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.create(key, data).await?;
+			Ok(())
+		})
+	}
+
+	fn get<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.store.get(&path(key)).await {
+				Ok(res) => Ok(Some(res.bytes().await.map_err(|e| e.to_string())?)),
+				Err(StoreError::NotFound {
+					..
+				}) => Ok(None),
+				Err(e) => Err(e.to_string()),
+			}
+		})
+	}
+
+	fn head<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectMeta>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.store.head(&path(key)).await {
+				Ok(res) => Ok(Some(ObjectMeta {
+					key: key.clone(),
+					..meta(res)
+				})),
+				Err(StoreError::NotFound {
+					..
+				}) => Ok(None),
+				Err(e) => Err(e.to_string()),
+			}
+		})
+	}
+
+	fn delete<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.store.delete(&path(key)).await {
+				Ok(_)
+				| Err(StoreError::NotFound {
+					..
+				}) => Ok(()),
+				Err(e) => Err(e.to_string()),
+			}
+		})
+	}
+
+	fn exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<bool, String>> + Send + 'a>> {
+		Box::pin(async move { Ok(self.head(key).await?.is_some()) })
+	}
+
+	fn copy<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.store.copy(&path(key), &path(target)).await {
+				Ok(_) => Ok(()),
+				Err(StoreError::NotFound {
+					..
+				}) => Err(format!("Source key does not exist: {}", key.as_str())),
+				Err(e) => Err(e.to_string()),
+			}
+		})
+	}
+
+	fn copy_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.copy_new(key, target).await?;
+			Ok(())
+		})
+	}
+
+	fn rename<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			self.copy(key, target).await?;
+			self.delete(key).await
+		})
+	}
+
+	fn rename_if_not_exists<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		target: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.copy_new(key, target).await? {
+				Some(true) => self.delete(key).await,
+				Some(false) => Ok(()),
+				None => Err(format!("Source key does not exist: {}", key.as_str())),
+			}
+		})
+	}
+
+	fn list<'a>(
+		&'a self,
+		opts: &'a ListOptions,
+	) -> Pin<Box<dyn Future<Output = Result<Vec<ObjectMeta>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			// S3 prefixes match whole path segments, so the objects are listed from
+			// the parent of the prefix, and then filtered by the prefix itself.
+			let prefix = opts.prefix.as_ref().map(|p| p.as_str()).unwrap_or("");
+			let parent = prefix.rfind('/').map(|i| Path::from(&prefix[..i]));
+			let objects = match &opts.start {
+				Some(start) => self.store.list_with_offset(parent.as_ref(), &path(start)),
+				None => self.store.list(parent.as_ref()),
+			};
+			let objects = objects
+				.map_ok(meta)
+				.try_filter(|v| futures::future::ready(v.key.as_str().starts_with(prefix)));
+			let objects = match opts.limit {
+				Some(limit) => objects.take(limit).try_collect().await,
+				None => objects.try_collect().await,
+			};
+			objects.map_err(|e: StoreError| e.to_string())
+		})
+	}
+
+	fn writer<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<BoxedWriter>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let upload = self.store.put_multipart(&path(key)).await.map_err(|e| e.to_string())?;
+			Ok(Some(Box::new(S3Writer(WriteMultipart::new(upload))) as BoxedWriter))
+		})
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ObjectStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			match self.store.get(&path(key)).await {
+				Ok(res) => Ok(Some(res.into_stream().map_err(|e| e.to_string()).boxed())),
+				Err(StoreError::NotFound {
+					..
+				}) => Ok(None),
+				Err(e) => Err(e.to_string()),
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+	use std::sync::Mutex;
+
+	use chrono::Utc;
+	use wiremock::matchers::any;
+	use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+	use super::*;
+
+	/// A minimal in-memory stand-in for an S3-compatible service, which
+	/// addresses buckets in the path of requests, like MinIO.
+	#[derive(Default)]
+	struct StandIn {
+		objects: Mutex<BTreeMap<String, Bytes>>,
+		parts: Mutex<BTreeMap<(String, u32), Bytes>>,
+	}
+
+	impl StandIn {
+		fn object(data: &Bytes) -> ResponseTemplate {
+			ResponseTemplate::new(200)
+				.insert_header("Content-Length", data.len().to_string())
+				.insert_header("Last-Modified", Utc::now().to_rfc2822())
+				.insert_header("ETag", "\"etag\"")
+		}
+	}
+
+	impl Respond for StandIn {
+		fn respond(&self, req: &Request) -> ResponseTemplate {
+			let mut objects = self.objects.lock().unwrap();
+			let Some(key) = req.url.path().strip_prefix("/bucket") else {
+				return ResponseTemplate::new(404);
+			};
+			let key = key.trim_start_matches('/').to_owned();
+			let query: BTreeMap<_, _> = req.url.query_pairs().into_owned().collect();
+			match req.method.as_str() {
+				"POST" if query.contains_key("uploads") => ResponseTemplate::new(200)
+					.set_body_string(
+						"<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+					),
+				"PUT" if query.contains_key("uploadId") => {
+					let part = query["partNumber"].parse().unwrap();
+					if let Some(source) = req.headers.get("x-amz-copy-source") {
+						let source = source.to_str().unwrap().trim_start_matches("bucket/");
+						let Some(data) = objects.get(source).cloned() else {
+							return ResponseTemplate::new(404);
+						};
+						self.parts.lock().unwrap().insert((key, part), data);
+						return ResponseTemplate::new(200).set_body_string(format!(
+							"<CopyPartResult><ETag>\"{part}\"</ETag></CopyPartResult>"
+						));
+					}
+					self.parts.lock().unwrap().insert((key, part), Bytes::from(req.body.clone()));
+					ResponseTemplate::new(200).insert_header("ETag", format!("\"{part}\""))
+				}
+				"POST" if query.contains_key("uploadId") => {
+					if req.headers.contains_key("if-none-match") && objects.contains_key(&key) {
+						return ResponseTemplate::new(412);
+					}
+					let parts = self.parts.lock().unwrap();
+					let data: Vec<u8> = parts
+						.range((key.clone(), 0)..=(key.clone(), u32::MAX))
+						.flat_map(|(_, data)| data.to_vec())
+						.collect();
+					objects.insert(key, Bytes::from(data));
+					ResponseTemplate::new(200).set_body_string(
+						"<CompleteMultipartUploadResult><ETag>\"etag\"</ETag></CompleteMultipartUploadResult>",
+					)
+				}
+				"PUT" => {
+					if let Some(source) = req.headers.get("x-amz-copy-source") {
+						let source = source.to_str().unwrap().trim_start_matches("bucket/");
+						let Some(data) = objects.get(source).cloned() else {
+							return ResponseTemplate::new(404);
+						};
+						objects.insert(key, data);
+						return ResponseTemplate::new(200);
+					}
+					if req.headers.contains_key("if-none-match") && objects.contains_key(&key) {
+						return ResponseTemplate::new(412);
+					}
+					objects.insert(key, Bytes::from(req.body.clone()));
+					ResponseTemplate::new(200).insert_header("ETag", "\"etag\"")
+				}
+				"GET" if key.is_empty() => {
+					let prefix = query.get("prefix").cloned().unwrap_or_default();
+					let start = query.get("start-after").cloned().unwrap_or_default();
+					let contents: String = objects
+						.iter()
+						.filter(|(k, _)| k.starts_with(&prefix) && **k > start)
+						.map(|(k, v)| {
+							format!(
+								"<Contents><Key>{k}</Key><Size>{}</Size><LastModified>{}</LastModified></Contents>",
+								v.len(),
+								Utc::now().to_rfc3339()
+							)
+						})
+						.collect();
+					ResponseTemplate::new(200).set_body_string(format!(
+						"<ListBucketResult><IsTruncated>false</IsTruncated>{contents}</ListBucketResult>"
+					))
+				}
+				"GET" => match objects.get(&key) {
+					Some(data) => Self::object(data).set_body_bytes(data.to_vec()),
+					None => ResponseTemplate::new(404),
+				},
+				"HEAD" => match objects.get(&key) {
+					Some(data) => Self::object(data),
+					None => ResponseTemplate::new(404),
+				},
+				"DELETE" if query.contains_key("uploadId") => {
+					self.parts.lock().unwrap().retain(|(k, _), _| *k != key);
+					ResponseTemplate::new(204)
+				}
+				"DELETE" => {
+					objects.remove(&key);
+					ResponseTemplate::new(204)
+				}
+				"POST" if req.url.query() == Some("delete") => {
+					let body = String::from_utf8_lossy(&req.body);
+					let deleted: String = body
+						.split("<Key>")
+						.skip(1)
+						.filter_map(|v| v.split("</Key>").next())
+						.map(|k| {
+							objects.remove(k);
+							format!("<Deleted><Key>{k}</Key></Deleted>")
+						})
+						.collect();
+					ResponseTemplate::new(200)
+						.set_body_string(format!("<DeleteResult>{deleted}</DeleteResult>"))
+				}
+				_ => ResponseTemplate::new(405),
+			}
+		}
+	}
+
+	async fn store(server: &MockServer) -> S3Store {
+		Mock::given(any()).respond_with(StandIn::default()).mount(server).await;
+		let url = format!(
+			"s3://bucket?endpoint={}&region=us-east-1&access_key_id=key&secret_access_key=secret&path_style&allow_http",
+			server.uri()
+		);
+		S3Store::parse_url(&url).unwrap().unwrap()
+	}
+
+	fn key(key: &str) -> ObjectKey {
+		ObjectKey::new(key)
+	}
+
+	#[test]
+	fn parse_url() {
+		assert!(S3Store::parse_url("memory").unwrap().is_none());
+		assert!(S3Store::parse_url("file:///tmp").unwrap().is_none());
+		assert!(S3Store::parse_url("s3://bucket?region=eu-west-2").unwrap().is_some());
+		assert!(S3Store::parse_url("s3://bucket?path_style=maybe").is_err());
+		assert!(S3Store::parse_url("s3://bucket?unknown=1").is_err());
+	}
+
+	#[test]
+	fn endpoint() {
+		assert_eq!(S3Store::endpoint("memory").unwrap(), None);
+		assert_eq!(
+			S3Store::endpoint("s3://bucket?region=eu-west-2").unwrap(),
+			Some(Url::parse("https://s3.eu-west-2.amazonaws.com").unwrap())
+		);
+		assert_eq!(
+			S3Store::endpoint("s3://bucket?path_style=false").unwrap(),
+			Some(Url::parse("https://bucket.s3.us-east-1.amazonaws.com").unwrap())
+		);
+		assert_eq!(
+			S3Store::endpoint("s3://bucket?endpoint=http://169.254.169.254:80").unwrap(),
+			Some(Url::parse("http://169.254.169.254").unwrap())
+		);
+		assert!(S3Store::endpoint("s3://bucket?endpoint=localhost").is_err());
+	}
+
+	#[tokio::test]
+	async fn objects() {
+		let server = MockServer::start().await;
+		let store = store(&server).await;
+		// Write and read objects
+		store.put(&key("a/one.txt"), Bytes::from_static(b"one")).await.unwrap();
+		store.put_if_not_exists(&key("a/one.txt"), Bytes::from_static(b"other")).await.unwrap();
+		assert_eq!(store.get(&key("a/one.txt")).await.unwrap().unwrap(), "one");
+		assert_eq!(store.get(&key("a/missing.txt")).await.unwrap(), None);
+		let meta = store.head(&key("a/one.txt")).await.unwrap().unwrap();
+		assert_eq!((meta.size, meta.key.as_str()), (3, "/a/one.txt"));
+		// Copy and rename objects
+		store.copy(&key("a/one.txt"), &key("a/two.txt")).await.unwrap();
+		store.rename(&key("a/two.txt"), &key("ab/three.txt")).await.unwrap();
+		store.put(&key("a/four.txt"), Bytes::from_static(b"four")).await.unwrap();
+		store.rename_if_not_exists(&key("a/four.txt"), &key("a/one.txt")).await.unwrap();
+		assert_eq!(store.get(&key("a/one.txt")).await.unwrap().unwrap(), "one");
+		assert!(!store.exists(&key("a/two.txt")).await.unwrap());
+		// Conditional copies never overwrite the target
+		store.copy_if_not_exists(&key("a/four.txt"), &key("a/one.txt")).await.unwrap();
+		assert_eq!(store.get(&key("a/one.txt")).await.unwrap().unwrap(), "one");
+		store.copy_if_not_exists(&key("a/four.txt"), &key("a/five.txt")).await.unwrap();
+		assert_eq!(store.get(&key("a/five.txt")).await.unwrap().unwrap(), "four");
+		store.copy_if_not_exists(&key("a/missing.txt"), &key("a/six.txt")).await.unwrap();
+		assert!(!store.exists(&key("a/six.txt")).await.unwrap());
+		store.rename_if_not_exists(&key("a/five.txt"), &key("a/six.txt")).await.unwrap();
+		assert_eq!(store.get(&key("a/six.txt")).await.unwrap().unwrap(), "four");
+		assert!(!store.exists(&key("a/five.txt")).await.unwrap());
+		assert!(store.rename_if_not_exists(&key("a/five.txt"), &key("a/seven.txt")).await.is_err());
+		store.delete(&key("a/six.txt")).await.unwrap();
+		// List objects with a prefix, an offset, and a limit
+		let list = |prefix: &str, start: Option<&str>, limit: Option<usize>| ListOptions {
+			prefix: Some(key(prefix)),
+			start: start.map(key),
+			limit,
+		};
+		let keys = |objects: Vec<ObjectMeta>| {
+			objects.into_iter().map(|v| v.key.to_string()).collect::<Vec<_>>()
+		};
+		assert_eq!(
+			keys(store.list(&list("/a", None, None)).await.unwrap()),
+			["/a/four.txt", "/a/one.txt", "/ab/three.txt"]
+		);
+		assert_eq!(
+			keys(store.list(&list("/a/", Some("/a/four.txt"), None)).await.unwrap()),
+			["/a/one.txt"]
+		);
+		assert_eq!(keys(store.list(&list("/", None, Some(1))).await.unwrap()), ["/a/four.txt"]);
+		// Delete objects
+		store.delete(&key("a/one.txt")).await.unwrap();
+		store.delete(&key("a/one.txt")).await.unwrap();
+		assert!(!store.exists(&key("a/one.txt")).await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn streams() {
+		let server = MockServer::start().await;
+		let store = store(&server).await;
+		// Write an object in chunks
+		let mut writer = store.writer(&key("big.bin")).await.unwrap().unwrap();
+		writer.write(Bytes::from_static(b"one ")).await.unwrap();
+		writer.write(Bytes::from_static(b"two")).await.unwrap();
+		assert!(!store.exists(&key("big.bin")).await.unwrap());
+		writer.commit().await.unwrap();
+		// Read the object in chunks
+		let stream = store.get_stream(&key("big.bin")).await.unwrap().unwrap();
+		let data: Vec<Bytes> = stream.try_collect().await.unwrap();
+		assert_eq!(data.concat(), b"one two");
+		assert!(store.get_stream(&key("missing.bin")).await.unwrap().is_none());
+	}
+}
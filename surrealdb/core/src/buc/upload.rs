@@ -0,0 +1,683 @@
+//! Streaming and resumable multipart uploads into buckets.
+//!
+//! An [`Upload`] streams the data of a single object into a bucket, without
+//! holding the whole object in memory when the backend supports streaming
+//! writes. Backends which do not support streaming writes buffer the object
+//! in memory, up to `FILE_UPLOAD_MAX_BUFFER_SIZE` bytes.
+//!
+//! Uploads which span multiple requests are stored in the datastore as an
+//! [`UploadRecord`], keyed by an upload id, together with the data which has
+//! been written to them, until they are committed, aborted, or expire. This
+//! allows an upload to be continued from any node of a cluster. Each user can
+//! have up to `FILE_UPLOAD_MAX_SESSIONS` uploads in progress, and each upload
+//! is limited to `FILE_UPLOAD_MAX_PARTS` chunks. Uploads which have not been
+//! committed within `FILE_UPLOAD_TIMEOUT_SECS` of being started expire, no
+//! matter how often they are written to, and are discarded by a background
+//! task.
+//!
+//! No file can be larger than `FILE_UPLOAD_MAX_SIZE` bytes.
+
+use std::pin::pin;
+use std::sync::Arc;
+
+use anyhow::{Result, bail, ensure};
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use revision::revisioned;
+use uuid::Uuid;
+
+use crate::buc::store::{BoxedWriter, ObjectKey, ObjectStore};
+use crate::cnf::{
+	FILE_UPLOAD_MAX_BUFFER_SIZE, FILE_UPLOAD_MAX_PARTS, FILE_UPLOAD_MAX_SESSIONS,
+	FILE_UPLOAD_MAX_SIZE, FILE_UPLOAD_TIMEOUT_SECS, NORMAL_FETCH_SIZE,
+};
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::Auth;
+use crate::key::root::fu::{FileUpload, FileUploadPart};
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::{Datastore, KVValue, Key, impl_kv_value_revisioned};
+
+/// The size of the chunks in which the data of a multipart upload is stored
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The destination of the data of an upload
+enum Writer {
+	/// The backend streams the data into the object
+	Stream(BoxedWriter),
+	/// The backend does not support streaming writes, so the data is buffered
+	Buffer(BytesMut),
+}
+
+/// A streaming write of a single object into a bucket.
+///
+/// The data only becomes visible in the bucket once the upload is committed.
+pub(crate) struct Upload {
+	bucket: String,
+	key: ObjectKey,
+	store: Arc<dyn ObjectStore>,
+	writer: Writer,
+	size: u64,
+}
+
+impl Upload {
+	/// Starts a new upload into the specified key of a bucket.
+	pub(crate) async fn open(
+		bucket: String,
+		key: ObjectKey,
+		store: Arc<dyn ObjectStore>,
+	) -> Result<Self> {
+		let writer = match store
+			.writer(&key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(bucket.clone(), e))?
+		{
+			Some(writer) => Writer::Stream(writer),
+			None => Writer::Buffer(BytesMut::new()),
+		};
+		Ok(Self {
+			bucket,
+			key,
+			store,
+			writer,
+			size: 0,
+		})
+	}
+
+	/// The number of bytes which have been written so far.
+	pub(crate) fn size(&self) -> u64 {
+		self.size
+	}
+
+	/// Appends a chunk of data to the upload.
+	pub(crate) async fn write(&mut self, data: Bytes) -> Result<()> {
+		let len = data.len() as u64;
+		let limit = *FILE_UPLOAD_MAX_SIZE;
+		ensure!(self.size + len <= limit, Error::UploadSizeExceeded(limit));
+		match &mut self.writer {
+			Writer::Stream(writer) => writer
+				.write(data)
+				.await
+				.map_err(|e| Error::ObjectStoreFailure(self.bucket.clone(), e))?,
+			Writer::Buffer(buffer) => {
+				let limit = *FILE_UPLOAD_MAX_BUFFER_SIZE;
+				ensure!(
+					self.size + len <= limit,
+					Error::UploadTooLarge {
+						bucket: self.bucket.clone(),
+						limit,
+					}
+				);
+				buffer.extend_from_slice(&data)
+			}
+		}
+		self.size += len;
+		Ok(())
+	}
+
+	/// Completes the upload, storing the data at the key of the upload.
+	pub(crate) async fn commit(self) -> Result<()> {
+		match self.writer {
+			Writer::Stream(writer) => writer.commit().await,
+			Writer::Buffer(buffer) => self.store.put(&self.key, buffer.freeze()).await,
+		}
+		.map_err(|e| Error::ObjectStoreFailure(self.bucket, e))?;
+		Ok(())
+	}
+
+	/// Discards the data which was written to the upload.
+	pub(crate) async fn abort(self) -> Result<()> {
+		if let Writer::Stream(writer) = self.writer {
+			writer.abort().await.map_err(|e| Error::ObjectStoreFailure(self.bucket, e))?;
+		}
+		Ok(())
+	}
+}
+
+/// The state of a multipart upload which spans multiple requests.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UploadRecord {
+	/// The namespace of the bucket
+	ns: Option<String>,
+	/// The database of the bucket
+	db: Option<String>,
+	/// The user who started the upload
+	auth: Auth,
+	/// The bucket which the file is uploaded into
+	pub(crate) bucket: String,
+	/// The key of the uploaded file
+	pub(crate) key: String,
+	/// The number of bytes which have been written
+	size: u64,
+	/// The number of chunks which have been written
+	parts: u32,
+	/// When the upload was started, in seconds since the UNIX epoch
+	created: i64,
+}
+impl_kv_value_revisioned!(UploadRecord);
+
+impl UploadRecord {
+	/// Checks whether the upload was started by the same user, in the same
+	/// namespace and database, as the session.
+	fn owned_by(&self, sess: &Session) -> bool {
+		self.ns == sess.ns && self.db == sess.db && self.auth == *sess.au
+	}
+
+	/// Checks whether the upload was started for the key of a bucket.
+	fn targets(&self, bucket: &str, key: &str) -> bool {
+		self.bucket == bucket && self.key == key
+	}
+
+	/// Checks whether the upload was started before the expiry time.
+	fn expired(&self, expiry: i64) -> bool {
+		self.created < expiry
+	}
+}
+
+/// The time before which uploads must have been started to have expired.
+fn expiry() -> i64 {
+	Utc::now().timestamp().saturating_sub_unsigned(*FILE_UPLOAD_TIMEOUT_SECS)
+}
+
+/// The multipart uploads which are stored in a datastore.
+pub(crate) struct UploadSessions<'a> {
+	ds: &'a Datastore,
+}
+
+impl<'a> UploadSessions<'a> {
+	pub(crate) fn new(ds: &'a Datastore) -> Self {
+		Self {
+			ds,
+		}
+	}
+
+	/// Registers a new multipart upload, returning its upload id.
+	pub(crate) async fn begin(&self, sess: &Session, bucket: &str, key: &str) -> Result<Uuid> {
+		// Count the uploads which the user has in progress
+		let expiry = expiry();
+		let limit = *FILE_UPLOAD_MAX_SESSIONS;
+		let mut open = 0;
+		let mut beg = FileUpload::range().0;
+		while let Some(uploads) = self.batch(&mut beg).await? {
+			open += uploads
+				.iter()
+				.filter(|(_, record)| record.owned_by(sess) && !record.expired(expiry))
+				.count() as u64;
+			ensure!(open < limit, Error::UploadSessionsExceeded(limit));
+		}
+		let id = Uuid::new_v4();
+		let record = UploadRecord {
+			ns: sess.ns.clone(),
+			db: sess.db.clone(),
+			auth: (*sess.au).clone(),
+			bucket: bucket.to_owned(),
+			key: key.to_owned(),
+			size: 0,
+			parts: 0,
+			created: Utc::now().timestamp(),
+		};
+		let txn = self.ds.transaction(Write, Optimistic).await?;
+		run!(txn, txn.put(&FileUpload::new(id), &record, None).await)?;
+		Ok(id)
+	}
+
+	/// Appends the chunks of a stream to a multipart upload, returning the
+	/// total number of bytes which have been written to the upload.
+	///
+	/// If an offset is specified, it must match the number of bytes which
+	/// have already been written, so that an interrupted upload can be
+	/// resumed from where it stopped. The data is stored in chunks, so the
+	/// chunks which were stored before a stream fails are kept.
+	pub(crate) async fn write<S>(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+		offset: Option<u64>,
+		stream: S,
+	) -> Result<u64>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		let mut record = self.get(sess, bucket, key, id).await?;
+		if let Some(offset) = offset {
+			ensure!(
+				offset == record.size,
+				Error::UploadOffsetMismatch {
+					id: id.to_string(),
+					expected: record.size,
+					found: offset,
+				}
+			);
+		}
+		let mut stream = pin!(stream);
+		let mut chunk = BytesMut::new();
+		while let Some(data) = stream.next().await {
+			chunk.extend_from_slice(&data?);
+			while chunk.len() >= CHUNK_SIZE {
+				let data = chunk.split_to(CHUNK_SIZE).freeze();
+				record = self.append(id, record, data).await?;
+			}
+		}
+		if !chunk.is_empty() {
+			record = self.append(id, record, chunk.freeze()).await?;
+		}
+		Ok(record.size)
+	}
+
+	/// Stores a chunk of data, and updates the state of the upload, unless
+	/// the upload was changed by another request in the meantime, or the
+	/// chunk exceeds the limits of the upload.
+	async fn append(
+		&self,
+		id: Uuid,
+		mut record: UploadRecord,
+		data: Bytes,
+	) -> Result<UploadRecord> {
+		let limit = *FILE_UPLOAD_MAX_SIZE;
+		ensure!(record.size + data.len() as u64 <= limit, Error::UploadSizeExceeded(limit));
+		let limit = *FILE_UPLOAD_MAX_PARTS;
+		ensure!(
+			record.parts < limit,
+			Error::UploadPartsExceeded {
+				id: id.to_string(),
+				limit,
+			}
+		);
+		let key = FileUpload::new(id);
+		let txn = self.ds.transaction(Write, Optimistic).await?;
+		match catch!(txn, txn.get(&key, None).await) {
+			Some(current) if current == record => {}
+			Some(current) => {
+				txn.cancel().await?;
+				bail!(Error::UploadOffsetMismatch {
+					id: id.to_string(),
+					expected: current.size,
+					found: record.size,
+				});
+			}
+			None => {
+				txn.cancel().await?;
+				bail!(Error::UploadNotFound(id.to_string()));
+			}
+		}
+		catch!(txn, txn.set(&FileUploadPart::new(id, record.parts), &data.to_vec(), None).await);
+		record.size += data.len() as u64;
+		record.parts += 1;
+		run!(txn, txn.set(&key, &record, None).await)?;
+		Ok(record)
+	}
+
+	/// Returns the number of bytes which have been written to a multipart upload.
+	pub(crate) async fn size(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+	) -> Result<u64> {
+		Ok(self.get(sess, bucket, key, id).await?.size)
+	}
+
+	/// Writes the data of a multipart upload into the upload of the file, and
+	/// completes it, returning the size of the file.
+	pub(crate) async fn commit(
+		&self,
+		id: Uuid,
+		record: UploadRecord,
+		mut upload: Upload,
+	) -> Result<u64> {
+		for part in 0..record.parts {
+			let txn = self.ds.transaction(Read, Optimistic).await?;
+			let data = catch!(txn, txn.get(&FileUploadPart::new(id, part), None).await);
+			txn.cancel().await?;
+			let res = match data {
+				Some(data) => upload.write(Bytes::from(data)).await,
+				None => Err(Error::UploadNotFound(id.to_string()).into()),
+			};
+			if let Err(e) = res {
+				if let Err(e) = upload.abort().await {
+					warn!("Failed to discard file upload {id}: {e}");
+				}
+				return Err(e);
+			}
+		}
+		upload.commit().await?;
+		self.remove(id).await?;
+		Ok(record.size)
+	}
+
+	/// Discards a multipart upload.
+	pub(crate) async fn abort(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+	) -> Result<()> {
+		self.get(sess, bucket, key, id).await?;
+		self.remove(id).await
+	}
+
+	/// Fetches the state of an upload, ensuring that it was started by the
+	/// same user, for the same bucket and key. Uploads of other users, or of
+	/// other files, are reported as not existing, as are expired uploads
+	/// which have not been discarded yet.
+	pub(crate) async fn get(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+	) -> Result<UploadRecord> {
+		let txn = self.ds.transaction(Read, Optimistic).await?;
+		let record = catch!(txn, txn.get(&FileUpload::new(id), None).await);
+		txn.cancel().await?;
+		match record {
+			Some(record)
+				if record.owned_by(sess)
+					&& record.targets(bucket, key)
+					&& !record.expired(expiry()) =>
+			{
+				Ok(record)
+			}
+			_ => Err(Error::UploadNotFound(id.to_string()).into()),
+		}
+	}
+
+	/// Deletes an upload, and the data which was written to it.
+	async fn remove(&self, id: Uuid) -> Result<()> {
+		let (beg, end) = FileUploadPart::range(id);
+		let txn = self.ds.transaction(Write, Optimistic).await?;
+		catch!(txn, txn.delr(beg..end).await);
+		run!(txn, txn.del(&FileUpload::new(id)).await)
+	}
+
+	/// Fetches the next batch of uploads, starting from the specified key,
+	/// which is moved past the batch. Returns `None` once all uploads have
+	/// been fetched.
+	async fn batch(&self, beg: &mut Key) -> Result<Option<Vec<(Uuid, UploadRecord)>>> {
+		let end = FileUpload::range().1;
+		if *beg >= end {
+			return Ok(None);
+		}
+		let txn = self.ds.transaction(Read, Optimistic).await?;
+		let uploads =
+			catch!(txn, txn.scan(beg.clone()..end.clone(), *NORMAL_FETCH_SIZE, 0, None).await);
+		txn.cancel().await?;
+		let Some((last, _)) = uploads.last() else {
+			return Ok(None);
+		};
+		// The next batch starts after the last upload of this batch, unless
+		// this batch was the last one
+		if uploads.len() < *NORMAL_FETCH_SIZE as usize {
+			beg.clone_from(&end);
+		} else {
+			beg.clone_from(last);
+			beg.push(0x00);
+		}
+		uploads
+			.into_iter()
+			.map(|(key, val)| {
+				let id = FileUpload::decode_key(&key)?.id;
+				Ok((id, UploadRecord::kv_decode_value(val)?))
+			})
+			.collect::<Result<_>>()
+			.map(Some)
+	}
+
+	/// Discards uploads which were started longer ago than the timeout.
+	///
+	/// The uploads are checked in batches, each in its own transaction, so
+	/// that the number of uploads in progress does not bound a transaction.
+	pub(crate) async fn expire(&self) -> Result<()> {
+		let expiry = expiry();
+		let mut beg = FileUpload::range().0;
+		while let Some(uploads) = self.batch(&mut beg).await? {
+			for (id, record) in uploads {
+				if record.expired(expiry)
+					&& let Err(e) = self.remove(id).await
+				{
+					warn!("Failed to discard expired file upload {id}: {e}");
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use futures::{TryStreamExt, stream};
+
+	use super::*;
+	use crate::api::request::ApiRequest;
+	use crate::catalog::ApiMethod;
+	use crate::dbs::Capabilities;
+	use crate::dbs::capabilities::ExperimentalTarget;
+	use crate::kvs::Datastore;
+
+	async fn datastore() -> (Datastore, Session) {
+		let caps = Capabilities::default().with_experimental(ExperimentalTarget::Files.into());
+		let ds = Datastore::new_test("memory").await.with_capabilities(caps);
+		ds.test_query("DEFINE BUCKET test BACKEND \"memory\"").await;
+		(ds, Session::owner().with_ns("test").with_db("test"))
+	}
+
+	fn chunks(chunks: &[&'static str]) -> impl Stream<Item = Result<Bytes>> + use<> {
+		stream::iter(chunks.to_vec()).map(|v| Ok(Bytes::from_static(v.as_bytes())))
+	}
+
+	async fn read(ds: &Datastore, sess: &Session, key: &str) -> Option<String> {
+		let stream = ds.get_file(sess, "test", key).await.unwrap()?;
+		let data: Vec<Bytes> = stream.try_collect().await.unwrap();
+		Some(String::from_utf8(data.concat()).unwrap())
+	}
+
+	#[tokio::test]
+	async fn put_file() {
+		let (ds, sess) = datastore().await;
+		let size = ds.put_file(&sess, "test", "/a.txt", chunks(&["one ", "two"])).await.unwrap();
+		assert_eq!(size, 7);
+		assert_eq!(read(&ds, &sess, "/a.txt").await.unwrap(), "one two");
+		assert_eq!(read(&ds, &sess, "/b.txt").await, None);
+		// The file is not written if the stream fails
+		let failing =
+			chunks(&["one"]).chain(stream::once(async { Err(anyhow::anyhow!("failed")) }));
+		ds.put_file(&sess, "test", "/b.txt", failing).await.unwrap_err();
+		assert_eq!(read(&ds, &sess, "/b.txt").await, None);
+		// Files are only streamed from buckets which exist
+		ds.put_file(&sess, "missing", "/a.txt", chunks(&["one"])).await.unwrap_err();
+		assert!(ds.get_file(&sess, "missing", "/a.txt").await.is_err());
+	}
+
+	#[tokio::test]
+	async fn multipart_upload() {
+		let (ds, sess) = datastore().await;
+		let id = ds.begin_file_upload(&sess, "test", "/a.txt").await.unwrap();
+		assert_eq!(
+			ds.write_file_upload(&sess, "test", "/a.txt", id, Some(0), chunks(&["one "]))
+				.await
+				.unwrap(),
+			4
+		);
+		// Chunks which do not continue where the upload stopped are rejected
+		let err = ds
+			.write_file_upload(&sess, "test", "/a.txt", id, Some(0), chunks(&["one "]))
+			.await
+			.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref(),
+			Some(Error::UploadOffsetMismatch {
+				expected: 4,
+				found: 0,
+				..
+			})
+		));
+		// The upload is resumed from its current size
+		let size = ds.file_upload_size(&sess, "test", "/a.txt", id).await.unwrap();
+		assert_eq!(
+			ds.write_file_upload(&sess, "test", "/a.txt", id, Some(size), chunks(&["two"]))
+				.await
+				.unwrap(),
+			7
+		);
+		// Uploads can not be continued by other users
+		let other = Session::viewer().with_ns("test").with_db("test");
+		ds.file_upload_size(&other, "test", "/a.txt", id).await.unwrap_err();
+		// Uploads can not be continued under another bucket or key
+		ds.file_upload_size(&sess, "test", "/b.txt", id).await.unwrap_err();
+		ds.write_file_upload(&sess, "other", "/a.txt", id, None, chunks(&["two"]))
+			.await
+			.unwrap_err();
+		ds.commit_file_upload(&sess, "test", "/b.txt", id).await.unwrap_err();
+		ds.abort_file_upload(&sess, "test", "/b.txt", id).await.unwrap_err();
+		// The file only exists once the upload is committed
+		assert_eq!(read(&ds, &sess, "/a.txt").await, None);
+		assert_eq!(ds.commit_file_upload(&sess, "test", "/a.txt", id).await.unwrap(), 7);
+		assert_eq!(read(&ds, &sess, "/a.txt").await.unwrap(), "one two");
+		let err = ds.commit_file_upload(&sess, "test", "/a.txt", id).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::UploadNotFound(_))));
+		// Aborted uploads are discarded
+		let id = ds.begin_file_upload(&sess, "test", "/b.txt").await.unwrap();
+		ds.write_file_upload(&sess, "test", "/b.txt", id, None, chunks(&["one"])).await.unwrap();
+		ds.abort_file_upload(&sess, "test", "/b.txt", id).await.unwrap();
+		ds.file_upload_size(&sess, "test", "/b.txt", id).await.unwrap_err();
+		assert_eq!(read(&ds, &sess, "/b.txt").await, None);
+	}
+
+	#[tokio::test]
+	async fn multipart_upload_checks_bucket_access() {
+		let (ds, sess) = datastore().await;
+		let id = ds.begin_file_upload(&sess, "test", "/a.txt").await.unwrap();
+		ds.write_file_upload(&sess, "test", "/a.txt", id, None, chunks(&["one"])).await.unwrap();
+		// The bucket can not be written to anymore
+		ds.test_query("ALTER BUCKET test READONLY").await;
+		ds.write_file_upload(&sess, "test", "/a.txt", id, None, chunks(&["two"]))
+			.await
+			.unwrap_err();
+		ds.file_upload_size(&sess, "test", "/a.txt", id).await.unwrap_err();
+		ds.abort_file_upload(&sess, "test", "/a.txt", id).await.unwrap_err();
+		ds.commit_file_upload(&sess, "test", "/a.txt", id).await.unwrap_err();
+		ds.test_query("ALTER BUCKET test DROP READONLY").await;
+		assert_eq!(ds.file_upload_size(&sess, "test", "/a.txt", id).await.unwrap(), 3);
+		// Files are not enabled anymore
+		let ds = ds.with_capabilities(Capabilities::default());
+		let err = ds.file_upload_size(&sess, "test", "/a.txt", id).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::FilesNotEnabled)));
+		let err = ds
+			.write_file_upload(&sess, "test", "/a.txt", id, None, chunks(&["two"]))
+			.await
+			.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::FilesNotEnabled)));
+		let err = ds.abort_file_upload(&sess, "test", "/a.txt", id).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::FilesNotEnabled)));
+	}
+
+	#[tokio::test]
+	async fn multipart_upload_is_stored_in_chunks() {
+		let (ds, sess) = datastore().await;
+		let id = ds.begin_file_upload(&sess, "test", "/a.bin").await.unwrap();
+		let data = Bytes::from(vec![7u8; CHUNK_SIZE * 2 + 5]);
+		let stream = stream::iter([Ok(data.slice(..10)), Ok(data.slice(10..))]);
+		assert_eq!(
+			ds.write_file_upload(&sess, "test", "/a.bin", id, Some(0), stream).await.unwrap(),
+			data.len() as u64
+		);
+		// The upload can be continued by any node, from the datastore
+		let record = UploadSessions::new(&ds).get(&sess, "test", "/a.bin", id).await.unwrap();
+		assert_eq!(record.parts, 3);
+		assert_eq!(
+			ds.commit_file_upload(&sess, "test", "/a.bin", id).await.unwrap(),
+			data.len() as u64
+		);
+		let stream = ds.get_file(&sess, "test", "/a.bin").await.unwrap().unwrap();
+		let stored: Vec<Bytes> = stream.try_collect().await.unwrap();
+		assert_eq!(stored.concat(), data);
+		// The upload and its chunks are removed once committed
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let (beg, end) = FileUpload::range();
+		assert!(txn.keys(beg..end, 1, 0, None).await.unwrap().is_empty());
+		let (beg, end) = FileUploadPart::range(id);
+		assert!(txn.keys(beg..end, 1, 0, None).await.unwrap().is_empty());
+		txn.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn old_uploads_expire() {
+		let (ds, sess) = datastore().await;
+		let old = ds.begin_file_upload(&sess, "test", "/a.txt").await.unwrap();
+		let active = ds.begin_file_upload(&sess, "test", "/b.txt").await.unwrap();
+		// Mark the first upload as started longer ago than the timeout
+		let uploads = UploadSessions::new(&ds);
+		let mut record = uploads.get(&sess, "test", "/a.txt", old).await.unwrap();
+		record.created -= *FILE_UPLOAD_TIMEOUT_SECS as i64 + 1;
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		txn.set(&FileUpload::new(old), &record, None).await.unwrap();
+		txn.commit().await.unwrap();
+		// Writing to an upload does not extend its lifetime
+		ds.write_file_upload(&sess, "test", "/a.txt", old, None, chunks(&["one"]))
+			.await
+			.unwrap_err();
+		ds.commit_file_upload(&sess, "test", "/a.txt", old).await.unwrap_err();
+		// Only the old upload and its chunks are discarded
+		ds.file_upload_expiry(Duration::from_secs(1)).await.unwrap();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(txn.get(&FileUpload::new(old), None).await.unwrap().is_none());
+		let (beg, end) = FileUploadPart::range(old);
+		assert!(txn.keys(beg..end, 1, 0, None).await.unwrap().is_empty());
+		txn.cancel().await.unwrap();
+		assert_eq!(ds.file_upload_size(&sess, "test", "/b.txt", active).await.unwrap(), 0);
+	}
+
+	#[tokio::test]
+	async fn uploads_are_limited_per_user() {
+		let (ds, sess) = datastore().await;
+		let limit = *FILE_UPLOAD_MAX_SESSIONS;
+		let mut ids = Vec::new();
+		for _ in 0..limit {
+			ids.push(ds.begin_file_upload(&sess, "test", "/a.txt").await.unwrap());
+		}
+		let err = ds.begin_file_upload(&sess, "test", "/a.txt").await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::UploadSessionsExceeded(_))));
+		// Other users have their own uploads
+		let other = Session::editor().with_ns("test").with_db("test");
+		ds.begin_file_upload(&other, "test", "/a.txt").await.unwrap();
+		// Uploads can be started again once others are completed
+		ds.abort_file_upload(&sess, "test", "/a.txt", ids[0]).await.unwrap();
+		ds.begin_file_upload(&sess, "test", "/a.txt").await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn api_file_response() {
+		let (ds, sess) = datastore().await;
+		ds.put_file(&sess, "test", "/a.txt", chunks(&["one"])).await.unwrap();
+		let sql = r#"
+			DEFINE API "/a" FOR get THEN { { status: 200, body: f"test:/a.txt" } };
+			DEFINE API "/b" FOR get THEN { { status: 200, body: f"test:/b.txt" } };
+		"#;
+		ds.execute(sql, &sess, None).await.unwrap();
+		let req = || ApiRequest {
+			method: ApiMethod::Get,
+			..Default::default()
+		};
+		// Files are streamed, rather than returned in the body
+		let (res, stream) =
+			ds.invoke_api_handler_streaming("test", "test", "/a", &sess, req()).await.unwrap();
+		assert_eq!(res.status, 200);
+		assert!(res.body.is_none());
+		let data: Vec<Bytes> = stream.unwrap().try_collect().await.unwrap();
+		assert_eq!(data.concat(), b"one");
+		// Files which do not exist are not found
+		let (res, stream) =
+			ds.invoke_api_handler_streaming("test", "test", "/b", &sess, req()).await.unwrap();
+		assert_eq!(res.status, 404);
+		assert!(stream.is_none());
+	}
+}
@@ -41,26 +41,36 @@
 use std::fmt::Write;
 use std::hash::Hash;
 use std::mem;
+use std::sync::Arc;
 
 use ahash::HashMap;
 use anyhow::{Result, bail, ensure};
+use reblessive::tree::Stk;
 use revision::revisioned;
 use surrealdb_types::ToSql;
 
+use crate::catalog::FunctionDefinition;
+use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::{Context, FrozenContext};
+use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::field::Selector;
+use crate::expr::function::check_perms;
 use crate::expr::statements::define::DefineConfigStatement;
 use crate::expr::statements::{
-	CreateStatement, DefineAccessStatement, DefineApiStatement, DefineFieldStatement,
-	DefineFunctionStatement, DefineIndexStatement, InsertStatement, RelateStatement,
-	UpdateStatement, UpsertStatement,
+	CreateStatement, DefineAccessStatement, DefineAggregateStatement, DefineApiStatement,
+	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, InsertStatement,
+	RelateStatement, UpdateStatement, UpsertStatement,
 };
-use crate::expr::visit::{MutVisitor, VisitMut};
-use crate::expr::{Expr, Field, Fields, Function, Groups, Idiom, Part, SelectStatement};
+use crate::expr::visit::{MutVisitor, Visit, VisitMut, Visitor};
+use crate::expr::{
+	Block, Expr, Field, Fields, FlowResultExt as _, Function, Groups, Idiom, Part, SelectStatement,
+};
+use crate::iam::AuthLimit;
 use crate::val::{Array, Datetime, Number, Object, TryAdd as _, TryFloatDiv, TryMul, Value};
 
 /// An expression which will be aggregated over for each group.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Aggregation {
 	Count,
@@ -76,6 +86,12 @@ pub enum Aggregation {
 	DatetimeMax(usize),
 	DatetimeMin(usize),
 	Accumulate(usize),
+	/// A user-defined aggregate, with the index of the expression for each of its arguments.
+	#[revision(start = 2)]
+	Custom {
+		name: String,
+		args: Vec<usize>,
+	},
 }
 
 impl Aggregation {
@@ -129,12 +145,21 @@ impl Aggregation {
 				arg,
 				values: Vec::new(),
 			},
+			Aggregation::Custom {
+				ref name,
+				ref args,
+			} => AggregationStat::Custom {
+				name: name.clone(),
+				args: args.clone(),
+				state: Value::None,
+				count: 0,
+			},
 		}
 	}
 }
 
 /// A enum containing the data for an aggregation.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq)]
 pub enum AggregationStat {
 	Count {
@@ -186,6 +211,15 @@ pub enum AggregationStat {
 		arg: usize,
 		values: Vec<Value>,
 	},
+	#[revision(start = 2)]
+	Custom {
+		name: String,
+		args: Vec<usize>,
+		/// The state of the aggregate, which is only created once the first record is folded.
+		state: Value,
+		/// The number of records folded into the state.
+		count: i64,
+	},
 }
 
 impl AggregationStat {
@@ -405,6 +439,11 @@ pub fn add_to_aggregation_stats(arguments: &[Value], stats: &mut [AggregationSta
 			} => {
 				values.push(arguments[*arg].clone());
 			}
+			AggregationStat::Custom {
+				..
+			} => {
+				// Computed by `accumulate_custom_aggregation_stats`, as it runs SurrealQL.
+			}
 		}
 	}
 	Ok(())
@@ -486,6 +525,10 @@ pub fn create_field_document(group: &[Value], stats: &[AggregationStat]) -> Obje
 				values,
 				..
 			} => Value::Array(Array(values.clone())),
+			// Set by `finalize_custom_aggregation_stats`, as it runs SurrealQL.
+			AggregationStat::Custom {
+				..
+			} => Value::None,
 		};
 		res.0.insert(aggregate_field_name(idx), value);
 	}
@@ -502,6 +545,7 @@ struct AggregateExprCollector<'a> {
 	exprs_map: &'a mut HashMap<Expr, usize>,
 	aggregations: &'a mut Vec<Aggregation>,
 	groups: &'a Groups,
+	custom: &'a CustomAggregates,
 }
 
 impl AggregateExprCollector<'_> {
@@ -525,6 +569,35 @@ impl AggregateExprCollector<'_> {
 		self.aggregations.push(f(arg));
 		Ok(())
 	}
+
+	/// Adds a user-defined aggregation, which takes the arguments of its definition.
+	fn push_custom_aggregate(
+		&mut self,
+		name: &str,
+		def: &FunctionDefinition,
+		args: &[Expr],
+	) -> Result<()> {
+		// Trailing arguments may be omitted if they accept `NONE`
+		let required = def.args.len()
+			- def.args.iter().rev().take_while(|(_, kind)| kind.can_be_none()).count();
+		ensure!(
+			(required..=def.args.len()).contains(&args.len()),
+			Error::InvalidFunctionArguments {
+				name: format!("fn::{name}"),
+				message: format!("The function expects {} arguments.", def.args.len()),
+			}
+		);
+		let mut indexes = Vec::with_capacity(args.len());
+		for expr in args {
+			let len = self.exprs_map.len();
+			indexes.push(*self.exprs_map.entry(expr.clone()).or_insert_with(|| len));
+		}
+		self.aggregations.push(Aggregation::Custom {
+			name: name.to_string(),
+			args: indexes,
+		});
+		Ok(())
+	}
 }
 
 impl MutVisitor for AggregateExprCollector<'_> {
@@ -606,6 +679,10 @@ impl MutVisitor for AggregateExprCollector<'_> {
 							return f.visit_mut(self);
 						}
 					}
+				} else if let Function::Custom(name) = &f.receiver
+					&& let Some(def) = self.custom.get(name)
+				{
+					self.push_custom_aggregate(name, def, &f.arguments)?;
 				} else {
 					return f.visit_mut(self);
 				}
@@ -805,6 +882,14 @@ impl MutVisitor for AggregateExprCollector<'_> {
 		Ok(())
 	}
 
+	fn visit_mut_define_aggregate(
+		&mut self,
+		d: &mut DefineAggregateStatement,
+	) -> std::result::Result<(), Self::Error> {
+		self.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
 	fn visit_mut_define_access(
 		&mut self,
 		d: &mut DefineAccessStatement,
@@ -930,6 +1015,13 @@ impl MutVisitor for ParentRewritor {
 		Ok(())
 	}
 
+	fn visit_mut_define_aggregate(
+		&mut self,
+		_: &mut DefineAggregateStatement,
+	) -> std::result::Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn visit_mut_define_access(
 		&mut self,
 		d: &mut DefineAccessStatement,
@@ -988,10 +1080,14 @@ impl AggregationAnalysis {
 	/// there is no aggregate which maintains a per group record count and will reject any
 	/// accumulate aggregations as we currently don't have a way to support them on
 	/// materialized views.
+	///
+	/// The `custom` argument contains the user-defined aggregates which are called within the
+	/// fields, as fetched by [`custom_aggregates`].
 	pub fn analyze_fields_groups(
 		fields: &Fields,
 		groups: &Groups,
 		materialized_view: bool,
+		custom: &CustomAggregates,
 	) -> Result<Self> {
 		// Find all the aggregates within the select statement.
 		let mut aggregations = Vec::new();
@@ -1008,6 +1104,7 @@ impl AggregationAnalysis {
 			exprs_map: &mut exprs_map,
 			aggregations: &mut aggregations,
 			groups,
+			custom,
 		};
 
 		// Collect the expressions which calculate the fields of the final object after
@@ -1102,3 +1199,345 @@ impl AggregationAnalysis {
 		})
 	}
 }
+
+/// The definitions of the user-defined aggregates used within an aggregation, by name.
+pub type CustomAggregates = HashMap<String, Arc<FunctionDefinition>>;
+
+/// Fetches the definitions of the user-defined aggregates which are called within the fields of
+/// an aggregation.
+///
+/// Whether a `fn::` function is an aggregate is only known from its definition, so this needs to
+/// run before [`AggregationAnalysis::analyze_fields_groups`].
+pub async fn custom_aggregates(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	fields: &Fields,
+) -> Result<CustomAggregates> {
+	let mut collector = CustomCallCollector::default();
+	let _ = collector.visit_fields(fields);
+	fetch_custom_aggregates(stk, ctx, opt, collector.names, false).await
+}
+
+impl AggregationAnalysis {
+	/// Fetches the definitions of the user-defined aggregates of this analysis.
+	pub async fn custom_aggregates(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+	) -> Result<CustomAggregates> {
+		let names = self
+			.aggregations
+			.iter()
+			.filter_map(|x| match x {
+				Aggregation::Custom {
+					name,
+					..
+				} => Some(name.clone()),
+				_ => None,
+			})
+			.collect();
+		fetch_custom_aggregates(stk, ctx, opt, names, true).await
+	}
+}
+
+/// Fetches the definitions of the given functions which are aggregates.
+///
+/// When `required` is false, the functions which don't exist or which are not aggregates are
+/// skipped, as they are reported once they are called.
+async fn fetch_custom_aggregates(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	names: Vec<String>,
+	required: bool,
+) -> Result<CustomAggregates> {
+	let mut res = CustomAggregates::default();
+	if names.is_empty() {
+		return Ok(res);
+	}
+	let Some((ns, db)) = ctx.try_ns_db_ids(opt).await? else {
+		ensure!(
+			!required,
+			Error::FcNotFound {
+				name: names[0].clone()
+			}
+		);
+		return Ok(res);
+	};
+	for name in names {
+		if res.contains_key(&name) {
+			continue;
+		}
+		let def = match ctx.tx().get_db_function(ns, db, &name).await {
+			Ok(def) => def,
+			Err(_) if !required => continue,
+			Err(e) => return Err(e),
+		};
+		let fnc = format!("fn::{name}");
+		if def.aggregate.is_none() {
+			ensure!(
+				!required,
+				Error::InvalidFunction {
+					name: fnc,
+					message: "The function is not an aggregate".to_string(),
+				}
+			);
+			continue;
+		}
+		// Check this function is allowed
+		ctx.check_allowed_function(&fnc)?;
+		// Check permissions
+		check_perms(stk, ctx, opt, None, &fnc, &def.permissions)
+			.await
+			.map(|_| Value::None)
+			.catch_return()?;
+		res.insert(name, def);
+	}
+	Ok(res)
+}
+
+fn custom_aggregate<'a>(
+	custom: &'a CustomAggregates,
+	name: &str,
+) -> Result<&'a Arc<FunctionDefinition>> {
+	let Some(def) = custom.get(name) else {
+		fail!("The definition of the aggregate `fn::{name}` was not fetched");
+	};
+	Ok(def)
+}
+
+/// Runs a clause of a user-defined aggregate with the given parameters.
+async fn compute_custom_clause(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	def: &FunctionDefinition,
+	clause: &Block,
+	params: Vec<(String, Value)>,
+) -> Result<Value> {
+	let opt = AuthLimit::try_from(&def.auth_limit)?.limit_opt(opt);
+	let mut ctx = Context::new_isolated(ctx);
+	for (name, value) in params {
+		ctx.add_value(name, value.into());
+	}
+	let ctx = ctx.freeze();
+	stk.run(|stk| clause.compute(stk, &ctx, &opt, None)).await.catch_return()
+}
+
+/// Creates the state of an empty group for a user-defined aggregate.
+async fn init_custom_aggregation(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	def: &FunctionDefinition,
+) -> Result<Value> {
+	let Some(aggregate) = def.aggregate.as_ref() else {
+		fail!("Function `fn::{}` is not an aggregate", def.name);
+	};
+	let opt = AuthLimit::try_from(&def.auth_limit)?.limit_opt(opt);
+	let ctx = Context::new_isolated(ctx).freeze();
+	stk.run(|stk| aggregate.state.compute(stk, &ctx, &opt, None)).await.catch_return()
+}
+
+/// Folds the arguments of a record into the states of the user-defined aggregates.
+pub async fn accumulate_custom_aggregation_stats(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	custom: &CustomAggregates,
+	arguments: &[Value],
+	stats: &mut [AggregationStat],
+) -> Result<()> {
+	for stat in stats {
+		let AggregationStat::Custom {
+			name,
+			args,
+			state,
+			count,
+		} = stat
+		else {
+			continue;
+		};
+		let def = custom_aggregate(custom, name)?;
+		let Some(aggregate) = def.aggregate.as_ref() else {
+			fail!("Function `fn::{name}` is not an aggregate");
+		};
+		let current = if *count == 0 {
+			init_custom_aggregation(stk, ctx, opt, def).await?
+		} else {
+			mem::take(state)
+		};
+		let mut params = vec![("state".to_string(), current)];
+		for (idx, (param, kind)) in def.args.iter().enumerate() {
+			let value =
+				args.get(idx).and_then(|x| arguments.get(*x)).cloned().unwrap_or(Value::None);
+			let value =
+				value.coerce_to_kind(kind).map_err(|e| Error::InvalidFunctionArguments {
+					name: format!("fn::{name}"),
+					message: format!("Failed to coerce argument `${param}`: {e}"),
+				})?;
+			params.push((param.clone(), value));
+		}
+		*state = compute_custom_clause(stk, ctx, opt, def, &aggregate.accumulate, params).await?;
+		*count += 1;
+	}
+	Ok(())
+}
+
+/// Computes the states of the user-defined aggregates anew, from the arguments of every record
+/// which remains within a group.
+///
+/// Used when a record is removed from a group, as a state can't be taken apart again.
+pub async fn recompute_custom_aggregation_stats(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	custom: &CustomAggregates,
+	records: &[Vec<Value>],
+	stats: &mut [AggregationStat],
+) -> Result<()> {
+	for stat in stats.iter_mut() {
+		if let AggregationStat::Custom {
+			state,
+			count,
+			..
+		} = stat
+		{
+			*state = Value::None;
+			*count = 0;
+		}
+	}
+	for arguments in records {
+		accumulate_custom_aggregation_stats(stk, ctx, opt, custom, arguments, stats).await?;
+	}
+	Ok(())
+}
+
+/// Combines the states of the user-defined aggregates of two parts of the same group into the
+/// left stats.
+pub async fn merge_custom_aggregation_stats(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	custom: &CustomAggregates,
+	stats: &mut [AggregationStat],
+	other: Vec<AggregationStat>,
+) -> Result<()> {
+	for (stat, other) in stats.iter_mut().zip(other) {
+		let (
+			AggregationStat::Custom {
+				name,
+				state,
+				count,
+				..
+			},
+			AggregationStat::Custom {
+				state: other_state,
+				count: other_count,
+				..
+			},
+		) = (stat, other)
+		else {
+			continue;
+		};
+		if other_count == 0 {
+			continue;
+		}
+		if *count == 0 {
+			*state = other_state;
+			*count = other_count;
+			continue;
+		}
+		let def = custom_aggregate(custom, name)?;
+		let Some(aggregate) = def.aggregate.as_ref() else {
+			fail!("Function `fn::{name}` is not an aggregate");
+		};
+		let params =
+			vec![("state".to_string(), mem::take(state)), ("other".to_string(), other_state)];
+		*state = compute_custom_clause(stk, ctx, opt, def, &aggregate.merge, params).await?;
+		*count += other_count;
+	}
+	Ok(())
+}
+
+/// Computes the result of a user-defined aggregate from its state.
+pub async fn finalize_custom_aggregation(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	def: &FunctionDefinition,
+	state: Value,
+	count: i64,
+) -> Result<Value> {
+	let Some(aggregate) = def.aggregate.as_ref() else {
+		fail!("Function `fn::{}` is not an aggregate", def.name);
+	};
+	let state = if count == 0 {
+		init_custom_aggregation(stk, ctx, opt, def).await?
+	} else {
+		state
+	};
+	let params = vec![("state".to_string(), state)];
+	let result = compute_custom_clause(stk, ctx, opt, def, &aggregate.finalize, params).await?;
+	match &def.returns {
+		Some(kind) => result.coerce_to_kind(kind).map_err(|e| {
+			anyhow::Error::new(Error::ReturnCoerce {
+				name: format!("fn::{}", def.name),
+				error: Box::new(e),
+			})
+		}),
+		None => Ok(result),
+	}
+}
+
+/// Computes the results of the user-defined aggregates, and sets them within a document created
+/// by [`create_field_document`].
+pub async fn finalize_custom_aggregation_stats(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	custom: &CustomAggregates,
+	stats: &[AggregationStat],
+	doc: &mut Object,
+) -> Result<()> {
+	for (idx, stat) in stats.iter().enumerate() {
+		if let AggregationStat::Custom {
+			name,
+			state,
+			count,
+			..
+		} = stat
+		{
+			let def = custom_aggregate(custom, name)?;
+			let value =
+				finalize_custom_aggregation(stk, ctx, opt, def, state.clone(), *count).await?;
+			doc.0.insert(aggregate_field_name(idx), value);
+		}
+	}
+	Ok(())
+}
+
+/// Collects the names of the `fn::` functions called in an expression, skipping subqueries.
+#[derive(Default)]
+struct CustomCallCollector {
+	names: Vec<String>,
+}
+
+impl Visitor for CustomCallCollector {
+	type Error = std::convert::Infallible;
+
+	fn visit_function(&mut self, function: &Function) -> Result<(), Self::Error> {
+		if let Function::Custom(name) = function
+			&& !self.names.contains(name)
+		{
+			self.names.push(name.clone());
+		}
+		function.visit(self)
+	}
+
+	fn visit_select(&mut self, _: &SelectStatement) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
@@ -24,7 +24,8 @@ use crate::catalog::record::{Record, RecordType};
 use crate::catalog::schema::base::Base;
 use crate::catalog::{
 	ApiActionDefinition, ApiConfigDefinition, ApiMethod, DatabaseId, IndexId, ModuleDefinition,
-	ModuleExecutable, NamespaceId, NodeLiveQuery, SiloExecutable, SurrealismExecutable, TableId,
+	ModuleExecutable, ModuleLimits, NamespaceId, NodeLiveQuery, SiloExecutable,
+	SurrealismExecutable, TableId,
 };
 use crate::cf::mutations::{TableMutation, TableMutations};
 use crate::dbs::node::{Node, Timestamp};
@@ -129,6 +130,7 @@ pub fn table_basic() -> TableDefinition {
 		changefeed: None,
 		comment: None,
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -161,6 +163,7 @@ pub fn table_with_view() -> TableDefinition {
 		changefeed: None,
 		comment: Some("User statistics view".to_string()),
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -185,6 +188,7 @@ pub fn table_schemafull() -> TableDefinition {
 		}),
 		comment: Some("Order records".to_string()),
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -217,6 +221,7 @@ pub fn table_relation() -> TableDefinition {
 			to: vec!["posts".to_string(), "comments".to_string()],
 			enforced: true,
 		}),
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -242,6 +247,7 @@ pub fn table_with_materialized_view() -> TableDefinition {
 		changefeed: None,
 		comment: Some("Materialized view of active users".to_string()),
 		table_type: TableType::Normal,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -263,6 +269,7 @@ pub fn table_any_type() -> TableDefinition {
 		changefeed: None,
 		comment: None,
 		table_type: TableType::Any,
+		ttl: None,
 		cache_fields_ts: UuidExt::nil(),
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
@@ -559,6 +566,7 @@ pub fn analyzer_basic() -> AnalyzerDefinition {
 		function: None,
 		tokenizers: None,
 		filters: None,
+		operators: false,
 		comment: None,
 	}
 }
@@ -570,6 +578,7 @@ pub fn analyzer_with_tokenizers() -> AnalyzerDefinition {
 		function: Some("fn::custom_analyzer".to_string()),
 		tokenizers: Some(vec![Tokenizer::Camel, Tokenizer::Class]),
 		filters: Some(vec![Filter::Ascii, Filter::Lowercase]),
+		operators: false,
 		comment: Some("English text analyzer".to_string()),
 	}
 }
@@ -792,6 +801,7 @@ pub fn field_basic() -> FieldDefinition {
 		reference: None,
 		auth_limit: AuthLimit::new_no_limit(),
 		computed_deps: None,
+		encrypted: None,
 	}
 }
 
@@ -814,6 +824,7 @@ pub fn field_with_type() -> FieldDefinition {
 		reference: None,
 		auth_limit: AuthLimit::new_no_limit(),
 		computed_deps: None,
+		encrypted: None,
 	}
 }
 
@@ -836,6 +847,7 @@ pub fn field_readonly() -> FieldDefinition {
 		reference: None,
 		auth_limit: AuthLimit::new_no_limit(),
 		computed_deps: None,
+		encrypted: None,
 	}
 }
 
@@ -865,6 +877,7 @@ pub fn field_flexible_with_reference() -> FieldDefinition {
 			fields: vec!["price".to_string(), "quantity".to_string()],
 			is_complete: true,
 		}),
+		encrypted: None,
 	}
 }
 
@@ -896,6 +909,7 @@ pub fn field_with_default_set() -> FieldDefinition {
 			fields: vec![],
 			is_complete: false,
 		}),
+		encrypted: None,
 	}
 }
 
@@ -925,6 +939,7 @@ pub fn field_record_type() -> FieldDefinition {
 			Some("Editor".to_string()),
 		),
 		computed_deps: None,
+		encrypted: None,
 	}
 }
 
@@ -942,6 +957,7 @@ pub fn function_basic() -> FunctionDefinition {
 		permissions: Permission::Full,
 		returns: None,
 		auth_limit: AuthLimit::new_no_limit(),
+		aggregate: None,
 	}
 }
 
@@ -955,6 +971,7 @@ pub fn function_with_args() -> FunctionDefinition {
 		permissions: Permission::Full,
 		returns: Some(Kind::Number),
 		auth_limit: AuthLimit::new_no_limit(),
+		aggregate: None,
 	}
 }
 
@@ -1450,6 +1467,7 @@ pub fn module_surrealism() -> ModuleDefinition {
 			bucket: "my_bucket".to_string(),
 			key: "module_key".to_string(),
 		}),
+		limits: ModuleLimits::default(),
 	}
 }
 
@@ -1466,6 +1484,7 @@ pub fn module_silo() -> ModuleDefinition {
 			minor: 2,
 			patch: 3,
 		}),
+		limits: ModuleLimits::default(),
 	}
 }
 
@@ -1479,6 +1498,7 @@ pub fn module_no_name() -> ModuleDefinition {
 			bucket: "default_bucket".to_string(),
 			key: "anonymous_module".to_string(),
 		}),
+		limits: ModuleLimits::default(),
 	}
 }
 
@@ -1583,9 +1603,10 @@ pub fn node_archived() -> Node {
 // TermDocument fixtures
 // ===========================================================================
 
-/// Term document - basic default
+/// Term document - basic default (offsets of the frozen versions carry no
+/// term position)
 pub fn term_document_basic() -> TermDocument {
-	TermDocument::new(123, vec![Offset::new(1, 2, 3, 4)])
+	TermDocument::new(123, vec![Offset::new(1, 2, 3, 4, Offset::UNKNOWN_POS)])
 }
 
 // ===========================================================================
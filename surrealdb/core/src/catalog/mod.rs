@@ -31,7 +31,7 @@ pub(crate) use namespace::*;
 pub(crate) use record::*;
 pub use schema::ApiMethod;
 pub(crate) use schema::{
-	ApiDefinition, Distance, FullTextParams, HnswParams, Scoring, VectorType, *,
+	ApiDefinition, Distance, FullTextParams, HnswParams, Scoring, SpatialParams, VectorType, *,
 };
 pub(crate) use subscription::*;
 pub(crate) use table::*;
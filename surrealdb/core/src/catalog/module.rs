@@ -1,10 +1,11 @@
 use std::fmt::{self, Display};
+use std::time;
 
 use revision::revisioned;
 
 use crate::catalog::ModuleDefinition;
 use crate::expr::statements::info::InfoStructure;
-use crate::val::Value;
+use crate::val::{Duration, Value};
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -62,6 +63,34 @@ impl InfoStructure for SiloExecutable {
 	}
 }
 
+/// The resource limits of a module, which override those declared by its package
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct ModuleLimits {
+	/// The fuel available to a single invocation
+	pub fuel: Option<u64>,
+	/// The maximum size of the linear memory, in bytes
+	pub memory: Option<u64>,
+	/// The maximum duration of a single invocation
+	pub timeout: Option<time::Duration>,
+}
+
+impl ModuleLimits {
+	pub(crate) fn is_empty(&self) -> bool {
+		self.fuel.is_none() && self.memory.is_none() && self.timeout.is_none()
+	}
+}
+
+impl InfoStructure for ModuleLimits {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"fuel".to_string(), if let Some(v) = self.fuel => v.into(),
+			"memory".to_string(), if let Some(v) = self.memory => v.into(),
+			"timeout".to_string(), if let Some(v) = self.timeout => Duration(v).into(),
+		})
+	}
+}
+
 // This enum is not actually stored, but is used to generate the storage name of a module
 // Therefor I found it to fit better inside catalog, and to then let expr use this enum aswell,
 // to have a single point where the storage name is generated.
@@ -792,6 +792,131 @@ pub(crate) trait BucketProvider {
 	}
 }
 
+/// Job data access provider.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub(crate) trait JobProvider {
+	/// Retrieve all job definitions for a specific database.
+	async fn all_db_jobs(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::JobDefinition]>>;
+
+	/// Retrieve a specific job definition.
+	async fn get_db_job(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		jb: &str,
+	) -> Result<Option<Arc<catalog::JobDefinition>>>;
+
+	/// Retrieve a specific job definition returning an error if it does not exist.
+	async fn expect_db_job(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		jb: &str,
+	) -> Result<Arc<catalog::JobDefinition>> {
+		match self.get_db_job(ns, db, jb).await? {
+			Some(val) => Ok(val),
+			None => anyhow::bail!(Error::JbNotFound {
+				name: jb.to_owned(),
+			}),
+		}
+	}
+}
+
+/// Changefeed sink data access provider.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub(crate) trait SinkProvider {
+	/// Retrieve all sink definitions for a specific database.
+	async fn all_db_sinks(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::SinkDefinition]>>;
+
+	/// Retrieve a specific sink definition.
+	async fn get_db_sink(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		sk: &str,
+	) -> Result<Option<Arc<catalog::SinkDefinition>>>;
+}
+
+/// Changefeed consumer data access provider.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub(crate) trait ConsumerProvider {
+	/// Retrieve all consumer definitions for a specific database.
+	async fn all_db_consumers(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::ConsumerDefinition]>>;
+
+	/// Retrieve a specific consumer definition.
+	async fn get_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cs: &str,
+	) -> Result<Option<Arc<catalog::ConsumerDefinition>>>;
+
+	/// Retrieve a specific consumer definition returning an error if it does not exist.
+	async fn expect_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cs: &str,
+	) -> Result<Arc<catalog::ConsumerDefinition>> {
+		match self.get_db_consumer(ns, db, cs).await? {
+			Some(val) => Ok(val),
+			None => anyhow::bail!(Error::CsNotFound {
+				name: cs.to_owned(),
+			}),
+		}
+	}
+}
+
+/// Encryption key data access provider.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub(crate) trait KeyProvider {
+	/// Retrieve all key definitions for a specific database.
+	async fn all_db_keys(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::KeyDefinition]>>;
+
+	/// Retrieve a specific key definition.
+	async fn get_db_key(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ky: &str,
+	) -> Result<Option<Arc<catalog::KeyDefinition>>>;
+
+	/// Retrieve a specific key definition returning an error if it does not exist.
+	async fn expect_db_key(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ky: &str,
+	) -> Result<Arc<catalog::KeyDefinition>> {
+		match self.get_db_key(ns, db, ky).await? {
+			Some(val) => Ok(val),
+			None => anyhow::bail!(Error::KyNotFound {
+				name: ky.to_owned(),
+			}),
+		}
+	}
+}
+
 /// The catalog provider is a trait that provides access to the catalog of the datastore.
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
@@ -804,6 +929,10 @@ pub(crate) trait CatalogProvider:
 	+ AuthorisationProvider
 	+ ApiProvider
 	+ BucketProvider
+	+ JobProvider
+	+ SinkProvider
+	+ ConsumerProvider
+	+ KeyProvider
 {
 	/// Get or add a database with a default configuration, only if we are in
 	/// dynamic mode.
@@ -7,13 +7,16 @@ use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::val::{Array, Value};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct AnalyzerDefinition {
 	pub name: String,
 	pub function: Option<String>,
 	pub tokenizers: Option<Vec<Tokenizer>>,
 	pub filters: Option<Vec<Filter>>,
+	/// Whether the query strings of full-text searches support operators
+	#[revision(start = 2)]
+	pub operators: bool,
 	pub comment: Option<String>,
 }
 
@@ -27,6 +30,7 @@ impl AnalyzerDefinition {
 			function: self.function.clone(),
 			tokenizers: self.tokenizers.clone().map(|v| v.into_iter().map(|t| t.into()).collect()),
 			filters: self.filters.clone().map(|v| v.into_iter().map(|f| f.into()).collect()),
+			operators: self.operators,
 			comment: self
 				.comment
 				.clone()
@@ -51,6 +55,7 @@ impl InfoStructure for AnalyzerDefinition {
 				v.into_iter().map(|v| v.to_string().into()).collect::<Array>().into(),
 			"filters".to_string(), if let Some(v) = self.filters =>
 				v.into_iter().map(|v| v.to_sql().into()).collect::<Array>().into(),
+			"operators".to_string(), if self.operators => Value::Bool(true),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
@@ -1,6 +1,7 @@
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use surrealdb_types::{SqlFormat, ToSql};
+use url::Url;
 
 use crate::catalog::Permission;
 use crate::expr::statements::info::InfoStructure;
@@ -26,12 +27,44 @@ pub struct BucketDefinition {
 }
 impl_kv_value_revisioned!(BucketDefinition);
 
+/// The query options of a backend URL which hold secrets
+const SECRET_OPTIONS: [&str; 2] = ["secret_access_key", "session_token"];
+
 impl BucketDefinition {
+	/// Returns the backend URL, with the values of its secret query options
+	/// redacted.
+	fn redacted_backend(&self) -> Option<String> {
+		self.backend.as_deref().map(Self::redact_backend)
+	}
+
+	/// Redacts the values of the secret query options of a backend URL.
+	pub(crate) fn redact_backend(backend: &str) -> String {
+		let Ok(mut url) = Url::parse(backend) else {
+			return backend.to_owned();
+		};
+		if !url.query_pairs().any(|(key, _)| SECRET_OPTIONS.contains(&key.as_ref())) {
+			return backend.to_owned();
+		}
+		let pairs: Vec<(String, String)> = url
+			.query_pairs()
+			.map(|(key, value)| {
+				let value = if SECRET_OPTIONS.contains(&key.as_ref()) {
+					"[REDACTED]".to_owned()
+				} else {
+					value.into_owned()
+				};
+				(key.into_owned(), value)
+			})
+			.collect();
+		url.query_pairs_mut().clear().extend_pairs(pairs);
+		url.to_string()
+	}
+
 	pub fn to_sql_definition(&self) -> DefineBucketStatement {
 		DefineBucketStatement {
 			kind: DefineKind::Default,
 			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
-			backend: self.backend.clone().map(|v| sql::Expr::Literal(sql::Literal::String(v))),
+			backend: self.redacted_backend().map(|v| sql::Expr::Literal(sql::Literal::String(v))),
 			permissions: self.permissions.clone().into(),
 			readonly: self.readonly,
 			comment: self
@@ -45,10 +78,11 @@ impl BucketDefinition {
 
 impl InfoStructure for BucketDefinition {
 	fn structure(self) -> Value {
+		let backend = self.redacted_backend();
 		Value::from(map! {
 			"name".to_string() => self.name.into(),
 			"permissions".to_string() => self.permissions.structure(),
-			"backend".to_string(), if let Some(backend) = self.backend => Value::String(backend),
+			"backend".to_string(), if let Some(backend) = backend => Value::String(backend),
 			"readonly".to_string() => self.readonly.into(),
 			"comment".to_string(), if let Some(comment) = self.comment => comment.into(),
 		})
@@ -60,3 +94,30 @@ impl ToSql for BucketDefinition {
 		self.to_sql_definition().fmt_sql(f, fmt)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn backend_secrets_are_redacted() {
+		let bucket = BucketDefinition {
+			name: "files".to_owned(),
+			backend: Some(
+				"s3://bucket?region=eu-west-2&access_key_id=key&secret_access_key=secret&session_token=token"
+					.to_owned(),
+			),
+			..Default::default()
+		};
+		let sql = bucket.to_sql();
+		assert!(sql.contains("access_key_id=key") && sql.contains("REDACTED"), "{sql}");
+		assert!(!sql.contains("=secret") && !sql.contains("=token"), "{sql}");
+		let Value::Object(info) = bucket.structure() else {
+			panic!("Expected an object");
+		};
+		let Some(Value::String(backend)) = info.get("backend") else {
+			panic!("Expected a backend");
+		};
+		assert!(!backend.contains("=secret") && !backend.contains("=token"), "{backend}");
+	}
+}
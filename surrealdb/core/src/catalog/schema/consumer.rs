@@ -0,0 +1,78 @@
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql;
+use crate::sql::statements::define::{DefineConsumerStatement, DefineKind};
+use crate::val::{Datetime, TableName, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ConsumerDefinition {
+	pub(crate) name: String,
+	/// The table whose changes are consumed, or all the tables if not set.
+	pub(crate) table: Option<TableName>,
+	/// Whether the changefeed garbage collector keeps the changes which this
+	/// consumer has not yet acknowledged, even past the changefeed expiry.
+	pub(crate) retain: bool,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(ConsumerDefinition);
+
+impl ConsumerDefinition {
+	pub fn to_sql_definition(&self) -> DefineConsumerStatement {
+		DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			table: self.table.clone().map(|tb| sql::Expr::Table(tb.into_string())),
+			retain: self.retain,
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+}
+
+impl InfoStructure for ConsumerDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"table".to_string(), if let Some(v) = self.table => v.into(),
+			"retain".to_string() => self.retain.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for ConsumerDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+/// The acknowledged offset of a consumer, stored separately from its
+/// definition.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ConsumerOffset {
+	/// The versionstamp of the next change set to read, one past the last
+	/// acknowledged versionstamp.
+	pub(crate) versionstamp: u64,
+	/// The time of the last acknowledgement.
+	pub(crate) acknowledged: Option<Datetime>,
+}
+
+impl_kv_value_revisioned!(ConsumerOffset);
+
+impl InfoStructure for ConsumerOffset {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"versionstamp".to_string() => self.versionstamp.into(),
+			"acknowledged".to_string(), if let Some(v) = self.acknowledged => Value::Datetime(v),
+		})
+	}
+}
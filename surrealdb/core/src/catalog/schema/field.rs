@@ -36,7 +36,7 @@ pub struct ComputedDeps {
 	pub is_complete: bool,
 }
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct FieldDefinition {
 	// TODO: Needs to be it's own type.
@@ -68,6 +68,10 @@ pub struct FieldDefinition {
 	/// When `None` on a computed field, deps are extracted on-the-fly at query time.
 	#[revision(start = 3, default_fn = "default_computed_deps")]
 	pub(crate) computed_deps: Option<ComputedDeps>,
+
+	/// The name of the key which the value of this field is encrypted with.
+	#[revision(start = 4)]
+	pub(crate) encrypted: Option<String>,
 }
 
 impl FieldDefinition {
@@ -116,6 +120,7 @@ impl FieldDefinition {
 				.map(|x| sql::Expr::Literal(sql::Literal::String(x)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			reference: self.reference.clone().map(|x| x.into()),
+			encrypted: self.encrypted.clone(),
 		}
 	}
 }
@@ -133,6 +138,7 @@ impl InfoStructure for FieldDefinition {
 			"default_always".to_string(), if matches!(&self.default, DefineDefault::Always(_) | DefineDefault::Set(_)) => Value::Bool(matches!(self.default,DefineDefault::Always(_))), // Only reported if DEFAULT is also enabled for this field
 			"default".to_string(), if let DefineDefault::Always(v) | DefineDefault::Set(v) = self.default => v.structure(),
 			"reference".to_string(), if let Some(v) = self.reference => v.structure(),
+			"encrypted".to_string(), if let Some(v) = self.encrypted => v.into(),
 			"readonly".to_string() => self.readonly.into(),
 			"permissions".to_string() => Value::from(map!{
 				"select".to_string() => self.select_permission.structure(),
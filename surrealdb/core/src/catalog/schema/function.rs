@@ -4,13 +4,14 @@ use surrealdb_types::{SqlFormat, ToSql};
 use crate::catalog::Permission;
 use crate::catalog::auth::AuthLimit;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Block, Kind};
+use crate::expr::{Block, Expr, Kind};
 use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::statements::define::DefineAggregateStatement;
 use crate::sql::statements::define::DefineKind;
 use crate::sql::{self, DefineFunctionStatement};
 use crate::val::Value;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FunctionDefinition {
 	pub(crate) name: String,
@@ -22,6 +23,9 @@ pub struct FunctionDefinition {
 	/// The auth limit of the API.
 	#[revision(start = 2, default_fn = "default_auth_limit")]
 	pub(crate) auth_limit: AuthLimit,
+	/// The clauses of a function defined with `DEFINE AGGREGATE`.
+	#[revision(start = 3)]
+	pub(crate) aggregate: Option<AggregateDefinition>,
 }
 
 // This was pushed in after the first beta, so we need to add auth_limit to structs in a
@@ -34,7 +38,57 @@ impl FunctionDefinition {
 
 impl_kv_value_revisioned!(FunctionDefinition);
 
+/// The clauses of a user-defined aggregate function.
+///
+/// The arguments of the function are bound for each record in `ACCUMULATE`,
+/// together with the current `$state` of the group.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AggregateDefinition {
+	/// The state of an empty group.
+	pub(crate) state: Expr,
+	/// Folds the arguments of a record into `$state`.
+	pub(crate) accumulate: Block,
+	/// Combines `$state` with the partial state `$other` of the same group.
+	pub(crate) merge: Block,
+	/// Computes the result of the aggregate from `$state`.
+	pub(crate) finalize: Block,
+}
+
+impl InfoStructure for AggregateDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"state".to_string() => self.state.to_sql().into(),
+			"accumulate".to_string() => self.accumulate.to_sql().into(),
+			"merge".to_string() => self.merge.to_sql().into(),
+			"final".to_string() => self.finalize.to_sql().into(),
+		})
+	}
+}
+
 impl FunctionDefinition {
+	fn comment_expr(&self) -> sql::Expr {
+		self.comment
+			.clone()
+			.map(|x| sql::Expr::Literal(sql::Literal::String(x)))
+			.unwrap_or(sql::Expr::Literal(sql::Literal::None))
+	}
+
+	fn to_sql_aggregate(&self, aggregate: &AggregateDefinition) -> DefineAggregateStatement {
+		DefineAggregateStatement {
+			kind: DefineKind::Default,
+			name: self.name.clone(),
+			args: self.args.clone().into_iter().map(|(n, k)| (n, sql::Kind::from(k))).collect(),
+			returns: self.returns.clone().map(|k| k.into()),
+			state: aggregate.state.clone().into(),
+			accumulate: aggregate.accumulate.clone().into(),
+			merge: aggregate.merge.clone().into(),
+			finalize: aggregate.finalize.clone().into(),
+			permissions: self.permissions.clone().into(),
+			comment: self.comment_expr(),
+		}
+	}
+
 	fn to_sql_definition(&self) -> DefineFunctionStatement {
 		DefineFunctionStatement {
 			kind: DefineKind::Default,
@@ -43,11 +97,7 @@ impl FunctionDefinition {
 			block: self.block.clone().into(),
 			permissions: self.permissions.clone().into(),
 			returns: self.returns.clone().map(|k| k.into()),
-			comment: self
-				.comment
-				.clone()
-				.map(|x| sql::Expr::Literal(sql::Literal::String(x)))
-				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			comment: self.comment_expr(),
 		}
 	}
 }
@@ -61,16 +111,20 @@ impl InfoStructure for FunctionDefinition {
 				.map(|(n, k)| vec![n.into(), k.to_sql().into()].into())
 				.collect::<Vec<Value>>()
 				.into(),
-			"block".to_string() => self.block.to_sql().into(),
+			"block".to_string(), if self.aggregate.is_none() => self.block.to_sql().into(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.to_sql().into(),
 			"returns".to_string(), if let Some(v) = self.returns => v.to_sql().into(),
+			"aggregate".to_string(), if let Some(v) = self.aggregate => v.structure(),
 		})
 	}
 }
 
 impl ToSql for &FunctionDefinition {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
-		self.to_sql_definition().fmt_sql(f, fmt)
+		match &self.aggregate {
+			Some(aggregate) => self.to_sql_aggregate(aggregate).fmt_sql(f, fmt),
+			None => self.to_sql_definition().fmt_sql(f, fmt),
+		}
 	}
 }
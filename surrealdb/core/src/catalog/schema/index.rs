@@ -138,6 +138,8 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Spatial index for geometry-based queries
+	Spatial(SpatialParams),
 }
 
 impl Index {
@@ -148,6 +150,7 @@ impl Index {
 			Self::Hnsw(params) => sql::index::Index::Hnsw(params.clone().into()),
 			Self::FullText(params) => sql::index::Index::FullText(params.clone().into()),
 			Self::Count(cond) => sql::index::Index::Count(cond.clone().map(Into::into)),
+			Self::Spatial(params) => sql::index::Index::Spatial(params.clone().into()),
 		}
 	}
 
@@ -346,6 +349,14 @@ impl Display for VectorType {
 	}
 }
 
+/// Spatial index parameters.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct SpatialParams {
+	/// The depth of the quadtree cells used to cover the indexed geometries.
+	pub level: u8,
+}
+
 /// HNSW index parameters.
 #[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
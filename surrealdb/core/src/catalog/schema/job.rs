@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::Expr;
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql;
+use crate::sql::statements::define::{DefineJobStatement, DefineKind};
+use crate::val::{Datetime, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JobDefinition {
+	pub(crate) name: String,
+	/// The cron expression of the schedule of the job.
+	pub(crate) schedule: String,
+	pub(crate) then: Expr,
+	pub(crate) comment: Option<String>,
+	/// The time at which the job was defined, from which its schedule starts.
+	pub(crate) created: Datetime,
+}
+
+impl_kv_value_revisioned!(JobDefinition);
+
+impl JobDefinition {
+	pub fn to_sql_definition(&self) -> DefineJobStatement {
+		DefineJobStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			schedule: sql::Expr::Literal(sql::Literal::String(self.schedule.clone())),
+			then: self.then.clone().into(),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+}
+
+impl InfoStructure for JobDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"schedule".to_string() => self.schedule.into(),
+			"then".to_string() => self.then.structure(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for JobDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+/// The most recent runs of a job, stored separately from its definition.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct JobHistory {
+	/// The runs of the job, the most recent first.
+	pub(crate) runs: Vec<JobRun>,
+}
+
+impl_kv_value_revisioned!(JobHistory);
+
+impl JobHistory {
+	/// Returns the start time of the most recent run of the job.
+	pub(crate) fn last_run(&self) -> Option<&Datetime> {
+		self.runs.first().map(|run| &run.started)
+	}
+
+	/// Records a run of the job, keeping at most `limit` runs.
+	pub(crate) fn record(&mut self, run: JobRun, limit: usize) {
+		self.runs.insert(0, run);
+		self.runs.truncate(limit);
+	}
+}
+
+impl InfoStructure for JobHistory {
+	fn structure(self) -> Value {
+		self.runs.into_iter().map(InfoStructure::structure).collect::<Vec<_>>().into()
+	}
+}
+
+/// A single run of a job.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JobRun {
+	/// The time at which the run started.
+	pub(crate) started: Datetime,
+	/// How long the run took.
+	pub(crate) duration: Duration,
+	/// Whether the run was triggered with `RUN JOB`, rather than by the schedule.
+	pub(crate) manual: bool,
+	/// The error which made the run fail, if any.
+	pub(crate) error: Option<String>,
+}
+
+impl InfoStructure for JobRun {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"started".to_string() => Value::Datetime(self.started),
+			"duration".to_string() => Value::Duration(self.duration.into()),
+			"trigger".to_string() => if self.manual { "manual" } else { "schedule" }.into(),
+			"error".to_string(), if let Some(v) = self.error => v.into(),
+		})
+	}
+}
@@ -0,0 +1,449 @@
+use std::fmt;
+use std::sync::LazyLock;
+
+use anyhow::{Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use quick_cache::sync::{Cache, GuardResult};
+use revision::revisioned;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::cnf::{KEY_CIPHER_CACHE_SIZE, KEY_MASTER_KEY_FILE};
+use crate::err::Error;
+use crate::expr::Idiom;
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::{EncryptionKey, impl_kv_value_revisioned};
+use crate::sql;
+use crate::sql::statements::define::{DefineKeyStatement, DefineKind};
+use crate::val::Value;
+
+/// The length in bytes of the key material of an encryption key.
+pub(crate) const KEY_LENGTH: usize = 32;
+
+/// The header which starts every encrypted field value.
+const CIPHERTEXT_MAGIC: &[u8; 4] = b"SENC";
+
+/// The length of the ciphertext header: the magic bytes, the key version,
+/// and the nonce.
+const CIPHERTEXT_HEADER: usize = CIPHERTEXT_MAGIC.len() + 4 + NONCE_LEN;
+
+/// The master key, loaded from the file at `SURREAL_KEY_MASTER_KEY_FILE`.
+static MASTER_KEY: LazyLock<Option<Result<EncryptionKey, String>>> = LazyLock::new(|| {
+	KEY_MASTER_KEY_FILE
+		.as_ref()
+		.map(|path| EncryptionKey::from_file(path).map_err(|e| e.to_string()))
+});
+
+/// The ciphers of the keys, by their wrapped key material, so that the key
+/// material of each version of a key is only unwrapped with the master key
+/// once. Wrapped key material is bound to the name of its key, and is checked
+/// to unwrap under that name whenever a key is defined.
+static CIPHER_CACHE: LazyLock<Cache<Vec<u8>, LessSafeKey>> =
+	LazyLock::new(|| Cache::new(KEY_CIPHER_CACHE_SIZE.max(10)));
+
+/// An encryption key, used for encrypting the values of the fields which are
+/// defined with `ENCRYPTED WITH KEY`.
+///
+/// Encrypted values are stored as bytes, made up of a `SENC` header, the
+/// version of the key, a random nonce, and the AES-256-GCM ciphertext of the
+/// serialized value. The version is bumped each time the key is rotated with
+/// `REBUILD KEY`.
+///
+/// The key material is stored wrapped with the master key of the server, so
+/// that the encrypted values can not be read from a copy of the datastore.
+#[revisioned(revision = 1)]
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct KeyDefinition {
+	pub(crate) name: String,
+	pub(crate) version: u32,
+	/// The key material, wrapped with the master key
+	pub(crate) material: Vec<u8>,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(KeyDefinition);
+
+impl fmt::Debug for KeyDefinition {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("KeyDefinition")
+			.field("name", &self.name)
+			.field("version", &self.version)
+			.field("material", &"[REDACTED]")
+			.field("comment", &self.comment)
+			.finish()
+	}
+}
+
+impl KeyDefinition {
+	/// Creates a key definition, wrapping the key material with the master key.
+	pub(crate) fn new(
+		name: String,
+		version: u32,
+		material: &[u8],
+		comment: Option<String>,
+	) -> Result<Self> {
+		let material = match Self::master_key(&name)?.seal(name.as_bytes(), material) {
+			Ok(v) => v,
+			Err(e) => bail!(Error::KyMasterKey {
+				name,
+				message: e.to_string(),
+			}),
+		};
+		Ok(Self {
+			name,
+			version,
+			material,
+			comment,
+		})
+	}
+
+	/// Generates new random key material.
+	pub(crate) fn generate_material() -> Result<Vec<u8>> {
+		let mut material = vec![0u8; KEY_LENGTH];
+		if SystemRandom::new().fill(&mut material).is_err() {
+			bail!(Error::unreachable("Unable to generate random key material"));
+		}
+		Ok(material)
+	}
+
+	/// Decodes key material from a base64 encoded string or from bytes.
+	pub(crate) fn decode_material(name: &str, value: Value) -> Result<Vec<u8>> {
+		let material = match value {
+			Value::Bytes(v) => v.into_inner().to_vec(),
+			Value::String(v) => match STANDARD.decode(v.as_bytes()) {
+				Ok(v) => v,
+				Err(e) => bail!(Error::KyInvalid {
+					name: name.to_owned(),
+					message: e.to_string(),
+				}),
+			},
+			v => bail!(Error::KyInvalid {
+				name: name.to_owned(),
+				message: format!("expected bytes or a base64 string, found {}", v.kind_of()),
+			}),
+		};
+		if material.len() != KEY_LENGTH {
+			bail!(Error::KyInvalid {
+				name: name.to_owned(),
+				message: format!("expected {KEY_LENGTH} bytes, found {}", material.len()),
+			});
+		}
+		Ok(material)
+	}
+
+	/// Decodes wrapped key material from a base64 encoded string or from
+	/// bytes, checking that it can be unwrapped with the master key.
+	pub(crate) fn decode_wrapped(name: &str, value: Value) -> Result<Vec<u8>> {
+		let wrapped = match value {
+			Value::Bytes(v) => v.into_inner().to_vec(),
+			Value::String(v) => match STANDARD.decode(v.as_bytes()) {
+				Ok(v) => v,
+				Err(e) => bail!(Error::KyInvalid {
+					name: name.to_owned(),
+					message: e.to_string(),
+				}),
+			},
+			v => bail!(Error::KyInvalid {
+				name: name.to_owned(),
+				message: format!("expected bytes or a base64 string, found {}", v.kind_of()),
+			}),
+		};
+		Self::unwrap_material(name, &wrapped)?;
+		Ok(wrapped)
+	}
+
+	/// Returns the definition of this key, with the key material redacted.
+	pub(crate) fn to_sql_definition(&self) -> DefineKeyStatement {
+		DefineKeyStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			version: Some(self.version),
+			value: Some(sql::Expr::Literal(sql::Literal::String("[REDACTED]".to_owned()))),
+			encrypted: false,
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+
+	/// Returns the definition of this key for an export, including its key
+	/// material wrapped with the master key, so that the exported data can
+	/// be decrypted once imported into a server with the same master key.
+	pub(crate) fn to_export_definition(&self) -> DefineKeyStatement {
+		DefineKeyStatement {
+			value: Some(sql::Expr::Literal(sql::Literal::String(STANDARD.encode(&self.material)))),
+			encrypted: true,
+			..self.to_sql_definition()
+		}
+	}
+
+	/// Checks whether a value is an encrypted field value.
+	pub(crate) fn is_ciphertext(value: &Value) -> bool {
+		match value {
+			Value::Bytes(v) => {
+				v.len() >= CIPHERTEXT_HEADER + AES_256_GCM.tag_len()
+					&& v.starts_with(CIPHERTEXT_MAGIC)
+			}
+			_ => false,
+		}
+	}
+
+	/// Encrypts the value of a field within a document. Fields which are not
+	/// set, or which already hold a value encrypted with this key, are left
+	/// untouched.
+	pub(crate) fn encrypt_field(&self, doc: &mut Value, field: &Idiom) -> Result<()> {
+		self.encrypt_field_with(doc, field, || self.cipher())
+	}
+
+	/// Decrypts the value of a field within a document. Fields which do not
+	/// hold an encrypted value, for instance because they were stored before
+	/// the field was encrypted, are left untouched.
+	pub(crate) fn decrypt_field(&self, doc: &mut Value, field: &Idiom) -> Result<()> {
+		self.decrypt_field_with(doc, field, || self.cipher())
+	}
+
+	/// Encrypts the value of a field, only loading the cipher when the field
+	/// is set.
+	fn encrypt_field_with(
+		&self,
+		doc: &mut Value,
+		field: &Idiom,
+		cipher: impl FnOnce() -> Result<LessSafeKey>,
+	) -> Result<()> {
+		let val = doc.pick(field);
+		if val.is_none() {
+			return Ok(());
+		}
+		let cipher = cipher()?;
+		if self.open(&cipher, &val).is_some() {
+			return Ok(());
+		}
+		let Some(val) = self.seal(&cipher, &val) else {
+			bail!(self.error("encrypt", field));
+		};
+		doc.put(field, val);
+		Ok(())
+	}
+
+	/// Decrypts the value of a field, only loading the cipher when the field
+	/// holds an encrypted value.
+	fn decrypt_field_with(
+		&self,
+		doc: &mut Value,
+		field: &Idiom,
+		cipher: impl FnOnce() -> Result<LessSafeKey>,
+	) -> Result<()> {
+		let val = doc.pick(field);
+		if !Self::is_ciphertext(&val) {
+			return Ok(());
+		}
+		let Some(val) = self.open(&cipher()?, &val) else {
+			bail!(self.error("decrypt", field));
+		};
+		doc.put(field, val);
+		Ok(())
+	}
+
+	/// Encrypts a value with the current version of this key.
+	fn seal(&self, key: &LessSafeKey, value: &Value) -> Option<Value> {
+		let mut nonce = [0u8; NONCE_LEN];
+		SystemRandom::new().fill(&mut nonce).ok()?;
+		let mut out = Vec::with_capacity(CIPHERTEXT_HEADER);
+		out.extend_from_slice(CIPHERTEXT_MAGIC);
+		out.extend_from_slice(&self.version.to_be_bytes());
+		out.extend_from_slice(&nonce);
+		// The header is authenticated along with the ciphertext
+		let mut data = revision::to_vec(value).ok()?;
+		key.seal_in_place_append_tag(
+			Nonce::assume_unique_for_key(nonce),
+			Aad::from(&out[..]),
+			&mut data,
+		)
+		.ok()?;
+		out.extend_from_slice(&data);
+		Some(Value::Bytes(out.into()))
+	}
+
+	/// Decrypts a value which was encrypted with this version of this key.
+	fn open(&self, key: &LessSafeKey, value: &Value) -> Option<Value> {
+		let Value::Bytes(bytes) = value else {
+			return None;
+		};
+		if !Self::is_ciphertext(value) {
+			return None;
+		}
+		let (header, data) = bytes.split_at(CIPHERTEXT_HEADER);
+		let version = u32::from_be_bytes(header[CIPHERTEXT_MAGIC.len()..][..4].try_into().ok()?);
+		if version != self.version {
+			return None;
+		}
+		let nonce = Nonce::try_assume_unique_for_key(&header[CIPHERTEXT_MAGIC.len() + 4..]).ok()?;
+		let mut data = data.to_vec();
+		let plain = key.open_in_place(nonce, Aad::from(header), &mut data).ok()?;
+		revision::from_slice(plain).ok()
+	}
+
+	/// Loads the cipher of this key, unwrapping the key material the first
+	/// time this version of the key is used.
+	fn cipher(&self) -> Result<LessSafeKey> {
+		match CIPHER_CACHE.get_value_or_guard(self.material.as_slice(), None) {
+			GuardResult::Value(v) => Ok(v),
+			GuardResult::Guard(g) => {
+				let cipher = Self::load_cipher(
+					&self.name,
+					&Self::unwrap_material(&self.name, &self.material)?,
+				)?;
+				g.insert(cipher.clone()).ok();
+				Ok(cipher)
+			}
+			GuardResult::Timeout => {
+				Self::load_cipher(&self.name, &Self::unwrap_material(&self.name, &self.material)?)
+			}
+		}
+	}
+
+	/// Loads a cipher from unwrapped key material.
+	fn load_cipher(name: &str, material: &[u8]) -> Result<LessSafeKey> {
+		match UnboundKey::new(&AES_256_GCM, material) {
+			Ok(v) => Ok(LessSafeKey::new(v)),
+			Err(_) => bail!(Error::KyInvalid {
+				name: name.to_owned(),
+				message: "unable to load the key material".to_owned(),
+			}),
+		}
+	}
+
+	/// Unwraps key material with the master key.
+	fn unwrap_material(name: &str, wrapped: &[u8]) -> Result<Vec<u8>> {
+		match Self::master_key(name)?.open(name.as_bytes(), wrapped) {
+			Ok(v) => Ok(v),
+			Err(_) => bail!(Error::KyMasterKey {
+				name: name.to_owned(),
+				message: "the key material was wrapped with a different master key".to_owned(),
+			}),
+		}
+	}
+
+	/// Returns the configured master key.
+	fn master_key(name: &str) -> Result<&'static EncryptionKey> {
+		match MASTER_KEY.as_ref() {
+			Some(Ok(v)) => Ok(v),
+			Some(Err(e)) => bail!(Error::KyMasterKey {
+				name: name.to_owned(),
+				message: e.clone(),
+			}),
+			None => bail!(Error::KyMasterKeyMissing {
+				name: name.to_owned(),
+			}),
+		}
+	}
+
+	fn error(&self, action: &'static str, field: &Idiom) -> Error {
+		Error::FieldEncryption {
+			action,
+			field: field.to_sql(),
+			key: self.name.clone(),
+		}
+	}
+}
+
+impl InfoStructure for KeyDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"version".to_string() => self.version.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for KeyDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::val::Object;
+
+	/// A key, whose material stands in for wrapped key material, and the
+	/// cipher of the key, as the master key is not configured in tests
+	fn key(version: u32) -> (KeyDefinition, LessSafeKey) {
+		let material = KeyDefinition::generate_material().unwrap();
+		let cipher = KeyDefinition::load_cipher("pii", &material).unwrap();
+		let key = KeyDefinition {
+			name: "pii".to_owned(),
+			version,
+			material,
+			comment: None,
+		};
+		(key, cipher)
+	}
+
+	fn encrypt(key: &(KeyDefinition, LessSafeKey), doc: &mut Value, field: &Idiom) -> Result<()> {
+		let (key, cipher) = key;
+		key.encrypt_field_with(doc, field, || Ok(cipher.clone()))
+	}
+
+	fn decrypt(key: &(KeyDefinition, LessSafeKey), doc: &mut Value, field: &Idiom) -> Result<()> {
+		let (key, cipher) = key;
+		key.decrypt_field_with(doc, field, || Ok(cipher.clone()))
+	}
+
+	#[test]
+	fn encrypt_decrypt_field() {
+		let key = key(1);
+		let field = Idiom::field("email".to_owned());
+		let mut doc = Value::Object(Object::from(map! {
+			"email".to_string() => Value::from("tobie@surrealdb.com"),
+			"name".to_string() => Value::from("Tobie"),
+		}));
+		let plain = doc.clone();
+		encrypt(&key, &mut doc, &field).unwrap();
+		assert!(KeyDefinition::is_ciphertext(&doc.pick(&field)));
+		assert_eq!(doc.pick(&Idiom::field("name".to_owned())), Value::from("Tobie"));
+		// Encrypting twice leaves the ciphertext untouched
+		let encrypted = doc.clone();
+		encrypt(&key, &mut doc, &field).unwrap();
+		assert_eq!(doc, encrypted);
+		decrypt(&key, &mut doc, &field).unwrap();
+		assert_eq!(doc, plain);
+	}
+
+	#[test]
+	fn decrypt_with_other_version_fails() {
+		let field = Idiom::field("email".to_owned());
+		let mut doc = Value::Object(Object::from(map! {
+			"email".to_string() => Value::from("tobie@surrealdb.com"),
+		}));
+		let (one, cipher) = key(1);
+		encrypt(&(one.clone(), cipher.clone()), &mut doc, &field).unwrap();
+		let two = KeyDefinition {
+			version: 2,
+			..one
+		};
+		decrypt(&(two, cipher), &mut doc, &field).unwrap_err();
+	}
+
+	#[test]
+	fn definition_is_redacted() {
+		let (key, _) = key(1);
+		let sql = key.to_sql();
+		assert_eq!(sql, "DEFINE KEY pii VERSION 1 VALUE '[REDACTED]'");
+		assert!(!sql.contains(&STANDARD.encode(&key.material)));
+	}
+
+	#[test]
+	fn decode_material() {
+		let material = KeyDefinition::generate_material().unwrap();
+		let encoded = Value::from(STANDARD.encode(&material));
+		assert_eq!(KeyDefinition::decode_material("pii", encoded).unwrap(), material);
+		KeyDefinition::decode_material("pii", Value::from("c2hvcnQ=")).unwrap_err();
+	}
+}
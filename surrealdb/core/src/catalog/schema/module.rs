@@ -2,20 +2,22 @@ use anyhow::bail;
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
-use crate::catalog::{ModuleExecutable, Permission};
+use crate::catalog::{ModuleExecutable, ModuleLimits, Permission};
 use crate::expr::statements::info::InfoStructure;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql::statements::define::DefineKind;
 use crate::sql::{self, DefineModuleStatement};
 use crate::val::Value;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ModuleDefinition {
 	pub(crate) name: Option<String>,
 	pub(crate) comment: Option<String>,
 	pub(crate) permissions: Permission,
 	pub(crate) executable: ModuleExecutable,
+	#[revision(start = 2)]
+	pub(crate) limits: ModuleLimits,
 }
 
 impl_kv_value_revisioned!(ModuleDefinition);
@@ -26,6 +28,7 @@ impl ModuleDefinition {
 			kind: DefineKind::Default,
 			name: self.name.clone(),
 			executable: self.executable.clone().into(),
+			limits: self.limits.into(),
 			permissions: self.permissions.clone().into(),
 			comment: self
 				.comment
@@ -55,6 +58,7 @@ impl InfoStructure for ModuleDefinition {
 		Value::from(map! {
 			"name".to_string(), if let Some(name) = self.name => name.into(),
 			"executable".to_string() => self.executable.structure(),
+			"limits".to_string(), if !self.limits.is_empty() => self.limits.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.to_sql().into(),
 		})
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use revision::revisioned;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql;
+use crate::sql::statements::define::{DefineKind, DefineSinkStatement};
+use crate::val::{Datetime, TableName, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SinkDefinition {
+	pub(crate) name: String,
+	/// The tables whose changes are forwarded, or all the tables if empty.
+	pub(crate) tables: Vec<TableName>,
+	pub(crate) target: SinkTarget,
+	/// The maximum number of change sets delivered at once.
+	pub(crate) batch: u32,
+	/// The number of times a failed delivery is retried with an exponential
+	/// backoff, before being retried on each run of the sink processor.
+	pub(crate) retry: u32,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(SinkDefinition);
+
+impl SinkDefinition {
+	pub(crate) const DEFAULT_BATCH: u32 = 100;
+	pub(crate) const DEFAULT_RETRY: u32 = 3;
+
+	/// Whether the changes of the given table are forwarded by this sink.
+	pub(crate) fn includes(&self, tb: &TableName) -> bool {
+		self.tables.is_empty() || self.tables.contains(tb)
+	}
+
+	pub fn to_sql_definition(&self) -> DefineSinkStatement {
+		DefineSinkStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			tables: self
+				.tables
+				.iter()
+				.map(|tb| sql::Expr::Table(tb.clone().into_string()))
+				.collect(),
+			target: self.target.to_sql_definition(),
+			batch: self.batch,
+			retry: self.retry,
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+}
+
+impl InfoStructure for SinkDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"tables".to_string() => self.tables.into_iter().map(Value::from).collect(),
+			"target".to_string() => self.target.structure(),
+			"batch".to_string() => self.batch.into(),
+			"retry".to_string() => self.retry.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for SinkDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
+
+/// Where the changes forwarded by a sink are delivered.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SinkTarget {
+	/// Changes are posted as JSON to an HTTP endpoint.
+	Webhook {
+		url: String,
+		headers: BTreeMap<String, String>,
+	},
+	/// Changes are appended as newline-delimited JSON to a rotating file.
+	File {
+		path: String,
+	},
+	/// Changes are written as newline-delimited JSON to the standard output.
+	Stdout,
+	/// Changes are handed to a sink registered with the datastore.
+	Custom {
+		name: String,
+	},
+}
+
+impl SinkTarget {
+	/// Returns the headers of a webhook, with their values redacted, as they
+	/// may hold credentials.
+	pub(crate) fn redacted_headers<'a>(
+		names: impl IntoIterator<Item = &'a String>,
+	) -> BTreeMap<String, String> {
+		names.into_iter().map(|k| (k.clone(), "[REDACTED]".to_owned())).collect()
+	}
+
+	fn to_sql_definition(&self) -> sql::statements::define::SinkTarget {
+		let string = |v: &str| sql::Expr::Literal(sql::Literal::String(v.to_owned()));
+		match self {
+			Self::Webhook {
+				url,
+				headers,
+			} => sql::statements::define::SinkTarget::Webhook {
+				url: string(url),
+				headers: if headers.is_empty() {
+					None
+				} else {
+					Some(sql::Expr::Literal(sql::Literal::Object(
+						Self::redacted_headers(headers.keys())
+							.iter()
+							.map(|(k, v)| sql::literal::ObjectEntry {
+								key: k.clone(),
+								value: string(v),
+							})
+							.collect(),
+					)))
+				},
+			},
+			Self::File {
+				path,
+			} => sql::statements::define::SinkTarget::File(string(path)),
+			Self::Stdout => sql::statements::define::SinkTarget::Stdout,
+			Self::Custom {
+				name,
+			} => sql::statements::define::SinkTarget::Custom(string(name)),
+		}
+	}
+}
+
+impl InfoStructure for SinkTarget {
+	fn structure(self) -> Value {
+		match self {
+			Self::Webhook {
+				url,
+				headers,
+			} => Value::from(map! {
+				"kind".to_string() => "webhook".into(),
+				"url".to_string() => url.into(),
+				"headers".to_string() => Value::Object(Self::redacted_headers(headers.keys()).into()),
+			}),
+			Self::File {
+				path,
+			} => Value::from(map! {
+				"kind".to_string() => "file".into(),
+				"path".to_string() => path.into(),
+			}),
+			Self::Stdout => Value::from(map! {
+				"kind".to_string() => "stdout".into(),
+			}),
+			Self::Custom {
+				name,
+			} => Value::from(map! {
+				"kind".to_string() => "custom".into(),
+				"name".to_string() => name.into(),
+			}),
+		}
+	}
+}
+
+/// The delivery cursor of a sink, stored separately from its definition.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SinkPosition {
+	/// The versionstamp of the next change set to deliver.
+	pub(crate) versionstamp: u64,
+	/// The time of the last successful delivery.
+	pub(crate) delivered: Option<Datetime>,
+	/// The error of the last failed delivery, cleared once a delivery succeeds.
+	pub(crate) error: Option<String>,
+	/// The number of failed attempts at delivering the current batch.
+	pub(crate) attempts: u32,
+	/// The time before which a failed delivery is not retried.
+	pub(crate) retry_at: Option<Datetime>,
+}
+
+impl_kv_value_revisioned!(SinkPosition);
+
+impl InfoStructure for SinkPosition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"versionstamp".to_string() => self.versionstamp.into(),
+			"delivered".to_string(), if let Some(v) = self.delivered => Value::Datetime(v),
+			"error".to_string(), if let Some(v) = self.error => v.into(),
+			"attempts".to_string() => self.attempts.into(),
+			"retry_at".to_string(), if let Some(v) = self.retry_at => Value::Datetime(v),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn webhook_headers_are_redacted() {
+		let sink = SinkDefinition {
+			name: "out".to_string(),
+			tables: Vec::new(),
+			target: SinkTarget::Webhook {
+				url: "https://example.com/hook".to_string(),
+				headers: BTreeMap::from([(
+					"Authorization".to_string(),
+					"Bearer secret".to_string(),
+				)]),
+			},
+			batch: SinkDefinition::DEFAULT_BATCH,
+			retry: SinkDefinition::DEFAULT_RETRY,
+			comment: None,
+		};
+		let sql = sink.to_sql();
+		assert!(sql.contains("Authorization") && sql.contains("REDACTED"), "{sql}");
+		assert!(!sql.contains("secret"), "{sql}");
+		let info = format!("{:?}", sink.structure());
+		assert!(info.contains("REDACTED") && !info.contains("secret"), "{info}");
+	}
+}
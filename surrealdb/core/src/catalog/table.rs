@@ -1,3 +1,5 @@
+use std::time;
+
 use revision::{DeserializeRevisioned, Revisioned, SerializeRevisioned, revisioned};
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use uuid::Uuid;
@@ -9,7 +11,7 @@ use crate::fmt::EscapeKwFreeIdent;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::sql::statements::DefineTableStatement;
-use crate::val::{TableName, Value};
+use crate::val::{Duration, TableName, Value};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -40,7 +42,7 @@ impl DeserializeRevisioned for TableId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -54,6 +56,9 @@ pub struct TableDefinition {
 	pub(crate) changefeed: Option<ChangeFeed>,
 	pub(crate) comment: Option<String>,
 	pub(crate) table_type: TableType,
+	/// How long a record is kept after it was last written
+	#[revision(start = 2)]
+	pub(crate) ttl: Option<time::Duration>,
 
 	/// The last time that a DEFINE FIELD was added to this table
 	pub(crate) cache_fields_ts: Uuid,
@@ -87,6 +92,7 @@ impl TableDefinition {
 			changefeed: None,
 			comment: None,
 			table_type: TableType::default(),
+			ttl: None,
 			cache_fields_ts: now,
 			cache_events_ts: now,
 			cache_tables_ts: now,
@@ -103,7 +109,7 @@ impl TableDefinition {
 		matches!(self.table_type, TableType::Relation(_) | TableType::Any)
 	}
 
-	fn to_sql_definition(&self) -> DefineTableStatement {
+	pub(crate) fn to_sql_definition(&self) -> DefineTableStatement {
 		DefineTableStatement {
 			id: Some(self.table_id.0),
 			name: sql::Expr::Table(self.name.clone().into_string()),
@@ -118,6 +124,7 @@ impl TableDefinition {
 				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			table_type: self.table_type.clone().into(),
+			ttl: self.ttl.map(Into::into),
 			..Default::default()
 		}
 	}
@@ -138,6 +145,7 @@ impl InfoStructure for TableDefinition {
 			"kind".to_string() => self.table_type.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"ttl".to_string(), if let Some(v) = self.ttl => Duration(v).into(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 			"id".to_string() => self.table_id.0.into(),
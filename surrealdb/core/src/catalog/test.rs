@@ -60,11 +60,12 @@ use crate::val::{Datetime, TableName, Value};
 	}),
 	comment: Some("comment".to_string()),
 	table_type: TableType::Normal,
+	ttl: None,
 	cache_fields_ts: Uuid::default(),
 	cache_events_ts: Uuid::default(),
 	cache_tables_ts: Uuid::default(),
 	cache_indexes_ts: Uuid::default(),
-}, 149)]
+}, 150)]
 #[case::subscription(SubscriptionDefinition {
 	id: Uuid::default(),
 	node: Uuid::default(),
@@ -119,8 +120,9 @@ use crate::val::{Datetime, TableName, Value};
 	function: Some("function".to_string()),
 	tokenizers: Some(vec![Tokenizer::Camel]),
 	filters: Some(vec![Filter::Ascii]),
+	operators: true,
 	comment: Some("comment".to_string()),
-}, 37)]
+}, 38)]
 #[case::api(ApiDefinition {
 	path: "/test".parse().unwrap(),
 	actions: vec![
@@ -187,7 +189,8 @@ use crate::val::{Datetime, TableName, Value};
 	reference: None,
 	auth_limit: AuthLimit::default(),
 	computed_deps: None,
-}, 44)]
+	encrypted: None,
+}, 45)]
 #[case::function(FunctionDefinition {
 	name: "function".to_string(),
 	args: vec![],
@@ -198,7 +201,8 @@ use crate::val::{Datetime, TableName, Value};
 	permissions: Permission::Full,
 	returns: Some(Kind::Any),
 	auth_limit: AuthLimit::default(),
-}, 40)]
+	aggregate: None,
+}, 41)]
 #[case::index(IndexDefinition {
 	index_id: IndexId(123),
 	name: "test".to_string(),
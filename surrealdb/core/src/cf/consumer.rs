@@ -0,0 +1,136 @@
+//! Changefeed consumers, defined with `DEFINE CONSUMER`, which keep the
+//! versionstamp acknowledged by a reader of the changes on the server.
+//!
+//! `SHOW CHANGES FOR CONSUMER` reads the changes from the offset of the
+//! consumer, and `ACK CONSUMER` moves the offset past the changes which have
+//! been processed. Consumers defined with `RETAIN` prevent the changefeed
+//! garbage collector from removing the changes they have not acknowledged.
+use anyhow::Result;
+
+use crate::catalog::providers::ConsumerProvider;
+use crate::catalog::{ConsumerOffset, DatabaseId, NamespaceId};
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::Transaction;
+use crate::val::Value;
+
+/// Returns the offset of a consumer which reads the changes made from now on.
+pub(crate) async fn initial_offset(txn: &Transaction) -> Result<ConsumerOffset> {
+	Ok(ConsumerOffset {
+		versionstamp: txn.timestamp().await?.as_versionstamp() as u64,
+		..Default::default()
+	})
+}
+
+/// Returns the acknowledged offset of a consumer. A consumer whose offset is
+/// missing reads the changes made from now on, rather than the whole
+/// changefeed.
+pub(crate) async fn offset(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	cs: &str,
+) -> Result<ConsumerOffset> {
+	let key = crate::key::database::co::new(ns, db, cs);
+	match txn.get(&key, None).await? {
+		Some(offset) => Ok(offset),
+		None => initial_offset(txn).await,
+	}
+}
+
+/// Returns the earliest versionstamp which has not been acknowledged by every
+/// consumer defined with `RETAIN`, if there are any.
+pub(crate) async fn retained_versionstamp(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+) -> Result<Option<u64>> {
+	let mut earliest: Option<u64> = None;
+	for cs in txn.all_db_consumers(ns, db).await?.iter().filter(|cs| cs.retain) {
+		let vs = offset(txn, ns, db, &cs.name).await?.versionstamp;
+		earliest = Some(earliest.map_or(vs, |v| v.min(vs)));
+	}
+	Ok(earliest)
+}
+
+/// Returns the structure of the consumers of a database, along with their
+/// acknowledged offset, as shown by `INFO FOR DB STRUCTURE`.
+pub(crate) async fn structure(txn: &Transaction, ns: NamespaceId, db: DatabaseId) -> Result<Value> {
+	let mut out = Vec::new();
+	for cs in txn.all_db_consumers(ns, db).await?.iter() {
+		let offset = offset(txn, ns, db, &cs.name).await?;
+		let mut value = cs.clone().structure();
+		if let Value::Object(obj) = &mut value {
+			obj.insert("offset".to_string(), offset.structure());
+		}
+		out.push(value);
+	}
+	Ok(out.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::providers::DatabaseProvider;
+	use crate::dbs::Session;
+	use crate::iam::{Level, Role};
+	use crate::kvs::{Datastore, LockType, TransactionType};
+
+	async fn datastore_with_changefeeds() -> Datastore {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE TABLE person CHANGEFEED 1h; DEFINE TABLE pet CHANGEFEED 1h").await;
+		ds
+	}
+
+	async fn retained(ds: &Datastore) -> Option<u64> {
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let db = tx.expect_db_by_name("test", "test").await.unwrap();
+		let vs = retained_versionstamp(&tx, db.namespace_id, db.database_id).await.unwrap();
+		tx.cancel().await.unwrap();
+		vs
+	}
+
+	#[tokio::test]
+	async fn reads_from_acknowledged_offset() {
+		let ds = datastore_with_changefeeds().await;
+		ds.test_query("DEFINE CONSUMER out ON TABLE person").await;
+		ds.test_query("CREATE person:one; CREATE pet:one; CREATE person:two").await;
+		let changes = ds.test_query("SHOW CHANGES FOR CONSUMER out").await.into_json_value();
+		assert_eq!(changes.as_array().unwrap().len(), 2);
+		// Acknowledged changes are not read again
+		let vs = changes[0]["versionstamp"].as_u64().unwrap();
+		ds.test_query(&format!("ACK CONSUMER out AT {vs}")).await;
+		let changes = ds.test_query("SHOW CHANGES FOR CONSUMER out").await.into_json_value();
+		assert_eq!(changes.as_array().unwrap().len(), 1);
+		assert!(changes[0].to_string().contains("person:two"));
+		// The offset never moves backwards
+		ds.test_query("ACK CONSUMER out AT 0").await;
+		let changes = ds.test_query("SHOW CHANGES FOR CONSUMER out").await.into_json_value();
+		assert_eq!(changes.as_array().unwrap().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn viewers_cannot_acknowledge() {
+		let ds = datastore_with_changefeeds().await;
+		ds.test_query("DEFINE CONSUMER out").await;
+		let ses = Session::for_level(Level::Database("test".into(), "test".into()), Role::Viewer);
+		let mut res = ds.execute("ACK CONSUMER out AT 0", &ses, None).await.unwrap();
+		assert!(res.pop().unwrap().result.is_err());
+	}
+
+	#[tokio::test]
+	async fn retained_versionstamp_follows_retaining_consumers() {
+		let ds = datastore_with_changefeeds().await;
+		ds.test_query("DEFINE CONSUMER other").await;
+		assert_eq!(retained(&ds).await, None);
+		ds.test_query("DEFINE CONSUMER out RETAIN").await;
+		let start = retained(&ds).await.unwrap();
+		ds.test_query("CREATE person:one").await;
+		let changes = ds.test_query("SHOW CHANGES FOR CONSUMER out").await.into_json_value();
+		let vs = changes[0]["versionstamp"].as_u64().unwrap();
+		assert!(vs >= start);
+		ds.test_query(&format!("ACK CONSUMER out AT {vs}")).await;
+		assert_eq!(retained(&ds).await, Some(vs + 1));
+		ds.test_query("REMOVE CONSUMER out").await;
+		assert_eq!(retained(&ds).await, None);
+	}
+}
@@ -5,6 +5,7 @@ use chrono::{DateTime, Utc};
 
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::catalog::{DatabaseId, NamespaceId};
+use crate::cf::{consumer, sink};
 use crate::key::change;
 use crate::key::debug::Sprintable;
 use crate::kvs::tasklease::LeaseHandler;
@@ -50,7 +51,19 @@ pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction) -> Result<()> {
 
 			let ts = tx.timestamp().await?;
 			// Calculate the changefeed watermark cutoff time
-			let watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+			let mut watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+			// Keep the changes not yet acknowledged by the retaining consumers,
+			// nor delivered by the sinks
+			let consumers =
+				consumer::retained_versionstamp(tx, db.namespace_id, db.database_id).await?;
+			let sinks = sink::retained_versionstamp(tx, db.namespace_id, db.database_id).await?;
+			if let Some(vs) = consumers.into_iter().chain(sinks).min()
+				&& (vs as u128) < watermark_ts.as_versionstamp()
+			{
+				watermark_ts = ts_impl
+					.create_from_versionstamp(vs as u128)
+					.unwrap_or_else(|| ts_impl.earliest());
+			}
 			// Garbage collect all entries older than the watermark
 			gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
 			// Possibly renew the lease
@@ -1,9 +1,12 @@
+pub(crate) mod consumer;
 pub(crate) mod gc;
 pub(crate) mod mutations;
 pub(crate) mod reader;
+pub(crate) mod sink;
 pub(crate) mod writer;
 
 pub use self::gc::*;
 pub use self::mutations::*;
 pub use self::reader::read;
+pub use self::sink::{Sink, SinkBatch};
 pub use self::writer::Writer;
@@ -0,0 +1,98 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_family = "wasm"))]
+use anyhow::Context;
+use anyhow::{Result, bail};
+use chrono::Utc;
+#[cfg(not(target_family = "wasm"))]
+use tokio::task::spawn_blocking;
+
+use super::{Sink, SinkBatch};
+use crate::cnf::{SINK_FILE_ALLOWLIST, SINK_FILE_MAX_SIZE};
+use crate::err::Error;
+
+/// Appends the changes as newline-delimited JSON to a file, which is rotated
+/// once it grows above `SURREAL_SINK_FILE_MAX_SIZE`. Rotated files are kept
+/// next to the file, suffixed with the time of the rotation. The file is
+/// written on the blocking thread pool, off the async runtime.
+///
+/// The file must be within one of the directories of
+/// `SURREAL_SINK_FILE_ALLOWLIST`, which allows nothing when empty.
+pub(crate) struct FileSink {
+	path: PathBuf,
+}
+
+impl FileSink {
+	pub(crate) fn new(path: PathBuf) -> Self {
+		Self {
+			path,
+		}
+	}
+
+	/// Checks that the file of a sink is within the directories allowed by
+	/// `SURREAL_SINK_FILE_ALLOWLIST`, returning its canonical path. The
+	/// directory of the file must exist, and the file must not be a symlink.
+	pub(crate) fn check_path(path: &Path) -> Result<PathBuf> {
+		let denied = || Error::FileAccessDenied(path.display().to_string());
+		// If the allowlist is empty, nothing is allowed
+		if SINK_FILE_ALLOWLIST.is_empty() {
+			bail!(denied());
+		}
+		let Some(name) = path.file_name() else {
+			bail!(Error::InvalidPath(path.display().to_string()));
+		};
+		let dir = match path.parent() {
+			Some(dir) if !dir.as_os_str().is_empty() => dir,
+			_ => Path::new("."),
+		};
+		let Ok(dir) = fs::canonicalize(dir) else {
+			bail!(denied());
+		};
+		// Check if the directory is within any of the allowed paths
+		if !SINK_FILE_ALLOWLIST.iter().any(|allowed| dir.starts_with(allowed)) {
+			bail!(denied());
+		}
+		// Do not follow a symlink out of the allowed directories
+		let path = dir.join(name);
+		if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_symlink()) {
+			bail!(denied());
+		}
+		Ok(path)
+	}
+
+	fn write(path: &Path, data: &[u8]) -> Result<()> {
+		// The file must be allowed at the time of the write
+		let path = Self::check_path(path)?;
+		// Rotate the file once it is too large
+		if fs::metadata(&path).is_ok_and(|m| m.len() >= *SINK_FILE_MAX_SIZE) {
+			let suffix = Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+			let mut rotated = path.clone().into_os_string();
+			rotated.push(format!(".{suffix}"));
+			fs::rename(&path, rotated)?;
+		}
+		let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+		file.write_all(data)?;
+		file.sync_data()?;
+		Ok(())
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Sink for FileSink {
+	#[cfg(not(target_family = "wasm"))]
+	async fn deliver(&self, batch: &SinkBatch) -> Result<()> {
+		let path = self.path.clone();
+		let data = batch.to_ndjson();
+		spawn_blocking(move || Self::write(&path, data.as_bytes()))
+			.await
+			.context("Sink file write task join error")?
+	}
+
+	#[cfg(target_family = "wasm")]
+	async fn deliver(&self, batch: &SinkBatch) -> Result<()> {
+		Self::write(&self.path, batch.to_ndjson().as_bytes())
+	}
+}
@@ -0,0 +1,491 @@
+//! Changefeed sinks, defined with `DEFINE SINK`, which push the changes of a
+//! database to a webhook, a file, the standard output, or a custom target.
+//!
+//! Each sink keeps a cursor of the next versionstamp to deliver. The cursor
+//! only moves forward once a batch has been delivered, so that a batch which
+//! failed, or whose delivery was interrupted, is delivered again: delivery is
+//! at-least-once, and targets should use the versionstamps of the change sets
+//! to discard duplicates. The changefeed garbage collector keeps the changes
+//! which have not been delivered by every sink of a database.
+mod file;
+mod webhook;
+
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::json;
+
+pub(crate) use self::file::FileSink;
+pub(crate) use self::webhook::WebhookSink;
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, SinkProvider};
+use crate::catalog::{DatabaseId, NamespaceId, SinkDefinition, SinkPosition, SinkTarget};
+use crate::cf::{ChangeSet, DatabaseMutation};
+use crate::cnf::SINK_RETRY_BACKOFF_MS;
+use crate::err::Error;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::statements::show::ShowSince;
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
+use crate::types::PublicValue;
+use crate::val::{Datetime, Value, convert_value_to_public_value};
+
+/// A batch of change sets delivered to a sink.
+#[derive(Clone, Debug)]
+pub struct SinkBatch {
+	/// The name of the sink.
+	pub sink: String,
+	/// The namespace of the changes.
+	pub namespace: String,
+	/// The database of the changes.
+	pub database: String,
+	/// The change sets, in the format returned by `SHOW CHANGES`, ordered by
+	/// versionstamp.
+	pub changes: Vec<PublicValue>,
+}
+
+impl SinkBatch {
+	/// Returns the batch as a single JSON object.
+	pub fn to_json(&self) -> serde_json::Value {
+		json!({
+			"sink": self.sink,
+			"namespace": self.namespace,
+			"database": self.database,
+			"changes": self.changes.iter().cloned().map(PublicValue::into_json_value).collect::<Vec<_>>(),
+		})
+	}
+
+	/// Returns the batch as newline-delimited JSON, with a line per change set.
+	pub fn to_ndjson(&self) -> String {
+		let mut out = String::new();
+		for change in self.changes.iter() {
+			let mut line = json!({
+				"sink": self.sink,
+				"namespace": self.namespace,
+				"database": self.database,
+			});
+			if let (Some(line), serde_json::Value::Object(change)) =
+				(line.as_object_mut(), change.clone().into_json_value())
+			{
+				line.extend(change);
+			}
+			out.push_str(&line.to_string());
+			out.push('\n');
+		}
+		out
+	}
+}
+
+/// A target to which a sink delivers its changes.
+///
+/// Custom targets are registered with [`Datastore::with_sink`], and used by
+/// sinks defined with `TO CUSTOM <name>`. As batches may be delivered more than
+/// once, implementations should be idempotent.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub trait Sink: Send + Sync {
+	/// Delivers a batch of changes, returning an error if it should be retried.
+	async fn deliver(&self, batch: &SinkBatch) -> Result<()>;
+}
+
+/// Writes the changes as newline-delimited JSON to the standard output.
+pub(crate) struct StdoutSink;
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Sink for StdoutSink {
+	async fn deliver(&self, batch: &SinkBatch) -> Result<()> {
+		let mut out = std::io::stdout().lock();
+		out.write_all(batch.to_ndjson().as_bytes())?;
+		out.flush()?;
+		Ok(())
+	}
+}
+
+/// Returns the cursor of a sink which starts with the changes made from now
+/// on.
+pub(crate) async fn initial_position(txn: &Transaction) -> Result<SinkPosition> {
+	Ok(SinkPosition {
+		versionstamp: txn.timestamp().await?.as_versionstamp() as u64,
+		..Default::default()
+	})
+}
+
+/// Returns the earliest versionstamp which has not been delivered by every
+/// sink of a database, if there are any.
+pub(crate) async fn retained_versionstamp(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+) -> Result<Option<u64>> {
+	let mut earliest: Option<u64> = None;
+	for sk in txn.all_db_sinks(ns, db).await?.iter() {
+		let key = crate::key::database::sp::new(ns, db, &sk.name);
+		if let Some(position) = txn.get(&key, None).await? {
+			let vs = position.versionstamp;
+			earliest = Some(earliest.map_or(vs, |v| v.min(vs)));
+		}
+	}
+	Ok(earliest)
+}
+
+/// Returns the structure of the sinks of a database, along with their
+/// delivery cursor, as shown by `INFO FOR DB STRUCTURE`.
+pub(crate) async fn structure(txn: &Transaction, ns: NamespaceId, db: DatabaseId) -> Result<Value> {
+	let mut out = Vec::new();
+	for sk in txn.all_db_sinks(ns, db).await?.iter() {
+		let key = crate::key::database::sp::new(ns, db, &sk.name);
+		let mut value = sk.clone().structure();
+		if let (Value::Object(obj), Some(position)) = (&mut value, txn.get(&key, None).await?) {
+			obj.insert("position".to_string(), position.structure());
+		}
+		out.push(value);
+	}
+	Ok(out.into())
+}
+
+/// A sink to process, along with its namespace and database.
+struct PendingSink {
+	ns: NamespaceId,
+	db: DatabaseId,
+	namespace: String,
+	database: String,
+	sink: Arc<SinkDefinition>,
+}
+
+/// Delivers the changes of the databases to their sinks.
+pub(crate) struct SinkProcessor;
+
+impl SinkProcessor {
+	/// Delivers the next batch of changes of every sink, across all the
+	/// namespaces and databases. Returns the number of change sets delivered.
+	///
+	/// Each sink makes a single delivery attempt per run, so that a failing
+	/// target doesn't hold up the other sinks. The error of a failed delivery
+	/// is recorded in the cursor of the sink, along with the time of the next
+	/// attempt, which backs off exponentially up to the number of retries of
+	/// the sink. Past that, the batch is delivered again on each run.
+	pub(crate) async fn process_sinks(ds: &Datastore, lh: Option<&LeaseHandler>) -> Result<usize> {
+		// Collect the sinks
+		let pending = {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let res = catch!(tx, Self::all_sinks(&tx).await);
+			tx.cancel().await?;
+			res
+		};
+		let mut count = 0;
+		for sk in pending {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			match Self::process(ds, &sk).await {
+				Ok(n) => count += n,
+				Err(e) => error!(
+					"Unexpected error while processing the sink '{}' in {}/{}. Error: {e}",
+					sk.sink.name, sk.namespace, sk.database
+				),
+			}
+		}
+		Ok(count)
+	}
+
+	async fn all_sinks(tx: &Transaction) -> Result<Vec<PendingSink>> {
+		let mut out = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			for db in tx.all_db(ns.namespace_id).await?.iter() {
+				for sk in tx.all_db_sinks(ns.namespace_id, db.database_id).await?.iter() {
+					out.push(PendingSink {
+						ns: ns.namespace_id,
+						db: db.database_id,
+						namespace: ns.name.clone(),
+						database: db.name.clone(),
+						sink: Arc::new(sk.clone()),
+					});
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Delivers the next batch of changes of a sink, and moves its cursor.
+	async fn process(ds: &Datastore, pending: &PendingSink) -> Result<usize> {
+		let PendingSink {
+			ns,
+			db,
+			sink: sk,
+			..
+		} = pending;
+		let key = crate::key::database::sp::new(*ns, *db, &sk.name);
+		// Read the next batch of changes from the cursor
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let Some(mut position) = catch!(tx, tx.get(&key, None).await) else {
+			tx.cancel().await?;
+			return Self::reset_position(ds, pending).await.map(|_| 0);
+		};
+		// Wait for the backoff of a failed delivery to elapse
+		if position.retry_at.as_ref().is_some_and(|at| at.0 > Utc::now()) {
+			tx.cancel().await?;
+			return Ok(0);
+		}
+		let start = ShowSince::Versionstamp(position.versionstamp);
+		let sets = catch!(tx, crate::cf::read(&tx, *ns, *db, None, start, Some(sk.batch)).await);
+		tx.cancel().await?;
+		let Some(last) = sets.last().map(|cs| cs.0) else {
+			return Ok(0);
+		};
+		// Keep the changes of the tables forwarded by the sink
+		let mut changes = Vec::new();
+		for ChangeSet(vs, DatabaseMutation(tbs)) in sets {
+			let tbs: Vec<_> = tbs.into_iter().filter(|tm| sk.includes(&tm.0)).collect();
+			if !tbs.is_empty() {
+				let value = ChangeSet(vs, DatabaseMutation(tbs)).into_value();
+				changes.push(convert_value_to_public_value(value)?);
+			}
+		}
+		let count = changes.len();
+		// Deliver the changes, if any are left
+		let res = match count {
+			0 => Ok(()),
+			_ => {
+				let batch = SinkBatch {
+					sink: sk.name.clone(),
+					namespace: pending.namespace.clone(),
+					database: pending.database.clone(),
+					changes,
+				};
+				Self::deliver(ds, sk, &batch).await
+			}
+		};
+		match res {
+			Ok(()) => {
+				position = SinkPosition {
+					versionstamp: last as u64 + 1,
+					delivered: Some(Datetime::now()),
+					error: None,
+					attempts: 0,
+					retry_at: None,
+				};
+			}
+			Err(e) => {
+				warn!(
+					"Failed to deliver the changes of the sink '{}' in {}/{}: {e}",
+					sk.name, pending.namespace, pending.database
+				);
+				position.error = Some(e.to_string());
+				position.attempts += 1;
+				position.retry_at = if position.attempts <= sk.retry {
+					Self::backoff(position.attempts)
+				} else {
+					None
+				};
+			}
+		}
+		// Store the cursor, unless the sink was removed in the meantime
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		if catch!(tx, tx.get_db_sink(*ns, *db, &sk.name).await).is_none() {
+			tx.cancel().await?;
+			return Ok(0);
+		}
+		catch!(tx, tx.set(&key, &position, None).await);
+		tx.commit().await?;
+		Ok(match position.error {
+			Some(_) => 0,
+			None => count,
+		})
+	}
+
+	/// Delivers a batch to the target of a sink.
+	async fn deliver(ds: &Datastore, sk: &SinkDefinition, batch: &SinkBatch) -> Result<()> {
+		Self::target(ds, sk)?.deliver(batch).await
+	}
+
+	/// Returns the time of the next attempt after a number of failed attempts,
+	/// doubling the delay on each attempt.
+	fn backoff(attempts: u32) -> Option<Datetime> {
+		let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+		let delay = Duration::from_millis(*SINK_RETRY_BACKOFF_MS).saturating_mul(factor);
+		let delay = chrono::Duration::from_std(delay).ok()?;
+		Utc::now().checked_add_signed(delay).map(Datetime::from)
+	}
+
+	/// Stores a new cursor for a sink whose cursor is missing, starting with
+	/// the changes made from now on, rather than replaying the whole
+	/// changefeed.
+	async fn reset_position(ds: &Datastore, pending: &PendingSink) -> Result<()> {
+		warn!(
+			"The cursor of the sink '{}' in {}/{} is missing, delivering the changes made from now on",
+			pending.sink.name, pending.namespace, pending.database
+		);
+		let key = crate::key::database::sp::new(pending.ns, pending.db, &pending.sink.name);
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		if catch!(tx, tx.get_db_sink(pending.ns, pending.db, &pending.sink.name).await).is_none()
+			|| catch!(tx, tx.get(&key, None).await).is_some()
+		{
+			return tx.cancel().await;
+		}
+		let position = catch!(tx, initial_position(&tx).await);
+		catch!(tx, tx.set(&key, &position, None).await);
+		tx.commit().await
+	}
+
+	fn target(ds: &Datastore, sk: &SinkDefinition) -> Result<Arc<dyn Sink>> {
+		Ok(match &sk.target {
+			SinkTarget::Webhook {
+				url,
+				headers,
+			} => Arc::new(WebhookSink::new(ds.setup_ctx()?.freeze(), url.clone(), headers.clone())),
+			SinkTarget::File {
+				path,
+			} => Arc::new(FileSink::new(path.into())),
+			SinkTarget::Stdout => Arc::new(StdoutSink),
+			SinkTarget::Custom {
+				name,
+			} => ds.sink(name).ok_or_else(|| Error::SinkNotRegistered(name.clone()))?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use super::*;
+
+	/// Records the batches delivered to it, or fails every delivery.
+	#[derive(Default)]
+	struct MemorySink {
+		fail: bool,
+		batches: Mutex<Vec<SinkBatch>>,
+	}
+
+	#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+	#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+	impl Sink for MemorySink {
+		async fn deliver(&self, batch: &SinkBatch) -> Result<()> {
+			if self.fail {
+				anyhow::bail!("unreachable");
+			}
+			self.batches.lock().unwrap().push(batch.clone());
+			Ok(())
+		}
+	}
+
+	impl MemorySink {
+		fn changes(&self) -> usize {
+			self.batches.lock().unwrap().iter().map(|b| b.changes.len()).sum()
+		}
+	}
+
+	async fn datastore_with_sink(sink: Arc<MemorySink>) -> Datastore {
+		let ds = Datastore::new_test("memory").await.with_sink("memory", sink);
+		ds.test_query("DEFINE TABLE person CHANGEFEED 1h; DEFINE TABLE pet CHANGEFEED 1h").await;
+		ds
+	}
+
+	async fn position(ds: &Datastore, sk: &str) -> SinkPosition {
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let db = tx.expect_db_by_name("test", "test").await.unwrap();
+		let key = crate::key::database::sp::new(db.namespace_id, db.database_id, sk);
+		let position = tx.get(&key, None).await.unwrap().unwrap();
+		tx.cancel().await.unwrap();
+		position
+	}
+
+	#[tokio::test]
+	async fn delivers_and_advances_cursor() {
+		let sink = Arc::new(MemorySink::default());
+		let ds = datastore_with_sink(sink.clone()).await;
+		ds.test_query("DEFINE SINK out TO CUSTOM 'memory'").await;
+		ds.test_query("CREATE person:one; CREATE person:two").await;
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 2);
+		assert_eq!(sink.changes(), 2);
+		let position = position(&ds, "out").await;
+		assert!(position.delivered.is_some());
+		assert_eq!(position.error, None);
+		// Delivered changes are not delivered again
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 0);
+		assert_eq!(sink.changes(), 2);
+		ds.test_query("CREATE person:three").await;
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 1);
+		assert_eq!(sink.changes(), 3);
+	}
+
+	#[tokio::test]
+	async fn failed_deliveries_keep_cursor() {
+		let sink = Arc::new(MemorySink {
+			fail: true,
+			..Default::default()
+		});
+		let ds = datastore_with_sink(sink).await;
+		ds.test_query("DEFINE SINK out TO CUSTOM 'memory' RETRY 0").await;
+		let start = position(&ds, "out").await.versionstamp;
+		ds.test_query("CREATE person:one").await;
+		for attempts in 1..=2 {
+			assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 0);
+			let position = position(&ds, "out").await;
+			assert_eq!(position.versionstamp, start);
+			assert_eq!(position.attempts, attempts);
+			assert!(position.error.as_deref().is_some_and(|e| e.contains("unreachable")));
+		}
+	}
+
+	#[tokio::test]
+	async fn failed_deliveries_back_off() {
+		let sink = Arc::new(MemorySink {
+			fail: true,
+			..Default::default()
+		});
+		let ds = datastore_with_sink(sink).await;
+		ds.test_query("DEFINE SINK out TO CUSTOM 'memory' RETRY 3").await;
+		ds.test_query("CREATE person:one").await;
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 0);
+		let first = position(&ds, "out").await;
+		assert_eq!(first.attempts, 1);
+		assert!(first.retry_at.is_some());
+		// The delivery is not attempted again before the backoff elapsed
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 0);
+		assert_eq!(position(&ds, "out").await.attempts, 1);
+	}
+
+	#[tokio::test]
+	async fn undelivered_changes_are_retained() {
+		let sink = Arc::new(MemorySink::default());
+		let ds = datastore_with_sink(sink).await;
+		ds.test_query("DEFINE SINK out TO CUSTOM 'memory'").await;
+		let start = position(&ds, "out").await.versionstamp;
+		ds.test_query("CREATE person:one").await;
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let db = tx.expect_db_by_name("test", "test").await.unwrap();
+		let retained = retained_versionstamp(&tx, db.namespace_id, db.database_id).await.unwrap();
+		tx.cancel().await.unwrap();
+		assert_eq!(retained, Some(start));
+	}
+
+	#[tokio::test]
+	async fn filters_tables() {
+		let sink = Arc::new(MemorySink::default());
+		let ds = datastore_with_sink(sink.clone()).await;
+		ds.test_query("DEFINE SINK out ON TABLE pet TO CUSTOM 'memory'").await;
+		ds.test_query("CREATE person:one; CREATE pet:one; CREATE person:two").await;
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 1);
+		let batches = sink.batches.lock().unwrap();
+		assert_eq!(batches.len(), 1);
+		assert_eq!(batches[0].sink, "out");
+		assert!(batches[0].changes[0].clone().into_json_value().to_string().contains("pet:one"));
+	}
+
+	#[tokio::test]
+	async fn unregistered_custom_sinks_fail() {
+		let ds = datastore_with_sink(Arc::new(MemorySink::default())).await;
+		ds.test_query("DEFINE SINK out TO CUSTOM 'missing' RETRY 0").await;
+		ds.test_query("CREATE person:one").await;
+		assert_eq!(SinkProcessor::process_sinks(&ds, None).await.unwrap(), 0);
+		let position = position(&ds, "out").await;
+		assert!(position.error.as_deref().is_some_and(|e| e.contains("not registered")));
+	}
+}
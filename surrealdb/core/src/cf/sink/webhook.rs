@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use super::{Sink, SinkBatch};
+use crate::ctx::FrozenContext;
+#[cfg(feature = "http")]
+use crate::sql::expression::convert_public_value_to_internal;
+#[cfg(feature = "http")]
+use crate::val::{Object, Value};
+
+/// Posts the changes as a JSON object to an HTTP endpoint, subject to the
+/// network capabilities of the datastore.
+pub(crate) struct WebhookSink {
+	#[cfg_attr(not(feature = "http"), expect(dead_code))]
+	ctx: FrozenContext,
+	#[cfg_attr(not(feature = "http"), expect(dead_code))]
+	url: String,
+	#[cfg_attr(not(feature = "http"), expect(dead_code))]
+	headers: BTreeMap<String, String>,
+}
+
+impl WebhookSink {
+	pub(crate) fn new(ctx: FrozenContext, url: String, headers: BTreeMap<String, String>) -> Self {
+		Self {
+			ctx,
+			url,
+			headers,
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Sink for WebhookSink {
+	#[cfg(feature = "http")]
+	async fn deliver(&self, batch: &SinkBatch) -> Result<()> {
+		let changes = batch.changes.iter().cloned().map(convert_public_value_to_internal);
+		let body = Value::from(map! {
+			"sink".to_string() => Value::from(batch.sink.clone()),
+			"namespace".to_string() => Value::from(batch.namespace.clone()),
+			"database".to_string() => Value::from(batch.database.clone()),
+			"changes".to_string() => Value::from(changes.collect::<Vec<_>>()),
+		});
+		let headers = Object::from(self.headers.clone());
+		crate::fnc::util::http::post(&self.ctx, self.url.clone(), body, headers).await?;
+		Ok(())
+	}
+
+	#[cfg(not(feature = "http"))]
+	async fn deliver(&self, _batch: &SinkBatch) -> Result<()> {
+		Err(crate::err::Error::HttpDisabled.into())
+	}
+}
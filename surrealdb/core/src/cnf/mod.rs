@@ -72,6 +72,11 @@ pub static TRANSACTION_CACHE_SIZE: LazyLock<usize> =
 pub static DATASTORE_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_DATASTORE_CACHE_SIZE", usize, 1_000);
 
+/// Specifies the number of query results which can be cached across
+/// transactions (default: 1,000)
+pub static QUERY_RESULT_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_QUERY_RESULT_CACHE_SIZE", usize, 1_000);
+
 /// Specifies the number of surrealism modules which can be cached across transactions
 /// (default: 100)
 pub static SURREALISM_CACHE_SIZE: LazyLock<usize> =
@@ -87,6 +92,36 @@ pub static NORMAL_FETCH_SIZE: LazyLock<u32> =
 pub static EXPORT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_EXPORT_BATCH_SIZE", u32, 1000);
 
+/// The maximum number of bytes which are buffered for a row group of a Parquet
+/// export, before the row group is written out (default: 16 MiB)
+pub static EXPORT_PARQUET_ROW_GROUP_BYTES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_EXPORT_PARQUET_ROW_GROUP_BYTES", usize, 16 * 1024 * 1024);
+
+/// The maximum number of keys that should be written at once when restoring a backup
+/// (default: 1000)
+pub static RESTORE_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_RESTORE_BATCH_SIZE", usize, 1000);
+
+/// The maximum number of records that should be written in a single transaction
+/// when bulk importing records (default: 10,000)
+pub static IMPORT_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_IMPORT_BATCH_SIZE", usize, 10_000);
+
+/// The maximum number of audit log events which are buffered before they are
+/// written to the datastore in a single transaction (default: 1000)
+pub static AUDIT_LOG_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_AUDIT_LOG_BATCH_SIZE", usize, 1000);
+
+/// The maximum number of rejected rows which are kept in the report of a bulk
+/// import, the others are only counted (default: 1,000)
+pub static IMPORT_MAX_REJECTED: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_IMPORT_MAX_REJECTED", usize, 1_000);
+
+/// The maximum size of an Apache Parquet file which is bulk imported, as the
+/// whole file is buffered in memory before it is decoded (default: 1 GiB)
+pub static IMPORT_PARQUET_MAX_SIZE: LazyLock<u64> =
+	lazy_env_parse!(bytes, "SURREAL_IMPORT_PARQUET_MAX_SIZE", u64, 1024 * 1024 * 1024);
+
 /// The maximum number of keys that should be scanned at once for count queries
 /// (default: 50,000)
 pub static COUNT_BATCH_SIZE: LazyLock<u32> =
@@ -97,6 +132,29 @@ pub static COUNT_BATCH_SIZE: LazyLock<u32> =
 pub static INDEXING_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_INDEXING_BATCH_SIZE", u32, 250);
 
+/// The maximum number of runs kept in the history of a scheduled job
+/// (default: 10)
+pub static JOB_HISTORY_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_JOB_HISTORY_SIZE", usize, 10);
+
+/// The size above which the file written by a changefeed sink is rotated
+/// (default: 64 MiB)
+pub static SINK_FILE_MAX_SIZE: LazyLock<u64> =
+	lazy_env_parse!(bytes, "SURREAL_SINK_FILE_MAX_SIZE", u64, 64 * 1024 * 1024);
+
+/// Specifies a list of directories in which changefeed sinks can write files.
+/// Nothing is allowed when empty (default: empty)
+pub static SINK_FILE_ALLOWLIST: LazyLock<Vec<PathBuf>> = LazyLock::new(|| {
+	std::env::var("SURREAL_SINK_FILE_ALLOWLIST")
+		.map(|input| extract_allowed_paths(&input, true, "sink file"))
+		.unwrap_or_default()
+});
+
+/// The delay in milliseconds before the first retry of a failed changefeed
+/// sink delivery, doubled on each subsequent retry (default: 100)
+pub static SINK_RETRY_BACKOFF_MS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SINK_RETRY_BACKOFF_MS", u64, 100);
+
 /// The number of batches each operator buffers ahead of downstream demand.
 /// Set to 0 to disable operator-level pipeline buffering.
 /// (default: 2)
@@ -154,6 +212,16 @@ pub static INSECURE_FORWARD_ACCESS_ERRORS: LazyLock<bool> =
 pub static EXTERNAL_SORTING_BUFFER_LIMIT: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_EXTERNAL_SORTING_BUFFER_LIMIT", usize, 50_000);
 
+/// The maximum number of terms of a full-text index compared to a prefix or
+/// fuzzy term of a query, above which the query fails (default: 1000)
+pub static FULLTEXT_MAX_EXPANSION_CANDIDATES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_FULLTEXT_MAX_EXPANSION_CANDIDATES", usize, 1000);
+
+/// The number of leading characters of a fuzzy full-text term which must match
+/// exactly (default: 1)
+pub static FULLTEXT_FUZZY_PREFIX_LENGTH: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_FULLTEXT_FUZZY_PREFIX_LENGTH", usize, 1);
+
 /// Used to limit allocation for builtin functions. Default: 2^20 (1 MiB),
 /// can be as large as 28 (2^28, 256 MiB)
 pub static GENERATION_ALLOCATION_LIMIT: LazyLock<usize> = LazyLock::new(|| {
@@ -190,6 +258,47 @@ pub static GLOBAL_BUCKET: LazyLock<Option<String>> =
 pub static GLOBAL_BUCKET_ENFORCED: LazyLock<bool> =
 	lazy_env_parse!("SURREAL_GLOBAL_BUCKET_ENFORCED", bool, false);
 
+/// Whether S3 buckets read their credentials and settings from the `AWS_*`
+/// environment variables of the server (default: false)
+pub static BUCKET_S3_FROM_ENV: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_BUCKET_S3_FROM_ENV", bool, false);
+
+/// The path to the file holding the master key, which wraps the key material
+/// of the encryption keys defined with `DEFINE KEY` (default: None)
+pub static KEY_MASTER_KEY_FILE: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_KEY_MASTER_KEY_FILE", Option<String>);
+
+/// Specifies the number of unwrapped encryption keys which can be cached in the
+/// engine (default: 1000)
+pub static KEY_CIPHER_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_KEY_CIPHER_CACHE_SIZE", usize, 1_000);
+
+/// How long after it was started a multipart file upload is discarded, unless
+/// it was committed, in seconds (default: 3600)
+pub static FILE_UPLOAD_TIMEOUT_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_FILE_UPLOAD_TIMEOUT_SECS", u64, 3600);
+
+/// The maximum size of a file which is uploaded to a bucket in bytes
+/// (default: 1 GiB)
+pub static FILE_UPLOAD_MAX_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_FILE_UPLOAD_MAX_SIZE", u64, 1024 * 1024 * 1024);
+
+/// The maximum number of multipart file uploads which a user can have in
+/// progress at once (default: 10)
+pub static FILE_UPLOAD_MAX_SESSIONS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_FILE_UPLOAD_MAX_SESSIONS", u64, 10);
+
+/// The maximum number of chunks which can be written to a single multipart
+/// file upload (default: 10000)
+pub static FILE_UPLOAD_MAX_PARTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_FILE_UPLOAD_MAX_PARTS", u32, 10_000);
+
+/// The maximum size of a file which is buffered in memory while it is written
+/// to a bucket backend which does not support streaming writes in bytes
+/// (default: 100 MiB)
+pub static FILE_UPLOAD_MAX_BUFFER_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_FILE_UPLOAD_MAX_BUFFER_SIZE", u64, 100 * 1024 * 1024);
+
 /// Specify the USER-AGENT string used by HTTP requests
 pub static SURREALDB_USER_AGENT: LazyLock<String> =
 	LazyLock::new(|| std::env::var("SURREAL_USER_AGENT").unwrap_or("SurrealDB".to_string()));
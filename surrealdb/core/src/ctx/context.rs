@@ -12,6 +12,8 @@ use async_channel::Sender;
 #[cfg(feature = "surrealism")]
 use surrealism_runtime::controller::Runtime;
 #[cfg(feature = "surrealism")]
+use surrealism_runtime::limits::SurrealismLimits;
+#[cfg(feature = "surrealism")]
 use surrealism_runtime::package::SurrealismPackage;
 #[cfg(feature = "http")]
 use url::Url;
@@ -37,6 +39,7 @@ use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
 use crate::idx::trees::store::IndexStores;
 use crate::kvs::Transaction;
+use crate::kvs::audit::AuditLog;
 use crate::kvs::cache::ds::DatastoreCache;
 use crate::kvs::index::IndexBuilder;
 use crate::kvs::sequences::Sequences;
@@ -59,6 +62,9 @@ pub struct Context {
 	// that exceed a given duration threshold. This configuration is propagated
 	// from the datastore into the context for the lifetime of a request.
 	slow_log: Option<SlowLog>,
+	// An optional audit log, which is enabled when the statements which
+	// change the schema or the permissions are audited.
+	audit_log: Option<AuditLog>,
 	// Whether or not this context is cancelled.
 	cancelled: Arc<AtomicBool>,
 	// A collection of read only values stored in this context.
@@ -138,6 +144,7 @@ impl Context {
 			parent: None,
 			deadline: None,
 			slow_log: None,
+			audit_log: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
 			query_planner: None,
@@ -169,6 +176,7 @@ impl Context {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			slow_log: parent.slow_log.clone(),
+			audit_log: parent.audit_log.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
@@ -203,6 +211,7 @@ impl Context {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			slow_log: parent.slow_log.clone(),
+			audit_log: parent.audit_log.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
@@ -244,6 +253,7 @@ impl Context {
 			values: from.collect_values(HashMap::default()),
 			deadline: from.deadline,
 			slow_log: from.slow_log.clone(),
+			audit_log: from.audit_log.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
 			query_planner: from.query_planner.clone(),
@@ -278,6 +288,7 @@ impl Context {
 			values: HashMap::default(),
 			deadline: None,
 			slow_log: from.slow_log.clone(),
+			audit_log: from.audit_log.clone(),
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
 			query_planner: from.query_planner.clone(),
@@ -309,6 +320,7 @@ impl Context {
 	pub(crate) fn from_ds(
 		time_out: Option<Duration>,
 		slow_log: Option<SlowLog>,
+		audit_log: Option<AuditLog>,
 		capabilities: Arc<Capabilities>,
 		index_stores: IndexStores,
 		index_builder: IndexBuilder,
@@ -324,6 +336,7 @@ impl Context {
 			parent: None,
 			deadline: None,
 			slow_log,
+			audit_log,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
 			query_planner: None,
@@ -533,6 +546,11 @@ impl Context {
 		self.slow_log.as_ref()
 	}
 
+	/// Get the audit log for this context, if any
+	pub(crate) fn audit_log(&self) -> Option<&AuditLog> {
+		self.audit_log.as_ref()
+	}
+
 	pub(crate) fn notifications(&self) -> Option<Sender<PublicNotification>> {
 		self.notifications.clone()
 	}
@@ -721,7 +739,7 @@ impl Context {
 	}
 
 	/// Get a 'static view into the cancellation status.
-	#[cfg(feature = "scripting")]
+	#[cfg(any(feature = "scripting", feature = "surrealism"))]
 	pub(crate) fn cancellation(&self) -> crate::ctx::cancellation::Cancellation {
 		crate::ctx::cancellation::Cancellation::new(
 			self.deadline.map(|(deadline, _)| deadline),
@@ -946,10 +964,13 @@ impl Context {
 		self.surrealism_cache.as_ref().map(|sc| sc.clone())
 	}
 
+	/// Returns the compiled runtime of a module, for controllers which run
+	/// within the given limits of its definition.
 	#[cfg(feature = "surrealism")]
 	pub(crate) async fn get_surrealism_runtime(
 		&self,
 		lookup: SurrealismCacheLookup<'_>,
+		limits: &SurrealismLimits,
 	) -> Result<Arc<Runtime>> {
 		if !self.get_capabilities().allows_experimental(&ExperimentalTarget::Surrealism) {
 			bail!(
@@ -962,7 +983,7 @@ impl Context {
 		};
 
 		cache
-			.get_or_insert_with(&lookup, async || {
+			.get_or_insert_with(&lookup, limits.fuel.is_some(), async || {
 				let SurrealismCacheLookup::File(ns, db, bucket, key) = lookup else {
 					bail!("silo lookups are not supported yet");
 				};
@@ -979,7 +1000,7 @@ impl Context {
 				};
 
 				let package = SurrealismPackage::from_reader(std::io::Cursor::new(surli))?;
-				let runtime = Arc::new(Runtime::new(package)?);
+				let runtime = Arc::new(Runtime::with_limits(package, limits)?);
 
 				Ok(runtime)
 			})
@@ -378,6 +378,7 @@ pub enum RouteTarget {
 	Ml,
 	GraphQL,
 	Api,
+	Files,
 }
 
 // impl display
@@ -397,6 +398,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Ml => write!(f, "ml"),
 			RouteTarget::GraphQL => write!(f, "graphql"),
 			RouteTarget::Api => write!(f, "api"),
+			RouteTarget::Files => write!(f, "files"),
 		}
 	}
 }
@@ -435,6 +437,7 @@ impl std::str::FromStr for RouteTarget {
 			"ml" => Ok(RouteTarget::Ml),
 			"graphql" => Ok(RouteTarget::GraphQL),
 			"api" => Ok(RouteTarget::Api),
+			"files" => Ok(RouteTarget::Files),
 			_ => Err(ParseRouteTargetError),
 		}
 	}
@@ -27,6 +27,7 @@ use crate::expr::plan::LogicalPlan;
 use crate::expr::statements::{OptionStatement, UseStatement};
 use crate::expr::{Base, ControlFlow, Expr, FlowResult, TopLevelExpr};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::audit;
 use crate::kvs::slowlog::SlowLogVisit;
 use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
 use crate::rpc::types_error_from_anyhow;
@@ -523,9 +524,23 @@ impl Executor {
 				ctx_mut!().set_transaction(txn);
 				s.compute(&self.ctx, &self.opt, None).await.map_err(ControlFlow::Err)
 			}
+			TopLevelExpr::Ack(s) => {
+				ctx_mut!().set_transaction(txn);
+				self.stack
+					.enter(|stk| s.compute(stk, &self.ctx, &self.opt, None))
+					.finish()
+					.await
+					.map_err(ControlFlow::Err)
+			}
 			TopLevelExpr::Access(s) => {
 				ctx_mut!().set_transaction(txn);
-				self.stack.enter(|stk| s.compute(stk, &self.ctx, &self.opt, None)).finish().await
+				let res = self
+					.stack
+					.enter(|stk| s.compute(stk, &self.ctx, &self.opt, None))
+					.finish()
+					.await;
+				audit::audit_access(&self.ctx, &self.opt, &s, &res);
+				res
 			}
 			// Process all other normal statements
 			TopLevelExpr::Expr(e) => {
@@ -606,7 +621,29 @@ impl Executor {
 		} else {
 			TransactionType::Write
 		};
+		// Check if the result of the statement can be cached, before the
+		// transaction starts so that any write committed meanwhile is seen
+		let cacheable = match &plan {
+			TopLevelExpr::Expr(Expr::Select(stm)) => {
+				kvs.result_cache().prepare(&self.ctx, &self.opt, stm)
+			}
+			_ => None,
+		};
 		let txn = Arc::new(kvs.transaction(transaction_type, LockType::Optimistic).await?);
+		// Serve the result from the cache if it is still valid
+		if let Some(cacheable) = &cacheable {
+			match kvs.result_cache().get(&txn, cacheable).await {
+				Ok(Some(value)) => {
+					let _ = txn.cancel().await;
+					return Ok(value);
+				}
+				Ok(None) => {}
+				Err(e) => {
+					let _ = txn.cancel().await;
+					return Err(e);
+				}
+			}
+		}
 		let receiver = self.prepare_broker();
 
 		let exec_result = match kvs.transaction_timeout() {
@@ -633,6 +670,12 @@ impl Executor {
 				// So cancel them instead. This is fine since a non-writable transaction
 				// has nothing to commit anyway.
 				if let TransactionType::Read = transaction_type {
+					// Keep the result for the next executions of the statement
+					if let Some(cacheable) = cacheable
+						&& let Err(e) = kvs.result_cache().insert(&txn, cacheable, &value).await
+					{
+						tracing::warn!("Failed to cache the result of a statement: {e}");
+					}
 					let _ = txn.cancel().await;
 					return Ok(value);
 				}
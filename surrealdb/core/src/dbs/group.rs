@@ -5,7 +5,9 @@ use anyhow::Result;
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 
-use crate::catalog::aggregation::{self, AggregateFields, AggregationAnalysis, AggregationStat};
+use crate::catalog::aggregation::{
+	self, AggregateFields, AggregationAnalysis, AggregationStat, CustomAggregates,
+};
 use crate::ctx::FrozenContext;
 use crate::dbs::plan::Explanation;
 use crate::dbs::store::MemoryCollector;
@@ -27,6 +29,8 @@ use crate::val::{Number, TryFloatDiv, Value};
 #[derive(Debug)]
 pub struct GroupCollector {
 	analysis: AggregationAnalysis,
+	/// The definitions of the user-defined aggregates used by the analysis.
+	custom: CustomAggregates,
 	omit: Vec<Idiom>,
 
 	/// buffers reused during pushing
@@ -38,7 +42,12 @@ pub struct GroupCollector {
 }
 
 impl GroupCollector {
-	pub fn new(stm: &Statement<'_>) -> Result<Self> {
+	pub async fn new(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<Self> {
 		let Some(fields) = stm.expr() else {
 			fail!("Tried to group a statement without a selector");
 		};
@@ -46,11 +55,13 @@ impl GroupCollector {
 			fail!("Tried to group a statement without a group");
 		};
 
-		let analysis = AggregationAnalysis::analyze_fields_groups(fields, groups, false)?;
+		let custom = aggregation::custom_aggregates(stk, ctx, opt, fields).await?;
+		let analysis = AggregationAnalysis::analyze_fields_groups(fields, groups, false, &custom)?;
 		let omit = stm.omit().to_vec();
 
 		Ok(GroupCollector {
 			analysis,
+			custom,
 			omit,
 
 			exprs_buffer: Vec::new(),
@@ -106,6 +117,14 @@ impl GroupCollector {
 					aggregation::Aggregation::DatetimeMax(x) => format!("DatetimeMax(expr{x})"),
 					aggregation::Aggregation::DatetimeMin(x) => format!("DatetimeMin(expr{x})"),
 					aggregation::Aggregation::Accumulate(x) => format!("Accumulate(expr{x})"),
+					aggregation::Aggregation::Custom {
+						name,
+						args,
+					} => {
+						let args =
+							args.iter().map(|x| format!("expr{x}")).collect::<Vec<_>>().join(", ");
+						format!("Custom(fn::{name}, {args})")
+					}
 				};
 				(format!("_a{idx}"), Value::from(res))
 			})
@@ -169,6 +188,15 @@ impl GroupCollector {
 			}
 
 			aggregation::add_to_aggregation_stats(&self.exprs_buffer, aggragates)?;
+			aggregation::accumulate_custom_aggregation_stats(
+				stk,
+				ctx,
+				opt,
+				&self.custom,
+				&self.exprs_buffer,
+				aggragates,
+			)
+			.await?;
 		}
 
 		Ok(())
@@ -233,6 +261,18 @@ impl GroupCollector {
 						values,
 						..
 					} => values.into(),
+					AggregationStat::Custom {
+						name,
+						state,
+						count,
+						..
+					} => {
+						let Some(def) = self.custom.get(&name) else {
+							fail!("The definition of the aggregate `fn::{name}` was not fetched");
+						};
+						aggregation::finalize_custom_aggregation(stk, ctx, opt, def, state, count)
+							.await?
+					}
 					AggregationStat::StdDev {
 						sum,
 						sum_of_squares,
@@ -692,13 +692,7 @@ impl Iterator {
 		// Process the query START clause
 		self.setup_start(stk, &cancel_ctx, opt, stm).await?;
 		// Prepare the results with possible optimisations on groups
-		self.results = self.results.prepare(
-			#[cfg(storage)]
-			ctx,
-			stm,
-			self.start,
-			self.limit,
-		)?;
+		self.results = self.results.prepare(stk, ctx, opt, stm, self.start, self.limit).await?;
 
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or
 		// without FULL
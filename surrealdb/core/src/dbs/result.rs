@@ -27,15 +27,17 @@ pub(super) enum Results {
 }
 
 impl Results {
-	pub(super) fn prepare(
+	pub(super) async fn prepare(
 		&mut self,
-		#[cfg(storage)] ctx: &FrozenContext,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
 		stm: &Statement<'_>,
 		start: Option<u32>,
 		limit: Option<u32>,
 	) -> Result<Self> {
 		if stm.expr().is_some() && stm.group().is_some() {
-			return Ok(Self::Groups(GroupCollector::new(stm)?));
+			return Ok(Self::Groups(GroupCollector::new(stk, ctx, opt, stm).await?));
 		}
 		#[cfg(storage)]
 		if stm.tempfiles()
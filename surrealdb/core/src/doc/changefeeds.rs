@@ -33,13 +33,18 @@ impl Document {
 		if let Some(cf) = dbcf.or(tbcf) {
 			// Create the changefeed entry
 			if let Some(id) = &self.id {
+				// Encrypted fields are never stored in plaintext
+				let mut initial = self.initial.doc.clone();
+				self.encrypt_fields(ctx, opt, &mut initial).await?;
+				let mut current = self.current.doc.clone();
+				self.encrypt_fields(ctx, opt, &mut current).await?;
 				ctx.tx().changefeed_buffer_record_change(
 					ns,
 					db,
 					&tbv.name,
 					id.as_ref(),
-					self.initial.doc.clone(),
-					self.current.doc.clone(),
+					initial,
+					current,
 					cf.store_diff,
 				);
 			}
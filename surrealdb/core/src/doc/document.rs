@@ -450,45 +450,8 @@ impl Document {
 		opt: &Options,
 		full: &CursorDoc,
 	) -> Result<CursorDoc> {
-		// The document to be reduced
-		let mut reduced = full.doc.clone();
-
 		let table_fields = self.fd(ctx, opt).await?;
-
-		// Loop over each field in document
-		for fd in table_fields.iter() {
-			// Limit auth
-			let opt = AuthLimit::try_from(&fd.auth_limit)?.limit_opt(opt);
-			// Loop over each field in document
-			for k in reduced.as_ref().each(&fd.name).iter() {
-				// Process the field permissions
-				match &fd.select_permission {
-					Permission::Full => (),
-					Permission::None => reduced.to_mut().cut(k),
-					Permission::Specific(e) => {
-						// Disable permissions
-						let opt = &opt.new_with_perms(false);
-						// Get the initial value
-						let val = Arc::new(full.doc.as_ref().pick(k));
-						// Configure the context
-						let mut ctx = Context::new(ctx);
-						ctx.add_value("value", val);
-						let ctx = ctx.freeze();
-						// Process the PERMISSION clause
-						if !stk
-							.run(|stk| e.compute(stk, &ctx, opt, Some(full)))
-							.await
-							.catch_return()?
-							.is_truthy()
-						{
-							reduced.to_mut().cut(k);
-						}
-					}
-				}
-			}
-		}
-		// Ok
-		Ok(CursorDoc::new(full.rid.clone(), full.ir.clone(), reduced))
+		reduce_fields(stk, ctx, opt, &table_fields, full).await
 	}
 
 	/// Retrieve the record id for this document
@@ -707,3 +670,51 @@ impl Document {
 		}
 	}
 }
+
+/// Reduces a document to only the fields which the
+/// current session is permitted to select, based on
+/// the `PERMISSIONS FOR select` clause of each field.
+pub(crate) async fn reduce_fields(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	fields: &[FieldDefinition],
+	full: &CursorDoc,
+) -> Result<CursorDoc> {
+	// The document to be reduced
+	let mut reduced = full.doc.clone();
+	// Loop over each field in document
+	for fd in fields.iter() {
+		// Limit auth
+		let opt = AuthLimit::try_from(&fd.auth_limit)?.limit_opt(opt);
+		// Loop over each field in document
+		for k in reduced.as_ref().each(&fd.name).iter() {
+			// Process the field permissions
+			match &fd.select_permission {
+				Permission::Full => (),
+				Permission::None => reduced.to_mut().cut(k),
+				Permission::Specific(e) => {
+					// Disable permissions
+					let opt = &opt.new_with_perms(false);
+					// Get the initial value
+					let val = Arc::new(full.doc.as_ref().pick(k));
+					// Configure the context
+					let mut ctx = Context::new(ctx);
+					ctx.add_value("value", val);
+					let ctx = ctx.freeze();
+					// Process the PERMISSION clause
+					if !stk
+						.run(|stk| e.compute(stk, &ctx, opt, Some(full)))
+						.await
+						.catch_return()?
+						.is_truthy()
+					{
+						reduced.to_mut().cut(k);
+					}
+				}
+			}
+		}
+	}
+	// Ok
+	Ok(CursorDoc::new(full.rid.clone(), full.ir.clone(), reduced))
+}
@@ -0,0 +1,389 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use reblessive::TreeStack;
+use reblessive::tree::Stk;
+use revision::revisioned;
+
+use crate::catalog::providers::{DatabaseProvider, TableProvider};
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::FrozenContext;
+use crate::dbs::{Options, Session};
+use crate::doc::{DefaultBroker, Document};
+use crate::expr::statements::DeleteStatement;
+use crate::expr::{Expr, Literal};
+use crate::iam::{Level, Role};
+use crate::key::record::RecordKey;
+use crate::key::root::eb::ExpiryBackfill;
+use crate::key::root::ex::ExpiryQueue;
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{
+	Datastore, KVValue, Key, LockType, Transaction, TransactionType, Val, impl_kv_value_revisioned,
+};
+use crate::val::{Datetime, RecordId, RecordIdKey, TableName};
+
+/// The delay after which an entry of the expiry queue which failed to be
+/// processed is retried
+const EXPIRY_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+impl Document {
+	/// Schedules the expiry of the record, if the table is defined with a
+	/// `TTL`. The record expires once the `TTL` has elapsed since it was
+	/// last written, so any previously queued expiry is replaced.
+	///
+	/// The records which exist when the `TTL` of the table is defined or
+	/// changed are scheduled by [`store_table_expiries`].
+	pub(super) async fn store_record_expiry(
+		&self,
+		ctx: &FrozenContext,
+		opt: &Options,
+	) -> Result<()> {
+		// Check if the records of the table expire
+		let Some(ttl) = self.tb().await?.ttl else {
+			return Ok(());
+		};
+		// Get the record id
+		let rid = self.id()?;
+		// Get the namespace / database
+		let (ns_name, db_name) = opt.ns_db()?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Queue the new expiry of the record
+		let ts = expiry_nanos(ttl)?;
+		queue_expiry(&txn, ts, (ns_name, db_name), (ns, db), &rid.table, &rid.key).await
+	}
+
+	/// Removes the expiry of a deleted record from the expiry queue.
+	pub(super) async fn purge_record_expiry(
+		&self,
+		ctx: &FrozenContext,
+		opt: &Options,
+		rid: &RecordId,
+	) -> Result<()> {
+		// Check if the records of the table expire
+		if self.tb().await?.ttl.is_none() {
+			return Ok(());
+		}
+		// Get the namespace / database
+		let (ns_name, db_name) = opt.ns_db()?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Purge the expiry of the record
+		let key = crate::key::table::ex::new(ns, db, &rid.table, &rid.key);
+		if let Some(ts) = txn.get(&key, None).await? {
+			let eq = ExpiryQueue::new(ts, ns_name, db_name, &rid.table, rid.key.clone());
+			txn.del(&eq).await?;
+			txn.del(&key).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+}
+
+/// Returns the current time, in nanoseconds since the UNIX epoch, as used by
+/// the expiry queue.
+fn now_nanos() -> Result<u64> {
+	Datetime::now().to_version_stamp()
+}
+
+/// Returns the time at which a record written now expires.
+fn expiry_nanos(ttl: Duration) -> Result<u64> {
+	Ok(now_nanos()?.saturating_add(u64::try_from(ttl.as_nanos()).unwrap_or(u64::MAX)))
+}
+
+/// Queues the expiry of a record, replacing its previously queued expiry.
+async fn queue_expiry(
+	txn: &Transaction,
+	ts: u64,
+	(ns_name, db_name): (&str, &str),
+	(ns, db): (NamespaceId, DatabaseId),
+	tb: &TableName,
+	id: &RecordIdKey,
+) -> Result<()> {
+	// Remove the previous entry from the expiry queue
+	let key = crate::key::table::ex::new(ns, db, tb, id);
+	if let Some(ts) = txn.get(&key, None).await? {
+		let eq = ExpiryQueue::new(ts, ns_name, db_name, tb, id.clone());
+		txn.del(&eq).await?;
+	}
+	// Queue the new expiry of the record
+	let eq = ExpiryQueue::new(ts, ns_name, db_name, tb, id.clone());
+	txn.set(&eq, &(), None).await?;
+	txn.set(&key, &ts, None).await
+}
+
+/// Schedules the expiry of the existing records of a table, when its `TTL` is
+/// defined or changed. Each record expires once the `TTL` has elapsed since
+/// then, replacing any previously queued expiry, as the time at which the
+/// records were last written is not known.
+///
+/// The records are not read within the transaction which defines the `TTL`.
+/// Instead, the table is marked for backfilling, and the record expiry task
+/// queues the expiry of its records in batches
+/// ([`RecordExpiry::process_next_backfill_batch`]). Any previous backfill of
+/// the table is restarted.
+pub(crate) async fn store_table_expiries(
+	txn: &Transaction,
+	(ns_name, db_name): (&str, &str),
+	(ns, db): (NamespaceId, DatabaseId),
+	tb: &TableName,
+	ttl: Duration,
+) -> Result<()> {
+	let state = ExpiryBackfillState {
+		ts: expiry_nanos(ttl)?,
+		next: crate::key::record::prefix(ns, db, tb)?,
+	};
+	txn.set(&ExpiryBackfill::new(ns_name, db_name, tb), &state, None).await
+}
+
+/// The progress of the backfill of the expiry of the records of a table.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ExpiryBackfillState {
+	/// The time at which the existing records expire, in nanoseconds since the
+	/// UNIX epoch
+	ts: u64,
+	/// The key of the record from which the backfill continues
+	next: Vec<u8>,
+}
+impl_kv_value_revisioned!(ExpiryBackfillState);
+
+/// Deletes the records whose `TTL` has elapsed.
+pub(crate) struct RecordExpiry;
+
+impl RecordExpiry {
+	/// Process a single batch of due entries of the expiry queue.
+	/// Returns the number of entries which have been removed from the queue.
+	///
+	/// Each record is deleted in its own transaction, through the same
+	/// document pipeline as a `DELETE` statement, so that the table events
+	/// are triggered and the live queries are notified. An entry which fails
+	/// to be processed is moved back in the queue, and is retried once
+	/// `EXPIRY_RETRY_DELAY` has elapsed, so that it doesn't prevent the
+	/// following entries from being processed.
+	pub(crate) async fn process_next_expiries_batch(
+		ds: &Datastore,
+		lh: Option<&LeaseHandler>,
+	) -> Result<usize> {
+		// Collect the next batch
+		let res = {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let (beg, end) = ExpiryQueue::range(now_nanos()?);
+			// Read a bounded batch without holding a write transaction.
+			let res = catch!(tx, tx.keys(beg..end, *NORMAL_FETCH_SIZE, 0, None).await);
+			tx.cancel().await?;
+			res
+		};
+		let mut count = 0;
+		let mut stack = TreeStack::new();
+		for k in res {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			match stack.enter(|stk| Self::expire(stk, ds, &k)).finish().await {
+				Ok(()) => count += 1,
+				Err(e) => {
+					error!(
+						"Unexpected error while deleting an expired record. Error: {e} - Key: {k:?}"
+					);
+					match Self::requeue(ds, &k).await {
+						Ok(()) => count += 1,
+						Err(e) => {
+							error!(
+								"Unexpected error while requeuing an expired record. Error: {e} - Key: {k:?}"
+							)
+						}
+					}
+				}
+			}
+		}
+		Ok(count)
+	}
+
+	/// Queues the expiry of a single batch of the existing records of a table
+	/// whose `TTL` was defined or changed. Returns whether there was a table
+	/// to backfill.
+	///
+	/// A record which has been written since the `TTL` was defined already
+	/// has its own expiry, which is kept. Such an expiry is not earlier than
+	/// the expiry of the backfill, and not later than the `TTL` from now.
+	pub(crate) async fn process_next_backfill_batch(
+		ds: &Datastore,
+		lh: Option<&LeaseHandler>,
+	) -> Result<bool> {
+		if let Some(lh) = lh {
+			lh.try_maintain_lease().await?;
+		}
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		let (beg, end) = ExpiryBackfill::range();
+		let Some((k, v)) = catch!(tx, tx.scan(beg..end, 1, 0, None).await).pop() else {
+			tx.cancel().await?;
+			return Ok(false);
+		};
+		catch!(tx, Self::backfill(&tx, &k, v).await);
+		catch!(tx, tx.commit().await);
+		Ok(true)
+	}
+
+	async fn backfill(txn: &Transaction, k: &Key, v: Val) -> Result<()> {
+		let eb = ExpiryBackfill::decode_key(k)?;
+		let state = ExpiryBackfillState::kv_decode_value(v)?;
+		// The backfill is stale if the table has been removed, or no longer has a `TTL`
+		let tb = match txn.get_db_by_name(&eb.ns, &eb.db).await? {
+			Some(db) => txn.get_tb(db.namespace_id, db.database_id, &eb.tb).await?,
+			None => None,
+		};
+		let Some((ttl, tb)) = tb.and_then(|tb| Some((tb.ttl?, tb))) else {
+			return txn.del(&eb).await;
+		};
+		let (ns, db) = (tb.namespace_id, tb.database_id);
+		let latest = expiry_nanos(ttl)?;
+		// Queue the expiry of the next batch of records
+		let end = crate::key::record::suffix(ns, db, &eb.tb)?;
+		let batch = txn.batch_keys(state.next.clone()..end, *NORMAL_FETCH_SIZE, None).await?;
+		for k in batch.result {
+			let rid = RecordKey::decode_key(&k)?;
+			let key = crate::key::table::ex::new(ns, db, &eb.tb, &rid.id);
+			match txn.get(&key, None).await? {
+				// The record has been written since the `TTL` was defined
+				Some(ts) if ts >= state.ts && ts <= latest => continue,
+				_ => {
+					let names = (eb.ns.as_ref(), eb.db.as_ref());
+					queue_expiry(txn, state.ts, names, (ns, db), &eb.tb, &rid.id).await?;
+				}
+			}
+		}
+		// Record the progress, or complete the backfill
+		match batch.next {
+			Some(rng) => {
+				let state = ExpiryBackfillState {
+					next: rng.start,
+					..state
+				};
+				txn.set(&eb, &state, None).await
+			}
+			None => txn.del(&eb).await,
+		}
+	}
+
+	/// Moves an entry of the expiry queue which failed to be processed, so
+	/// that it is retried once `EXPIRY_RETRY_DELAY` has elapsed.
+	async fn requeue(ds: &Datastore, k: &Key) -> Result<()> {
+		let eq = ExpiryQueue::decode_key(k)?;
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		// Remove the entry from the queue
+		catch!(tx, tx.del(&eq).await);
+		// Queue the entry again, unless it is stale
+		if let Some(db) = catch!(tx, tx.get_db_by_name(&eq.ns, &eq.db).await) {
+			let key = crate::key::table::ex::new(db.namespace_id, db.database_id, &eq.tb, &eq.id);
+			if catch!(tx, tx.get(&key, None).await) == Some(eq.ts) {
+				let delay = u64::try_from(EXPIRY_RETRY_DELAY.as_nanos()).unwrap_or(u64::MAX);
+				let ts = catch!(tx, now_nanos()).saturating_add(delay);
+				let next = ExpiryQueue::new(ts, &eq.ns, &eq.db, &eq.tb, eq.id.clone());
+				catch!(tx, tx.set(&next, &(), None).await);
+				catch!(tx, tx.set(&key, &ts, None).await);
+			}
+		}
+		catch!(tx, tx.commit().await);
+		Ok(())
+	}
+
+	/// Deletes the record of an entry of the expiry queue, and removes the
+	/// entry from the queue.
+	async fn expire(stk: &mut Stk, ds: &Datastore, k: &Key) -> Result<()> {
+		let eq = ExpiryQueue::decode_key(k)?;
+		// Setup the context and the options of the deletion
+		let mut ctx = ds.setup_ctx()?;
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		ctx.set_transaction(Arc::new(tx));
+		let ctx = ctx.freeze();
+		let tx = ctx.tx();
+		let sess =
+			Session::for_level(Level::Database(eq.ns.to_string(), eq.db.to_string()), Role::Editor);
+		let mut opt = ds.setup_options(&sess);
+		// Collect the live query notifications
+		let (send, recv) = async_channel::unbounded();
+		if ctx.has_notifications() {
+			opt.broker = Some(DefaultBroker::new(send));
+		}
+		// Delete the record, and remove the entry from the queue
+		catch!(tx, Self::delete_record(stk, &ctx, &opt, &eq).await);
+		catch!(tx, tx.del(&eq).await);
+		catch!(tx, tx.commit().await);
+		// Forward the notifications once the deletion is committed
+		if let Some(sink) = ctx.notifications() {
+			while let Ok(notification) = recv.try_recv() {
+				if sink.send(notification).await.is_err() {
+					break;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	async fn delete_record(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		eq: &ExpiryQueue<'_>,
+	) -> Result<()> {
+		let txn = ctx.tx();
+		// The database may have been removed since the entry was queued
+		let Some(db) = txn.get_db_by_name(&eq.ns, &eq.db).await? else {
+			return Ok(());
+		};
+		// The entry is stale if the record has been written or deleted since,
+		// or if the table has been removed or no longer has a `TTL`
+		let key = crate::key::table::ex::new(db.namespace_id, db.database_id, &eq.tb, &eq.id);
+		if txn.get(&key, None).await? != Some(eq.ts) {
+			return Ok(());
+		}
+		// Delete the record
+		let rid = RecordId::new(eq.tb.as_ref().clone(), eq.id.clone());
+		let stm = DeleteStatement {
+			what: vec![Expr::Literal(Literal::RecordId(rid.into_literal()))],
+			..DeleteStatement::default()
+		};
+		stm.compute(stk, ctx, opt, None).await?;
+		// Carry on
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::Value;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn records_written_before_the_ttl_expire() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query(
+			"
+			CREATE t:before; DEFINE TABLE OVERWRITE t TTL 1ns; CREATE t:after;
+			DEFINE TABLE OVERWRITE u TTL 1h; CREATE u:one; ALTER TABLE u TTL 1ns;
+			DEFINE TABLE OVERWRITE v TTL 1ns; ALTER TABLE v DROP TTL;
+		",
+		)
+		.await;
+		// The existing records are only queued by the record expiry task, one
+		// table at a time, replacing their previous expiry
+		let mut tables = 0;
+		while RecordExpiry::process_next_backfill_batch(&ds, None).await.unwrap() {
+			tables += 1;
+		}
+		assert_eq!(tables, 3);
+		tokio::time::sleep(Duration::from_millis(10)).await;
+		assert_eq!(RecordExpiry::process_next_expiries_batch(&ds, None).await.unwrap(), 3);
+		let res = ds.test_query("RETURN (SELECT VALUE id FROM t, u) == []").await;
+		assert_eq!(res, Value::Bool(true));
+	}
+}
@@ -5,10 +5,11 @@ use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 
-use crate::catalog::{self, FieldDefinition};
+use crate::catalog::providers::KeyProvider;
+use crate::catalog::{self, FieldDefinition, Record};
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::{Options, Statement};
-use crate::doc::Document;
+use crate::doc::{CursorRecord, Document, DocumentContext};
 use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::expr::data::Data;
@@ -400,6 +401,50 @@ impl Document {
 
 		Ok(())
 	}
+
+	/// Encrypts the values of the fields which are
+	/// defined with `ENCRYPTED WITH KEY`, so that the
+	/// value is never written to storage in plaintext.
+	pub(super) async fn encrypt_fields(
+		&self,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: &mut CursorRecord,
+	) -> Result<()> {
+		// Get the NS + DB
+		let ns = self.doc_ctx.ns().namespace_id;
+		let db = self.doc_ctx.db().database_id;
+		// Loop through all encrypted fields
+		for fd in self.fd(ctx, opt).await?.iter() {
+			if let Some(key) = &fd.encrypted {
+				ctx.tx().expect_db_key(ns, db, key).await?.encrypt_field(doc.to_mut(), &fd.name)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Decrypts the values of the fields which are defined
+/// with `ENCRYPTED WITH KEY`, on a record which has been
+/// fetched from storage.
+pub(super) async fn decrypt_record(
+	ctx: &FrozenContext,
+	doc_ctx: &DocumentContext,
+	mut record: Arc<Record>,
+) -> Result<Arc<Record>> {
+	// Only table documents have field definitions
+	let DocumentContext::NsDbTbCtx(tb_ctx) = doc_ctx else {
+		return Ok(record);
+	};
+	// Loop through all encrypted fields
+	for fd in tb_ctx.fields.iter() {
+		if let Some(key) = &fd.encrypted {
+			let key =
+				ctx.tx().expect_db_key(tb_ctx.ns.namespace_id, tb_ctx.db.database_id, key).await?;
+			key.decrypt_field(&mut Arc::make_mut(&mut record).data, &fd.name)?;
+		}
+	}
+	Ok(record)
 }
 
 struct FieldEditContext<'a> {
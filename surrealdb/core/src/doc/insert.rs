@@ -2,6 +2,7 @@ use anyhow::Result;
 use reblessive::tree::Stk;
 
 use super::IgnoreError;
+use super::field::decrypt_record;
 use crate::catalog::providers::TableProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::{Options, Statement};
@@ -137,6 +138,7 @@ impl Document {
 
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 		let val = ctx.tx().get_record(ns, db, &retry.table, &retry.key, opt.version).await?;
+		let val = decrypt_record(ctx, &self.doc_ctx, val).await?;
 
 		self.modify_for_update_retry(retry, val);
 
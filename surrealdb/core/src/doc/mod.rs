@@ -7,7 +7,9 @@
 
 pub(crate) use self::document::*;
 pub use self::event::AsyncEventRecord;
+pub(crate) use self::expiry::{ExpiryBackfillState, RecordExpiry, store_table_expiries};
 pub(crate) use self::lives::DefaultBroker;
+pub(crate) use self::select::check_select_permissions_table;
 
 mod document; // The entry point for a document to be processed
 
@@ -27,6 +29,7 @@ mod check; // Data and condition checking for this document
 mod compute; // Compute computed fields for this document
 mod edges; // Attempts to store the edge data for this document
 mod event; // Processes any table events relevant for this document
+mod expiry; // Schedules and processes the expiry of this document
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
 mod lives; // Processes any live queries relevant for this document
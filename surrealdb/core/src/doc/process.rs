@@ -1,6 +1,7 @@
 use reblessive::tree::Stk;
 
 use super::IgnoreError;
+use super::field::decrypt_record;
 use crate::catalog::Record;
 use crate::ctx::FrozenContext;
 use crate::dbs::{Operable, Options, Processable, Statement, Workable};
@@ -41,9 +42,10 @@ impl Document {
 				(Record::new(Value::from(count)).into_read_only(), Workable::Normal)
 			}
 		};
+		// Decrypt any encrypted fields
+		let val = decrypt_record(ctx, &doc_ctx, ins.0).await?;
 		// Setup a new document
-		let mut doc =
-			Document::new(doc_ctx, rid, ir, generate, ins.0, ins.1, false, record_strategy);
+		let mut doc = Document::new(doc_ctx, rid, ir, generate, val, ins.1, false, record_strategy);
 		// Process the statement
 		let res = match stm {
 			Statement::Select {
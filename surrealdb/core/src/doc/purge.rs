@@ -41,6 +41,8 @@ impl Document {
 			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 			// Purge the record data
 			txn.del_record(ns, db, &rid.table, &rid.key).await?;
+			// Purge the record expiry
+			self.purge_record_expiry(ctx, opt, rid.as_ref()).await?;
 			// Purge the record edges
 			self.purge_edges(stk, ctx, opt, rid.as_ref()).await?;
 			// Purge any record references
@@ -68,7 +68,7 @@ fn check_select_permissions_quick(
 	feature = "trace-doc-ops",
 	instrument(level = "trace", name = "Document::check_select_permissions_table", skip_all)
 )]
-pub(super) async fn check_select_permissions_table(
+pub(crate) async fn check_select_permissions_table(
 	stk: &mut Stk,
 	ctx: &FrozenContext,
 	opt: &Options,
@@ -36,6 +36,8 @@ impl Document {
 		if let crate::val::Value::Object(obj) = doc_without_id.to_mut() {
 			obj.0.remove("id");
 		}
+		// Encrypt any encrypted fields before storing
+		self.encrypt_fields(ctx, opt, &mut doc_without_id).await?;
 		// Match the statement type
 		match stm {
 			// This is a INSERT statement so try to insert the key.
@@ -162,6 +164,8 @@ impl Document {
 					.await
 			}
 		}?;
+		// Schedule the expiry of the record
+		self.store_record_expiry(ctx, opt).await?;
 		// Carry on
 		Ok(())
 	}
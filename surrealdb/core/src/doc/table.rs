@@ -349,9 +349,28 @@ impl Document {
 
 		aggregation::add_to_aggregation_stats(&args, &mut meta.aggregation_stats)?;
 
-		let doc =
-			Value::Object(aggregation::create_field_document(&group, &meta.aggregation_stats))
-				.into();
+		let custom = aggr.custom_aggregates(stk, ctx, opt).await?;
+		aggregation::accumulate_custom_aggregation_stats(
+			stk,
+			ctx,
+			opt,
+			&custom,
+			&args,
+			&mut meta.aggregation_stats,
+		)
+		.await?;
+
+		let mut doc = aggregation::create_field_document(&group, &meta.aggregation_stats);
+		aggregation::finalize_custom_aggregation_stats(
+			stk,
+			ctx,
+			opt,
+			&custom,
+			&meta.aggregation_stats,
+			&mut doc,
+		)
+		.await?;
+		let doc = Value::Object(doc).into();
 
 		let mut data = Value::empty_object();
 
@@ -446,7 +465,7 @@ impl Document {
 
 		if count == 1 {
 			// Only one record, we can just delete the record.
-			tx.del(&k).await?;
+			tx.del_record(db.namespace_id, db.database_id, view_table_name, &key).await?;
 
 			let ns = self.doc_ctx.ns();
 			let db = self.doc_ctx.db();
@@ -488,6 +507,7 @@ impl Document {
 		}
 
 		let mut recalculations = Vec::new();
+		let mut recompute_custom = false;
 		for (idx, a) in meta.aggregation_stats.iter_mut().enumerate() {
 			match a {
 				AggregationStat::Count {
@@ -614,6 +634,11 @@ impl Document {
 				AggregationStat::Accumulate {
 					..
 				} => fail!("Accumulate aggregation is not supported in materialized views"),
+				AggregationStat::Custom {
+					..
+				} => {
+					recompute_custom = true;
+				}
 			}
 		}
 
@@ -630,23 +655,7 @@ impl Document {
 				.collect();
 
 			// Build condition which filters out all values not belonging to the group.
-			let mut condition = None;
-			for (idx, g) in aggr.group_expressions.iter().enumerate() {
-				let expr = Expr::Binary {
-					left: Box::new(g.clone()),
-					op: BinaryOperator::Equal,
-					right: Box::new(group[idx].clone().into_literal()),
-				};
-				if let Some(c) = condition {
-					condition = Some(Expr::Binary {
-						left: Box::new(c),
-						op: BinaryOperator::And,
-						right: Box::new(expr),
-					})
-				} else {
-					condition = Some(expr)
-				}
-			}
+			let condition = group_condition(aggr, &group);
 
 			let table_name = self.id()?.table.clone();
 
@@ -674,6 +683,7 @@ impl Document {
 				timeout: Expr::Literal(Literal::None),
 				explain: None,
 				tempfiles: false,
+				cache: None,
 			};
 
 			let value = recalc_stmt.compute(stk, ctx, opt, None).await?;
@@ -720,9 +730,30 @@ impl Document {
 			}
 		}
 
-		let doc =
-			Value::Object(aggregation::create_field_document(&group, &meta.aggregation_stats))
-				.into();
+		if recompute_custom {
+			self.recompute_custom_aggregates(
+				stk,
+				ctx,
+				opt,
+				aggr,
+				&group,
+				&mut meta.aggregation_stats,
+			)
+			.await?;
+		}
+
+		let custom = aggr.custom_aggregates(stk, ctx, opt).await?;
+		let mut doc = aggregation::create_field_document(&group, &meta.aggregation_stats);
+		aggregation::finalize_custom_aggregation_stats(
+			stk,
+			ctx,
+			opt,
+			&custom,
+			&meta.aggregation_stats,
+			&mut doc,
+		)
+		.await?;
+		let doc = Value::Object(doc).into();
 
 		let mut data = Value::empty_object();
 
@@ -781,6 +812,60 @@ impl Document {
 		Ok(())
 	}
 
+	/// Folds the states of the user-defined aggregates of a group anew from the records which
+	/// remain within the group, as a record can't be taken back out of a state.
+	async fn recompute_custom_aggregates(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		aggr: &AggregationAnalysis,
+		group: &[Value],
+		stats: &mut [AggregationStat],
+	) -> Result<()> {
+		let table_name = self.id()?.table.clone();
+
+		let stmt = SelectStatement {
+			// SELECT VALUE [argument1, argument2,..]
+			fields: Fields::Value(Box::new(Selector {
+				expr: Expr::Literal(Literal::Array(aggr.aggregate_arguments.clone())),
+				alias: None,
+			})),
+			only: false,
+			what: vec![Expr::Table(table_name)],
+			// WHERE group_expr1 = group_value1 && group_expr2 = group_value2 && ..
+			cond: group_condition(aggr, group).map(Cond),
+			group: None,
+			omit: vec![],
+			with: None,
+			split: None,
+			order: None,
+			limit: None,
+			start: None,
+			fetch: None,
+			version: Expr::Literal(Literal::None),
+			timeout: Expr::Literal(Literal::None),
+			explain: None,
+			tempfiles: false,
+			cache: None,
+		};
+
+		let Value::Array(Array(rows)) = stmt.compute(stk, ctx, opt, None).await? else {
+			fail!("Aggregate recalculation select statement return an invalid result");
+		};
+		let mut records = Vec::with_capacity(rows.len());
+		for r in rows {
+			let Value::Array(Array(arguments)) = r else {
+				fail!("Aggregate recalculation select statement return an invalid result");
+			};
+			records.push(arguments);
+		}
+
+		let custom = aggr.custom_aggregates(stk, ctx, opt).await?;
+		aggregation::recompute_custom_aggregation_stats(stk, ctx, opt, &custom, &records, stats)
+			.await
+	}
+
 	/// Process an update to a entry in the materialized, aggregated view.
 	/// Only called for updates to values that remain within the same group.
 	async fn process_view_record_update(
@@ -820,6 +905,7 @@ impl Document {
 		}
 
 		let mut recalculations = Vec::new();
+		let mut recompute_custom = false;
 		for (idx, a) in meta.aggregation_stats.iter_mut().enumerate() {
 			match a {
 				AggregationStat::Count {
@@ -1021,6 +1107,11 @@ impl Document {
 				AggregationStat::Accumulate {
 					..
 				} => fail!("Accumulate aggregation is not supported in materialized views"),
+				AggregationStat::Custom {
+					..
+				} => {
+					recompute_custom = true;
+				}
 			}
 		}
 
@@ -1037,23 +1128,7 @@ impl Document {
 				.collect();
 
 			// Build condition which filters out all values not belonging to the group.
-			let mut condition = None;
-			for (idx, g) in aggr.group_expressions.iter().enumerate() {
-				let expr = Expr::Binary {
-					left: Box::new(g.clone()),
-					op: BinaryOperator::Equal,
-					right: Box::new(group[idx].clone().into_literal()),
-				};
-				if let Some(c) = condition {
-					condition = Some(Expr::Binary {
-						left: Box::new(c),
-						op: BinaryOperator::And,
-						right: Box::new(expr),
-					})
-				} else {
-					condition = Some(expr)
-				}
-			}
+			let condition = group_condition(aggr, &group);
 
 			let table_name = self.id()?.table.clone();
 
@@ -1081,6 +1156,7 @@ impl Document {
 				timeout: Expr::Literal(Literal::None),
 				explain: None,
 				tempfiles: false,
+				cache: None,
 			};
 
 			let value = recalc_stmt.compute(stk, ctx, opt, None).await?;
@@ -1127,9 +1203,30 @@ impl Document {
 			}
 		}
 
-		let doc =
-			Value::Object(aggregation::create_field_document(&group, &meta.aggregation_stats))
-				.into();
+		if recompute_custom {
+			self.recompute_custom_aggregates(
+				stk,
+				ctx,
+				opt,
+				aggr,
+				&group,
+				&mut meta.aggregation_stats,
+			)
+			.await?;
+		}
+
+		let custom = aggr.custom_aggregates(stk, ctx, opt).await?;
+		let mut doc = aggregation::create_field_document(&group, &meta.aggregation_stats);
+		aggregation::finalize_custom_aggregation_stats(
+			stk,
+			ctx,
+			opt,
+			&custom,
+			&meta.aggregation_stats,
+			&mut doc,
+		)
+		.await?;
+		let doc = Value::Object(doc).into();
 
 		let mut data = Value::empty_object();
 
@@ -1234,3 +1331,25 @@ impl Document {
 		Ok(())
 	}
 }
+
+/// Builds a condition which filters out all records not belonging to the group.
+fn group_condition(aggr: &AggregationAnalysis, group: &[Value]) -> Option<Expr> {
+	let mut condition = None;
+	for (idx, g) in aggr.group_expressions.iter().enumerate() {
+		let expr = Expr::Binary {
+			left: Box::new(g.clone()),
+			op: BinaryOperator::Equal,
+			right: Box::new(group[idx].clone().into_literal()),
+		};
+		if let Some(c) = condition {
+			condition = Some(Expr::Binary {
+				left: Box::new(c),
+				op: BinaryOperator::And,
+				right: Box::new(expr),
+			})
+		} else {
+			condition = Some(expr)
+		}
+	}
+	condition
+}
@@ -194,6 +194,10 @@ pub(crate) enum Error {
 	#[error("The value cannot be converted to a vector: {0}")]
 	InvalidVectorValue(String),
 
+	/// The value of a spatial nearest neighbour search is not a point
+	#[error("A nearest neighbour search on a spatial index requires a point, found: {0}")]
+	InvalidSpatialKnnValue(String),
+
 	/// Invalid regular expression
 	#[error("Invalid regular expression: {0:?}")]
 	InvalidRegex(String),
@@ -222,6 +226,10 @@ pub(crate) enum Error {
 	#[error("The query was not executed due to the memory threshold being reached")]
 	QueryBeyondMemoryThreshold,
 
+	/// A Surrealism module exceeded one of its resource limits
+	#[error("Failed to run the module: {0}")]
+	ModuleLimitExceeded(String),
+
 	/// The query did not execute, because the transaction has failed.
 	#[error("The query was not executed due to a failed transaction. {message}")]
 	QueryNotExecuted {
@@ -330,6 +338,38 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested job does not exist
+	#[error("The job '{name}' does not exist")]
+	JbNotFound {
+		name: String,
+	},
+
+	/// The requested changefeed sink does not exist
+	#[error("The sink '{name}' does not exist")]
+	SkNotFound {
+		name: String,
+	},
+
+	/// The requested changefeed consumer does not exist
+	#[error("The consumer '{name}' does not exist")]
+	CsNotFound {
+		name: String,
+	},
+
+	/// The requested encryption key does not exist
+	#[error("The key '{name}' does not exist")]
+	KyNotFound {
+		name: String,
+	},
+
+	/// The requested encryption key is still used by an encrypted field
+	#[error("The key '{name}' is used by the field '{field}' on table '{table}'")]
+	KyInUse {
+		name: String,
+		field: String,
+		table: String,
+	},
+
 	/// The requested analyzer does not exist
 	#[error("The index '{name}' does not exist")]
 	IxNotFound {
@@ -460,6 +500,24 @@ pub(crate) enum Error {
 		value: String,
 	},
 
+	/// Cannot execute ACK statement using the specified versionstamp
+	#[error("Cannot execute ACK statement using versionstamp: {value}")]
+	AckStatement {
+		value: String,
+	},
+
+	/// The acknowledged versionstamp has not been reached yet
+	#[error("Cannot acknowledge versionstamp {value}, which is beyond the current versionstamp")]
+	AckVersionstampAhead {
+		value: u64,
+	},
+
+	/// The audit log can only be shown since a datetime
+	#[error("Cannot show the audit log since versionstamp {value}, a datetime is expected")]
+	ShowAuditVersionstamp {
+		value: u64,
+	},
+
 	/// Cannot execute CREATE statement using the specified value
 	#[error("Expected a single result output when using the ONLY keyword")]
 	SingleOnlyOutput,
@@ -668,6 +726,18 @@ pub(crate) enum Error {
 	#[error("A value can't be highlighted: {0}")]
 	HighlightError(String),
 
+	/// Represents an error when parsing a full-text search query
+	#[error("Invalid full-text query: {0}")]
+	FullTextQueryError(String),
+
+	/// Represents an error when parsing the schedule of a job
+	#[error("Invalid job schedule: {0}")]
+	InvalidSchedule(String),
+
+	/// A changefeed sink targets a custom sink which is not registered
+	#[error("The custom sink '{0}' is not registered with the datastore")]
+	SinkNotRegistered(String),
+
 	/// Represents an underlying error with FST
 	#[error("FstError error: {0}")]
 	FstError(#[from] FstError),
@@ -728,7 +798,10 @@ pub(crate) enum Error {
 
 	/// Network target is not allowed
 	#[error("Access to network target '{0}' is not allowed")]
-	#[cfg_attr(not(feature = "http"), expect(dead_code))]
+	#[cfg_attr(
+		not(any(feature = "http", all(feature = "s3", not(target_arch = "wasm32")))),
+		expect(dead_code)
+	)]
 	NetTargetNotAllowed(String),
 
 	//
@@ -820,6 +893,34 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested job already exists
+	#[error("The job '{name}' already exists")]
+	JbAlreadyExists {
+		name: String,
+	},
+
+	/// The requested changefeed sink already exists
+	#[error("The sink '{name}' already exists")]
+	SkAlreadyExists {
+		name: String,
+	},
+
+	/// Only root owners can define sinks which write to the server
+	#[error("Only root owners can define sinks which write to a file or the standard output")]
+	SinkTargetNotAllowed,
+
+	/// The requested changefeed consumer already exists
+	#[error("The consumer '{name}' already exists")]
+	CsAlreadyExists {
+		name: String,
+	},
+
+	/// The requested encryption key already exists
+	#[error("The key '{name}' already exists")]
+	KyAlreadyExists {
+		name: String,
+	},
+
 	/// The requested index already exists
 	#[error("The index '{name}' already exists")]
 	IxAlreadyExists {
@@ -903,6 +1004,14 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// A full-text index was built before term positions were recorded
+	#[error(
+		"A full-text index on table `{table}` was built without term positions, run `REBUILD INDEX` before using phrase or proximity queries"
+	)]
+	FullTextIndexOutdated {
+		table: String,
+	},
+
 	/// A the index building has been cancelled
 	#[error("Index building has been cancelled: {reason}")]
 	IndexingBuildingCancelled {
@@ -1027,6 +1136,36 @@ pub(crate) enum Error {
 	#[error("This access grant has been revoked")]
 	AccessGrantRevoked,
 
+	/// The specified encryption key material is not valid
+	#[error("Invalid key material for key '{name}': {message}")]
+	KyInvalid {
+		name: String,
+		message: String,
+	},
+
+	/// No master key is configured for wrapping the key material
+	#[error(
+		"Unable to use the key '{name}' as no master key is configured, set SURREAL_KEY_MASTER_KEY_FILE"
+	)]
+	KyMasterKeyMissing {
+		name: String,
+	},
+
+	/// The key material could not be wrapped or unwrapped with the master key
+	#[error("Unable to use the master key for key '{name}': {message}")]
+	KyMasterKey {
+		name: String,
+		message: String,
+	},
+
+	/// A field value could not be encrypted or decrypted
+	#[error("Unable to {action} the value of field '{field}' with key '{key}'")]
+	FieldEncryption {
+		action: &'static str,
+		field: String,
+		key: String,
+	},
+
 	/// Found a table name for the record but this is not a valid table
 	#[error("Found {value} for the Record ID but this is not a valid table name")]
 	TbInvalid {
@@ -1167,6 +1306,41 @@ pub(crate) enum Error {
 	#[error("Operation for bucket `{0}` failed: {1}")]
 	ObjectStoreFailure(String, String),
 
+	#[error("Experimental capability `files` is not enabled")]
+	FilesNotEnabled,
+
+	#[error("The file upload `{0}` does not exist or has expired")]
+	UploadNotFound(String),
+
+	#[error(
+		"The file upload `{id}` continues at offset {expected}, but data for offset {found} was sent"
+	)]
+	UploadOffsetMismatch {
+		id: String,
+		expected: u64,
+		found: u64,
+	},
+
+	#[error(
+		"The file is larger than the {limit} bytes which can be buffered for bucket `{bucket}`, which does not support streaming writes"
+	)]
+	UploadTooLarge {
+		bucket: String,
+		limit: u64,
+	},
+
+	#[error("The file is larger than the maximum upload size of {0} bytes")]
+	UploadSizeExceeded(u64),
+
+	#[error("The file upload `{id}` can not be written in more than {limit} chunks")]
+	UploadPartsExceeded {
+		id: String,
+		limit: u32,
+	},
+
+	#[error("Can not start more than {0} file uploads at once")]
+	UploadSessionsExceeded(u64),
+
 	/// The `COMPUTED` clause cannot be used with other clauses altering or
 	/// working with the value
 	#[error("Cannot use the `{0}` keyword with `COMPUTED`.")]
@@ -1213,6 +1387,56 @@ pub(crate) enum Error {
 		field: String,
 		index: String,
 	},
+
+	#[error("Encrypted fields cannot be indexed. Index: '{index}' - Field: '{field}'")]
+	EncryptedFieldCannotBeIndexed {
+		field: String,
+		index: String,
+	},
+
+	#[error(
+		"Cannot define field `{0}` as `ENCRYPTED` since only plain field paths can be encrypted."
+	)]
+	EncryptedFieldPath(String),
+
+	#[error("The backup archive is invalid: {0}")]
+	InvalidBackup(String),
+
+	#[error("Incremental backups require a datastore with versioning enabled: {0}")]
+	IncrementalBackupUnsupported(String),
+
+	#[error("Invalid recovery point `{0}`, expected a datetime or a versionstamp")]
+	InvalidRecoveryPoint(String),
+
+	#[error("Invalid export format `{0}`, expected one of surql, ndjson, csv or parquet")]
+	InvalidExportFormat(String),
+
+	#[error("Invalid flatten policy `{0}`, expected json or columns")]
+	InvalidFlattenPolicy(String),
+
+	#[error("The {format} export format writes a single table, but {count} tables were selected")]
+	ExportSingleTable {
+		format: String,
+		count: usize,
+	},
+
+	#[error("The {0} export format is not supported by this build")]
+	ExportFormatUnsupported(String),
+
+	#[error("Invalid import format `{0}`, expected one of ndjson, csv or parquet")]
+	InvalidImportFormat(String),
+
+	#[error("The {0} import format is not supported by this build")]
+	ImportFormatUnsupported(String),
+
+	#[error("Unable to import the column `{name}` of type {kind}")]
+	ImportUnsupportedType {
+		name: String,
+		kind: String,
+	},
+
+	#[error("The Parquet file is larger than the maximum import size of {0} bytes")]
+	ImportSizeExceeded(u64),
 }
 
 impl Error {
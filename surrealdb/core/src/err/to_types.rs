@@ -226,6 +226,18 @@ pub fn into_types_error(error: Error) -> TypesError {
 		| MdAlreadyExists {
 			..
 		}
+		| JbAlreadyExists {
+			..
+		}
+		| SkAlreadyExists {
+			..
+		}
+		| CsAlreadyExists {
+			..
+		}
+		| KyAlreadyExists {
+			..
+		}
 		| IxAlreadyExists {
 			..
 		}
@@ -270,6 +282,9 @@ pub fn into_types_error(error: Error) -> TypesError {
 		}
 		| IndexingBuildingCancelled {
 			..
+		}
+		| FullTextIndexOutdated {
+			..
 		} => TypesError::internal(message),
 
 		// Thrown
@@ -288,6 +303,8 @@ pub fn into_types_error(error: Error) -> TypesError {
 		} => TypesError::internal(message),
 		AnalyzerError(..) => TypesError::internal(message),
 		HighlightError(..) => TypesError::internal(message),
+		FullTextQueryError(..) => TypesError::validation(message, None),
+		InvalidSchedule(..) => TypesError::validation(message, None),
 		FstError(_) => TypesError::internal(message),
 		ObsError(_) => TypesError::internal(message),
 		TimestampOverflow(..) => TypesError::internal(message),
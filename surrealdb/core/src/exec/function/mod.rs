@@ -28,7 +28,7 @@ pub use index::{
 };
 pub use method::MethodDescriptor;
 pub use projection::ProjectionFunction;
-pub use registry::FunctionRegistry;
+pub use registry::{FunctionRegistry, ModuleAggregates};
 pub use signature::Signature;
 
 use crate::exec::physical_expr::EvalContext;
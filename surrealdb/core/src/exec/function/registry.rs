@@ -5,6 +5,15 @@ use std::sync::Arc;
 
 use super::method::{self, MethodDescriptor, MethodRegistry};
 use super::{AggregateFunction, IndexFunction, ProjectionFunction, ScalarFunction, builtin};
+#[cfg(feature = "surrealism")]
+use crate::catalog::{DatabaseId, NamespaceId};
+#[cfg(feature = "surrealism")]
+use crate::ctx::FrozenContext;
+#[cfg(feature = "surrealism")]
+use crate::err::Error;
+use crate::expr::Function;
+#[cfg(feature = "surrealism")]
+use crate::kvs::Transaction;
 
 /// Registry of functions available during query execution.
 ///
@@ -96,6 +105,20 @@ impl FunctionRegistry {
 		self.aggregates.len()
 	}
 
+	/// Look up the aggregate function called by a function, either a built-in
+	/// aggregate registered by name, or a module aggregate registered for the
+	/// query.
+	pub(crate) fn get_function_aggregate<'a>(
+		&'a self,
+		function: &Function,
+		modules: &'a ModuleAggregates,
+	) -> Option<&'a Arc<dyn AggregateFunction>> {
+		match function {
+			Function::Normal(name) => self.get_aggregate(name),
+			function => modules.get(function),
+		}
+	}
+
 	/// Get the appropriate aggregate function for "count", handling the special case:
 	/// - count() with no arguments counts all rows (uses Count)
 	/// - count(expr) with arguments counts truthy values (uses CountField)
@@ -184,6 +207,92 @@ impl Default for FunctionRegistry {
 	}
 }
 
+/// The aggregate functions exported by modules, registered for a single query.
+///
+/// Built-in aggregates are registered by name when the registry is created.
+/// Whether a module function is an aggregate is only known once its module is
+/// loaded from the catalog, and a module aggregate runs within the context of
+/// the query which calls it, so module aggregates are registered for each
+/// query, keyed by the function which calls them.
+#[derive(Debug, Default)]
+pub struct ModuleAggregates {
+	aggregates: HashMap<Function, Arc<dyn AggregateFunction>>,
+}
+
+impl ModuleAggregates {
+	/// Register the aggregate called by a module function, if the module
+	/// exports the function as an aggregate.
+	///
+	/// Fails with [`Error::PlannerUnsupported`] when the function can not be
+	/// resolved, or when the module has permissions which must be checked for
+	/// each caller, so that the query falls back to the compute path.
+	#[cfg(feature = "surrealism")]
+	pub(crate) async fn register(
+		&mut self,
+		ctx: &FrozenContext,
+		txn: &Transaction,
+		(ns, db): (NamespaceId, DatabaseId),
+		function: &Function,
+	) -> Result<(), Error> {
+		use crate::catalog::Permission;
+		use crate::catalog::providers::DatabaseProvider;
+		use crate::expr::module::{ModuleExecutable, ModuleFunctionKind};
+		use crate::surrealism::aggregate::ModuleAggregate;
+
+		let (mod_name, sub) = match function {
+			Function::Module(module, sub) => (format!("mod::{module}"), sub.clone()),
+			Function::Silo {
+				org,
+				pkg,
+				major,
+				minor,
+				patch,
+				sub,
+			} => (format!("silo::{org}::{pkg}<{major}.{minor}.{patch}>"), sub.clone()),
+			_ => return Ok(()),
+		};
+		let fnc_name = match &sub {
+			Some(sub) => format!("{mod_name}::{sub}"),
+			None => mod_name.clone(),
+		};
+
+		let resolved = async {
+			ctx.check_allowed_function(&fnc_name)?;
+			let val = txn.get_db_module(ns, db, &mod_name).await?;
+			let executable: ModuleExecutable = val.executable.clone().into();
+			let signature =
+				executable.signature(ctx, &ns, &db, sub.as_deref(), &val.limits).await?;
+			let runtime = executable.runtime(ctx, &ns, &db, &val.limits).await?;
+			anyhow::Ok((val, signature, runtime))
+		}
+		.await;
+		// The compute path reports the error when the function is called
+		let Ok((val, signature, runtime)) = resolved else {
+			return Err(Error::PlannerUnsupported(format!(
+				"Module function '{fnc_name}' could not be resolved at plan time"
+			)));
+		};
+
+		if signature.kind != ModuleFunctionKind::Aggregate {
+			return Ok(());
+		}
+		if !matches!(val.permissions, Permission::Full) {
+			return Err(Error::PlannerUnsupported(format!(
+				"Module aggregate '{fnc_name}' has permissions which are checked per caller"
+			)));
+		}
+
+		let aggregate = ModuleAggregate::new(fnc_name, sub, ctx, runtime, &val.limits, signature);
+		self.aggregates.insert(function.clone(), Arc::new(aggregate));
+		Ok(())
+	}
+
+	/// Look up the aggregate registered for a module function.
+	pub(crate) fn get(&self, function: &Function) -> Option<&Arc<dyn AggregateFunction>> {
+		self.aggregates.get(function)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -239,6 +348,27 @@ mod tests {
 		assert!(registry.aggregate_len() >= 10);
 	}
 
+	#[test]
+	fn test_function_aggregate_lookup() {
+		let registry = FunctionRegistry::with_builtins();
+		let modules = ModuleAggregates::default();
+
+		// Built-in aggregates are looked up by name
+		let sum = registry
+			.get_function_aggregate(&Function::Normal("math::sum".to_string()), &modules)
+			.expect("math::sum should exist");
+		assert_eq!(sum.name(), "math::sum");
+		assert!(
+			registry
+				.get_function_aggregate(&Function::Normal("math::abs".to_string()), &modules)
+				.is_none()
+		);
+
+		// Module functions are only aggregates once registered for the query
+		let module = Function::Module("stats".to_string(), Some("total".to_string()));
+		assert!(registry.get_function_aggregate(&module, &modules).is_none());
+	}
+
 	#[test]
 	fn test_aggregate_lookup() {
 		let registry = FunctionRegistry::with_builtins();
@@ -3,17 +3,19 @@
 //! The [`IndexAnalyzer`] examines query conditions and ORDER BY clauses to find
 //! indexes that can accelerate the query.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use super::access_path::{AccessPath, BTreeAccess, IndexRef, RangeBound, select_access_path};
 use crate::catalog::{Index, IndexDefinition};
-use crate::exec::planner::util::try_literal_to_value;
+use crate::exec::planner::util::{try_expr_to_value, try_literal_to_value};
 use crate::expr::operator::{MatchesOperator, NearestNeighbor, PrefixOperator};
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
-use crate::expr::{BinaryOperator, Cond, Expr, Idiom};
+use crate::expr::{BinaryOperator, Cond, Expr, Idiom, Literal};
 use crate::idx::planner::ScanDirection;
-use crate::val::{Number, Value};
+use crate::idx::spatial;
+use crate::val::{Geometry, Number, Object, Value};
 
 /// Analyzes query conditions to find matching indexes.
 pub struct IndexAnalyzer<'a> {
@@ -299,6 +301,114 @@ impl<'a> IndexAnalyzer<'a> {
 		None
 	}
 
+	/// Try to resolve geometry operators (`INSIDE`, `INTERSECTS`, `CONTAINS`)
+	/// with a SPATIAL index.
+	///
+	/// The cells of the geometry are converted to a union of range scans over
+	/// the index. The index only returns candidates whose cells intersect the
+	/// cells of the geometry, so the original condition still has to be
+	/// applied on top of the union.
+	pub fn try_spatial_expansion(
+		&self,
+		cond: Option<&Cond>,
+		direction: ScanDirection,
+	) -> Option<AccessPath> {
+		let cond = cond?;
+
+		if matches!(self.with_hints, Some(With::NoIndex)) {
+			return None;
+		}
+
+		let mut exprs = Vec::new();
+		Self::collect_spatial_expressions(&cond.0, &mut exprs);
+
+		for (idiom, geometry) in &exprs {
+			for (idx, ix_def) in self.indexes.iter().enumerate() {
+				if ix_def.prepare_remove {
+					continue;
+				}
+				let Index::Spatial(params) = &ix_def.index else {
+					continue;
+				};
+
+				if let Some(With::Index(names)) = self.with_hints
+					&& !names.contains(&ix_def.name)
+				{
+					continue;
+				}
+
+				if let Some(first_col) = ix_def.cols.first()
+					&& idiom_matches(idiom, first_col)
+				{
+					let index_ref = IndexRef::new(self.indexes.clone(), idx);
+					let paths: Vec<AccessPath> = spatial::query_ranges(params.level, geometry)
+						.into_iter()
+						.map(|(from, to)| AccessPath::BTreeScan {
+							index_ref: index_ref.clone(),
+							access: BTreeAccess::Range {
+								from: Some(RangeBound::inclusive(Value::from(from))),
+								to: Some(RangeBound::exclusive(Value::from(to))),
+							},
+							direction,
+						})
+						.collect();
+					return Some(AccessPath::Union(paths));
+				}
+			}
+		}
+
+		None
+	}
+
+	/// Collect geometry operators between an idiom and a constant geometry,
+	/// on either side, from an AND tree.
+	///
+	/// The parameters and the constant expressions of the condition have
+	/// already been resolved to literals by the planner, so a parameter holding
+	/// a geometry, or a GeoJSON object, is matched as well.
+	fn collect_spatial_expressions(expr: &Expr, results: &mut Vec<(Idiom, Geometry)>) {
+		match expr {
+			Expr::Binary {
+				left,
+				op: BinaryOperator::And,
+				right,
+			} => {
+				Self::collect_spatial_expressions(left, results);
+				Self::collect_spatial_expressions(right, results);
+			}
+			Expr::Binary {
+				left,
+				op: BinaryOperator::Inside | BinaryOperator::Intersects | BinaryOperator::Contain,
+				right,
+			} => match (left.as_ref(), right.as_ref()) {
+				(Expr::Idiom(idiom), Expr::Literal(lit))
+				| (Expr::Literal(lit), Expr::Idiom(idiom)) => {
+					if let Some(g) = Self::literal_geometry(lit) {
+						results.push((idiom.clone(), g));
+					}
+				}
+				_ => {}
+			},
+			_ => {}
+		}
+	}
+
+	/// Returns the geometry of a literal, which is either a geometry, or an
+	/// object which has the shape of a GeoJSON geometry.
+	fn literal_geometry(lit: &Literal) -> Option<Geometry> {
+		match lit {
+			Literal::Geometry(g) => Some(g.clone()),
+			Literal::Object(entries) => {
+				let object = entries
+					.iter()
+					.map(|e| Some((e.key.clone(), try_expr_to_value(&e.value)?)))
+					.collect::<Option<BTreeMap<_, _>>>()?;
+				Geometry::try_from_object(&Object(object))
+			}
+			_ => None,
+		}
+	}
+
 	/// Collect CONTAINSALL/CONTAINSANY (idiom on left, array literal on right)
 	/// and ALLINSIDE/ANYINSIDE (array literal on left, idiom on right) from an
 	/// AND tree.
@@ -11,6 +11,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use futures::StreamExt;
 
+use crate::err::Error;
 use crate::exec::function::{Accumulator, AggregateFunction};
 use crate::exec::{
 	AccessMode, ContextLevel, EvalContext, ExecOperator, ExecutionContext, FlowResult,
@@ -435,7 +436,11 @@ impl ExecOperator for Aggregate {
 									state.accumulators[field_idx].get_mut(agg_idx)
 									&& let Err(e) = acc.update_batch(arg_col)
 								{
-									tracing::debug!(error = %e, "Accumulator batch update failed, skipping batch");
+									if is_fatal(&e) {
+										Err(e)?
+									} else {
+										tracing::debug!(error = %e, "Accumulator batch update failed, skipping batch");
+									}
 								}
 							}
 						} else if let Some(expr) = &agg.fallback_expr {
@@ -482,7 +487,11 @@ impl ExecOperator for Aggregate {
 										state.accumulators[field_idx].get_mut(agg_idx)
 										&& let Err(e) = acc.update(arg_value)
 									{
-										tracing::debug!(error = %e, "Accumulator update failed, skipping value");
+										if is_fatal(&e) {
+											Err(e)?
+										} else {
+											tracing::debug!(error = %e, "Accumulator update failed, skipping value");
+										}
 									}
 								}
 							} else if let Some(expr) = &agg.fallback_expr {
@@ -676,6 +685,17 @@ fn set_nested_value(obj: &mut Object, path: &[String], value: Value) {
 	}
 }
 
+/// Whether an accumulator error fails the query, rather than skipping the value.
+///
+/// A module aggregate which exceeded its limits, or which was interrupted
+/// because the query was cancelled or timed out, has no meaningful result.
+fn is_fatal(error: &anyhow::Error) -> bool {
+	matches!(
+		error.downcast_ref::<Error>(),
+		Some(Error::ModuleLimitExceeded(_) | Error::QueryTimedout(_) | Error::QueryCancelled)
+	)
+}
+
 /// Compute the final value for a field with aggregate expressions.
 ///
 /// If there's a post_expr, builds a document with all aggregate results
@@ -695,6 +715,7 @@ async fn compute_aggregate_field_value(
 	for (idx, acc) in accumulators.iter().enumerate() {
 		let value = match acc.finalize() {
 			Ok(v) => v,
+			Err(e) if is_fatal(&e) => return Err(e.into()),
 			Err(e) => {
 				tracing::debug!(error = %e, idx, "Accumulator finalize failed, using Null");
 				Value::Null
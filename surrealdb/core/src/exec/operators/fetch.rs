@@ -273,8 +273,8 @@ pub(crate) async fn fetch_raw_record(
 	Ok(Some(record.data.clone()))
 }
 
-/// Process a fetched record: check table-level permissions, evaluate computed
-/// fields, and apply field-level permissions.
+/// Process a fetched record: decrypt encrypted fields, check table-level
+/// permissions, evaluate computed fields, and apply field-level permissions.
 ///
 /// Returns `Ok(true)` if the record passes all permission checks, or
 /// `Ok(false)` if the record should be hidden (Deny or failed Conditional).
@@ -291,7 +291,14 @@ pub(crate) async fn process_fetched_record(
 	let check_perms =
 		should_check_perms(db_ctx, Action::View).context("Failed to check permissions")?;
 
-	// 1. Table-level permission check (on raw value, before computing fields)
+	// 1. Build FieldState (encrypted fields + computed fields + field permissions)
+	let field_state =
+		super::scan::pipeline::build_field_state(ctx, &rid.table, check_perms, None).await?;
+
+	// 2. Decrypt encrypted fields, so that permissions see the stored value
+	super::scan::pipeline::decrypt_fields_for_value(&field_state, val)?;
+
+	// 3. Table-level permission check (on raw value, before computing fields)
 	if check_perms {
 		let table_def =
 			db_ctx.get_table_def(&rid.table).await.context("Failed to get table definition")?;
@@ -314,14 +321,10 @@ pub(crate) async fn process_fetched_record(
 		}
 	}
 
-	// 2. Build FieldState (computed fields + field permissions)
-	let field_state =
-		super::scan::pipeline::build_field_state(ctx, &rid.table, check_perms, None).await?;
-
-	// 3. Evaluate computed fields via the modern PhysicalExpr path
+	// 4. Evaluate computed fields via the modern PhysicalExpr path
 	super::scan::pipeline::compute_fields_for_value(ctx, &field_state, val, false).await?;
 
-	// 4. Apply field-level permissions
+	// 5. Apply field-level permissions
 	if check_perms {
 		super::scan::pipeline::filter_fields_by_permission(ctx, &field_state, val).await?;
 	}
@@ -330,7 +333,7 @@ pub(crate) async fn process_fetched_record(
 }
 
 /// Fetch a single record by its ID without permission checks, but with
-/// computed fields evaluated.
+/// encrypted fields decrypted and computed fields evaluated.
 ///
 /// Used during permission predicate evaluation to prevent reentrant
 /// permission checks that would recurse infinitely on cyclic links.
@@ -345,6 +348,7 @@ pub(crate) async fn fetch_record_no_perms(
 	};
 	let field_state =
 		super::scan::pipeline::build_field_state(ctx, &rid.table, false, None).await?;
+	super::scan::pipeline::decrypt_fields_for_value(&field_state, &mut val)?;
 	super::scan::pipeline::compute_fields_for_value(ctx, &field_state, &mut val, true).await?;
 	Ok(val)
 }
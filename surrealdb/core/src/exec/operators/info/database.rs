@@ -7,6 +7,7 @@
 //! - Buckets
 //! - Functions
 //! - Modules
+//! - Jobs
 //! - Models
 //! - Params
 //! - Tables
@@ -21,9 +22,10 @@ use futures::stream;
 use surrealdb_types::ToSql;
 
 use crate::catalog::providers::{
-	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, TableProvider,
-	UserProvider,
+	ApiProvider, AuthorisationProvider, BucketProvider, ConsumerProvider, DatabaseProvider,
+	JobProvider, KeyProvider, SinkProvider, TableProvider, UserProvider,
 };
+use crate::cf;
 use crate::exec::context::{ContextLevel, ExecutionContext};
 use crate::exec::physical_expr::{EvalContext, PhysicalExpr};
 use crate::exec::{
@@ -32,6 +34,7 @@ use crate::exec::{
 };
 use crate::expr::statements::info::InfoStructure;
 use crate::iam::{Action, ResourceKind};
+use crate::job;
 use crate::val::{Datetime, Object, Value};
 
 /// Database INFO operator.
@@ -166,12 +169,16 @@ async fn execute_database_info(
 			"buckets".to_string() => process(txn.all_db_buckets(ns, db).await?),
 			"functions".to_string() => process(txn.all_db_functions(ns, db).await?),
 			"modules".to_string() => process(txn.all_db_modules(ns, db).await?),
+			"jobs".to_string() => job::structure(&txn, ns, db).await?,
+			"keys".to_string() => process(txn.all_db_keys(ns, db).await?),
 			"models".to_string() => process(txn.all_db_models(ns, db).await?),
 			"params".to_string() => process(txn.all_db_params(ns, db).await?),
 			"tables".to_string() => process(txn.all_tb(ns, db, version).await?),
 			"users".to_string() => process(txn.all_db_users(ns, db).await?),
 			"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
+			"consumers".to_string() => cf::consumer::structure(&txn, ns, db).await?,
 			"sequences".to_string() => process(txn.all_db_sequences(ns, db).await?),
+			"sinks".to_string() => cf::sink::structure(&txn, ns, db).await?,
 		};
 		Ok(Value::Object(Object(object)))
 	} else {
@@ -218,6 +225,20 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"jobs".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_jobs(ns, db).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
+			"keys".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_keys(ns, db).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"models".to_string() => {
 				let mut out = Object::default();
 				for v in txn.all_db_models(ns, db).await?.iter() {
@@ -260,6 +281,20 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"sinks".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_sinks(ns, db).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
+			"consumers".to_string() => {
+				let mut out = Object::default();
+				for v in txn.all_db_consumers(ns, db).await?.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 		};
 		Ok(Value::Object(Object(object)))
 	}
@@ -5,11 +5,12 @@
 
 use std::sync::Arc;
 
+use super::pipeline::{FieldState, build_field_state, decrypt_fields_for_value};
 use crate::catalog::{DatabaseId, NamespaceId};
 use crate::exec::{ControlFlowExt, EvalContext, ExecutionContext, PhysicalExpr};
 use crate::expr::ControlFlow;
 use crate::kvs::{CachePolicy, Transaction};
-use crate::val::{RecordId, RecordIdKey, Value};
+use crate::val::{RecordId, RecordIdKey, TableName, Value};
 
 /// Default batch size for collecting records before yielding downstream.
 pub(crate) const BATCH_SIZE: usize = 1000;
@@ -161,12 +162,25 @@ pub(crate) async fn fetch_and_filter_records_batch(
 		.await
 		.context("Failed to fetch records")?;
 
+	// Encrypted fields are resolved once per table
+	let mut field_state: Option<(&TableName, FieldState)> = None;
 	let mut values = Vec::with_capacity(rids.len());
-	for record in records {
+	for (rid, mut record) in rids.iter().zip(records) {
 		if record.data.is_none() {
 			continue;
 		}
 
+		// Decrypt any encrypted fields before the permission checks
+		if field_state.as_ref().is_none_or(|(tb, _)| **tb != rid.table) {
+			let state = build_field_state(ctx, &rid.table, false, None).await?;
+			field_state = Some((&rid.table, state));
+		}
+		if let Some((_, state)) = &field_state
+			&& !state.encrypted_fields.is_empty()
+		{
+			decrypt_fields_for_value(state, &mut Arc::make_mut(&mut record).data)?;
+		}
+
 		if check_perms {
 			// Permission checks need a reference; avoid moving data out of
 			// the Arc until we know the record is allowed.
@@ -555,7 +555,10 @@ async fn resolve_table_scan_stream(
 		None => None,
 	};
 
-	let access_path = if matches!(&cfg.with, Some(With::NoIndex)) {
+	// Versioned queries scan the table when indexes are not versioned
+	let access_path = if matches!(&cfg.with, Some(With::NoIndex))
+		|| (version_stamp.is_some() && !txn.versioned_indexes())
+	{
 		None
 	} else {
 		let db_ctx =
@@ -4,8 +4,8 @@
 //! (DynamicScan, TableScan, RecordIdScan, etc.):
 //!
 //! - [`ScanPipeline`] — per-batch filter + computed-fields + limit/start pipeline
-//! - [`FieldState`] / [`ComputedFieldDef`] — cached field definitions
-//! - [`build_field_state`] — resolves computed fields and field permissions
+//! - [`FieldState`] / [`ComputedFieldDef`] / [`EncryptedFieldDef`] — cached field definitions
+//! - [`build_field_state`] — resolves encrypted fields, computed fields and field permissions
 //! - [`filter_and_process_batch`] — single-pass permission + field processing
 //! - [`kv_scan_stream`] / [`decode_record`] — raw KV range scan helpers
 //! - [`range_start_key`] / [`range_end_key`] — RecordId range key encoding
@@ -18,8 +18,8 @@ use std::sync::Arc;
 
 use futures::StreamExt;
 
-use crate::catalog::providers::TableProvider;
-use crate::catalog::{DatabaseId, NamespaceId};
+use crate::catalog::providers::{KeyProvider, TableProvider};
+use crate::catalog::{DatabaseId, KeyDefinition, NamespaceId};
 use crate::exec::permission::{
 	PhysicalPermission, check_permission_for_value, convert_permission_to_physical,
 };
@@ -62,8 +62,8 @@ pub(crate) struct ScanPipeline {
 
 impl ScanPipeline {
 	/// Check whether any post-decode processing (permission filtering,
-	/// computed fields, field-level permissions, or WHERE predicate) is
-	/// needed.
+	/// field decryption, computed fields, field-level permissions, or WHERE
+	/// predicate) is needed.
 	///
 	/// This is cached internally so that [`process_batch`] can skip work
 	/// when nothing is needed.
@@ -74,6 +74,7 @@ impl ScanPipeline {
 		predicate: Option<&Arc<dyn PhysicalExpr>>,
 	) -> bool {
 		!matches!(permission, PhysicalPermission::Allow)
+			|| !field_state.encrypted_fields.is_empty()
 			|| !field_state.computed_fields.is_empty()
 			|| (check_perms && !field_state.field_permissions.is_empty())
 			|| predicate.is_some()
@@ -293,7 +294,7 @@ macro_rules! check_perm {
 /// Combined single-pass filter and process for a batch of decoded values.
 ///
 /// Per-record pipeline (sequential, in-place):
-///   table permission -> computed fields -> WHERE predicate -> field permissions.
+///   decryption -> table permission -> computed fields -> field permissions -> WHERE predicate.
 /// Records that fail any check are compacted out via an in-place swap so the
 /// surviving prefix can be truncated at the end with no extra allocation.
 pub(crate) async fn filter_and_process_batch(
@@ -307,8 +308,10 @@ pub(crate) async fn filter_and_process_batch(
 	let needs_perm_filter = !matches!(permission, PhysicalPermission::Allow);
 
 	// Fast path: when only the predicate is active (no permissions, no
-	// computed fields), use evaluate_batch for potentially better throughput.
+	// encrypted or computed fields), use evaluate_batch for potentially
+	// better throughput.
 	if !needs_perm_filter
+		&& state.encrypted_fields.is_empty()
 		&& state.computed_fields.is_empty()
 		&& (!check_perms || state.field_permissions.is_empty())
 		&& let Some(pred) = predicate
@@ -330,6 +333,8 @@ pub(crate) async fn filter_and_process_batch(
 
 	let mut write_idx = 0;
 	for read_idx in 0..batch.len() {
+		// Encrypted fields (must run before anything reads the document)
+		decrypt_fields_for_value(state, &mut batch[read_idx])?;
 		// Table-level permission (skip if Allow)
 		if needs_perm_filter && !check_perm!(permission, &batch[read_idx], ctx)? {
 			continue;
@@ -444,7 +449,8 @@ pub(crate) async fn eval_limit_expr(
 // Field state
 // =============================================================================
 
-/// Cached state for field processing (computed fields and permissions).
+/// Cached state for field processing (encrypted fields, computed fields and
+/// permissions).
 /// Initialized on first batch and reused for subsequent batches.
 ///
 /// `field_permissions` and `dep_map` are wrapped in `Arc` so that
@@ -452,6 +458,8 @@ pub(crate) async fn eval_limit_expr(
 /// copies without cloning the underlying `HashMap`.
 #[derive(Debug, Clone)]
 pub(crate) struct FieldState {
+	/// Encrypted field definitions, along with their keys
+	pub(crate) encrypted_fields: Vec<EncryptedFieldDef>,
 	/// Computed field definitions converted to physical expressions
 	pub(crate) computed_fields: Vec<ComputedFieldDef>,
 	/// Field-level permissions (field name -> permission)
@@ -463,9 +471,11 @@ pub(crate) struct FieldState {
 }
 
 impl FieldState {
-	/// Create an empty field state with no computed fields or field permissions.
+	/// Create an empty field state with no encrypted fields, computed fields or
+	/// field permissions.
 	pub(crate) fn empty() -> Self {
 		Self {
+			encrypted_fields: Vec::new(),
 			computed_fields: Vec::new(),
 			field_permissions: Arc::new(HashMap::new()),
 			dep_map: Arc::new(HashMap::new()),
@@ -484,6 +494,15 @@ pub(crate) struct ComputedFieldDef {
 	kind: Option<crate::expr::Kind>,
 }
 
+/// An encrypted field definition, with the key needed to decrypt it.
+#[derive(Debug, Clone)]
+pub(crate) struct EncryptedFieldDef {
+	/// The encrypted field
+	field: crate::expr::Idiom,
+	/// The key which the field is encrypted with
+	key: Arc<KeyDefinition>,
+}
+
 /// Build field state from raw transaction and context parameters.
 ///
 /// This is the core implementation that does the actual work: KV lookup of
@@ -503,6 +522,21 @@ pub(crate) async fn build_field_state_raw(
 		.await
 		.context("Failed to get field definitions")?;
 
+	// Resolve the keys of any encrypted fields
+	let mut encrypted_fields = Vec::new();
+	for fd in field_defs.iter() {
+		if let Some(ref key) = fd.encrypted {
+			let key = txn
+				.expect_db_key(ns_id, db_id, key)
+				.await
+				.context("Failed to get encryption key")?;
+			encrypted_fields.push(EncryptedFieldDef {
+				field: fd.name.clone(),
+				key,
+			});
+		}
+	}
+
 	// Fast path: if there are no computed fields and no field-level permissions
 	// that need checking, skip the expensive resolution. Both Permission::None
 	// (deny) and Permission::Specific (conditional) require enforcement.
@@ -512,7 +546,10 @@ pub(crate) async fn build_field_state_raw(
 			.iter()
 			.any(|fd| !matches!(fd.select_permission, crate::catalog::Permission::Full));
 	if !has_computed && !has_field_perms {
-		return Ok(FieldState::empty());
+		return Ok(FieldState {
+			encrypted_fields,
+			..FieldState::empty()
+		});
 	}
 
 	// Collect ALL computed fields and their dependency metadata.
@@ -571,6 +608,7 @@ pub(crate) async fn build_field_state_raw(
 	}
 
 	Ok(FieldState {
+		encrypted_fields,
 		computed_fields,
 		field_permissions: Arc::new(field_permissions),
 		dep_map: Arc::new(dep_map),
@@ -648,12 +686,24 @@ pub(crate) fn filter_field_state_for_projection(
 	};
 
 	FieldState {
+		encrypted_fields: full_state.encrypted_fields.clone(),
 		computed_fields,
 		field_permissions: Arc::clone(&full_state.field_permissions),
 		dep_map: Arc::clone(&full_state.dep_map),
 	}
 }
 
+/// Decrypt all encrypted fields of a single value, as read from storage.
+pub(crate) fn decrypt_fields_for_value(
+	state: &FieldState,
+	value: &mut Value,
+) -> Result<(), ControlFlow> {
+	for ef in &state.encrypted_fields {
+		ef.key.decrypt_field(value, &ef.field)?;
+	}
+	Ok(())
+}
+
 /// Compute all computed fields for a single value.
 ///
 /// When `skip_fetch_perms` is `true`, any RecordId dereferences inside
@@ -77,8 +77,9 @@ impl PhysicalExpr for SurrealismModuleExec {
 		// Get the executable and signature
 		let executable: ModuleExecutable = val.executable.clone().into();
 		let frozen_ctx = ctx.exec_ctx.ctx();
-		let signature =
-			executable.signature(frozen_ctx, &ns_id, &db_id, self.sub.as_deref()).await?;
+		let signature = executable
+			.signature(frozen_ctx, &ns_id, &db_id, self.sub.as_deref(), &val.limits)
+			.await?;
 
 		// Evaluate all arguments
 		let args = evaluate_args(&self.arguments, ctx.clone()).await?;
@@ -127,6 +128,7 @@ impl PhysicalExpr for SurrealismModuleExec {
 					doc.as_ref(),
 					coerced_args,
 					self.sub.as_deref(),
+					&val.limits,
 				)
 			})
 			.finish()
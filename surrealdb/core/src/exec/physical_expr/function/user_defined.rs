@@ -60,6 +60,9 @@ impl PhysicalExpr for UserDefinedFunctionExec {
 			.get_db_function(ns_id, db_id, &self.name)
 			.await
 			.map_err(|e| anyhow::anyhow!("Function '{}' not found: {}", func_name, e))?;
+		if func_def.aggregate.is_some() {
+			return Err(crate::expr::function::aggregate_call_error(func_name).into());
+		}
 
 		// 4. Apply auth limiting — cap the caller's privileges to the definer's auth level,
 		//    matching the old compute path behaviour.
@@ -2,7 +2,8 @@
 //!
 //! MATCHES is purely index-driven: it checks whether a record is in the
 //! full-text index's hit set for the given query, using `get_doc_id()` +
-//! `contains_doc()` (a KV lookup + bitmap check). There is no slow
+//! `matches_doc()` (a KV lookup + bitmap check, plus a check of the term
+//! positions for phrase and proximity queries). There is no slow
 //! tokenization fallback.
 //!
 //! When no full-text index exists for the field, evaluation returns `false`
@@ -29,7 +30,7 @@ use crate::val::Value;
 ///
 /// Evaluation mirrors the old executor's `fulltext_matches_with_doc_id` path:
 /// 1. Resolve `RecordId → DocId` via `fti.get_doc_id()`
-/// 2. Check `qt.contains_doc(doc_id)` (bitmap check)
+/// 2. Check `fti.matches_doc(doc_id)` (bitmap check, and term positions)
 ///
 /// Returns `false` when no full-text index exists for the field.
 pub struct MatchesOp {
@@ -196,10 +197,10 @@ impl PhysicalExpr for MatchesOp {
 
 		let tx = ctx.txn();
 
-		// Resolve RecordId → DocId via the full-text index, then bitmap check.
+		// Resolve RecordId → DocId via the full-text index, then check the query.
 		// This mirrors the old executor's `fulltext_matches_with_doc_id` path.
 		let matches = match fti.get_doc_id(&tx, &rid).await? {
-			Some(doc_id) => qt.contains_doc(doc_id),
+			Some(doc_id) => fti.matches_doc(&tx, qt, self.operator.operator, doc_id).await?,
 			// Record not in the index → doesn't match
 			None => false,
 		};
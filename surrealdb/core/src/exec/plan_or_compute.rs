@@ -297,7 +297,9 @@ pub(crate) fn expr_required_context(expr: &Expr) -> ContextLevel {
 		| Expr::Upsert(_)
 		| Expr::Delete(_)
 		| Expr::Relate(_)
-		| Expr::Insert(_) => ContextLevel::Database,
+		| Expr::Insert(_)
+		| Expr::Run(_)
+		| Expr::History(_) => ContextLevel::Database,
 
 		// DDL statements need a database
 		Expr::Define(_) | Expr::Remove(_) | Expr::Alter(_) | Expr::Rebuild(_) => {
@@ -203,6 +203,12 @@ impl<'ctx> Planner<'ctx> {
 			Expr::Rebuild(_) => Err(Error::PlannerUnsupported(
 				"REBUILD statements not yet supported in execution plans".to_string(),
 			)),
+			Expr::Run(_) => Err(Error::PlannerUnsupported(
+				"RUN statements not yet supported in execution plans".to_string(),
+			)),
+			Expr::History(_) => Err(Error::PlannerUnsupported(
+				"SHOW HISTORY statements not yet supported in execution plans".to_string(),
+			)),
 			Expr::Alter(_) => Err(Error::PlannerUnsupported(
 				"ALTER statements not yet supported in execution plans".to_string(),
 			)),
@@ -317,7 +323,9 @@ impl<'ctx> Planner<'ctx> {
 			| Expr::Upsert(_)
 			| Expr::Delete(_)
 			| Expr::Relate(_)
-			| Expr::Insert(_) => Err(Error::PlannerUnsupported(
+			| Expr::Insert(_)
+			| Expr::Run(_)
+			| Expr::History(_) => Err(Error::PlannerUnsupported(
 				"DML subqueries not yet supported in execution plans".to_string(),
 			)),
 		}
@@ -862,7 +870,9 @@ impl<'ctx> Planner<'ctx> {
 				| Expr::Upsert(_)
 				| Expr::Delete(_)
 				| Expr::Insert(_)
-				| Expr::Relate(_) => Err(Error::PlannerUnsupported(
+				| Expr::Relate(_)
+				| Expr::Run(_)
+				| Expr::History(_) => Err(Error::PlannerUnsupported(
 					"DML statements not yet supported in execution plans".to_string(),
 				)),
 				Expr::Define(_) | Expr::Remove(_) | Expr::Rebuild(_) | Expr::Alter(_) => {
@@ -1081,6 +1091,8 @@ macro_rules! try_plan_expr {
 				| $crate::expr::Expr::Define(_)
 				| $crate::expr::Expr::Remove(_)
 				| $crate::expr::Expr::Rebuild(_)
+				| $crate::expr::Expr::Run(_)
+				| $crate::expr::Expr::History(_)
 				| $crate::expr::Expr::Alter(_)
 		) {
 			Err($crate::err::Error::PlannerUnsupported(String::new()))
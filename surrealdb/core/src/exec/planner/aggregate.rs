@@ -7,11 +7,12 @@ use std::sync::Arc;
 use super::Planner;
 use super::util::{derive_field_name, idiom_to_field_name};
 use crate::err::Error;
+use crate::exec::function::ModuleAggregates;
 use crate::exec::operators::{
 	AggregateExprInfo, AggregateField, ExtractedAggregate, aggregate_field_name,
 };
 use crate::expr::field::{Field, Fields};
-use crate::expr::visit::{MutVisitor, VisitMut};
+use crate::expr::visit::{MutVisitor, Visit, VisitMut, Visitor};
 use crate::expr::{Expr, Function, FunctionCall, Idiom, Literal};
 
 // ============================================================================
@@ -148,8 +149,10 @@ impl<'ctx> Planner<'ctx> {
 		mut expr: Expr,
 	) -> Result<(Option<AggregateExprInfo>, Option<Arc<dyn crate::exec::PhysicalExpr>>), Error> {
 		let registry = self.function_registry();
+		self.reject_custom_aggregates(&expr).await?;
+		let modules = self.module_aggregates(&expr).await?;
 
-		let mut extractor = AggregateExtractor::new(registry);
+		let mut extractor = AggregateExtractor::new(registry, &modules);
 		let _ = extractor.visit_mut_expr(&mut expr);
 
 		if let Some(err) = extractor.error {
@@ -177,11 +180,15 @@ impl<'ctx> Planner<'ctx> {
 		}
 
 		let mut extracted_aggregates = Vec::new();
-		for (name, call) in extractor.aggregates {
-			let func = if name.as_str() == "count" {
-				registry.get_count_aggregate(!call.arguments.is_empty())
-			} else {
-				registry.get_aggregate(&name).expect("aggregate function should exist").clone()
+		for call in extractor.aggregates {
+			let func = match &call.receiver {
+				Function::Normal(name) if name.as_str() == "count" => {
+					registry.get_count_aggregate(!call.arguments.is_empty())
+				}
+				function => registry
+					.get_function_aggregate(function, &modules)
+					.expect("aggregate function should exist")
+					.clone(),
 			};
 
 			let mut args = call.arguments.into_iter();
@@ -224,6 +231,73 @@ impl<'ctx> Planner<'ctx> {
 			None,
 		))
 	}
+
+	/// Register the aggregates among the module functions called in an expression.
+	///
+	/// Whether a module function is an aggregate is only known once its module
+	/// is loaded, which requires plan-time catalog access. Without it, the
+	/// query falls back to the compute path.
+	#[cfg(feature = "surrealism")]
+	async fn module_aggregates(&self, expr: &Expr) -> Result<ModuleAggregates, Error> {
+		let mut collector = ModuleCallCollector::default();
+		let _ = collector.visit_expr(expr);
+
+		let mut aggregates = ModuleAggregates::default();
+		if collector.functions.is_empty() {
+			return Ok(aggregates);
+		}
+
+		let (Some(txn), Some(ids)) = (&self.txn, self.ns_db_ids().await) else {
+			return Err(Error::PlannerUnsupported(
+				"Module functions in GROUP BY queries require plan-time catalog access".to_string(),
+			));
+		};
+
+		for function in &collector.functions {
+			aggregates.register(self.ctx, txn, ids, function).await?;
+		}
+
+		Ok(aggregates)
+	}
+
+	#[cfg(not(feature = "surrealism"))]
+	async fn module_aggregates(&self, _expr: &Expr) -> Result<ModuleAggregates, Error> {
+		Ok(ModuleAggregates::default())
+	}
+
+	/// Reject the aggregates defined with `DEFINE AGGREGATE` among the custom
+	/// functions called in an expression.
+	///
+	/// Their clauses run SurrealQL against a per group state, which is only
+	/// supported by the compute path, so the query falls back to it.
+	async fn reject_custom_aggregates(&self, expr: &Expr) -> Result<(), Error> {
+		use crate::catalog::providers::DatabaseProvider;
+
+		let mut collector = CustomCallCollector::default();
+		let _ = collector.visit_expr(expr);
+		if collector.names.is_empty() {
+			return Ok(());
+		}
+
+		let (Some(txn), Some((ns, db))) = (&self.txn, self.ns_db_ids().await) else {
+			return Err(Error::PlannerUnsupported(
+				"Custom functions in GROUP BY queries require plan-time catalog access".to_string(),
+			));
+		};
+
+		for name in collector.names {
+			// The function is reported when called if it doesn't exist
+			if let Ok(def) = txn.get_db_function(ns, db, &name).await
+				&& def.aggregate.is_some()
+			{
+				return Err(Error::PlannerUnsupported(format!(
+					"User-defined aggregate 'fn::{name}' is only supported by the compute path"
+				)));
+			}
+		}
+
+		Ok(())
+	}
 }
 
 // ============================================================================
@@ -260,16 +334,21 @@ pub(super) fn find_group_key_index(
 /// Visitor that extracts aggregate functions from an expression.
 struct AggregateExtractor<'a> {
 	registry: &'a crate::exec::function::FunctionRegistry,
-	aggregates: Vec<(String, FunctionCall)>,
+	modules: &'a ModuleAggregates,
+	aggregates: Vec<FunctionCall>,
 	aggregate_count: usize,
 	inside_aggregate: bool,
 	error: Option<Error>,
 }
 
 impl<'a> AggregateExtractor<'a> {
-	fn new(registry: &'a crate::exec::function::FunctionRegistry) -> Self {
+	fn new(
+		registry: &'a crate::exec::function::FunctionRegistry,
+		modules: &'a ModuleAggregates,
+	) -> Self {
 		Self {
 			registry,
+			modules,
 			aggregates: Vec::new(),
 			aggregate_count: 0,
 			inside_aggregate: false,
@@ -277,11 +356,13 @@ impl<'a> AggregateExtractor<'a> {
 		}
 	}
 
+	fn is_aggregate(&self, function: &Function) -> bool {
+		self.registry.get_function_aggregate(function, self.modules).is_some()
+	}
+
 	fn contains_aggregate_call(&self, expr: &Expr) -> bool {
-		if let Expr::FunctionCall(func_call) = expr
-			&& let Function::Normal(name) = &func_call.receiver
-		{
-			return self.registry.get_aggregate(name.as_str()).is_some();
+		if let Expr::FunctionCall(func_call) = expr {
+			return self.is_aggregate(&func_call.receiver);
 		}
 		false
 	}
@@ -295,17 +376,16 @@ impl MutVisitor for AggregateExtractor<'_> {
 			return Ok(());
 		}
 
-		if let Expr::FunctionCall(func_call) = expr
-			&& let Function::Normal(name) = &func_call.receiver
-		{
-			if name.as_str() == "array::distinct"
+		if let Expr::FunctionCall(func_call) = expr {
+			if let Function::Normal(name) = &func_call.receiver
+				&& name.as_str() == "array::distinct"
 				&& !func_call.arguments.is_empty()
 				&& self.contains_aggregate_call(&func_call.arguments[0])
 			{
 				return expr.visit_mut(self);
 			}
 
-			if self.registry.get_aggregate(name.as_str()).is_some() {
+			if self.is_aggregate(&func_call.receiver) {
 				if self.inside_aggregate {
 					self.error = Some(Error::Query {
 						message: "Nested aggregate functions are not supported".to_string(),
@@ -324,7 +404,7 @@ impl MutVisitor for AggregateExtractor<'_> {
 				}
 
 				let field_name = aggregate_field_name(self.aggregate_count);
-				self.aggregates.push((name.clone(), func_call.as_ref().clone()));
+				self.aggregates.push(func_call.as_ref().clone());
 				self.aggregate_count += 1;
 
 				*expr = Expr::Idiom(Idiom::field(field_name));
@@ -373,3 +453,66 @@ impl MutVisitor for AggregateExtractor<'_> {
 		Ok(())
 	}
 }
+
+// ============================================================================
+// ModuleCallCollector Visitor
+// ============================================================================
+
+/// Collects the module functions called in an expression, skipping subqueries.
+/// Used as the first pass before the module aggregates are resolved.
+#[cfg(feature = "surrealism")]
+#[derive(Default)]
+struct ModuleCallCollector {
+	functions: Vec<Function>,
+}
+
+#[cfg(feature = "surrealism")]
+impl Visitor for ModuleCallCollector {
+	type Error = std::convert::Infallible;
+
+	fn visit_function(&mut self, function: &Function) -> Result<(), Self::Error> {
+		if matches!(function, Function::Module(..) | Function::Silo { .. })
+			&& !self.functions.contains(function)
+		{
+			self.functions.push(function.clone());
+		}
+		function.visit(self)
+	}
+
+	fn visit_select(
+		&mut self,
+		_s: &crate::expr::statements::SelectStatement,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
+// ============================================================================
+// CustomCallCollector Visitor
+// ============================================================================
+
+/// Collects the custom functions called in an expression, skipping subqueries.
+#[derive(Default)]
+struct CustomCallCollector {
+	names: Vec<String>,
+}
+
+impl Visitor for CustomCallCollector {
+	type Error = std::convert::Infallible;
+
+	fn visit_function(&mut self, function: &Function) -> Result<(), Self::Error> {
+		if let Function::Custom(name) = function
+			&& !self.names.contains(name)
+		{
+			self.names.push(name.clone());
+		}
+		function.visit(self)
+	}
+
+	fn visit_select(
+		&mut self,
+		_s: &crate::expr::statements::SelectStatement,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
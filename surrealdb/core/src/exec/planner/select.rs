@@ -22,11 +22,11 @@ use super::util::{
 	SELECT_ITERATION_PARAMS, all_value_sources, check_forbidden_group_by_params, derive_field_name,
 	extract_bruteforce_knn, extract_count_field_names, extract_matches_context,
 	extract_record_id_point_lookup, extract_version, fold_condition_expressions,
-	get_effective_limit_literal, has_knn_k_operator, has_knn_operator, has_top_level_or,
-	idiom_to_field_name, idiom_to_field_path, index_covers_ordering, is_count_all_eligible,
-	is_indexed_count_eligible, order_is_scan_compatible, resolve_condition_params,
-	resolve_param_value, resolve_projection_field_idioms, strip_fts_condition,
-	strip_index_conditions, strip_knn_from_condition,
+	get_effective_limit_literal, has_knn_k_operator, has_knn_ktree_operator, has_knn_operator,
+	has_top_level_or, idiom_to_field_name, idiom_to_field_path, index_covers_ordering,
+	is_count_all_eligible, is_indexed_count_eligible, order_is_scan_compatible,
+	resolve_condition_params, resolve_param_value, resolve_projection_field_idioms,
+	strip_fts_condition, strip_index_conditions, strip_knn_from_condition,
 };
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::cnf::MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE;
@@ -127,7 +127,9 @@ impl<'ctx> Planner<'ctx> {
 	/// Mirrors `Context::try_ns_db_ids` but uses the planner's stored ns/db
 	/// strings instead of `Options` (which the planner doesn't have).
 	/// Returns None when the transaction or namespace/database is unavailable.
-	async fn ns_db_ids(&self) -> Option<(crate::catalog::NamespaceId, crate::catalog::DatabaseId)> {
+	pub(super) async fn ns_db_ids(
+		&self,
+	) -> Option<(crate::catalog::NamespaceId, crate::catalog::DatabaseId)> {
 		let (txn, ns, db) = match (&self.txn, &self.ns, &self.db) {
 			(Some(txn), Some(ns), Some(db)) => (txn, ns, db),
 			_ => return None,
@@ -1183,10 +1185,19 @@ impl<'ctx> Planner<'ctx> {
 			timeout,
 			explain: _,
 			tempfiles,
+			cache: _,
 		} = select;
 
 		let version = extract_version(version, self).await?;
 
+		// Versioned queries scan the table when indexes are not versioned
+		let with = match (&version, &self.txn) {
+			(Some(_), Some(txn)) if !txn.versioned_indexes() => {
+				Some(crate::expr::with::With::NoIndex)
+			}
+			_ => with,
+		};
+
 		// COUNT fast-path
 		if is_count_all_eligible(&fields, &group, &cond, &split, &order, &fetch, &omit, &what) {
 			use crate::exec::operators::CountScan;
@@ -1409,6 +1420,11 @@ impl<'ctx> Planner<'ctx> {
 
 		let (cond_for_index, cond_for_filter) = if has_knn {
 			let stripped = cond.as_ref().and_then(strip_knn_from_condition);
+			if stripped.as_ref().is_some_and(|c| has_knn_ktree_operator(&c.0)) {
+				return Err(Error::PlannerUnimplemented(
+					"Index-only KNN is not supported in the streaming executor".to_string(),
+				));
+			}
 			if stripped.as_ref().is_some_and(|c| has_knn_operator(&c.0)) {
 				return Err(Error::Query {
 					message: "KNN operators nested in OR/NOT expressions or mixed with \
@@ -2332,6 +2348,10 @@ impl<'ctx> Planner<'ctx> {
 			if let Some(path) = analyzer.try_containment_expansion(analysis_cond, direction) {
 				return Ok(Some((path, direction)));
 			}
+			// Try resolving geometry operators with a SPATIAL index
+			if let Some(path) = analyzer.try_spatial_expansion(analysis_cond, direction) {
+				return Ok(Some((path, direction)));
+			}
 			return Ok(Some((AccessPath::TableScan, direction)));
 		}
 
@@ -63,10 +63,10 @@ pub(crate) fn try_literal_to_value(
 			let values: Option<Vec<Value>> = arr.iter().map(try_expr_to_value).collect();
 			values.map(|v| Value::Array(v.into()))
 		}
+		Literal::Geometry(g) => Some(Value::Geometry(g.clone())),
 		// Types that cannot be converted without async or are unsupported
 		Literal::Bytes(_)
 		| Literal::Regex(_)
-		| Literal::Geometry(_)
 		| Literal::File(_)
 		| Literal::Object(_)
 		| Literal::Set(_)
@@ -298,6 +298,7 @@ pub(super) fn has_knn_operator(expr: &Expr) -> bool {
 	let mut checker = KnnOperatorChecker {
 		found_any: false,
 		found_k: false,
+		found_ktree: false,
 	};
 	let _ = checker.visit_expr(expr);
 	checker.found_any
@@ -311,15 +312,28 @@ pub(super) fn has_knn_k_operator(expr: &Expr) -> bool {
 	let mut checker = KnnOperatorChecker {
 		found_any: false,
 		found_k: false,
+		found_ktree: false,
 	};
 	let _ = checker.visit_expr(expr);
 	checker.found_k
 }
 
+/// Check if an expression contains an index-only KNN operator (`NearestNeighbor::KTree`).
+pub(super) fn has_knn_ktree_operator(expr: &Expr) -> bool {
+	let mut checker = KnnOperatorChecker {
+		found_any: false,
+		found_k: false,
+		found_ktree: false,
+	};
+	let _ = checker.visit_expr(expr);
+	checker.found_ktree
+}
+
 /// Visitor that detects the presence of KNN operators in an expression tree.
 struct KnnOperatorChecker {
 	found_any: bool,
 	found_k: bool,
+	found_ktree: bool,
 }
 
 impl Visitor for KnnOperatorChecker {
@@ -332,8 +346,10 @@ impl Visitor for KnnOperatorChecker {
 		} = expr
 		{
 			self.found_any = true;
-			if matches!(nn.as_ref(), NearestNeighbor::K(..)) {
-				self.found_k = true;
+			match nn.as_ref() {
+				NearestNeighbor::K(..) => self.found_k = true,
+				NearestNeighbor::KTree(_) => self.found_ktree = true,
+				NearestNeighbor::Approximate(..) => {}
 			}
 		}
 		expr.visit(self)
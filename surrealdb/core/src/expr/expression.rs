@@ -14,15 +14,16 @@ use crate::expr::closure::ClosureExpr;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::statements::{
 	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
-	RelateStatement, RemoveStatement, SelectStatement, SetStatement, UpdateStatement,
-	UpsertStatement,
+	HistoryStatement, IfelseStatement, InfoStatement, InsertStatement, OutputStatement,
+	RebuildStatement, RelateStatement, RemoveStatement, RunStatement, SelectStatement,
+	SetStatement, UpdateStatement, UpsertStatement,
 };
 use crate::expr::{
 	BinaryOperator, Block, Constant, ControlFlow, FlowResult, FunctionCall, Idiom, Literal, Mock,
 	ObjectEntry, Param, PostfixOperator, PrefixOperator, RecordIdKeyLit, RecordIdLit,
 };
 use crate::fnc;
+use crate::kvs::audit;
 use crate::types::PublicValue;
 use crate::val::{Array, Range, TableName, Value};
 
@@ -78,6 +79,8 @@ pub(crate) enum Expr {
 	Define(Box<DefineStatement>),
 	Remove(Box<RemoveStatement>),
 	Rebuild(Box<RebuildStatement>),
+	Run(Box<RunStatement>),
+	History(Box<HistoryStatement>),
 	Alter(Box<AlterStatement>),
 	Info(Box<InfoStatement>),
 	Foreach(Box<ForeachStatement>),
@@ -124,6 +127,7 @@ impl Expr {
 			Expr::Throw(expr) => expr.read_only(),
 			Expr::IfElse(s) => s.read_only(),
 			Expr::Select(s) => s.read_only(),
+			Expr::History(s) => s.read_only(),
 			Expr::Let(s) => s.read_only(),
 			Expr::Foreach(s) => s.read_only(),
 			Expr::Explain {
@@ -139,6 +143,7 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Run(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_) => false,
 		}
@@ -314,6 +319,8 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Run(_)
+			| Expr::History(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
@@ -429,19 +436,31 @@ impl Expr {
 				insert_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
 			Expr::Define(define_statement) => {
-				define_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
+				let res = define_statement.compute(stk, ctx, &opt, doc).await;
+				audit::audit_expr(ctx, &opt, self, &res);
+				res.map_err(ControlFlow::Err)
 			}
 			Expr::Remove(remove_statement) => {
-				remove_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
+				let res = remove_statement.compute(stk, ctx, &opt, doc).await;
+				audit::audit_expr(ctx, &opt, self, &res);
+				res.map_err(ControlFlow::Err)
 			}
 			Expr::Rebuild(rebuild_statement) => {
 				rebuild_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
+			Expr::Run(run_statement) => {
+				run_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
+			}
+			Expr::History(history_statement) => {
+				history_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
+			}
 			Expr::Upsert(upsert_statement) => {
 				upsert_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
 			Expr::Alter(alter_statement) => {
-				alter_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
+				let res = alter_statement.compute(stk, ctx, &opt, doc).await;
+				audit::audit_expr(ctx, &opt, self, &res);
+				res.map_err(ControlFlow::Err)
 			}
 			Expr::Info(info_statement) => {
 				info_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
@@ -762,6 +781,8 @@ impl Expr {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Run(_)
+			| Expr::History(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
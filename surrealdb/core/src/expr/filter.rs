@@ -1,7 +1,17 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::buc::BucketController;
+use crate::buc::store::ObjectKey;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
 use crate::expr::language::Language;
+use crate::idx::ft::analyzer::{stopwords, synonyms};
+use crate::val::File;
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -13,6 +23,80 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	Stopwords(Stopwords),
+	Synonyms(Synonyms, Option<FilterStage>),
+}
+
+/// The terms which are removed by a `STOPWORDS` filter
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Stopwords {
+	/// The built-in list of stop words of a language
+	Language(Language),
+	/// A custom list of stop words
+	Custom(Vec<String>),
+}
+
+/// The rules of a `SYNONYMS` filter
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Synonyms {
+	/// Rules which are specified in the analyzer
+	Rules(Vec<String>),
+	/// Rules which are read from a file in a bucket. The file is read when
+	/// the analyzer is defined, and its rules are stored with the analyzer.
+	File(File, Vec<String>),
+}
+
+/// The stage of the analysis in which a filter is applied
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum FilterStage {
+	Indexing,
+	Querying,
+}
+
+impl Filter {
+	/// Prepares a filter to be stored in an analyzer definition, reading the
+	/// rules of synonyms files from their bucket, and checking that the stop
+	/// words and synonyms of the filter can be used.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Filter> {
+		match self {
+			Filter::Stopwords(Stopwords::Language(language)) => {
+				stopwords::builtin(*language)?;
+			}
+			Filter::Synonyms(Synonyms::Rules(rules), _) => {
+				synonyms::Synonyms::new(rules)?;
+			}
+			Filter::Synonyms(Synonyms::File(file, _), stage) => {
+				let mut controller =
+					BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+				let Some(data) = controller.get(&ObjectKey::new(file.key.clone())).await? else {
+					bail!(Error::AnalyzerError(format!(
+						"The synonyms file {} does not exist",
+						file.display_inner()
+					)));
+				};
+				let Ok(data) = String::from_utf8(data.0.to_vec()) else {
+					bail!(Error::AnalyzerError(format!(
+						"The synonyms file {} is not valid UTF-8",
+						file.display_inner()
+					)));
+				};
+				let rules: Vec<String> = data.lines().map(str::to_string).collect();
+				synonyms::Synonyms::new(&rules)?;
+				return Ok(Filter::Synonyms(Synonyms::File(file.clone(), rules), *stage));
+			}
+			_ => {}
+		}
+		Ok(self.clone())
+	}
 }
 
 impl ToSql for Filter {
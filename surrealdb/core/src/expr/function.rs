@@ -114,6 +114,10 @@ impl Function {
 				// Get the function definition
 				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
 				let val = ctx.tx().get_db_function(ns, db, s).await?;
+				// Aggregates are only computed over the records of a group
+				if val.aggregate.is_some() {
+					return Err(ControlFlow::from(anyhow::Error::new(aggregate_call_error(name))));
+				}
 				let opt = AuthLimit::try_from(&val.auth_limit)?.limit_opt(opt);
 
 				// Check permissions
@@ -164,13 +168,15 @@ impl Function {
 
 				// Get the executable & signature
 				let executable: ModuleExecutable = val.executable.clone().into();
-				let signature = executable.signature(ctx, &ns, &db, sub.as_deref()).await?;
+				let signature =
+					executable.signature(ctx, &ns, &db, sub.as_deref(), &val.limits).await?;
 
 				// Validate the arguments
 				validate_args(&fnc_name, &args, &signature.args)?;
 
 				// Run the module
-				let result = executable.run(stk, ctx, opt, doc, args, sub.as_deref()).await?;
+				let result =
+					executable.run(stk, ctx, opt, doc, args, sub.as_deref(), &val.limits).await?;
 
 				// Validate the return value
 				validate_return(fnc_name, signature.returns.as_ref(), result)
@@ -199,13 +205,15 @@ impl Function {
 
 				// Get the executable & signature
 				let executable: ModuleExecutable = val.executable.clone().into();
-				let signature = executable.signature(ctx, &ns, &db, sub.as_deref()).await?;
+				let signature =
+					executable.signature(ctx, &ns, &db, sub.as_deref(), &val.limits).await?;
 
 				// Validate the arguments
 				validate_args(&fnc_name, &args, &signature.args)?;
 
 				// Run the module
-				let result = executable.run(stk, ctx, opt, doc, args, sub.as_deref()).await?;
+				let result =
+					executable.run(stk, ctx, opt, doc, args, sub.as_deref(), &val.limits).await?;
 
 				// Validate the return value
 				validate_return(fnc_name, signature.returns.as_ref(), result)
@@ -261,7 +269,15 @@ impl FunctionCall {
 	}
 }
 
-async fn check_perms(
+/// The error for a user-defined aggregate which is called outside of a `GROUP BY` selector.
+pub(crate) fn aggregate_call_error(name: String) -> Error {
+	Error::InvalidFunction {
+		name,
+		message: "The function is an aggregate, and can only be used within the selector of a GROUP BY query".to_string(),
+	}
+}
+
+pub(crate) async fn check_perms(
 	stk: &mut Stk,
 	ctx: &FrozenContext,
 	opt: &Options,
@@ -1,17 +1,27 @@
 #[cfg(feature = "surrealism")]
+use std::sync::Arc;
+#[cfg(feature = "surrealism")]
 use std::thread;
 
 use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 use surrealdb_types::{SqlFormat, ToSql};
+#[cfg(feature = "surrealism")]
+use surrealism_runtime::controller::{Controller, FunctionKind, Runtime};
+#[cfg(feature = "surrealism")]
+use surrealism_runtime::limits::{LimitError, SurrealismLimits};
 
 use crate::catalog;
-use crate::catalog::{DatabaseId, NamespaceId};
+use crate::catalog::{DatabaseId, ModuleLimits, NamespaceId};
 use crate::ctx::FrozenContext;
+#[cfg(feature = "surrealism")]
+use crate::ctx::reason::Reason;
 use crate::dbs::Options;
 #[cfg(feature = "surrealism")]
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::doc::CursorDoc;
+#[cfg(feature = "surrealism")]
+use crate::err::Error;
 use crate::expr::{Kind, Value};
 #[cfg(feature = "surrealism")]
 use crate::surrealism::cache::SurrealismCacheLookup;
@@ -19,6 +29,8 @@ use crate::surrealism::cache::SurrealismCacheLookup;
 use crate::surrealism::host::Host;
 #[cfg(feature = "surrealism")]
 use crate::surrealism::host::SignatureHost;
+#[cfg(feature = "surrealism")]
+use crate::types::PublicValue;
 use crate::val::File;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -56,15 +68,17 @@ impl ModuleExecutable {
 		ns: &NamespaceId,
 		db: &DatabaseId,
 		sub: Option<&str>,
+		limits: &ModuleLimits,
 	) -> Result<Signature> {
 		match self {
 			ModuleExecutable::Surrealism(surrealism) => {
-				surrealism.signature(ctx, ns, db, sub).await
+				surrealism.signature(ctx, ns, db, sub, limits).await
 			}
-			ModuleExecutable::Silo(silo) => silo.signature(ctx, sub).await,
+			ModuleExecutable::Silo(silo) => silo.signature(ctx, sub, limits).await,
 		}
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn run(
 		&self,
 		stk: &mut Stk,
@@ -73,14 +87,39 @@ impl ModuleExecutable {
 		doc: Option<&CursorDoc>,
 		args: Vec<Value>,
 		sub: Option<&str>,
+		limits: &ModuleLimits,
 	) -> Result<Value> {
 		match self {
 			ModuleExecutable::Surrealism(surrealism) => {
-				surrealism.run(stk, ctx, opt, doc, args, sub).await
+				surrealism.run(stk, ctx, opt, doc, args, sub, limits).await
 			}
-			ModuleExecutable::Silo(silo) => silo.run(stk, ctx, opt, doc, args, sub).await,
+			ModuleExecutable::Silo(silo) => silo.run(stk, ctx, opt, doc, args, sub, limits).await,
 		}
 	}
+
+	/// Returns the compiled runtime of the module.
+	#[cfg(feature = "surrealism")]
+	pub(crate) async fn runtime(
+		&self,
+		ctx: &FrozenContext,
+		ns: &NamespaceId,
+		db: &DatabaseId,
+		limits: &ModuleLimits,
+	) -> Result<Arc<Runtime>> {
+		let lookup = match self {
+			ModuleExecutable::Surrealism(surrealism) => {
+				SurrealismCacheLookup::File(ns, db, &surrealism.0.bucket, &surrealism.0.key)
+			}
+			ModuleExecutable::Silo(silo) => SurrealismCacheLookup::Silo(
+				&silo.organisation,
+				&silo.package,
+				silo.major,
+				silo.minor,
+				silo.patch,
+			),
+		};
+		ctx.get_surrealism_runtime(lookup, &SurrealismLimits::from(limits)).await
+	}
 }
 
 impl ToSql for ModuleExecutable {
@@ -94,6 +133,33 @@ impl ToSql for ModuleExecutable {
 pub(crate) struct Signature {
 	pub(crate) args: Vec<Kind>,
 	pub(crate) returns: Option<Kind>,
+	#[cfg_attr(not(feature = "surrealism"), allow(dead_code))]
+	pub(crate) kind: ModuleFunctionKind,
+}
+
+/// How a module function is called.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(not(feature = "surrealism"), allow(dead_code))]
+pub(crate) enum ModuleFunctionKind {
+	/// Called once, and returns a single value.
+	#[default]
+	Scalar,
+	/// Called with an array of the values to aggregate as its first argument.
+	/// In a `GROUP BY` query, it is computed incrementally for each group.
+	Aggregate,
+	/// Returns an array of objects, which can be selected from.
+	Table,
+}
+
+#[cfg(feature = "surrealism")]
+impl From<FunctionKind> for ModuleFunctionKind {
+	fn from(kind: FunctionKind) -> Self {
+		match kind {
+			FunctionKind::Scalar => ModuleFunctionKind::Scalar,
+			FunctionKind::Aggregate => ModuleFunctionKind::Aggregate,
+			FunctionKind::Table => ModuleFunctionKind::Table,
+		}
+	}
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -129,6 +195,7 @@ impl SurrealismExecutable {
 		ns: &NamespaceId,
 		db: &DatabaseId,
 		sub: Option<&str>,
+		limits: &ModuleLimits,
 	) -> Result<Signature> {
 		if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Surrealism) {
 			bail!(
@@ -137,29 +204,18 @@ impl SurrealismExecutable {
 		}
 
 		let lookup = SurrealismCacheLookup::File(ns, db, &self.0.bucket, &self.0.key);
-		let runtime = ctx.get_surrealism_runtime(lookup).await?;
+		let limits = SurrealismLimits::from(limits);
+		let runtime = ctx.get_surrealism_runtime(lookup, &limits).await?;
 
 		spawn_thread(move || async move {
 			let host = Box::new(SignatureHost::new());
-			let mut controller = runtime.new_controller(host).await?;
-
-			let args = controller
-				.args(sub.map(String::from))
-				.await?
-				.into_iter()
-				.map(|x| x.into())
-				.collect();
-
-			let returns =
-				controller.returns(sub.map(String::from)).await.map(|x| Some(x.into()))?;
-
-			Ok(Signature {
-				args,
-				returns,
-			})
+			let mut controller = runtime.new_controller_with_limits(host, &limits).await?;
+			read_signature(&mut controller, sub).await
 		})
+		.map_err(|e| limit_error(ctx, e))
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn run(
 		&self,
 		_stk: &mut Stk,
@@ -168,6 +224,7 @@ impl SurrealismExecutable {
 		doc: Option<&CursorDoc>,
 		args: Vec<Value>,
 		sub: Option<&str>,
+		limits: &ModuleLimits,
 	) -> Result<Value> {
 		if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Surrealism) {
 			bail!(
@@ -177,20 +234,19 @@ impl SurrealismExecutable {
 
 		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
 		let lookup = SurrealismCacheLookup::File(&ns, &db, &self.0.bucket, &self.0.key);
-		let runtime = ctx.get_surrealism_runtime(lookup).await?;
+		let limits = SurrealismLimits::from(limits);
+		let runtime = ctx.get_surrealism_runtime(lookup, &limits).await?;
 
-		let ctx = ctx.clone();
+		let frozen = ctx.clone();
 		let opt = opt.clone();
 		let doc = doc.cloned();
 		spawn_thread(move || async move {
-			let host = Box::new(Host::new(&ctx, &opt, doc.as_ref()));
-			let mut controller = runtime.new_controller(host).await?;
+			let host = Box::new(Host::new(&frozen, &opt, doc.as_ref()));
+			let mut controller = runtime.new_controller_with_limits(host, &limits).await?;
 
-			let args: Result<Vec<crate::types::PublicValue>, _> =
-				args.into_iter().map(|x| x.try_into()).collect();
-			let args = args?;
-			controller.invoke(sub.map(String::from), args).await.map(|x| x.into())
+			invoke(&mut controller, sub, args).await
 		})
+		.map_err(|e| limit_error(ctx, e))
 	}
 }
 
@@ -202,10 +258,12 @@ impl SurrealismExecutable {
 		_ns: &NamespaceId,
 		_db: &DatabaseId,
 		_sub: Option<&str>,
+		_limits: &ModuleLimits,
 	) -> Result<Signature> {
 		bail!("Surrealism modules are not supported in WASM environments")
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn run(
 		&self,
 		_stk: &mut Stk,
@@ -214,6 +272,7 @@ impl SurrealismExecutable {
 		_doc: Option<&CursorDoc>,
 		_args: Vec<Value>,
 		_sub: Option<&str>,
+		_limits: &ModuleLimits,
 	) -> Result<Value> {
 		bail!("Surrealism functions are not supported in WASM environments")
 	}
@@ -265,6 +324,7 @@ impl SiloExecutable {
 		&self,
 		ctx: &FrozenContext,
 		sub: Option<&str>,
+		limits: &ModuleLimits,
 	) -> Result<Signature> {
 		if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Surrealism) {
 			bail!(
@@ -279,29 +339,18 @@ impl SiloExecutable {
 			self.minor,
 			self.patch,
 		);
-		let runtime = ctx.get_surrealism_runtime(lookup).await?;
+		let limits = SurrealismLimits::from(limits);
+		let runtime = ctx.get_surrealism_runtime(lookup, &limits).await?;
 
 		spawn_thread(move || async move {
 			let host = Box::new(SignatureHost::new());
-			let mut controller = runtime.new_controller(host).await?;
-
-			let args = controller
-				.args(sub.map(String::from))
-				.await?
-				.into_iter()
-				.map(|x| x.into())
-				.collect();
-
-			let returns =
-				controller.returns(sub.map(String::from)).await.map(|x| Some(x.into()))?;
-
-			Ok(Signature {
-				args,
-				returns,
-			})
+			let mut controller = runtime.new_controller_with_limits(host, &limits).await?;
+			read_signature(&mut controller, sub).await
 		})
+		.map_err(|e| limit_error(ctx, e))
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn run(
 		&self,
 		_stk: &mut Stk,
@@ -310,6 +359,7 @@ impl SiloExecutable {
 		doc: Option<&CursorDoc>,
 		args: Vec<Value>,
 		sub: Option<&str>,
+		limits: &ModuleLimits,
 	) -> Result<Value> {
 		if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Surrealism) {
 			bail!(
@@ -324,20 +374,19 @@ impl SiloExecutable {
 			self.minor,
 			self.patch,
 		);
-		let runtime = ctx.get_surrealism_runtime(lookup).await?;
+		let limits = SurrealismLimits::from(limits);
+		let runtime = ctx.get_surrealism_runtime(lookup, &limits).await?;
 
-		let ctx = ctx.clone();
+		let frozen = ctx.clone();
 		let opt = opt.clone();
 		let doc = doc.cloned();
 		spawn_thread(move || async move {
-			let host = Box::new(Host::new(&ctx, &opt, doc.as_ref()));
-			let mut controller = runtime.new_controller(host).await?;
+			let host = Box::new(Host::new(&frozen, &opt, doc.as_ref()));
+			let mut controller = runtime.new_controller_with_limits(host, &limits).await?;
 
-			let args: Result<Vec<crate::types::PublicValue>, _> =
-				args.into_iter().map(|x| x.try_into()).collect();
-			let args = args?;
-			controller.invoke(sub.map(String::from), args).await.map(|x| x.into())
+			invoke(&mut controller, sub, args).await
 		})
+		.map_err(|e| limit_error(ctx, e))
 	}
 }
 
@@ -347,10 +396,12 @@ impl SiloExecutable {
 		&self,
 		_ctx: &FrozenContext,
 		_sub: Option<&str>,
+		_limits: &ModuleLimits,
 	) -> Result<Signature> {
 		bail!("Surrealism functions are not supported in WASM environments")
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn run(
 		&self,
 		_stk: &mut Stk,
@@ -359,18 +410,128 @@ impl SiloExecutable {
 		_doc: Option<&CursorDoc>,
 		_args: Vec<Value>,
 		_sub: Option<&str>,
+		_limits: &ModuleLimits,
 	) -> Result<Value> {
 		bail!("Surrealism functions are not supported in WASM environments")
 	}
 }
 
+#[cfg(feature = "surrealism")]
+impl From<&ModuleLimits> for SurrealismLimits {
+	fn from(limits: &ModuleLimits) -> Self {
+		Self {
+			fuel: limits.fuel,
+			memory: limits.memory,
+			timeout_ms: limits.timeout.map(|v| v.as_millis().try_into().unwrap_or(u64::MAX)),
+		}
+	}
+}
+
+/// Reads the signature of a function from a module.
+#[cfg(feature = "surrealism")]
+async fn read_signature(controller: &mut Controller, sub: Option<&str>) -> Result<Signature> {
+	let sub = sub.map(String::from);
+	// A module without the export is reported when its arguments are read
+	let kind = match controller.kind(sub.clone())? {
+		Some(kind) => ModuleFunctionKind::from(kind),
+		None => ModuleFunctionKind::Scalar,
+	};
+	let mut args: Vec<Kind> =
+		controller.args(sub.clone()).await?.into_iter().map(|x| x.into()).collect();
+	// An aggregate which is called directly receives all of its values at once
+	if kind == ModuleFunctionKind::Aggregate
+		&& let Some(first) = args.first_mut()
+	{
+		*first = Kind::Array(Box::new(first.clone()), None);
+	}
+	let returns = controller.returns(sub).await.map(|x| Some(x.into()))?;
+	Ok(Signature {
+		args,
+		returns,
+		kind,
+	})
+}
+
+/// Invokes a function of a module. An aggregate is computed over the array of
+/// values in its first argument, and a table function must return an array of
+/// objects.
+#[cfg(feature = "surrealism")]
+async fn invoke(controller: &mut Controller, sub: Option<&str>, args: Vec<Value>) -> Result<Value> {
+	let sub = sub.map(String::from);
+	let args: Result<Vec<PublicValue>, _> = args.into_iter().map(|x| x.try_into()).collect();
+	let mut args = args?;
+	match controller.kind(sub.clone())? {
+		Some(FunctionKind::Aggregate) => {
+			if args.is_empty() {
+				bail!("An aggregate function must be called with the values to aggregate");
+			}
+			let values = match args.remove(0) {
+				PublicValue::Array(values) => values.into_iter().collect(),
+				value => vec![value],
+			};
+			let state = accumulate(controller, sub.clone(), None, values, &args).await?;
+			controller.aggregate_finalize(sub, state).await.map(|x| x.into())
+		}
+		Some(FunctionKind::Table) => {
+			let result: Value = controller.invoke(sub, args).await?.into();
+			match &result {
+				Value::Array(rows) if rows.iter().all(|row| matches!(row, Value::Object(_))) => {
+					Ok(result)
+				}
+				_ => bail!("A table function must return an array of objects"),
+			}
+		}
+		_ => controller.invoke(sub, args).await.map(|x| x.into()),
+	}
+}
+
+/// Folds values into the state of an aggregate, starting from the state of an
+/// empty group when there is no state yet. The `extra` arguments are passed
+/// unchanged with every value.
+#[cfg(feature = "surrealism")]
+pub(crate) async fn accumulate(
+	controller: &mut Controller,
+	sub: Option<String>,
+	state: Option<PublicValue>,
+	values: Vec<PublicValue>,
+	extra: &[PublicValue],
+) -> Result<PublicValue> {
+	let mut state = match state {
+		Some(state) => state,
+		None => controller.aggregate_init(sub.clone()).await?,
+	};
+	for value in values {
+		let mut args = Vec::with_capacity(extra.len() + 1);
+		args.push(value);
+		args.extend(extra.iter().cloned());
+		state = controller.aggregate_accumulate(sub.clone(), state, args).await?;
+	}
+	Ok(state)
+}
+
+/// Converts the error of a module which exceeded one of its limits. A module
+/// which was interrupted because the query was cancelled, or timed out, fails
+/// with the same error as the query.
+#[cfg(feature = "surrealism")]
+pub(crate) fn limit_error(ctx: &FrozenContext, error: anyhow::Error) -> anyhow::Error {
+	match error.downcast::<LimitError>() {
+		Ok(LimitError::Cancelled) => match ctx.done(true) {
+			Ok(Some(Reason::Timedout(d))) => Error::QueryTimedout(d).into(),
+			Ok(_) => Error::QueryCancelled.into(),
+			Err(e) => e,
+		},
+		Ok(e) => Error::ModuleLimitExceeded(e.to_string()).into(),
+		Err(e) => e,
+	}
+}
+
 /// Spawn a dedicated thread to run async operations.
 ///
 /// Uses scoped threads to allow safe borrowing from the current scope without requiring
 /// 'static lifetime bounds. Creates a single-threaded tokio runtime in the thread to
 /// handle async operations. The function blocks until the spawned thread completes.
 #[cfg(feature = "surrealism")]
-fn spawn_thread<F, Fut, R>(f: F) -> Result<R>
+pub(crate) fn spawn_thread<F, Fut, R>(f: F) -> Result<R>
 where
 	F: FnOnce() -> Fut + Send,
 	Fut: std::future::Future<Output = Result<R>> + Send,
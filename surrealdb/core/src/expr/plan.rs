@@ -1,6 +1,7 @@
 use crate::expr::Expr;
 use crate::expr::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, AckStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement,
+	UseStatement,
 };
 
 #[derive(Clone, Debug)]
@@ -19,6 +20,7 @@ pub(crate) enum TopLevelExpr {
 	Option(OptionStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
+	Ack(AckStatement),
 	Expr(Expr),
 }
 
@@ -31,6 +33,7 @@ impl TopLevelExpr {
 			| TopLevelExpr::Commit
 			| TopLevelExpr::Show(_) => true,
 			TopLevelExpr::Kill(_)
+			| TopLevelExpr::Ack(_)
 			| TopLevelExpr::Live(_)
 			| TopLevelExpr::Option(_)
 			| TopLevelExpr::Use(_)
@@ -0,0 +1,73 @@
+use anyhow::{Result, bail, ensure};
+use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::ConsumerProvider;
+use crate::cf;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Base, Expr, FlowResultExt as _};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Datetime, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct AckStatement {
+	pub consumer: String,
+	// Versionstamp of the last change set read
+	// or Param resolving to the versionstamp
+	pub versionstamp: Expr,
+}
+
+impl AckStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "AckStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run? Acknowledging moves the offset of the consumer, which
+		// decides what its readers see and what the changefeed retains.
+		opt.is_allowed(Action::Edit, ResourceKind::Consumer, &Base::Db)?;
+		// Resolve the versionstamp
+		let vs = match stk
+			.run(|stk| self.versionstamp.compute(stk, ctx, opt, None))
+			.await
+			.catch_return()?
+			.coerce_to::<i64>()
+		{
+			Ok(vs) if vs >= 0 => vs as u64,
+			_ => {
+				bail!(Error::AckStatement {
+					value: self.versionstamp.to_sql(),
+				})
+			}
+		};
+		// Get the consumer definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		let cs = txn.expect_db_consumer(ns, db, &self.consumer).await?;
+		// A change set can't be acknowledged before it was written
+		let current = txn.timestamp().await?.as_versionstamp() as u64;
+		ensure!(
+			vs < current,
+			Error::AckVersionstampAhead {
+				value: vs,
+			}
+		);
+		// Move the offset past the acknowledged versionstamp. The offset
+		// never moves backwards, so that a late or repeated acknowledgement
+		// does not cause changes to be read again.
+		let mut offset = cf::consumer::offset(&txn, ns, db, &cs.name).await?;
+		offset.versionstamp = offset.versionstamp.max(vs.saturating_add(1));
+		offset.acknowledged = Some(Datetime::now());
+		let key = crate::key::database::co::new(ns, db, &cs.name);
+		txn.set(&key, &offset, None).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
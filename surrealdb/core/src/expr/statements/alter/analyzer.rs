@@ -1,12 +1,14 @@
 use std::ops::Deref;
 
 use anyhow::Result;
+use reblessive::tree::Stk;
 use surrealdb_types::{SqlFormat, ToSql};
 
 use super::AlterKind;
 use crate::catalog::providers::DatabaseProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
+use crate::doc::CursorDoc;
 use crate::expr::{Base, Filter, Tokenizer};
 use crate::iam::{Action, ResourceKind};
 use crate::val::Value;
@@ -23,7 +25,13 @@ pub(crate) struct AlterAnalyzerStatement {
 
 impl AlterAnalyzerStatement {
 	#[instrument(level = "trace", name = "AlterAnalyzerStatement::compute", skip_all)]
-	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
 		opt.is_allowed(Action::Edit, ResourceKind::Analyzer, &Base::Db)?;
 		let (_, _) = opt.ns_db()?;
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
@@ -52,7 +60,13 @@ impl AlterAnalyzerStatement {
 		}
 
 		match self.filters {
-			AlterKind::Set(ref v) => az.filters = Some(v.clone()),
+			AlterKind::Set(ref v) => {
+				let mut filters = Vec::with_capacity(v.len());
+				for f in v {
+					filters.push(f.compute(stk, ctx, opt, doc).await?);
+				}
+				az.filters = Some(filters);
+			}
 			AlterKind::Drop => az.filters = None,
 			AlterKind::None => {}
 		}
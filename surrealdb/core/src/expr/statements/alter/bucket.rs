@@ -4,8 +4,8 @@ use anyhow::Result;
 use surrealdb_types::{SqlFormat, ToSql};
 
 use super::AlterKind;
-use crate::catalog::Permission;
 use crate::catalog::providers::BucketProvider;
+use crate::catalog::{BucketDefinition, Permission};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
@@ -24,6 +24,15 @@ pub(crate) struct AlterBucketStatement {
 }
 
 impl AlterBucketStatement {
+	/// Remove the secrets from the backend URL, as they are redacted from the
+	/// definition of a bucket.
+	pub(crate) fn redact(mut self) -> Self {
+		if let AlterKind::Set(ref mut backend) = self.backend {
+			*backend = BucketDefinition::redact_backend(backend);
+		}
+		self
+	}
+
 	#[instrument(level = "trace", name = "AlterBucketStatement::compute", skip_all)]
 	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
 		opt.is_allowed(Action::Edit, ResourceKind::Bucket, &Base::Db)?;
@@ -45,7 +54,12 @@ impl AlterBucketStatement {
 		};
 
 		match self.backend {
-			AlterKind::Set(ref v) => bu.backend = Some(v.clone()),
+			AlterKind::Set(ref v) => {
+				// Check the backend connects to an allowed network target
+				#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+				crate::buc::check_backend_net(ctx, v).await?;
+				bu.backend = Some(v.clone())
+			}
 			AlterKind::Drop => bu.backend = None,
 			AlterKind::None => {}
 		}
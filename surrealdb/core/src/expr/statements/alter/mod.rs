@@ -131,6 +131,34 @@ pub(crate) enum AlterStatement {
 }
 
 impl AlterStatement {
+	/// Remove the secrets which are redacted from the catalog definitions, so
+	/// that the statement can be logged.
+	///
+	/// Every statement is matched explicitly, so that a new statement must
+	/// be considered here.
+	pub(crate) fn redact(self) -> Self {
+		match self {
+			Self::Bucket(v) => Self::Bucket(v.redact()),
+			// User passwords are already hashed when the statement is converted
+			v @ (Self::System(_)
+			| Self::Namespace(_)
+			| Self::Database(_)
+			| Self::Table(_)
+			| Self::Api(_)
+			| Self::Event(_)
+			| Self::Index(_)
+			| Self::Sequence(_)
+			| Self::Field(_)
+			| Self::Param(_)
+			| Self::Config(_)
+			| Self::Analyzer(_)
+			| Self::Function(_)
+			| Self::User(_)
+			| Self::Access(_)
+			| Self::Module(_)) => v,
+		}
+	}
+
 	/// Executes this statement, returning a simple value.
 	///
 	/// All `ALTER` statements currently return `Value::None` on success and may
@@ -156,7 +184,7 @@ impl AlterStatement {
 			Self::Param(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(ctx, opt).await,
 			Self::Config(v) => v.compute(stk, ctx, opt, doc).await,
-			Self::Analyzer(v) => v.compute(ctx, opt).await,
+			Self::Analyzer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Function(v) => v.compute(ctx, opt).await,
 			Self::User(v) => v.compute(ctx, opt).await,
 			Self::Access(v) => v.compute(ctx, opt).await,
@@ -1,4 +1,5 @@
 use std::ops::Deref;
+use std::time;
 
 use anyhow::Result;
 use surrealdb_types::{SqlFormat, ToSql};
@@ -8,6 +9,7 @@ use crate::catalog::providers::TableProvider;
 use crate::catalog::{Permissions, TableType};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
+use crate::doc;
 use crate::err::Error;
 use crate::expr::statements::DefineTableStatement;
 use crate::expr::{Base, ChangeFeed};
@@ -22,6 +24,7 @@ use crate::val::{TableName, Value};
 /// - update `PERMISSIONS`
 /// - set/drop `CHANGEFEED`
 /// - set/drop table `COMMENT`
+/// - set/drop record `TTL`
 /// - change table `TYPE` (`NORMAL`/`RELATION`/`ANY`)
 /// - request a table-level storage `COMPACT`
 ///
@@ -29,6 +32,7 @@ use crate::val::{TableName, Value};
 /// - When switching to a `RELATION` table type, in/out fields are created as needed via
 ///   `DefineTableStatement::add_in_out_fields`.
 /// - When `compact` is true, underlying storage for this table is compacted.
+/// - When the `TTL` is dropped, the records of the table no longer expire.
 pub(crate) struct AlterTableStatement {
 	/// Table name.
 	pub name: TableName,
@@ -42,6 +46,8 @@ pub(crate) struct AlterTableStatement {
 	pub(crate) changefeed: AlterKind<ChangeFeed>,
 	/// Set/drop human‑readable comment.
 	pub(crate) comment: AlterKind<String>,
+	/// Set/drop the time records are kept after they were last written.
+	pub(crate) ttl: AlterKind<time::Duration>,
 	/// Request a compaction of the table’s keyspace.
 	pub(crate) compact: bool,
 	/// Change the table type (`NORMAL` / `RELATION` / `ANY`).
@@ -57,6 +63,8 @@ impl AlterTableStatement {
 	/// - May write table definition metadata
 	/// - May compact the underlying storage if `compact` is true
 	/// - May create relation helper fields when switching to `RELATION`
+	/// - May remove the record expiries when dropping the `TTL`, or schedule the
+	///   expiry of the existing records when setting it
 	#[instrument(level = "trace", name = "AlterTableStatement::compute", skip_all)]
 	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
 		// Allowed to run?
@@ -109,6 +117,21 @@ impl AlterTableStatement {
 			AlterKind::None => {}
 		}
 
+		match self.ttl {
+			AlterKind::Set(x) => {
+				if dt.ttl.replace(x) != Some(x) {
+					doc::store_table_expiries(&txn, (ns_name, db_name), (ns, db), &self.name, x)
+						.await?;
+				}
+			}
+			AlterKind::Drop => {
+				if dt.ttl.take().is_some() {
+					DefineTableStatement::remove_record_expiries(&txn, ns, db, &self.name).await?;
+				}
+			}
+			AlterKind::None => {}
+		}
+
 		if let Some(kind) = &self.kind {
 			dt.table_type = kind.clone();
 		}
@@ -0,0 +1,106 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use super::DefineKind;
+use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
+use crate::catalog::{AggregateDefinition, FunctionDefinition, Permission};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Base, Block, Expr, FlowResultExt, Kind};
+use crate::iam::{Action, AuthLimit, ResourceKind};
+use crate::val::Value;
+
+/// Defines an aggregate function, which shares the `fn::` namespace with the
+/// functions defined with `DEFINE FUNCTION`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineAggregateStatement {
+	pub kind: DefineKind,
+	pub name: String,
+	pub args: Vec<(String, Kind)>,
+	pub returns: Option<Kind>,
+	pub state: Expr,
+	pub accumulate: Block,
+	pub merge: Block,
+	pub finalize: Block,
+	pub comment: Expr,
+	pub permissions: Permission,
+}
+
+impl DefineAggregateStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "DefineAggregateStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Function, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		if txn.get_db_function(ns, db, &self.name).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::FcAlreadyExists {
+							name: self.name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+
+		// Process the statement
+		let (ns_name, db_name) = opt.ns_db()?;
+		txn.get_or_add_db(Some(ctx), ns_name, db_name).await?;
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		txn.put_db_function(
+			ns,
+			db,
+			&FunctionDefinition {
+				name: self.name.clone(),
+				args: self.args.clone(),
+				block: Block::default(),
+				permissions: self.permissions.clone(),
+				returns: self.returns.clone(),
+				comment,
+				auth_limit: AuthLimit::new_from_auth(&opt.auth).into(),
+				aggregate: Some(AggregateDefinition {
+					state: self.state.clone(),
+					accumulate: self.accumulate.clone(),
+					merge: self.merge.clone(),
+					finalize: self.finalize.clone(),
+				}),
+			},
+		)
+		.await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for DefineAggregateStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::define::DefineAggregateStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
@@ -22,6 +22,7 @@ pub(crate) struct DefineAnalyzerStatement {
 	pub function: Option<String>,
 	pub tokenizers: Option<Vec<Tokenizer>>,
 	pub filters: Option<Vec<Filter>>,
+	pub operators: bool,
 	pub comment: Expr,
 }
 
@@ -33,6 +34,7 @@ impl Default for DefineAnalyzerStatement {
 			function: None,
 			tokenizers: None,
 			filters: None,
+			operators: false,
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -52,11 +54,23 @@ impl DefineAnalyzerStatement {
 			.catch_return()?
 			.cast_to()?;
 
+		let filters = match &self.filters {
+			Some(filters) => {
+				let mut res = Vec::with_capacity(filters.len());
+				for f in filters {
+					res.push(f.compute(stk, ctx, opt, doc).await?);
+				}
+				Some(res)
+			}
+			None => None,
+		};
+
 		Ok(catalog::AnalyzerDefinition {
 			name: expr_to_ident(stk, ctx, opt, doc, &self.name, "analyzer name").await?,
 			function: self.function.clone(),
 			tokenizers: self.tokenizers.clone(),
-			filters: self.filters.clone(),
+			filters,
+			operators: self.operators,
 			comment,
 		})
 	}
@@ -68,6 +82,7 @@ impl DefineAnalyzerStatement {
 			function: def.function.clone(),
 			tokenizers: def.tokenizers.clone(),
 			filters: def.filters.clone(),
+			operators: def.operators,
 			comment: def
 				.comment
 				.as_ref()
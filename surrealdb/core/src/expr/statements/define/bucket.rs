@@ -36,6 +36,19 @@ impl Default for DefineBucketStatement {
 }
 
 impl DefineBucketStatement {
+	/// Remove the secrets from the backend URL, as they are redacted from the
+	/// definition of a bucket.
+	pub(crate) fn redact(mut self) -> Self {
+		self.backend = self.backend.map(|backend| {
+			let backend = match backend {
+				Expr::Literal(Literal::String(v)) => BucketDefinition::redact_backend(&v),
+				_ => "[REDACTED]".to_string(),
+			};
+			Expr::Literal(Literal::String(backend))
+		});
+		self
+	}
+
 	#[instrument(level = "trace", name = "DefineBucketStatement::compute", skip_all)]
 	pub(crate) async fn compute(
 		&self,
@@ -79,6 +92,12 @@ impl DefineBucketStatement {
 			None
 		};
 
+		// Check the backend connects to an allowed network target
+		#[cfg(all(not(target_arch = "wasm32"), feature = "s3"))]
+		if let Some(ref backend) = backend {
+			crate::buc::check_backend_net(ctx, backend).await?;
+		}
+
 		// Create and cache a new backend
 		if let Some(buckets) = ctx.get_buckets() {
 			buckets.new_backend(ns, db, &name, self.readonly, backend.as_deref()).await?;
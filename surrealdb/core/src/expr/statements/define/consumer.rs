@@ -0,0 +1,103 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::{CursorDoc, DefineKind};
+use crate::catalog::ConsumerDefinition;
+use crate::catalog::providers::ConsumerProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{TableName, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub table: Option<Expr>,
+	pub retain: bool,
+	pub comment: Expr,
+}
+
+impl Default for DefineConsumerStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			table: None,
+			retain: false,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl DefineConsumerStatement {
+	#[instrument(level = "trace", name = "DefineConsumerStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Consumer, &Base::Db)?;
+		// Process the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "consumer name").await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		if let Some(consumer) = txn.get_db_consumer(ns, db, &name).await? {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::CsAlreadyExists {
+							name: consumer.name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+		// Process the table
+		let table = match &self.table {
+			Some(tb) => {
+				Some(TableName::new(expr_to_ident(stk, ctx, opt, doc, tb, "table name").await?))
+			}
+			None => None,
+		};
+		// Process the statement
+		let key = crate::key::database::cs::new(ns, db, &name);
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		let cs = ConsumerDefinition {
+			name: name.clone(),
+			table,
+			retain: self.retain,
+			comment,
+		};
+		txn.set(&key, &cs, None).await?;
+		// A new consumer reads the changes made from now on, while a
+		// redefined consumer carries on from its acknowledged offset
+		let key = crate::key::database::co::new(ns, db, &name);
+		if txn.get(&key, None).await?.is_none() {
+			let offset = crate::cf::consumer::initial_offset(&txn).await?;
+			txn.set(&key, &offset, None).await?;
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -6,7 +6,7 @@ use surrealdb_types::ToSql;
 use uuid::Uuid;
 
 use super::DefineKind;
-use crate::catalog::providers::TableProvider;
+use crate::catalog::providers::{KeyProvider, TableProvider};
 use crate::catalog::{
 	self, DatabaseId, FieldDefinition, NamespaceId, Permission, Permissions, Relation,
 	TableDefinition, TableType,
@@ -47,6 +47,7 @@ pub(crate) struct DefineFieldStatement {
 	pub permissions: Permissions,
 	pub comment: Expr,
 	pub reference: Option<Reference>,
+	pub encrypted: Option<String>,
 }
 
 impl Default for DefineFieldStatement {
@@ -65,6 +66,7 @@ impl Default for DefineFieldStatement {
 			permissions: Permissions::default(),
 			comment: Expr::Literal(Literal::None),
 			reference: None,
+			encrypted: None,
 		}
 	}
 }
@@ -138,6 +140,7 @@ impl DefineFieldStatement {
 			reference: self.reference.clone(),
 			auth_limit: AuthLimit::new_from_auth(opt.auth.as_ref()).into(),
 			computed_deps,
+			encrypted: self.encrypted.clone(),
 		})
 	}
 
@@ -168,6 +171,9 @@ impl DefineFieldStatement {
 		// Validate reference options
 		self.validate_reference_options(&definition)?;
 
+		// Validate encryption options
+		self.validate_encrypted_options(ns, db, ctx.tx(), &definition).await?;
+
 		// Disallow mismatched types
 		self.disallow_mismatched_types(ctx, ns, db, &definition).await?;
 
@@ -376,6 +382,7 @@ impl DefineFieldStatement {
 				Error::ComputedKeywordConflict("DEFAULT".into())
 			);
 			ensure!(!self.readonly, Error::ComputedKeywordConflict("READONLY".into()));
+			ensure!(self.encrypted.is_none(), Error::ComputedKeywordConflict("ENCRYPTED".into()));
 
 			// Ensure no nested fields exist
 			for field in fields.iter() {
@@ -595,6 +602,39 @@ impl DefineFieldStatement {
 		Ok(())
 	}
 
+	pub(crate) async fn validate_encrypted_options(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		txn: Arc<Transaction>,
+		definition: &catalog::FieldDefinition,
+	) -> Result<()> {
+		let Some(key) = &self.encrypted else {
+			return Ok(());
+		};
+		// Ensure the key exists
+		txn.expect_db_key(ns, db, key).await?;
+		// Ensure the field is a plain field path
+		ensure!(
+			definition.name.iter().all(|p| matches!(p, Part::Field(_))),
+			Error::EncryptedFieldPath(definition.name.to_sql())
+		);
+		// Ensure no index covers the field, or a parent of the field
+		for ix in txn.all_tb_indexes(ns, db, &definition.table).await?.iter() {
+			if ix
+				.cols
+				.iter()
+				.any(|col| col.starts_with(&definition.name) || definition.name.starts_with(col))
+			{
+				bail!(Error::EncryptedFieldCannotBeIndexed {
+					index: ix.name.clone(),
+					field: definition.name.to_raw_string(),
+				})
+			}
+		}
+		Ok(())
+	}
+
 	pub(crate) fn validate_id_restrictions(
 		&self,
 		definition: &catalog::FieldDefinition,
@@ -609,6 +649,9 @@ impl DefineFieldStatement {
 			// Ensure no `COMPUTED` clause is specified
 			ensure!(self.computed.is_none(), Error::IdFieldKeywordConflict("COMPUTED".into()));
 
+			// Ensure no `ENCRYPTED` clause is specified
+			ensure!(self.encrypted.is_none(), Error::IdFieldKeywordConflict("ENCRYPTED".into()));
+
 			// Ensure no `DEFAULT` clause is specified
 			ensure!(
 				matches!(self.default, DefineDefault::None),
@@ -77,6 +77,7 @@ impl DefineFunctionStatement {
 				returns: self.returns.clone(),
 				comment,
 				auth_limit: AuthLimit::new_from_auth(&opt.auth).into(),
+				aggregate: None,
 			},
 		)
 		.await?;
@@ -95,6 +95,18 @@ impl DefineIndexStatement {
 		// Compute columns
 		let cols = exprs_to_fields(stk, ctx, opt, doc, self.cols.as_slice()).await?;
 
+		// Encrypted fields cannot be indexed, as the index would hold their plaintext
+		for f in txn.all_tb_fields(tb.namespace_id, tb.database_id, &tb.name, None).await?.iter() {
+			if f.encrypted.is_some()
+				&& cols.iter().any(|col| col.starts_with(&f.name) || f.name.starts_with(col))
+			{
+				bail!(Error::EncryptedFieldCannotBeIndexed {
+					field: f.name.to_raw_string(),
+					index: name
+				});
+			}
+		}
+
 		// Validate each indexed field:
 		// 1. Computed fields cannot be indexed (regardless of schemafull/schemaless). This applies
 		//    to both exact field matches and sub-field paths whose parent is a computed field.
@@ -0,0 +1,101 @@
+use anyhow::{Result, bail};
+use chrono::Utc;
+use reblessive::tree::Stk;
+
+use super::{CursorDoc, DefineKind};
+use crate::catalog::JobDefinition;
+use crate::catalog::providers::JobProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal};
+use crate::iam::{Action, ResourceKind};
+use crate::job::parse_schedule;
+use crate::val::{Datetime, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineJobStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub schedule: Expr,
+	pub then: Expr,
+	pub comment: Expr,
+}
+
+impl Default for DefineJobStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			schedule: Expr::Literal(Literal::None),
+			then: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl DefineJobStatement {
+	#[instrument(level = "trace", name = "DefineJobStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Job, &Base::Db)?;
+		// Process the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "job name").await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		if let Some(job) = txn.get_db_job(ns, db, &name).await? {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::JbAlreadyExists {
+							name: job.name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+		// Process the schedule
+		let schedule = stk
+			.run(|stk| self.schedule.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.coerce_to::<String>()?;
+		if parse_schedule(&schedule)?.next_after(Utc::now()).is_none() {
+			bail!(Error::InvalidSchedule(format!("The schedule `{schedule}` never runs")));
+		}
+		// Process the statement
+		let key = crate::key::database::jb::new(ns, db, &name);
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		let jb = JobDefinition {
+			name: name.clone(),
+			schedule,
+			then: self.then.clone(),
+			comment,
+			created: Datetime::now(),
+		};
+		txn.set(&key, &jb, None).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
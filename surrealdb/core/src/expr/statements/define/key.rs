@@ -0,0 +1,126 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::{CursorDoc, DefineKind};
+use crate::catalog::KeyDefinition;
+use crate::catalog::providers::KeyProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal};
+use crate::iam::{Action, ResourceKind};
+use crate::val::Value;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineKeyStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub version: Option<u32>,
+	pub value: Option<Expr>,
+	pub encrypted: bool,
+	pub comment: Expr,
+}
+
+impl Default for DefineKeyStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			version: None,
+			value: None,
+			encrypted: false,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl DefineKeyStatement {
+	/// Remove the key material, as it is redacted from the definition of a
+	/// key.
+	pub(crate) fn redact(mut self) -> Self {
+		self.value = self.value.map(|_| Expr::Literal(Literal::String("[REDACTED]".to_string())));
+		self
+	}
+
+	#[instrument(level = "trace", name = "DefineKeyStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Key, &Base::Db)?;
+		// Process the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "key name").await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		let existing = txn.get_db_key(ns, db, &name).await?;
+		if let Some(ky) = &existing {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::KyAlreadyExists {
+							name: ky.name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+		let version = match (self.version, &existing) {
+			(Some(v), _) => v,
+			(None, Some(ky)) => ky.version,
+			(None, None) => 1,
+		};
+		// Process the statement
+		let key = crate::key::database::ky::new(ns, db, &name);
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+		// Process the key material, keeping the material of an
+		// existing key, so that its encrypted data remains readable
+		let ky = match &self.value {
+			Some(v) => {
+				let v = stk.run(|stk| v.compute(stk, ctx, opt, doc)).await.catch_return()?;
+				if self.encrypted {
+					KeyDefinition {
+						name: name.clone(),
+						version,
+						material: KeyDefinition::decode_wrapped(&name, v)?,
+						comment,
+					}
+				} else {
+					let material = KeyDefinition::decode_material(&name, v)?;
+					KeyDefinition::new(name.clone(), version, &material, comment)?
+				}
+			}
+			None => match &existing {
+				Some(ky) => KeyDefinition {
+					version,
+					comment,
+					..ky.as_ref().clone()
+				},
+				None => {
+					let material = KeyDefinition::generate_material()?;
+					KeyDefinition::new(name.clone(), version, &material, comment)?
+				}
+			},
+		};
+		txn.set(&key, &ky, None).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -1,39 +1,49 @@
 mod access;
+mod aggregate;
 mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
+mod key;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
 pub(crate) use access::DefineAccessStatement;
+pub(crate) use aggregate::DefineAggregateStatement;
 pub(crate) use analyzer::DefineAnalyzerStatement;
 use anyhow::Result;
 pub(crate) use api::{ApiAction, DefineApiStatement};
 pub(crate) use bucket::DefineBucketStatement;
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::DefineConsumerStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
 pub(crate) use function::DefineFunctionStatement;
 pub(crate) use index::DefineIndexStatement;
 pub(in crate::expr::statements) use index::run_indexing;
+pub(crate) use job::DefineJobStatement;
+pub(crate) use key::DefineKeyStatement;
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::DefineSequenceStatement;
+pub(crate) use sink::{DefineSinkStatement, SinkTarget};
 pub(crate) use table::DefineTableStatement;
 pub(crate) use user::DefineUserStatement;
 
@@ -55,6 +65,7 @@ pub(crate) enum DefineStatement {
 	Namespace(DefineNamespaceStatement),
 	Database(DefineDatabaseStatement),
 	Function(DefineFunctionStatement),
+	Aggregate(DefineAggregateStatement),
 	Analyzer(DefineAnalyzerStatement),
 	Param(DefineParamStatement),
 	Table(DefineTableStatement),
@@ -69,9 +80,46 @@ pub(crate) enum DefineStatement {
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
 	Module(DefineModuleStatement),
+	Job(DefineJobStatement),
+	Sink(DefineSinkStatement),
+	Consumer(DefineConsumerStatement),
+	Key(DefineKeyStatement),
 }
 
 impl DefineStatement {
+	/// Remove the secrets which are redacted from the catalog definitions, so
+	/// that the statement can be logged.
+	///
+	/// Every statement is matched explicitly, so that a new statement must
+	/// be considered here.
+	pub(crate) fn redact(self) -> Self {
+		match self {
+			Self::Access(v) => Self::Access(v.redact()),
+			Self::Bucket(v) => Self::Bucket(v.redact()),
+			Self::Key(v) => Self::Key(v.redact()),
+			Self::Sink(v) => Self::Sink(v.redact()),
+			// User passwords are already hashed when the statement is converted
+			v @ (Self::Namespace(_)
+			| Self::Database(_)
+			| Self::Function(_)
+			| Self::Aggregate(_)
+			| Self::Analyzer(_)
+			| Self::Param(_)
+			| Self::Table(_)
+			| Self::Event(_)
+			| Self::Field(_)
+			| Self::Index(_)
+			| Self::User(_)
+			| Self::Model(_)
+			| Self::Config(_)
+			| Self::Api(_)
+			| Self::Sequence(_)
+			| Self::Module(_)
+			| Self::Job(_)
+			| Self::Consumer(_)) => v,
+		}
+	}
+
 	/// Process this type returning a computed simple Value
 	#[instrument(level = "trace", name = "DefineStatement::compute", skip_all)]
 	pub(crate) async fn compute(
@@ -85,6 +133,7 @@ impl DefineStatement {
 			Self::Namespace(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Database(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Function(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Aggregate(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Param(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Table(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Event(v) => v.compute(stk, ctx, opt, doc).await,
@@ -99,6 +148,10 @@ impl DefineStatement {
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Job(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sink(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Key(v) => v.compute(stk, ctx, opt, doc).await,
 		}
 	}
 }
@@ -3,7 +3,7 @@ use reblessive::tree::Stk;
 
 use super::DefineKind;
 use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
-use crate::catalog::{ModuleDefinition, ModuleName, Permission};
+use crate::catalog::{ModuleDefinition, ModuleLimits, ModuleName, Permission};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -19,6 +19,7 @@ pub(crate) struct DefineModuleStatement {
 	pub kind: DefineKind,
 	pub name: Option<String>,
 	pub executable: ModuleExecutable,
+	pub limits: ModuleLimits,
 	pub comment: Expr,
 	pub permissions: Permission,
 }
@@ -96,6 +97,7 @@ impl DefineModuleStatement {
 			&ModuleDefinition {
 				name: self.name.clone(),
 				executable: self.executable.clone().into(),
+				limits: self.limits,
 				comment,
 				permissions: self.permissions.clone(),
 			},
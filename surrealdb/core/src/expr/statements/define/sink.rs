@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::{CursorDoc, DefineKind};
+use crate::catalog::providers::SinkProvider;
+use crate::catalog::{self, SinkDefinition};
+use crate::cf::sink::FileSink;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal, ObjectEntry};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Object, TableName, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineSinkStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub tables: Vec<Expr>,
+	pub target: SinkTarget,
+	pub batch: u32,
+	pub retry: u32,
+	pub comment: Expr,
+}
+
+impl Default for DefineSinkStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			tables: Vec::new(),
+			target: SinkTarget::Stdout,
+			batch: SinkDefinition::DEFAULT_BATCH,
+			retry: SinkDefinition::DEFAULT_RETRY,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum SinkTarget {
+	Webhook {
+		url: Expr,
+		headers: Option<Expr>,
+	},
+	File(Expr),
+	Stdout,
+	Custom(Expr),
+}
+
+impl DefineSinkStatement {
+	/// Remove the values of the webhook headers, which may hold credentials,
+	/// as they are redacted from the definition of a sink.
+	pub(crate) fn redact(mut self) -> Self {
+		if let SinkTarget::Webhook {
+			headers: Some(ref mut headers),
+			..
+		} = self.target
+		{
+			*headers = match headers {
+				Expr::Literal(Literal::Object(entries)) => Expr::Literal(Literal::Object(
+					catalog::SinkTarget::redacted_headers(entries.iter().map(|e| &e.key))
+						.into_iter()
+						.map(|(key, value)| ObjectEntry {
+							key,
+							value: Expr::Literal(Literal::String(value)),
+						})
+						.collect(),
+				)),
+				_ => Expr::Literal(Literal::String("[REDACTED]".to_string())),
+			};
+		}
+		self
+	}
+
+	#[instrument(level = "trace", name = "DefineSinkStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Sink, &Base::Db)?;
+		// Sinks which write to the server are limited to root owners
+		if matches!(self.target, SinkTarget::File(_) | SinkTarget::Stdout) {
+			opt.is_allowed(Action::Edit, ResourceKind::Sink, &Base::Root)?;
+			if opt.auth_enabled && !opt.auth.has_owner_role() {
+				bail!(Error::SinkTargetNotAllowed);
+			}
+		}
+		// Process the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sink name").await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Check if the definition exists
+		if let Some(sink) = txn.get_db_sink(ns, db, &name).await? {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::SkAlreadyExists {
+							name: sink.name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => {
+					return Ok(Value::None);
+				}
+			}
+		}
+		// Process the tables
+		let mut tables = Vec::with_capacity(self.tables.len());
+		for tb in self.tables.iter() {
+			tables.push(TableName::new(expr_to_ident(stk, ctx, opt, doc, tb, "table name").await?));
+		}
+		// Process the target
+		let target = self.compute_target(stk, ctx, opt, doc).await?;
+		// Process the statement
+		let key = crate::key::database::sk::new(ns, db, &name);
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		let sk = SinkDefinition {
+			name: name.clone(),
+			tables,
+			target,
+			batch: self.batch.max(1),
+			retry: self.retry,
+			comment,
+		};
+		txn.set(&key, &sk, None).await?;
+		// A new sink forwards the changes made from now on, while a
+		// redefined sink carries on from where it was
+		let key = crate::key::database::sp::new(ns, db, &name);
+		if txn.get(&key, None).await?.is_none() {
+			let position = crate::cf::sink::initial_position(&txn).await?;
+			txn.set(&key, &position, None).await?;
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	async fn compute_target(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<catalog::SinkTarget> {
+		let target = match &self.target {
+			SinkTarget::Webhook {
+				url,
+				headers,
+			} => {
+				let url = stk
+					.run(|stk| url.compute(stk, ctx, opt, doc))
+					.await
+					.catch_return()?
+					.coerce_to::<String>()?;
+				// Check the URL is valid and allowed
+				check_webhook_url(ctx, &url).await?;
+				let headers = match headers {
+					Some(headers) => stk
+						.run(|stk| headers.compute(stk, ctx, opt, doc))
+						.await
+						.catch_return()?
+						.coerce_to::<Object>()?
+						.into_iter()
+						.map(|(k, v)| Ok((k, v.coerce_to::<String>()?)))
+						.collect::<Result<BTreeMap<_, _>>>()?,
+					None => BTreeMap::new(),
+				};
+				catalog::SinkTarget::Webhook {
+					url,
+					headers,
+				}
+			}
+			SinkTarget::File(path) => {
+				let path = stk
+					.run(|stk| path.compute(stk, ctx, opt, doc))
+					.await
+					.catch_return()?
+					.coerce_to::<String>()?;
+				// Check the file is allowed
+				FileSink::check_path(Path::new(&path))?;
+				catalog::SinkTarget::File {
+					path,
+				}
+			}
+			SinkTarget::Stdout => catalog::SinkTarget::Stdout,
+			SinkTarget::Custom(name) => catalog::SinkTarget::Custom {
+				name: stk
+					.run(|stk| name.compute(stk, ctx, opt, doc))
+					.await
+					.catch_return()?
+					.coerce_to::<String>()?,
+			},
+		};
+		Ok(target)
+	}
+}
+
+/// Checks that the URL of a webhook is valid, and allowed by the network
+/// capabilities of the datastore.
+#[cfg(feature = "http")]
+async fn check_webhook_url(ctx: &FrozenContext, url: &str) -> Result<()> {
+	let parsed = url::Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_owned()))?;
+	ctx.check_allowed_net(&parsed).await
+}
+
+/// Webhooks can not be delivered without HTTP support.
+#[cfg(not(feature = "http"))]
+async fn check_webhook_url(_ctx: &FrozenContext, _url: &str) -> Result<()> {
+	bail!(Error::HttpDisabled)
+}
@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time;
 
 use anyhow::{Result, bail};
 use reblessive::tree::Stk;
@@ -43,6 +45,7 @@ pub(crate) struct DefineTableStatement {
 	pub changefeed: Option<ChangeFeed>,
 	pub comment: Expr,
 	pub table_type: TableType,
+	pub ttl: Option<time::Duration>,
 }
 
 impl Default for DefineTableStatement {
@@ -58,6 +61,7 @@ impl Default for DefineTableStatement {
 			changefeed: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
+			ttl: None,
 		}
 	}
 }
@@ -88,7 +92,8 @@ impl DefineTableStatement {
 		let db = txn.expect_db_by_name(ns_name, db_name).await?;
 
 		// Check if the definition exists
-		let table_id = if let Some(tb) = txn.get_tb(ns.namespace_id, db.database_id, &name).await? {
+		let previous = txn.get_tb(ns.namespace_id, db.database_id, &name).await?;
+		let table_id = if let Some(tb) = &previous {
 			match self.kind {
 				DefineKind::Default => {
 					if !opt.import {
@@ -112,6 +117,11 @@ impl DefineTableStatement {
 			.catch_return()?
 			.cast_to()?;
 
+		let view = match &self.view {
+			Some(v) => Some(v.to_definition(stk, ctx, opt).await?),
+			None => None,
+		};
+
 		// Process the statement
 		let cache_ts = Uuid::now_v7();
 		let mut tb_def = TableDefinition {
@@ -122,10 +132,11 @@ impl DefineTableStatement {
 			drop: self.drop,
 			schemafull: self.full,
 			table_type: self.table_type.clone(),
-			view: self.view.clone().map(|v| v.to_definition()).transpose()?,
+			view,
 			permissions: self.permissions.clone(),
 			comment,
 			changefeed: self.changefeed,
+			ttl: self.ttl,
 
 			cache_fields_ts: cache_ts,
 			cache_events_ts: cache_ts,
@@ -141,6 +152,26 @@ impl DefineTableStatement {
 			txn.changefeed_buffer_table_change(ns.namespace_id, db.database_id, &name, &tb_def);
 		}
 
+		let previous_ttl = previous.as_ref().and_then(|tb| tb.ttl);
+		match self.ttl {
+			// The records don't expire anymore
+			None if previous_ttl.is_some() => {
+				Self::remove_record_expiries(&txn, ns.namespace_id, db.database_id, &name).await?;
+			}
+			// The existing records expire once the new TTL has elapsed
+			Some(ttl) if previous_ttl != Some(ttl) => {
+				doc::store_table_expiries(
+					&txn,
+					(ns_name, db_name),
+					(ns.namespace_id, db.database_id),
+					&name,
+					ttl,
+				)
+				.await?;
+			}
+			_ => {}
+		}
+
 		// Update the catalog
 		let tb = txn.put_tb(ns_name, db_name, &tb_def).await?;
 		let fields = txn.all_tb_fields(ns.namespace_id, db.database_id, &name, opt.version).await?;
@@ -300,6 +331,7 @@ impl DefineTableStatement {
 			timeout: Expr::Literal(Literal::None),
 			explain: None,
 			tempfiles: false,
+			cache: None,
 		};
 
 		let Value::Array(Array(v)) = select.compute(stk, ctx, opt, None).await? else {
@@ -438,6 +470,11 @@ impl DefineTableStatement {
 				Aggregation::Accumulate(_) => {
 					fail!("Accumulate aggregation is not supported in materialized views")
 				}
+				Aggregation::Custom {
+					..
+				} => {
+					// Folded from the records below, as it can't be computed by a select.
+				}
 			}
 		}
 
@@ -488,7 +525,11 @@ impl DefineTableStatement {
 						receiver: Function::Normal("time::min".to_string()),
 						arguments: vec![analysis.aggregate_arguments[*arg].clone()],
 					},
-					Aggregation::StdDev(_) | Aggregation::Variance(_) => {
+					Aggregation::StdDev(_)
+					| Aggregation::Variance(_)
+					| Aggregation::Custom {
+						..
+					} => {
 						// Not used for initialization.
 						unreachable!()
 					}
@@ -547,12 +588,96 @@ impl DefineTableStatement {
 			timeout: Expr::Literal(Literal::None),
 			explain: None,
 			tempfiles: false,
+			cache: None,
 		};
 		let res = stmt.compute(stk, ctx, opt, None).await?;
 		let Value::Array(res) = res else {
 			fail!("initial select for view did not return an array");
 		};
 
+		// User-defined aggregates are folded from the arguments of every record. Each source
+		// table is folded separately, after which the states of a group are merged.
+		let custom = analysis.custom_aggregates(stk, ctx, opt).await?;
+		let mut custom_stats: BTreeMap<Vec<Value>, Vec<AggregationStat>> = BTreeMap::new();
+		if !custom.is_empty() {
+			for table in tables {
+				let group_exprs = analysis.group_expressions.clone();
+				let argument_exprs = analysis.aggregate_arguments.clone();
+				let stmt = SelectStatement {
+					// SELECT [group_expr1, ..] as g, [argument1, ..] as a
+					fields: Fields::Select(vec![
+						Field::Single(Selector {
+							expr: Expr::Literal(Literal::Array(group_exprs)),
+							alias: Some(Idiom::field("g".to_string())),
+						}),
+						Field::Single(Selector {
+							expr: Expr::Literal(Literal::Array(argument_exprs)),
+							alias: Some(Idiom::field("a".to_string())),
+						}),
+					]),
+					// WHERE cond
+					cond: condition.cloned().map(Cond),
+					group: None,
+					what: vec![Expr::Table(table.clone())],
+					omit: vec![],
+					only: false,
+					with: None,
+					split: None,
+					order: None,
+					limit: None,
+					start: None,
+					fetch: None,
+					version: Expr::Literal(Literal::None),
+					timeout: Expr::Literal(Literal::None),
+					explain: None,
+					tempfiles: false,
+					cache: None,
+				};
+				let Value::Array(rows) = stmt.compute(stk, ctx, opt, None).await? else {
+					fail!("initial select for view did not return an array");
+				};
+
+				let mut table_stats: BTreeMap<Vec<Value>, Vec<AggregationStat>> = BTreeMap::new();
+				for r in rows {
+					let Value::Object(mut obj) = r else {
+						fail!("select without VALUE did not return an object");
+					};
+					let Some(Value::Array(Array(group))) = obj.remove("g") else {
+						fail!("select result did not contain a field for a selection");
+					};
+					let Some(Value::Array(Array(arguments))) = obj.remove("a") else {
+						fail!("select result did not contain a field for a selection");
+					};
+					let stats = table_stats.entry(group).or_insert_with(|| {
+						analysis.aggregations.iter().map(|x| x.to_stat()).collect()
+					});
+					aggregation::accumulate_custom_aggregation_stats(
+						stk, ctx, opt, &custom, &arguments, stats,
+					)
+					.await?;
+				}
+
+				for (group, stats) in table_stats {
+					match custom_stats.entry(group) {
+						Entry::Vacant(x) => {
+							x.insert(stats);
+						}
+						Entry::Occupied(mut x) => {
+							aggregation::merge_custom_aggregation_stats(
+								stk,
+								ctx,
+								opt,
+								&custom,
+								x.get_mut(),
+								stats,
+							)
+							.await?;
+						}
+					}
+				}
+			}
+		}
+
 		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
 		let tx = ctx.tx();
 
@@ -709,13 +834,28 @@ impl DefineTableStatement {
 					Aggregation::Accumulate {
 						..
 					} => fail!("Accumulate aggregation is not supported in materialized views"),
+					Aggregation::Custom {
+						..
+					} => {
+						let stat = custom_stats
+							.get(&group)
+							.and_then(|x| x.get(stats.len()))
+							.cloned()
+							.unwrap_or_else(|| a.to_stat());
+						stats.push(stat);
+					}
 				}
 			}
 
 			// We have now computed the aggregation stats so now we need to insert the record.
 			// first calculate the actual value for the record.
 
-			let doc = Value::Object(aggregation::create_field_document(&group, &stats)).into();
+			let mut doc = aggregation::create_field_document(&group, &stats);
+			aggregation::finalize_custom_aggregation_stats(
+				stk, ctx, opt, &custom, &stats, &mut doc,
+			)
+			.await?;
+			let doc = Value::Object(doc).into();
 
 			let mut data = Value::empty_object();
 
@@ -767,6 +907,20 @@ impl DefineTableStatement {
 		Ok(())
 	}
 
+	/// Used to stop the expiry of the records of a table which no longer has a
+	/// `TTL`. The remaining entries of the expiry queue are discarded by the
+	/// reaper, as they no longer match any record expiry.
+	pub(crate) async fn remove_record_expiries(
+		txn: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+	) -> Result<()> {
+		let beg = key::table::ex::prefix(ns, db, tb)?;
+		let end = key::table::ex::suffix(ns, db, tb)?;
+		txn.delr(beg..end).await
+	}
+
 	/// Used to add relational fields to existing table records
 	///
 	/// Returns the cache key ts.
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use chrono::DateTime;
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::catalog::providers::TableProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::{CursorDoc, IgnoreError, check_select_permissions_table, reduce_fields};
+use crate::err::Error;
+use crate::expr::{Expr, FlowResultExt, Operation};
+use crate::iam::Action;
+use crate::val::{Array, Datetime, Object, Value};
+
+/// A SHOW HISTORY statement for listing the stored versions of a record.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct HistoryStatement {
+	pub what: Expr,
+	pub since: Option<Expr>,
+	pub until: Option<Expr>,
+}
+
+impl HistoryStatement {
+	/// Check if this statement is read-only
+	pub(crate) fn read_only(&self) -> bool {
+		self.what.read_only()
+			&& self.since.as_ref().map(|x| x.read_only()).unwrap_or(true)
+			&& self.until.as_ref().map(|x| x.read_only()).unwrap_or(true)
+	}
+
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "HistoryStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Compute the record
+		let rid = match stk.run(|stk| self.what.compute(stk, ctx, opt, doc)).await.catch_return()? {
+			Value::RecordId(v) => v,
+			v => bail!(Error::InvalidStatementTarget {
+				value: v.to_sql(),
+			}),
+		};
+		// Compute the timestamps
+		let since = match &self.since {
+			Some(v) => Self::version(stk, ctx, opt, doc, v).await?,
+			None => 0,
+		};
+		let until = match &self.until {
+			Some(v) => Self::version(stk, ctx, opt, doc, v).await?,
+			None => u64::MAX,
+		};
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		// Fetch the table and field permissions
+		let tb = txn.get_tb(ns, db, &rid.table).await?;
+		let fields = txn.all_tb_fields(ns, db, &rid.table, None).await?;
+		// The permissions of a removed table can not be checked
+		if tb.is_none() && opt.check_perms(Action::View)? {
+			return Ok(Value::Array(Array::new()));
+		}
+		// Fetch the versions of the record
+		let key = crate::key::record::new(ns, db, &rid.table, &rid.key);
+		let versions = txn.versions(&key, since, until).await?;
+		let rid = Arc::new(rid);
+		// Output each version, with the changes since the previous version
+		let mut prev = Value::None;
+		let mut out = Vec::with_capacity(versions.len());
+		for (ts, record) in versions {
+			let value = match record {
+				Some(mut record) => {
+					record.data.def(rid.as_ref().clone());
+					let doc = CursorDoc::new(Some(rid.clone()), None, record);
+					// Check the table permissions for this version
+					match check_select_permissions_table(stk, ctx, opt, tb.as_ref(), &doc).await {
+						Ok(()) => (),
+						Err(IgnoreError::Ignore) => continue,
+						Err(IgnoreError::Error(e)) => return Err(e),
+					}
+					// Reduce the version to the permitted fields
+					if opt.check_perms(Action::View)? {
+						reduce_fields(stk, ctx, opt, &fields, &doc).await?.doc.into_owned()
+					} else {
+						doc.doc.into_owned()
+					}
+				}
+				None => Value::None,
+			};
+			let diff = Operation::operations_to_value(prev.diff(&value));
+			out.push(Value::Object(Object::from(map! {
+				"version".to_string() => Value::Datetime(Datetime::from(DateTime::from_timestamp_nanos(ts as i64))),
+				"value".to_string() => value.clone(),
+				"diff".to_string() => diff,
+			})));
+			prev = value;
+		}
+		Ok(out.into())
+	}
+
+	/// Computes a timestamp bound into a version
+	async fn version(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		expr: &Expr,
+	) -> Result<u64> {
+		stk.run(|stk| expr.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to::<Datetime>()?
+			.to_version_stamp()
+	}
+}
+
+impl ToSql for HistoryStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::history::HistoryStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
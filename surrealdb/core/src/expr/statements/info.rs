@@ -5,9 +5,11 @@ use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 
 use crate::catalog::providers::{
-	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, NamespaceProvider,
-	NodeProvider, RootProvider, TableProvider, UserProvider,
+	ApiProvider, AuthorisationProvider, BucketProvider, ConsumerProvider, DatabaseProvider,
+	JobProvider, KeyProvider, NamespaceProvider, NodeProvider, RootProvider, SinkProvider,
+	TableProvider, UserProvider,
 };
+use crate::cf;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -15,6 +17,7 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, FlowResultExt};
 use crate::iam::{Action, ResourceKind};
+use crate::job;
 use crate::sys::INFORMATION;
 use crate::val::{Datetime, Object, TableName, Value};
 
@@ -175,12 +178,16 @@ impl InfoStatement {
 						"buckets".to_string() => process(txn.all_db_buckets(ns, db).await?),
 						"functions".to_string() => process(txn.all_db_functions(ns, db).await?),
 						"modules".to_string() => process(txn.all_db_modules(ns, db).await?),
+						"jobs".to_string() => job::structure(&txn, ns, db).await?,
+						"keys".to_string() => process(txn.all_db_keys(ns, db).await?),
 						"models".to_string() => process(txn.all_db_models(ns, db).await?),
 						"params".to_string() => process(txn.all_db_params(ns, db).await?),
 						"tables".to_string() => process(txn.all_tb(ns, db, version).await?),
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
+						"consumers".to_string() => cf::consumer::structure(&txn, ns, db).await?,
 						"sequences".to_string() => process(txn.all_db_sequences(ns, db).await?),
+						"sinks".to_string() => cf::sink::structure(&txn, ns, db).await?,
 					};
 					Value::Object(Object(object))
 				} else {
@@ -227,6 +234,20 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"jobs".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_jobs(ns, db).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
+						"keys".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_keys(ns, db).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 						"models".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_models(ns, db).await?.iter() {
@@ -269,6 +290,20 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"sinks".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_sinks(ns, db).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
+						"consumers".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_consumers(ns, db).await?.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 					};
 					Value::Object(Object(object))
 				};
@@ -1,4 +1,5 @@
 pub(crate) mod access;
+pub(crate) mod ack;
 pub(crate) mod alter;
 pub(crate) mod create;
 // needs to be public because the RPC layer is accessing the kv store for api
@@ -6,6 +7,7 @@ pub(crate) mod create;
 pub mod define;
 pub(crate) mod delete;
 pub(crate) mod foreach;
+pub(crate) mod history;
 pub(crate) mod ifelse;
 pub(crate) mod info;
 pub(crate) mod insert;
@@ -16,6 +18,7 @@ pub(crate) mod output;
 pub(crate) mod rebuild;
 pub(crate) mod relate;
 pub(crate) mod remove;
+pub(crate) mod run;
 pub(crate) mod select;
 pub(crate) mod set;
 pub(crate) mod show;
@@ -25,16 +28,18 @@ pub(crate) mod upsert;
 pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
+pub(crate) use self::ack::AckStatement;
 pub(crate) use self::alter::AlterStatement;
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
+	DefineAccessStatement, DefineAggregateStatement, DefineAnalyzerStatement, DefineApiStatement,
+	DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
 	DefineModelStatement, DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement,
 	DefineStatement, DefineTableStatement, DefineUserStatement,
 };
 pub(crate) use self::delete::DeleteStatement;
 pub(crate) use self::foreach::ForeachStatement;
+pub(crate) use self::history::HistoryStatement;
 pub(crate) use self::ifelse::IfelseStatement;
 pub(crate) use self::info::InfoStatement;
 pub(crate) use self::insert::InsertStatement;
@@ -50,6 +55,7 @@ pub(crate) use self::remove::{
 	RemoveModelStatement, RemoveModuleStatement, RemoveNamespaceStatement, RemoveParamStatement,
 	RemoveStatement, RemoveTableStatement, RemoveUserStatement,
 };
+pub(crate) use self::run::RunStatement;
 pub(crate) use self::select::SelectStatement;
 pub(crate) use self::set::SetStatement;
 pub(crate) use self::show::ShowStatement;
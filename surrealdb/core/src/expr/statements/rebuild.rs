@@ -2,19 +2,25 @@ use anyhow::Result;
 use reblessive::tree::Stk;
 use surrealdb_types::{SqlFormat, ToSql};
 
-use crate::catalog::providers::TableProvider;
+use crate::catalog::providers::{KeyProvider, TableProvider};
+use crate::catalog::{KeyDefinition, Record};
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::Base;
+use crate::expr::Idiom;
 use crate::expr::statements::define::run_indexing;
 use crate::iam::{Action, ResourceKind};
+use crate::key::record;
+use crate::kvs::KVValue;
 use crate::val::{TableName, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum RebuildStatement {
 	Index(RebuildIndexStatement),
+	Key(RebuildKeyStatement),
 }
 
 impl RebuildStatement {
@@ -29,6 +35,7 @@ impl RebuildStatement {
 	) -> Result<Value> {
 		match self {
 			Self::Index(s) => s.compute(ctx, opt).await,
+			Self::Key(s) => s.compute(ctx, opt).await,
 		}
 	}
 }
@@ -84,3 +91,84 @@ impl ToSql for RebuildIndexStatement {
 		stmt.fmt_sql(f, fmt);
 	}
 }
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct RebuildKeyStatement {
+	pub name: String,
+	pub if_exists: bool,
+}
+
+impl RebuildKeyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Key, &Base::Db)?;
+		// Get the key definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		let old = match txn.get_db_key(ns, db, &self.name).await? {
+			Some(x) => x,
+			None => {
+				if self.if_exists {
+					return Ok(Value::None);
+				} else {
+					return Err(Error::KyNotFound {
+						name: self.name.clone(),
+					}
+					.into());
+				}
+			}
+		};
+		// Rotate the key material
+		let new = KeyDefinition::new(
+			old.name.clone(),
+			old.version + 1,
+			&KeyDefinition::generate_material()?,
+			old.comment.clone(),
+		)?;
+		// Re-encrypt the fields which use this key
+		for tb in txn.all_tb(ns, db, None).await?.iter() {
+			let fields: Vec<Idiom> = txn
+				.all_tb_fields(ns, db, &tb.name, None)
+				.await?
+				.iter()
+				.filter(|fd| fd.encrypted.as_deref() == Some(old.name.as_str()))
+				.map(|fd| fd.name.clone())
+				.collect();
+			if fields.is_empty() {
+				continue;
+			}
+			let beg = record::prefix(ns, db, &tb.name)?;
+			let end = record::suffix(ns, db, &tb.name)?;
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+				next = batch.next;
+				for (k, v) in batch.result {
+					let key = record::RecordKey::decode_key(&k)?;
+					let mut record = Record::kv_decode_value(v)?;
+					for fd in fields.iter() {
+						old.decrypt_field(&mut record.data, fd)?;
+						new.encrypt_field(&mut record.data, fd)?;
+					}
+					txn.set_record(ns, db, &tb.name, &key.id, record.into_read_only(), None)
+						.await?;
+				}
+			}
+		}
+		// Store the rotated key
+		let key = crate::key::database::ky::new(ns, db, &new.name);
+		txn.set(&key, &new, None).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for RebuildKeyStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::rebuild::RebuildKeyStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
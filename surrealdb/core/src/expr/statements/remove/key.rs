@@ -0,0 +1,76 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::{KeyProvider, TableProvider};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveKeyStatement {
+	pub name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveKeyStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveKeyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Key, &Base::Db)?;
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "key name").await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let Some(ky) = txn.get_db_key(ns, db, &name).await? else {
+			if self.if_exists {
+				return Ok(Value::None);
+			} else {
+				return Err(Error::KyNotFound {
+					name,
+				}
+				.into());
+			}
+		};
+		// The data encrypted with this key would be lost
+		for tb in txn.all_tb(ns, db, None).await?.iter() {
+			for fd in txn.all_tb_fields(ns, db, &tb.name, None).await?.iter() {
+				if fd.encrypted.as_deref() == Some(ky.name.as_str()) {
+					bail!(Error::KyInUse {
+						name: ky.name.clone(),
+						field: fd.name.to_sql(),
+						table: tb.name.clone().into_string(),
+					});
+				}
+			}
+		}
+		// Delete the definition
+		let key = crate::key::database::ky::new(ns, db, &ky.name);
+		txn.del(&key).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -3,16 +3,20 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
+mod key;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,17 +26,21 @@ use anyhow::Result;
 pub(crate) use api::RemoveApiStatement;
 pub(crate) use bucket::RemoveBucketStatement;
 pub(crate) use config::RemoveConfigStatement;
+pub(crate) use consumer::RemoveConsumerStatement;
 pub(crate) use database::RemoveDatabaseStatement;
 pub(crate) use event::RemoveEventStatement;
 pub(crate) use field::RemoveFieldStatement;
 pub(crate) use function::RemoveFunctionStatement;
 pub(crate) use index::RemoveIndexStatement;
+pub(crate) use job::RemoveJobStatement;
+pub(crate) use key::RemoveKeyStatement;
 pub(crate) use model::RemoveModelStatement;
 pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::RemoveSequenceStatement;
+pub(crate) use sink::RemoveSinkStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
 
@@ -60,6 +68,10 @@ pub(crate) enum RemoveStatement {
 	Sequence(RemoveSequenceStatement),
 	Module(RemoveModuleStatement),
 	Config(RemoveConfigStatement),
+	Job(RemoveJobStatement),
+	Sink(RemoveSinkStatement),
+	Consumer(RemoveConsumerStatement),
+	Key(RemoveKeyStatement),
 }
 
 impl RemoveStatement {
@@ -89,6 +101,10 @@ impl RemoveStatement {
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(ctx, opt).await,
 			Self::Config(v) => v.compute(ctx, opt).await,
+			Self::Job(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sink(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Key(v) => v.compute(stk, ctx, opt, doc).await,
 		}
 	}
 }
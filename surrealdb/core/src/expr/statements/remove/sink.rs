@@ -0,0 +1,67 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::SinkProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveSinkStatement {
+	pub name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveSinkStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveSinkStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Sink, &Base::Db)?;
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sink name").await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let Some(sk) = txn.get_db_sink(ns, db, &name).await? else {
+			if self.if_exists {
+				return Ok(Value::None);
+			} else {
+				return Err(Error::SkNotFound {
+					name,
+				}
+				.into());
+			}
+		};
+
+		// Delete the definition
+		let key = crate::key::database::sk::new(ns, db, &sk.name);
+		txn.del(&key).await?;
+		// Delete the delivery cursor
+		let key = crate::key::database::sp::new(ns, db, &sk.name);
+		txn.del(&key).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::catalog::providers::JobProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::expr::Base;
+use crate::iam::{Action, ResourceKind};
+use crate::job;
+use crate::val::Value;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum RunStatement {
+	Job(RunJobStatement),
+}
+
+impl RunStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "RunStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		match self {
+			Self::Job(s) => s.compute(stk, ctx, opt).await,
+		}
+	}
+}
+
+impl ToSql for RunStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::run::RunStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct RunJobStatement {
+	pub name: String,
+}
+
+impl RunJobStatement {
+	/// Runs the job straight away, within the current transaction, and
+	/// returns the result of its body. The run is recorded in the history of
+	/// the job if the transaction commits.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Job, &Base::Db)?;
+		// Get the job definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		let jb = txn.expect_db_job(ns, db, &self.name).await?;
+		// Run the job
+		let (res, run) = job::execute(stk, ctx, opt, &jb, true).await;
+		let res = res?;
+		job::record_run(&txn, ns, db, &jb.name, run).await?;
+		// Ok all good
+		Ok(res)
+	}
+}
+
+impl ToSql for RunJobStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::run::RunJobStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
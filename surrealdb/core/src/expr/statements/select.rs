@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Result, ensure};
 use reblessive::tree::Stk;
@@ -50,6 +51,10 @@ pub(crate) struct SelectStatement {
 	pub timeout: Expr,
 	pub explain: Option<Explain>,
 	pub tempfiles: bool,
+	/// How long the result of the statement is kept in the result cache.
+	///
+	/// The CACHE 30s part in `SELECT count() FROM baz GROUP ALL CACHE 30s`.
+	pub cache: Option<Duration>,
 }
 
 impl SelectStatement {
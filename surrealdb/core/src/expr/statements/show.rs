@@ -1,8 +1,11 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 
+use crate::catalog::providers::ConsumerProvider;
+use crate::cf;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
+use crate::err::Error;
 use crate::expr::{Base, Value};
 use crate::iam::{Action, ResourceKind};
 use crate::val::{Datetime, TableName};
@@ -13,11 +16,19 @@ pub enum ShowSince {
 	Versionstamp(u64),
 }
 
-/// A SHOW CHANGES statement for displaying changes made to a table or database.
+/// A SHOW CHANGES statement for displaying changes made to a table or database,
+/// or a SHOW AUDIT statement for displaying the audit log.
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct ShowStatement {
 	pub table: Option<TableName>,
+	/// The consumer from whose acknowledged offset the changes are read, in
+	/// which case `table` and `since` are unused.
+	pub consumer: Option<String>,
+	/// Whether the audit log is shown, in which case `table` and `consumer`
+	/// are unused, and `since` must be a timestamp, or a versionstamp of 0
+	/// when the whole audit log is shown.
+	pub audit: bool,
 	pub since: ShowSince,
 	pub limit: Option<u32>,
 }
@@ -31,14 +42,39 @@ impl ShowStatement {
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value> {
+		if self.audit {
+			// Only root users can read the audit log
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			let since = match self.since {
+				ShowSince::Timestamp(ref v) => v.to_version_stamp()?,
+				ShowSince::Versionstamp(0) => 0,
+				ShowSince::Versionstamp(value) => {
+					bail!(Error::ShowAuditVersionstamp {
+						value,
+					})
+				}
+			};
+			return Ok(crate::kvs::audit::read(&ctx.tx(), since, self.limit).await?.into());
+		}
 		// Allowed to run?
 		opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Process the show query
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
-		let r = crate::cf::read(&txn, ns, db, self.table.as_ref(), self.since.clone(), self.limit)
-			.await?;
+		let r = match self.consumer {
+			// Resume from the offset acknowledged by the consumer
+			Some(ref cs) => {
+				let cs = txn.expect_db_consumer(ns, db, cs).await?;
+				let offset = cf::consumer::offset(&txn, ns, db, &cs.name).await?;
+				let since = ShowSince::Versionstamp(offset.versionstamp);
+				crate::cf::read(&txn, ns, db, cs.table.as_ref(), since, self.limit).await?
+			}
+			None => {
+				crate::cf::read(&txn, ns, db, self.table.as_ref(), self.since.clone(), self.limit)
+					.await?
+			}
+		};
 		// Return the changes
 		let a: Vec<Value> = r.iter().cloned().map(|x| x.into_value()).collect();
 		Ok(a.into())
@@ -1,10 +1,13 @@
 use std::fmt::Debug;
 
 use anyhow::{Result, bail};
+use reblessive::tree::Stk;
 use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::catalog::ViewDefinition;
-use crate::catalog::aggregation::{AggregateFields, AggregationAnalysis};
+use crate::catalog::aggregation::{self, AggregateFields, AggregationAnalysis};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Cond, Fields, Groups, Value};
@@ -20,7 +23,12 @@ pub(crate) struct View {
 }
 
 impl View {
-	pub(crate) fn to_definition(&self) -> Result<ViewDefinition> {
+	pub(crate) async fn to_definition(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+	) -> Result<ViewDefinition> {
 		if !self.materialize {
 			return Ok(ViewDefinition::Select {
 				fields: self.expr.clone(),
@@ -39,7 +47,9 @@ impl View {
 			});
 		};
 
-		let analysis = AggregationAnalysis::analyze_fields_groups(&self.expr, group, true)?;
+		let custom = aggregation::custom_aggregates(stk, ctx, opt, &self.expr).await?;
+		let analysis =
+			AggregationAnalysis::analyze_fields_groups(&self.expr, group, true, &custom)?;
 		if let AggregateFields::Value(_) = analysis.fields {
 			bail!(Error::InvalidAggregation {
 				message: "the selector `VALUE` clause is not supported on DEFINE TABLE .. AS SELECT .. GROUP .. aggregates"
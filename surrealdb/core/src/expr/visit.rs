@@ -22,25 +22,29 @@ use crate::expr::statements::define::config::ConfigInner;
 use crate::expr::statements::define::config::api::ApiConfig;
 use crate::expr::statements::define::config::defaults::DefaultConfig;
 use crate::expr::statements::define::{
-	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefineSequenceStatement,
+	ApiAction, DefineAggregateStatement, DefineBucketStatement, DefineConfigStatement,
+	DefineConsumerStatement, DefineDefault, DefineJobStatement, DefineKeyStatement,
+	DefineSequenceStatement, DefineSinkStatement, SinkTarget,
 };
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::statements::remove::{
-	RemoveApiStatement, RemoveBucketStatement, RemoveSequenceStatement,
+	RemoveApiStatement, RemoveBucketStatement, RemoveConsumerStatement, RemoveJobStatement,
+	RemoveKeyStatement, RemoveSequenceStatement, RemoveSinkStatement,
 };
+use crate::expr::statements::run::{RunJobStatement, RunStatement};
 use crate::expr::statements::{
-	AccessStatement, AlterStatement, CreateStatement, DefineAccessStatement,
+	AccessStatement, AckStatement, AlterStatement, CreateStatement, DefineAccessStatement,
 	DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement, DefineEventStatement,
 	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, DefineModelStatement,
 	DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement, DefineUserStatement, DeleteStatement, ForeachStatement, IfelseStatement,
-	InfoStatement, InsertStatement, KillStatement, LiveFields, LiveStatement, OptionStatement,
-	OutputStatement, RelateStatement, RemoveAccessStatement, RemoveAnalyzerStatement,
-	RemoveConfigStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
-	RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement, RemoveModuleStatement,
-	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-	RemoveUserStatement, SelectStatement, SetStatement, ShowStatement, SleepStatement,
-	UpdateStatement, UpsertStatement, UseStatement,
+	DefineTableStatement, DefineUserStatement, DeleteStatement, ForeachStatement, HistoryStatement,
+	IfelseStatement, InfoStatement, InsertStatement, KillStatement, LiveFields, LiveStatement,
+	OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
+	RemoveAnalyzerStatement, RemoveConfigStatement, RemoveDatabaseStatement, RemoveEventStatement,
+	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement,
+	RemoveModuleStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
+	RemoveTableStatement, RemoveUserStatement, SelectStatement, SetStatement, ShowStatement,
+	SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::expr::{
 	AccessType, Block, ClosureExpr, Data, Expr, Field, Fields, Function, FunctionCall, Idiom,
@@ -140,6 +144,7 @@ implement_visitor! {
 		TopLevelExpr::Option(s) =>{ this.visit_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_show(s)?; },
+		TopLevelExpr::Ack(s) => {this.visit_ack(s)?; },
 		TopLevelExpr::Expr(e) => {this.visit_expr(e)?; },
 		}
 		Ok(())
@@ -228,6 +233,11 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_ack(this, a: &AckStatement){
+		this.visit_expr(&a.versionstamp)?;
+		Ok(())
+	}
+
 	fn visit_expr(this, s: &Expr){
 		match s {
 			Expr::Literal(literal) => {
@@ -284,6 +294,12 @@ implement_visitor! {
 			Expr::Rebuild(s) => {
 				this.visit_rebuild(s)?;
 			},
+			Expr::Run(s) => {
+				this.visit_run(s)?;
+			},
+			Expr::History(s) => {
+				this.visit_history(s)?;
+			},
 			Expr::Alter(s) => {
 				this.visit_alter(s)?;
 			},
@@ -541,6 +557,30 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_run(this, r: &RunStatement){
+		match r {
+			RunStatement::Job(r) => {
+				this.visit_run_job(r)?;
+			},
+		}
+		Ok(())
+	}
+
+	fn visit_run_job(this, r: &RunJobStatement){
+		Ok(())
+	}
+
+	fn visit_history(this, h: &HistoryStatement){
+		this.visit_expr(&h.what)?;
+		if let Some(s) = h.since.as_ref(){
+			this.visit_expr(s)?;
+		}
+		if let Some(u) = h.until.as_ref(){
+			this.visit_expr(u)?;
+		}
+		Ok(())
+	}
+
 	fn visit_use(this, t: &UseStatement){
 		Ok(())
 	}
@@ -664,6 +704,18 @@ implement_visitor! {
 			RemoveStatement::Config(r) => {
 				this.visit_remove_config(r)?;
 			},
+			RemoveStatement::Job(r) => {
+				this.visit_remove_job(r)?;
+			},
+			RemoveStatement::Sink(r) => {
+				this.visit_remove_sink(r)?;
+			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_remove_consumer(r)?;
+			},
+			RemoveStatement::Key(r) => {
+				this.visit_remove_key(r)?;
+			},
 		}
 		Ok(())
 	}
@@ -750,6 +802,26 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_remove_job(this, r: &RemoveJobStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
+	fn visit_remove_sink(this, r: &RemoveSinkStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
+	fn visit_remove_consumer(this, r: &RemoveConsumerStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
+	fn visit_remove_key(this, r: &RemoveKeyStatement){
+		this.visit_expr(&r.name)?;
+		Ok(())
+	}
+
 	fn visit_relate(this, o: &RelateStatement){
 		this.visit_expr(&o.through)?;
 		this.visit_expr(&o.from)?;
@@ -858,6 +930,9 @@ implement_visitor! {
 			DefineStatement::Function(d) => {
 				this.visit_define_function(d)?;
 			},
+			DefineStatement::Aggregate(d) => {
+				this.visit_define_aggregate(d)?;
+			},
 			DefineStatement::Analyzer(d) => {
 				this.visit_define_analyzer(d)?;
 			},
@@ -900,6 +975,18 @@ implement_visitor! {
 			DefineStatement::Module(d) => {
 				this.visit_define_module(d)?;
 			},
+			DefineStatement::Job(d) => {
+				this.visit_define_job(d)?;
+			},
+			DefineStatement::Sink(d) => {
+				this.visit_define_sink(d)?;
+			},
+			DefineStatement::Consumer(d) => {
+				this.visit_define_consumer(d)?;
+			},
+			DefineStatement::Key(d) => {
+				this.visit_define_key(d)?;
+			},
 		}
 		Ok(())
 	}
@@ -912,6 +999,61 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_job(this, d: &DefineJobStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.schedule)?;
+		this.visit_expr(&d.then)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
+	fn visit_define_sink(this, d: &DefineSinkStatement) {
+		this.visit_expr(&d.name)?;
+		for t in d.tables.iter() {
+			this.visit_expr(t)?;
+		}
+		this.visit_sink_target(&d.target)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
+	fn visit_sink_target(this, t: &SinkTarget) {
+		match t {
+			SinkTarget::Webhook { url, headers } => {
+				this.visit_expr(url)?;
+				if let Some(headers) = headers.as_ref() {
+					this.visit_expr(headers)?;
+				}
+			}
+			SinkTarget::File(path) => {
+				this.visit_expr(path)?;
+			}
+			SinkTarget::Stdout => {}
+			SinkTarget::Custom(name) => {
+				this.visit_expr(name)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn visit_define_consumer(this, d: &DefineConsumerStatement) {
+		this.visit_expr(&d.name)?;
+		if let Some(t) = d.table.as_ref() {
+			this.visit_expr(t)?;
+		}
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
+	fn visit_define_key(this, d: &DefineKeyStatement) {
+		this.visit_expr(&d.name)?;
+		if let Some(v) = d.value.as_ref() {
+			this.visit_expr(v)?;
+		}
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
 	fn visit_define_bucket(this, d: &DefineBucketStatement) {
 		this.visit_expr(&d.name)?;
 		if let Some(expr) = d.backend.as_ref(){
@@ -1210,6 +1352,22 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_aggregate(this, d: &DefineAggregateStatement){
+		for (_, k) in d.args.iter(){
+			this.visit_kind(k)?;
+		}
+		if let Some(k) = d.returns.as_ref(){
+			this.visit_kind(k)?;
+		}
+		this.visit_expr(&d.state)?;
+		this.visit_block(&d.accumulate)?;
+		this.visit_block(&d.merge)?;
+		this.visit_block(&d.finalize)?;
+		this.visit_permission(&d.permissions)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
 	fn visit_permission(this, p: &Permission){
 		match p {
 			Permission::None |
@@ -1650,6 +1808,7 @@ implement_visitor_mut! {
 		TopLevelExpr::Option(s) =>{ this.visit_mut_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_mut_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_mut_show(s)?; },
+		TopLevelExpr::Ack(s) => {this.visit_mut_ack(s)?; },
 		TopLevelExpr::Expr(e) => {this.visit_mut_expr(e)?; },
 		}
 		Ok(())
@@ -1738,6 +1897,11 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_ack(this, a: &mut AckStatement){
+		this.visit_mut_expr(&mut a.versionstamp)?;
+		Ok(())
+	}
+
 	fn visit_mut_expr(this, s: &mut Expr){
 		match s {
 			Expr::Literal(literal) => {
@@ -1794,6 +1958,12 @@ implement_visitor_mut! {
 			Expr::Rebuild(s) => {
 				this.visit_mut_rebuild(s)?;
 			},
+			Expr::Run(s) => {
+				this.visit_mut_run(s)?;
+			},
+			Expr::History(s) => {
+				this.visit_mut_history(s)?;
+			},
 			Expr::Alter(s) => {
 				this.visit_mut_alter(s)?;
 			},
@@ -2051,6 +2221,30 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_run(this, r: &mut RunStatement){
+		match r {
+			RunStatement::Job(r) => {
+				this.visit_mut_run_job(r)?;
+			},
+		}
+		Ok(())
+	}
+
+	fn visit_mut_run_job(this, r: &mut RunJobStatement){
+		Ok(())
+	}
+
+	fn visit_mut_history(this, h: &mut HistoryStatement){
+		this.visit_mut_expr(&mut h.what)?;
+		if let Some(s) = h.since.as_mut(){
+			this.visit_mut_expr(s)?;
+		}
+		if let Some(u) = h.until.as_mut(){
+			this.visit_mut_expr(u)?;
+		}
+		Ok(())
+	}
+
 	fn visit_mut_use(this, t: &mut UseStatement){
 		Ok(())
 	}
@@ -2174,6 +2368,18 @@ implement_visitor_mut! {
 			RemoveStatement::Config(r) => {
 				this.visit_mut_remove_config(r)?;
 			},
+			RemoveStatement::Job(r) => {
+				this.visit_mut_remove_job(r)?;
+			},
+			RemoveStatement::Sink(r) => {
+				this.visit_mut_remove_sink(r)?;
+			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_mut_remove_consumer(r)?;
+			},
+			RemoveStatement::Key(r) => {
+				this.visit_mut_remove_key(r)?;
+			},
 		}
 		Ok(())
 	}
@@ -2260,6 +2466,26 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_remove_job(this, r: &mut RemoveJobStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
+	fn visit_mut_remove_sink(this, r: &mut RemoveSinkStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
+	fn visit_mut_remove_consumer(this, r: &mut RemoveConsumerStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
+	fn visit_mut_remove_key(this, r: &mut RemoveKeyStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		Ok(())
+	}
+
 	fn visit_mut_relate(this, o: &mut RelateStatement){
 		this.visit_mut_expr(&mut o.through)?;
 		this.visit_mut_expr(&mut o.from)?;
@@ -2368,6 +2594,9 @@ implement_visitor_mut! {
 			DefineStatement::Function(d) => {
 				this.visit_mut_define_function(d)?;
 			},
+			DefineStatement::Aggregate(d) => {
+				this.visit_mut_define_aggregate(d)?;
+			},
 			DefineStatement::Analyzer(d) => {
 				this.visit_mut_define_analyzer(d)?;
 			},
@@ -2410,6 +2639,18 @@ implement_visitor_mut! {
 			DefineStatement::Module(d) => {
 				this.visit_mut_define_module(d)?;
 			},
+			DefineStatement::Job(d) => {
+				this.visit_mut_define_job(d)?;
+			},
+			DefineStatement::Sink(d) => {
+				this.visit_mut_define_sink(d)?;
+			},
+			DefineStatement::Consumer(d) => {
+				this.visit_mut_define_consumer(d)?;
+			},
+			DefineStatement::Key(d) => {
+				this.visit_mut_define_key(d)?;
+			},
 		}
 		Ok(())
 	}
@@ -2423,6 +2664,61 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_job(this, d: &mut DefineJobStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.schedule)?;
+		this.visit_mut_expr(&mut d.then)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
+	fn visit_mut_define_sink(this, d: &mut DefineSinkStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		for t in d.tables.iter_mut() {
+			this.visit_mut_expr(t)?;
+		}
+		this.visit_mut_sink_target(&mut d.target)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
+	fn visit_mut_sink_target(this, t: &mut SinkTarget) {
+		match t {
+			SinkTarget::Webhook { url, headers } => {
+				this.visit_mut_expr(url)?;
+				if let Some(headers) = headers.as_mut() {
+					this.visit_mut_expr(headers)?;
+				}
+			}
+			SinkTarget::File(path) => {
+				this.visit_mut_expr(path)?;
+			}
+			SinkTarget::Stdout => {}
+			SinkTarget::Custom(name) => {
+				this.visit_mut_expr(name)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn visit_mut_define_consumer(this, d: &mut DefineConsumerStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		if let Some(t) = d.table.as_mut() {
+			this.visit_mut_expr(t)?;
+		}
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
+	fn visit_mut_define_key(this, d: &mut DefineKeyStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		if let Some(v) = d.value.as_mut() {
+			this.visit_mut_expr(v)?;
+		}
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
 	fn visit_mut_define_bucket(this, d: &mut DefineBucketStatement) {
 		this.visit_mut_expr(&mut d.name)?;
 		if let Some(expr) = d.backend.as_mut(){
@@ -2717,6 +3013,22 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_aggregate(this, d: &mut DefineAggregateStatement){
+		for (_, k) in d.args.iter_mut(){
+			this.visit_mut_kind(k)?;
+		}
+		if let Some(k) = d.returns.as_mut(){
+			this.visit_mut_kind(k)?;
+		}
+		this.visit_mut_expr(&mut d.state)?;
+		this.visit_mut_block(&mut d.accumulate)?;
+		this.visit_mut_block(&mut d.merge)?;
+		this.visit_mut_block(&mut d.finalize)?;
+		this.visit_mut_permission(&mut d.permissions)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
 	fn visit_mut_permission(this, p: &mut Permission){
 		match p {
 			Permission::None |
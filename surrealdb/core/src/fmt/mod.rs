@@ -179,6 +179,8 @@ impl ToSql for CoverStmts<'_> {
 			| sql::Expr::Define(_)
 			| sql::Expr::Remove(_)
 			| sql::Expr::Rebuild(_)
+			| sql::Expr::Run(_)
+			| sql::Expr::History(_)
 			| sql::Expr::Alter(_)
 			| sql::Expr::Info(_)
 			| sql::Expr::Foreach(_)
@@ -0,0 +1,270 @@
+//! Grouped aggregates for table queries.
+//!
+//! For each table (e.g. `person`) a `personAggregate` Query field is generated
+//! which runs a `GROUP BY` query over the table:
+//!
+//! ```graphql
+//! personAggregate(groupBy: [_orderable_person!], filter/where, version): [personAggregate!]!
+//! ```
+//!
+//! Each returned group has a `count`, the values of the `groupBy` fields in
+//! `group`, and the `sum`, `avg`, `min` and `max` of the numeric fields of the
+//! table. Without `groupBy` a single group covering all of the matching
+//! records is returned.
+//!
+//! Only the aggregates which are selected in the GraphQL query are computed.
+//! The numeric aggregates are limited to required `int`, `float`, `decimal`
+//! and `number` fields, as the math functions reject missing values.
+//!
+//! ## Generated types
+//!
+//! - `personAggregate` -- a group with its `count`, `group` values and aggregates.
+//! - `personAggregateNumbers` -- the `sum`, `min` or `max` of each numeric field.
+//! - `personAggregateAverages` -- the `avg` of each numeric field.
+
+use std::sync::Arc;
+
+use async_graphql::Value as GqlValue;
+use async_graphql::dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, Type, TypeRef};
+use surrealdb_types::ToSql;
+
+use super::error::{GqlError, resolver_error};
+use super::schema::{kind_to_type, unwrap_type};
+use super::tables::{
+	execute_select, filter_name_from_table, make_sub_field_resolver, parse_filter_arg,
+	parse_version_arg, resolve_nested_object_value, select_all_from_table,
+};
+use crate::catalog::{FieldDefinition, TableDefinition};
+use crate::dbs::Session;
+use crate::expr::field::{Field as SelectField, Selector};
+use crate::expr::{Expr, Fields, Function, FunctionCall, Group, Groups, Idiom, Kind};
+use crate::gql::error::internal_error;
+use crate::kvs::Datastore;
+use crate::val::{Array as SurArray, Object as SurObject, Value};
+
+/// The numeric aggregates, with the function which computes each of them.
+const NUMERIC_AGGREGATES: [(&str, &str); 4] =
+	[("sum", "math::sum"), ("avg", "math::mean"), ("min", "math::min"), ("max", "math::max")];
+
+/// The name of the aggregate type of a table (e.g. `personAggregate`).
+pub(crate) fn aggregate_name_from_table(tb_name: &str) -> String {
+	format!("{tb_name}Aggregate")
+}
+
+/// The fields of a table which numeric aggregates can be computed over.
+fn numeric_fields(fds: &[FieldDefinition]) -> Vec<(String, Kind)> {
+	fds.iter()
+		.filter(|fd| fd.name.0.len() == 1 && !fd.name.is_id())
+		.filter_map(|fd| match &fd.field_kind {
+			Some(k @ (Kind::Int | Kind::Float | Kind::Decimal | Kind::Number)) => {
+				Some((fd.name.to_sql(), k.clone()))
+			}
+			_ => None,
+		})
+		.collect()
+}
+
+/// Parse the optional `groupBy` argument into the names of the grouped fields.
+fn parse_group_by_arg(
+	args: &async_graphql::dynamic::indexmap::IndexMap<async_graphql::Name, GqlValue>,
+) -> Result<Vec<String>, GqlError> {
+	match args.get("groupBy") {
+		Some(GqlValue::List(items)) => items
+			.iter()
+			.map(|v| match v {
+				GqlValue::Enum(name) => Ok(name.to_string()),
+				_ => Err(resolver_error("groupBy must be a list of fields")),
+			})
+			.collect(),
+		Some(GqlValue::Enum(name)) => Ok(vec![name.to_string()]),
+		Some(GqlValue::Null) | None => Ok(Vec::new()),
+		Some(_) => Err(resolver_error("groupBy must be a list of fields")),
+	}
+}
+
+/// Build a call to an aggregate function.
+fn aggregate_call(function: &str, arguments: Vec<Expr>) -> Expr {
+	Expr::FunctionCall(Box::new(FunctionCall {
+		receiver: Function::Normal(function.to_string()),
+		arguments,
+	}))
+}
+
+// ---------------------------------------------------------------------------
+// Query root field
+// ---------------------------------------------------------------------------
+
+/// Build the `<table>Aggregate` query field for computing grouped aggregates.
+pub(crate) fn make_table_aggregate_field(
+	tb: &TableDefinition,
+	fds: Arc<[FieldDefinition]>,
+	kvs: Arc<Datastore>,
+) -> Field {
+	let tb_name = tb.name.clone();
+	let tb_name_str = tb_name.clone().into_string();
+	let aggregate_name = aggregate_name_from_table(&tb_name_str);
+	let table_orderable_name = format!("_orderable_{tb_name}");
+	let table_filter_name = filter_name_from_table(&tb_name);
+	let numeric: Arc<[(String, Kind)]> = numeric_fields(&fds).into();
+
+	Field::new(aggregate_name.clone(), TypeRef::named_nn_list_nn(&aggregate_name), move |ctx| {
+		let tb_name = tb_name.clone();
+		let fds = fds.clone();
+		let kvs = kvs.clone();
+		let numeric = numeric.clone();
+		FieldFuture::new(async move {
+			let sess = ctx.data::<Arc<Session>>()?;
+			let args = ctx.args.as_index_map();
+
+			let group_by = parse_group_by_arg(args)?;
+			let version = parse_version_arg(args)?;
+			let cond = parse_filter_arg(args, &fds, tb_name.as_str())?;
+
+			// SELECT <group fields>, count() AS _count, math::sum(<field>) AS _sum_<field>, ..
+			let mut fields = Vec::new();
+			let mut groups = Vec::new();
+			for g in group_by.iter() {
+				fields.push(SelectField::Single(Selector {
+					expr: Expr::Idiom(Idiom::field(g.clone())),
+					alias: None,
+				}));
+				groups.push(Group(Idiom::field(g.clone())));
+			}
+			fields.push(SelectField::Single(Selector {
+				expr: aggregate_call("count", Vec::new()),
+				alias: Some(Idiom::field("_count".to_string())),
+			}));
+			// Only compute the aggregates which were requested
+			let look_ahead = ctx.look_ahead();
+			let mut selected = Vec::new();
+			for (op, function) in NUMERIC_AGGREGATES {
+				for (name, _) in numeric.iter() {
+					if !look_ahead.field(op).field(name).exists() {
+						continue;
+					}
+					let alias = format!("_{op}_{name}");
+					fields.push(SelectField::Single(Selector {
+						expr: aggregate_call(
+							function,
+							vec![Expr::Idiom(Idiom::field(name.clone()))],
+						),
+						alias: Some(Idiom::field(alias.clone())),
+					}));
+					selected.push((op, name.clone(), alias));
+				}
+			}
+
+			let mut stmt =
+				select_all_from_table(Expr::Table(tb_name), cond, None, None, None, &version);
+			stmt.fields = Fields::Select(fields);
+			// GROUP BY <group fields>, or GROUP ALL without any
+			stmt.group = Some(Groups(groups));
+
+			let res = execute_select(&kvs, sess, stmt).await?;
+			let Value::Array(SurArray(rows)) = res else {
+				error!("Found top level value, in result which should be array: {res:?}");
+				return Err(internal_error("Unexpected result type from aggregate query").into());
+			};
+
+			let mut out = Vec::with_capacity(rows.len());
+			for row in rows {
+				let Value::Object(mut row) = row else {
+					error!("Expected object in result, found: {row:?}");
+					return Err(internal_error("Expected object in result").into());
+				};
+				let mut aggregate = SurObject::default();
+				aggregate.insert("count".to_string(), row.remove("_count").unwrap_or(Value::None));
+				for (op, name, alias) in selected.iter() {
+					let value = row.remove(alias.as_str()).unwrap_or(Value::None);
+					let values = aggregate
+						.entry((*op).to_string())
+						.or_insert_with(|| Value::Object(SurObject::default()));
+					if let Value::Object(values) = values {
+						values.insert(name.clone(), value);
+					}
+				}
+				let mut group = SurObject::default();
+				for g in group_by.iter() {
+					group.insert(g.clone(), row.remove(g.as_str()).unwrap_or(Value::None));
+				}
+				aggregate.insert("group".to_string(), Value::Object(group));
+				out.push(FieldValue::owned_any(aggregate));
+			}
+			Ok(Some(FieldValue::list(out)))
+		})
+	})
+	.description(format!(
+		"Generated from table `{}`\nallows computing aggregates over groups of records",
+		tb.name
+	))
+	.argument(InputValue::new("groupBy", TypeRef::named_nn_list(&table_orderable_name)))
+	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+}
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Build the aggregate types of a table.
+pub(crate) fn make_aggregate_types(
+	tb_name: &str,
+	fds: &[FieldDefinition],
+	types: &mut Vec<Type>,
+) -> Result<Vec<Object>, GqlError> {
+	let aggregate_name = aggregate_name_from_table(tb_name);
+	let numbers_name = format!("{aggregate_name}Numbers");
+	let averages_name = format!("{aggregate_name}Averages");
+
+	let mut aggregate = Object::new(&aggregate_name)
+		.description(format!("Generated from `{tb_name}` the aggregates of a group of records"))
+		.field(Field::new(
+			"count",
+			TypeRef::named_nn(TypeRef::INT),
+			make_sub_field_resolver("count".to_string(), Some(Kind::Int), None),
+		))
+		.field(Field::new(
+			"group",
+			TypeRef::named_nn("object"),
+			make_sub_field_resolver("group".to_string(), Some(Kind::Object), None),
+		));
+
+	let numeric = numeric_fields(fds);
+	// An object type must have at least one field
+	if numeric.is_empty() {
+		return Ok(vec![aggregate]);
+	}
+
+	let mut numbers = Object::new(&numbers_name);
+	let mut averages = Object::new(&averages_name);
+	for (name, kind) in numeric {
+		let ty = unwrap_type(kind_to_type(kind.clone(), types, false)?);
+		numbers = numbers.field(Field::new(
+			&name,
+			ty,
+			make_sub_field_resolver(name.clone(), Some(kind), None),
+		));
+		averages = averages.field(Field::new(
+			&name,
+			TypeRef::named("number"),
+			make_sub_field_resolver(name.clone(), Some(Kind::Number), None),
+		));
+	}
+
+	for (op, _) in NUMERIC_AGGREGATES {
+		let ty = if op == "avg" {
+			&averages_name
+		} else {
+			&numbers_name
+		};
+		aggregate = aggregate.field(Field::new(op, TypeRef::named(ty), move |ctx| {
+			FieldFuture::new(async move {
+				let obj = ctx.parent_value.try_downcast_ref::<SurObject>()?;
+				resolve_nested_object_value(obj.get(op).cloned().unwrap_or(Value::None), false)
+			})
+		}));
+	}
+
+	Ok(vec![aggregate, numbers, averages])
+}
@@ -0,0 +1,595 @@
+//! Relay-style cursor pagination for table queries.
+//!
+//! For each table (e.g. `person`) a `personConnection` Query field is
+//! generated next to the plain list query:
+//!
+//! ```graphql
+//! personConnection(first, after, last, before, order, filter/where, version): personConnection!
+//! ```
+//!
+//! Unlike `start`/`limit`, the pages are selected with a keyset condition on
+//! the ordered fields rather than an offset, so a page is stable when records
+//! are inserted or deleted before it, and paging deep into a table does not
+//! scan all of the preceding records.
+//!
+//! ## Cursors
+//!
+//! A cursor holds the values of the ordered fields of a record, followed by
+//! its id which breaks ties between records with equal order keys. The values
+//! are stored with their revisioned encoding and base64 encoded, so cursors
+//! are opaque to clients. A cursor is only meaningful for the `order` with
+//! which it was returned.
+//!
+//! Cursors are sent back by clients, so they are validated before they are
+//! turned into a query condition: a cursor is limited to
+//! [`MAX_CURSOR_LENGTH`] characters, the ordered fields can only hold scalar
+//! values of the type defined for the field, and the id must be a record of
+//! the paged table. Connections can therefore not be ordered by fields which
+//! hold arrays, objects or other compound values.
+//!
+//! ## Generated types
+//!
+//! - `personConnection` -- `edges`, `nodes`, `pageInfo` and `totalCount`.
+//! - `personEdge` -- a `cursor` and the `node` record.
+//! - `PageInfo` -- shared by all tables, as defined by the Relay specification.
+//!
+//! `totalCount` is only computed when requested, with a separate
+//! `SELECT VALUE count() ... GROUP ALL` query.
+
+use std::sync::Arc;
+
+use async_graphql::dynamic::indexmap::IndexMap;
+use async_graphql::dynamic::{
+	Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, TypeRef,
+};
+use async_graphql::{Name, Value as GqlValue};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use surrealdb_types::ToSql;
+
+use super::error::{GqlError, resolver_error};
+use super::tables::{
+	CachedRecord, execute_select, filter_name_from_table, parse_filter_arg, parse_order_arg,
+	parse_version_arg, select_all_from_table,
+};
+use crate::catalog::{FieldDefinition, TableDefinition};
+use crate::dbs::Session;
+use crate::expr::field::Selector;
+use crate::expr::order::{Order, OrderList, Ordering};
+use crate::expr::part::Part;
+use crate::expr::{
+	BinaryOperator, Cond, Expr, Fields, Function, FunctionCall, Groups, Idiom, Limit, Literal,
+};
+use crate::gql::error::internal_error;
+use crate::gql::utils::GqlValueUtils;
+use crate::kvs::Datastore;
+use crate::val::{Array as SurArray, Datetime, Object as SurObject, RecordIdKey, TableName, Value};
+
+/// The name of the connection type of a table (e.g. `personConnection`).
+pub(crate) fn connection_name_from_table(tb_name: &str) -> String {
+	format!("{tb_name}Connection")
+}
+
+/// The name of the edge type of a table (e.g. `personEdge`).
+fn edge_name_from_table(tb_name: &str) -> String {
+	format!("{tb_name}Edge")
+}
+
+/// A page of records returned by a connection field.
+struct Connection {
+	edges: Vec<Edge>,
+	page_info: PageInfo,
+	/// The query used to count all of the records matching the filter.
+	count: CountQuery,
+}
+
+/// A record within a page, along with the cursor pointing at it.
+#[derive(Clone)]
+struct Edge {
+	cursor: String,
+	node: CachedRecord,
+}
+
+/// Information about the position of a page within the whole result.
+#[derive(Clone)]
+struct PageInfo {
+	has_next_page: bool,
+	has_previous_page: bool,
+	start_cursor: Option<String>,
+	end_cursor: Option<String>,
+}
+
+/// Everything needed to resolve `totalCount` lazily.
+struct CountQuery {
+	table: TableName,
+	cond: Option<Cond>,
+	version: Option<Datetime>,
+}
+
+// ---------------------------------------------------------------------------
+// Cursors
+// ---------------------------------------------------------------------------
+
+/// The maximum length of a cursor, checked before a cursor is decoded.
+const MAX_CURSOR_LENGTH: usize = 4096;
+
+/// Whether the idiom of an order is the id of the record.
+fn is_id_order(order: &Order) -> bool {
+	order.value == Idiom::field("id".to_string())
+}
+
+/// Whether a value can be stored in a cursor as the value of an ordered field.
+fn is_scalar(value: &Value) -> bool {
+	matches!(
+		value,
+		Value::None
+			| Value::Null
+			| Value::Bool(_)
+			| Value::Number(_)
+			| Value::String(_)
+			| Value::Duration(_)
+			| Value::Datetime(_)
+			| Value::Uuid(_)
+	)
+}
+
+/// Whether a value can be stored in a cursor as a part of a record id key.
+fn is_id_part(value: &Value) -> bool {
+	match value {
+		Value::Array(SurArray(values)) => values.iter().all(is_id_part),
+		Value::Object(values) => values.values().all(is_id_part),
+		v => is_scalar(v),
+	}
+}
+
+/// Whether a value can be stored in a cursor as the id of a record in the
+/// given table.
+fn is_cursor_id(value: &Value, tb: &TableName) -> bool {
+	let Value::RecordId(rid) = value else {
+		return false;
+	};
+	rid.table == *tb
+		&& match &rid.key {
+			RecordIdKey::Number(_) | RecordIdKey::String(_) | RecordIdKey::Uuid(_) => true,
+			RecordIdKey::Array(SurArray(values)) => values.iter().all(is_id_part),
+			RecordIdKey::Object(values) => values.values().all(is_id_part),
+			RecordIdKey::Range(_) => false,
+		}
+}
+
+/// Encode the order key of a record into an opaque cursor.
+fn encode_cursor(values: Vec<Value>, orders: &[Order]) -> Result<String, GqlError> {
+	for (order, value) in orders.iter().zip(&values) {
+		if !is_id_order(order) && !is_scalar(value) {
+			return Err(resolver_error(format!(
+				"Can not page through records ordered by `{}`, which does not hold a scalar value",
+				order.value.to_sql()
+			)));
+		}
+	}
+	let bytes = revision::to_vec(&Value::Array(SurArray(values)))
+		.map_err(|e| internal_error(format!("Failed to encode cursor: {e}")))?;
+	let cursor = URL_SAFE_NO_PAD.encode(bytes);
+	if cursor.len() > MAX_CURSOR_LENGTH {
+		return Err(resolver_error("The ordered values of a record are too large for a cursor"));
+	}
+	Ok(cursor)
+}
+
+/// Decode a cursor into the order key of a record, checking that each value
+/// matches the ordered field it was taken from.
+fn decode_cursor(
+	cursor: &str,
+	orders: &[Order],
+	fds: &[FieldDefinition],
+	tb: &TableName,
+) -> Result<Vec<Value>, GqlError> {
+	if cursor.len() > MAX_CURSOR_LENGTH {
+		return Err(resolver_error("Invalid cursor"));
+	}
+	let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| resolver_error("Invalid cursor"))?;
+	let values = match revision::from_slice::<Value>(&bytes) {
+		Ok(Value::Array(SurArray(values))) if values.len() == orders.len() => values,
+		_ => return Err(resolver_error("Invalid cursor for the given order")),
+	};
+	for (order, value) in orders.iter().zip(&values) {
+		let valid = if is_id_order(order) {
+			is_cursor_id(value, tb)
+		} else {
+			// A record may not hold the ordered field at all
+			let kind =
+				fds.iter().find(|fd| fd.name == order.value).and_then(|fd| fd.field_kind.as_ref());
+			is_scalar(value)
+				&& (matches!(value, Value::None)
+					|| kind.is_none_or(|kind| value.can_coerce_to_kind(kind)))
+		};
+		if !valid {
+			return Err(resolver_error("Invalid cursor for the given order"));
+		}
+	}
+	Ok(values)
+}
+
+/// Extract the order key of a record from its data.
+fn order_key(data: &SurObject, orders: &[Order]) -> Vec<Value> {
+	orders
+		.iter()
+		.map(|o| match o.value.0.as_slice() {
+			[Part::Field(name)] => data.get(name.as_str()).cloned().unwrap_or(Value::None),
+			_ => Value::None,
+		})
+		.collect()
+}
+
+/// Build the keyset condition which selects the records after (or before) a
+/// cursor in the given order.
+///
+/// For orders `a, b` and a cursor `(x, y)`, the records after the cursor are
+/// those where `a > x OR (a = x AND b > y)`, with the comparison flipped for
+/// descending fields.
+fn cursor_cond(orders: &[Order], values: Vec<Value>, after: bool) -> Option<Expr> {
+	let mut cond = None;
+	let mut prefix: Option<Expr> = None;
+	for (order, value) in orders.iter().zip(values) {
+		let field = Expr::Idiom(order.value.clone());
+		let value = value.into_literal();
+		let op = if order.direction == after {
+			BinaryOperator::MoreThan
+		} else {
+			BinaryOperator::LessThan
+		};
+		let cmp = Expr::Binary {
+			left: Box::new(field.clone()),
+			op,
+			right: Box::new(value.clone()),
+		};
+		let term = match prefix.clone() {
+			Some(p) => Expr::Binary {
+				left: Box::new(p),
+				op: BinaryOperator::And,
+				right: Box::new(cmp),
+			},
+			None => cmp,
+		};
+		cond = Some(match cond {
+			Some(c) => Expr::Binary {
+				left: Box::new(c),
+				op: BinaryOperator::Or,
+				right: Box::new(term),
+			},
+			None => term,
+		});
+		let eq = Expr::Binary {
+			left: Box::new(field),
+			op: BinaryOperator::Equal,
+			right: Box::new(value),
+		};
+		prefix = Some(match prefix {
+			Some(p) => Expr::Binary {
+				left: Box::new(p),
+				op: BinaryOperator::And,
+				right: Box::new(eq),
+			},
+			None => eq,
+		});
+	}
+	cond
+}
+
+/// Combine two optional conditions with `AND`.
+fn and_cond(left: Option<Expr>, right: Option<Expr>) -> Option<Cond> {
+	match (left, right) {
+		(Some(l), Some(r)) => Some(Cond(Expr::Binary {
+			left: Box::new(l),
+			op: BinaryOperator::And,
+			right: Box::new(r),
+		})),
+		(Some(c), None) | (None, Some(c)) => Some(Cond(c)),
+		(None, None) => None,
+	}
+}
+
+/// Parse a non-negative page size argument.
+fn parse_page_size_arg(
+	args: &IndexMap<Name, GqlValue>,
+	name: &str,
+) -> Result<Option<i64>, GqlError> {
+	match args.get(name).and_then(|v| v.as_i64()) {
+		Some(n) if n < 0 => Err(resolver_error(format!("`{name}` must not be negative"))),
+		n => Ok(n),
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Query root field
+// ---------------------------------------------------------------------------
+
+/// Build the `<table>Connection` query field for paging through a table.
+pub(crate) fn make_table_connection_field(
+	tb: &TableDefinition,
+	fds: Arc<[FieldDefinition]>,
+	kvs: Arc<Datastore>,
+) -> Field {
+	let tb_name = tb.name.clone();
+	let tb_name_str = tb_name.clone().into_string();
+	let connection_name = connection_name_from_table(&tb_name_str);
+	let table_order_name = format!("_order_{tb_name}");
+	let table_filter_name = filter_name_from_table(&tb_name);
+
+	Field::new(connection_name.clone(), TypeRef::named_nn(&connection_name), move |ctx| {
+		let tb_name = tb_name.clone();
+		let fds = fds.clone();
+		let kvs = kvs.clone();
+		FieldFuture::new(async move {
+			let sess = ctx.data::<Arc<Session>>()?;
+			let args = ctx.args.as_index_map();
+
+			let first = parse_page_size_arg(args, "first")?;
+			let last = parse_page_size_arg(args, "last")?;
+			if first.is_some() && last.is_some() {
+				return Err(resolver_error("`first` and `last` can not be used together").into());
+			}
+			let version = parse_version_arg(args)?;
+			let filter = parse_filter_arg(args, &fds, tb_name.as_str())?.map(|c| c.0);
+
+			// Order by the requested fields, and then by id so that every
+			// record has a unique position.
+			let mut orders = match parse_order_arg(args)? {
+				Some(Ordering::Order(OrderList(orders))) => orders,
+				_ => Vec::new(),
+			};
+			if !orders.iter().any(is_id_order) {
+				orders.push(Order {
+					value: Idiom::field("id".to_string()),
+					direction: true,
+					..Default::default()
+				});
+			}
+
+			// Paging backwards selects the records in the reverse order, and
+			// flips them back once fetched.
+			let backwards = last.is_some()
+				|| (first.is_none() && matches!(args.get("before"), Some(GqlValue::String(_))));
+			let (cursor, size) = if backwards {
+				(args.get("before"), last)
+			} else {
+				(args.get("after"), first)
+			};
+			let cursor = match cursor {
+				Some(GqlValue::String(c)) => Some(decode_cursor(c, &orders, &fds, &tb_name)?),
+				_ => None,
+			};
+			let has_cursor = cursor.is_some();
+			let keyset = cursor.and_then(|c| cursor_cond(&orders, c, !backwards));
+
+			let query_orders = if backwards {
+				orders
+					.iter()
+					.cloned()
+					.map(|o| Order {
+						direction: !o.direction,
+						..o
+					})
+					.collect()
+			} else {
+				orders.clone()
+			};
+
+			// Fetch one more record than requested to know if there is another page
+			let limit = size.map(|n| Limit(Expr::Literal(Literal::Integer(n.saturating_add(1)))));
+			let stmt = select_all_from_table(
+				Expr::Table(tb_name.clone()),
+				and_cond(filter.clone(), keyset),
+				Some(Ordering::Order(OrderList(query_orders))),
+				limit,
+				None,
+				&version,
+			);
+			let res = execute_select(&kvs, sess, stmt).await?;
+			let Value::Array(SurArray(mut rows)) = res else {
+				error!("Found top level value, in result which should be array: {res:?}");
+				return Err(internal_error("Unexpected result type from table query").into());
+			};
+
+			let has_more = size.is_some_and(|n| rows.len() as i64 > n);
+			if let Some(n) = size {
+				rows.truncate(n as usize);
+			}
+			if backwards {
+				rows.reverse();
+			}
+
+			let mut edges = Vec::with_capacity(rows.len());
+			for row in rows {
+				let Value::Object(data) = row else {
+					error!("Expected object in result, found: {row:?}");
+					return Err(internal_error("Expected object in result").into());
+				};
+				let Some(Value::RecordId(rid)) = data.get("id").cloned() else {
+					error!("Object missing 'id' field or id is not a RecordId: {data:?}");
+					return Err(internal_error("Record missing 'id' field").into());
+				};
+				let cursor = encode_cursor(order_key(&data, &orders), &orders)?;
+				edges.push(Edge {
+					cursor,
+					node: CachedRecord {
+						rid,
+						version: version.clone(),
+						data,
+					},
+				});
+			}
+
+			let page_info = PageInfo {
+				has_next_page: if backwards {
+					has_cursor
+				} else {
+					has_more
+				},
+				has_previous_page: if backwards {
+					has_more
+				} else {
+					has_cursor
+				},
+				start_cursor: edges.first().map(|e| e.cursor.clone()),
+				end_cursor: edges.last().map(|e| e.cursor.clone()),
+			};
+
+			Ok(Some(FieldValue::owned_any(Connection {
+				edges,
+				page_info,
+				count: CountQuery {
+					table: tb_name,
+					cond: filter.map(Cond),
+					version,
+				},
+			})))
+		})
+	})
+	.description(format!(
+		"Generated from table `{}`\nallows paging through a table with cursors",
+		tb.name
+	))
+	.argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
+	.argument(InputValue::new("after", TypeRef::named(TypeRef::STRING)))
+	.argument(InputValue::new("last", TypeRef::named(TypeRef::INT)))
+	.argument(InputValue::new("before", TypeRef::named(TypeRef::STRING)))
+	.argument(InputValue::new("order", TypeRef::named(&table_order_name)))
+	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+}
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Build the connection and edge types of a table.
+pub(crate) fn make_connection_types(tb_name: &str) -> (Object, Object) {
+	let connection_name = connection_name_from_table(tb_name);
+	let edge_name = edge_name_from_table(tb_name);
+
+	let connection = Object::new(&connection_name)
+		.description(format!("Generated from `{tb_name}` a page of records"))
+		.field(Field::new("edges", TypeRef::named_nn_list_nn(&edge_name), |ctx| {
+			FieldFuture::new(async move {
+				let conn = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				let edges = conn.edges.iter().cloned().map(FieldValue::owned_any);
+				Ok(Some(FieldValue::list(edges)))
+			})
+		}))
+		.field(Field::new("nodes", TypeRef::named_nn_list_nn(tb_name), |ctx| {
+			FieldFuture::new(async move {
+				let conn = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				let nodes = conn.edges.iter().map(|e| FieldValue::owned_any(e.node.clone()));
+				Ok(Some(FieldValue::list(nodes)))
+			})
+		}))
+		.field(Field::new("pageInfo", TypeRef::named_nn("PageInfo"), |ctx| {
+			FieldFuture::new(async move {
+				let conn = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				Ok(Some(FieldValue::owned_any(conn.page_info.clone())))
+			})
+		}))
+		.field(Field::new("totalCount", TypeRef::named_nn(TypeRef::INT), |ctx| {
+			FieldFuture::new(async move {
+				let ds = ctx.data::<Arc<Datastore>>()?;
+				let sess = ctx.data::<Arc<Session>>()?;
+				let conn = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				let count = count_records(ds, sess, &conn.count).await?;
+				Ok(Some(FieldValue::value(count)))
+			})
+		}));
+
+	let edge = Object::new(&edge_name)
+		.description(format!("Generated from `{tb_name}` a record within a page"))
+		.field(Field::new("cursor", TypeRef::named_nn(TypeRef::STRING), |ctx| {
+			FieldFuture::new(async move {
+				let edge = ctx.parent_value.try_downcast_ref::<Edge>()?;
+				Ok(Some(FieldValue::value(edge.cursor.clone())))
+			})
+		}))
+		.field(Field::new("node", TypeRef::named_nn(tb_name), |ctx| {
+			FieldFuture::new(async move {
+				let edge = ctx.parent_value.try_downcast_ref::<Edge>()?;
+				Ok(Some(FieldValue::owned_any(edge.node.clone())))
+			})
+		}));
+
+	(connection, edge)
+}
+
+/// Build the `PageInfo` type shared by all connections.
+pub(crate) fn make_page_info_type() -> Object {
+	fn page_info_field(name: &str, ty: TypeRef, f: fn(&PageInfo) -> GqlValue) -> Field {
+		Field::new(name, ty, move |ctx: ResolverContext| {
+			FieldFuture::new(async move {
+				let info = ctx.parent_value.try_downcast_ref::<PageInfo>()?;
+				match f(info) {
+					GqlValue::Null => Ok(None),
+					v => Ok(Some(FieldValue::value(v))),
+				}
+			})
+		})
+	}
+
+	fn cursor_value(c: &Option<String>) -> GqlValue {
+		c.clone().map(GqlValue::String).unwrap_or(GqlValue::Null)
+	}
+
+	Object::new("PageInfo")
+		.description("Information about the position of a page of records")
+		.field(page_info_field("hasNextPage", TypeRef::named_nn(TypeRef::BOOLEAN), |i| {
+			GqlValue::Boolean(i.has_next_page)
+		}))
+		.field(page_info_field("hasPreviousPage", TypeRef::named_nn(TypeRef::BOOLEAN), |i| {
+			GqlValue::Boolean(i.has_previous_page)
+		}))
+		.field(page_info_field("startCursor", TypeRef::named(TypeRef::STRING), |i| {
+			cursor_value(&i.start_cursor)
+		}))
+		.field(page_info_field("endCursor", TypeRef::named(TypeRef::STRING), |i| {
+			cursor_value(&i.end_cursor)
+		}))
+}
+
+/// Count the records matching the filter of a connection.
+async fn count_records(
+	ds: &Datastore,
+	sess: &Session,
+	query: &CountQuery,
+) -> Result<i64, GqlError> {
+	let mut stmt = select_all_from_table(
+		Expr::Table(query.table.clone()),
+		query.cond.clone(),
+		None,
+		None,
+		None,
+		&query.version,
+	);
+	// SELECT VALUE count() ... GROUP ALL
+	stmt.fields = Fields::Value(Box::new(Selector {
+		expr: Expr::FunctionCall(Box::new(FunctionCall {
+			receiver: Function::Normal("count".to_string()),
+			arguments: Vec::new(),
+		})),
+		alias: None,
+	}));
+	stmt.group = Some(Groups(Vec::new()));
+
+	match execute_select(ds, sess, stmt).await? {
+		Value::Array(SurArray(values)) => match values.first() {
+			Some(Value::Number(n)) => Ok(n.as_int()),
+			// Grouping an empty table returns no groups
+			None => Ok(0),
+			Some(v) => {
+				error!("Expected number in count result, found: {v:?}");
+				Err(internal_error("Unexpected result type from count query"))
+			}
+		},
+		v => {
+			error!("Found top level value, in result which should be array: {v:?}");
+			Err(internal_error("Unexpected result type from count query"))
+		}
+	}
+}
@@ -42,6 +42,10 @@ pub async fn process_fns(
 			// Skip functions without a declared return type
 			continue;
 		};
+		if fnd.aggregate.is_some() {
+			// Aggregates can only be called within a GROUP BY query
+			continue;
+		}
 
 		// Clone values that will be moved into the resolver closure
 		let sess1 = session.clone();
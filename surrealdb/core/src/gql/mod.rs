@@ -14,6 +14,12 @@
 //! - **Table queries** ([`tables`]) -- generates Query root fields and Object types for each
 //!   exposed table, including field resolvers, filter/order types, nested objects, and relation
 //!   fields.
+//! - **Connections** ([`connections`]) -- generates Relay-style cursor paginated `<table>Connection`
+//!   fields with `pageInfo` and `totalCount`.
+//! - **Aggregates** ([`aggregates`]) -- generates `<table>Aggregate` fields computing grouped
+//!   counts, sums, averages, minimums and maximums.
+//! - **Search** ([`search`]) -- adds full-text and nearest neighbour search arguments to table
+//!   queries for fields backed by `FULLTEXT` and `HNSW` indexes.
 //! - **Mutations** ([`mutations`]) -- generates Mutation root fields (create, update, upsert,
 //!   delete -- single and bulk) with corresponding input types.
 //! - **Functions** ([`functions`]) -- exposes user-defined database functions as Query fields.
@@ -36,14 +42,17 @@
 //! because `async_graphql` and the HTTP serving stack are not compatible with WASM.
 #![cfg(not(target_family = "wasm"))]
 
+mod aggregates;
 mod auth;
 pub mod cache;
+mod connections;
 pub mod error;
 mod ext;
 mod functions;
 mod mutations;
 mod relations;
 pub mod schema;
+mod search;
 mod subscriptions;
 mod tables;
 mod utils;
@@ -0,0 +1,510 @@
+//! Full-text and vector search for table queries.
+//!
+//! Fields which are backed by a `FULLTEXT` or `HNSW` index are searchable
+//! through additional arguments on the table list query:
+//!
+//! ```graphql
+//! person(search: _search_person, nearest: _nearest_person, ...): [person!]!
+//! ```
+//!
+//! - `search: { bio: { query: "rust" } }` -- translated to `bio @1@ "rust"`. When several fields
+//!   are searched every one of them must match.
+//! - `nearest: { embedding: { vector: [..], k: 10, ef: 40 } }` -- translated to `embedding <|10,40|>
+//!   [..]`. Only a single field can be searched at a time.
+//!
+//! The results of a search are exposed on the table type:
+//!
+//! - `_score` -- the summed `search::score` of the searched fields.
+//! - `_search { bio { score highlight } }` -- the `search::score` and `search::highlight` of each
+//!   searched field.
+//! - `_distance` -- the `vector::distance::knn` of a nearest neighbour search.
+//!
+//! Only the scores and highlights which are selected in the GraphQL query are
+//! computed. Unless an `order` is given, nearest neighbour results are ordered
+//! by their distance and full-text results by their score.
+//!
+//! ## Generated types
+//!
+//! - `_search_<table>` / `_nearest_<table>` -- the `search` / `nearest` argument inputs, with a
+//!   field for each indexed column.
+//! - `_search_result_<table>` -- the per-field matches of a full-text search.
+//! - `_fulltext_query`, `_nearest_query`, `_search_match` -- shared by all tables.
+
+use async_graphql::Value as GqlValue;
+use async_graphql::dynamic::indexmap::IndexMap;
+use async_graphql::dynamic::{
+	Field, FieldFuture, FieldValue, InputObject, InputValue, Object, ResolverContext, Type, TypeRef,
+};
+use surrealdb_types::ToSql;
+
+use super::error::{GqlError, resolver_error};
+use super::schema::sql_value_to_gql_value;
+use super::tables::{CachedRecord, make_sub_field_resolver, resolve_nested_object_value};
+use crate::catalog::{Index, IndexDefinition};
+use crate::expr::field::{Field as SelectField, Selector};
+use crate::expr::operator::{BooleanOperator, MatchesOperator, NearestNeighbor};
+use crate::expr::order::{OrderList, Ordering};
+use crate::expr::part::Part;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{
+	BinaryOperator, Cond, Expr, Fields, Function, FunctionCall, Idiom, Kind, Literal,
+};
+use crate::gql::utils::GqlValueUtils;
+use crate::idx::ft::MatchRef;
+use crate::val::{Object as SurObject, Value};
+
+/// The input type for searching a full-text indexed field.
+const FULLTEXT_QUERY: &str = "_fulltext_query";
+/// The input type for searching an HNSW indexed field.
+const NEAREST_QUERY: &str = "_nearest_query";
+/// The output type with the score and highlight of a searched field.
+const SEARCH_MATCH: &str = "_search_match";
+
+/// The default size of the dynamic candidate list of an HNSW search.
+const DEFAULT_EF: u32 = 40;
+
+/// A column which is backed by a full-text index.
+#[derive(Clone, Debug)]
+struct FullTextColumn {
+	name: String,
+	highlight: bool,
+}
+
+/// The searchable columns of a table, derived from its index definitions.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SearchIndexes {
+	fulltext: Vec<FullTextColumn>,
+	hnsw: Vec<String>,
+}
+
+impl SearchIndexes {
+	/// Collect the single-column `FULLTEXT` and `HNSW` indexes of a table.
+	pub(crate) fn from_indexes(ixs: &[IndexDefinition]) -> Self {
+		let mut out = SearchIndexes::default();
+		for ix in ixs.iter().filter(|ix| !ix.prepare_remove) {
+			let [col] = ix.cols.as_slice() else {
+				continue;
+			};
+			if col.0.len() != 1 || !matches!(col.0[0], Part::Field(_)) {
+				continue;
+			}
+			let name = col.to_sql();
+			match &ix.index {
+				Index::FullText(params) => {
+					if !out.fulltext.iter().any(|c| c.name == name) {
+						out.fulltext.push(FullTextColumn {
+							name,
+							highlight: params.highlight,
+						});
+					}
+				}
+				Index::Hnsw(_) => {
+					if !out.hnsw.contains(&name) {
+						out.hnsw.push(name);
+					}
+				}
+				_ => {}
+			}
+		}
+		out
+	}
+
+	pub(crate) fn has_fulltext(&self) -> bool {
+		!self.fulltext.is_empty()
+	}
+
+	pub(crate) fn has_hnsw(&self) -> bool {
+		!self.hnsw.is_empty()
+	}
+}
+
+fn search_input_name(tb_name: &str) -> String {
+	format!("_search_{tb_name}")
+}
+
+fn nearest_input_name(tb_name: &str) -> String {
+	format!("_nearest_{tb_name}")
+}
+
+fn search_result_name(tb_name: &str) -> String {
+	format!("_search_result_{tb_name}")
+}
+
+fn alias(path: &[&str]) -> Idiom {
+	Idiom(path.iter().map(|p| Part::Field((*p).to_string())).collect())
+}
+
+fn function_call(function: &str, arguments: Vec<Expr>) -> Expr {
+	Expr::FunctionCall(Box::new(FunctionCall {
+		receiver: Function::Normal(function.to_string()),
+		arguments,
+	}))
+}
+
+// ---------------------------------------------------------------------------
+// Query translation
+// ---------------------------------------------------------------------------
+
+/// The parts of a `SELECT` statement which implement a search.
+#[derive(Default)]
+pub(crate) struct SearchQuery {
+	cond: Option<Expr>,
+	fields: Vec<SelectField>,
+	order: Option<Ordering>,
+}
+
+impl SearchQuery {
+	/// Add the search condition, computed fields, and default ordering to a
+	/// `SELECT * FROM <table>` statement.
+	pub(crate) fn apply(self, stmt: &mut SelectStatement) {
+		if let Some(search) = self.cond {
+			stmt.cond = Some(match stmt.cond.take() {
+				Some(Cond(filter)) => Cond(Expr::Binary {
+					left: Box::new(filter),
+					op: BinaryOperator::And,
+					right: Box::new(search),
+				}),
+				None => Cond(search),
+			});
+		}
+		if !self.fields.is_empty() {
+			let mut fields = vec![SelectField::All];
+			fields.extend(self.fields);
+			stmt.fields = Fields::Select(fields);
+		}
+		if stmt.order.is_none() {
+			stmt.order = self.order;
+		}
+	}
+}
+
+fn and_expr(left: Option<Expr>, right: Expr) -> Expr {
+	match left {
+		Some(left) => Expr::Binary {
+			left: Box::new(left),
+			op: BinaryOperator::And,
+			right: Box::new(right),
+		},
+		None => right,
+	}
+}
+
+fn order_by(field: &str, ascending: bool) -> Ordering {
+	Ordering::Order(OrderList(vec![crate::expr::Order {
+		value: Idiom::field(field.to_string()),
+		direction: ascending,
+		..Default::default()
+	}]))
+}
+
+/// Parse the `search` and `nearest` arguments of a table list query.
+pub(crate) fn parse_search_args(
+	ctx: &ResolverContext,
+	indexes: &SearchIndexes,
+) -> Result<SearchQuery, GqlError> {
+	let args = ctx.args.as_index_map();
+	let mut out = SearchQuery::default();
+	let look_ahead = ctx.look_ahead();
+
+	if let Some(search) = args.get("search").and_then(GqlValueUtils::as_object) {
+		let mut scores = Vec::new();
+		for (rf, (name, query)) in search.iter().enumerate() {
+			let Some(query) = query.as_object() else {
+				continue;
+			};
+			let Some(column) = indexes.fulltext.iter().find(|c| c.name == name.as_str()) else {
+				return Err(resolver_error(format!("Field `{name}` has no full-text index")));
+			};
+			let Some(text) = query.get("query").and_then(GqlValueUtils::as_string) else {
+				return Err(resolver_error("Full-text search requires a `query`"));
+			};
+			let rf = MatchRef::try_from(rf + 1)
+				.map_err(|_| resolver_error("Too many fields in full-text search"))?;
+			let rf_expr = Expr::Literal(Literal::Integer(i64::from(rf)));
+
+			// <field> @<rf>@ <query>
+			let cond = Expr::Binary {
+				left: Box::new(Expr::Idiom(Idiom::field(column.name.clone()))),
+				op: BinaryOperator::Matches(MatchesOperator {
+					rf: Some(rf),
+					operator: BooleanOperator::And,
+				}),
+				right: Box::new(Expr::Literal(Literal::String(text))),
+			};
+			out.cond = Some(and_expr(out.cond.take(), cond));
+			scores.push(function_call("search::score", vec![rf_expr.clone()]));
+
+			let matched = look_ahead.field("_search").field(&column.name);
+			if matched.field("score").exists() {
+				out.fields.push(SelectField::Single(Selector {
+					expr: function_call("search::score", vec![rf_expr.clone()]),
+					alias: Some(alias(&["_search", &column.name, "score"])),
+				}));
+			}
+			if matched.field("highlight").exists() {
+				if !column.highlight {
+					return Err(resolver_error(format!(
+						"The full-text index on `{}` does not have HIGHLIGHTS enabled",
+						column.name
+					)));
+				}
+				let prefix = query
+					.get("highlightPrefix")
+					.and_then(GqlValueUtils::as_string)
+					.unwrap_or_else(|| "<b>".to_string());
+				let suffix = query
+					.get("highlightSuffix")
+					.and_then(GqlValueUtils::as_string)
+					.unwrap_or_else(|| "</b>".to_string());
+				out.fields.push(SelectField::Single(Selector {
+					expr: function_call(
+						"search::highlight",
+						vec![
+							Expr::Literal(Literal::String(prefix)),
+							Expr::Literal(Literal::String(suffix)),
+							rf_expr,
+						],
+					),
+					alias: Some(alias(&["_search", &column.name, "highlight"])),
+				}));
+			}
+		}
+
+		// search::score(1) + search::score(2) + .. AS _score
+		if let Some(score) = scores.into_iter().reduce(|left, right| Expr::Binary {
+			left: Box::new(left),
+			op: BinaryOperator::Add,
+			right: Box::new(right),
+		}) {
+			out.fields.push(SelectField::Single(Selector {
+				expr: score,
+				alias: Some(alias(&["_score"])),
+			}));
+			out.order = Some(order_by("_score", false));
+		}
+	}
+
+	if let Some(nearest) = args.get("nearest").and_then(GqlValueUtils::as_object) {
+		let mut queries = nearest.iter().filter(|(_, v)| !matches!(v, GqlValue::Null));
+		if let Some((name, query)) = queries.next() {
+			if queries.next().is_some() {
+				return Err(resolver_error("Only one field can be searched with `nearest`"));
+			}
+			if !indexes.hnsw.iter().any(|c| c == name.as_str()) {
+				return Err(resolver_error(format!("Field `{name}` has no HNSW index")));
+			}
+			let query = query
+				.as_object()
+				.ok_or_else(|| resolver_error("Nearest neighbour search must be an object"))?;
+			let vector = parse_vector(query)?;
+			let k = parse_u32(query, "k")?
+				.ok_or_else(|| resolver_error("Nearest neighbour search requires `k`"))?;
+			let ef = parse_u32(query, "ef")?.unwrap_or(DEFAULT_EF);
+
+			// <field> <|k,ef|> <vector>
+			let cond = Expr::Binary {
+				left: Box::new(Expr::Idiom(Idiom::field(name.to_string()))),
+				op: BinaryOperator::NearestNeighbor(Box::new(NearestNeighbor::Approximate(k, ef))),
+				right: Box::new(Expr::Literal(Literal::Array(vector))),
+			};
+			out.cond = Some(and_expr(out.cond.take(), cond));
+			out.fields.push(SelectField::Single(Selector {
+				expr: function_call("vector::distance::knn", Vec::new()),
+				alias: Some(alias(&["_distance"])),
+			}));
+			out.order = Some(order_by("_distance", true));
+		}
+	}
+
+	Ok(out)
+}
+
+fn parse_vector(query: &IndexMap<async_graphql::Name, GqlValue>) -> Result<Vec<Expr>, GqlError> {
+	let Some(items) = query.get("vector").and_then(GqlValueUtils::as_list) else {
+		return Err(resolver_error("Nearest neighbour search requires a `vector`"));
+	};
+	items
+		.iter()
+		.map(|v| match v {
+			GqlValue::Number(n) => n
+				.as_f64()
+				.map(|f| Expr::Literal(Literal::Float(f)))
+				.ok_or_else(|| resolver_error("Vector elements must be numbers")),
+			_ => Err(resolver_error("Vector elements must be numbers")),
+		})
+		.collect()
+}
+
+fn parse_u32(
+	query: &IndexMap<async_graphql::Name, GqlValue>,
+	name: &str,
+) -> Result<Option<u32>, GqlError> {
+	match query.get(name).and_then(GqlValueUtils::as_i64) {
+		Some(n) => u32::try_from(n)
+			.ok()
+			.filter(|n| *n > 0)
+			.map(Some)
+			.ok_or_else(|| resolver_error(format!("`{name}` must be a positive integer"))),
+		None => Ok(None),
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Schema
+// ---------------------------------------------------------------------------
+
+/// Add the `search` and `nearest` arguments to a table list query field.
+pub(crate) fn add_search_arguments(
+	mut field: Field,
+	tb_name: &str,
+	indexes: &SearchIndexes,
+) -> Field {
+	if indexes.has_fulltext() {
+		field =
+			field.argument(InputValue::new("search", TypeRef::named(search_input_name(tb_name))));
+	}
+	if indexes.has_hnsw() {
+		field =
+			field.argument(InputValue::new("nearest", TypeRef::named(nearest_input_name(tb_name))));
+	}
+	field
+}
+
+/// Add the `_score`, `_search` and `_distance` fields to a table type.
+pub(crate) fn add_search_fields(mut obj: Object, tb_name: &str, indexes: &SearchIndexes) -> Object {
+	if indexes.has_fulltext() {
+		obj = obj
+			.field(
+				Field::new(
+					"_score",
+					TypeRef::named(TypeRef::FLOAT),
+					make_cached_value_resolver("_score"),
+				)
+				.description("The full-text search score of this record"),
+			)
+			.field(
+				Field::new("_search", TypeRef::named(search_result_name(tb_name)), |ctx| {
+					FieldFuture::new(async move {
+						let Ok(cached) = ctx.parent_value.try_downcast_ref::<CachedRecord>() else {
+							return Ok(None);
+						};
+						resolve_nested_object_value(
+							cached.data.get("_search").cloned().unwrap_or(Value::None),
+							false,
+						)
+					})
+				})
+				.description("The full-text search matches of this record"),
+			);
+	}
+	if indexes.has_hnsw() {
+		obj = obj.field(
+			Field::new(
+				"_distance",
+				TypeRef::named(TypeRef::FLOAT),
+				make_cached_value_resolver("_distance"),
+			)
+			.description("The nearest neighbour search distance of this record"),
+		);
+	}
+	obj
+}
+
+/// Resolve a value computed by a search from the cached record data.
+fn make_cached_value_resolver(
+	name: &'static str,
+) -> impl for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static {
+	move |ctx: ResolverContext| {
+		FieldFuture::new(async move {
+			let Ok(cached) = ctx.parent_value.try_downcast_ref::<CachedRecord>() else {
+				return Ok(None);
+			};
+			let val = cached.data.get(name).cloned().unwrap_or(Value::None);
+			Ok(Some(FieldValue::value(sql_value_to_gql_value(val)?)))
+		})
+	}
+}
+
+/// Build the search input and result types of a table.
+pub(crate) fn make_search_types(tb_name: &str, indexes: &SearchIndexes) -> Vec<Type> {
+	let mut types = Vec::new();
+	if indexes.has_fulltext() {
+		let mut input = InputObject::new(search_input_name(tb_name))
+			.description(format!("Full-text search of the `{tb_name}` table"));
+		let mut result = Object::new(search_result_name(tb_name));
+		for column in indexes.fulltext.iter() {
+			input = input.field(InputValue::new(&column.name, TypeRef::named(FULLTEXT_QUERY)));
+			result = result.field(Field::new(
+				&column.name,
+				TypeRef::named(SEARCH_MATCH),
+				make_nested_match_resolver(column.name.clone()),
+			));
+		}
+		types.push(Type::InputObject(input));
+		types.push(Type::Object(result));
+	}
+	if indexes.has_hnsw() {
+		let mut input = InputObject::new(nearest_input_name(tb_name))
+			.description(format!("Nearest neighbour search of the `{tb_name}` table"));
+		for column in indexes.hnsw.iter() {
+			input = input.field(InputValue::new(column, TypeRef::named(NEAREST_QUERY)));
+		}
+		types.push(Type::InputObject(input));
+	}
+	types
+}
+
+fn make_nested_match_resolver(
+	name: String,
+) -> impl for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static {
+	move |ctx: ResolverContext| {
+		let name = name.clone();
+		FieldFuture::new(async move {
+			let obj = ctx.parent_value.try_downcast_ref::<SurObject>()?;
+			resolve_nested_object_value(obj.get(&name).cloned().unwrap_or(Value::None), false)
+		})
+	}
+}
+
+/// Build the full-text search types which are shared by all tables.
+pub(crate) fn make_fulltext_types() -> Vec<Type> {
+	let query = InputObject::new(FULLTEXT_QUERY)
+		.description("A full-text search of a field")
+		.field(InputValue::new("query", TypeRef::named_nn(TypeRef::STRING)))
+		.field(
+			InputValue::new("highlightPrefix", TypeRef::named(TypeRef::STRING))
+				.description("Inserted before highlighted terms, defaults to `<b>`"),
+		)
+		.field(
+			InputValue::new("highlightSuffix", TypeRef::named(TypeRef::STRING))
+				.description("Inserted after highlighted terms, defaults to `</b>`"),
+		);
+	let matched = Object::new(SEARCH_MATCH)
+		.description("The result of a full-text search of a field")
+		.field(Field::new(
+			"score",
+			TypeRef::named(TypeRef::FLOAT),
+			make_sub_field_resolver("score".to_string(), Some(Kind::Float), None),
+		))
+		.field(Field::new(
+			"highlight",
+			TypeRef::named(TypeRef::STRING),
+			make_sub_field_resolver("highlight".to_string(), Some(Kind::String), None),
+		));
+	vec![Type::InputObject(query), Type::Object(matched)]
+}
+
+/// Build the nearest neighbour search type which is shared by all tables.
+pub(crate) fn make_nearest_type() -> Type {
+	Type::InputObject(
+		InputObject::new(NEAREST_QUERY)
+			.description("A nearest neighbour search of a vector field")
+			.field(InputValue::new("vector", TypeRef::named_nn_list_nn(TypeRef::FLOAT)))
+			.field(InputValue::new("k", TypeRef::named_nn(TypeRef::INT)))
+			.field(
+				InputValue::new("ef", TypeRef::named(TypeRef::INT)).description(format!(
+					"The size of the candidate list, defaults to {DEFAULT_EF}"
+				)),
+			),
+	)
+}
@@ -7,8 +7,14 @@
 //!
 //! For each table (e.g. `person`), the following Query fields are created:
 //!
-//! - `person(limit, start, order, filter/where, version)` -- list query returning `[person!]!`
+//! - `person(limit, start, order, filter/where, search, nearest, version)` -- list query
+//!   returning `[person!]!`, where `search` and `nearest` are only present for tables with
+//!   full-text or HNSW indexes (see [`super::search`])
 //! - `_get_person(id, version)` -- single-record fetch returning `person`
+//! - `personConnection(first, after, last, before, order, filter/where, version)` -- cursor
+//!   paginated query returning `personConnection!` (see [`super::connections`])
+//! - `personAggregate(groupBy, filter/where, version)` -- grouped aggregates returning
+//!   `[personAggregate!]!` (see [`super::aggregates`])
 //!
 //! A generic `_get(id, version)` field is also added to fetch any record by
 //! its full ID string (e.g. `"person:alice"`).
@@ -50,12 +56,18 @@ use async_graphql::dynamic::{
 use async_graphql::{Name, Value as GqlValue};
 use surrealdb_types::ToSql;
 
+use super::aggregates::{make_aggregate_types, make_table_aggregate_field};
+use super::connections::{make_connection_types, make_page_info_type, make_table_connection_field};
 use super::error::{GqlError, resolver_error};
 use super::relations::{RelationDirection, RelationInfo};
 use super::schema::{
 	SchemaContext, gql_to_sql_kind, gql_to_sql_kind_with_scope, sql_value_to_gql_value,
 	sql_value_to_gql_value_with_kind,
 };
+use super::search::{
+	SearchIndexes, add_search_arguments, add_search_fields, make_fulltext_types, make_nearest_type,
+	make_search_types, parse_search_args,
+};
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{FieldDefinition, TableDefinition};
 use crate::dbs::Session;
@@ -135,7 +147,9 @@ fn version_to_expr(version: &Option<Datetime>) -> Expr {
 
 /// Parse the optional `version` argument from GraphQL query arguments.
 /// Expects an ISO 8601 / RFC 3339 datetime string (e.g. `"2024-06-01T00:00:00Z"`).
-fn parse_version_arg(args: &IndexMap<Name, GqlValue>) -> Result<Option<Datetime>, GqlError> {
+pub(super) fn parse_version_arg(
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Option<Datetime>, GqlError> {
 	match args.get("version") {
 		Some(GqlValue::String(s)) => {
 			let dt = crate::syn::datetime(s)
@@ -169,7 +183,9 @@ fn parse_limit_arg(args: &IndexMap<Name, GqlValue>) -> Option<Limit> {
 /// ```
 /// Each node has exactly one of `asc` or `desc` (an enum value naming the
 /// field) and an optional `then` link to the next ordering criterion.
-fn parse_order_arg(args: &IndexMap<Name, GqlValue>) -> Result<Option<Ordering>, GqlError> {
+pub(super) fn parse_order_arg(
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Option<Ordering>, GqlError> {
 	let order = args.get("order");
 	match order {
 		Some(GqlValue::Object(o)) => {
@@ -252,6 +268,7 @@ fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectS
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		cache: None,
 	}
 }
 
@@ -285,14 +302,16 @@ fn select_field_from_record(
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		cache: None,
 	}
 }
 
 /// Build a `SELECT * FROM <table>` statement with optional filtering,
 /// ordering, pagination, and versioning.
 ///
-/// Used by the table list query and relation field resolvers.
-fn select_all_from_table(
+/// Used by the table list, connection and aggregate queries and relation
+/// field resolvers.
+pub(super) fn select_all_from_table(
 	what: Expr,
 	cond: Option<Cond>,
 	order: Option<Ordering>,
@@ -317,11 +336,12 @@ fn select_all_from_table(
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		cache: None,
 	}
 }
 
 /// Execute a `SelectStatement` via `LogicalPlan` and return the result.
-async fn execute_select(
+pub(super) async fn execute_select(
 	ds: &Datastore,
 	sess: &Session,
 	stmt: SelectStatement,
@@ -504,7 +524,7 @@ fn make_nested_object_type(
 ///
 /// The resolver downcasts the parent value to `SurObject` and extracts the
 /// named field, converting it to the appropriate GraphQL value.
-fn make_sub_field_resolver(
+pub(super) fn make_sub_field_resolver(
 	field_name: String,
 	kind: Option<Kind>,
 	enum_scope: Option<String>,
@@ -602,7 +622,7 @@ fn make_nested_object_field_resolver(
 ///
 /// For arrays, each `Value::Object` element becomes a `FieldValue::owned_any(SurObject(..))`.
 /// For plain objects, the `SurObject` is returned directly.
-fn resolve_nested_object_value(
+pub(super) fn resolve_nested_object_value(
 	val: Value,
 	is_array: bool,
 ) -> Result<Option<FieldValue<'static>>, async_graphql::Error> {
@@ -694,56 +714,70 @@ fn objects_to_cached_records(
 fn make_table_list_field(
 	tb: &TableDefinition,
 	fds: Arc<[FieldDefinition]>,
+	search_indexes: Arc<SearchIndexes>,
 	kvs: Arc<Datastore>,
 ) -> Field {
 	let tb_name = tb.name.clone();
 	let tb_name_str = tb_name.clone().into_string();
 	let table_order_name = format!("_order_{tb_name}");
 	let table_filter_name = filter_name_from_table(&tb_name);
+	let field_search_indexes = search_indexes.clone();
+
+	let field =
+		Field::new(tb_name_str.clone(), TypeRef::named_nn_list_nn(&tb_name_str), move |ctx| {
+			let tb_name = tb_name.clone();
+			let fds = fds.clone();
+			let search_indexes = field_search_indexes.clone();
+			let kvs = kvs.clone();
+			FieldFuture::new(async move {
+				let sess = ctx.data::<Arc<Session>>()?;
+				let args = ctx.args.as_index_map();
+				trace!("received request with args: {args:?}");
+
+				let start = parse_start_arg(args);
+				let limit = parse_limit_arg(args);
+				let version = parse_version_arg(args)?;
+				let order = parse_order_arg(args)?;
+				let tb_name_str_ref = tb_name.as_str();
+				let cond = parse_filter_arg(args, &fds, tb_name_str_ref)?;
+				let search = parse_search_args(&ctx, &search_indexes)?;
+
+				trace!("parsed order: {order:?}");
+				trace!("parsed filter: {cond:?}");
+
+				let mut stmt = select_all_from_table(
+					Expr::Table(tb_name),
+					cond,
+					order,
+					limit,
+					start,
+					&version,
+				);
+				search.apply(&mut stmt);
+				let res = execute_select(&kvs, sess, stmt).await?;
 
-	Field::new(tb_name_str.clone(), TypeRef::named_nn_list_nn(&tb_name_str), move |ctx| {
-		let tb_name = tb_name.clone();
-		let fds = fds.clone();
-		let kvs = kvs.clone();
-		FieldFuture::new(async move {
-			let sess = ctx.data::<Arc<Session>>()?;
-			let args = ctx.args.as_index_map();
-			trace!("received request with args: {args:?}");
-
-			let start = parse_start_arg(args);
-			let limit = parse_limit_arg(args);
-			let version = parse_version_arg(args)?;
-			let order = parse_order_arg(args)?;
-			let tb_name_str_ref = tb_name.as_str();
-			let cond = parse_filter_arg(args, &fds, tb_name_str_ref)?;
-
-			trace!("parsed order: {order:?}");
-			trace!("parsed filter: {cond:?}");
-
-			let stmt =
-				select_all_from_table(Expr::Table(tb_name), cond, order, limit, start, &version);
-			let res = execute_select(&kvs, sess, stmt).await?;
-
-			match res {
-				Value::Array(a) => objects_to_cached_records(a, version),
-				v => {
-					error!("Found top level value, in result which should be array: {v:?}");
-					Err(internal_error("Unexpected result type from table query").into())
+				match res {
+					Value::Array(a) => objects_to_cached_records(a, version),
+					v => {
+						error!("Found top level value, in result which should be array: {v:?}");
+						Err(internal_error("Unexpected result type from table query").into())
+					}
 				}
-			}
+			})
 		})
-	})
-	.description(if let Some(c) = &tb.comment {
-		c.clone()
-	} else {
-		format!("Generated from table `{}`\nallows querying a table with filters", tb.name)
-	})
-	.argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)))
-	.argument(InputValue::new("start", TypeRef::named(TypeRef::INT)))
-	.argument(InputValue::new("order", TypeRef::named(&table_order_name)))
-	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
-	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
-	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+		.description(if let Some(c) = &tb.comment {
+			c.clone()
+		} else {
+			format!("Generated from table `{}`\nallows querying a table with filters", tb.name)
+		})
+		.argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)))
+		.argument(InputValue::new("start", TypeRef::named(TypeRef::INT)))
+		.argument(InputValue::new("order", TypeRef::named(&table_order_name)))
+		.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+		.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+		.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)));
+
+	add_search_arguments(field, &tb_name_str, &search_indexes)
 }
 
 /// Build the `_get_<table>` query field for fetching a single record by ID.
@@ -1106,14 +1140,29 @@ pub async fn process_tbs(
 	let exposed_table_names: HashSet<String> =
 		tbs.iter().map(|t| t.name.clone().into_string()).collect();
 
+	// The search types shared by all tables are only registered when used
+	let mut has_fulltext = false;
+	let mut has_hnsw = false;
+
 	for tb in tbs.iter() {
 		trace!("Adding table: {}", tb.name);
 		let fds = ctx.tx.all_tb_fields(ctx.ns, ctx.db, &tb.name, None).await?;
 		table_fields.insert(tb.name.clone().into_string(), fds.clone());
+		let ixs = ctx.tx.all_tb_indexes(ctx.ns, ctx.db, &tb.name).await?;
+		let search_indexes = Arc::new(SearchIndexes::from_indexes(&ixs));
+		has_fulltext |= search_indexes.has_fulltext();
+		has_hnsw |= search_indexes.has_hnsw();
 
 		// Add query root fields for this table
-		query = query.field(make_table_list_field(tb, fds.clone(), ctx.datastore.clone()));
+		query = query.field(make_table_list_field(
+			tb,
+			fds.clone(),
+			search_indexes.clone(),
+			ctx.datastore.clone(),
+		));
 		query = query.field(make_table_get_field(tb, ctx.datastore.clone()));
+		query = query.field(make_table_connection_field(tb, fds.clone(), ctx.datastore.clone()));
+		query = query.field(make_table_aggregate_field(tb, fds.clone(), ctx.datastore.clone()));
 
 		// Build and register the table's type system
 		let tt = build_table_type(
@@ -1124,10 +1173,31 @@ pub async fn process_tbs(
 			&relation_table_fds,
 			types,
 		)?;
-		types.push(Type::Object(tt.ty_obj));
+		types.push(Type::Object(add_search_fields(tt.ty_obj, tb.name.as_str(), &search_indexes)));
+		types.extend(make_search_types(tb.name.as_str(), &search_indexes));
 		types.push(tt.order.into());
 		types.push(Type::Enum(tt.orderable));
 		types.push(Type::InputObject(tt.filter));
+
+		// Register the connection and aggregate types
+		let (connection, edge) = make_connection_types(tb.name.as_str());
+		types.push(Type::Object(connection));
+		types.push(Type::Object(edge));
+		for ty in make_aggregate_types(tb.name.as_str(), &fds, types)? {
+			types.push(Type::Object(ty));
+		}
+	}
+
+	if has_fulltext {
+		types.extend(make_fulltext_types());
+	}
+	if has_hnsw {
+		types.push(make_nearest_type());
+	}
+
+	// The `PageInfo` type is shared by all connections
+	if !tbs.is_empty() {
+		types.push(Type::Object(make_page_info_type()));
 	}
 
 	// Add generic _get query field for fetching any record by full ID
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use super::Level;
 use crate::catalog::base::Base;
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 9)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ResourceKind {
@@ -33,6 +33,14 @@ pub enum ResourceKind {
 	Bucket,
 	#[revision(start = 5)]
 	Sequence,
+	#[revision(start = 6)]
+	Job,
+	#[revision(start = 7)]
+	Sink,
+	#[revision(start = 8)]
+	Consumer,
+	#[revision(start = 9)]
+	Key,
 	// IAM
 	Actor,
 }
@@ -80,6 +88,10 @@ impl std::fmt::Display for ResourceKind {
 			ResourceKind::Config(c) => write!(f, "Config::{c}"),
 			ResourceKind::Bucket => write!(f, "Bucket"),
 			ResourceKind::Sequence => write!(f, "Sequence"),
+			ResourceKind::Job => write!(f, "Job"),
+			ResourceKind::Sink => write!(f, "Sink"),
+			ResourceKind::Consumer => write!(f, "Consumer"),
+			ResourceKind::Key => write!(f, "Key"),
 		}
 	}
 }
@@ -32,6 +32,7 @@ use crate::iam::{self, Auth, algorithm_to_jwt_algorithm};
 use crate::kvs::Datastore;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::audit::AuditKind;
 use crate::types::{PublicValue, PublicVariables};
 use crate::val::{Datetime, Value};
 
@@ -93,6 +94,19 @@ pub async fn signin(
 	kvs: &Datastore,
 	session: &mut Session,
 	vars: PublicVariables,
+) -> Result<Token> {
+	// Keep the signin target for the audit log, as the variables are consumed
+	let target = kvs.audit_log().map(|_| vars.clone());
+	let res = try_signin(kvs, session, vars).await;
+	kvs.audit_auth(AuditKind::Signin, session, target.as_ref(), &res).await;
+	res
+}
+
+/// Signs in to the target specified by the variables.
+async fn try_signin(
+	kvs: &Datastore,
+	session: &mut Session,
+	vars: PublicVariables,
 ) -> Result<Token> {
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns")).cloned();
@@ -18,6 +18,7 @@ use crate::iam::{Actor, Auth, Level, Role, algorithm_to_jwt_algorithm};
 use crate::kvs::Datastore;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::audit::AuditKind;
 use crate::types::PublicVariables;
 use crate::val::Value;
 
@@ -80,6 +81,19 @@ pub async fn signup(
 	kvs: &Datastore,
 	session: &mut Session,
 	vars: PublicVariables,
+) -> Result<Token> {
+	// Keep the signup target for the audit log, as the variables are consumed
+	let target = kvs.audit_log().map(|_| vars.clone());
+	let res = try_signup(kvs, session, vars).await;
+	kvs.audit_auth(AuditKind::Signup, session, target.as_ref(), &res).await;
+	res
+}
+
+/// Signs up to the target specified by the variables.
+async fn try_signup(
+	kvs: &Datastore,
+	session: &mut Session,
+	vars: PublicVariables,
 ) -> Result<Token> {
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns")).cloned();
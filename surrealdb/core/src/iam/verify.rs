@@ -21,6 +21,7 @@ use crate::iam::{self, Actor, Auth, Level, Role};
 use crate::kvs::Datastore;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::audit::AuditKind;
 use crate::{catalog, syn};
 
 /// Returns the decoding key as wel as the method by which to verify the key against
@@ -153,6 +154,13 @@ pub async fn basic(
 }
 
 pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<()> {
+	let res = try_token(kvs, session, token).await;
+	kvs.audit_auth(AuditKind::Authenticate, session, None, &res).await;
+	res
+}
+
+/// Authenticates the session with the token.
+async fn try_token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<()> {
 	// Log the authentication type
 	trace!("Attempting token authentication");
 	// Decode the token without verifying to extract routing claims
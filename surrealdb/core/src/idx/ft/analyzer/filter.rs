@@ -1,19 +1,37 @@
+use std::sync::Arc;
+
+use ahash::HashSet;
 use anyhow::Result;
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
 
-use crate::expr::filter::Filter as SqlFilter;
+use crate::expr::filter::{
+	Filter as SqlFilter, FilterStage as SqlFilterStage, Stopwords as SqlStopwords,
+	Synonyms as SqlSynonyms,
+};
 use crate::expr::language::Language;
 use crate::idx::ft::Position;
 use crate::idx::ft::analyzer::mapper::Mapper;
+use crate::idx::ft::analyzer::stopwords;
+use crate::idx::ft::analyzer::synonyms::Synonyms;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::trees::store::IndexStores;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(in crate::idx::ft) enum FilteringStage {
 	Indexing,
 	Querying,
 }
+
+impl From<SqlFilterStage> for FilteringStage {
+	fn from(v: SqlFilterStage) -> Self {
+		match v {
+			SqlFilterStage::Indexing => Self::Indexing,
+			SqlFilterStage::Querying => Self::Querying,
+		}
+	}
+}
+
 pub(super) enum Filter {
 	Stemmer(Stemmer),
 	Ascii,
@@ -22,6 +40,8 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	Stopwords(Arc<HashSet<String>>),
+	Synonyms(Synonyms, Option<FilteringStage>),
 }
 
 impl Filter {
@@ -56,6 +76,16 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::Stopwords(SqlStopwords::Language(l)) => {
+				let words = stopwords::builtin(*l)?;
+				Filter::Stopwords(Arc::new(words.iter().map(|w| (*w).to_string()).collect()))
+			}
+			SqlFilter::Stopwords(SqlStopwords::Custom(words)) => {
+				Filter::Stopwords(Arc::new(words.iter().cloned().collect()))
+			}
+			SqlFilter::Synonyms(SqlSynonyms::Rules(rules) | SqlSynonyms::File(_, rules), stage) => {
+				Filter::Synonyms(Synonyms::new(rules)?, stage.map(Into::into))
+			}
 		};
 		Ok(f)
 	}
@@ -76,10 +106,10 @@ impl Filter {
 	}
 
 	fn is_stage(&self, stage: FilteringStage) -> bool {
-		if let FilteringStage::Querying = stage {
-			!matches!(self, Filter::EdgeNgram(_, _) | Filter::Ngram(_, _))
-		} else {
-			true
+		match self {
+			Filter::EdgeNgram(_, _) | Filter::Ngram(_, _) => stage == FilteringStage::Indexing,
+			Filter::Synonyms(_, Some(s)) => *s == stage,
+			_ => true,
 		}
 	}
 
@@ -107,6 +137,8 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
+			Filter::Stopwords(w) => Self::stopwords(w, c),
+			Filter::Synonyms(s, _) => s.expand(c),
 		}
 	}
 
@@ -141,6 +173,15 @@ impl Filter {
 		Self::check_term(c, s.stem(&c.to_lowercase()).into())
 	}
 
+	#[inline]
+	fn stopwords(w: &HashSet<String>, c: &str) -> FilterResult {
+		if w.contains(c) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
 	#[inline]
 	fn ngram(c: &str, min: u16, max: u16) -> FilterResult {
 		let min = min as usize;
@@ -901,4 +942,43 @@ mod tests {
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_stopwords() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,stopwords(english)",
+			"The quick fox is in the garden",
+			&["quick", "fox", "garden"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,stopwords(['quick', 'fox'])",
+			"The quick fox",
+			&["the"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_synonyms() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,synonyms(['tv, television', 'couch => sofa, settee'])",
+			"TV on the couch",
+			&["tv", "television", "on", "the", "sofa", "settee"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,synonyms(['tv, television'], indexing)",
+			"TV",
+			&["tv", "television"],
+		)
+		.await;
+		// Query-time synonyms are not applied when indexing
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,synonyms(['tv, television'], querying)",
+			"TV",
+			&["tv"],
+		)
+		.await;
+	}
 }
@@ -21,6 +21,8 @@ use crate::val::Value;
 
 pub(in crate::idx::ft) mod filter;
 pub(in crate::idx) mod mapper;
+pub(crate) mod stopwords;
+pub(crate) mod synonyms;
 pub(in crate::idx::ft) mod tokenizer;
 
 #[derive(Clone)]
@@ -37,6 +39,11 @@ impl Analyzer {
 		})
 	}
 
+	/// Whether the query strings of full-text searches support operators.
+	pub(in crate::idx::ft) fn operators(&self) -> bool {
+		self.az.operators
+	}
+
 	pub(in crate::idx::ft) async fn analyze_content(
 		&self,
 		stk: &mut Stk,
@@ -158,10 +165,17 @@ impl Analyzer {
 		let mut dl = 0;
 		let mut tfos: HashMap<&str, Vec<Offset>> = HashMap::new();
 		for (i, tks) in inputs.iter().enumerate() {
+			// Terms produced from the same part of the value share the same position
+			let mut pos = 0;
+			let mut source = None;
 			for tk in tks.list() {
 				dl += 1;
+				if source.is_some_and(|s| s != tk.source()) {
+					pos += 1;
+				}
+				source = Some(tk.source());
 				let s = tks.get_token_string(tk)?;
-				let o = tk.new_offset(i as u32);
+				let o = tk.new_offset(i as u32, pos);
 				tfos.entry(s).or_default().push(o);
 			}
 		}
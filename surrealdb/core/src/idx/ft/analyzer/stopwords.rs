@@ -0,0 +1,36 @@
+use anyhow::{Result, bail};
+
+use crate::err::Error;
+use crate::expr::language::Language;
+
+/// Returns the built-in list of stop words of a language.
+///
+/// The lists are lowercase, so the `STOPWORDS` filter should follow a
+/// `LOWERCASE` filter.
+pub(crate) fn builtin(language: Language) -> Result<&'static [&'static str]> {
+	let code = match language {
+		Language::Arabic => "ar",
+		Language::Danish => "da",
+		Language::Dutch => "nl",
+		Language::English => "en",
+		Language::Finnish => "fi",
+		Language::French => "fr",
+		Language::German => "de",
+		Language::Greek => "el",
+		Language::Hungarian => "hu",
+		Language::Italian => "it",
+		Language::Norwegian => "no",
+		Language::Portuguese => "pt",
+		Language::Romanian => "ro",
+		Language::Russian => "ru",
+		Language::Spanish => "es",
+		Language::Swedish => "sv",
+		Language::Turkish => "tr",
+		Language::Tamil => {
+			bail!(Error::AnalyzerError(format!(
+				"There is no built-in list of stop words for {language}"
+			)))
+		}
+	};
+	Ok(stop_words::get(code))
+}
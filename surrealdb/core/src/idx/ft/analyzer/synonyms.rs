@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use ahash::HashMap;
+use anyhow::{Result, ensure};
+
+use crate::err::Error;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+#[derive(Clone, Default)]
+pub(crate) struct Synonyms {
+	terms: Arc<HashMap<String, Vec<String>>>,
+}
+
+impl Synonyms {
+	/// Parses a list of synonym rules.
+	///
+	/// A rule is either a comma-separated list of equivalent terms
+	/// (`tv, television`), where each term is expanded to every term of the
+	/// list, or an explicit mapping (`couch => sofa, settee`), where the terms
+	/// on the left are replaced by the terms on the right. Empty rules, and
+	/// rules starting with `#`, are ignored.
+	pub(crate) fn new(rules: &[String]) -> Result<Self> {
+		let mut terms: HashMap<String, Vec<String>> = HashMap::default();
+		for (i, rule) in rules.iter().enumerate() {
+			let rule = rule.trim();
+			if rule.is_empty() || rule.starts_with('#') {
+				continue;
+			}
+			let (from, to) = match rule.split_once("=>") {
+				Some((from, to)) => (Self::parse_terms(from, i)?, Self::parse_terms(to, i)?),
+				None => {
+					let terms = Self::parse_terms(rule, i)?;
+					(terms.clone(), terms)
+				}
+			};
+			for term in from {
+				let expansion = terms.entry(term).or_default();
+				for t in &to {
+					if !expansion.contains(t) {
+						expansion.push(t.clone());
+					}
+				}
+			}
+		}
+		Ok(Self {
+			terms: Arc::new(terms),
+		})
+	}
+
+	fn parse_terms(list: &str, rule: usize) -> Result<Vec<String>> {
+		let terms: Vec<String> = list.split(',').map(|t| t.trim().to_string()).collect();
+		for t in &terms {
+			ensure!(
+				!t.is_empty() && !t.contains(char::is_whitespace),
+				Error::AnalyzerError(format!(
+					"Expected single terms separated by commas in synonym rule {}: {list}",
+					rule + 1
+				))
+			);
+		}
+		Ok(terms)
+	}
+
+	pub(super) fn expand(&self, token: &str) -> FilterResult {
+		let Some(expansion) = self.terms.get(token) else {
+			return FilterResult::Term(Term::Unchanged);
+		};
+		let terms = expansion
+			.iter()
+			.map(|t| {
+				if t == token {
+					Term::Unchanged
+				} else {
+					Term::NewTerm(t.clone(), 0)
+				}
+			})
+			.collect();
+		FilterResult::Terms(terms)
+	}
+}
@@ -64,15 +64,6 @@ impl Tokens {
 	pub(in crate::idx::ft) fn list(&self) -> &Vec<Token> {
 		&self.t
 	}
-
-	pub(in crate::idx::ft) fn try_contains(&self, s: &str) -> Result<bool> {
-		for t in &self.t {
-			if self.get_token_string(t)?.eq(s) {
-				return Ok(true);
-			}
-		}
-		Ok(false)
-	}
 }
 
 impl TryFrom<Tokens> for Value {
@@ -129,16 +120,30 @@ impl Token {
 		}
 	}
 
-	pub(in crate::idx::ft) fn new_offset(&self, i: u32) -> Offset {
+	/// The position of the bytes in the input string which produced the token.
+	pub(in crate::idx::ft) fn source(&self) -> (Position, Position) {
+		match self {
+			Token::Ref {
+				bytes,
+				..
+			}
+			| Token::String {
+				bytes,
+				..
+			} => *bytes,
+		}
+	}
+
+	pub(in crate::idx::ft) fn new_offset(&self, i: u32, pos: Position) -> Offset {
 		match self {
 			Token::Ref {
 				chars,
 				..
-			} => Offset::new(i, chars.0, chars.1, chars.2),
+			} => Offset::new(i, chars.0, chars.1, chars.2, pos),
 			Token::String {
 				chars,
 				..
-			} => Offset::new(i, chars.0, chars.1, chars.2),
+			} => Offset::new(i, chars.0, chars.1, chars.2, pos),
 		}
 	}
 
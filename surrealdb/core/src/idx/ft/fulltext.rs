@@ -1,8 +1,8 @@
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use roaring::RoaringTreemap;
@@ -22,8 +22,10 @@ use crate::catalog::{FullTextParams, Scoring};
 /// - Efficient term frequency tracking
 /// - Document length normalization
 /// - Compaction of index data
+use crate::cnf::{FULLTEXT_FUZZY_PREFIX_LENGTH, FULLTEXT_MAX_EXPANSION_CANDIDATES};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
+use crate::err::Error;
 use crate::expr::Idiom;
 use crate::expr::operator::BooleanOperator;
 use crate::idx::IndexKeyBase;
@@ -32,13 +34,21 @@ use crate::idx::ft::analyzer::filter::FilteringStage;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::ft::highlighter::{HighlightParams, Highlighter, Offseter};
 use crate::idx::ft::offset::Offset;
+use crate::idx::ft::query::{Expansion, FullTextQuery, ParsedQuery, fuzzy_distance};
 use crate::idx::ft::{DocLength, Score, TermFrequency};
 use crate::idx::planner::iterators::MatchesHitsIterator;
 use crate::idx::seqdocids::{DocId, SeqDocIds};
 use crate::idx::trees::store::IndexStores;
+use crate::key::index::td::Td;
 use crate::key::index::tt::Tt;
 use crate::kvs::{Transaction, impl_kv_value_revisioned};
 use crate::val::{RecordId, Value};
+/// The maximum number of terms of the index a prefix or fuzzy term of a query
+/// is expanded to
+const MAX_EXPANSIONS: usize = 50;
+/// The number of keys read at once while walking the terms of the index
+const EXPANSION_BATCH_SIZE: u32 = 100;
+
 #[revisioned(revision = 1)]
 #[derive(Debug, Default, PartialEq)]
 /// Represents a term occurrence within a document
@@ -84,20 +94,22 @@ impl DocLengthAndCount {
 
 /// Represents the terms in a search query and their associated document sets
 pub(crate) struct QueryTerms {
-	/// The tokenized query terms
-	#[allow(dead_code)]
-	tokens: Tokens,
+	/// The tokenized query terms, for each part of the query string which is
+	/// analyzed separately
+	tokens: Vec<Tokens>,
 	/// Document sets for each term (RoaringTreemap of document IDs)
 	#[allow(dead_code)]
 	docs: Vec<Option<RoaringTreemap>>,
 	/// Indicates if any terms in the query are not found in the index
 	#[allow(dead_code)]
 	has_unknown_terms: bool,
+	/// The terms and the clauses of the query
+	query: Arc<FullTextQuery>,
 }
 
 impl QueryTerms {
 	pub(crate) fn is_empty(&self) -> bool {
-		self.tokens.list().is_empty()
+		self.tokens.iter().all(|t| t.list().is_empty())
 	}
 
 	pub(crate) fn contains_doc(&self, doc_id: DocId) -> bool {
@@ -108,35 +120,6 @@ impl QueryTerms {
 		}
 		false
 	}
-
-	pub(in crate::idx::ft) fn matches_or(&self, tks: &[Tokens]) -> Result<bool> {
-		for t in self.tokens.list() {
-			let t = self.tokens.get_token_string(t)?;
-			for tokens in tks {
-				if tokens.try_contains(t)? {
-					return Ok(true);
-				}
-			}
-		}
-		Ok(false)
-	}
-
-	pub(in crate::idx::ft) fn matches_and(&self, tks: &[Tokens]) -> Result<bool> {
-		for t in self.tokens.list() {
-			let t = self.tokens.get_token_string(t)?;
-			let mut found = false;
-			for tokens in tks {
-				if tokens.try_contains(t)? {
-					found = true;
-					break;
-				}
-			}
-			if !found {
-				return Ok(false);
-			}
-		}
-		Ok(true)
-	}
 }
 
 #[derive(Clone)]
@@ -373,9 +356,11 @@ impl FullTextIndex {
 
 	/// Extracts query terms from a search string
 	///
-	/// Tokenizes the query string, then retrieves the document bitmaps for each
-	/// unique term. The compacted bitmap fetches are batched via `tx.getm()` to
-	/// reduce KV round trips (one batch instead of N sequential gets).
+	/// Parses the query string, when the analyzer supports query operators,
+	/// and tokenizes each of its terms and phrases, expands the prefix and
+	/// fuzzy terms against the terms of the index, then retrieves the document bitmaps for each unique term. The compacted
+	/// bitmap fetches are batched via `tx.getm()` to reduce KV round trips (one
+	/// batch instead of N sequential gets).
 	pub(crate) async fn extract_querying_terms(
 		&self,
 		stk: &mut Stk,
@@ -383,24 +368,36 @@ impl FullTextIndex {
 		opt: &Options,
 		query_string: String,
 	) -> Result<QueryTerms> {
-		let tokens = self
-			.analyzer
-			.generate_tokens(stk, ctx, opt, FilteringStage::Querying, query_string)
-			.await?;
-
-		let mut unique_terms: Vec<&str> = Vec::new();
-		let mut unique_tokens = HashSet::new();
-		for token in tokens.list() {
-			if unique_tokens.insert(token) {
-				unique_terms.push(tokens.get_token_string(token)?);
-			}
+		let parsed = if self.analyzer.operators() {
+			ParsedQuery::parse(&query_string)?
+		} else {
+			ParsedQuery::plain(&query_string)
+		};
+		let mut tokens = Vec::with_capacity(parsed.operands.len());
+		for operand in &parsed.operands {
+			tokens.push(
+				self.analyzer
+					.generate_tokens(stk, ctx, opt, FilteringStage::Querying, operand.text.clone())
+					.await?,
+			);
 		}
-
+		let mut query = FullTextQuery::new(parsed, &tokens)?;
+		ensure!(
+			self.highlighting || !query.is_positional(),
+			Error::FullTextQueryError(
+				"Phrase and proximity queries require an index defined with HIGHLIGHTS".to_string()
+			)
+		);
 		let tx = ctx.tx();
+		for (group, term, expansion) in query.expansions() {
+			let terms = self.expand_term(&tx, &term, expansion).await?;
+			query.expand(group, terms);
+		}
+		let unique_terms = query.terms();
 
 		// Phase 1: Collect deltas for each term (sequential range scans)
 		let mut all_deltas: Vec<HashMap<DocId, i64>> = Vec::with_capacity(unique_terms.len());
-		for term in &unique_terms {
+		for term in unique_terms {
 			let (beg, end) = self.ikb.new_tt_term_range(term)?;
 			let mut deltas: HashMap<DocId, i64> = HashMap::new();
 			for k in tx.keys(beg..end, u32::MAX, 0, None).await? {
@@ -450,9 +447,113 @@ impl FullTextIndex {
 			tokens,
 			docs,
 			has_unknown_terms,
+			query: Arc::new(query),
 		})
 	}
 
+	/// Returns the terms of the index a query term expands to, in
+	/// lexicographic order, excluding the term itself.
+	///
+	/// A prefix term walks the terms of the index starting with the term. A
+	/// fuzzy term walks the terms starting with the same first
+	/// `FULLTEXT_FUZZY_PREFIX_LENGTH` characters, and keeps those within the
+	/// edit distance. The walk reads the keys in batches, and seeks from the
+	/// last term of a batch to the next one, skipping the remaining documents
+	/// of that term. The query fails when the walk finds more than
+	/// `FULLTEXT_MAX_EXPANSION_CANDIDATES` terms, rather than matching an
+	/// arbitrary subset of them.
+	///
+	/// When more than `MAX_EXPANSIONS` terms match, the closest ones are kept,
+	/// the distance being the edit distance of a fuzzy term, or the number of
+	/// additional characters of a prefix term. Terms at the same distance are
+	/// ranked by the number of documents in their compacted bitmap.
+	async fn expand_term(
+		&self,
+		tx: &Transaction,
+		term: &str,
+		expansion: Expansion,
+	) -> Result<Vec<String>> {
+		let (prefix, fuzzy) = match expansion {
+			Expansion::Prefix => (term, None),
+			Expansion::Fuzzy(distance) => {
+				let distance = fuzzy_distance(distance, term) as usize;
+				if distance == 0 {
+					return Ok(Vec::new());
+				}
+				let prefix = term
+					.char_indices()
+					.nth(*FULLTEXT_FUZZY_PREFIX_LENGTH)
+					.map(|(i, _)| i)
+					.unwrap_or(term.len());
+				(&term[..prefix], Some((distance, term.chars().count())))
+			}
+		};
+		let (mut beg, end) = self.ikb.new_td_terms_range(prefix)?;
+		let len = term.chars().count();
+		let mut terms: Vec<(usize, String)> = Vec::new();
+		let mut candidates = 0;
+		loop {
+			let keys = tx.keys(beg..end.clone(), EXPANSION_BATCH_SIZE, 0, None).await?;
+			let complete = keys.len() < EXPANSION_BATCH_SIZE as usize;
+			let mut last: Option<String> = None;
+			for key in &keys {
+				let candidate = Td::decode_term(key)?;
+				// The keys of a term are contiguous, one per document
+				if last.as_ref() == Some(&candidate) {
+					continue;
+				}
+				if candidate != term {
+					let distance = match fuzzy {
+						Some((distance, _))
+							if candidate.chars().count().abs_diff(len) > distance =>
+						{
+							None
+						}
+						Some((distance, _)) => {
+							Some(strsim::levenshtein(term, &candidate)).filter(|d| *d <= distance)
+						}
+						None => Some(candidate.chars().count() - len),
+					};
+					ensure!(
+						candidates < *FULLTEXT_MAX_EXPANSION_CANDIDATES,
+						Error::FullTextQueryError(format!(
+							"The prefix or fuzzy term {term} has more than {} candidate terms in the index",
+							*FULLTEXT_MAX_EXPANSION_CANDIDATES
+						))
+					);
+					candidates += 1;
+					if let Some(distance) = distance {
+						terms.push((distance, candidate.clone()));
+					}
+				}
+				last = Some(candidate);
+			}
+			match last {
+				Some(last) if !complete => beg = self.ikb.new_td_next_term(&last)?,
+				_ => break,
+			}
+		}
+		if terms.len() > MAX_EXPANSIONS {
+			let keys: Vec<_> = terms.iter().map(|(_, term)| self.ikb.new_td_root(term)).collect();
+			let bitmaps: Vec<Option<RoaringTreemap>> = tx.getm(keys, None).await?;
+			let mut ranked: Vec<_> = terms
+				.into_iter()
+				.zip(bitmaps)
+				.map(|((distance, term), docs)| {
+					(distance, Reverse(docs.map(|docs| docs.len()).unwrap_or(0)), term)
+				})
+				.collect();
+			ranked.sort_unstable();
+			terms = ranked
+				.into_iter()
+				.take(MAX_EXPANSIONS)
+				.map(|(distance, _, term)| (distance, term))
+				.collect();
+			terms.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+		}
+		Ok(terms.into_iter().map(|(_, term)| term).collect())
+	}
+
 	pub(in crate::idx) async fn matches_value(
 		&self,
 		stk: &mut Stk,
@@ -464,10 +565,52 @@ impl FullTextIndex {
 	) -> Result<bool> {
 		let mut tks = vec![];
 		self.analyzer.analyze_value(stk, ctx, opt, val, FilteringStage::Indexing, &mut tks).await?;
-		match bo {
-			BooleanOperator::And => qt.matches_and(&tks),
-			BooleanOperator::Or => qt.matches_or(&tks),
+		let (_, offsets) = Analyzer::extract_offsets(&tks)?;
+		let doc: Vec<Option<Vec<Offset>>> =
+			qt.query.terms().iter().map(|t| offsets.get(t.as_str()).cloned()).collect();
+		Ok(qt.query.matches(bo, &doc))
+	}
+
+	/// Checks whether an indexed document matches the query terms
+	pub(crate) async fn matches_doc(
+		&self,
+		tx: &Transaction,
+		qt: &QueryTerms,
+		bo: BooleanOperator,
+		doc_id: DocId,
+	) -> Result<bool> {
+		if qt.query.is_plain() {
+			return Ok(qt.contains_doc(doc_id));
 		}
+		let doc = if qt.query.is_positional() {
+			Self::read_doc_terms(&self.ikb, tx, &qt.query, doc_id).await?
+		} else {
+			qt.docs
+				.iter()
+				.map(|d| d.as_ref().filter(|d| d.contains(doc_id)).map(|_| Vec::new()))
+				.collect()
+		};
+		Ok(qt.query.matches(bo, &doc))
+	}
+
+	/// Reads the offsets of each term of the query in a document.
+	/// The offsets of a term which is not in the document are `None`.
+	async fn read_doc_terms(
+		ikb: &IndexKeyBase,
+		tx: &Transaction,
+		query: &FullTextQuery,
+		doc_id: DocId,
+	) -> Result<Vec<Option<Vec<Offset>>>> {
+		let keys: Vec<_> = query.terms().iter().map(|t| ikb.new_td(t, doc_id)).collect();
+		let tds: Vec<Option<TermDocument>> = tx.getm(keys, None).await?;
+		// Offsets indexed before positions were recorded can't be matched
+		ensure!(
+			tds.iter().flatten().flat_map(|td| td.o.iter()).all(Offset::has_pos),
+			Error::FullTextIndexOutdated {
+				table: ikb.table().to_string(),
+			}
+		);
+		Ok(tds.into_iter().map(|td| td.map(|td| td.o)).collect())
 	}
 
 	async fn append_term_docs_delta(
@@ -576,24 +719,25 @@ impl FullTextIndex {
 		qt: &QueryTerms,
 		bo: BooleanOperator,
 	) -> Option<FullTextHitsIterator> {
-		// Execute the operation depending on the operator
-		let hits = match bo {
-			BooleanOperator::And => Self::intersection_operation(&qt.docs),
-			BooleanOperator::Or => Self::union_operation(&qt.docs),
-		};
+		// Execute the operations depending on the clauses and the operator
+		let hits = qt.query.hits(&qt.docs, bo);
 
 		// Create and return an iterator if we have matching documents
 		if let Some(hits) = hits
 			&& !hits.is_empty()
 		{
-			return Some(FullTextHitsIterator::new(self.ikb.clone(), hits));
+			// Phrases and proximity are checked on each document
+			let positional = qt.query.is_positional().then(|| (qt.query.clone(), bo));
+			return Some(FullTextHitsIterator::new(self.ikb.clone(), hits, positional));
 		}
 
 		// No documents match the terms
 		None
 	}
 
-	fn intersection_operation(docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+	pub(in crate::idx::ft) fn intersection_operation(
+		docs: &[Option<RoaringTreemap>],
+	) -> Option<RoaringTreemap> {
 		// Early return for empty input
 		if docs.is_empty() {
 			return None;
@@ -634,7 +778,9 @@ impl FullTextIndex {
 		}
 	}
 
-	fn union_operation(docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+	pub(in crate::idx::ft) fn union_operation(
+		docs: &[Option<RoaringTreemap>],
+	) -> Option<RoaringTreemap> {
 		// Convert docs to an iterator
 		let mut docs = docs.iter().flatten();
 
@@ -757,11 +903,18 @@ impl FullTextIndex {
 		let doc_id = self.get_doc_id(tx, thg).await?;
 		if let Some(doc_id) = doc_id {
 			let mut hl = Highlighter::new(hlp, idiom, doc);
-			for tk in qt.tokens.list() {
-				if let Some(td) =
-					self.get_term_document(tx, doc_id, qt.tokens.get_token_string(tk)?).await?
-				{
-					hl.highlight(tk.get_char_len(), td.o);
+			for tks in &qt.tokens {
+				for tk in tks.list() {
+					if let Some(td) =
+						self.get_term_document(tx, doc_id, tks.get_token_string(tk)?).await?
+					{
+						hl.highlight(tk.get_char_len(), td.o);
+					}
+				}
+			}
+			for term in qt.query.expanded_terms() {
+				if let Some(td) = self.get_term_document(tx, doc_id, term).await? {
+					hl.highlight(term.chars().count() as u32, td.o);
 				}
 			}
 			return hl.try_into();
@@ -789,11 +942,19 @@ impl FullTextIndex {
 		let doc_id = self.get_doc_id(tx, thg).await?;
 		if let Some(doc_id) = doc_id {
 			let mut or = Offseter::new(partial);
-			for tk in qt.tokens.list() {
-				let term = qt.tokens.get_token_string(tk)?;
+			for tks in &qt.tokens {
+				for tk in tks.list() {
+					let term = tks.get_token_string(tk)?;
+					let o = self.get_term_document(tx, doc_id, term).await?;
+					if let Some(o) = o {
+						or.highlight(tk.get_char_len(), o.o);
+					}
+				}
+			}
+			for term in qt.query.expanded_terms() {
 				let o = self.get_term_document(tx, doc_id, term).await?;
 				if let Some(o) = o {
-					or.highlight(tk.get_char_len(), o.o);
+					or.highlight(term.chars().count() as u32, o.o);
 				}
 			}
 			return Ok(or.into());
@@ -809,6 +970,9 @@ pub(crate) struct FullTextHitsIterator {
 	ikb: IndexKeyBase,
 	/// Iterator over the document IDs in the search results
 	iter: IntoIter,
+	/// The query whose phrases and proximity clauses are checked on each
+	/// document
+	positional: Option<(Arc<FullTextQuery>, BooleanOperator)>,
 }
 
 impl FullTextHitsIterator {
@@ -816,10 +980,15 @@ impl FullTextHitsIterator {
 	///
 	/// This method initializes an iterator with the index key base and a bitmap
 	/// of matching document IDs.
-	fn new(ikb: IndexKeyBase, hits: RoaringTreemap) -> Self {
+	fn new(
+		ikb: IndexKeyBase,
+		hits: RoaringTreemap,
+		positional: Option<(Arc<FullTextQuery>, BooleanOperator)>,
+	) -> Self {
 		Self {
 			ikb,
 			iter: hits.into_iter(),
+			positional,
 		}
 	}
 }
@@ -840,6 +1009,12 @@ impl MatchesHitsIterator for FullTextHitsIterator {
 	/// it to a Thing. It returns None when there are no more hits.
 	async fn next(&mut self, tx: &Transaction) -> Result<Option<(RecordId, DocId)>> {
 		for doc_id in self.iter.by_ref() {
+			if let Some((query, bo)) = &self.positional {
+				let doc = FullTextIndex::read_doc_terms(&self.ikb, tx, query, doc_id).await?;
+				if !query.matches(*bo, &doc) {
+					continue;
+				}
+			}
 			if let Some(key) = SeqDocIds::get_id(&self.ikb, tx, doc_id).await? {
 				let rid = RecordId {
 					table: self.ikb.table().clone(),
@@ -895,14 +1070,13 @@ impl Scorer {
 		doc_id: DocId,
 	) -> Result<Score> {
 		let mut sc = 0.0;
-		let tl = qt.tokens.list();
+		let terms = qt.query.terms();
 		let doc_length = fti.get_doc_length(tx, doc_id).await?.unwrap_or(0) as f64;
 		for (i, d) in qt.docs.iter().enumerate() {
 			if let Some(docs) = d
 				&& docs.contains(doc_id)
-				&& let Some(token) = tl.get(i)
+				&& let Some(term) = terms.get(i)
 			{
-				let term = qt.tokens.get_token_string(token)?;
 				let td = fti.get_term_document(tx, doc_id, term).await?;
 				if let Some(td) = td {
 					sc += self.compute_bm25_score(td.f as f64, docs.len() as f64, doc_length)
@@ -1137,30 +1311,35 @@ mod tests {
 							start: 44,
 							gen_start: 44,
 							end: 47,
+							pos: 6,
 						},
 						Offset {
 							index: 3,
 							start: 42,
 							gen_start: 42,
 							end: 45,
+							pos: 7,
 						},
 						Offset {
 							index: 16,
 							start: 4,
 							gen_start: 4,
 							end: 7,
+							pos: 1,
 						},
 						Offset {
 							index: 18,
 							start: 8,
 							gen_start: 8,
 							end: 11,
+							pos: 2,
 						},
 						Offset {
 							index: 19,
 							start: 59,
 							gen_start: 59,
 							end: 62,
+							pos: 12,
 						},
 					],
 				}
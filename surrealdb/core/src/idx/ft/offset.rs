@@ -2,7 +2,7 @@ use revision::revisioned;
 
 use crate::idx::ft::Position;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Offset {
 	pub(super) index: u32,
@@ -12,15 +12,38 @@ pub(crate) struct Offset {
 	pub(super) gen_start: Position,
 	// End position of the original term
 	pub(super) end: Position,
+	// Position of the original term in the sequence of terms of the value
+	#[revision(start = 2, default_fn = "default_pos")]
+	pub(super) pos: Position,
 }
 
 impl Offset {
-	pub(crate) fn new(index: u32, start: Position, gen_start: Position, end: Position) -> Self {
+	/// Offsets written before revision 2 did not record the position of the
+	/// term, they are marked with this sentinel until the index is rebuilt.
+	pub(crate) const UNKNOWN_POS: Position = Position::MAX;
+
+	fn default_pos(_revision: u16) -> Result<Position, revision::Error> {
+		Ok(Self::UNKNOWN_POS)
+	}
+
+	/// Returns true if the position of the term is known
+	pub(super) fn has_pos(&self) -> bool {
+		self.pos != Self::UNKNOWN_POS
+	}
+
+	pub(crate) fn new(
+		index: u32,
+		start: Position,
+		gen_start: Position,
+		end: Position,
+		pos: Position,
+	) -> Self {
 		Self {
 			index,
 			start,
 			gen_start,
 			end,
+			pos,
 		}
 	}
 }
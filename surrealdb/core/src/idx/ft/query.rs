@@ -0,0 +1,779 @@
+//! Parsing and evaluation of full-text search query strings.
+//!
+//! Unless the analyzer of the index is defined with `OPERATORS`, a query
+//! string is analyzed as a whole, and every term it produces is matched.
+//! Otherwise, besides plain terms, a query string supports:
+//! - phrases (`"quick brown fox"`), matching consecutive terms,
+//! - proximity (`quick NEAR/3 fox`), matching terms at most 3 positions apart,
+//! - `+term`, for a term which must match, and `-term`, for a term which must
+//!   not match,
+//! - `OR` between terms, any of which may match,
+//! - `term*`, matching the terms of the index starting with the term,
+//! - `term~` and `term~2`, matching the terms of the index within a Levenshtein
+//!   distance of the term, which start with the same leading characters as the
+//!   term (one by default, `SURREAL_FULLTEXT_FUZZY_PREFIX_LENGTH`).
+//!
+//! A prefix or fuzzy term expands to at most 50 terms of the index, keeping
+//! the closest ones, then the ones found in the most documents. The query fails
+//! when a prefix or fuzzy term has to be compared to more terms of the index
+//! than `SURREAL_FULLTEXT_MAX_EXPANSION_CANDIDATES` (1000 by default).
+//!
+//! Each term or phrase of the query string is analyzed separately, and the
+//! clauses of the query refer to the terms produced by the analyzer.
+//! Phrases and proximity are checked against the positions stored in the
+//! offsets of the terms.
+//!
+//! A query string which has none of these operators is analyzed as a whole,
+//! as it is without `OPERATORS`. Otherwise, a word such as `OR`, `-lazy` or
+//! `fox*` is read as an operator. Such a word is matched literally when
+//! written as a single word phrase, e.g. `"OR"`, `"-lazy"` or `"fox*"`, which
+//! does not require the positions of the terms.
+//!
+//! Indexes built before positions were stored in the offsets have to be
+//! rebuilt (`REBUILD INDEX`) before phrase or proximity queries can be used.
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail, ensure};
+use roaring::RoaringTreemap;
+
+use crate::err::Error;
+use crate::expr::operator::BooleanOperator;
+use crate::idx::ft::Position;
+use crate::idx::ft::analyzer::tokenizer::Tokens;
+use crate::idx::ft::fulltext::FullTextIndex;
+use crate::idx::ft::offset::Offset;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Occur {
+	Should,
+	Must,
+	MustNot,
+}
+
+/// The maximum edit distance of a fuzzy term
+const MAX_DISTANCE: u32 = 2;
+
+/// How a term of the query string is expanded to other terms of the index
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(in crate::idx::ft) enum Expansion {
+	/// The terms starting with the term
+	Prefix,
+	/// The terms within an edit distance of the term. Without an explicit
+	/// distance, it depends on the length of the term.
+	Fuzzy(Option<u32>),
+}
+
+/// Returns the maximum edit distance of a fuzzy term, which depends on the
+/// length of the term when it is not explicit
+pub(in crate::idx::ft) fn fuzzy_distance(distance: Option<u32>, term: &str) -> u32 {
+	distance.unwrap_or_else(|| match term.chars().count() {
+		0..=2 => 0,
+		3..=5 => 1,
+		_ => MAX_DISTANCE,
+	})
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(in crate::idx::ft) enum Matcher<T> {
+	/// Every term must be present
+	Terms(T),
+	/// The terms must be consecutive
+	Phrase(T),
+	/// Two sequences of consecutive terms, at most a number of positions apart
+	Near(T, T, u32),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(in crate::idx::ft) struct Clauses<T> {
+	must: Vec<Matcher<T>>,
+	must_not: Vec<Matcher<T>>,
+	/// The optional clauses. The matchers of each clause are the alternatives
+	/// separated by `OR`.
+	should: Vec<Vec<Matcher<T>>>,
+}
+
+impl<T> Default for Clauses<T> {
+	fn default() -> Self {
+		Self {
+			must: Vec::new(),
+			must_not: Vec::new(),
+			should: Vec::new(),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq)]
+enum Lexeme<'a> {
+	Operand {
+		occur: Occur,
+		text: &'a str,
+		phrase: bool,
+		expansion: Option<Expansion>,
+	},
+	Or,
+	Near(u32),
+}
+
+/// A term or a phrase of the query string
+#[derive(Debug, PartialEq, Eq)]
+pub(in crate::idx::ft) struct Operand {
+	/// The text to analyze
+	pub(in crate::idx::ft) text: String,
+	/// The expansion of the terms produced by the analyzer
+	expansion: Option<Expansion>,
+}
+
+/// A query string split into the parts which are analyzed separately
+#[derive(Debug, PartialEq, Eq)]
+pub(in crate::idx::ft) struct ParsedQuery {
+	/// The terms and phrases of the query string
+	pub(in crate::idx::ft) operands: Vec<Operand>,
+	/// The clauses, referring to the operands. A query string without any
+	/// operator has no clauses, and is analyzed as a whole.
+	clauses: Option<Clauses<usize>>,
+}
+
+impl ParsedQuery {
+	/// A query string which is analyzed as a whole, without operators.
+	pub(in crate::idx::ft) fn plain(query: &str) -> Self {
+		Self {
+			operands: vec![Operand {
+				text: query.to_string(),
+				expansion: None,
+			}],
+			clauses: None,
+		}
+	}
+
+	pub(in crate::idx::ft) fn parse(query: &str) -> Result<Self> {
+		let lexemes = Self::lex(query)?;
+		if lexemes.iter().all(|l| {
+			matches!(
+				l,
+				Lexeme::Operand {
+					occur: Occur::Should,
+					phrase: false,
+					expansion: None,
+					..
+				}
+			)
+		}) {
+			return Ok(Self::plain(query));
+		}
+		let mut operands = Vec::new();
+		let mut clauses = Clauses::default();
+		// The alternatives of the current optional clause
+		let mut chain = Vec::new();
+		// The last clause, which may still be followed by `OR` or `NEAR/n`
+		let mut pending: Option<(Occur, Matcher<usize>)> = None;
+		let mut or = false;
+		let mut near = None;
+		for lexeme in lexemes {
+			match lexeme {
+				Lexeme::Operand {
+					occur,
+					text,
+					phrase,
+					expansion,
+				} => {
+					let operand = operands.len();
+					operands.push(Operand {
+						text: text.to_string(),
+						expansion,
+					});
+					if let Some(distance) = near.take() {
+						let Some((o, Matcher::Terms(left) | Matcher::Phrase(left))) = pending
+						else {
+							bail!(Self::near_error(distance));
+						};
+						ensure!(occur == Occur::Should, Self::near_error(distance));
+						pending = Some((o, Matcher::Near(left, operand, distance)));
+						continue;
+					}
+					let matcher = if phrase {
+						Matcher::Phrase(operand)
+					} else {
+						Matcher::Terms(operand)
+					};
+					if or {
+						ensure!(occur == Occur::Should, Self::or_error());
+						if let Some((_, m)) = pending.take() {
+							chain.push(m);
+						}
+						or = false;
+					} else {
+						Self::push(&mut clauses, &mut chain, pending.take());
+					}
+					pending = Some((occur, matcher));
+				}
+				Lexeme::Or => {
+					ensure!(
+						!or && near.is_none() && matches!(pending, Some((Occur::Should, _))),
+						Self::or_error()
+					);
+					or = true;
+				}
+				Lexeme::Near(distance) => {
+					ensure!(
+						!or && near.is_none()
+							&& matches!(pending, Some((_, Matcher::Terms(_) | Matcher::Phrase(_)))),
+						Self::near_error(distance)
+					);
+					near = Some(distance);
+				}
+			}
+		}
+		ensure!(!or, Self::or_error());
+		if let Some(distance) = near {
+			bail!(Self::near_error(distance));
+		}
+		Self::push(&mut clauses, &mut chain, pending);
+		Ok(Self {
+			operands,
+			clauses: Some(clauses),
+		})
+	}
+
+	fn lex(query: &str) -> Result<Vec<Lexeme<'_>>> {
+		let mut lexemes = Vec::new();
+		let mut rest = query.trim_start();
+		while !rest.is_empty() {
+			// A `+` or `-` prefix, which is not on its own
+			let occur = match rest.chars().next() {
+				Some('+') if rest[1..].starts_with(|c: char| !c.is_whitespace()) => Occur::Must,
+				Some('-') if rest[1..].starts_with(|c: char| !c.is_whitespace()) => Occur::MustNot,
+				_ => Occur::Should,
+			};
+			if occur != Occur::Should {
+				rest = &rest[1..];
+			}
+			if let Some(phrase) = rest.strip_prefix('"') {
+				let Some(end) = phrase.find('"') else {
+					bail!(Error::FullTextQueryError(format!(
+						"The phrase \"{phrase}\" is not terminated by a double quote"
+					)));
+				};
+				lexemes.push(Lexeme::Operand {
+					occur,
+					text: &phrase[..end],
+					phrase: true,
+					expansion: None,
+				});
+				rest = &phrase[end + 1..];
+			} else {
+				let end = rest.find(|c: char| c.is_whitespace() || c == '"').unwrap_or(rest.len());
+				let word = &rest[..end];
+				let distance = word.strip_prefix("NEAR/").and_then(|d| d.parse().ok());
+				lexemes.push(match (occur, word, distance) {
+					(Occur::Should, "OR", _) => Lexeme::Or,
+					(Occur::Should, _, Some(distance)) => Lexeme::Near(distance),
+					_ => {
+						let (text, expansion) = Self::expansion(word)?;
+						Lexeme::Operand {
+							occur,
+							text,
+							phrase: false,
+							expansion,
+						}
+					}
+				});
+				rest = &rest[end..];
+			}
+			rest = rest.trim_start();
+		}
+		Ok(lexemes)
+	}
+
+	/// Splits the `*` or `~` suffix of a word
+	fn expansion(word: &str) -> Result<(&str, Option<Expansion>)> {
+		if let Some(text) = word.strip_suffix('*')
+			&& !text.is_empty()
+		{
+			return Ok((text, Some(Expansion::Prefix)));
+		}
+		if let Some((text, distance)) = word.rsplit_once('~')
+			&& !text.is_empty()
+		{
+			if distance.is_empty() {
+				return Ok((text, Some(Expansion::Fuzzy(None))));
+			}
+			if let Ok(distance) = distance.parse() {
+				ensure!(
+					distance <= MAX_DISTANCE,
+					Error::FullTextQueryError(format!(
+						"The edit distance of {word} must not be greater than {MAX_DISTANCE}"
+					))
+				);
+				return Ok((text, Some(Expansion::Fuzzy(Some(distance)))));
+			}
+		}
+		Ok((word, None))
+	}
+
+	fn push(
+		clauses: &mut Clauses<usize>,
+		chain: &mut Vec<Matcher<usize>>,
+		clause: Option<(Occur, Matcher<usize>)>,
+	) {
+		match clause {
+			Some((Occur::Should, m)) => {
+				chain.push(m);
+				clauses.should.push(std::mem::take(chain));
+			}
+			Some((Occur::Must, m)) => clauses.must.push(m),
+			Some((Occur::MustNot, m)) => clauses.must_not.push(m),
+			None => {}
+		}
+	}
+
+	fn or_error() -> Error {
+		Error::FullTextQueryError(
+			"OR must be placed between two terms or phrases without a + or - prefix".to_string(),
+		)
+	}
+
+	fn near_error(distance: u32) -> Error {
+		Error::FullTextQueryError(format!(
+			"NEAR/{distance} must be placed between two terms or phrases"
+		))
+	}
+}
+
+/// The terms of an analyzed query string, and the clauses they must satisfy
+pub(in crate::idx::ft) struct FullTextQuery {
+	/// The terms produced by the analyzer, followed by the terms of the index
+	/// the prefix and fuzzy terms expand to
+	terms: Vec<String>,
+	/// The number of terms produced by the analyzer
+	analyzed: usize,
+	/// The indexes of the terms produced by each token of the query string.
+	/// Synonyms expanded at query time produce several alternative terms for
+	/// the same token.
+	groups: Vec<Vec<usize>>,
+	/// The groups whose terms are expanded to other terms of the index
+	expansions: Vec<(usize, Expansion)>,
+	/// The clauses, referring to the groups
+	clauses: Clauses<Vec<usize>>,
+	/// Whether the query string has no operator
+	plain: bool,
+}
+
+impl FullTextQuery {
+	/// Builds the query from the tokens produced by the analyzer for each
+	/// operand of the parsed query.
+	pub(in crate::idx::ft) fn new(parsed: ParsedQuery, tokens: &[Tokens]) -> Result<Self> {
+		let mut terms = Vec::new();
+		let mut groups: Vec<Vec<usize>> = Vec::new();
+		let mut operands = Vec::with_capacity(tokens.len());
+		let mut expansions = Vec::new();
+		for (tks, op) in tokens.iter().zip(&parsed.operands) {
+			let mut operand = Vec::new();
+			let mut unique_tokens = HashSet::new();
+			let mut sources = HashMap::new();
+			for token in tks.list() {
+				if unique_tokens.insert(token) {
+					// Terms produced from the same part of the query string are grouped
+					let group = *sources.entry(token.source()).or_insert_with(|| {
+						groups.push(Vec::new());
+						operand.push(groups.len() - 1);
+						groups.len() - 1
+					});
+					groups[group].push(terms.len());
+					terms.push(tks.get_token_string(token)?.to_string());
+				}
+			}
+			if let Some(expansion) = op.expansion {
+				expansions.extend(operand.iter().map(|g| (*g, expansion)));
+			}
+			operands.push(operand);
+		}
+		let plain = parsed.clauses.is_none();
+		let clauses = match parsed.clauses {
+			// Without any operator, each token is a clause on its own
+			None => Clauses {
+				should: operands
+					.concat()
+					.into_iter()
+					.map(|g| vec![Matcher::Terms(vec![g])])
+					.collect(),
+				..Default::default()
+			},
+			Some(clauses) => Clauses {
+				must: clauses
+					.must
+					.into_iter()
+					.filter_map(|m| Self::resolve(m, &operands))
+					.collect(),
+				must_not: clauses
+					.must_not
+					.into_iter()
+					.filter_map(|m| Self::resolve(m, &operands))
+					.collect(),
+				should: clauses
+					.should
+					.into_iter()
+					.map(|c| {
+						c.into_iter()
+							.filter_map(|m| Self::resolve(m, &operands))
+							.collect::<Vec<_>>()
+					})
+					.filter(|c| !c.is_empty())
+					.collect(),
+			},
+		};
+		Ok(Self {
+			analyzed: terms.len(),
+			terms,
+			groups,
+			expansions,
+			clauses,
+			plain,
+		})
+	}
+
+	/// Replaces the operands of a matcher with their groups. Operands which
+	/// did not produce any term (e.g. stop words) are ignored.
+	fn resolve(m: Matcher<usize>, operands: &[Vec<usize>]) -> Option<Matcher<Vec<usize>>> {
+		let sequence = |groups: &Vec<usize>| match groups.len() {
+			0 => None,
+			1 => Some(Matcher::Terms(groups.clone())),
+			_ => Some(Matcher::Phrase(groups.clone())),
+		};
+		match m {
+			Matcher::Terms(o) if operands[o].is_empty() => None,
+			Matcher::Terms(o) => Some(Matcher::Terms(operands[o].clone())),
+			Matcher::Phrase(o) => sequence(&operands[o]),
+			Matcher::Near(l, r, distance) => match (&operands[l], &operands[r]) {
+				(l, r) if l.is_empty() => sequence(r),
+				(l, r) if r.is_empty() => sequence(l),
+				(l, r) => Some(Matcher::Near(l.clone(), r.clone(), distance)),
+			},
+		}
+	}
+
+	pub(in crate::idx::ft) fn terms(&self) -> &[String] {
+		&self.terms
+	}
+
+	/// Returns the terms which are not produced by the analyzer, but by the
+	/// expansion of the prefix and fuzzy terms
+	pub(in crate::idx::ft) fn expanded_terms(&self) -> &[String] {
+		&self.terms[self.analyzed..]
+	}
+
+	/// Returns the terms to expand, with their group and their expansion
+	pub(in crate::idx::ft) fn expansions(&self) -> Vec<(usize, String, Expansion)> {
+		self.expansions
+			.iter()
+			.flat_map(|(g, e)| self.groups[*g].iter().map(|t| (*g, self.terms[*t].clone(), *e)))
+			.collect()
+	}
+
+	/// Adds the terms an expanded term of a group expands to. The new terms
+	/// are alternatives of the existing terms of the group.
+	pub(in crate::idx::ft) fn expand(&mut self, group: usize, terms: Vec<String>) {
+		for term in terms {
+			if !self.groups[group].iter().any(|t| self.terms[*t] == term) {
+				self.groups[group].push(self.terms.len());
+				self.terms.push(term);
+			}
+		}
+	}
+
+	pub(in crate::idx::ft) fn is_plain(&self) -> bool {
+		self.plain
+	}
+
+	/// Whether the query has phrases or proximity clauses, which require the
+	/// positions of the terms
+	pub(in crate::idx::ft) fn is_positional(&self) -> bool {
+		self.clauses
+			.must
+			.iter()
+			.chain(&self.clauses.must_not)
+			.chain(self.clauses.should.iter().flatten())
+			.any(|m| !matches!(m, Matcher::Terms(_)))
+	}
+
+	/// Returns the documents matching the query, given the documents of each
+	/// term. Phrases and proximity are not checked, so the result is a superset
+	/// of the matching documents when the query is positional.
+	pub(in crate::idx::ft) fn hits(
+		&self,
+		docs: &[Option<RoaringTreemap>],
+		bo: BooleanOperator,
+	) -> Option<RoaringTreemap> {
+		let mut sets: Vec<Option<RoaringTreemap>> =
+			self.clauses.must.iter().map(|m| self.matcher_hits(m, docs)).collect();
+		let should: Vec<Option<RoaringTreemap>> = self
+			.clauses
+			.should
+			.iter()
+			.map(|c| {
+				let alternatives: Vec<_> = c.iter().map(|m| self.matcher_hits(m, docs)).collect();
+				FullTextIndex::union_operation(&alternatives)
+			})
+			.collect();
+		match bo {
+			BooleanOperator::And => sets.extend(should),
+			BooleanOperator::Or if sets.is_empty() => {
+				sets.push(FullTextIndex::union_operation(&should))
+			}
+			// With mandatory clauses, the optional clauses don't need to match
+			BooleanOperator::Or => {}
+		}
+		let mut hits = FullTextIndex::intersection_operation(&sets)?;
+		for m in &self.clauses.must_not {
+			if let Matcher::Terms(_) = m
+				&& let Some(excluded) = self.matcher_hits(m, docs)
+			{
+				hits -= excluded;
+			}
+		}
+		Some(hits)
+	}
+
+	fn matcher_hits(
+		&self,
+		m: &Matcher<Vec<usize>>,
+		docs: &[Option<RoaringTreemap>],
+	) -> Option<RoaringTreemap> {
+		let groups = match m {
+			Matcher::Terms(g) | Matcher::Phrase(g) => g.clone(),
+			Matcher::Near(l, r, _) => [l.as_slice(), r.as_slice()].concat(),
+		};
+		// A group matches the documents matching any of its terms
+		let group_docs: Vec<Option<RoaringTreemap>> = groups
+			.iter()
+			.map(|g| {
+				let terms: Vec<_> = self.groups[*g].iter().map(|t| docs[*t].clone()).collect();
+				FullTextIndex::union_operation(&terms)
+			})
+			.collect();
+		FullTextIndex::intersection_operation(&group_docs)
+	}
+
+	/// Checks whether a document matches the query, given the offsets of each
+	/// term in the document (`None` if the term is not in the document).
+	pub(in crate::idx::ft) fn matches(
+		&self,
+		bo: BooleanOperator,
+		doc: &[Option<Vec<Offset>>],
+	) -> bool {
+		if self.clauses.must.is_empty() && self.clauses.should.is_empty() {
+			return false;
+		}
+		if !self.clauses.must.iter().all(|m| self.matcher_matches(m, doc)) {
+			return false;
+		}
+		if self.clauses.must_not.iter().any(|m| self.matcher_matches(m, doc)) {
+			return false;
+		}
+		let mut should =
+			self.clauses.should.iter().map(|c| c.iter().any(|m| self.matcher_matches(m, doc)));
+		match bo {
+			BooleanOperator::And => should.all(|s| s),
+			BooleanOperator::Or if self.clauses.must.is_empty() => should.any(|s| s),
+			BooleanOperator::Or => true,
+		}
+	}
+
+	fn matcher_matches(&self, m: &Matcher<Vec<usize>>, doc: &[Option<Vec<Offset>>]) -> bool {
+		match m {
+			Matcher::Terms(groups) => groups
+				.iter()
+				.all(|g| self.groups[*g].iter().any(|t| doc.get(*t).is_some_and(Option::is_some))),
+			Matcher::Phrase(groups) => !self.phrase_positions(groups, doc).is_empty(),
+			Matcher::Near(left, right, distance) => {
+				let (ll, rl) = (left.len() as Position - 1, right.len() as Position - 1);
+				let right = self.phrase_positions(right, doc);
+				self.phrase_positions(left, doc).iter().any(|(i, p)| {
+					right.iter().any(|(j, q)| {
+						// The distance between the closest terms of both sequences
+						i == j
+							&& (p.saturating_sub(q + rl)).max(q.saturating_sub(p + ll)) <= *distance
+					})
+				})
+			}
+		}
+	}
+
+	/// Returns the value indexes and positions where the sequence of groups
+	/// starts in the document.
+	fn phrase_positions(
+		&self,
+		groups: &[usize],
+		doc: &[Option<Vec<Offset>>],
+	) -> Vec<(u32, Position)> {
+		let positions = |g: usize| -> HashSet<(u32, Position)> {
+			self.groups[g]
+				.iter()
+				.filter_map(|t| doc.get(*t).and_then(Option::as_ref))
+				.flatten()
+				.map(|o| (o.index, o.pos))
+				.collect()
+		};
+		let Some((first, rest)) = groups.split_first() else {
+			return Vec::new();
+		};
+		let mut starts: Vec<(u32, Position)> = positions(*first).into_iter().collect();
+		for (n, g) in rest.iter().enumerate() {
+			if starts.is_empty() {
+				break;
+			}
+			let next = positions(*g);
+			starts.retain(|(i, p)| next.contains(&(*i, p + n as Position + 1)));
+		}
+		starts
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Clauses, Expansion, Lexeme, Matcher, Occur, Operand, ParsedQuery, fuzzy_distance};
+
+	#[test]
+	fn test_lex() {
+		assert_eq!(
+			ParsedQuery::lex(r#"+quick -"brown fox" jumps OR leaps NEAR/2 dog - c++ or"#).unwrap(),
+			vec![
+				Lexeme::Operand {
+					occur: Occur::Must,
+					text: "quick",
+					phrase: false,
+					expansion: None,
+				},
+				Lexeme::Operand {
+					occur: Occur::MustNot,
+					text: "brown fox",
+					phrase: true,
+					expansion: None,
+				},
+				Lexeme::Operand {
+					occur: Occur::Should,
+					text: "jumps",
+					phrase: false,
+					expansion: None,
+				},
+				Lexeme::Or,
+				Lexeme::Operand {
+					occur: Occur::Should,
+					text: "leaps",
+					phrase: false,
+					expansion: None,
+				},
+				Lexeme::Near(2),
+				Lexeme::Operand {
+					occur: Occur::Should,
+					text: "dog",
+					phrase: false,
+					expansion: None,
+				},
+				Lexeme::Operand {
+					occur: Occur::Should,
+					text: "-",
+					phrase: false,
+					expansion: None,
+				},
+				Lexeme::Operand {
+					occur: Occur::Should,
+					text: "c++",
+					phrase: false,
+					expansion: None,
+				},
+				Lexeme::Operand {
+					occur: Occur::Should,
+					text: "or",
+					phrase: false,
+					expansion: None,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_parse_plain() {
+		assert_eq!(
+			ParsedQuery::parse("hello world").unwrap(),
+			ParsedQuery {
+				operands: vec![Operand {
+					text: "hello world".to_string(),
+					expansion: None,
+				}],
+				clauses: None,
+			}
+		);
+	}
+
+	#[test]
+	fn test_parse_clauses() {
+		let parsed =
+			ParsedQuery::parse(r#"+quick -"brown fox" jumps OR leaps NEAR/2 dog cat"#).unwrap();
+		assert_eq!(
+			parsed.operands.iter().map(|o| o.text.as_str()).collect::<Vec<_>>(),
+			vec!["quick", "brown fox", "jumps", "leaps", "dog", "cat"]
+		);
+		assert_eq!(
+			parsed.clauses,
+			Some(Clauses {
+				must: vec![Matcher::Terms(0)],
+				must_not: vec![Matcher::Phrase(1)],
+				should: vec![
+					vec![Matcher::Terms(2), Matcher::Near(3, 4, 2)],
+					vec![Matcher::Terms(5)]
+				],
+			})
+		);
+	}
+
+	#[test]
+	fn test_parse_errors() {
+		for query in ["OR cat", "cat OR", "+cat OR dog", "cat OR -dog", "NEAR/2 cat", "cat NEAR/2"]
+		{
+			assert!(ParsedQuery::parse(query).is_err(), "{query}");
+		}
+		assert!(ParsedQuery::parse("a NEAR/1 b NEAR/1 c").is_err());
+		assert!(ParsedQuery::parse(r#""brown fox"#).is_err());
+		assert!(ParsedQuery::parse("fox~3").is_err());
+	}
+
+	#[test]
+	fn test_lex_expansions() {
+		let expansions: Vec<_> = ParsedQuery::lex("qui* fox~ fxo~2 dog~0 * ~ a~b c++")
+			.unwrap()
+			.into_iter()
+			.map(|l| match l {
+				Lexeme::Operand {
+					text,
+					expansion,
+					..
+				} => (text, expansion),
+				_ => unreachable!(),
+			})
+			.collect();
+		assert_eq!(
+			expansions,
+			vec![
+				("qui", Some(Expansion::Prefix)),
+				("fox", Some(Expansion::Fuzzy(None))),
+				("fxo", Some(Expansion::Fuzzy(Some(2)))),
+				("dog", Some(Expansion::Fuzzy(Some(0)))),
+				("*", None),
+				("~", None),
+				("a~b", None),
+				("c++", None),
+			]
+		);
+		// A query with an expansion is not analyzed as a whole
+		assert!(ParsedQuery::parse("quick fox*").unwrap().clauses.is_some());
+	}
+
+	#[test]
+	fn test_fuzzy_distance() {
+		assert_eq!(fuzzy_distance(None, "ox"), 0);
+		assert_eq!(fuzzy_distance(None, "fox"), 1);
+		assert_eq!(fuzzy_distance(None, "quick"), 1);
+		assert_eq!(fuzzy_distance(None, "brownie"), 2);
+		assert_eq!(fuzzy_distance(Some(2), "fox"), 2);
+	}
+}
@@ -1,5 +1,5 @@
 //! This module applies index mutations for a single document across different
-//! index types (UNIQUE, regular, search, fulltext, Hnsw). Index keys are
+//! index types (UNIQUE, regular, search, fulltext, Hnsw, spatial). Index keys are
 //! constructed via key::index and field values are encoded using
 //! key::value::Array.
 //!
@@ -13,6 +13,8 @@
 //! - Numeric predicates need a single probe/range in the index; per-variant fan-out is no longer
 //!   required.
 
+use std::collections::BTreeSet;
+
 use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
@@ -20,7 +22,8 @@ use uuid::Uuid;
 
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{
-	DatabaseId, FullTextParams, HnswParams, Index, IndexDefinition, NamespaceId, TableId,
+	DatabaseId, FullTextParams, HnswParams, Index, IndexDefinition, NamespaceId, SpatialParams,
+	TableId,
 };
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
@@ -29,6 +32,7 @@ use crate::expr::{Cond, Part};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::FullTextIndex;
 use crate::idx::planner::iterators::IndexCountThingIterator;
+use crate::idx::spatial;
 use crate::idx::trees::store::IndexStores;
 use crate::key;
 use crate::key::index::iu::IndexCountKey;
@@ -99,6 +103,7 @@ impl<'a> IndexOperation<'a> {
 			Index::FullText(p) => self.index_fulltext(stk, p, require_compaction).await,
 			Index::Hnsw(p) => self.index_hnsw(p, require_compaction).await,
 			Index::Count(c) => self.index_count(stk, c.as_ref(), require_compaction).await,
+			Index::Spatial(p) => self.index_spatial(p).await,
 		}
 	}
 
@@ -199,6 +204,39 @@ impl<'a> IndexOperation<'a> {
 		Ok(())
 	}
 
+	/// Computes the set of cells the geometries of the given values are
+	/// stored under.
+	fn spatial_cells(p: &SpatialParams, values: Option<&Vec<Value>>) -> BTreeSet<i64> {
+		let mut cells = BTreeSet::new();
+		if let Some(values) = values {
+			let mut geometries = vec![];
+			values.iter().for_each(|v| spatial::collect_geometries(v, &mut geometries));
+			for g in geometries {
+				cells.extend(spatial::index_cells(p.level, g));
+			}
+		}
+		cells
+	}
+
+	async fn index_spatial(&mut self, p: &SpatialParams) -> Result<()> {
+		let txn = self.ctx.tx();
+		let o = Self::spatial_cells(p, self.o.take().as_ref());
+		let n = Self::spatial_cells(p, self.n.take().as_ref());
+		// Delete the cells which are not covered anymore
+		for cell in o.difference(&n) {
+			let fd = Array::from(vec![Value::from(*cell)]);
+			let key = self.get_non_unique_index_key(&fd)?;
+			txn.del(&key).await?;
+		}
+		// Create the newly covered cells
+		for cell in n.difference(&o) {
+			let fd = Array::from(vec![Value::from(*cell)]);
+			let key = self.get_non_unique_index_key(&fd)?;
+			txn.set(&key, self.rid, None).await?;
+		}
+		Ok(())
+	}
+
 	async fn index_count(
 		&mut self,
 		_stk: &mut Stk,
@@ -2,6 +2,7 @@ pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub(super) mod seqdocids;
+pub(crate) mod spatial;
 pub mod trees;
 
 use std::borrow::Cow;
@@ -179,6 +180,14 @@ impl IndexKeyBase {
 		Td::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term, doc_id)
 	}
 
+	fn new_td_terms_range(&self, prefix: &str) -> Result<(Key, Key)> {
+		Td::terms_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, prefix)
+	}
+
+	fn new_td_next_term(&self, term: &str) -> Result<Key> {
+		Td::next_term_key(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term)
+	}
+
 	fn new_tt<'a>(
 		&'a self,
 		term: &'a str,
@@ -56,6 +56,8 @@ impl MutVisitor for CountLimitRewriter {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Run(_)
+			| Expr::History(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
@@ -7,7 +7,7 @@ use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 
 use crate::catalog::providers::TableProvider;
-use crate::catalog::{DatabaseId, Distance, Index, IndexDefinition, NamespaceId};
+use crate::catalog::{DatabaseId, Distance, Index, IndexDefinition, NamespaceId, SpatialParams};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::{CursorDoc, NsDbTbCtx};
@@ -30,8 +30,9 @@ use crate::idx::planner::plan::IndexOperator::Matches;
 use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
 use crate::idx::planner::tree::{IdiomPosition, IndexReference};
 use crate::idx::planner::{IterationStage, ScanDirection};
+use crate::idx::spatial::{self, SpatialKnnSearch};
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
-use crate::val::{Array, Number, Object, RecordId, TableName, Value};
+use crate::val::{Array, Geometry, Number, Object, RecordId, TableName, Value};
 
 pub(super) type KnnBruteForceEntry = (KnnPriorityList, Idiom, Arc<Vec<Number>>, Distance);
 
@@ -74,6 +75,7 @@ enum PerExpressionEntry {
 	FullText(FullTextEntry),
 	Hnsw(HnswEntry),
 	KnnBruteForce(KnnBruteForceEntry),
+	SpatialKnn(SpatialKnnEntry),
 }
 
 /// Entry keyed by MatchRef for MATCHES queries, decoupling expression identity
@@ -252,6 +254,23 @@ impl InnerQueryExecutor {
 						}
 					}
 				}
+				Index::Spatial(p) => {
+					if let IndexOperator::SpatialKnn(v, k) = io.op() {
+						let se = SpatialKnnEntry::new(
+							stk,
+							ctx,
+							opt,
+							doc_ctx,
+							index_reference,
+							p,
+							v,
+							*k,
+							knn_condition.clone(),
+						)
+						.await?;
+						exp_entries.insert(exp, PerExpressionEntry::SpatialKnn(se));
+					}
+				}
 				_ => {}
 			}
 		}
@@ -394,6 +413,7 @@ impl QueryExecutor {
 				..
 			} => self.new_fulltext_index_iterator(irf, io.clone()).await,
 			Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+			Index::Spatial(ref p) => self.new_spatial_index_iterator(ns, db, irf, io, p),
 		}
 	}
 
@@ -658,6 +678,45 @@ impl QueryExecutor {
 		None
 	}
 
+	fn new_spatial_index_iterator(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		irf: IteratorRef,
+		io: &IndexOption,
+		p: &SpatialParams,
+	) -> Result<Option<RecordIterator>> {
+		match io.op() {
+			IndexOperator::Spatial(_, v) => {
+				let Value::Geometry(g) = v.as_ref() else {
+					return Ok(None);
+				};
+				let ranges: Vec<(Array, Array)> = spatial::query_ranges(p.level, g)
+					.into_iter()
+					.map(|(from, to)| {
+						(Array::from(vec![Value::from(from)]), Array::from(vec![Value::from(to)]))
+					})
+					.collect();
+				Ok(Some(RecordIterator::IndexUnion(IndexUnionThingIterator::ranges(
+					irf,
+					ns,
+					db,
+					io.index_reference(),
+					&ranges,
+				)?)))
+			}
+			IndexOperator::SpatialKnn(..) => {
+				if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(irf)
+					&& let Some(PerExpressionEntry::SpatialKnn(se)) = self.0.exp_entries.get(exp)
+				{
+					return Ok(Some(RecordIterator::Knn(KnnIterator::new(irf, se.res.clone()))));
+				}
+				Ok(None)
+			}
+			_ => Ok(None),
+		}
+	}
+
 	async fn build_iterators(
 		&self,
 		ns: NamespaceId,
@@ -712,10 +771,8 @@ impl QueryExecutor {
 			return Ok(false);
 		}
 		let tx = ctx.tx();
-		if let Some(doc_id) = fti.get_doc_id(&tx, thg).await?
-			&& fte.0.qt.contains_doc(doc_id)
-		{
-			return Ok(true);
+		if let Some(doc_id) = fti.get_doc_id(&tx, thg).await? {
+			return fti.matches_doc(&tx, &fte.0.qt, fte.0.bo, doc_id).await;
 		}
 		Ok(false)
 	}
@@ -870,6 +927,42 @@ impl FullTextEntry {
 }
 
 #[derive(Clone)]
+pub(super) struct SpatialKnnEntry {
+	res: VecDeque<KnnIteratorResult>,
+}
+
+impl SpatialKnnEntry {
+	#[expect(clippy::too_many_arguments)]
+	async fn new(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc_ctx: &NsDbTbCtx,
+		ix: &IndexDefinition,
+		p: &SpatialParams,
+		v: &Value,
+		k: u32,
+		cond: Option<Arc<Cond>>,
+	) -> Result<Self> {
+		let Value::Geometry(Geometry::Point(point)) = v else {
+			return Err(anyhow::Error::new(Error::InvalidSpatialKnnValue(v.to_sql())));
+		};
+		let res = SpatialKnnSearch::new(
+			doc_ctx.ns.namespace_id,
+			doc_ctx.db.database_id,
+			ix,
+			p.level,
+			*point,
+			cond,
+		)
+		.search(stk, ctx, opt, k as usize)
+		.await?;
+		Ok(Self {
+			res,
+		})
+	}
+}
+
 pub(super) struct HnswEntry {
 	res: VecDeque<KnnIteratorResult>,
 }
@@ -913,6 +913,30 @@ impl IndexUnionThingIterator {
 		})
 	}
 
+	/// Creates an iterator over a list of half-open ranges of field values.
+	/// Each range includes every record whose value is greater than or equal
+	/// to the lower bound and strictly lower than the upper bound.
+	pub(super) fn ranges(
+		irf: IteratorRef,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ix: &IndexDefinition,
+		ranges: &[(Array, Array)],
+	) -> Result<Self> {
+		let mut values: VecDeque<(Vec<u8>, Vec<u8>)> = VecDeque::with_capacity(ranges.len());
+		for (from, to) in ranges {
+			let beg = Index::prefix_ids_beg(ns, db, &ix.table_name, ix.index_id, from)?;
+			let end = Index::prefix_ids_beg(ns, db, &ix.table_name, ix.index_id, to)?;
+			values.push_back((beg, end));
+		}
+		let current = values.pop_front();
+		Ok(Self {
+			irf,
+			values,
+			current,
+		})
+	}
+
 	async fn next_batch<B: IteratorBatch>(
 		&mut self,
 		ctx: &FrozenContext,
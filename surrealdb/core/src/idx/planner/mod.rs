@@ -78,12 +78,17 @@ impl<'a> StatementContext<'a> {
 		stm: &'a Statement<'a>,
 	) -> Result<Self> {
 		let is_perm = opt.check_perms(stm.into())?;
+		// Versioned queries scan the table when indexes are not versioned
+		let with = match opt.version {
+			Some(_) if !ctx.tx().versioned_indexes() => Some(&With::NoIndex),
+			_ => stm.with(),
+		};
 		Ok(Self {
 			ctx,
 			opt,
 			stm,
 			fields: stm.expr(),
-			with: stm.with(),
+			with,
 			order: stm.order(),
 			cond: stm.cond(),
 			group: stm.group(),
@@ -413,6 +413,11 @@ pub(super) enum IndexOperator {
 	Range(Vec<Value>, Vec<(BinaryOperator, Arc<Value>)>),
 	Matches(String, MatchesOperator),
 	Ann(Arc<Vec<Number>>, u32, u32),
+	/// A geometry operator (INSIDE, INTERSECTS, CONTAINS) resolved by the cells
+	/// of a SPATIAL index
+	Spatial(BinaryOperator, Arc<Value>),
+	/// The k nearest neighbours of a point resolved by a SPATIAL index
+	SpatialKnn(Arc<Value>, u32),
 	/// false = ascending, true = descending
 	Order(bool),
 	Count,
@@ -434,7 +439,13 @@ impl IndexOption {
 	}
 
 	pub(super) fn require_distinct(&self) -> bool {
-		matches!(self.index_operator.as_ref(), IndexOperator::Union(_))
+		matches!(self.index_operator.as_ref(), IndexOperator::Union(_) | IndexOperator::Spatial(..))
+	}
+
+	/// Returns `true` if the index only returns candidates, which then
+	/// need to be checked against the condition.
+	pub(super) fn is_approximate(&self) -> bool {
+		matches!(self.index_operator.as_ref(), IndexOperator::Spatial(..))
 	}
 
 	pub(super) fn is_order(&self) -> bool {
@@ -516,6 +527,14 @@ impl IndexOption {
 				e.insert("operator", op);
 				e.insert("value", val);
 			}
+			IndexOperator::Spatial(op, v) => {
+				e.insert("operator", Value::from(op.to_sql()));
+				e.insert("value", v.as_ref().clone());
+			}
+			IndexOperator::SpatialKnn(v, k) => {
+				e.insert("operator", Value::from(NearestNeighbor::KTree(*k).to_sql()));
+				e.insert("value", v.as_ref().clone());
+			}
 			IndexOperator::Order(reverse) => {
 				e.insert(
 					"operator",
@@ -38,6 +38,8 @@ impl<'a> MutVisitor for KnnConditionRewriter<'a> {
 			| Expr::Define(_)
 			| Expr::Remove(_)
 			| Expr::Rebuild(_)
+			| Expr::Run(_)
+			| Expr::History(_)
 			| Expr::Upsert(_)
 			| Expr::Alter(_)
 			| Expr::Info(_)
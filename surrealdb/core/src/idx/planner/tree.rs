@@ -278,7 +278,8 @@ impl<'a> TreeBuilder<'a> {
 				| Literal::None
 				| Literal::Null
 				| Literal::Decimal(_)
-				| Literal::Float(_),
+				| Literal::Float(_)
+				| Literal::Geometry(_),
 			)
 			| Expr::Param(_)
 			| Expr::FunctionCall(_) => {
@@ -467,6 +468,7 @@ impl<'a> TreeBuilder<'a> {
 	fn check_leaf_node_with_index(&mut self, io: Option<&IndexOption>) {
 		if let Some(io) = io
 			&& self.with_indexes.allowed_index(io.index_reference().index_id)
+			&& !io.is_approximate()
 		{
 			self.leaf_nodes_with_index_count += 2;
 		}
@@ -525,6 +527,7 @@ impl<'a> TreeBuilder<'a> {
 					..
 				} if *col == 0 => Self::eval_matches_operator(op, n),
 				Index::Hnsw(_) if *col == 0 => self.eval_hnsw_knn(e, op, n)?,
+				Index::Spatial(_) if *col == 0 => self.eval_spatial_operator(e, op, n, p),
 				_ => None,
 			};
 			if res.is_none()
@@ -579,7 +582,40 @@ impl<'a> TreeBuilder<'a> {
 		Ok(None)
 	}
 
+	/// Geometry operators are matched against a SPATIAL index whatever the
+	/// position of the idiom. Nearest-neighbour searches require a point,
+	/// which is checked by the executor so that other geometries are rejected.
+	fn eval_spatial_operator(
+		&mut self,
+		exp: &Arc<Expr>,
+		op: &BinaryOperator,
+		n: &Node,
+		p: IdiomPosition,
+	) -> Option<IndexOperator> {
+		let v = n.is_computed()?;
+		if !matches!(v.as_ref(), Value::Geometry(_)) {
+			return None;
+		}
+		match (op, p) {
+			(BinaryOperator::Inside | BinaryOperator::Intersects | BinaryOperator::Contain, _) => {
+				Some(IndexOperator::Spatial(op.clone(), v))
+			}
+			(BinaryOperator::NearestNeighbor(nn), IdiomPosition::Left) => {
+				let (NearestNeighbor::K(k, _) | NearestNeighbor::KTree(k)) = &**nn else {
+					return None;
+				};
+				self.knn_expressions.insert(exp.clone());
+				Some(IndexOperator::SpatialKnn(v, *k))
+			}
+			_ => None,
+		}
+	}
+
 	fn eval_bruteforce_knn(&mut self, id: &Idiom, val: &Node, exp: &Arc<Expr>) -> Result<()> {
+		// The expression may already be resolved by a SPATIAL index
+		if self.knn_expressions.contains(exp) {
+			return Ok(());
+		}
 		let Expr::Binary {
 			op,
 			..
@@ -0,0 +1,540 @@
+//! Cell arithmetic and search support for SPATIAL indexes.
+//!
+//! A SPATIAL index divides the longitude/latitude plane into a quadtree. At a
+//! given level the plane is split into `2^level x 2^level` cells, and each cell
+//! is identified by the Z-order (Morton) interleaving of its column and row.
+//! Because the Morton code of a parent cell is the code of any of its children
+//! shifted right by two bits, every cell at a coarser level maps to a
+//! contiguous range of codes at the index level. This lets region queries and
+//! nearest-neighbour searches use range scans over the regular index keyspace.
+//!
+//! Geometries are stored under every index-level cell covering their bounding
+//! box. Geometries covering too many cells are stored once under
+//! [`OVERFLOW_CELL`], which every query also scans.
+//!
+//! Comparisons on `geo::distance` are not planned onto the index, and scan the
+//! table. The function returns `NONE` for geometries other than points, which
+//! sorts before every number, so a condition such as
+//! `geo::distance(location, $point) < 5000` matches those geometries wherever
+//! they are, and can not be restricted to the cells around the point. Queries
+//! which only need nearby records can use `INSIDE` with a polygon around the
+//! point, or a nearest neighbour search (`<|k|>`), which both use the index.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use anyhow::Result;
+use geo::{BoundingRect, Centroid, Distance, Haversine, Point, Rect};
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseId, IndexDefinition, NamespaceId};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::expr::{Cond, FlowResultExt as _};
+use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::idx::trees::knn::FloatKey;
+use crate::key::index::Index;
+use crate::kvs::Transaction;
+use crate::val::{Array, Geometry, RecordId, Value};
+
+/// The cell under which geometries covering too many cells are stored.
+pub(crate) const OVERFLOW_CELL: i64 = -1;
+
+/// The maximum number of cells a single geometry is stored under before it
+/// is moved to the overflow cell.
+const MAX_INDEX_CELLS: u64 = 64;
+
+/// The maximum number of cells used to cover a query geometry. Larger query
+/// geometries are covered with coarser cells.
+const MAX_QUERY_CELLS: u64 = 64;
+
+/// When a cell contains more entries than this during a nearest-neighbour
+/// search, it is split into its four children rather than being loaded.
+const KNN_SPLIT_THRESHOLD: u32 = 64;
+
+/// The mean earth radius in meters, matching the one used by `geo::Haversine`.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// A quadtree cell at a given level.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct Cell {
+	level: u8,
+	x: u32,
+	y: u32,
+}
+
+impl Cell {
+	/// The cell covering the whole plane.
+	pub(crate) fn root() -> Self {
+		Self {
+			level: 0,
+			x: 0,
+			y: 0,
+		}
+	}
+
+	/// Returns the cell containing the given coordinate at the given level.
+	/// Coordinates outside of the longitude/latitude range are clamped to the
+	/// border cells.
+	pub(crate) fn from_coord(level: u8, lon: f64, lat: f64) -> Self {
+		let n = 1u64 << level;
+		let axis = |v: f64, min: f64, span: f64| -> u32 {
+			let v = ((v - min) / span * n as f64).floor();
+			if v.is_nan() || v < 0.0 {
+				0
+			} else if v >= n as f64 {
+				(n - 1) as u32
+			} else {
+				v as u32
+			}
+		};
+		Self {
+			level,
+			x: axis(lon, -180.0, 360.0),
+			y: axis(lat, -90.0, 180.0),
+		}
+	}
+
+	/// The Morton code of this cell.
+	pub(crate) fn code(&self) -> i64 {
+		let mut code = 0i64;
+		for i in 0..self.level {
+			code |= (((self.x >> i) & 1) as i64) << (2 * i);
+			code |= (((self.y >> i) & 1) as i64) << (2 * i + 1);
+		}
+		code
+	}
+
+	/// The half-open range of codes covered by this cell at the given index
+	/// level.
+	pub(crate) fn range(&self, level: u8) -> (i64, i64) {
+		let shift = 2 * (level.saturating_sub(self.level)) as u32;
+		(self.code() << shift, (self.code() + 1) << shift)
+	}
+
+	/// The four cells one level below this one.
+	pub(crate) fn children(&self) -> [Cell; 4] {
+		let (level, x, y) = (self.level + 1, self.x << 1, self.y << 1);
+		[
+			Cell {
+				level,
+				x,
+				y,
+			},
+			Cell {
+				level,
+				x: x + 1,
+				y,
+			},
+			Cell {
+				level,
+				x,
+				y: y + 1,
+			},
+			Cell {
+				level,
+				x: x + 1,
+				y: y + 1,
+			},
+		]
+	}
+
+	/// The (min longitude, min latitude, max longitude, max latitude) of this
+	/// cell in degrees.
+	fn bounds(&self) -> (f64, f64, f64, f64) {
+		let n = (1u64 << self.level) as f64;
+		let (w, h) = (360.0 / n, 180.0 / n);
+		let lon = -180.0 + self.x as f64 * w;
+		let lat = -90.0 + self.y as f64 * h;
+		(lon, lat, lon + w, lat + h)
+	}
+
+	/// A lower bound, in meters, of the great-circle distance between the
+	/// given point and any point within this cell.
+	pub(crate) fn min_distance(&self, p: &Point<f64>) -> f64 {
+		let (lon_min, lat_min, lon_max, lat_max) = self.bounds();
+		let (lon, lat) = (p.x(), p.y());
+		// Any point of the cell is at least as far as the latitude difference
+		let lat_bound = if lat < lat_min {
+			(lat_min - lat).to_radians()
+		} else if lat > lat_max {
+			(lat - lat_max).to_radians()
+		} else {
+			0.0
+		};
+		// Any point of the cell is at least as far as the nearest meridian
+		let lon_bound = if lon >= lon_min && lon <= lon_max {
+			0.0
+		} else {
+			let diff = |a: f64, b: f64| {
+				let d = (a - b).abs() % 360.0;
+				d.min(360.0 - d)
+			};
+			let dl = diff(lon, lon_min).min(diff(lon, lon_max)).min(90.0);
+			(lat.to_radians().cos() * dl.to_radians().sin()).clamp(-1.0, 1.0).asin()
+		};
+		// Absorb floating point differences with the haversine formula
+		lat_bound.max(lon_bound) * EARTH_RADIUS * (1.0 - 1e-9)
+	}
+}
+
+/// Returns the cells covering the given bounding rectangle at the given level,
+/// or `None` if there are more than `max` of them.
+fn cover_rect(level: u8, rect: &Rect<f64>, max: u64) -> Option<Vec<Cell>> {
+	let min = Cell::from_coord(level, rect.min().x, rect.min().y);
+	let max_cell = Cell::from_coord(level, rect.max().x, rect.max().y);
+	let count = (max_cell.x - min.x + 1) as u64 * (max_cell.y - min.y + 1) as u64;
+	if count > max {
+		return None;
+	}
+	let mut cells = Vec::with_capacity(count as usize);
+	for y in min.y..=max_cell.y {
+		for x in min.x..=max_cell.x {
+			cells.push(Cell {
+				level,
+				x,
+				y,
+			});
+		}
+	}
+	Some(cells)
+}
+
+fn bounding_rect(g: &Geometry) -> Option<Rect<f64>> {
+	geo::Geometry::from(g.clone()).bounding_rect()
+}
+
+/// Collects the geometries held by an indexed value. Arrays of geometries are
+/// flattened, any other value is ignored.
+pub(crate) fn collect_geometries<'a>(v: &'a Value, res: &mut Vec<&'a Geometry>) {
+	match v {
+		Value::Geometry(g) => res.push(g),
+		Value::Array(a) => a.iter().for_each(|v| collect_geometries(v, res)),
+		_ => {}
+	}
+}
+
+/// Returns the index-level cells a geometry has to be stored under.
+pub(crate) fn index_cells(level: u8, g: &Geometry) -> Vec<i64> {
+	let Some(rect) = bounding_rect(g) else {
+		return vec![];
+	};
+	match cover_rect(level, &rect, MAX_INDEX_CELLS) {
+		Some(cells) => cells.iter().map(Cell::code).collect(),
+		None => vec![OVERFLOW_CELL],
+	}
+}
+
+/// Returns the sorted and merged half-open ranges of index-level codes that
+/// have to be scanned to find every geometry whose bounding box intersects
+/// the bounding box of the given geometry. The overflow cell is always part of
+/// the result.
+pub(crate) fn query_ranges(level: u8, g: &Geometry) -> Vec<(i64, i64)> {
+	let mut ranges = vec![(OVERFLOW_CELL, OVERFLOW_CELL + 1)];
+	let Some(rect) = bounding_rect(g) else {
+		return ranges;
+	};
+	let mut covering = vec![];
+	for l in (0..=level).rev() {
+		if let Some(cells) = cover_rect(l, &rect, MAX_QUERY_CELLS) {
+			covering = cells.iter().map(|c| c.range(level)).collect();
+			break;
+		}
+	}
+	covering.sort_unstable();
+	for r in covering {
+		match ranges.last_mut() {
+			Some(last) if last.1 >= r.0 => last.1 = last.1.max(r.1),
+			_ => ranges.push(r),
+		}
+	}
+	ranges
+}
+
+/// The great-circle distance, in meters, between a point and a geometry.
+/// Geometries other than points are measured from their centroid.
+pub(crate) fn distance(p: &Point<f64>, g: &Geometry) -> Option<f64> {
+	let target = match g {
+		Geometry::Point(t) => *t,
+		g => geo::Geometry::from(g.clone()).centroid()?,
+	};
+	Some(Haversine.distance(*p, target))
+}
+
+/// A candidate of the best-first nearest-neighbour search.
+enum Candidate {
+	/// A cell which has not been explored yet
+	Cell(Cell),
+	/// A record with its exact distance
+	Record(Arc<RecordId>, Arc<crate::catalog::Record>),
+}
+
+/// Best-first nearest-neighbour search over a SPATIAL index.
+///
+/// Cells are explored by increasing lower-bound distance. Cells holding only a
+/// few entries are loaded and their records are queued with their exact
+/// distance; denser cells are split into their children. A record is returned
+/// once it is the closest entry of the queue, which guarantees that no
+/// unexplored cell can hold a closer record.
+pub(crate) struct SpatialKnnSearch<'a> {
+	ns: NamespaceId,
+	db: DatabaseId,
+	ix: &'a IndexDefinition,
+	level: u8,
+	point: Point<f64>,
+	cond: Option<Arc<Cond>>,
+	queue: BTreeMap<(FloatKey, u64), Candidate>,
+	seen: HashSet<Arc<RecordId>>,
+	seq: u64,
+}
+
+impl<'a> SpatialKnnSearch<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		ix: &'a IndexDefinition,
+		level: u8,
+		point: Point<f64>,
+		cond: Option<Arc<Cond>>,
+	) -> Self {
+		Self {
+			ns,
+			db,
+			ix,
+			level,
+			point,
+			cond,
+			queue: BTreeMap::new(),
+			seen: HashSet::new(),
+			seq: 0,
+		}
+	}
+
+	fn push(&mut self, dist: f64, candidate: Candidate) {
+		self.seq += 1;
+		self.queue.insert((FloatKey::from(dist), self.seq), candidate);
+	}
+
+	fn range_keys(&self, (from, to): (i64, i64)) -> Result<(Vec<u8>, Vec<u8>)> {
+		let tb = &self.ix.table_name;
+		let ix = self.ix.index_id;
+		let from = Array::from(vec![Value::from(from)]);
+		let to = Array::from(vec![Value::from(to)]);
+		Ok((
+			Index::prefix_ids_beg(self.ns, self.db, tb, ix, &from)?,
+			Index::prefix_ids_beg(self.ns, self.db, tb, ix, &to)?,
+		))
+	}
+
+	pub(crate) async fn search(
+		mut self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		k: usize,
+	) -> Result<VecDeque<KnnIteratorResult>> {
+		let tx = ctx.tx();
+		let mut res = VecDeque::with_capacity(k);
+		if k == 0 {
+			return Ok(res);
+		}
+		// Geometries stored in the overflow cell can be anywhere
+		let overflow = self.range_keys((OVERFLOW_CELL, OVERFLOW_CELL + 1))?;
+		self.load(stk, ctx, opt, &tx, overflow).await?;
+		self.push(0.0, Candidate::Cell(Cell::root()));
+		while let Some(((dist, _), candidate)) = self.queue.pop_first() {
+			if ctx.is_done(None).await? {
+				break;
+			}
+			match candidate {
+				Candidate::Record(rid, record) => {
+					res.push_back((rid, dist.into(), Some(record)));
+					if res.len() >= k {
+						break;
+					}
+				}
+				Candidate::Cell(cell) => {
+					let (beg, end) = self.range_keys(cell.range(self.level))?;
+					let keys =
+						tx.keys(beg.clone()..end.clone(), KNN_SPLIT_THRESHOLD + 1, 0, None).await?;
+					if keys.is_empty() {
+						continue;
+					}
+					if keys.len() as u32 > KNN_SPLIT_THRESHOLD && cell.level < self.level {
+						for child in cell.children() {
+							let d = child.min_distance(&self.point);
+							self.push(d, Candidate::Cell(child));
+						}
+					} else {
+						self.load(stk, ctx, opt, &tx, (beg, end)).await?;
+					}
+				}
+			}
+		}
+		Ok(res)
+	}
+
+	/// Loads every record stored in the given key range, and queues the ones
+	/// which have not been seen yet with their exact distance.
+	async fn load(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		tx: &Transaction,
+		(mut beg, end): (Vec<u8>, Vec<u8>),
+	) -> Result<()> {
+		loop {
+			let batch = tx.scan(beg.clone()..end.clone(), 1000, 0, None).await?;
+			let Some((last, _)) = batch.last() else {
+				return Ok(());
+			};
+			beg.clone_from(last);
+			beg.push(0x00);
+			for (_, val) in batch {
+				let rid: Arc<RecordId> = Arc::new(revision::from_slice(&val)?);
+				if !self.seen.insert(rid.clone()) {
+					continue;
+				}
+				if let Some((dist, record)) = self.evaluate(stk, ctx, opt, tx, rid.clone()).await? {
+					self.push(dist, Candidate::Record(rid, record));
+				}
+			}
+		}
+	}
+
+	/// Fetches a record, checks the filter condition, and computes its
+	/// distance to the query point.
+	async fn evaluate(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		tx: &Transaction,
+		rid: Arc<RecordId>,
+	) -> Result<Option<(f64, Arc<crate::catalog::Record>)>> {
+		let val = tx.get_record(self.ns, self.db, &rid.table, &rid.key, None).await?;
+		if val.data.is_nullish() {
+			return Ok(None);
+		}
+		let doc = CursorDoc {
+			rid: Some(rid),
+			ir: None,
+			doc: val.into(),
+			fields_computed: false,
+		};
+		let Some(col) = self.ix.cols.first() else {
+			return Ok(None);
+		};
+		let v = stk.run(|stk| col.compute(stk, ctx, opt, Some(&doc))).await.catch_return()?;
+		let mut geometries = vec![];
+		collect_geometries(&v, &mut geometries);
+		let Some(dist) =
+			geometries.into_iter().filter_map(|g| distance(&self.point, g)).min_by(f64::total_cmp)
+		else {
+			return Ok(None);
+		};
+		if let Some(cond) = &self.cond
+			&& !stk
+				.run(|stk| cond.0.compute(stk, ctx, opt, Some(&doc)))
+				.await
+				.catch_return()?
+				.is_truthy()
+		{
+			return Ok(None);
+		}
+		Ok(Some((dist, doc.doc.into_read_only())))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cell_codes_nest() {
+		let cell = Cell::from_coord(12, -0.1278, 51.5074);
+		let parent = Cell::from_coord(11, -0.1278, 51.5074);
+		assert_eq!(cell.code() >> 2, parent.code());
+		let (from, to) = parent.range(12);
+		assert!(from <= cell.code() && cell.code() < to);
+		assert_eq!(to - from, 4);
+	}
+
+	#[test]
+	fn coordinates_are_clamped() {
+		let min = Cell::from_coord(4, -500.0, -500.0);
+		let max = Cell::from_coord(4, 500.0, 500.0);
+		assert_eq!((min.x, min.y), (0, 0));
+		assert_eq!((max.x, max.y), (15, 15));
+		assert_eq!(Cell::from_coord(4, 180.0, 90.0), max);
+	}
+
+	#[test]
+	fn children_cover_parent() {
+		let parent = Cell::from_coord(8, 2.35, 48.85);
+		let (from, to) = parent.range(10);
+		let mut covered: Vec<(i64, i64)> = parent.children().iter().map(|c| c.range(10)).collect();
+		covered.sort_unstable();
+		assert_eq!(covered.first().unwrap().0, from);
+		assert_eq!(covered.last().unwrap().1, to);
+		assert!(covered.windows(2).all(|w| w[0].1 == w[1].0));
+	}
+
+	#[test]
+	fn point_is_indexed_in_one_cell() {
+		let g = Geometry::Point((-0.1278, 51.5074).into());
+		let cells = index_cells(12, &g);
+		assert_eq!(cells, vec![Cell::from_coord(12, -0.1278, 51.5074).code()]);
+	}
+
+	#[test]
+	fn large_geometry_overflows() {
+		let g = Geometry::Polygon(geo::Polygon::new(
+			vec![(-10.0, -10.0), (10.0, -10.0), (10.0, 10.0), (-10.0, 10.0), (-10.0, -10.0)].into(),
+			vec![],
+		));
+		assert_eq!(index_cells(12, &g), vec![OVERFLOW_CELL]);
+		assert_eq!(index_cells(2, &g).len(), 4);
+	}
+
+	#[test]
+	fn query_ranges_cover_indexed_cells() {
+		let area = Geometry::Polygon(geo::Polygon::new(
+			vec![(-1.0, 51.0), (1.0, 51.0), (1.0, 52.0), (-1.0, 52.0), (-1.0, 51.0)].into(),
+			vec![],
+		));
+		let ranges = query_ranges(12, &area);
+		assert_eq!(ranges[0], (OVERFLOW_CELL, OVERFLOW_CELL + 1));
+		assert!(ranges.windows(2).all(|w| w[0].1 < w[1].0));
+		for (lon, lat) in [(-0.1278, 51.5074), (-1.0, 51.0), (1.0, 52.0), (0.0, 51.99)] {
+			let code = Cell::from_coord(12, lon, lat).code();
+			assert!(ranges.iter().any(|(from, to)| *from <= code && code < *to), "{lon} {lat}");
+		}
+	}
+
+	#[test]
+	fn min_distance_is_a_lower_bound() {
+		let london = Point::new(-0.1278, 51.5074);
+		let paris = Point::new(2.3522, 48.8566);
+		let tokyo = Point::new(139.6917, 35.6895);
+		for level in [0, 3, 8, 12] {
+			for target in [paris, tokyo, london] {
+				let cell = Cell::from_coord(level, target.x(), target.y());
+				let d = Haversine.distance(london, target);
+				assert!(cell.min_distance(&london) <= d, "{level} {target:?}");
+			}
+		}
+		assert_eq!(Cell::from_coord(12, london.x(), london.y()).min_distance(&london), 0.0);
+		assert!(Cell::from_coord(12, tokyo.x(), tokyo.y()).min_distance(&london) > 4_000_000.0);
+	}
+
+	#[test]
+	fn min_distance_wraps_around_the_antimeridian() {
+		let p = Point::new(179.9, 0.0);
+		let cell = Cell::from_coord(12, -179.9, 0.0);
+		assert!(cell.min_distance(&p) < 50_000.0);
+	}
+}
@@ -126,7 +126,7 @@ impl DoublePriorityQueue {
 /// It provides an implementation so it can be used as a key in a BTreeMap or
 /// BTreeSet.
 #[derive(Debug, Clone, Copy)]
-pub(super) struct FloatKey(f64);
+pub(in crate::idx) struct FloatKey(f64);
 impl From<FloatKey> for f64 {
 	fn from(v: FloatKey) -> Self {
 		v.0
@@ -0,0 +1,287 @@
+//! Parsing and evaluation of the cron expressions used to schedule jobs.
+//!
+//! A schedule is made of five fields, separated by whitespace:
+//!
+//! ```text
+//! ┌──────────── minute (0 - 59)
+//! │ ┌────────── hour (0 - 23)
+//! │ │ ┌──────── day of the month (1 - 31)
+//! │ │ │ ┌────── month (1 - 12, or JAN - DEC)
+//! │ │ │ │ ┌──── day of the week (0 - 7, or SUN - SAT, where both 0 and 7 are Sunday)
+//! * * * * *
+//! ```
+//!
+//! Each field is either `*`, a value, a range of values (`1-5`), or a list of
+//! these (`1,15,30-35`), optionally followed by a step (`*/15`, `9-17/2`).
+//! The `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`, `@midnight`
+//! and `@hourly` shorthands are also supported. As with the traditional cron,
+//! when both the day of the month and the day of the week are restricted, a
+//! day matches if either of them matches. Schedules are evaluated in UTC.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+
+/// The number of years searched for the next run of a schedule, which covers
+/// the schedules running on February 29th.
+const SEARCH_YEARS: i32 = 8;
+
+const MONTHS: [&str; 12] =
+	["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A parsed cron expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Schedule {
+	minutes: u64,
+	hours: u64,
+	days: u64,
+	months: u64,
+	weekdays: u64,
+	/// Whether the day of the month field is unrestricted (starts with `*`)
+	any_day: bool,
+	/// Whether the day of the week field is unrestricted (starts with `*`)
+	any_weekday: bool,
+}
+
+/// The error returned when a cron expression is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ScheduleError(String);
+
+impl fmt::Display for ScheduleError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+impl FromStr for Schedule {
+	type Err = ScheduleError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let expanded = match s.to_ascii_lowercase().as_str() {
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			"@monthly" => "0 0 1 * *",
+			"@weekly" => "0 0 * * 0",
+			"@daily" | "@midnight" => "0 0 * * *",
+			"@hourly" => "0 * * * *",
+			_ if s.starts_with('@') => {
+				return Err(ScheduleError(format!("Unknown shorthand `{s}`")));
+			}
+			_ => s,
+		};
+		let fields: Vec<&str> = expanded.split_whitespace().collect();
+		let [minute, hour, day, month, weekday] = fields[..] else {
+			return Err(ScheduleError(format!(
+				"Expected 5 fields (minute, hour, day of month, month, day of week), found {}",
+				fields.len()
+			)));
+		};
+		// Sunday can be written as either 0 or 7
+		let mut weekdays = parse_field(weekday, "day of week", 0, 7, &WEEKDAYS)?;
+		if weekdays & (1 << 7) != 0 {
+			weekdays = (weekdays & !(1 << 7)) | 1;
+		}
+		Ok(Self {
+			minutes: parse_field(minute, "minute", 0, 59, &[])?,
+			hours: parse_field(hour, "hour", 0, 23, &[])?,
+			days: parse_field(day, "day of month", 1, 31, &[])?,
+			months: parse_field(month, "month", 1, 12, &MONTHS)?,
+			weekdays,
+			any_day: day.starts_with('*'),
+			any_weekday: weekday.starts_with('*'),
+		})
+	}
+}
+
+impl Schedule {
+	/// Returns the first time strictly after `after` at which the schedule
+	/// runs, or `None` if the schedule never runs (e.g. on February 30th).
+	pub(crate) fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+		// Start from the next whole minute
+		let mut next = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+		let limit = next.year() + SEARCH_YEARS;
+		while next.year() <= limit {
+			if !bit(self.months, next.month()) {
+				// Skip to the first day of the next month
+				let (year, month) = match next.month() {
+					12 => (next.year() + 1, 1),
+					m => (next.year(), m + 1),
+				};
+				next = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?.and_utc();
+				continue;
+			}
+			if !self.matches_day(&next) {
+				// Skip to the start of the next day
+				next = (next.date_naive() + Duration::days(1)).and_hms_opt(0, 0, 0)?.and_utc();
+				continue;
+			}
+			if !bit(self.hours, next.hour()) {
+				// Skip to the start of the next hour
+				next = next.with_minute(0)? + Duration::hours(1);
+				continue;
+			}
+			if !bit(self.minutes, next.minute()) {
+				next += Duration::minutes(1);
+				continue;
+			}
+			return Some(next);
+		}
+		None
+	}
+
+	fn matches_day(&self, date: &DateTime<Utc>) -> bool {
+		let day = bit(self.days, date.day());
+		let weekday = bit(self.weekdays, date.weekday().num_days_from_sunday());
+		match (self.any_day, self.any_weekday) {
+			(true, true) => true,
+			(true, false) => weekday,
+			(false, true) => day,
+			(false, false) => day || weekday,
+		}
+	}
+}
+
+fn bit(set: u64, value: u32) -> bool {
+	set & (1 << value) != 0
+}
+
+/// Parses a field of a cron expression into a set of values.
+fn parse_field(
+	field: &str,
+	name: &str,
+	min: u32,
+	max: u32,
+	names: &[&str],
+) -> Result<u64, ScheduleError> {
+	let mut set = 0;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => {
+				let step = step.parse::<u32>().ok().filter(|s| *s > 0).ok_or_else(|| {
+					ScheduleError(format!("Invalid step `{step}` in the {name} field"))
+				})?;
+				(range, Some(step))
+			}
+			None => (part, None),
+		};
+		let (beg, end) = if range == "*" {
+			(min, max)
+		} else if let Some((beg, end)) = range.split_once('-') {
+			(parse_value(beg, name, min, max, names)?, parse_value(end, name, min, max, names)?)
+		} else {
+			let value = parse_value(range, name, min, max, names)?;
+			// A single value with a step runs from that value to the maximum
+			match step {
+				Some(_) => (value, max),
+				None => (value, value),
+			}
+		};
+		if beg > end {
+			return Err(ScheduleError(format!("Invalid range `{range}` in the {name} field")));
+		}
+		for value in (beg..=end).step_by(step.unwrap_or(1) as usize) {
+			set |= 1 << value;
+		}
+	}
+	Ok(set)
+}
+
+/// Parses a single value of a field of a cron expression.
+fn parse_value(
+	value: &str,
+	name: &str,
+	min: u32,
+	max: u32,
+	names: &[&str],
+) -> Result<u32, ScheduleError> {
+	// Month and weekday names are numbered from the minimum of the field
+	if let Some(pos) = names.iter().position(|n| n.eq_ignore_ascii_case(value)) {
+		return Ok(min + pos as u32);
+	}
+	match value.parse::<u32>() {
+		Ok(v) if (min..=max).contains(&v) => Ok(v),
+		Ok(_) => Err(ScheduleError(format!(
+			"Value `{value}` is out of range in the {name} field, expected {min} to {max}"
+		))),
+		Err(_) => Err(ScheduleError(format!("Invalid value `{value}` in the {name} field"))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::TimeZone;
+
+	use super::*;
+
+	fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+	}
+
+	fn next(schedule: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+		schedule.parse::<Schedule>().unwrap().next_after(after)
+	}
+
+	#[test]
+	fn every_minute() {
+		assert_eq!(next("* * * * *", at(2024, 1, 1, 0, 0)), Some(at(2024, 1, 1, 0, 1)));
+		let after = at(2024, 1, 1, 0, 0) + Duration::seconds(30);
+		assert_eq!(next("* * * * *", after), Some(at(2024, 1, 1, 0, 1)));
+	}
+
+	#[test]
+	fn steps_and_lists() {
+		let after = at(2024, 1, 1, 10, 7);
+		assert_eq!(next("*/15 * * * *", after), Some(at(2024, 1, 1, 10, 15)));
+		assert_eq!(next("5,50 * * * *", after), Some(at(2024, 1, 1, 10, 50)));
+		assert_eq!(next("0 9-17/4 * * *", after), Some(at(2024, 1, 1, 13, 0)));
+		assert_eq!(next("30 2/12 * * *", after), Some(at(2024, 1, 1, 14, 30)));
+	}
+
+	#[test]
+	fn rolls_over() {
+		assert_eq!(next("0 0 * * *", at(2024, 12, 31, 23, 59)), Some(at(2025, 1, 1, 0, 0)));
+		assert_eq!(next("0 0 1 * *", at(2024, 1, 31, 12, 0)), Some(at(2024, 2, 1, 0, 0)));
+		assert_eq!(next("@yearly", at(2024, 6, 1, 0, 0)), Some(at(2025, 1, 1, 0, 0)));
+		assert_eq!(next("0 12 29 feb *", at(2024, 3, 1, 0, 0)), Some(at(2028, 2, 29, 12, 0)));
+	}
+
+	#[test]
+	fn weekdays() {
+		// 2024-01-01 is a Monday
+		let after = at(2024, 1, 1, 0, 0);
+		assert_eq!(next("0 0 * * SUN", after), Some(at(2024, 1, 7, 0, 0)));
+		assert_eq!(next("0 0 * * 7", after), Some(at(2024, 1, 7, 0, 0)));
+		assert_eq!(next("0 8 * * mon-fri", at(2024, 1, 5, 9, 0)), Some(at(2024, 1, 8, 8, 0)));
+		// Either the day of the month or the day of the week matches
+		assert_eq!(next("0 0 15 * 3", after), Some(at(2024, 1, 3, 0, 0)));
+		assert_eq!(next("0 0 15 * 3", at(2024, 1, 12, 0, 0)), Some(at(2024, 1, 15, 0, 0)));
+	}
+
+	#[test]
+	fn never() {
+		assert_eq!(next("0 0 30 2 *", at(2024, 1, 1, 0, 0)), None);
+	}
+
+	#[test]
+	fn invalid() {
+		for schedule in [
+			"",
+			"* * * *",
+			"* * * * * *",
+			"60 * * * *",
+			"* 24 * * *",
+			"* * 0 * *",
+			"* * * 13 *",
+			"* * * * 8",
+			"*/0 * * * *",
+			"5-1 * * * *",
+			"a * * * *",
+			"@sometimes",
+		] {
+			assert!(schedule.parse::<Schedule>().is_err(), "{schedule}");
+		}
+	}
+}
@@ -0,0 +1,282 @@
+//! Scheduled jobs, defined with `DEFINE JOB` and run by the job scheduler.
+mod cron;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reblessive::TreeStack;
+use reblessive::tree::Stk;
+use web_time::Instant;
+
+pub(crate) use self::cron::Schedule;
+use crate::catalog::providers::{DatabaseProvider, JobProvider, NamespaceProvider};
+use crate::catalog::{DatabaseId, JobDefinition, JobHistory, JobRun, NamespaceId};
+use crate::cnf::JOB_HISTORY_SIZE;
+use crate::ctx::FrozenContext;
+use crate::dbs::{Options, Session};
+use crate::err::Error;
+use crate::expr::FlowResultExt;
+use crate::expr::statements::info::InfoStructure;
+use crate::iam::{Level, Role};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
+use crate::val::{Datetime, Value};
+
+/// Parses the cron expression of the schedule of a job.
+pub(crate) fn parse_schedule(schedule: &str) -> Result<Schedule> {
+	schedule.parse().map_err(|e: cron::ScheduleError| Error::InvalidSchedule(e.to_string()).into())
+}
+
+/// Runs the body of a job, and returns the run to record in its history.
+pub(crate) async fn execute(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	jb: &JobDefinition,
+	manual: bool,
+) -> (Result<Value>, JobRun) {
+	let started = Datetime::now();
+	let timer = Instant::now();
+	let res = stk.run(|stk| jb.then.compute(stk, ctx, opt, None)).await.catch_return();
+	let run = JobRun {
+		started,
+		duration: timer.elapsed(),
+		manual,
+		error: res.as_ref().err().map(ToString::to_string),
+	};
+	(res, run)
+}
+
+/// Records a run of a job in its history.
+pub(crate) async fn record_run(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	jb: &str,
+	run: JobRun,
+) -> Result<()> {
+	let key = crate::key::database::jh::new(ns, db, jb);
+	let history = match txn.get(&key, None).await? {
+		Some(mut history) => {
+			history.record(run, *JOB_HISTORY_SIZE);
+			history
+		}
+		// This is the first run of the job
+		None => JobHistory {
+			runs: vec![run],
+		},
+	};
+	txn.set(&key, &history, None).await
+}
+
+/// Returns the structure of the jobs of a database, along with their history,
+/// as shown by `INFO FOR DB STRUCTURE`.
+pub(crate) async fn structure(txn: &Transaction, ns: NamespaceId, db: DatabaseId) -> Result<Value> {
+	let mut out = Vec::new();
+	for jb in txn.all_db_jobs(ns, db).await?.iter() {
+		let key = crate::key::database::jh::new(ns, db, &jb.name);
+		let history = match txn.get(&key, None).await? {
+			Some(history) => history.structure(),
+			// The job never ran
+			None => Value::from(Vec::<Value>::new()),
+		};
+		let mut value = jb.clone().structure();
+		if let Value::Object(obj) = &mut value {
+			obj.insert("history".to_string(), history);
+		}
+		out.push(value);
+	}
+	Ok(out.into())
+}
+
+/// Runs the jobs whose schedule is due.
+pub(crate) struct JobScheduler;
+
+impl JobScheduler {
+	/// Runs the jobs which are due, across all the namespaces and databases.
+	/// Returns the number of jobs which have been run.
+	///
+	/// A job is due once its schedule has elapsed since its last run, or since
+	/// it was defined if it never ran. Runs which were missed while no node
+	/// was holding the lease are caught up with a single run. Each job runs
+	/// in its own transaction, as an editor of its database.
+	pub(crate) async fn process_due_jobs(
+		ds: &Datastore,
+		lh: Option<&LeaseHandler>,
+	) -> Result<usize> {
+		// Collect the due jobs
+		let due = {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+			let res = catch!(tx, Self::due_jobs(&tx, Utc::now()).await);
+			tx.cancel().await?;
+			res
+		};
+		let mut count = 0;
+		let mut stack = TreeStack::new();
+		for (ns, db, jb) in due {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			match stack.enter(|stk| Self::run(stk, ds, &ns, &db, &jb)).finish().await {
+				Ok(()) => count += 1,
+				Err(e) => {
+					error!("Unexpected error while running the job '{jb}' in {ns}/{db}. Error: {e}")
+				}
+			}
+		}
+		Ok(count)
+	}
+
+	/// Returns the namespace, database and name of the jobs which are due.
+	async fn due_jobs(
+		tx: &Transaction,
+		now: DateTime<Utc>,
+	) -> Result<Vec<(String, String, String)>> {
+		let mut due = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			for db in tx.all_db(ns.namespace_id).await?.iter() {
+				for jb in tx.all_db_jobs(ns.namespace_id, db.database_id).await?.iter() {
+					let schedule = match parse_schedule(&jb.schedule) {
+						Ok(schedule) => schedule,
+						Err(e) => {
+							warn!("Skipping the job '{}' in {}/{}: {e}", jb.name, ns.name, db.name);
+							continue;
+						}
+					};
+					let key =
+						crate::key::database::jh::new(ns.namespace_id, db.database_id, &jb.name);
+					let history: Option<JobHistory> = tx.get(&key, None).await?;
+					let since =
+						history.as_ref().and_then(JobHistory::last_run).unwrap_or(&jb.created);
+					if schedule.next_after(since.0).is_some_and(|next| next <= now) {
+						due.push((ns.name.clone(), db.name.clone(), jb.name.clone()));
+					}
+				}
+			}
+		}
+		Ok(due)
+	}
+
+	/// Runs a job, and records the run in its history.
+	async fn run(stk: &mut Stk, ds: &Datastore, ns: &str, db: &str, jb: &str) -> Result<()> {
+		// Setup the context and the options of the run
+		let mut ctx = ds.setup_ctx()?;
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		ctx.set_transaction(Arc::new(tx));
+		let ctx = ctx.freeze();
+		let tx = ctx.tx();
+		let sess =
+			Session::for_level(Level::Database(ns.to_string(), db.to_string()), Role::Editor);
+		let opt = ds.setup_options(&sess);
+		// The job may have been removed since it was found to be due
+		let (ns, db) = catch!(tx, ctx.expect_ns_db_ids(&opt).await);
+		let Some(jb) = catch!(tx, tx.get_db_job(ns, db, jb).await) else {
+			return tx.cancel().await;
+		};
+		// Run the job, recording a successful run in the same transaction
+		let (res, mut run) = execute(stk, &ctx, &opt, &jb, false).await;
+		match res {
+			Ok(_) => {
+				catch!(tx, record_run(&tx, ns, db, &jb.name, run.clone()).await);
+				match tx.commit().await {
+					Ok(()) => return Ok(()),
+					Err(e) => run.error = Some(e.to_string()),
+				}
+			}
+			Err(_) => tx.cancel().await?,
+		}
+		// Record a failed run in its own transaction
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		catch!(tx, record_run(&tx, ns, db, &jb.name, run).await);
+		tx.commit().await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::Duration;
+	use surrealdb_types::ToSql;
+
+	use super::*;
+
+	async fn due(ds: &Datastore, now: DateTime<Utc>) -> Vec<String> {
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let due = JobScheduler::due_jobs(&tx, now).await.unwrap();
+		tx.cancel().await.unwrap();
+		due.into_iter().map(|(_, _, jb)| jb).collect()
+	}
+
+	async fn run(ds: &Datastore, jb: &str) {
+		let mut stack = TreeStack::new();
+		stack.enter(|stk| JobScheduler::run(stk, ds, "test", "test", jb)).finish().await.unwrap();
+	}
+
+	async fn history(ds: &Datastore, jb: &str) -> JobHistory {
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let (ns, db) = {
+			let db = tx.expect_db_by_name("test", "test").await.unwrap();
+			(db.namespace_id, db.database_id)
+		};
+		let key = crate::key::database::jh::new(ns, db, jb);
+		let history = tx.get(&key, None).await.unwrap().unwrap_or_default();
+		tx.cancel().await.unwrap();
+		history
+	}
+
+	#[tokio::test]
+	async fn due_once_scheduled() {
+		let ds = Datastore::new_test("memory").await;
+		let before = Utc::now();
+		ds.test_query("DEFINE JOB tick SCHEDULE '* * * * *' THEN { CREATE log }").await;
+		// A job is not due before its schedule elapsed
+		assert!(due(&ds, before).await.is_empty());
+		assert_eq!(due(&ds, Utc::now() + Duration::minutes(2)).await, vec!["tick".to_string()]);
+		// Running the job records the run
+		run(&ds, "tick").await;
+		assert_eq!(ds.test_query("RETURN count(SELECT * FROM log)").await.to_sql(), "1");
+		let history = history(&ds, "tick").await;
+		assert_eq!(history.runs.len(), 1);
+		assert!(!history.runs[0].manual);
+		assert_eq!(history.runs[0].error, None);
+		// The schedule then starts from the last run
+		let last = history.last_run().unwrap().0;
+		assert!(due(&ds, last).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn failed_runs_are_recorded() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE JOB fail SCHEDULE '@daily' THEN { CREATE log; THROW 'boom' }").await;
+		run(&ds, "fail").await;
+		// The changes of a failed run are rolled back
+		assert_eq!(ds.test_query("RETURN count(SELECT * FROM log)").await.to_sql(), "0");
+		let history = history(&ds, "fail").await;
+		assert_eq!(history.runs.len(), 1);
+		assert!(history.runs[0].error.as_deref().is_some_and(|e| e.contains("boom")));
+	}
+
+	#[tokio::test]
+	async fn jobs_run_as_database_editors() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE JOB escalate SCHEDULE '@daily' THEN { DEFINE USER admin ON ROOT PASSWORD 'secret' ROLES OWNER }").await;
+		run(&ds, "escalate").await;
+		// A job can't act beyond the database it is defined in
+		let history = history(&ds, "escalate").await;
+		assert_eq!(history.runs.len(), 1);
+		assert!(history.runs[0].error.is_some());
+	}
+
+	#[tokio::test]
+	async fn removed_jobs_are_skipped() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE JOB tick SCHEDULE '@daily' THEN { CREATE log }").await;
+		ds.test_query("REMOVE JOB tick").await;
+		run(&ds, "tick").await;
+		assert_eq!(ds.test_query("RETURN count(SELECT * FROM log)").await.to_sql(), "0");
+		assert!(history(&ds, "tick").await.runs.is_empty());
+	}
+}
@@ -37,6 +37,22 @@ pub enum Category {
 	IndexCompaction,
 	/// crate::key::root::eq                 /!eq{ns}{db}{tb}{ev}{ts}{nid}
 	EventQueue,
+	/// crate::key::root::ex                 /!ex{ts}{ns}{db}{tb}{id}
+	ExpiryQueue,
+	/// crate::key::root::eb                 /!eb{ns}{db}{tb}
+	ExpiryBackfill,
+	/// crate::key::root::au                 /!au{ts}{id}
+	AuditLog,
+	/// crate::key::root::ri                 /!ri{ts}{id}
+	ResultInvalidation,
+	/// crate::key::root::fu                 /!fu{id}
+	FileUpload,
+	/// crate::key::root::fu                 /!fp{id}{part}
+	FileUploadPart,
+	/// crate::key::root::rv                 /!rv
+	RestoredVersionstamp,
+	/// crate::key::root::id                 /!id
+	DatastoreId,
 	///
 	/// ------------------------------
 	///
@@ -91,12 +107,26 @@ pub enum Category {
 	DatabaseAnalyzer,
 	/// crate::key::database::bu             /*{ns}*{db}!bu{bu}
 	DatabaseBucket,
+	/// crate::key::database::cs             /*{ns}*{db}!cs{cs}
+	DatabaseConsumer,
+	/// crate::key::database::co             /*{ns}*{db}!co{cs}
+	DatabaseConsumerOffset,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
+	/// crate::key::database::jb             /*{ns}*{db}!jb{jb}
+	DatabaseJob,
+	/// crate::key::database::jh             /*{ns}*{db}!jh{jb}
+	DatabaseJobHistory,
+	/// crate::key::database::ky             /*{ns}*{db}!ky{ky}
+	DatabaseKey,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::sk             /*{ns}*{db}!sk{sk}
+	DatabaseSink,
+	/// crate::key::database::sp             /*{ns}*{db}!sp{sk}
+	DatabaseSinkPosition,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -116,6 +146,8 @@ pub enum Category {
 	TableRoot,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
+	/// crate::key::table::ex                /*{ns}*{db}*{tb}!ex{id}
+	TableRecordExpiry,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 	TableField,
 	/// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
@@ -236,9 +268,16 @@ impl Display for Category {
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
 			Self::DatabaseBucket => "DatabaseBucket",
+			Self::DatabaseConsumer => "DatabaseConsumer",
+			Self::DatabaseConsumerOffset => "DatabaseConsumerOffset",
 			Self::DatabaseFunction => "DatabaseFunction",
+			Self::DatabaseJob => "DatabaseJob",
+			Self::DatabaseJobHistory => "DatabaseJobHistory",
+			Self::DatabaseKey => "DatabaseKey",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseSink => "DatabaseSink",
+			Self::DatabaseSinkPosition => "DatabaseSinkPosition",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifierBatch => "DatabaseTableIdentifierBatch",
 			Self::DatabaseTableIdentifierState => "DatabaseTableIdentifierState",
@@ -249,6 +288,7 @@ impl Display for Category {
 			Self::DatabaseConfig => "DatabaseConfig",
 			Self::TableRoot => "TableRoot",
 			Self::TableEvent => "TableEvent",
+			Self::TableRecordExpiry => "TableRecordExpiry",
 			Self::TableField => "TableField",
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
@@ -287,6 +327,14 @@ impl Display for Category {
 			Self::IndexCompaction => "IndexCompaction",
 			Self::IndexCountState => "IndexCountState",
 			Self::EventQueue => "EventQueue",
+			Self::ExpiryQueue => "ExpiryQueue",
+			Self::ExpiryBackfill => "ExpiryBackfill",
+			Self::AuditLog => "AuditLog",
+			Self::ResultInvalidation => "ResultInvalidation",
+			Self::FileUpload => "FileUpload",
+			Self::FileUploadPart => "FileUploadPart",
+			Self::RestoredVersionstamp => "RestoredVersionstamp",
+			Self::DatastoreId => "DatastoreId",
 			Self::TableIndexIdentifierBatch => "TableIndexIdentifierBatch",
 			Self::TableIndexIdentifierState => "TableIndexIdentifierState",
 		};
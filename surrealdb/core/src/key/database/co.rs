@@ -0,0 +1,62 @@
+//! Stores the acknowledged offset of a DEFINE CONSUMER definition
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerOffset, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct ConsumerOffsetKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub cs: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(ConsumerOffsetKey<'_> => ConsumerOffset);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, cs: &str) -> ConsumerOffsetKey<'_> {
+	ConsumerOffsetKey::new(ns, db, cs)
+}
+
+impl Categorise for ConsumerOffsetKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseConsumerOffset
+	}
+}
+
+impl<'a> ConsumerOffsetKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, cs: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'c', // c
+			_e: b'o', // o
+			cs: Cow::Borrowed(cs),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = ConsumerOffsetKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = ConsumerOffsetKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cotest\0");
+	}
+}
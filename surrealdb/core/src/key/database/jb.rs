@@ -0,0 +1,86 @@
+//! Stores a DEFINE JOB definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, JobDefinition, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct JobKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub jb: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(JobKey<'_> => JobDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, jb: &str) -> JobKey<'_> {
+	JobKey::new(ns, db, jb)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!jb\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!jb\xff");
+	Ok(k)
+}
+
+impl Categorise for JobKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseJob
+	}
+}
+
+impl<'a> JobKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, jb: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'j', // j
+			_e: b'b', // b
+			jb: Cow::Borrowed(jb),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = JobKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = JobKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!jbtest\0");
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!jb\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!jb\xff");
+	}
+}
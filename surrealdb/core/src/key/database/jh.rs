@@ -0,0 +1,62 @@
+//! Stores the run history of a DEFINE JOB definition
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, JobHistory, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct JobHistoryKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub jb: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(JobHistoryKey<'_> => JobHistory);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, jb: &str) -> JobHistoryKey<'_> {
+	JobHistoryKey::new(ns, db, jb)
+}
+
+impl Categorise for JobHistoryKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseJobHistory
+	}
+}
+
+impl<'a> JobHistoryKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, jb: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'j', // j
+			_e: b'h', // h
+			jb: Cow::Borrowed(jb),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = JobHistoryKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = JobHistoryKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!jhtest\0");
+	}
+}
@@ -0,0 +1,86 @@
+//! Stores a DEFINE KEY definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, KeyDefinition, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Ky<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ky: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Ky<'_> => KeyDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, ky: &str) -> Ky<'_> {
+	Ky::new(ns, db, ky)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!ky\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!ky\xff");
+	Ok(k)
+}
+
+impl Categorise for Ky<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseKey
+	}
+}
+
+impl<'a> Ky<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, ky: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b'k', // k
+			_e: b'y', // y
+			ky: Cow::Borrowed(ky),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = Ky::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = Ky::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!kytest\0");
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!ky\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!ky\xff");
+	}
+}
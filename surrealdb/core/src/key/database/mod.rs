@@ -5,10 +5,17 @@ pub mod ap;
 pub mod az;
 pub mod bu;
 pub mod cg;
+pub mod co;
+pub mod cs;
 pub mod fc;
+pub mod jb;
+pub mod jh;
+pub mod ky;
 pub mod md;
 pub mod ml;
 pub mod pa;
+pub mod sk;
+pub mod sp;
 pub mod sq;
 pub mod tb;
 pub mod th;
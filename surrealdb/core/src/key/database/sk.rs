@@ -0,0 +1,86 @@
+//! Stores a DEFINE SINK definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, SinkDefinition};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct SinkKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub sk: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(SinkKey<'_> => SinkDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, sk: &str) -> SinkKey<'_> {
+	SinkKey::new(ns, db, sk)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!sk\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!sk\xff");
+	Ok(k)
+}
+
+impl Categorise for SinkKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseSink
+	}
+}
+
+impl<'a> SinkKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, sk: &'a str) -> Self {
+		Self {
+			__: b'/', // /
+			_a: b'*', // *
+			ns,
+			_b: b'*', // *
+			db,
+			_c: b'!', // !
+			_d: b's', // s
+			_e: b'k', // k
+			sk: Cow::Borrowed(sk),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = SinkKey::new(NamespaceId(1), DatabaseId(2), "test");
+		let enc = SinkKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!sktest\0");
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!sk\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!sk\xff");
+	}
+}
@@ -61,6 +61,23 @@ impl<'a> TableKey<'a> {
 			tb: Cow::Borrowed(tb),
 		}
 	}
+
+	/// Checks whether a key is the key of a table definition
+	#[cfg_attr(not(any(feature = "kv-rocksdb", feature = "kv-tikv")), allow(dead_code))]
+	pub(crate) fn is_table_key(k: &[u8]) -> bool {
+		matches!(
+			storekey::decode_borrow(k),
+			Ok(TableKey {
+				__: b'/',
+				_a: b'*',
+				_b: b'*',
+				_c: b'!',
+				_d: b't',
+				_e: b'b',
+				..
+			})
+		)
+	}
 }
 
 #[cfg(test)]
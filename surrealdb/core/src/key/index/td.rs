@@ -16,6 +16,7 @@
 
 use std::borrow::Cow;
 
+use anyhow::Result;
 use roaring::RoaringTreemap;
 use storekey::{BorrowDecode, Encode};
 
@@ -23,7 +24,7 @@ use crate::catalog::{DatabaseId, IndexId, NamespaceId};
 use crate::idx::ft::fulltext::TermDocument;
 use crate::idx::seqdocids::DocId;
 use crate::key::category::{Categorise, Category};
-use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -146,10 +147,94 @@ impl<'a> Td<'a> {
 	}
 }
 
+impl Td<'_> {
+	/// Creates a key range for querying the terms of an index starting with a
+	/// prefix
+	///
+	/// The range contains both the root key and the document keys of each
+	/// term. It's used to walk the dictionary of terms for prefix and fuzzy
+	/// searches.
+	pub(crate) fn terms_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: IndexId,
+		prefix: &str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = TdTermsPrefix::new(ns, db, tb, ix).encode_key()?;
+		beg.extend_from_slice(prefix.as_bytes());
+		let mut end = beg.clone();
+		end.push(255);
+		Ok((beg, end))
+	}
+
+	/// Returns the first key following all the keys of a term
+	pub(crate) fn next_term_key(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: IndexId,
+		term: &str,
+	) -> Result<Vec<u8>> {
+		let mut key = TdRoot::new(ns, db, tb, ix, term).encode_key()?;
+		// The term is terminated by a null byte, which is followed by the
+		// document ids of the term
+		if let Some(b) = key.last_mut() {
+			*b = 1;
+		}
+		Ok(key)
+	}
+
+	/// Decodes the term of a root key or of a document key
+	pub(crate) fn decode_term(k: &[u8]) -> Result<String> {
+		match storekey::decode_borrow::<TdRoot>(k) {
+			Ok(root) => Ok(root.term.into_owned()),
+			Err(_) => Ok(storekey::decode_borrow::<Td>(k)?.term.into_owned()),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct TdTermsPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+}
+
+impl_kv_key_storekey!(TdTermsPrefix<'_> => String);
+
+impl<'a> TdTermsPrefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b't',
+			_g: b'd',
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::kvs::KVKey;
 
 	#[test]
 	fn root() {
@@ -169,4 +254,30 @@ mod tests {
 			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdterm\0\0\0\0\0\0\0\0\x81"
 		);
 	}
+
+	#[test]
+	fn terms_range() {
+		let tb = TableName::from("testtb");
+		let (beg, end) =
+			Td::terms_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "te").unwrap();
+		assert_eq!(beg, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte");
+		assert_eq!(end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte\xff");
+	}
+
+	#[test]
+	fn decode_term() {
+		let tb = TableName::from("testtb");
+		let root = TdRoot::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term");
+		let key = Td::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term", 129);
+		assert_eq!(Td::decode_term(&root.encode_key().unwrap()).unwrap(), "term");
+		assert_eq!(Td::decode_term(&key.encode_key().unwrap()).unwrap(), "term");
+		let next =
+			Td::next_term_key(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term").unwrap();
+		assert!(next > key.encode_key().unwrap());
+		assert!(
+			next < TdRoot::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "terms")
+				.encode_key()
+				.unwrap()
+		);
+	}
 }
@@ -19,6 +19,8 @@
 //! - {db_name}: &str
 //!
 //! crate::key::version                  !v -> Version
+//! crate::kvs::history                  !h{key}{ts} (rocksdb and tikv when versioned)
+//! crate::kvs::encryption               !e (rocksdb and surrealkv when encrypted)
 //!
 //! crate::key::root::all                /
 //! crate::key::root::ac                 /!ac{ac}
@@ -28,6 +30,14 @@
 //! crate::key::root::us                 /!us{us}
 //! crate::key::root::tl                 /!tl{tl}
 //! crate::key::root::cg                 /!cg{ty}
+//! crate::key::root::ex                 /!ex{ts}{ns_name}{db_name}{tb_name}{id}
+//! crate::key::root::eb                 /!eb{ns_name}{db_name}{tb_name} -> ExpiryBackfillState
+//! crate::key::root::au                 /!au{ts}{id}
+//! crate::key::root::ri                 /!ri{ts}{id}
+//! crate::key::root::fu                 /!fu{id} -> UploadRecord
+//! crate::key::root::fu                 /!fp{id}{part}
+//! crate::key::root::rv                 /!rv
+//! crate::key::root::id                 /!id
 //!
 //! crate::key::node::all                /${nd}
 //! crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
@@ -49,10 +59,17 @@
 //! crate::key::database::ac             /*{ns}*{db}!ac{ac_name}
 //! crate::key::database::az             /*{ns}*{db}!az{az_name}
 //! crate::key::database::bu             /*{ns}*{db}!bu{bu_name}
+//! crate::key::database::co             /*{ns}*{db}!co{cs_name} -> ConsumerOffset
+//! crate::key::database::cs             /*{ns}*{db}!cs{cs_name} -> ConsumerDefinition
 //! crate::key::database::fc             /*{ns}*{db}!fn{fc_name}
+//! crate::key::database::jb             /*{ns}*{db}!jb{jb_name} -> JobDefinition
+//! crate::key::database::jh             /*{ns}*{db}!jh{jb_name} -> JobHistory
+//! crate::key::database::ky             /*{ns}*{db}!ky{ky_name} -> KeyDefinition
 //! crate::key::database::md             /*{ns}*{db}!md{md_name} -> ModuleDefinition
 //! crate::key::database::ml             /*{ns}*{db}!ml{ml_name}{vn}
 //! crate::key::database::pa             /*{ns}*{db}!pa{pa_name}
+//! crate::key::database::sk             /*{ns}*{db}!sk{sk_name} -> SinkDefinition
+//! crate::key::database::sp             /*{ns}*{db}!sp{sk_name} -> SinkPosition
 //! crate::key::database::sq             /*{ns}*{db}!sq{sq_name}
 //! crate::key::database::tb             /*{ns}*{db}!tb{tb_name} -> TableDefinition
 //! crate::key::database::ti             /+{ns}*{db}!ti
@@ -66,6 +83,7 @@
 //!
 //! crate::key::table::all               /*{ns}*{db}*{tb_name}
 //! crate::key::table::ev                /*{ns}*{db}*{tb_name}!ev{ev}
+//! crate::key::table::ex                /*{ns}*{db}*{tb_name}!ex{id} -> ts
 //! crate::key::table::fd                /*{ns}*{db}*{tb_name}!fd{fd}
 //! crate::key::table::ft                /*{ns}*{db}*{tb_name}!ft{ft}
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!il{ix} -> ix_name
@@ -70,6 +70,22 @@ impl<'a> RecordKey<'a> {
 	pub fn decode_key(k: &[u8]) -> Result<RecordKey<'_>> {
 		Ok(storekey::decode_borrow(k)?)
 	}
+
+	/// Checks whether a key is the key of a record document
+	#[cfg_attr(not(any(feature = "kv-rocksdb", feature = "kv-tikv")), allow(dead_code))]
+	pub(crate) fn is_record_key(k: &[u8]) -> bool {
+		matches!(
+			Self::decode_key(k),
+			Ok(RecordKey {
+				__: b'/',
+				_a: b'*',
+				_b: b'*',
+				_c: b'*',
+				_d: b'*',
+				..
+			})
+		)
+	}
 }
 
 #[cfg(test)]
@@ -89,6 +105,13 @@ mod tests {
 		);
 		let enc = RecordKey::encode_key(&val).unwrap();
 		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0*\x03testid\0");
+		assert!(RecordKey::is_record_key(&enc));
+		// Other keys of the table are not record keys
+		let tb = crate::key::table::all::new(NamespaceId(1), DatabaseId(2), &tb);
+		let mut other = tb.encode_key().unwrap();
+		other.extend_from_slice(b"!fdname\0");
+		assert!(!RecordKey::is_record_key(&other));
+		assert!(!RecordKey::is_record_key(b"/!fu"));
 	}
 	#[test]
 	fn key_complex() {
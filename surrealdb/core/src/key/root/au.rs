@@ -0,0 +1,90 @@
+//! Audit Log
+//!
+//! This module defines the key structure used for the entries of the audit
+//! log, when the audit log is written to the datastore. The keys are ordered
+//! by the time of the audited event, so that the entries since a given time
+//! can be read with a single range scan.
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::audit::AuditEvent;
+use crate::kvs::impl_kv_key_storekey;
+
+/// Represents an entry in the audit log
+///
+/// The identifier only ensures that events which happened at the same time
+/// are all kept.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct AuditEntry {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	/// Timestamp (nanoseconds since the UNIX epoch) of the audited event
+	pub ts: u64,
+	pub id: Uuid,
+}
+
+impl_kv_key_storekey!(AuditEntry => AuditEvent);
+
+impl Categorise for AuditEntry {
+	fn categorise(&self) -> Category {
+		Category::AuditLog
+	}
+}
+
+impl AuditEntry {
+	pub(crate) fn new(ts: u64, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'a',
+			_c: b'u',
+			ts,
+			id,
+		}
+	}
+
+	/// Returns the range of the entries recorded since the given timestamp
+	pub(crate) fn range(since: u64) -> (Vec<u8>, Vec<u8>) {
+		let mut beg = b"/!au".to_vec();
+		beg.extend_from_slice(&since.to_be_bytes());
+		(beg, b"/!au\xff".to_vec())
+	}
+
+	/// Returns the range of the entries recorded before the given timestamp
+	pub(crate) fn range_until(until: u64) -> (Vec<u8>, Vec<u8>) {
+		let mut end = b"/!au".to_vec();
+		end.extend_from_slice(&until.to_be_bytes());
+		(b"/!au".to_vec(), end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = AuditEntry::new(1, Uuid::nil());
+		let enc = AuditEntry::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!au\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+	}
+
+	#[test]
+	fn range() {
+		let (beg, end) = AuditEntry::range(2);
+		let before = AuditEntry::new(1, Uuid::from_u128(u128::MAX)).encode_key().unwrap();
+		let after = AuditEntry::new(2, Uuid::nil()).encode_key().unwrap();
+		assert!(before < beg);
+		assert!(beg <= after);
+		assert!(after < end);
+		let (beg, end) = AuditEntry::range_until(2);
+		assert!(beg <= before);
+		assert!(before < end);
+		assert!(end <= after);
+	}
+}
@@ -0,0 +1,81 @@
+//! Record Expiry Backfill
+//!
+//! This module defines the key structure used to schedule the expiry of the
+//! existing records of a table. When the `TTL` of a table is defined or
+//! changed, an entry is stored for the table, and the record expiry task
+//! queues the expiry of its records in batches, recording its progress in the
+//! entry, until every record was queued.
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::doc::ExpiryBackfillState;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::TableName;
+
+/// Represents the records of a table whose expiry still has to be queued
+///
+/// The namespace and the database are stored by name, like the entries of
+/// the expiry queue ([`crate::key::root::ex`]).
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct ExpiryBackfill<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: Cow<'a, str>,
+	pub db: Cow<'a, str>,
+	pub tb: Cow<'a, TableName>,
+}
+
+impl_kv_key_storekey!(ExpiryBackfill<'_> => ExpiryBackfillState);
+
+impl Categorise for ExpiryBackfill<'_> {
+	fn categorise(&self) -> Category {
+		Category::ExpiryBackfill
+	}
+}
+
+impl<'a> ExpiryBackfill<'a> {
+	pub(crate) fn new(ns: &'a str, db: &'a str, tb: &'a TableName) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'b',
+			ns: Cow::Borrowed(ns),
+			db: Cow::Borrowed(db),
+			tb: Cow::Borrowed(tb),
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<ExpiryBackfill<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of the tables whose records are being scheduled
+	pub(crate) fn range() -> (Vec<u8>, Vec<u8>) {
+		(b"/!eb".to_vec(), b"/!eb\xff".to_vec())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = ExpiryBackfill::new("testns", "testdb", &tb);
+		let enc = ExpiryBackfill::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!ebtestns\0testdb\0testtb\0");
+		let dec = ExpiryBackfill::decode_key(&enc).unwrap();
+		assert_eq!(dec, val);
+		let (beg, end) = ExpiryBackfill::range();
+		assert!(beg < enc && enc < end);
+	}
+}
@@ -0,0 +1,108 @@
+//! Record Expiry Queue
+//!
+//! This module defines the key structure used for the record expiry queue.
+//! When a record of a table defined with a `TTL` is written, an entry is queued
+//! with the time when the record expires. The keys are ordered by expiry time,
+//! so the record expiry task only scans the entries which are due, instead of
+//! scanning the tables.
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::{RecordIdKey, TableName};
+
+/// Represents an entry in the record expiry queue
+///
+/// The namespace and the database are stored by name, so that the record can
+/// be deleted with the options of a session using them. The entry is only
+/// acted upon if it matches the expiry stored with the record
+/// ([`crate::key::table::ex`]), which discards the entries of records which
+/// have been updated or deleted since.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct ExpiryQueue<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	/// Timestamp (nanoseconds since the UNIX epoch) when the record expires
+	pub ts: u64,
+	pub ns: Cow<'a, str>,
+	pub db: Cow<'a, str>,
+	pub tb: Cow<'a, TableName>,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(ExpiryQueue<'_> => ());
+
+impl Categorise for ExpiryQueue<'_> {
+	fn categorise(&self) -> Category {
+		Category::ExpiryQueue
+	}
+}
+
+impl<'a> ExpiryQueue<'a> {
+	pub(crate) fn new(
+		ts: u64,
+		ns: &'a str,
+		db: &'a str,
+		tb: &'a TableName,
+		id: RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'x',
+			ts,
+			ns: Cow::Borrowed(ns),
+			db: Cow::Borrowed(db),
+			tb: Cow::Borrowed(tb),
+			id,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<ExpiryQueue<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of the entries expiring before the given timestamp
+	pub(crate) fn range(until: u64) -> (Vec<u8>, Vec<u8>) {
+		let mut end = b"/!ex".to_vec();
+		end.extend_from_slice(&until.to_be_bytes());
+		(b"/!ex".to_vec(), end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val =
+			ExpiryQueue::new(1, "testns", "testdb", &tb, RecordIdKey::from("testid".to_owned()));
+		let enc = ExpiryQueue::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!ex\0\0\0\0\0\0\0\x01testns\0testdb\0testtb\0\x03testid\0");
+		let dec = ExpiryQueue::decode_key(&enc).unwrap();
+		assert_eq!(dec, val);
+	}
+
+	#[test]
+	fn range() {
+		let tb = TableName::from("testtb");
+		let (beg, end) = ExpiryQueue::range(2);
+		let due =
+			ExpiryQueue::new(1, "testns", "testdb", &tb, RecordIdKey::from("testid".to_owned()));
+		let later =
+			ExpiryQueue::new(2, "testns", "testdb", &tb, RecordIdKey::from("testid".to_owned()));
+		assert!(beg < due.encode_key().unwrap());
+		assert!(due.encode_key().unwrap() < end);
+		assert!(later.encode_key().unwrap() > end);
+	}
+}
@@ -0,0 +1,126 @@
+//! File uploads
+//!
+//! This module defines the key structure used for the multipart file uploads
+//! which are in progress. The state of an upload, and the data which has been
+//! written to it, are stored in the datastore, so that an upload can be
+//! continued from any node of the cluster, and survives restarts.
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::buc::upload::UploadRecord;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+/// Represents the state of a multipart file upload
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct FileUpload {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub id: Uuid,
+}
+
+impl_kv_key_storekey!(FileUpload => UploadRecord);
+
+impl Categorise for FileUpload {
+	fn categorise(&self) -> Category {
+		Category::FileUpload
+	}
+}
+
+impl FileUpload {
+	pub(crate) fn new(id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'f',
+			_c: b'u',
+			id,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> anyhow::Result<FileUpload> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of all of the file uploads
+	pub(crate) fn range() -> (Vec<u8>, Vec<u8>) {
+		let mut end = b"/!fu".to_vec();
+		end.extend_from_slice(&[0xff; 17]);
+		(b"/!fu".to_vec(), end)
+	}
+}
+
+/// Represents a chunk of the data of a multipart file upload
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct FileUploadPart {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub id: Uuid,
+	pub part: u32,
+}
+
+impl_kv_key_storekey!(FileUploadPart => Vec<u8>);
+
+impl Categorise for FileUploadPart {
+	fn categorise(&self) -> Category {
+		Category::FileUploadPart
+	}
+}
+
+impl FileUploadPart {
+	pub(crate) fn new(id: Uuid, part: u32) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'f',
+			_c: b'p',
+			id,
+			part,
+		}
+	}
+
+	/// Returns the range of the chunks of a file upload
+	pub(crate) fn range(id: Uuid) -> (Vec<u8>, Vec<u8>) {
+		let mut beg = b"/!fp".to_vec();
+		beg.extend_from_slice(id.as_bytes());
+		let mut end = beg.clone();
+		end.extend_from_slice(&[0xff; 5]);
+		(beg, end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = FileUpload::new(Uuid::nil());
+		let enc = FileUpload::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!fu\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+	}
+
+	#[test]
+	fn part() {
+		let val = FileUploadPart::new(Uuid::nil(), 1);
+		let enc = FileUploadPart::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!fp\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01");
+	}
+
+	#[test]
+	fn range() {
+		let (beg, end) = FileUploadPart::range(Uuid::nil());
+		let first = FileUploadPart::new(Uuid::nil(), 0).encode_key().unwrap();
+		let last = FileUploadPart::new(Uuid::nil(), u32::MAX).encode_key().unwrap();
+		let other = FileUploadPart::new(Uuid::from_u128(1), 0).encode_key().unwrap();
+		assert!(beg <= first && last < end);
+		assert!(other >= end);
+	}
+}
@@ -0,0 +1,58 @@
+//! Stores the identifier of the datastore which backups are taken from
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+/// Key structure for storing the identifier of the datastore.
+///
+/// The identifier is generated by the first backup of a datastore, and is
+/// recorded in the header of every backup archive. It is restored along with
+/// a full backup, so that the archives of a different datastore can not be
+/// applied onto the restored datastore.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct DatastoreId {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+impl_kv_key_storekey!(DatastoreId => Uuid);
+
+impl Default for DatastoreId {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Categorise for DatastoreId {
+	fn categorise(&self) -> Category {
+		Category::DatastoreId
+	}
+}
+
+impl DatastoreId {
+	pub fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'i',
+			_c: b'd',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = DatastoreId::new();
+		let enc = DatastoreId::encode_key(&val).unwrap();
+		assert_eq!(&enc, b"/!id");
+	}
+}
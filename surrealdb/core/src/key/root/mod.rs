@@ -1,12 +1,19 @@
 pub mod ac;
 pub mod access;
 pub mod all;
+pub mod au;
+pub mod eb;
 pub mod eq;
+pub mod ex;
+pub mod fu;
 pub mod ic;
+pub mod id;
 pub mod nd;
 pub mod nh;
 pub mod ni;
 pub mod ns;
+pub mod ri;
 pub mod root_config;
+pub mod rv;
 pub mod tl;
 pub mod us;
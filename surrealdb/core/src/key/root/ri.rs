@@ -0,0 +1,101 @@
+//! Result Invalidations
+//!
+//! This module defines the key structure used to publish the tables written
+//! by a transaction to the other nodes of a cluster, so that each node can
+//! invalidate the cached results which read those tables. The keys are
+//! ordered by the time of the commit, so that the invalidations since a given
+//! time can be read with a single range scan.
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::cache::result::Invalidation;
+use crate::kvs::impl_kv_key_storekey;
+
+/// Represents a published result cache invalidation
+///
+/// The identifier only ensures that invalidations which were published at
+/// the same time are all kept, and lets a node skip the invalidations which
+/// it has already applied.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct ResultInvalidation {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	/// Timestamp (nanoseconds since the UNIX epoch) of the publication
+	pub ts: u64,
+	pub id: Uuid,
+}
+
+impl_kv_key_storekey!(ResultInvalidation => Invalidation);
+
+impl Categorise for ResultInvalidation {
+	fn categorise(&self) -> Category {
+		Category::ResultInvalidation
+	}
+}
+
+impl ResultInvalidation {
+	pub(crate) fn new(ts: u64, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b'i',
+			ts,
+			id,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<ResultInvalidation> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of the invalidations published since the given
+	/// timestamp
+	pub(crate) fn range(since: u64) -> (Vec<u8>, Vec<u8>) {
+		let mut beg = b"/!ri".to_vec();
+		beg.extend_from_slice(&since.to_be_bytes());
+		(beg, b"/!ri\xff".to_vec())
+	}
+
+	/// Returns the range of the invalidations published before the given
+	/// timestamp
+	pub(crate) fn before(ts: u64) -> (Vec<u8>, Vec<u8>) {
+		let mut end = b"/!ri".to_vec();
+		end.extend_from_slice(&ts.to_be_bytes());
+		(b"/!ri".to_vec(), end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = ResultInvalidation::new(1, Uuid::nil());
+		let enc = ResultInvalidation::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!ri\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+		let dec = ResultInvalidation::decode_key(&enc).unwrap();
+		assert_eq!(dec, val);
+	}
+
+	#[test]
+	fn range() {
+		let (beg, end) = ResultInvalidation::range(2);
+		let before = ResultInvalidation::new(1, Uuid::from_u128(u128::MAX)).encode_key().unwrap();
+		let after = ResultInvalidation::new(2, Uuid::nil()).encode_key().unwrap();
+		assert!(before < beg);
+		assert!(beg <= after);
+		assert!(after < end);
+		let (beg, end) = ResultInvalidation::before(2);
+		assert!(beg <= before);
+		assert!(before < end);
+		assert!(end <= after);
+	}
+}
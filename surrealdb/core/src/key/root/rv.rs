@@ -0,0 +1,56 @@
+//! Stores the versionstamp of the last restored backup
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+/// Key structure for storing the versionstamp of the last restored backup.
+///
+/// An incremental backup only contains the changes since the versionstamp of
+/// a previous backup, so it can only be restored into a datastore which was
+/// restored up to that versionstamp.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct RestoredVersionstamp {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+impl_kv_key_storekey!(RestoredVersionstamp => u64);
+
+impl Default for RestoredVersionstamp {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Categorise for RestoredVersionstamp {
+	fn categorise(&self) -> Category {
+		Category::RestoredVersionstamp
+	}
+}
+
+impl RestoredVersionstamp {
+	pub fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b'v',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = RestoredVersionstamp::new();
+		let enc = RestoredVersionstamp::encode_key(&val).unwrap();
+		assert_eq!(&enc, b"/!rv");
+	}
+}
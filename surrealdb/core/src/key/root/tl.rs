@@ -29,6 +29,12 @@ impl Tl {
 			TaskLeaseType::ChangeFeedCleanup => 1,
 			TaskLeaseType::IndexCompaction => 2,
 			TaskLeaseType::EventProcessing => 3,
+			TaskLeaseType::RecordExpiry => 4,
+			TaskLeaseType::JobScheduler => 5,
+			TaskLeaseType::ChangefeedSinks => 6,
+			TaskLeaseType::ResultInvalidations => 7,
+			TaskLeaseType::FileUploadExpiry => 8,
+			TaskLeaseType::HistoryRetention => 9,
 		};
 		Self {
 			__: b'/',
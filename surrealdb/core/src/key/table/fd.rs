@@ -65,6 +65,24 @@ impl<'a> Fd<'a> {
 			fd: Cow::Borrowed(fd),
 		}
 	}
+
+	/// Checks whether a key is the key of a field definition
+	#[cfg_attr(not(any(feature = "kv-rocksdb", feature = "kv-tikv")), allow(dead_code))]
+	pub(crate) fn is_field_key(k: &[u8]) -> bool {
+		matches!(
+			storekey::decode_borrow(k),
+			Ok(Fd {
+				__: b'/',
+				_a: b'*',
+				_b: b'*',
+				_c: b'*',
+				_d: b'!',
+				_e: b'f',
+				_f: b'd',
+				..
+			})
+		)
+	}
 }
 
 #[cfg(test)]
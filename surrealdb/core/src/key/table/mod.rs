@@ -1,5 +1,6 @@
 pub mod all;
 pub mod ev;
+pub mod ex;
 pub mod fd;
 pub mod ft;
 pub mod ih;
@@ -94,6 +94,16 @@ pub trait Transactable: requirements::TransactionRequirements {
 	/// will return a [`crate::kvs::Error::TransactionReadonly`] error.
 	fn writeable(&self) -> bool;
 
+	/// Check if indexes can be read at a version.
+	///
+	/// Storage engines which keep every version of every key can read the
+	/// entries of an index at a version, so that versioned queries can use
+	/// indexes. Storage engines which only keep a history of records return
+	/// [`false`], and versioned queries then scan the records of a table.
+	fn versioned_indexes(&self) -> bool {
+		false
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -174,6 +184,20 @@ pub trait Transactable: requirements::TransactionRequirements {
 		version: Option<u64>,
 	) -> Result<Vec<(Key, Val)>>;
 
+	/// Retrieve every stored version of a key from the datastore.
+	///
+	/// This function returns the versions of the key between the two
+	/// timestamps (inclusive) in ascending order, with a `None` value for
+	/// each version at which the key was deleted.
+	async fn versions(
+		&self,
+		_key: Key,
+		_since: u64,
+		_until: u64,
+	) -> Result<Vec<(u64, Option<Val>)>> {
+		Err(Error::UnsupportedVersionedQueries)
+	}
+
 	/// Insert or replace a key in the datastore.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
 	async fn replace(&self, key: Key, val: Val) -> Result<()> {
@@ -491,4 +515,13 @@ pub trait Transactable: requirements::TransactionRequirements {
 	async fn compact(&self, _range: Option<Range<Key>>) -> anyhow::Result<()> {
 		bail!(Error::CompactionNotSupported)
 	}
+
+	/// Remove a batch of the versions which have left the retention window.
+	///
+	/// Only storage engines which keep a history of versions of their own
+	/// remove anything. Returns the key from which to continue with the next
+	/// batch, or [`None`] once every version has been checked.
+	async fn prune_history(&self, _from: Option<Key>) -> Result<Option<Key>> {
+		Ok(None)
+	}
 }
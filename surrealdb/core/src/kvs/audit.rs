@@ -0,0 +1,834 @@
+//! Audit logging support.
+//!
+//! This module provides an audit trail of the authentication attempts
+//! (`signin`, `signup` and `authenticate`), and of the statements which change
+//! the schema or the permissions (`DEFINE`, `REMOVE`, `ALTER` and `ACCESS`).
+//! Each event records the session, the access method, the IP address of the
+//! client and the authenticated user, along with the outcome of the attempt.
+//!
+//! Key behaviors:
+//! - Events can be written as JSON lines to a file, which is rotated when it reaches a maximum
+//!   size, and/or to the datastore, where root users can read them with `SHOW AUDIT`.
+//! - The file is written by a dedicated thread, so that the async runtime is never blocked on the
+//!   file system. The thread stops once the audit log is dropped.
+//! - Statements are audited wherever they run, including within blocks, conditions and functions.
+//!   Their events are buffered in the transaction, and recorded once it is committed or cancelled,
+//!   so that a statement whose changes were reverted is recorded as a failure.
+//! - Events are buffered before they are written to the datastore, and are written in a single
+//!   transaction when a batch is full or when the audit log is flushed, every
+//!   `audit_log_flush_interval`. The events of a batch which fails to be written are buffered
+//!   again, and written with the next batch.
+//! - The events stored in the datastore are kept forever, unless a retention period is configured,
+//!   in which case the older events are removed by a background task, a batch at a time.
+//! - The buffered events are lost when the process stops without flushing the audit log, e.g. when
+//!   it crashes. Setting `SURREAL_AUDIT_LOG_BATCH_SIZE` to 1 writes every event to the datastore
+//!   as soon as it is recorded, at the cost of a transaction per event.
+//! - Statements are rendered to SQL and whitespace is collapsed so each statement fits on one line.
+//! - Secrets are never logged: user passwords are hashed before the statements are rendered, and
+//!   the secrets which are redacted from the catalog definitions are redacted from the statements.
+//!
+//! Note: a failure to write an event is logged, but does not fail the audited
+//! operation.
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+use std::time::Duration;
+
+#[cfg(not(target_family = "wasm"))]
+use anyhow::Context as _;
+use anyhow::Result;
+#[cfg(not(target_family = "wasm"))]
+use anyhow::bail;
+#[cfg(not(target_family = "wasm"))]
+use async_channel::{Receiver, Sender};
+use parking_lot::Mutex;
+use revision::revisioned;
+use surrealdb_types::ToSql;
+#[cfg(not(target_family = "wasm"))]
+use tokio::sync::oneshot;
+
+use crate::cnf::{AUDIT_LOG_BATCH_SIZE, NORMAL_FETCH_SIZE};
+use crate::ctx::Context;
+use crate::dbs::{Options, Session};
+#[cfg(not(target_family = "wasm"))]
+use crate::err::Error;
+use crate::expr::paths::{AC, DB, ID, IP, NS};
+use crate::expr::statements::AccessStatement;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{ControlFlow, Expr, FlowResult, Part};
+use crate::iam::{Auth, Level};
+use crate::key::root::au::AuditEntry;
+use crate::kvs::sequences::Sequences;
+use crate::kvs::{
+	KVValue, LockType, Transaction, TransactionFactory, TransactionType, impl_kv_value_revisioned,
+};
+use crate::types::{PublicValue, PublicVariables};
+use crate::val::{Datetime, Uuid, Value, convert_value_to_public_value};
+
+/// The number of batches of events which are kept buffered while they can not
+/// be written to the datastore, before the oldest events are discarded
+const MAX_PENDING_BATCHES: usize = 10;
+
+/// The configuration of an audit log file.
+#[derive(Clone, Debug)]
+pub struct AuditFile {
+	/// The path of the file the events are appended to
+	pub path: PathBuf,
+	/// The size in bytes above which the file is rotated
+	pub max_size: u64,
+	/// The number of rotated files which are kept
+	pub max_files: usize,
+}
+
+/// The kind of an audited event.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum AuditKind {
+	Signin,
+	Signup,
+	Authenticate,
+	Statement,
+}
+
+impl AuditKind {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Signin => "signin",
+			Self::Signup => "signup",
+			Self::Authenticate => "authenticate",
+			Self::Statement => "statement",
+		}
+	}
+}
+
+/// An event recorded in the audit log.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct AuditEvent {
+	pub(crate) time: Datetime,
+	pub(crate) kind: AuditKind,
+	/// The id of the session
+	pub(crate) session: Option<Uuid>,
+	/// The IP address of the client
+	pub(crate) ip: Option<String>,
+	pub(crate) ns: Option<String>,
+	pub(crate) db: Option<String>,
+	/// The access method used to authenticate
+	pub(crate) ac: Option<String>,
+	/// The level of the authenticated user
+	pub(crate) level: Level,
+	/// The authenticated user, or the user attempting to sign in
+	pub(crate) user: Option<String>,
+	/// The normalized SQL of an audited statement
+	pub(crate) sql: Option<String>,
+	/// The error which made the attempt fail, if any
+	pub(crate) error: Option<String>,
+}
+
+impl_kv_value_revisioned!(AuditEvent);
+
+impl AuditEvent {
+	/// Creates an event for an authentication attempt.
+	///
+	/// The target of the attempt is taken from the variables of a signin or a
+	/// signup, falling back to the session, so that a failed attempt records
+	/// what was attempted.
+	pub(crate) fn auth<T>(
+		kind: AuditKind,
+		session: &Session,
+		vars: Option<&PublicVariables>,
+		res: &Result<T>,
+	) -> Self {
+		let var = |names: &[&str]| match vars {
+			Some(vars) => names.iter().find_map(|n| match vars.get(n) {
+				Some(PublicValue::String(v)) => Some(v.clone()),
+				_ => None,
+			}),
+			None => None,
+		};
+		Self {
+			time: Datetime::now(),
+			kind,
+			session: session.id.map(Uuid::from),
+			ip: session.ip.clone(),
+			ns: var(&["NS", "ns"]).or_else(|| session.ns.clone()),
+			db: var(&["DB", "db"]).or_else(|| session.db.clone()),
+			ac: var(&["AC", "ac"]).or_else(|| session.ac.clone()),
+			level: session.au.level().clone(),
+			user: var(&["user"]).or_else(|| user(&session.au)),
+			sql: None,
+			error: res.as_ref().err().map(ToString::to_string),
+		}
+	}
+
+	/// Creates an event for a statement run by the session of a context.
+	pub(crate) fn statement(
+		ctx: &Context,
+		auth: &Auth,
+		sql: String,
+		error: Option<String>,
+	) -> Self {
+		let session = ctx.value("session").unwrap_or(&Value::None);
+		let string = |path: &[Part]| match session.pick(path) {
+			Value::String(v) => Some(v),
+			_ => None,
+		};
+		Self {
+			time: Datetime::now(),
+			kind: AuditKind::Statement,
+			session: match session.pick(ID.as_ref()) {
+				Value::Uuid(v) => Some(v),
+				_ => None,
+			},
+			ip: string(IP.as_ref()),
+			ns: string(NS.as_ref()),
+			db: string(DB.as_ref()),
+			ac: string(AC.as_ref()),
+			level: auth.level().clone(),
+			user: user(auth),
+			sql: Some(sql),
+			error,
+		}
+	}
+}
+
+impl InfoStructure for AuditEvent {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"time".to_string() => Value::Datetime(self.time),
+			"kind".to_string() => self.kind.as_str().into(),
+			"session".to_string(), if let Some(v) = self.session => Value::Uuid(v),
+			"ip".to_string(), if let Some(v) = self.ip => v.into(),
+			"ns".to_string(), if let Some(v) = self.ns => v.into(),
+			"db".to_string(), if let Some(v) = self.db => v.into(),
+			"ac".to_string(), if let Some(v) = self.ac => v.into(),
+			"level".to_string(), if !self.level.is_anonymous() => self.level.to_string().into(),
+			"user".to_string(), if let Some(v) = self.user => v.into(),
+			"sql".to_string(), if let Some(v) = self.sql => v.into(),
+			"outcome".to_string() => if self.error.is_some() { "failure" } else { "success" }.into(),
+			"error".to_string(), if let Some(v) = self.error => v.into(),
+		})
+	}
+}
+
+/// Returns the id of an authenticated user, if any.
+fn user(auth: &Auth) -> Option<String> {
+	Some(auth.id()).filter(|id| !id.is_empty()).map(str::to_owned)
+}
+
+/// Returns the normalized SQL of a statement which changes the schema, with
+/// the secrets which are redacted from the catalog definitions removed, or
+/// `None` if the statement is not audited.
+pub(crate) fn statement_sql(expr: &Expr) -> Option<String> {
+	let sql = match expr {
+		Expr::Define(s) => Expr::Define(Box::new(s.as_ref().clone().redact())).to_sql(),
+		Expr::Alter(s) => Expr::Alter(Box::new(s.as_ref().clone().redact())).to_sql(),
+		Expr::Remove(_) => expr.to_sql(),
+		_ => return None,
+	};
+	Some(normalize(&sql))
+}
+
+/// Ensures a statement is logged on a single line by collapsing whitespace.
+fn normalize(sql: &str) -> String {
+	sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Audits a statement which changes the schema, if audit logging is enabled.
+pub(crate) fn audit_expr<T>(ctx: &Context, opt: &Options, expr: &Expr, res: &Result<T>) {
+	if ctx.audit_log().is_none() {
+		return;
+	}
+	if let Some(sql) = statement_sql(expr) {
+		buffer(ctx, opt, sql, res.as_ref().err().map(ToString::to_string));
+	}
+}
+
+/// Audits a statement which changes the access grants, if audit logging is
+/// enabled.
+pub(crate) fn audit_access(
+	ctx: &Context,
+	opt: &Options,
+	stmt: &AccessStatement,
+	res: &FlowResult<Value>,
+) {
+	if ctx.audit_log().is_none() {
+		return;
+	}
+	let error = match res {
+		Err(ControlFlow::Err(e)) => Some(e.to_string()),
+		_ => None,
+	};
+	buffer(ctx, opt, normalize(&stmt.to_sql()), error);
+}
+
+/// Buffers the event of a statement in the transaction it runs in, so that
+/// it is recorded with the outcome of the transaction.
+fn buffer(ctx: &Context, opt: &Options, sql: String, error: Option<String>) {
+	if let Some(tx) = ctx.try_tx() {
+		tx.audit(AuditEvent::statement(ctx, &opt.auth, sql, error));
+	}
+}
+
+/// Reads the events recorded in the datastore since the given time, the
+/// oldest first.
+pub(crate) async fn read(tx: &Transaction, since: u64, limit: Option<u32>) -> Result<Vec<Value>> {
+	let (beg, end) = AuditEntry::range(since);
+	let limit = limit.unwrap_or(u32::MAX);
+	let mut out = Vec::new();
+	for (_, v) in tx.scan(beg..end, limit, 0, None).await? {
+		out.push(AuditEvent::kv_decode_value(v)?.structure());
+	}
+	Ok(out)
+}
+
+/// Removes a batch of the events recorded in the datastore before the given
+/// time, and returns whether there may be more to remove.
+pub(crate) async fn remove_expired(tx: &Transaction, before: u64) -> Result<bool> {
+	let (beg, end) = AuditEntry::range_until(before);
+	let batch = *NORMAL_FETCH_SIZE;
+	let keys = tx.keys(beg..end, batch, 0, None).await?;
+	for key in &keys {
+		tx.del(key).await?;
+	}
+	Ok(keys.len() >= batch as usize)
+}
+
+#[derive(Clone)]
+/// The audit log of a datastore.
+///
+/// An `AuditLog` writes each event to the configured file, if any, and
+/// buffers it to be written to the datastore when the audit log is stored in
+/// the datastore.
+pub(crate) struct AuditLog(Arc<Inner>);
+
+struct Inner {
+	file: Option<AuditFileLog>,
+	table: Option<AuditTable>,
+}
+
+/// The events waiting to be written to the datastore.
+struct AuditTable {
+	tf: TransactionFactory,
+	sequences: Sequences,
+	pending: Mutex<Vec<AuditEvent>>,
+	/// How long the events are kept, if they are not kept forever
+	retention: Option<Duration>,
+}
+
+impl AuditTable {
+	/// Buffers the events of a batch which failed to be written again, ahead
+	/// of the events which were recorded in the meantime.
+	fn requeue(&self, mut events: Vec<AuditEvent>) {
+		let mut pending = self.pending.lock();
+		events.append(&mut pending);
+		let limit = *AUDIT_LOG_BATCH_SIZE * MAX_PENDING_BATCHES;
+		if events.len() > limit {
+			let dropped = events.len() - limit;
+			events.drain(..dropped);
+			error!("Discarded {dropped} audit log events which could not be written");
+		}
+		*pending = events;
+	}
+}
+
+impl AuditLog {
+	/// Create a new audit log.
+	///
+	/// Parameters:
+	/// - `file`: The file the events are appended to as JSON lines, if any.
+	/// - `table`: The transaction factory used to store the events in the datastore, if the audit
+	///   log is stored in the datastore.
+	/// - `retention`: How long the events stored in the datastore are kept, if they are not kept
+	///   forever.
+	pub(super) fn new(
+		file: Option<AuditFile>,
+		table: Option<(TransactionFactory, Sequences)>,
+		retention: Option<Duration>,
+	) -> Result<Self> {
+		Ok(Self(Arc::new(Inner {
+			file: file.map(AuditFileLog::new).transpose()?,
+			table: table.map(|(tf, sequences)| AuditTable {
+				tf,
+				sequences,
+				pending: Mutex::new(Vec::new()),
+				retention,
+			}),
+		})))
+	}
+
+	/// Returns the time before which the events stored in the datastore have
+	/// left the retention period, or `None` if the events are kept forever.
+	pub(crate) fn retention_cutoff(&self) -> Result<Option<u64>> {
+		let Some(retention) = self.0.table.as_ref().and_then(|t| t.retention) else {
+			return Ok(None);
+		};
+		let now = Datetime::now().to_version_stamp()?;
+		let retention = u64::try_from(retention.as_nanos()).unwrap_or(u64::MAX);
+		Ok(Some(now.saturating_sub(retention)))
+	}
+
+	/// Record an event in the audit log.
+	pub(crate) async fn record(&self, event: AuditEvent) {
+		if let Some(file) = &self.0.file
+			&& let Err(e) = Self::write_file(file, &event).await
+		{
+			error!("Failed to write to the audit log file: {e}");
+		}
+		if let Some(table) = &self.0.table {
+			let full = {
+				let mut pending = table.pending.lock();
+				pending.push(event);
+				pending.len() >= *AUDIT_LOG_BATCH_SIZE
+			};
+			// Write a full batch now, rather than waiting for the next flush.
+			// The flush is boxed, as it commits a transaction, which records
+			// the events audited in that transaction.
+			if full && let Err(e) = Box::pin(self.flush()).await {
+				error!("Failed to write to the audit log: {e}");
+			}
+		}
+	}
+
+	/// Wait for the events to be written to the file, and write the buffered
+	/// events to the datastore in a single transaction.
+	pub(crate) async fn flush(&self) -> Result<()> {
+		if let Some(file) = &self.0.file {
+			file.sync().await?;
+		}
+		let Some(table) = &self.0.table else {
+			return Ok(());
+		};
+		let events = std::mem::take(&mut *table.pending.lock());
+		if events.is_empty() {
+			return Ok(());
+		}
+		let res = async {
+			let tx = table
+				.tf
+				.transaction(TransactionType::Write, LockType::Optimistic, table.sequences.clone())
+				.await?;
+			run!(tx, Self::write_table(&tx, &events).await)
+		}
+		.await;
+		if res.is_err() {
+			table.requeue(events);
+		}
+		res
+	}
+
+	async fn write_file(file: &AuditFileLog, event: &AuditEvent) -> Result<()> {
+		let value = convert_value_to_public_value(event.clone().structure())?;
+		let mut line = serde_json::to_vec(&value.into_json_value())?;
+		line.push(b'\n');
+		file.write(line).await
+	}
+
+	async fn write_table(tx: &Transaction, events: &[AuditEvent]) -> Result<()> {
+		for event in events {
+			let key = AuditEntry::new(event.time.to_version_stamp()?, uuid::Uuid::new_v4());
+			tx.set(&key, event, None).await?;
+		}
+		Ok(())
+	}
+}
+
+/// A message handled by the thread which writes the audit log file.
+#[cfg(not(target_family = "wasm"))]
+enum FileMessage {
+	/// Append a line to the file
+	Write(Vec<u8>),
+	/// Notify the sender once the previous lines have been written
+	Sync(oneshot::Sender<()>),
+}
+
+/// Hands the events to the thread which writes the audit log file.
+///
+/// The channel is bounded, so that events are not buffered without limit
+/// when the file can not keep up. The thread exits once the channel is
+/// closed, when the audit log is dropped.
+struct AuditFileLog {
+	#[cfg(not(target_family = "wasm"))]
+	sender: Sender<FileMessage>,
+	#[cfg(target_family = "wasm")]
+	writer: Mutex<AuditWriter>,
+}
+
+impl AuditFileLog {
+	#[cfg(not(target_family = "wasm"))]
+	fn new(config: AuditFile) -> Result<Self> {
+		let (sender, receiver) = async_channel::bounded(*AUDIT_LOG_BATCH_SIZE);
+		let writer = AuditWriter::new(config);
+		thread::Builder::new()
+			.name("audit-log-writer".to_string())
+			.spawn(move || writer.run(receiver))
+			.context("Failed to spawn the audit log writer thread")?;
+		Ok(Self {
+			sender,
+		})
+	}
+
+	#[cfg(target_family = "wasm")]
+	fn new(config: AuditFile) -> Result<Self> {
+		Ok(Self {
+			writer: Mutex::new(AuditWriter::new(config)),
+		})
+	}
+
+	#[cfg(not(target_family = "wasm"))]
+	async fn write(&self, line: Vec<u8>) -> Result<()> {
+		if self.sender.send(FileMessage::Write(line)).await.is_err() {
+			bail!(Error::Channel("The audit log writer has stopped".to_string()));
+		}
+		Ok(())
+	}
+
+	#[cfg(target_family = "wasm")]
+	async fn write(&self, line: Vec<u8>) -> Result<()> {
+		Ok(self.writer.lock().write(&line)?)
+	}
+
+	/// Waits until the events which were recorded before have been written.
+	#[cfg(not(target_family = "wasm"))]
+	async fn sync(&self) -> Result<()> {
+		let (tx, rx) = oneshot::channel();
+		if self.sender.send(FileMessage::Sync(tx)).await.is_err() || rx.await.is_err() {
+			bail!(Error::Channel("The audit log writer has stopped".to_string()));
+		}
+		Ok(())
+	}
+
+	#[cfg(target_family = "wasm")]
+	async fn sync(&self) -> Result<()> {
+		Ok(())
+	}
+}
+
+/// Appends the events to a file, rotating it when it reaches its maximum size.
+struct AuditWriter {
+	config: AuditFile,
+	file: Option<File>,
+	size: u64,
+}
+
+impl AuditWriter {
+	fn new(config: AuditFile) -> Self {
+		Self {
+			config,
+			file: None,
+			size: 0,
+		}
+	}
+
+	/// Writes the lines received from the audit log until it is dropped.
+	#[cfg(not(target_family = "wasm"))]
+	fn run(mut self, receiver: Receiver<FileMessage>) {
+		while let Ok(message) = receiver.recv_blocking() {
+			match message {
+				FileMessage::Write(line) => {
+					if let Err(e) = self.write(&line) {
+						error!("Failed to write to the audit log file: {e}");
+					}
+				}
+				FileMessage::Sync(tx) => {
+					if tx.send(()).is_err() {
+						trace!("The audit log flush was cancelled");
+					}
+				}
+			}
+		}
+	}
+
+	fn write(&mut self, line: &[u8]) -> io::Result<()> {
+		let mut file = match self.file.take() {
+			Some(file) => file,
+			None => self.open()?,
+		};
+		if self.size > 0 && self.size + line.len() as u64 > self.config.max_size {
+			drop(file);
+			self.rotate()?;
+			file = self.open()?;
+		}
+		file.write_all(line)?;
+		self.size += line.len() as u64;
+		self.file = Some(file);
+		Ok(())
+	}
+
+	fn open(&mut self) -> io::Result<File> {
+		let file = OpenOptions::new().create(true).append(true).open(&self.config.path)?;
+		self.size = file.metadata()?.len();
+		Ok(file)
+	}
+
+	/// Renames the current file to `<path>.1`, shifting the previously
+	/// rotated files and removing the oldest one.
+	fn rotate(&mut self) -> io::Result<()> {
+		if self.config.max_files == 0 {
+			return fs::remove_file(&self.config.path);
+		}
+		for i in (1..self.config.max_files).rev() {
+			let from = self.rotated(i);
+			if from.exists() {
+				fs::rename(from, self.rotated(i + 1))?;
+			}
+		}
+		fs::rename(&self.config.path, self.rotated(1))
+	}
+
+	fn rotated(&self, n: usize) -> PathBuf {
+		let mut path = self.config.path.clone().into_os_string();
+		path.push(format!(".{n}"));
+		path.into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use temp_dir::TempDir;
+	use tokio_util::sync::CancellationToken;
+
+	use super::*;
+	use crate::expr::TopLevelExpr;
+	use crate::iam::Role;
+	use crate::iam::signin::signin;
+	use crate::kvs::Datastore;
+	use crate::{sql, syn};
+
+	fn writer(dir: &TempDir, max_size: u64, max_files: usize) -> AuditWriter {
+		AuditWriter::new(AuditFile {
+			path: dir.path().join("audit.log"),
+			max_size,
+			max_files,
+		})
+	}
+
+	fn audited(sql: &str) -> Option<String> {
+		let plan = syn::parse(sql).unwrap().expressions.remove(0);
+		match plan.into() {
+			TopLevelExpr::Expr(expr) => statement_sql(&expr),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn rotates_files() {
+		let dir = TempDir::new().unwrap();
+		let mut w = writer(&dir, 8, 2);
+		for line in ["one\n", "two\n", "three\n", "four\n", "five\n"] {
+			w.write(line.as_bytes()).unwrap();
+		}
+		let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+		assert_eq!(read("audit.log"), "five\n");
+		assert_eq!(read("audit.log.1"), "four\n");
+		assert_eq!(read("audit.log.2"), "three\n");
+		assert!(!dir.path().join("audit.log.3").exists());
+	}
+
+	#[test]
+	fn appends_to_existing_file() {
+		let dir = TempDir::new().unwrap();
+		fs::write(dir.path().join("audit.log"), "one\n").unwrap();
+		let mut w = writer(&dir, 8, 1);
+		w.write(b"two\n").unwrap();
+		w.write(b"three\n").unwrap();
+		assert_eq!(fs::read_to_string(dir.path().join("audit.log.1")).unwrap(), "one\ntwo\n");
+		assert_eq!(fs::read_to_string(dir.path().join("audit.log")).unwrap(), "three\n");
+	}
+
+	#[test]
+	fn audits_schema_statements() {
+		assert_eq!(
+			audited("DEFINE TABLE person"),
+			Some("DEFINE TABLE person TYPE ANY SCHEMALESS PERMISSIONS NONE".to_string())
+		);
+		assert_eq!(audited("REMOVE TABLE person"), Some("REMOVE TABLE person".to_string()));
+		assert_eq!(audited("SELECT * FROM person"), None);
+		assert_eq!(audited("CREATE person"), None);
+	}
+
+	#[test]
+	fn redacts_secrets() {
+		let sql =
+			audited("DEFINE ACCESS api ON DATABASE TYPE JWT ALGORITHM HS512 KEY 'secret'").unwrap();
+		assert!(sql.contains("[REDACTED]") && !sql.contains("secret"), "{sql}");
+		let sql = audited("DEFINE USER admin ON ROOT PASSWORD 'secret' ROLES OWNER").unwrap();
+		assert!(sql.contains("PASSHASH") && !sql.contains("'secret'"), "{sql}");
+		let sql = audited("DEFINE KEY customer VALUE 'c2VjcmV0'").unwrap();
+		assert!(sql.contains("[REDACTED]") && !sql.contains("c2VjcmV0"), "{sql}");
+		let sql = audited(
+			r#"DEFINE SINK orders ON TABLE order TO WEBHOOK "https://example.com/hook" HEADERS { authorization: "secret" }"#,
+		)
+		.unwrap();
+		assert!(sql.contains("authorization") && !sql.contains("secret"), "{sql}");
+		let sql = audited(
+			r#"DEFINE SINK orders ON TABLE order TO WEBHOOK "https://example.com/hook" HEADERS $headers"#,
+		)
+		.unwrap();
+		assert!(sql.contains("[REDACTED]") && !sql.contains("$headers"), "{sql}");
+		let backend = "s3://bucket?region=eu-west-2&access_key_id=key&secret_access_key=secret";
+		for sql in [
+			format!("DEFINE BUCKET files BACKEND '{backend}'"),
+			format!("ALTER BUCKET files BACKEND '{backend}'"),
+		] {
+			let sql = audited(&sql).unwrap();
+			assert!(sql.contains("region=eu-west-2") && !sql.contains("=secret"), "{sql}");
+		}
+	}
+
+	#[test]
+	fn event_structure() {
+		let auth = Auth::for_root(Role::Owner);
+		let ctx = Context::background();
+		let event = AuditEvent::statement(&ctx, &auth, "REMOVE TABLE person".to_string(), None);
+		let Value::Object(obj) = event.structure() else {
+			panic!("expected an object");
+		};
+		assert_eq!(obj.get("kind"), Some(&Value::from("statement")));
+		assert_eq!(obj.get("level"), Some(&Value::from("/")));
+		assert_eq!(obj.get("outcome"), Some(&Value::from("success")));
+		assert!(obj.get("error").is_none());
+	}
+
+	#[tokio::test]
+	async fn records_events() {
+		let dir = TempDir::new().unwrap();
+		let file = AuditFile {
+			path: dir.path().join("audit.log"),
+			max_size: 1 << 20,
+			max_files: 1,
+		};
+		let ds =
+			Datastore::new("memory").await.unwrap().with_audit_log(Some(file), true, None).unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE TABLE person; CREATE person:one; REMOVE TABLE missing";
+		assert!(ds.test_execute(sql, &ses).await.is_err());
+		// A failed signin is recorded with the attempted user
+		let vars = PublicVariables::from_iter([
+			("user".to_string(), PublicValue::String("root".to_string())),
+			("pass".to_string(), PublicValue::String("wrong".to_string())),
+		]);
+		assert!(signin(&ds, &mut Session::default(), vars).await.is_err());
+		// The events are buffered until the audit log is flushed
+		let events =
+			ds.test_execute("SHOW AUDIT", &Session::owner()).await.unwrap().into_json_value();
+		assert_eq!(events.as_array().unwrap().len(), 0);
+		ds.flush_audit_log().await.unwrap();
+		// Root users can read the audit log
+		let events =
+			ds.test_execute("SHOW AUDIT", &Session::owner()).await.unwrap().into_json_value();
+		let events = events.as_array().unwrap();
+		assert_eq!(events.len(), 3);
+		assert_eq!(events[0]["sql"], "DEFINE TABLE person TYPE ANY SCHEMALESS PERMISSIONS NONE");
+		assert_eq!(events[0]["outcome"], "success");
+		assert_eq!(events[1]["sql"], "REMOVE TABLE missing");
+		assert_eq!(events[1]["outcome"], "failure");
+		assert_eq!(events[2]["kind"], "signin");
+		assert_eq!(events[2]["user"], "root");
+		assert_eq!(events[2]["outcome"], "failure");
+		let events = ds
+			.test_execute("SHOW AUDIT LIMIT 1", &Session::owner())
+			.await
+			.unwrap()
+			.into_json_value();
+		assert_eq!(events.as_array().unwrap().len(), 1);
+		// Other users can not
+		let ses = Session::for_level(Level::Namespace("test".to_string()), Role::Owner);
+		let res = ds.test_execute("SHOW AUDIT", &ses).await;
+		assert!(res.is_err(), "{res:?}");
+		// The same events are written to the file
+		let lines = fs::read_to_string(dir.path().join("audit.log")).unwrap();
+		assert_eq!(lines.lines().count(), 3);
+	}
+
+	#[tokio::test]
+	async fn rejects_versionstamps() {
+		let ds = Datastore::new("memory").await.unwrap().with_audit_log(None, true, None).unwrap();
+		let mut ast = syn::parse("SHOW AUDIT").unwrap();
+		let sql::TopLevelExpr::Show(stmt) = &mut ast.expressions[0] else {
+			panic!("expected a SHOW statement");
+		};
+		stmt.since = sql::statements::show::ShowSince::Versionstamp(10);
+		let res = ds.process(ast, &Session::owner(), None).await.unwrap();
+		let err = res.into_iter().next().unwrap().result.unwrap_err();
+		assert!(err.to_string().contains("versionstamp 10"), "{err}");
+	}
+
+	#[tokio::test]
+	async fn records_nested_and_cancelled_statements() {
+		let ds = Datastore::new("memory").await.unwrap().with_audit_log(None, true, None).unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = r#"
+			IF true { DEFINE TABLE nested };
+			DEFINE FUNCTION fn::define() { DEFINE TABLE called };
+			fn::define();
+			BEGIN;
+			DEFINE TABLE cancelled;
+			CANCEL;
+			BEGIN;
+			DEFINE TABLE failed;
+			THROW "failure";
+			COMMIT;
+		"#;
+		let _ = ds.test_execute(sql, &ses).await;
+		ds.flush_audit_log().await.unwrap();
+		let events =
+			ds.test_execute("SHOW AUDIT", &Session::owner()).await.unwrap().into_json_value();
+		let events = events.as_array().unwrap();
+		let outcome = |table: &str| {
+			let sql = format!("DEFINE TABLE {table} TYPE ANY SCHEMALESS PERMISSIONS NONE");
+			let event = events.iter().find(|e| e["sql"] == sql).unwrap_or_else(|| panic!("{sql}"));
+			(event["outcome"].clone(), event["error"].clone())
+		};
+		assert_eq!(outcome("nested").0, "success");
+		assert_eq!(outcome("called").0, "success");
+		assert_eq!(
+			outcome("cancelled"),
+			("failure".into(), "The transaction was cancelled".into())
+		);
+		assert_eq!(outcome("failed").0, "failure");
+		// The statements whose changes were reverted were not applied
+		let res = ds.test_execute("INFO FOR DB", &ses).await.unwrap().into_json_value();
+		assert!(res["tables"].get("cancelled").is_none() && res["tables"].get("failed").is_none());
+	}
+
+	#[tokio::test]
+	async fn removes_expired_events() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let count = async |ds: &Datastore| {
+			let events =
+				ds.test_execute("SHOW AUDIT", &Session::owner()).await.unwrap().into_json_value();
+			events.as_array().unwrap().len()
+		};
+		for (retention, remaining) in [(Duration::from_secs(3600), 1), (Duration::ZERO, 0)] {
+			let ds = Datastore::new("memory")
+				.await
+				.unwrap()
+				.with_audit_log(None, true, Some(retention))
+				.unwrap();
+			ds.test_execute("DEFINE TABLE person", &ses).await.unwrap();
+			ds.flush_audit_log().await.unwrap();
+			assert_eq!(count(&ds).await, 1);
+			// Nothing is removed once the task is cancelled
+			let canceller = CancellationToken::new();
+			canceller.cancel();
+			ds.audit_log_retention(Duration::from_secs(1), &canceller).await.unwrap();
+			assert_eq!(count(&ds).await, 1);
+			// The events which have left the retention period are removed
+			tokio::time::sleep(Duration::from_millis(1)).await;
+			ds.audit_log_retention(Duration::from_secs(1), &CancellationToken::new())
+				.await
+				.unwrap();
+			assert_eq!(count(&ds).await, remaining);
+		}
+		// The events are kept forever without a retention period
+		let ds = Datastore::new("memory").await.unwrap().with_audit_log(None, true, None).unwrap();
+		ds.test_execute("DEFINE TABLE person", &ses).await.unwrap();
+		ds.flush_audit_log().await.unwrap();
+		ds.audit_log_retention(Duration::from_secs(1), &CancellationToken::new()).await.unwrap();
+		assert_eq!(count(&ds).await, 1);
+	}
+}
@@ -0,0 +1,676 @@
+//! Raw key-value backups of a datastore.
+//!
+//! A backup is read from a single transaction, so it is a consistent snapshot
+//! of the whole keyspace at one versionstamp. It is written as a portable
+//! archive of key-value frames, which can be restored into any storage engine.
+//!
+//! An incremental backup only contains the keys which were set or deleted
+//! since the versionstamp of a previous backup. It is computed by comparing
+//! the keyspace as it was at that versionstamp with the current snapshot, so
+//! it requires a storage engine with versioning enabled, and the base
+//! versionstamp must still be within the history retention window of the
+//! datastore. This is checked before anything is written. Both snapshots are
+//! scanned in full, so an incremental backup reads twice as many keys as a
+//! full backup, and only saves on the size of the archive.
+//!
+//! Every archive records the identifier of the datastore it was taken from,
+//! which is generated by the first backup of a datastore, and restored along
+//! with a full backup. Incremental backups and changefeed recoveries are only
+//! applied onto a datastore restored from a backup of the same datastore, as
+//! the namespace and database identifiers of their keys would otherwise refer
+//! to unrelated namespaces and databases.
+//!
+//! The archive is laid out as follows, with all integers in big-endian:
+//!
+//! ```text
+//! archive = MAGIC, header length (u32), header, frame*, end
+//! frame   = SET (u8), key length (u32), key, value length (u32), value
+//!         | DEL (u8), key length (u32), key
+//! end     = END (u8), number of frames (u64)
+//! ```
+use std::cmp::Ordering;
+use std::future::ready;
+use std::ops::Range;
+use std::pin::pin;
+
+use anyhow::{Result, bail, ensure};
+use async_channel::Sender;
+use futures::{Stream, TryStreamExt, stream};
+use revision::revisioned;
+use uuid::Uuid;
+
+use super::{Key, Transaction, Val};
+use crate::err::Error;
+use crate::idx::planner::ScanDirection;
+use crate::key::root::id::DatastoreId;
+use crate::key::root::rv::RestoredVersionstamp;
+
+/// The bytes identifying a backup archive
+pub const MAGIC: &[u8; 8] = b"SDBKVBAK";
+
+/// The key storing the version of the datastore
+const VERSION_KEY: &[u8] = b"!v";
+
+const TAG_END: u8 = 0;
+const TAG_SET: u8 = 1;
+const TAG_DEL: u8 = 2;
+
+/// The size of the chunks sent to the backup channel
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Describes the snapshot contained in a backup archive.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Header {
+	/// The identifier of the datastore which the snapshot was taken from
+	pub datastore: Uuid,
+	/// The versionstamp at which the snapshot was taken
+	pub versionstamp: u64,
+	/// The versionstamp of the backup this archive is based on,
+	/// or `None` if this is a full backup
+	pub since: Option<u64>,
+	/// The changefeed timestamp at which the snapshot was taken, as a
+	/// versionstamp. Changefeed entries recorded after it are not contained
+	/// in the snapshot.
+	pub changefeed: u128,
+}
+
+impl Header {
+	/// Whether this archive only contains the changes since a previous backup
+	pub fn is_incremental(&self) -> bool {
+		self.since.is_some()
+	}
+}
+
+/// An entry of a backup archive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Entry {
+	/// The key was set to the value
+	Set(Key, Val),
+	/// The key was deleted
+	Del(Key),
+}
+
+/// The range covering every key of the datastore
+fn keyspace() -> Range<Key> {
+	vec![0x00]..vec![0xff]
+}
+
+/// Whether a key belongs to the nodes of the cluster, rather than to its data.
+///
+/// Node registrations, node live queries, per-node ID generator state, task
+/// leases, result cache invalidations, the versionstamp of the last restored
+/// backup and the file uploads in progress describe the cluster which a backup
+/// was taken from. They are not written to backups, and are skipped when
+/// restoring older archives, so that they are never merged into a running
+/// cluster. An upload can not be resumed from a restored datastore, as its
+/// client is gone.
+pub(crate) fn is_node_key(key: &[u8]) -> bool {
+	[b"/!nd".as_slice(), b"/!ni", b"/!tl", b"/!ri", b"/!rv", b"/!fu", b"/!fp", b"/$"]
+		.iter()
+		.any(|prefix| key.starts_with(prefix))
+}
+
+/// Encodes archive frames, sending them to the channel in chunks.
+struct ArchiveWriter {
+	chn: Sender<Vec<u8>>,
+	buf: Vec<u8>,
+	count: u64,
+}
+
+impl ArchiveWriter {
+	fn new(header: &Header, chn: Sender<Vec<u8>>) -> Result<Self> {
+		let encoded = revision::to_vec(header)?;
+		let mut buf = Vec::with_capacity(CHUNK_SIZE);
+		buf.extend_from_slice(MAGIC);
+		buf.extend_from_slice(&(encoded.len() as u32).to_be_bytes());
+		buf.extend_from_slice(&encoded);
+		Ok(Self {
+			chn,
+			buf,
+			count: 0,
+		})
+	}
+
+	fn put_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+		let Ok(len) = u32::try_from(bytes.len()) else {
+			bail!(Error::InvalidBackup(format!("an entry of {} bytes is too large", bytes.len())));
+		};
+		self.buf.extend_from_slice(&len.to_be_bytes());
+		self.buf.extend_from_slice(bytes);
+		Ok(())
+	}
+
+	async fn set(&mut self, key: &[u8], val: &[u8]) -> Result<()> {
+		self.buf.push(TAG_SET);
+		self.put_bytes(key)?;
+		self.put_bytes(val)?;
+		self.frame_written().await
+	}
+
+	async fn del(&mut self, key: &[u8]) -> Result<()> {
+		self.buf.push(TAG_DEL);
+		self.put_bytes(key)?;
+		self.frame_written().await
+	}
+
+	async fn frame_written(&mut self) -> Result<()> {
+		self.count += 1;
+		if self.buf.len() >= CHUNK_SIZE {
+			let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(CHUNK_SIZE));
+			self.send(chunk).await?;
+		}
+		Ok(())
+	}
+
+	async fn finish(mut self) -> Result<u64> {
+		self.buf.push(TAG_END);
+		self.buf.extend_from_slice(&self.count.to_be_bytes());
+		let chunk = std::mem::take(&mut self.buf);
+		self.send(chunk).await?;
+		Ok(self.count)
+	}
+
+	async fn send(&self, chunk: Vec<u8>) -> Result<()> {
+		if self.chn.send(chunk).await.is_err() {
+			bail!(Error::Channel("The backup channel was closed".to_owned()));
+		}
+		Ok(())
+	}
+}
+
+/// Incrementally decodes a backup archive from chunks of bytes.
+#[derive(Default)]
+pub struct ArchiveReader {
+	buf: Vec<u8>,
+	pos: usize,
+	header: Option<Header>,
+	count: u64,
+	finished: bool,
+}
+
+impl ArchiveReader {
+	/// Appends bytes read from the archive
+	pub fn push(&mut self, bytes: &[u8]) {
+		// Drop the bytes which have already been decoded
+		if self.pos > 0 {
+			self.buf.drain(..self.pos);
+			self.pos = 0;
+		}
+		self.buf.extend_from_slice(bytes);
+	}
+
+	/// Returns the header of the archive, once enough bytes have been read
+	pub fn header(&mut self) -> Result<Option<Header>> {
+		if self.header.is_none() {
+			let Some(rest) = self.buf.get(self.pos..) else {
+				return Ok(None);
+			};
+			if rest.len() < MAGIC.len() {
+				return Ok(None);
+			}
+			ensure!(
+				&rest[..MAGIC.len()] == MAGIC,
+				Error::InvalidBackup("the file is not a backup archive".to_owned())
+			);
+			let Some(len) = read_u32(&rest[MAGIC.len()..]) else {
+				return Ok(None);
+			};
+			let beg = MAGIC.len() + 4;
+			let Some(encoded) = rest.get(beg..beg + len as usize) else {
+				return Ok(None);
+			};
+			self.header = Some(revision::from_slice(encoded)?);
+			self.pos += beg + len as usize;
+		}
+		Ok(self.header)
+	}
+
+	/// Returns the next entry of the archive, or `None` if more bytes are
+	/// needed or the end of the archive has been reached.
+	pub fn next_entry(&mut self) -> Result<Option<Entry>> {
+		if self.finished || self.header()?.is_none() {
+			return Ok(None);
+		}
+		let rest = &self.buf[self.pos..];
+		let Some(&tag) = rest.first() else {
+			return Ok(None);
+		};
+		let (entry, used) = match tag {
+			TAG_END => {
+				let Some(count) = rest.get(1..9) else {
+					return Ok(None);
+				};
+				let count = u64::from_be_bytes(count.try_into().expect("8 bytes"));
+				ensure!(
+					count == self.count,
+					Error::InvalidBackup(format!(
+						"expected {count} entries but the archive contains {}",
+						self.count
+					))
+				);
+				self.pos += 9;
+				self.finished = true;
+				return Ok(None);
+			}
+			TAG_SET => {
+				let Some((key, used)) = read_bytes(&rest[1..]) else {
+					return Ok(None);
+				};
+				let Some((val, more)) = read_bytes(&rest[1 + used..]) else {
+					return Ok(None);
+				};
+				(Entry::Set(key.to_vec(), val.to_vec()), 1 + used + more)
+			}
+			TAG_DEL => {
+				let Some((key, used)) = read_bytes(&rest[1..]) else {
+					return Ok(None);
+				};
+				(Entry::Del(key.to_vec()), 1 + used)
+			}
+			tag => bail!(Error::InvalidBackup(format!("unknown frame tag {tag}"))),
+		};
+		self.pos += used;
+		self.count += 1;
+		Ok(Some(entry))
+	}
+
+	/// Whether the end of the archive has been reached
+	pub fn is_finished(&self) -> bool {
+		self.finished
+	}
+}
+
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+	bytes.get(..4).map(|b| u32::from_be_bytes(b.try_into().expect("4 bytes")))
+}
+
+/// Reads a length-prefixed slice, returning it with the number of bytes used
+fn read_bytes(bytes: &[u8]) -> Option<(&[u8], usize)> {
+	let len = read_u32(bytes)? as usize;
+	bytes.get(4..4 + len).map(|b| (b, 4 + len))
+}
+
+/// Flattens a stream of key-value batches into a stream of key-values
+fn entries<'a>(
+	batches: impl Stream<Item = Result<Vec<(Key, Val)>>> + 'a,
+) -> impl Stream<Item = Result<(Key, Val)>> + 'a {
+	batches.map_ok(|batch| stream::iter(batch.into_iter().map(Ok))).try_flatten()
+}
+
+impl Transaction {
+	/// Writes every key of the datastore as a backup archive.
+	///
+	/// When `header.since` is set, only the keys which changed since that
+	/// versionstamp are written. Returns the number of written entries.
+	pub(crate) async fn backup(&self, header: Header, chn: Sender<Vec<u8>>) -> Result<u64> {
+		let mut writer = ArchiveWriter::new(&header, chn)?;
+		let current =
+			self.stream_keys_vals(keyspace(), None, None, 0, ScanDirection::Forward, true);
+		let mut current = pin!(entries(current).try_filter(|(key, _)| ready(!is_node_key(key))));
+		match header.since {
+			None => {
+				while let Some((key, val)) = current.try_next().await? {
+					writer.set(&key, &val).await?;
+				}
+			}
+			Some(since) => {
+				let previous = self.stream_keys_vals(
+					keyspace(),
+					Some(since),
+					None,
+					0,
+					ScanDirection::Forward,
+					true,
+				);
+				let mut previous =
+					pin!(entries(previous).try_filter(|(key, _)| ready(!is_node_key(key))));
+				let unsupported = |e: anyhow::Error| {
+					anyhow::Error::new(Error::IncrementalBackupUnsupported(e.to_string()))
+				};
+				// Both scans are ordered by key, so they can be merged
+				let mut old = previous.try_next().await.map_err(unsupported)?;
+				let mut new = current.try_next().await?;
+				loop {
+					let order = match (&old, &new) {
+						(None, None) => break,
+						(Some(_), None) => Ordering::Less,
+						(None, Some(_)) => Ordering::Greater,
+						(Some((a, _)), Some((b, _))) => a.cmp(b),
+					};
+					match order {
+						// The key does not exist anymore
+						Ordering::Less => {
+							if let Some((key, _)) = &old {
+								writer.del(key).await?;
+							}
+							old = previous.try_next().await.map_err(unsupported)?;
+						}
+						// The key has been created
+						Ordering::Greater => {
+							if let Some((key, val)) = &new {
+								writer.set(key, val).await?;
+							}
+							new = current.try_next().await?;
+						}
+						// The key may have been updated
+						Ordering::Equal => {
+							if let (Some((_, a)), Some((key, b))) = (&old, &new)
+								&& a != b
+							{
+								writer.set(key, b).await?;
+							}
+							old = previous.try_next().await.map_err(unsupported)?;
+							new = current.try_next().await?;
+						}
+					}
+				}
+			}
+		}
+		writer.finish().await
+	}
+
+	/// Checks that the keyspace can still be read as it was at the base
+	/// versionstamp of an incremental backup, before the backup is started.
+	pub(crate) async fn ensure_versioned(&self, since: u64) -> Result<()> {
+		if let Err(e) = self.keys(keyspace(), 1, 0, Some(since)).await {
+			bail!(Error::IncrementalBackupUnsupported(e.to_string()));
+		}
+		Ok(())
+	}
+
+	/// Returns the identifier of the datastore, which is generated by its
+	/// first backup.
+	pub(crate) async fn datastore_id(&self) -> Result<Uuid> {
+		let key = DatastoreId::new();
+		if let Some(id) = self.get(&key, None).await? {
+			return Ok(id);
+		}
+		let id = Uuid::now_v7();
+		self.put(&key, &id, None).await?;
+		Ok(id)
+	}
+
+	/// Applies entries read from a backup archive.
+	///
+	/// Keys belonging to the nodes of the source cluster are skipped.
+	pub(crate) async fn restore(&self, entries: Vec<Entry>) -> Result<()> {
+		for entry in entries {
+			match entry {
+				Entry::Set(key, _) | Entry::Del(key) if is_node_key(&key) => {}
+				Entry::Set(key, val) => self.set(&key, &val, None).await?,
+				Entry::Del(key) => self.del(&key).await?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks that the datastore does not contain any data, before a full
+	/// backup is restored into it.
+	///
+	/// A new datastore only contains its version and the keys of its nodes.
+	pub(crate) async fn ensure_restorable(&self) -> Result<()> {
+		let keys = self.stream_keys(keyspace(), None, None, 0, ScanDirection::Forward);
+		let mut keys = pin!(
+			keys.map_ok(|batch| stream::iter(batch.into_iter().map(Ok::<_, anyhow::Error>)))
+				.try_flatten()
+		);
+		while let Some(key) = keys.try_next().await? {
+			ensure!(
+				is_node_key(&key) || key.as_slice() == VERSION_KEY,
+				Error::InvalidBackup(
+					"a full backup can only be restored into an empty datastore".to_owned()
+				)
+			);
+		}
+		Ok(())
+	}
+
+	/// Checks that an incremental backup is based on the backup which was
+	/// last restored into the datastore, as its changes would otherwise be
+	/// applied onto a different snapshot.
+	pub(crate) async fn ensure_incremental(&self, since: u64) -> Result<()> {
+		let restored = self.get(&RestoredVersionstamp::new(), None).await?;
+		ensure!(
+			restored == Some(since),
+			Error::InvalidBackup(match restored {
+				Some(restored) => format!(
+					"the incremental backup is based on versionstamp {since}, but the datastore was restored up to versionstamp {restored}"
+				),
+				None => format!(
+					"the incremental backup is based on versionstamp {since}, but no backup was restored into the datastore"
+				),
+			})
+		);
+		Ok(())
+	}
+
+	/// Checks that the datastore was restored from a backup of the datastore
+	/// which an archive was taken from, before the archive is applied onto it.
+	pub(crate) async fn ensure_datastore(&self, datastore: Uuid) -> Result<()> {
+		let restored = self.get(&DatastoreId::new(), None).await?;
+		ensure!(
+			restored == Some(datastore),
+			Error::InvalidBackup(match restored {
+				Some(restored) => format!(
+					"the archive was taken from datastore {datastore}, but the datastore was restored from datastore {restored}"
+				),
+				None => format!(
+					"the archive was taken from datastore {datastore}, but no backup of it was restored into the datastore"
+				),
+			})
+		);
+		Ok(())
+	}
+
+	/// Records the versionstamp of a restored backup, which the next
+	/// incremental backup must be based on.
+	pub(crate) async fn set_restored(&self, versionstamp: u64) -> Result<()> {
+		self.set(&RestoredVersionstamp::new(), &versionstamp, None).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bytes::Bytes;
+	use surrealdb_types::ToSql;
+
+	use super::*;
+	use crate::kvs::Datastore;
+	use crate::kvs::LockType::*;
+	use crate::kvs::TransactionType::*;
+
+	async fn backup(ds: &Datastore, since: Option<u64>) -> (Header, Vec<u8>) {
+		let (snd, rcv) = async_channel::unbounded();
+		let (header, job) = ds.backup(since, snd).await.unwrap();
+		job.await.unwrap();
+		let mut archive = Vec::new();
+		while let Ok(chunk) = rcv.try_recv() {
+			archive.extend(chunk);
+		}
+		(header, archive)
+	}
+
+	async fn restore(ds: &Datastore, archive: &[u8]) -> Header {
+		// Split the archive to exercise the incremental decoding
+		let chunks: Vec<Result<Bytes>> =
+			archive.chunks(7).map(|c| Ok(Bytes::copy_from_slice(c))).collect();
+		ds.restore(stream::iter(chunks)).await.unwrap()
+	}
+
+	async fn keys(ds: &Datastore) -> Vec<(Key, Val)> {
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let res = tx.getr(keyspace(), None).await.unwrap();
+		tx.cancel().await.unwrap();
+		res.into_iter().filter(|(key, _)| !is_node_key(key)).collect()
+	}
+
+	#[tokio::test]
+	async fn archive_roundtrip() {
+		let header = Header {
+			datastore: Uuid::now_v7(),
+			versionstamp: 42,
+			since: Some(7),
+			changefeed: 3,
+		};
+		let (snd, rcv) = async_channel::unbounded();
+		let mut writer = ArchiveWriter::new(&header, snd).unwrap();
+		writer.set(b"a", b"1").await.unwrap();
+		writer.del(b"b").await.unwrap();
+		writer.set(b"c", b"").await.unwrap();
+		assert_eq!(writer.finish().await.unwrap(), 3);
+		let archive = rcv.try_recv().unwrap();
+		let mut reader = ArchiveReader::default();
+		let mut entries = Vec::new();
+		for byte in archive {
+			reader.push(&[byte]);
+			while let Some(entry) = reader.next_entry().unwrap() {
+				entries.push(entry);
+			}
+		}
+		assert!(reader.is_finished());
+		assert_eq!(reader.header().unwrap(), Some(header));
+		assert_eq!(
+			entries,
+			vec![
+				Entry::Set(b"a".to_vec(), b"1".to_vec()),
+				Entry::Del(b"b".to_vec()),
+				Entry::Set(b"c".to_vec(), b"".to_vec()),
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn truncated_archive_is_rejected() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("CREATE person:tobie").await;
+		let (_, archive) = backup(&ds, None).await;
+		let chunks = vec![Ok(Bytes::copy_from_slice(&archive[..archive.len() - 1]))];
+		let target = Datastore::new("memory").await.unwrap();
+		let err = target.restore(stream::iter(chunks)).await.unwrap_err();
+		assert!(err.to_string().contains("truncated"), "{err}");
+		let chunks = vec![Ok(Bytes::from_static(b"DEFINE TABLE person;"))];
+		let err = target.restore(stream::iter(chunks)).await.unwrap_err();
+		assert!(err.to_string().contains("not a backup archive"), "{err}");
+	}
+
+	#[tokio::test]
+	async fn full_backup_restores_into_another_datastore() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE TABLE person; DEFINE INDEX age ON person FIELDS age;").await;
+		ds.test_query("CREATE person:tobie SET age = 33; CREATE person:jaime SET age = 28;").await;
+		let (header, archive) = backup(&ds, None).await;
+		assert!(!header.is_incremental());
+		let target = Datastore::new("memory").await.unwrap();
+		assert_eq!(restore(&target, &archive).await, header);
+		assert_eq!(
+			target.test_query("SELECT VALUE id FROM person WHERE age > 30").await,
+			ds.test_query("SELECT VALUE id FROM person WHERE age > 30").await
+		);
+		// The restored keyspace is identical
+		assert_eq!(keys(&ds).await, keys(&target).await);
+	}
+
+	#[tokio::test]
+	async fn full_backup_requires_an_empty_datastore() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("CREATE person:tobie").await;
+		let (_, archive) = backup(&ds, None).await;
+		let target = Datastore::new_test("memory").await;
+		target.test_query("CREATE person:jaime").await;
+		let chunks = vec![Ok(Bytes::from(archive))];
+		let err = target.restore(stream::iter(chunks)).await.unwrap_err();
+		assert!(err.to_string().contains("empty datastore"), "{err}");
+		// Nothing was written into the target
+		let ids = target.test_query("SELECT VALUE id FROM person").await.to_sql();
+		assert!(ids.contains("jaime") && !ids.contains("tobie"), "{ids}");
+	}
+
+	#[test]
+	fn node_keys_are_not_backed_up() {
+		assert!(is_node_key(b"/!ndnode"));
+		assert!(is_node_key(b"/!tlchangefeed_cleanup"));
+		assert!(is_node_key(b"/$node!lq"));
+		assert!(is_node_key(b"/!ri"));
+		assert!(is_node_key(b"/!rv"));
+		assert!(is_node_key(b"/!fuupload"));
+		assert!(is_node_key(b"/!fpupload"));
+		assert!(!is_node_key(b"/!nstest"));
+		assert!(!is_node_key(b"/*test"));
+	}
+
+	#[tokio::test]
+	async fn incremental_backup_contains_changes_since_base() {
+		let ds = Datastore::new_test("mem://?versioned=true").await;
+		ds.test_query("CREATE person:tobie SET age = 33; CREATE person:jaime SET age = 28;").await;
+		let (base, archive) = backup(&ds, None).await;
+		let target = Datastore::new("memory").await.unwrap();
+		restore(&target, &archive).await;
+		let cached = target.test_query("SELECT * FROM person CACHE 1m").await;
+		ds.test_query("UPDATE person:tobie SET age = 34; DELETE person:jaime; CREATE person:john;")
+			.await;
+		let (header, archive) = backup(&ds, Some(base.versionstamp)).await;
+		assert_eq!(header.since, Some(base.versionstamp));
+		assert_eq!(header.datastore, base.datastore);
+		assert!(header.versionstamp >= base.versionstamp);
+		restore(&target, &archive).await;
+		assert_eq!(
+			target.test_query("SELECT * FROM person").await,
+			ds.test_query("SELECT * FROM person").await
+		);
+		// Cached results don't outlive the restore
+		let restored = target.test_query("SELECT * FROM person CACHE 1m").await;
+		assert_ne!(restored, cached);
+		assert_eq!(restored, ds.test_query("SELECT * FROM person").await);
+	}
+
+	#[tokio::test]
+	async fn incremental_backup_requires_its_base() {
+		let ds = Datastore::new_test("mem://?versioned=true").await;
+		ds.test_query("CREATE person:tobie").await;
+		let (first, archive) = backup(&ds, None).await;
+		ds.test_query("CREATE person:jaime").await;
+		let (second, _) = backup(&ds, None).await;
+		ds.test_query("CREATE person:john").await;
+		let (_, incremental) = backup(&ds, Some(second.versionstamp)).await;
+		// A datastore which no backup was restored into
+		let target = Datastore::new_test("memory").await;
+		let chunks = vec![Ok(Bytes::from(incremental.clone()))];
+		let err = target.restore(stream::iter(chunks)).await.unwrap_err();
+		assert!(err.to_string().contains("no backup was restored"), "{err}");
+		// A datastore which was restored from an older backup
+		let target = Datastore::new("memory").await.unwrap();
+		restore(&target, &archive).await;
+		let chunks = vec![Ok(Bytes::from(incremental))];
+		let err = target.restore(stream::iter(chunks)).await.unwrap_err();
+		let expected = format!("restored up to versionstamp {}", first.versionstamp);
+		assert!(err.to_string().contains(&expected), "{err}");
+		// Nothing was written into the target
+		let ids = target.test_query("SELECT VALUE id FROM person").await.to_sql();
+		assert!(ids.contains("tobie") && !ids.contains("jaime"), "{ids}");
+	}
+
+	#[tokio::test]
+	async fn incremental_backup_requires_the_same_datastore() {
+		let ds = Datastore::new_test("mem://?versioned=true").await;
+		ds.test_query("CREATE person:tobie").await;
+		let (base, _) = backup(&ds, None).await;
+		ds.test_query("CREATE person:jaime").await;
+		let (_, incremental) = backup(&ds, Some(base.versionstamp)).await;
+		// A datastore restored from a backup of another datastore
+		let other = Datastore::new_test("memory").await;
+		let (header, archive) = backup(&other, None).await;
+		assert_ne!(header.datastore, base.datastore);
+		let target = Datastore::new("memory").await.unwrap();
+		restore(&target, &archive).await;
+		// Pretend the other backup was taken at the same versionstamp
+		let txn = target.transaction(Write, Optimistic).await.unwrap();
+		txn.set_restored(base.versionstamp).await.unwrap();
+		txn.commit().await.unwrap();
+		let chunks = vec![Ok(Bytes::from(incremental))];
+		let err = target.restore(stream::iter(chunks)).await.unwrap_err();
+		let expected = format!("taken from datastore {}", base.datastore);
+		assert!(err.to_string().contains(&expected), "{err}");
+		assert!(!target.test_query("SELECT VALUE id FROM person").await.to_sql().contains("jaime"));
+	}
+}
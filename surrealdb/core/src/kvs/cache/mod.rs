@@ -1,2 +1,3 @@
 pub(crate) mod ds;
+pub(crate) mod result;
 pub(crate) mod tx;
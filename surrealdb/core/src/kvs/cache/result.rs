@@ -0,0 +1,633 @@
+//! Cross-transaction cache of the results of `SELECT ... CACHE` statements.
+//!
+//! A cached result is keyed on the statement, on the values of the parameters
+//! which it uses, and on the namespace, database and authentication of the
+//! session. It is only served while none of the tables which the statement
+//! reads have received a write, and while the definitions of those tables are
+//! unchanged. Writes are recorded by the transactions of this datastore once
+//! they are committed.
+//!
+//! When the storage engine is shared by the nodes of a cluster, each
+//! transaction also publishes the tables which it wrote in the datastore, and
+//! each node periodically applies the invalidations published by the others.
+//! Writes committed on other nodes are therefore seen after the interval at
+//! which the invalidations are received.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use parking_lot::{Mutex, RwLock};
+use revision::revisioned;
+use uuid::Uuid;
+use web_time::Instant;
+
+use crate::catalog::providers::{DatabaseProvider, TableProvider};
+use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::expr::statements::SelectStatement;
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{Expr, Function, Idiom, Literal, Part, RecordIdLit};
+use crate::iam::Auth;
+use crate::key::root::ri::ResultInvalidation;
+use crate::kvs::{KVValue, Transaction, impl_kv_value_revisioned};
+use crate::val::{Datetime, TableName, Value};
+
+/// The tables written by a transaction, grouped by database
+pub(crate) type Written = HashMap<(NamespaceId, DatabaseId), HashSet<TableName>>;
+
+/// How far before the most recent invalidation received the invalidations
+/// are read again, as concurrent commits can publish in any order
+const INVALIDATION_WINDOW: Duration = Duration::from_secs(10);
+
+/// How long the published invalidations are kept, which is how long every
+/// node has to receive them
+const INVALIDATION_RETENTION: Duration = Duration::from_secs(60);
+
+/// The tables written by a transaction, as published to the other nodes which
+/// share the storage engine
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Invalidation {
+	/// The written tables of each database, or `None` when every cached result
+	/// is invalidated
+	tables: Option<Vec<(NamespaceId, DatabaseId, Vec<TableName>)>>,
+}
+
+impl_kv_value_revisioned!(Invalidation);
+
+impl Invalidation {
+	/// Creates an invalidation of the written tables
+	pub(crate) fn written(written: &Written) -> Self {
+		let tables = written
+			.iter()
+			.map(|((ns, db), tables)| (*ns, *db, tables.iter().cloned().collect()))
+			.collect();
+		Self {
+			tables: Some(tables),
+		}
+	}
+
+	/// Creates an invalidation of every cached result
+	pub(crate) fn all() -> Self {
+		Self {
+			tables: None,
+		}
+	}
+}
+
+/// The invalidations received from the other nodes
+#[derive(Default)]
+struct Received {
+	/// The timestamp from which the next invalidations are read
+	from: u64,
+	/// The invalidations already applied, with their timestamps
+	seen: HashMap<Uuid, u64>,
+}
+
+/// Identifies a cached result
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct ResultKey {
+	ns: String,
+	db: String,
+	auth: Auth,
+	stm: SelectStatement,
+	vars: Vec<(String, Value)>,
+}
+
+/// A statement whose result can be cached
+pub(crate) struct Cacheable {
+	key: ResultKey,
+	ttl: Duration,
+	/// The write version when the statement started
+	since: u64,
+	/// The tables read by the statement
+	tables: BTreeSet<TableName>,
+	/// Whether the statement reads only the tables above
+	complete: bool,
+}
+
+/// A cached result
+struct CachedResult {
+	value: Value,
+	expires: Instant,
+	since: u64,
+	ns: NamespaceId,
+	db: DatabaseId,
+	/// The tables read, along with their schema when the result was computed
+	tables: Vec<(TableName, Option<[Uuid; 4]>)>,
+	complete: bool,
+}
+
+/// The last write version of a database and of each of its tables
+#[derive(Default)]
+struct DatabaseVersions {
+	version: u64,
+	tables: HashMap<TableName, u64>,
+}
+
+pub(crate) struct ResultCache {
+	/// Store the cached results
+	entries: quick_cache::sync::Cache<ResultKey, Arc<CachedResult>>,
+	/// The last assigned write version
+	counter: AtomicU64,
+	/// The write versions of the databases and tables
+	versions: RwLock<HashMap<(NamespaceId, DatabaseId), DatabaseVersions>>,
+	/// The write version when every cached result was last invalidated
+	cleared: AtomicU64,
+	/// The invalidations received from the other nodes
+	received: Mutex<Received>,
+}
+
+impl ResultCache {
+	/// Creates a new result cache
+	pub(in crate::kvs) fn new() -> Self {
+		Self {
+			entries: quick_cache::sync::Cache::new(*crate::cnf::QUERY_RESULT_CACHE_SIZE),
+			counter: AtomicU64::new(0),
+			versions: RwLock::new(HashMap::new()),
+			cleared: AtomicU64::new(0),
+			received: Mutex::new(Received::default()),
+		}
+	}
+
+	/// Invalidates every cached result
+	pub(crate) fn clear(&self) {
+		let version = self.counter.fetch_add(1, Ordering::AcqRel) + 1;
+		self.cleared.fetch_max(version, Ordering::AcqRel);
+		self.entries.clear();
+	}
+
+	/// Invalidates every cached result, on this node and, when the storage
+	/// engine is shared, on the other nodes of the cluster
+	pub(crate) async fn invalidate_all(&self, tx: &Transaction) -> Result<()> {
+		self.clear();
+		if !tx.is_local() {
+			self.publish(tx, &Invalidation::all()).await?;
+		}
+		Ok(())
+	}
+
+	/// Publishes an invalidation to the other nodes which share the storage
+	/// engine, as part of the given transaction
+	pub(crate) async fn publish(
+		&self,
+		tx: &Transaction,
+		invalidation: &Invalidation,
+	) -> Result<()> {
+		let key = ResultInvalidation::new(Datetime::now().to_version_stamp()?, Uuid::new_v4());
+		// This node applies its own invalidations when they are committed
+		self.received.lock().seen.insert(key.id, key.ts);
+		tx.set(&key, invalidation, None).await
+	}
+
+	/// Applies the invalidations published by the other nodes since the last
+	/// call.
+	///
+	/// The invalidations are read again from a little before the most recent
+	/// one received, skipping those already applied, as transactions which
+	/// commit concurrently can publish them out of order. If more were
+	/// published than can be read at once, every cached result is invalidated.
+	pub(crate) async fn receive(&self, tx: &Transaction) -> Result<()> {
+		let from = self.received.lock().from;
+		let (beg, end) = ResultInvalidation::range(from);
+		let limit = *crate::cnf::NORMAL_FETCH_SIZE;
+		let res = tx.scan(beg..end, limit, 0, None).await?;
+		let truncated = res.len() >= limit as usize;
+		let mut published = Vec::with_capacity(res.len());
+		for (k, v) in res {
+			let key = ResultInvalidation::decode_key(&k)?;
+			published.push((key, v));
+		}
+		// Skip the invalidations which were already applied
+		let pending = {
+			let mut received = self.received.lock();
+			let mut pending = Vec::new();
+			let mut last = from;
+			for (key, v) in published {
+				last = last.max(key.ts);
+				if received.seen.insert(key.id, key.ts).is_none() {
+					pending.push(v);
+				}
+			}
+			let window = INVALIDATION_WINDOW.as_nanos() as u64;
+			received.from = match truncated {
+				// Continue after the invalidations which were read
+				true => last,
+				false => last.saturating_sub(window).max(from),
+			};
+			let horizon = received.from.min(last.saturating_sub(window));
+			received.seen.retain(|_, ts| *ts >= horizon);
+			pending
+		};
+		if truncated {
+			self.clear();
+			return Ok(());
+		}
+		for v in pending {
+			match Invalidation::kv_decode_value(v)?.tables {
+				None => self.clear(),
+				Some(tables) => self.written(
+					tables
+						.into_iter()
+						.map(|(ns, db, tables)| ((ns, db), tables.into_iter().collect()))
+						.collect(),
+				),
+			}
+		}
+		Ok(())
+	}
+
+	/// Removes the published invalidations which every node has received
+	pub(crate) async fn expire(tx: &Transaction) -> Result<()> {
+		let retention = INVALIDATION_RETENTION.as_nanos() as u64;
+		let before = Datetime::now().to_version_stamp()?.saturating_sub(retention);
+		let (beg, end) = ResultInvalidation::before(before);
+		// Avoid a write when there is nothing to remove
+		if tx.keys(beg.clone()..end.clone(), 1, 0, None).await?.is_empty() {
+			return Ok(());
+		}
+		tx.delr(beg..end).await
+	}
+
+	/// Records the tables written by a committed transaction
+	pub(crate) fn written(&self, written: Written) {
+		let mut versions = self.versions.write();
+		let version = self.counter.fetch_add(1, Ordering::AcqRel) + 1;
+		for (db, tables) in written {
+			let db = versions.entry(db).or_default();
+			db.version = version;
+			for tb in tables {
+				db.tables.insert(tb, version);
+			}
+		}
+	}
+
+	/// Checks if any of the tables were written after the given version
+	fn changed<'a>(
+		&self,
+		since: u64,
+		db: (NamespaceId, DatabaseId),
+		complete: bool,
+		mut tables: impl Iterator<Item = &'a TableName>,
+	) -> bool {
+		if self.cleared.load(Ordering::Acquire) > since {
+			return true;
+		}
+		let versions = self.versions.read();
+		let Some(db) = versions.get(&db) else {
+			return false;
+		};
+		if !complete {
+			return db.version > since;
+		}
+		tables.any(|tb| db.tables.get(tb).is_some_and(|v| *v > since))
+	}
+
+	/// Checks if the result of a statement can be cached
+	pub(crate) fn prepare(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		stm: &SelectStatement,
+	) -> Option<Cacheable> {
+		let ttl = stm.cache?;
+		let (ns, db) = opt.ns_db().ok()?;
+		// Find the tables read by the statement
+		let mut reads = Reads {
+			tables: BTreeSet::new(),
+			params: BTreeSet::new(),
+			complete: true,
+		};
+		let _ = reads.visit_select(stm);
+		// Any computed target could be a record in any table
+		if !stm.what.iter().all(|w| {
+			matches!(w, Expr::Table(_) | Expr::Literal(Literal::RecordId(_)) | Expr::Select(_))
+		}) {
+			reads.complete = false;
+		}
+		// Table permissions apply to record users, and can read any table
+		if opt.auth.is_record() {
+			reads.complete = false;
+		}
+		let vars = reads
+			.params
+			.into_iter()
+			.map(|p| {
+				let v = ctx.value(&p).cloned().unwrap_or(Value::None);
+				(p, v)
+			})
+			.collect();
+		Some(Cacheable {
+			key: ResultKey {
+				ns: ns.to_owned(),
+				db: db.to_owned(),
+				auth: opt.auth.as_ref().clone(),
+				stm: stm.clone(),
+				vars,
+			},
+			ttl,
+			since: self.counter.load(Ordering::Acquire),
+			tables: reads.tables,
+			complete: reads.complete,
+		})
+	}
+
+	/// Fetches the cached result of a statement, if it is still valid
+	pub(crate) async fn get(&self, tx: &Transaction, stm: &Cacheable) -> Result<Option<Value>> {
+		let Some(entry) = self.entries.get(&stm.key) else {
+			return Ok(None);
+		};
+		if entry.expires <= Instant::now()
+			|| self.changed(
+				entry.since,
+				(entry.ns, entry.db),
+				entry.complete,
+				entry.tables.iter().map(|(tb, _)| tb),
+			) || !entry.current(tx, &stm.key).await?
+		{
+			self.entries.remove(&stm.key);
+			return Ok(None);
+		}
+		Ok(Some(entry.value.clone()))
+	}
+
+	/// Stores the result of a statement
+	pub(crate) async fn insert(
+		&self,
+		tx: &Transaction,
+		stm: Cacheable,
+		value: &Value,
+	) -> Result<()> {
+		let Some(db) = tx.get_db_by_name(&stm.key.ns, &stm.key.db).await? else {
+			return Ok(());
+		};
+		let (ns, db) = (db.namespace_id, db.database_id);
+		let mut complete = stm.complete;
+		let mut tables = Vec::with_capacity(stm.tables.len());
+		for tb in stm.tables {
+			let def = tx.get_tb(ns, db, &tb).await?;
+			// Computed fields are evaluated when read, and can read any table
+			if complete && def.is_some() {
+				let fields = tx.all_tb_fields(ns, db, &tb, None).await?;
+				if fields.iter().any(|f| f.computed.is_some()) {
+					complete = false;
+				}
+			}
+			tables.push((tb, def.as_deref().map(schema)));
+		}
+		// Don't store a result which is already out of date
+		if self.changed(stm.since, (ns, db), complete, tables.iter().map(|(tb, _)| tb)) {
+			return Ok(());
+		}
+		let entry = CachedResult {
+			value: value.clone(),
+			expires: Instant::now() + stm.ttl,
+			since: stm.since,
+			ns,
+			db,
+			tables,
+			complete,
+		};
+		self.entries.insert(stm.key, Arc::new(entry));
+		Ok(())
+	}
+}
+
+impl CachedResult {
+	/// Checks that the schema of the tables read is unchanged
+	async fn current(&self, tx: &Transaction, key: &ResultKey) -> Result<bool> {
+		match tx.get_db_by_name(&key.ns, &key.db).await? {
+			Some(db) if db.namespace_id == self.ns && db.database_id == self.db => {}
+			_ => return Ok(false),
+		}
+		for (tb, ts) in self.tables.iter() {
+			let def = tx.get_tb(self.ns, self.db, tb).await?;
+			if def.as_deref().map(schema) != *ts {
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+}
+
+/// The timestamps of the last schema changes on a table
+fn schema(tb: &TableDefinition) -> [Uuid; 4] {
+	[tb.cache_tables_ts, tb.cache_fields_ts, tb.cache_events_ts, tb.cache_indexes_ts]
+}
+
+/// Visitor which finds the tables read by a statement.
+struct Reads {
+	tables: BTreeSet<TableName>,
+	params: BTreeSet<String>,
+	/// Whether all the tables read could be statically determined
+	complete: bool,
+}
+
+impl Visitor for Reads {
+	type Error = Infallible;
+
+	fn visit_expr(&mut self, expr: &Expr) -> Result<(), Self::Error> {
+		match expr {
+			Expr::Table(tb) => {
+				self.tables.insert(tb.clone());
+			}
+			Expr::Param(p) => {
+				self.params.insert(p.as_str().to_owned());
+			}
+			Expr::Select(s) => {
+				self.visit_select(s)?;
+			}
+			// Other statements can read any table
+			Expr::Create(_)
+			| Expr::Update(_)
+			| Expr::Upsert(_)
+			| Expr::Delete(_)
+			| Expr::Relate(_)
+			| Expr::Insert(_)
+			| Expr::Define(_)
+			| Expr::Remove(_)
+			| Expr::Rebuild(_)
+			| Expr::Run(_)
+			| Expr::History(_)
+			| Expr::Alter(_)
+			| Expr::Info(_)
+			| Expr::Foreach(_)
+			| Expr::Let(_) => {
+				self.complete = false;
+				expr.visit(self)?;
+			}
+			_ => {
+				expr.visit(self)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn visit_select(&mut self, s: &SelectStatement) -> Result<(), Self::Error> {
+		// Fetched record links can point to any table
+		if s.fetch.is_some() {
+			self.complete = false;
+		}
+		s.visit(self)
+	}
+
+	fn visit_record_id(&mut self, rid: &RecordIdLit) -> Result<(), Self::Error> {
+		self.tables.insert(rid.table.clone());
+		rid.visit(self)
+	}
+
+	fn visit_idiom(&mut self, idiom: &Idiom) -> Result<(), Self::Error> {
+		// Any nested part could follow a record link to another table
+		if idiom.0.len() > 1 {
+			self.complete = false;
+		}
+		idiom.visit(self)
+	}
+
+	fn visit_part(&mut self, part: &Part) -> Result<(), Self::Error> {
+		// Graph traversals read the edge tables
+		if let Part::Lookup(_) = part {
+			self.complete = false;
+		}
+		part.visit(self)
+	}
+
+	fn visit_function(&mut self, f: &Function) -> Result<(), Self::Error> {
+		match f {
+			Function::Normal(name)
+				if !name.starts_with("record::") && !name.starts_with("search::") => {}
+			// User defined functions, scripts and models can read any table
+			_ => self.complete = false,
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::{HashMap, HashSet};
+
+	use uuid::Uuid;
+
+	use super::Invalidation;
+	use crate::catalog::providers::DatabaseProvider;
+	use crate::dbs::Session;
+	use crate::key::root::ri::ResultInvalidation;
+	use crate::kvs::Datastore;
+	use crate::kvs::LockType::*;
+	use crate::kvs::TransactionType::*;
+	use crate::types::{PublicValue, PublicVariables};
+	use crate::val::{Datetime, TableName};
+
+	async fn run(
+		ds: &Datastore,
+		ses: &Session,
+		sql: &str,
+		vars: Option<PublicVariables>,
+	) -> PublicValue {
+		let res = ds.execute(sql, ses, vars).await.unwrap();
+		res.into_iter().map(|r| r.result.unwrap()).next_back().unwrap()
+	}
+
+	#[tokio::test]
+	async fn invalidates_on_write() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		run(&ds, &ses, "CREATE order:1 SET total = 10; CREATE customer:1", None).await;
+		let sql = "SELECT rand::uuid() AS r FROM order:1 CACHE 1m";
+		let first = run(&ds, &ses, sql, None).await;
+		// The result is served from the cache
+		assert_eq!(run(&ds, &ses, sql, None).await, first);
+		// Writes to other tables keep the cached result
+		run(&ds, &ses, "UPDATE customer:1 SET name = 'Tobie'", None).await;
+		assert_eq!(run(&ds, &ses, sql, None).await, first);
+		// Writes to the table read invalidate the cached result
+		run(&ds, &ses, "UPDATE order:1 SET total = 20", None).await;
+		let second = run(&ds, &ses, sql, None).await;
+		assert_ne!(second, first);
+		assert_eq!(run(&ds, &ses, sql, None).await, second);
+		// Schema changes on the table read invalidate the cached result
+		run(&ds, &ses, "DEFINE FIELD total ON order TYPE int", None).await;
+		assert_ne!(run(&ds, &ses, sql, None).await, second);
+		// Statements without CACHE are never served from the cache
+		let sql = "SELECT rand::uuid() AS r FROM order:1";
+		assert_ne!(run(&ds, &ses, sql, None).await, run(&ds, &ses, sql, None).await);
+	}
+
+	#[tokio::test]
+	async fn keys_on_parameters() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		run(&ds, &ses, "CREATE order:1 SET total = 10; CREATE order:2 SET total = 20", None).await;
+		let sql = "SELECT VALUE total FROM order WHERE total > $min CACHE 1m";
+		let vars = |min: i64| {
+			PublicVariables::from_iter([("min".to_string(), PublicValue::Number(min.into()))])
+		};
+		let all = run(&ds, &ses, sql, Some(vars(5))).await;
+		let some = run(&ds, &ses, sql, Some(vars(15))).await;
+		assert_eq!(all.into_json_value().as_array().unwrap().len(), 2);
+		assert_eq!(some.into_json_value().as_array().unwrap().len(), 1);
+		// Other sessions don't share the cached result
+		let ses = Session::owner().with_ns("test").with_db("other");
+		run(&ds, &ses, "CREATE order:3 SET total = 30", None).await;
+		let other = run(&ds, &ses, sql, Some(vars(5))).await;
+		assert_eq!(other.into_json_value().as_array().unwrap().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn applies_published_invalidations() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		run(&ds, &ses, "CREATE order:1 SET total = 10; CREATE customer:1", None).await;
+		let sql = "SELECT rand::uuid() AS r FROM order:1 CACHE 1m";
+		let first = run(&ds, &ses, sql, None).await;
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let db = tx.get_db_by_name("test", "test").await.unwrap().unwrap();
+		tx.cancel().await.unwrap();
+		let written = |tb: &str| {
+			let tables = HashSet::from([TableName::from(tb)]);
+			Invalidation::written(&HashMap::from([((db.namespace_id, db.database_id), tables)]))
+		};
+		let receive = async || {
+			let tx = ds.transaction(Read, Optimistic).await.unwrap();
+			ds.result_cache().receive(&tx).await.unwrap();
+			tx.cancel().await.unwrap();
+		};
+		let publish = async |invalidation: Invalidation| {
+			let ts = Datetime::now().to_version_stamp().unwrap();
+			let tx = ds.transaction(Write, Optimistic).await.unwrap();
+			tx.set(&ResultInvalidation::new(ts, Uuid::new_v4()), &invalidation, None)
+				.await
+				.unwrap();
+			tx.commit().await.unwrap();
+		};
+		// Invalidations published by this node are not applied again
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		ds.result_cache().publish(&tx, &written("order")).await.unwrap();
+		tx.commit().await.unwrap();
+		receive().await;
+		assert_eq!(run(&ds, &ses, sql, None).await, first);
+		// Invalidations of other tables keep the cached result
+		publish(written("customer")).await;
+		receive().await;
+		assert_eq!(run(&ds, &ses, sql, None).await, first);
+		// Invalidations of the table read, published by other nodes, are applied
+		publish(written("order")).await;
+		assert_eq!(run(&ds, &ses, sql, None).await, first);
+		receive().await;
+		let second = run(&ds, &ses, sql, None).await;
+		assert_ne!(second, first);
+		// Invalidations are only applied once
+		receive().await;
+		assert_eq!(run(&ds, &ses, sql, None).await, second);
+		// Invalidations of every table are applied
+		publish(Invalidation::all()).await;
+		receive().await;
+		assert_ne!(run(&ds, &ses, sql, None).await, second);
+	}
+}
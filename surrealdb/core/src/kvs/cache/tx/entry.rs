@@ -44,6 +44,14 @@ pub(crate) enum Entry {
 	Dus(Arc<[catalog::UserDefinition]>),
 	/// A slice of DefineFunctionStatement specified on a database.
 	Fcs(Arc<[catalog::FunctionDefinition]>),
+	/// A slice of DefineJobStatement specified on a database.
+	Jbs(Arc<[catalog::JobDefinition]>),
+	/// A slice of DefineSinkStatement specified on a database.
+	Sks(Arc<[catalog::SinkDefinition]>),
+	/// A slice of DefineConsumerStatement specified on a database.
+	Css(Arc<[catalog::ConsumerDefinition]>),
+	/// A slice of DefineKeyStatement specified on a database.
+	Kys(Arc<[catalog::KeyDefinition]>),
 	/// A slice of DefineModuleStatement specified on a database.
 	Mds(Arc<[catalog::ModuleDefinition]>),
 	/// A slice of TableDefinition specified on a database.
@@ -220,6 +228,40 @@ impl Entry {
 		}
 	}
 
+	/// Converts this cache entry into a slice of [`catalog::JobDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Jbs`].
+	pub(crate) fn try_into_jbs(self) -> Result<Arc<[catalog::JobDefinition]>> {
+		match self {
+			Entry::Jbs(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Jbs"),
+		}
+	}
+
+	/// Converts this cache entry into a slice of [`catalog::SinkDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Sks`].
+	pub(crate) fn try_into_sks(self) -> Result<Arc<[catalog::SinkDefinition]>> {
+		match self {
+			Entry::Sks(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Sks"),
+		}
+	}
+	/// Converts this cache entry into a slice of [`catalog::ConsumerDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Css`].
+	pub(crate) fn try_into_css(self) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		match self {
+			Entry::Css(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Css"),
+		}
+	}
+	/// Converts this cache entry into a slice of [`catalog::KeyDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Kys`].
+	pub(crate) fn try_into_kys(self) -> Result<Arc<[catalog::KeyDefinition]>> {
+		match self {
+			Entry::Kys(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Kys"),
+		}
+	}
+
 	/// Converts this cache entry into a slice of [`catalog::ModuleDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Mds`].
 	pub(crate) fn try_into_mds(self) -> Result<Arc<[catalog::ModuleDefinition]>> {
@@ -38,6 +38,10 @@ pub(crate) enum Key {
 	Bus(NamespaceId, DatabaseId),
 	/// A cache key for functions (on a database)
 	Fcs(NamespaceId, DatabaseId),
+	/// A cache key for jobs (on a database)
+	Jbs(NamespaceId, DatabaseId),
+	/// A cache key for sinks (on a database)
+	Sks(NamespaceId, DatabaseId),
 	/// A cache key for modules (on a database)
 	Mds(NamespaceId, DatabaseId),
 	/// A cache key for models (on a database)
@@ -94,6 +98,18 @@ pub(crate) enum Key {
 	Bu(NamespaceId, DatabaseId, String),
 	/// A cache key for a function (on a database)
 	Fc(NamespaceId, DatabaseId, String),
+	/// A cache key for a job (on a database)
+	Jb(NamespaceId, DatabaseId, String),
+	/// A cache key for a sink (on a database)
+	Sk(NamespaceId, DatabaseId, String),
+	/// A cache key for consumers (on a database)
+	Css(NamespaceId, DatabaseId),
+	/// A cache key for a consumer (on a database)
+	Cs(NamespaceId, DatabaseId, String),
+	/// A cache key for keys (on a database)
+	Kys(NamespaceId, DatabaseId),
+	/// A cache key for a key (on a database)
+	Ky(NamespaceId, DatabaseId, String),
 	/// A cache key for a module (on a database)
 	Md(NamespaceId, DatabaseId, String),
 	/// A cache key for a model (on a database)
@@ -139,6 +155,8 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Azs(a, b) => Key::Azs(a, b),
 			Lookup::Bus(a, b) => Key::Bus(a, b),
 			Lookup::Fcs(a, b) => Key::Fcs(a, b),
+			Lookup::Jbs(a, b) => Key::Jbs(a, b),
+			Lookup::Sks(a, b) => Key::Sks(a, b),
 			Lookup::Mds(a, b) => Key::Mds(a, b),
 			Lookup::Mls(a, b) => Key::Mls(a, b),
 			Lookup::Cgs(a, b) => Key::Cgs(a, b),
@@ -168,6 +186,12 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Az(a, b, c) => Key::Az(a, b, c.to_string()),
 			Lookup::Bu(a, b, c) => Key::Bu(a, b, c.to_string()),
 			Lookup::Fc(a, b, c) => Key::Fc(a, b, c.to_string()),
+			Lookup::Jb(a, b, c) => Key::Jb(a, b, c.to_string()),
+			Lookup::Sk(a, b, c) => Key::Sk(a, b, c.to_string()),
+			Lookup::Css(a, b) => Key::Css(a, b),
+			Lookup::Cs(a, b, c) => Key::Cs(a, b, c.to_string()),
+			Lookup::Kys(a, b) => Key::Kys(a, b),
+			Lookup::Ky(a, b, c) => Key::Ky(a, b, c.to_string()),
 			Lookup::Md(a, b, c) => Key::Md(a, b, c.to_string()),
 			Lookup::Ml(a, b, c, d) => Key::Ml(a, b, c.to_string(), d.to_string()),
 			Lookup::Cg(a, b, c) => Key::Cg(a, b, c.to_string()),
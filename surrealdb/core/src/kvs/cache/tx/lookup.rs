@@ -39,6 +39,10 @@ pub(crate) enum Lookup<'a> {
 	Bus(NamespaceId, DatabaseId),
 	/// A cache key for functions (on a database)
 	Fcs(NamespaceId, DatabaseId),
+	/// A cache key for jobs (on a database)
+	Jbs(NamespaceId, DatabaseId),
+	/// A cache key for sinks (on a database)
+	Sks(NamespaceId, DatabaseId),
 	/// A cache key for modules (on a database)
 	Mds(NamespaceId, DatabaseId),
 	/// A cache key for models (on a database)
@@ -95,6 +99,18 @@ pub(crate) enum Lookup<'a> {
 	Bu(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a function (on a database)
 	Fc(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a job (on a database)
+	Jb(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a sink (on a database)
+	Sk(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for consumers (on a database)
+	Css(NamespaceId, DatabaseId),
+	/// A cache key for a consumer (on a database)
+	Cs(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for keys (on a database)
+	Kys(NamespaceId, DatabaseId),
+	/// A cache key for a key (on a database)
+	Ky(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a module (on a database)
 	Md(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a model (on a database)
@@ -140,6 +156,8 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Azs(la, lb), Key::Azs(ka, kb)) => la == ka && lb == kb,
 			(Self::Bus(la, lb), Key::Bus(ka, kb)) => la == ka && lb == kb,
 			(Self::Fcs(la, lb), Key::Fcs(ka, kb)) => la == ka && lb == kb,
+			(Self::Jbs(la, lb), Key::Jbs(ka, kb)) => la == ka && lb == kb,
+			(Self::Sks(la, lb), Key::Sks(ka, kb)) => la == ka && lb == kb,
 			(Self::Mds(la, lb), Key::Mds(ka, kb)) => la == ka && lb == kb,
 			(Self::Mls(la, lb), Key::Mls(ka, kb)) => la == ka && lb == kb,
 			(Self::Cgs(la, lb), Key::Cgs(ka, kb)) => la == ka && lb == kb,
@@ -169,6 +187,12 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Az(la, lb, lc), Key::Az(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Bu(la, lb, lc), Key::Bu(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Fc(la, lb, lc), Key::Fc(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Jb(la, lb, lc), Key::Jb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Sk(la, lb, lc), Key::Sk(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Css(la, lb), Key::Css(ka, kb)) => la == ka && lb == kb,
+			(Self::Cs(la, lb, lc), Key::Cs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Kys(la, lb), Key::Kys(ka, kb)) => la == ka && lb == kb,
+			(Self::Ky(la, lb, lc), Key::Ky(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Md(la, lb, lc), Key::Md(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ml(la, lb, lc, ld), Key::Ml(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Cg(la, lb, lc), Key::Cg(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -233,6 +257,16 @@ mod tests {
 		Key::Fcs(NamespaceId(1), DatabaseId(1)),
 		true
 	)]
+	#[case(
+		Lookup::Jbs(NamespaceId(1), DatabaseId(1)),
+		Key::Jbs(NamespaceId(1), DatabaseId(1)),
+		true
+	)]
+	#[case(
+		Lookup::Sks(NamespaceId(1), DatabaseId(1)),
+		Key::Sks(NamespaceId(1), DatabaseId(1)),
+		true
+	)]
 	#[case(
 		Lookup::Mls(NamespaceId(1), DatabaseId(1)),
 		Key::Mls(NamespaceId(1), DatabaseId(1)),
@@ -274,6 +308,20 @@ mod tests {
 	#[case(Lookup::Az(NamespaceId(1), DatabaseId(1), "test"), Key::Az(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Bu(NamespaceId(1), DatabaseId(1), "test"), Key::Bu(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Fc(NamespaceId(1), DatabaseId(1), "test"), Key::Fc(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Jb(NamespaceId(1), DatabaseId(1), "test"), Key::Jb(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Sk(NamespaceId(1), DatabaseId(1), "test"), Key::Sk(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(
+		Lookup::Css(NamespaceId(1), DatabaseId(1)),
+		Key::Css(NamespaceId(1), DatabaseId(1)),
+		true
+	)]
+	#[case(Lookup::Cs(NamespaceId(1), DatabaseId(1), "test"), Key::Cs(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(
+		Lookup::Kys(NamespaceId(1), DatabaseId(1)),
+		Key::Kys(NamespaceId(1), DatabaseId(1)),
+		true
+	)]
+	#[case(Lookup::Ky(NamespaceId(1), DatabaseId(1), "test"), Key::Ky(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Ml(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ml(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Cg(NamespaceId(1), DatabaseId(1), "test"), Key::Cg(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Pa(NamespaceId(1), DatabaseId(1), "test"), Key::Pa(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 use std::fmt;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 
+use super::encryption::EncryptionKey;
 use super::err::{Error, Result};
 
 // --------------------------------------------------
@@ -18,14 +19,14 @@ static SURREAL_DATASTORE_SYNC_DATA: LazyLock<Option<String>> =
 
 /// Whether MVCC versioning is enabled (default: false).
 /// This is an alternative to the `versioned` query parameter.
-/// Only used by the 'memory' and 'surrealkv' engines.
+/// Used by the 'memory', 'surrealkv', 'rocksdb', and 'tikv' engines.
 /// Accepts: "true", "false", "1", "0".
 static SURREAL_DATASTORE_VERSIONED: LazyLock<Option<String>> =
 	lazy_env_parse!("SURREAL_DATASTORE_VERSIONED", Option<String>);
 
 /// Version retention period as a duration string (default: 0 / unlimited).
 /// This is an alternative to the `retention` query parameter.
-/// Only used by the 'memory' and 'surrealkv' engines.
+/// Used by the 'memory', 'surrealkv', 'rocksdb', and 'tikv' engines.
 /// Accepts: a duration string (e.g. "30d", "24h").
 static SURREAL_DATASTORE_RETENTION: LazyLock<Option<String>> =
 	lazy_env_parse!("SURREAL_DATASTORE_RETENTION", Option<String>);
@@ -51,6 +52,19 @@ static SURREAL_DATASTORE_AOL: LazyLock<Option<String>> =
 static SURREAL_DATASTORE_SNAPSHOT: LazyLock<Option<String>> =
 	lazy_env_parse!("SURREAL_DATASTORE_SNAPSHOT", Option<String>);
 
+/// Path to a file containing the datastore encryption key.
+/// This is an alternative to the `key_file` query parameter.
+/// Used by the 'surrealkv' and 'rocksdb' engines.
+/// Accepts: a filesystem path to a file with 32 bytes of key material, or the key encoded as base64.
+static SURREAL_DATASTORE_KEY_FILE: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_DATASTORE_KEY_FILE", Option<String>);
+
+/// The datastore encryption key, used when no key file is specified.
+/// Used by the 'surrealkv' and 'rocksdb' engines.
+/// Accepts: 32 bytes of key material encoded as base64.
+static SURREAL_DATASTORE_KEY: LazyLock<Option<String>> =
+	lazy_env_parse!("SURREAL_DATASTORE_KEY", Option<String>);
+
 // --------------------------------------------------
 // Query parameter parsing helpers
 // --------------------------------------------------
@@ -183,6 +197,8 @@ pub struct SurrealKvConfig {
 	pub retention_ns: u64,
 	/// Disk sync mode.
 	pub sync_mode: SyncMode,
+	/// The key used for encrypting the data at rest, if any.
+	pub encryption: Option<Arc<EncryptionKey>>,
 }
 
 impl Default for SurrealKvConfig {
@@ -191,6 +207,7 @@ impl Default for SurrealKvConfig {
 			versioned: false,
 			retention_ns: 0,
 			sync_mode: SyncMode::Every,
+			encryption: None,
 		}
 	}
 }
@@ -221,6 +238,8 @@ impl SurrealKvConfig {
 		} else if let Some(v) = SURREAL_DATASTORE_SYNC_DATA.as_deref() {
 			config.sync_mode = parse_sync_mode(v)?;
 		}
+		// Load the encryption key (query param > env var > default)
+		config.encryption = parse_encryption_key(params)?;
 		// Return the configuration
 		Ok(config)
 	}
@@ -239,6 +258,8 @@ pub struct RocksDbConfig {
 	pub retention_ns: u64,
 	/// Disk sync mode.
 	pub sync_mode: SyncMode,
+	/// The key used for encrypting the data at rest, if any.
+	pub encryption: Option<Arc<EncryptionKey>>,
 }
 
 impl Default for RocksDbConfig {
@@ -247,6 +268,7 @@ impl Default for RocksDbConfig {
 			versioned: false,
 			retention_ns: 0,
 			sync_mode: SyncMode::Every,
+			encryption: None,
 		}
 	}
 }
@@ -277,11 +299,71 @@ impl RocksDbConfig {
 		} else if let Some(v) = SURREAL_DATASTORE_SYNC_DATA.as_deref() {
 			config.sync_mode = parse_sync_mode(v)?;
 		}
+		// Load the encryption key (query param > env var > default)
+		config.encryption = parse_encryption_key(params)?;
 		// Return the configuration
 		Ok(config)
 	}
 }
 
+// --------------------------------------------------
+// TiKV configuration
+// --------------------------------------------------
+
+/// Configuration for the TiKV storage engine, parsed from query parameters.
+#[derive(Debug, Clone, Default)]
+pub struct TikvConfig {
+	/// Whether MVCC versioning is enabled.
+	pub versioned: bool,
+	/// Version retention period in nanoseconds (0 = unlimited).
+	pub retention_ns: u64,
+}
+
+impl TikvConfig {
+	/// Build configuration from parsed query parameters, with environment
+	/// variable fallbacks. Query parameters take precedence over env vars,
+	/// which take precedence over engine defaults.
+	pub fn from_params(params: &HashMap<String, String>) -> Result<Self> {
+		let mut config = Self::default();
+		// Check whether versioning is enabled (query param > env var > default)
+		if let Some(v) = params.get("versioned") {
+			config.versioned = v.eq_ignore_ascii_case("true") || v == "1";
+		} else if let Some(v) = SURREAL_DATASTORE_VERSIONED.as_deref() {
+			config.versioned = v.eq_ignore_ascii_case("true") || v == "1";
+		}
+		// Determine the version retention period (query param > env var > default)
+		if let Some(v) = params.get("retention") {
+			let dur = parse_duration(v)?;
+			config.retention_ns = dur.as_nanos() as u64;
+		} else if let Some(v) = SURREAL_DATASTORE_RETENTION.as_deref() {
+			let dur = parse_duration(v)?;
+			config.retention_ns = dur.as_nanos() as u64;
+		}
+		// Return the configuration
+		Ok(config)
+	}
+}
+
+// --------------------------------------------------
+// Encryption
+// --------------------------------------------------
+
+/// Load the datastore encryption key from the `key_file` query parameter,
+/// or from the environment. A key file takes precedence over a key which is
+/// provided directly in the environment.
+fn parse_encryption_key(params: &HashMap<String, String>) -> Result<Option<Arc<EncryptionKey>>> {
+	let key = if let Some(v) = params.get("key_file") {
+		EncryptionKey::from_file(v)?
+	} else if let Some(v) = SURREAL_DATASTORE_KEY_FILE.as_deref() {
+		EncryptionKey::from_file(v)?
+	} else if let Some(v) = SURREAL_DATASTORE_KEY.as_deref() {
+		EncryptionKey::from_base64(v)?
+	} else {
+		return Ok(None);
+	};
+	Ok(Some(Arc::new(key)))
+}
+
 // --------------------------------------------------
 // Duration
 // --------------------------------------------------
@@ -648,6 +730,21 @@ mod tests {
 		assert_eq!(config.sync_mode, SyncMode::Every);
 	}
 
+	#[test]
+	fn test_tikv_config_defaults() {
+		let config = TikvConfig::from_params(&HashMap::new()).unwrap();
+		assert!(!config.versioned);
+		assert_eq!(config.retention_ns, 0);
+	}
+
+	#[test]
+	fn test_tikv_config_full_params() {
+		let params = parse_query_params("versioned=true&retention=12h");
+		let config = TikvConfig::from_params(&params).unwrap();
+		assert!(config.versioned);
+		assert_eq!(config.retention_ns, 12 * 3600 * 1_000_000_000);
+	}
+
 	// --------------------------------------------------
 	// Query param override tests
 	// --------------------------------------------------
@@ -17,7 +17,7 @@ use anyhow::bail;
 use anyhow::{Context as _, Result, ensure};
 use async_channel::{Receiver, Sender};
 use bytes::{Bytes, BytesMut};
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt};
 use rand::{Rng, thread_rng};
 use reblessive::TreeStack;
 use surrealdb_types::{AuthError, Error as TypesError, SurrealValue, object};
@@ -32,24 +32,29 @@ use tracing::{debug, instrument, trace, warn};
 use uuid::Uuid;
 
 use super::api::Transactable;
+use super::recovery::{Recovery, RecoveryPoint};
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::MajorVersion;
-use super::{Key, Val, export};
+use super::{EncryptionKey, Key, Val, backup, export, import};
 use crate::api::err::ApiError;
 use crate::api::invocation::process_api_request;
 use crate::api::request::ApiRequest;
 use crate::api::response::ApiResponse;
-use crate::buc::BucketStoreProvider;
 use crate::buc::manager::BucketsManager;
+use crate::buc::store::{ObjectKey, ObjectStream};
+use crate::buc::upload::{Upload, UploadSessions};
+use crate::buc::{BucketStoreProvider, check_upload, open_stream, open_upload};
 use crate::catalog::providers::{
 	ApiProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider,
 	UserProvider,
 };
 use crate::catalog::{ApiDefinition, Index, NodeLiveQuery, SubscriptionDefinition};
-use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::cf::Sink;
+use crate::cf::sink::SinkProcessor;
 use crate::cnf::dynamic::DynamicConfiguration;
-use crate::ctx::Context;
+use crate::cnf::{NORMAL_FETCH_SIZE, RESTORE_BATCH_SIZE};
+use crate::ctx::{Context, FrozenContext};
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::capabilities::{
@@ -57,7 +62,7 @@ use crate::dbs::capabilities::{
 };
 use crate::dbs::node::{Node, Timestamp};
 use crate::dbs::{Capabilities, Executor, Options, QueryResult, QueryResultBuilder, Session};
-use crate::doc::AsyncEventRecord;
+use crate::doc::{AsyncEventRecord, RecordExpiry};
 use crate::err::Error;
 use crate::expr::model::get_model_path;
 use crate::expr::statements::{DefineModelStatement, DefineStatement, DefineUserStatement};
@@ -68,10 +73,13 @@ use crate::iam::{Action, Auth, Error as IamError, Resource, ResourceKind, Role};
 use crate::idx::IndexKeyBase;
 use crate::idx::index::IndexOperation;
 use crate::idx::trees::store::IndexStores;
+use crate::job::JobScheduler;
 use crate::key::root::ic::IndexCompactionKey;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::audit::{self, AuditEvent, AuditFile, AuditKind, AuditLog};
 use crate::kvs::cache::ds::DatastoreCache;
+use crate::kvs::cache::result::ResultCache;
 use crate::kvs::clock::SystemClock;
 use crate::kvs::ds::requirements::{
 	TransactionBuilderFactoryRequirements, TransactionBuilderRequirements,
@@ -86,7 +94,7 @@ use crate::sql::Ast;
 use crate::surrealism::cache::SurrealismCache;
 use crate::syn::parser::{ParserSettings, StatementStream};
 use crate::types::{PublicNotification, PublicValue, PublicVariables};
-use crate::val::convert_value_to_public_value;
+use crate::val::{Datetime, convert_value_to_public_value};
 use crate::{CommunityComposer, syn};
 
 const TARGET: &str = "surrealdb::core::kvs::ds";
@@ -110,6 +118,8 @@ pub struct Datastore {
 	dynamic_configuration: DynamicConfiguration,
 	/// The slow log configuration determining when a query should be logged
 	slow_log: Option<SlowLog>,
+	/// The audit log of authentication attempts and schema changes
+	audit_log: Option<AuditLog>,
 	/// The maximum duration timeout for running multiple statements in a
 	/// transaction.
 	transaction_timeout: Option<Duration>,
@@ -138,6 +148,8 @@ pub struct Datastore {
 	surrealism_cache: Arc<SurrealismCache>,
 	// Async event processing trigger
 	async_event_trigger: Arc<Notify>,
+	// The custom changefeed sinks
+	sinks: Arc<HashMap<String, Arc<dyn Sink>>>,
 }
 
 /// Represents a collection of metrics for a specific datastore flavor.
@@ -164,6 +176,10 @@ pub(crate) struct TransactionFactory {
 	builder: Arc<Box<dyn TransactionBuilder>>,
 	// Async event processing trigger
 	async_event_trigger: Arc<Notify>,
+	// The cross transaction cache of query results
+	results: Arc<ResultCache>,
+	// The audit log which records the statements audited in the transactions
+	audit_log: Option<AuditLog>,
 }
 
 impl TransactionFactory {
@@ -174,9 +190,16 @@ impl TransactionFactory {
 		Self {
 			builder: Arc::new(builder),
 			async_event_trigger,
+			results: Arc::new(ResultCache::new()),
+			audit_log: None,
 		}
 	}
 
+	/// Get the cross transaction cache of query results
+	pub(crate) fn result_cache(&self) -> &ResultCache {
+		&self.results
+	}
+
 	#[allow(
 		unreachable_code,
 		unreachable_patterns,
@@ -205,6 +228,8 @@ impl TransactionFactory {
 			local,
 			sequences,
 			self.async_event_trigger.clone(),
+			self.results.clone(),
+			self.audit_log.clone(),
 			Transactor {
 				inner,
 			},
@@ -262,6 +287,13 @@ pub trait TransactionBuilder: TransactionBuilderRequirements {
 	///
 	/// - `metric`: The name of the metric to collect.
 	fn collect_u64_metric(&self, metric: &str) -> Option<u64>;
+
+	/// Re-encrypts the data at rest with a new encryption key, if supported.
+	///
+	/// - `key`: The new encryption key.
+	async fn rotate_encryption_key(&self, _key: Arc<EncryptionKey>) -> Result<()> {
+		bail!(Error::Kvs(crate::kvs::Error::EncryptionUnsupported))
+	}
 }
 
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
@@ -436,7 +468,13 @@ impl TransactionBuilderFactory for CommunityComposer {
 			(flavour @ "tikv", path) => {
 				#[cfg(feature = "kv-tikv")]
 				{
-					let v = super::tikv::Datastore::new(&path).await.map(DatastoreFlavor::TiKV)?;
+					// Parse TiKV-specific configuration from query parameters
+					let config =
+						super::config::TikvConfig::from_params(&params).map_err(Error::Kvs)?;
+					// Initialise the storage engine
+					let v = super::tikv::Datastore::new(&path, config)
+						.await
+						.map(DatastoreFlavor::TiKV)?;
 					info!(target: TARGET, "Started {flavour} kvs store");
 					Ok(Box::<DatastoreFlavor>::new(v))
 				}
@@ -539,6 +577,18 @@ impl TransactionBuilder for DatastoreFlavor {
 		}
 	}
 
+	#[allow(unused_variables)]
+	async fn rotate_encryption_key(&self, key: Arc<EncryptionKey>) -> Result<()> {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			Self::RocksDB(v) => Ok(v.rotate_encryption_key(key).await?),
+			#[cfg(feature = "kv-surrealkv")]
+			Self::SurrealKV(v) => Ok(v.rotate_encryption_key(key).await?),
+			#[allow(unreachable_patterns)]
+			_ => bail!(Error::Kvs(crate::kvs::Error::EncryptionUnsupported)),
+		}
+	}
+
 	async fn shutdown(&self) -> Result<()> {
 		match self {
 			#[cfg(feature = "kv-mem")]
@@ -663,6 +713,7 @@ impl Datastore {
 			auth_enabled: false,
 			dynamic_configuration: DynamicConfiguration::default(),
 			slow_log: None,
+			audit_log: None,
 			transaction_timeout: None,
 			notification_channel: None,
 			capabilities: Arc::new(Capabilities::default()),
@@ -678,6 +729,7 @@ impl Datastore {
 			#[cfg(feature = "surrealism")]
 			surrealism_cache: Arc::new(SurrealismCache::new()),
 			async_event_trigger,
+			sinks: Arc::new(HashMap::new()),
 		})
 	}
 
@@ -704,6 +756,7 @@ impl Datastore {
 			auth_enabled: self.auth_enabled,
 			dynamic_configuration: DynamicConfiguration::default(),
 			slow_log: self.slow_log,
+			audit_log: self.audit_log,
 			transaction_timeout: self.transaction_timeout,
 			capabilities: self.capabilities,
 			notification_channel: self.notification_channel,
@@ -720,6 +773,7 @@ impl Datastore {
 			#[cfg(feature = "surrealism")]
 			surrealism_cache: Arc::new(SurrealismCache::new()),
 			async_event_trigger: self.async_event_trigger,
+			sinks: self.sinks,
 		}
 	}
 
@@ -760,6 +814,71 @@ impl Datastore {
 		self
 	}
 
+	/// Configure the audit log of authentication attempts and of the
+	/// statements which change the schema or the permissions.
+	///
+	/// Parameters:
+	/// - `file`: The file the events are appended to as JSON lines, rotated when it reaches its
+	///   maximum size.
+	/// - `table`: Whether the events are stored in the datastore, where root users can read them
+	///   with `SHOW AUDIT`.
+	/// - `retention`: How long the events stored in the datastore are kept. They are kept forever
+	///   when this is not set.
+	///
+	/// When neither `file` nor `table` is set, audit logging is disabled. The
+	/// file is written by a dedicated thread, and an error is returned if it
+	/// can not be spawned.
+	pub fn with_audit_log(
+		mut self,
+		file: Option<AuditFile>,
+		table: bool,
+		retention: Option<Duration>,
+	) -> Result<Self> {
+		// The events are written with a factory which does not audit the
+		// transactions, so that the audit log does not reference itself
+		let table = table.then(|| (self.transaction_factory.clone(), self.sequences.clone()));
+		self.audit_log = match (file, table) {
+			(None, None) => None,
+			(file, table) => Some(AuditLog::new(file, table, retention)?),
+		};
+		self.transaction_factory.audit_log = self.audit_log.clone();
+		Ok(self)
+	}
+
+	/// Wait for the events of the audit log to be written to the file, and
+	/// write the buffered events to the datastore, if the audit log is stored
+	/// in the datastore.
+	pub async fn flush_audit_log(&self) -> Result<()> {
+		match &self.audit_log {
+			Some(audit_log) => audit_log.flush().await,
+			None => Ok(()),
+		}
+	}
+
+	/// Get the audit log of this Datastore, if audit logging is enabled
+	pub(crate) fn audit_log(&self) -> Option<&AuditLog> {
+		self.audit_log.as_ref()
+	}
+
+	/// Get the cross transaction cache of query results
+	pub(crate) fn result_cache(&self) -> &ResultCache {
+		self.transaction_factory.result_cache()
+	}
+
+	/// Record the outcome of an authentication attempt in the audit log, if
+	/// audit logging is enabled.
+	pub(crate) async fn audit_auth<T>(
+		&self,
+		kind: AuditKind,
+		session: &Session,
+		vars: Option<&PublicVariables>,
+		res: &Result<T>,
+	) {
+		if let Some(audit_log) = &self.audit_log {
+			audit_log.record(AuditEvent::auth(kind, session, vars, res)).await;
+		}
+	}
+
 	/// Set a global transaction timeout for this Datastore
 	pub fn with_transaction_timeout(mut self, duration: Option<Duration>) -> Self {
 		self.transaction_timeout = duration;
@@ -783,6 +902,18 @@ impl Datastore {
 		self
 	}
 
+	/// Register a custom changefeed sink, used by the sinks defined with
+	/// `TO CUSTOM <name>`
+	pub fn with_sink(mut self, name: impl Into<String>, sink: Arc<dyn Sink>) -> Self {
+		Arc::make_mut(&mut self.sinks).insert(name.into(), sink);
+		self
+	}
+
+	/// Get a custom changefeed sink registered with this Datastore
+	pub(crate) fn sink(&self, name: &str) -> Option<Arc<dyn Sink>> {
+		self.sinks.get(name).cloned()
+	}
+
 	#[cfg(storage)]
 	/// Set a temporary directory for ordering of large result sets
 	pub fn with_temporary_directory(mut self, path: Option<PathBuf>) -> Self {
@@ -1002,12 +1133,24 @@ impl Datastore {
 	pub async fn shutdown(&self) -> Result<()> {
 		// Output function invocation details to logs
 		trace!(target: TARGET, "Running datastore shutdown operations");
+		// Write the buffered events of the audit log
+		self.flush_audit_log().await?;
 		// Delete this datastore from the cluster
 		self.delete_node().await?;
 		// Run any storage engine shutdown tasks
 		self.transaction_factory.builder.shutdown().await
 	}
 
+	/// Re-encrypt the data at rest with a new encryption key.
+	///
+	/// This is only supported by the `rocksdb` and `surrealkv` storage engines,
+	/// when the datastore was opened with its current encryption key. The
+	/// datastore should not be serving queries while the key is rotated.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn rotate_encryption_key(&self, key: EncryptionKey) -> Result<()> {
+		self.transaction_factory.builder.rotate_encryption_key(Arc::new(key)).await
+	}
+
 	// --------------------------------------------------
 	// Node functions
 	// --------------------------------------------------
@@ -1691,6 +1834,239 @@ impl Datastore {
 		}
 	}
 
+	/// Deletes the records whose `TTL` has elapsed, and queues the expiry of
+	/// the existing records of the tables whose `TTL` changed, using a
+	/// distributed lease to coordinate batches. Once a batch starts it runs to
+	/// completion even if the lease expires, so brief overlap is possible.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn record_expiry(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting record expiry process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::RecordExpiry,
+			interval * 2,
+		)?;
+		// We continue without interruptions while there are expired records and the lease
+		loop {
+			// Attempt to acquire a lease for the RecordExpiry task
+			// If we don't get the lease, another node is handling this task
+			if !lh.has_lease().await? {
+				return Ok(());
+			}
+			// Output function invocation details to logs
+			trace!(target: TARGET, "Running record expiry process");
+			// Queue the expiry of the existing records of the tables whose TTL changed
+			let backfilled = RecordExpiry::process_next_backfill_batch(self, Some(&lh)).await?;
+			let expired = RecordExpiry::process_next_expiries_batch(self, Some(&lh)).await?;
+			if !backfilled && expired == 0 {
+				// There was no table to backfill, and no record to delete,
+				// we can sleep until the next run
+				return Ok(());
+			}
+		}
+	}
+
+	/// Runs the scheduled jobs which are due, using a distributed lease so
+	/// that each job is only run by a single node of the cluster.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn job_scheduler(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting job scheduler process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::JobScheduler,
+			interval * 2,
+		)?;
+		// Attempt to acquire a lease for the JobScheduler task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running job scheduler process");
+		JobScheduler::process_due_jobs(self, Some(&lh)).await?;
+		Ok(())
+	}
+
+	/// Delivers the changefeeds to the sinks defined on the databases, using a
+	/// distributed lease so that each change is delivered by a single node of
+	/// the cluster.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn changefeed_sinks(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting changefeed sink process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::ChangefeedSinks,
+			interval * 2,
+		)?;
+		// Attempt to acquire a lease for the ChangefeedSinks task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running changefeed sink process");
+		SinkProcessor::process_sinks(self, Some(&lh)).await?;
+		Ok(())
+	}
+
+	/// Removes the versions of the history kept for versioned queries which
+	/// have left the retention window, using a distributed lease so that they
+	/// are removed by a single node of the cluster. The history is swept a
+	/// batch at a time, stopping between batches once `canceller` is
+	/// cancelled.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, canceller))]
+	pub async fn history_retention(
+		&self,
+		interval: Duration,
+		canceller: &CancellationToken,
+	) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting history retention process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::HistoryRetention,
+			interval * 2,
+		)?;
+		// Attempt to acquire a lease for the HistoryRetention task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running history retention process");
+		// Sweep the history a batch at a time
+		let mut next = None;
+		while !canceller.is_cancelled() {
+			lh.try_maintain_lease().await?;
+			let txn = self.transaction(Write, Optimistic).await?;
+			next = run!(txn, txn.prune_history(next).await)?;
+			if next.is_none() {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/// Removes the events of the audit log stored in the datastore which have
+	/// left the audit log retention period, if any, using a distributed lease
+	/// so that they are removed by a single node of the cluster. The events are
+	/// removed a batch at a time, stopping between batches once `canceller` is
+	/// cancelled.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, canceller))]
+	pub async fn audit_log_retention(
+		&self,
+		interval: Duration,
+		canceller: &CancellationToken,
+	) -> Result<()> {
+		// The events are kept forever without a retention period
+		let Some(before) =
+			self.audit_log.as_ref().map(AuditLog::retention_cutoff).transpose()?.flatten()
+		else {
+			return Ok(());
+		};
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting audit log retention process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::AuditLogRetention,
+			interval * 2,
+		)?;
+		// Attempt to acquire a lease for the AuditLogRetention task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running audit log retention process");
+		// Remove the expired events a batch at a time
+		while !canceller.is_cancelled() {
+			lh.try_maintain_lease().await?;
+			let txn = self.transaction(Write, Optimistic).await?;
+			if !run!(txn, audit::remove_expired(&txn, before).await)? {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/// Discards the multipart file uploads which have been idle for longer
+	/// than the upload timeout, using a distributed lease so that they are
+	/// discarded by a single node of the cluster.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn file_upload_expiry(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting file upload expiry process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::FileUploadExpiry,
+			interval * 2,
+		)?;
+		// Attempt to acquire a lease for the FileUploadExpiry task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running file upload expiry process");
+		UploadSessions::new(self).expire().await
+	}
+
+	/// Applies the result cache invalidations published by the other nodes of
+	/// the cluster, and removes those which every node has received, using a
+	/// distributed lease so that they are removed by a single node.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn result_invalidations(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting result invalidation process");
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Local storage engines are not shared with other nodes
+		if txn.is_local() {
+			return txn.cancel().await;
+		}
+		// Apply the invalidations published by the other nodes
+		catch!(txn, self.result_cache().receive(&txn).await);
+		txn.cancel().await?;
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::ResultInvalidations,
+			interval * 2,
+		)?;
+		// Attempt to acquire a lease for the ResultInvalidations task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running result invalidation cleanup");
+		let txn = self.transaction(Write, Optimistic).await?;
+		run!(txn, ResultCache::expire(&txn).await)
+	}
+
 	// --------------------------------------------------
 	// Other functions
 	// --------------------------------------------------
@@ -1952,6 +2328,28 @@ impl Datastore {
 		Executor::execute_stream(self, Arc::new(ctx), opt, true, stream).await
 	}
 
+	/// Execute a logical plan in import mode.
+	///
+	/// The plan must start with `OPTION IMPORT`, like the statements of
+	/// [`Datastore::execute_import`].
+	pub(crate) async fn execute_import_plan(
+		&self,
+		sess: &Session,
+		plan: LogicalPlan,
+	) -> Result<Vec<QueryResult>> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Create a new query options
+		let opt = self.setup_options(sess);
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		ctx.attach_session(sess)?;
+		// Process all statements
+		let stream = futures::stream::iter(plan.expressions.into_iter().map(Ok));
+		Executor::execute_expr_stream(self, Arc::new(ctx), opt, true, stream).await
+	}
+
 	/// Execute a pre-parsed SQL query
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn process(
@@ -2127,6 +2525,198 @@ impl Datastore {
 		self.execute_import(sess, None, stream).await
 	}
 
+	/// Bulk imports the records of an NDJSON, CSV or Parquet file into a table.
+	///
+	/// The records are written in batches, each in its own transaction, so
+	/// the records of the batches which were written are kept if the import
+	/// fails part way through. Rows which can not be imported are returned in
+	/// the report instead of failing the import. As the report only keeps the
+	/// first rejected rows, every rejected row is also sent to the `rejects`
+	/// channel when one is given, and the import fails if it is closed.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn import_records<S>(
+		&self,
+		sess: &Session,
+		cfg: import::Config,
+		stream: S,
+		rejects: Option<Sender<import::Reject>>,
+	) -> Result<import::Report>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Import the records
+		self.import_rows(sess, cfg, stream, rejects).await
+	}
+
+	/// Streams a file into a bucket, returning the size of the file.
+	///
+	/// The permissions of the bucket are checked before any data is written,
+	/// and the file only replaces any existing file once all of the data has
+	/// been written.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn put_file<S>(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		stream: S,
+	) -> Result<u64>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		let mut upload = self.open_upload(sess, bucket, key).await?;
+		let mut stream = pin!(stream);
+		while let Some(chunk) = stream.next().await {
+			let res = match chunk {
+				Ok(chunk) => upload.write(chunk).await,
+				Err(e) => Err(e),
+			};
+			if let Err(e) = res {
+				if let Err(e) = upload.abort().await {
+					warn!("Failed to discard file upload: {e}");
+				}
+				return Err(e);
+			}
+		}
+		let size = upload.size();
+		upload.commit().await?;
+		Ok(size)
+	}
+
+	/// Starts a resumable multipart upload of a file into a bucket, returning
+	/// the id of the upload.
+	///
+	/// Data is then appended with [`write_file_upload`](Self::write_file_upload),
+	/// and the file is stored once the upload is committed. Every step of the
+	/// upload checks the permissions of the bucket again, and is only allowed
+	/// for the user who started the upload.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn begin_file_upload(&self, sess: &Session, bucket: &str, key: &str) -> Result<Uuid> {
+		self.check_file_upload(sess, bucket, key).await?;
+		UploadSessions::new(self).begin(sess, bucket, key).await
+	}
+
+	/// Appends data to a multipart upload, returning the number of bytes which
+	/// have been written to the upload so far.
+	///
+	/// When an offset is specified, it must match the number of bytes which
+	/// have already been written, which allows an interrupted upload to be
+	/// resumed from the size returned by [`file_upload_size`](Self::file_upload_size).
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn write_file_upload<S>(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+		offset: Option<u64>,
+		stream: S,
+	) -> Result<u64>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		self.check_file_upload(sess, bucket, key).await?;
+		UploadSessions::new(self).write(sess, bucket, key, id, offset, stream).await
+	}
+
+	/// Returns the number of bytes which have been written to a multipart upload.
+	pub async fn file_upload_size(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+	) -> Result<u64> {
+		self.check_file_upload(sess, bucket, key).await?;
+		UploadSessions::new(self).size(sess, bucket, key, id).await
+	}
+
+	/// Completes a multipart upload, storing the file in the bucket, and
+	/// returning the size of the file.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn commit_file_upload(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+	) -> Result<u64> {
+		self.check_file_upload(sess, bucket, key).await?;
+		let uploads = UploadSessions::new(self);
+		let record = uploads.get(sess, bucket, key, id).await?;
+		let upload = self.open_upload(sess, &record.bucket, &record.key).await?;
+		uploads.commit(id, record, upload).await
+	}
+
+	/// Discards a multipart upload, and any data which was written to it.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn abort_file_upload(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+		id: Uuid,
+	) -> Result<()> {
+		self.check_file_upload(sess, bucket, key).await?;
+		UploadSessions::new(self).abort(sess, bucket, key, id).await
+	}
+
+	/// Streams a file from a bucket, checking the permissions of the bucket.
+	///
+	/// Returns `None` if the file does not exist.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn get_file(
+		&self,
+		sess: &Session,
+		bucket: &str,
+		key: &str,
+	) -> Result<Option<ObjectStream>> {
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		let (ctx, opt, tx) = self.setup_bucket_ctx(sess).await?;
+		let res = open_stream(&ctx, &opt, bucket, ObjectKey::new(key)).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Checks that the session can upload a file into a bucket, before any
+	/// step of a multipart upload.
+	async fn check_file_upload(&self, sess: &Session, bucket: &str, key: &str) -> Result<()> {
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		let (ctx, opt, tx) = self.setup_bucket_ctx(sess).await?;
+		let res = check_upload(&ctx, &opt, bucket, ObjectKey::new(key)).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Opens a streaming upload into a bucket, checking the permissions of the
+	/// bucket with the session.
+	async fn open_upload(&self, sess: &Session, bucket: &str, key: &str) -> Result<Upload> {
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		let (ctx, opt, tx) = self.setup_bucket_ctx(sess).await?;
+		let res = open_upload(&ctx, &opt, bucket, ObjectKey::new(key)).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Sets up a context with a read-only transaction for bucket operations.
+	async fn setup_bucket_ctx(
+		&self,
+		sess: &Session,
+	) -> Result<(FrozenContext, Options, Arc<Transaction>)> {
+		ensure!(
+			self.capabilities.allows_experimental(&ExperimentalTarget::Files),
+			Error::FilesNotEnabled
+		);
+		let opt = self.setup_options(sess);
+		let tx = Arc::new(self.transaction(Read, Optimistic).await?);
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(Arc::clone(&tx));
+		ctx.attach_session(sess)?;
+		Ok((ctx.freeze(), opt, tx))
+	}
+
 	/// Performs a full database export as SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn export(
@@ -2162,6 +2752,177 @@ impl Datastore {
 		})
 	}
 
+	/// Performs a raw key-value backup of the whole datastore.
+	///
+	/// The backup is read from a single snapshot of the datastore. When `since`
+	/// is the versionstamp of a previous backup, only the keys which changed
+	/// since then are written. An incremental backup requires a storage engine
+	/// with versioning enabled, and scans the keyspace both at `since` and at
+	/// the current snapshot, so it costs twice the reads of a full backup.
+	/// Returns the header of the backup archive, along with the job which
+	/// writes the archive into the channel.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn backup(
+		&self,
+		since: Option<u64>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(backup::Header, impl Future<Output = Result<u64>> + use<>)> {
+		// The identifier is written before the snapshot, so that it is restored
+		// along with the backup
+		let txn = self.transaction(Write, Optimistic).await?;
+		let datastore = run!(txn, txn.datastore_id().await)?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// The versionstamps are taken from the snapshot transaction itself, so
+		// that an incremental backup or a recovery based on this one can not
+		// miss any change
+		let ts = catch!(txn, txn.timestamp().await);
+		let changefeed = ts.as_versionstamp();
+		let versionstamp = catch!(
+			txn,
+			ts.as_datetime()
+				.ok_or_else(|| {
+					anyhow::Error::new(Error::InvalidBackup(
+						"the snapshot timestamp can not be converted to a versionstamp".to_owned(),
+					))
+				})
+				.and_then(|dt| Datetime::from(dt).to_version_stamp())
+		);
+		if let Some(since) = since
+			&& since > versionstamp
+		{
+			txn.cancel().await?;
+			bail!(Error::InvalidBackup(format!("the base versionstamp {since} is in the future")));
+		}
+		// Fail before anything is written if the base snapshot can not be read
+		if let Some(since) = since {
+			catch!(txn, txn.ensure_versioned(since).await);
+		}
+		let header = backup::Header {
+			datastore,
+			versionstamp,
+			since,
+			changefeed,
+		};
+		// Return an async backup job
+		Ok((header, async move {
+			let res = txn.backup(header, chn).await;
+			txn.cancel().await?;
+			res
+		}))
+	}
+
+	/// Restores a backup archive into the datastore.
+	///
+	/// A full backup can only be restored into an empty datastore, and an
+	/// incremental backup only into a datastore which was last restored from
+	/// the backup it is based on, taken from the same datastore. The keys of
+	/// the nodes of the source cluster are never restored. The entries are
+	/// written in batches of separate transactions, so a restore which fails
+	/// can leave the datastore partially restored.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn restore<S>(&self, stream: S) -> Result<backup::Header>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		let mut stream = pin!(stream);
+		let mut reader = backup::ArchiveReader::default();
+		let mut batch = Vec::with_capacity(*RESTORE_BATCH_SIZE);
+		let mut checked = false;
+		while !reader.is_finished() {
+			match stream.next().await {
+				Some(bytes) => reader.push(&bytes?),
+				None => bail!(Error::InvalidBackup("the archive is truncated".to_owned())),
+			}
+			// Check the target before anything is written to it
+			if !checked && let Some(header) = reader.header()? {
+				let txn = self.transaction(Read, Optimistic).await?;
+				match header.since {
+					Some(since) => {
+						catch!(txn, txn.ensure_incremental(since).await);
+						catch!(txn, txn.ensure_datastore(header.datastore).await);
+					}
+					None => catch!(txn, txn.ensure_restorable().await),
+				}
+				txn.cancel().await?;
+				checked = true;
+			}
+			while let Some(entry) = reader.next_entry()? {
+				batch.push(entry);
+				if batch.len() >= *RESTORE_BATCH_SIZE {
+					let entries = std::mem::take(&mut batch);
+					let txn = self.transaction(Write, Optimistic).await?;
+					run!(txn, txn.restore(entries).await)?;
+				}
+			}
+		}
+		if !batch.is_empty() {
+			let txn = self.transaction(Write, Optimistic).await?;
+			run!(txn, txn.restore(batch).await)?;
+		}
+		// The header has been read before the end of the archive
+		let header = reader
+			.header()?
+			.ok_or_else(|| Error::unreachable("the archive header was not read"))?;
+		// The next incremental backup must be based on this one
+		let txn = self.transaction(Write, Optimistic).await?;
+		run!(txn, txn.set_restored(header.versionstamp).await)?;
+		// Cached definitions may not match the restored keys
+		self.cache.clear();
+		// Neither may the cached results, on any node of the cluster
+		let txn = self.transaction(Write, Optimistic).await?;
+		run!(txn, self.result_cache().invalidate_all(&txn).await)?;
+		Ok(header)
+	}
+
+	/// Recovers the datastore to a point in time, by replaying changefeeds.
+	///
+	/// The datastore must have been restored from the backup described by
+	/// `base`. The changefeed entries recorded after that backup are read from
+	/// the `changes` archive, which is a more recent backup of the original
+	/// datastore, and replayed up to and including `until`. Both archives must
+	/// have been taken from the datastore which was restored. Returns the
+	/// number of replayed changesets.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn recover<S>(
+		&self,
+		base: &backup::Header,
+		changes: S,
+		until: RecoveryPoint,
+	) -> Result<u64>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// Check the target before anything is replayed onto it
+		let txn = self.transaction(Read, Optimistic).await?;
+		catch!(txn, txn.ensure_datastore(base.datastore).await);
+		txn.cancel().await?;
+		let mut stream = pin!(changes);
+		let mut reader = backup::ArchiveReader::default();
+		let mut recovery = Recovery::new(self, base.changefeed, until).await?;
+		let mut checked = false;
+		while !reader.is_finished() {
+			match stream.next().await {
+				Some(bytes) => reader.push(&bytes?),
+				None => bail!(Error::InvalidBackup("the archive is truncated".to_owned())),
+			}
+			if !checked && let Some(header) = reader.header()? {
+				ensure!(
+					header.datastore == base.datastore,
+					Error::InvalidBackup(format!(
+						"the changes were taken from datastore {}, but the base backup from datastore {}",
+						header.datastore, base.datastore
+					))
+				);
+				checked = true;
+			}
+			while let Some(entry) = reader.next_entry()? {
+				recovery.push(entry).await?;
+			}
+		}
+		recovery.finish().await
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<()> {
@@ -2189,6 +2950,7 @@ impl Datastore {
 		let mut ctx = Context::from_ds(
 			self.dynamic_configuration.get_query_timeout(),
 			self.slow_log.clone(),
+			self.audit_log.clone(),
 			self.capabilities.clone(),
 			self.index_stores.clone(),
 			self.index_builder.clone(),
@@ -2310,8 +3072,37 @@ impl Datastore {
 		db: &str,
 		path: &str,
 		session: &Session,
-		mut req: ApiRequest,
+		req: ApiRequest,
 	) -> Result<ApiResponse> {
+		let (res, _) = self.invoke_api(ns, db, path, session, req, false).await?;
+		Ok(res)
+	}
+
+	/// Invoke an API handler, streaming the files it responds with.
+	///
+	/// When the handler responds with a file as the body of the response, the
+	/// body is replaced with `NONE`, and the contents of the file are returned
+	/// as a stream, so that the file is never read into memory.
+	pub async fn invoke_api_handler_streaming(
+		&self,
+		ns: &str,
+		db: &str,
+		path: &str,
+		session: &Session,
+		req: ApiRequest,
+	) -> Result<(ApiResponse, Option<ObjectStream>)> {
+		self.invoke_api(ns, db, path, session, req, true).await
+	}
+
+	async fn invoke_api(
+		&self,
+		ns: &str,
+		db: &str,
+		path: &str,
+		session: &Session,
+		mut req: ApiRequest,
+		stream_files: bool,
+	) -> Result<(ApiResponse, Option<ObjectStream>)> {
 		let tx = Arc::new(self.transaction(TransactionType::Write, LockType::Optimistic).await?);
 
 		let db = tx.ensure_ns_db(None, ns, db).await?;
@@ -2335,7 +3126,33 @@ impl Datastore {
 				ctx.attach_session(session)?;
 				let ctx = &ctx.freeze();
 
-				process_api_request(ctx, &opt, api, req).await
+				match process_api_request(ctx, &opt, api, req).await {
+					// Stream files from their bucket, with the permissions of the session
+					Ok(res) => match &res.body {
+						PublicValue::File(file) if stream_files => {
+							let key = ObjectKey::new(file.key());
+							match open_stream(ctx, &opt, file.bucket(), key).await {
+								Ok(Some(stream)) => Ok((
+									ApiResponse {
+										body: PublicValue::None,
+										..res
+									},
+									Some(stream),
+								)),
+								Ok(None) => Ok((
+									ApiResponse::from_error(
+										ApiError::NotFound.into(),
+										res.request_id,
+									),
+									None,
+								)),
+								Err(e) => Err(e),
+							}
+						}
+						_ => Ok((res, None)),
+					},
+					Err(e) => Err(e),
+				}
 			}
 			None => {
 				trace!(
@@ -2344,9 +3161,9 @@ impl Datastore {
 					"No API definition found for path"
 				);
 				tx.cancel().await?;
-				return Ok(ApiResponse::from_error(
-					ApiError::NotFound.into(),
-					req.request_id.clone(),
+				return Ok((
+					ApiResponse::from_error(ApiError::NotFound.into(), req.request_id.clone()),
+					None,
 				));
 			}
 		};
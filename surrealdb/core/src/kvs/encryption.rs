@@ -0,0 +1,545 @@
+//! Encryption at rest for the `rocksdb` and `surrealkv` storage engines.
+//!
+//! When an encryption key is configured, every transaction is wrapped in an
+//! [`EncryptedTransaction`], which encrypts each value with AES-256-GCM before
+//! it is written to the storage engine, and decrypts it when it is read back.
+//! Each value is bound to its key, so that encrypted values can not be moved
+//! between keys. An encrypted value is stored as:
+//!
+//! `{version}{algorithm}{salt}{ciphertext}{tag}`
+//!
+//! Each value is encrypted with its own key, derived with HKDF-SHA256 from the
+//! encryption key and a random 256-bit salt, rather than with the encryption
+//! key and a random 96-bit nonce, as the nonces of AES-GCM would be likely to
+//! collide once around 2^32 values have been written with the same key. As a
+//! derived key only ever encrypts a single value, it is used with a fixed
+//! nonce.
+//!
+//! A known value is stored, encrypted, at the check key when the datastore is
+//! created, so that a wrong key is detected when the datastore is opened:
+//!
+//! `!e` -> the encrypted check value
+//!
+//! The key can be rotated while the datastore is offline. Every value is
+//! re-encrypted with the new key in batches, and the check value is rotated
+//! last, so an interrupted rotation can be resumed with the same keys. On
+//! SurrealKV, only the latest version of each key is re-encrypted.
+//!
+//! # Limitations
+//!
+//! Only values are encrypted. Keys are stored in plain text, so that they
+//! keep their order for range scans, and the data held within keys can be
+//! read from the files of the datastore. This includes the names of
+//! namespaces, databases, tables and fields, the ids of records, and the
+//! values of indexed fields, which are part of the keys of index entries. A
+//! unique index on an `email` field, for instance, stores every email address
+//! in plain text. Data which must not be readable on disk should not be used
+//! in record ids or in indexes, or should be encrypted with a field-level
+//! `DEFINE KEY` instead.
+
+use std::fmt;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+use std::ops::Range;
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::hkdf::{HKDF_SHA256, Salt};
+use ring::rand::{SecureRandom, SystemRandom};
+
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+use super::api::{ScanLimit, Transactable};
+use super::err::{Error, Result};
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+use super::{BoxTimeStamp, BoxTimeStampImpl, Key, Val};
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+use crate::cnf::NORMAL_FETCH_SIZE;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+use crate::key::debug::Sprintable;
+
+/// The length in bytes of a datastore encryption key
+const KEY_LENGTH: usize = 32;
+
+/// The format version of an encrypted value
+const VERSION: u8 = 1;
+
+/// The algorithm of a value encrypted with AES-256-GCM, using a key derived
+/// with HKDF-SHA256 from the encryption key and the salt of the value
+const AES_256_GCM_HKDF_SHA256: u8 = 1;
+
+/// The length in bytes of the salt the key of a value is derived with
+const SALT_LENGTH: usize = 32;
+
+/// The context in which the keys of the values are derived
+const VALUE_KEY_INFO: &[u8] = b"surrealdb encrypted value";
+
+/// The key of the check value
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+const CHECK_KEY: &[u8] = b"!e";
+
+/// The check value, which is stored encrypted at the check key
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+const CHECK_VAL: &[u8] = b"surrealdb";
+
+/// A 256-bit key used for encrypting the values in a datastore.
+pub struct EncryptionKey {
+	/// The key material, from which the key of each value is derived
+	material: [u8; KEY_LENGTH],
+}
+
+impl fmt::Debug for EncryptionKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("EncryptionKey([REDACTED])")
+	}
+}
+
+impl EncryptionKey {
+	/// Creates a key from 32 bytes of key material.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+		let Ok(material) = <[u8; KEY_LENGTH]>::try_from(bytes) else {
+			return Err(Error::EncryptionKeyInvalid(format!(
+				"expected {KEY_LENGTH} bytes of key material, found {}",
+				bytes.len()
+			)));
+		};
+		Ok(Self {
+			material,
+		})
+	}
+
+	/// Creates a key from base64 encoded key material.
+	pub fn from_base64(value: &str) -> Result<Self> {
+		let bytes = STANDARD.decode(value.trim()).map_err(|_| {
+			Error::EncryptionKeyInvalid("the key is not valid base64 encoded data".into())
+		})?;
+		Self::from_bytes(&bytes)
+	}
+
+	/// Loads a key from a file, which contains either the 32 bytes of key
+	/// material, or the key material encoded as base64.
+	pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+		let path = path.as_ref();
+		let bytes = std::fs::read(path).map_err(|e| {
+			Error::EncryptionKeyInvalid(format!("unable to read '{}': {e}", path.display()))
+		})?;
+		if bytes.len() == KEY_LENGTH {
+			return Self::from_bytes(&bytes);
+		}
+		match std::str::from_utf8(&bytes) {
+			Ok(v) => Self::from_base64(v),
+			Err(_) => Self::from_bytes(&bytes),
+		}
+	}
+
+	/// Derives the key which encrypts a single value from its salt.
+	fn value_key(&self, salt: &[u8]) -> Result<LessSafeKey> {
+		let prk = Salt::new(HKDF_SHA256, salt).extract(&self.material);
+		let okm = prk
+			.expand(&[VALUE_KEY_INFO], &AES_256_GCM)
+			.map_err(|_| Error::Internal("unable to derive a value key".into()))?;
+		Ok(LessSafeKey::new(UnboundKey::from(okm)))
+	}
+
+	/// Encrypts the value of a key.
+	pub(crate) fn seal(&self, key: &[u8], val: &[u8]) -> Result<Vec<u8>> {
+		let mut salt = [0u8; SALT_LENGTH];
+		SystemRandom::new()
+			.fill(&mut salt)
+			.map_err(|_| Error::Internal("unable to generate a salt".into()))?;
+		let head = 2 + SALT_LENGTH;
+		let mut out = Vec::with_capacity(head + val.len() + AES_256_GCM.tag_len());
+		out.push(VERSION);
+		out.push(AES_256_GCM_HKDF_SHA256);
+		out.extend_from_slice(&salt);
+		out.extend_from_slice(val);
+		let tag = self
+			.value_key(&salt)?
+			.seal_in_place_separate_tag(
+				Nonce::assume_unique_for_key([0u8; NONCE_LEN]),
+				Aad::from(key),
+				&mut out[head..],
+			)
+			.map_err(|_| Error::Internal("unable to encrypt a value".into()))?;
+		out.extend_from_slice(tag.as_ref());
+		Ok(out)
+	}
+
+	/// Decrypts the value of a key.
+	pub(crate) fn open(&self, key: &[u8], val: &[u8]) -> Result<Vec<u8>> {
+		let (salt, data) = match val {
+			[VERSION, AES_256_GCM_HKDF_SHA256, rest @ ..]
+				if rest.len() >= SALT_LENGTH + AES_256_GCM.tag_len() =>
+			{
+				rest.split_at(SALT_LENGTH)
+			}
+			_ => return Err(Error::Decryption),
+		};
+		let mut data = data.to_vec();
+		let len = self
+			.value_key(salt)?
+			.open_in_place(
+				Nonce::assume_unique_for_key([0u8; NONCE_LEN]),
+				Aad::from(key),
+				&mut data,
+			)
+			.map_err(|_| Error::Decryption)?
+			.len();
+		data.truncate(len);
+		Ok(data)
+}
+
+/// Checks that the encryption key, if any, matches the key the datastore
+/// was encrypted with, using a raw writeable transaction. A new datastore is
+/// marked as encrypted when it is first opened with a key.
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+pub(super) async fn verify(tx: Box<dyn Transactable>, key: Option<&EncryptionKey>) -> Result<()> {
+	let res = async {
+		match (tx.get(CHECK_KEY.to_vec(), None).await?, key) {
+			// The datastore is not encrypted
+			(None, None) => Ok(false),
+			// The datastore is encrypted with this key
+			(Some(v), Some(key)) => match key.open(CHECK_KEY, &v) {
+				Ok(v) if v == CHECK_VAL => Ok(false),
+				_ => Err(Error::EncryptionKeyMismatch),
+			},
+			// The datastore is encrypted, but no key was supplied
+			(Some(_), None) => Err(Error::EncryptionKeyMissing),
+			// A new datastore can be encrypted
+			(None, Some(key)) => {
+				let rng = Vec::new()..vec![0xff];
+				if !tx.keys(rng, ScanLimit::Count(1), 0, None).await?.is_empty() {
+					return Err(Error::NotEncrypted);
+				}
+				tx.set(CHECK_KEY.to_vec(), key.seal(CHECK_KEY, CHECK_VAL)?, None).await?;
+				Ok(true)
+			}
+		}
+	}
+	.await;
+	match res {
+		Ok(true) => tx.commit().await,
+		Ok(false) => tx.cancel().await,
+		Err(e) => {
+			// Return the original error, as it explains the failure
+			if let Err(c) = tx.cancel().await {
+				warn!("Unable to cancel the encryption check transaction: {c}");
+			}
+			Err(e)
+		}
+	}
+}
+
+/// Re-encrypts a batch of values with a new key, using a raw writeable
+/// transaction, and returns the start of the next batch. Values which are
+/// already encrypted with the new key are skipped, so that an interrupted
+/// rotation can be resumed. The check value is left to [`rotate_check`].
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+pub(super) async fn rotate(
+	tx: &dyn Transactable,
+	old: &EncryptionKey,
+	new: &EncryptionKey,
+	beg: Key,
+) -> Result<Option<Key>> {
+	let batch = *NORMAL_FETCH_SIZE;
+	let res = tx.scan(beg..vec![0xff], ScanLimit::Count(batch), 0, None).await?;
+	// Continue after the last entry of the batch
+	let next = match res.last() {
+		Some((last, _)) if res.len() >= batch as usize => {
+			let mut next = last.clone();
+			next.push(0x00);
+			Some(next)
+		}
+		_ => None,
+	};
+	for (k, v) in res {
+		if k == CHECK_KEY || new.open(&k, &v).is_ok() {
+			continue;
+		}
+		let v = old.open(&k, &v)?;
+		tx.set(k.clone(), new.seal(&k, &v)?, None).await?;
+	}
+	Ok(next)
+}
+
+/// Rotates the check value to a new key, once every other value has been
+/// re-encrypted with [`rotate`].
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+pub(super) async fn rotate_check(tx: &dyn Transactable, new: &EncryptionKey) -> Result<()> {
+	tx.set(CHECK_KEY.to_vec(), new.seal(CHECK_KEY, CHECK_VAL)?, None).await
+}
+
+/// A transaction which encrypts the values it writes, and decrypts the
+/// values it reads.
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+pub(super) struct EncryptedTransaction {
+	/// The underlying datastore transaction
+	inner: Box<dyn Transactable>,
+	/// The datastore encryption key
+	key: std::sync::Arc<EncryptionKey>,
+}
+
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+impl EncryptedTransaction {
+	pub(super) fn new(
+		inner: Box<dyn Transactable>,
+		key: std::sync::Arc<EncryptionKey>,
+	) -> Box<dyn Transactable> {
+		Box::new(Self {
+			inner,
+			key,
+		})
+	}
+
+	fn open_all(&self, res: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>> {
+		res.into_iter()
+			.map(|(k, v)| {
+				let v = self.key.open(&k, &v)?;
+				Ok((k, v))
+			})
+			.collect()
+	}
+
+	/// Fetches the stored value of a key if it matches a condition.
+	async fn check(&self, key: &Key, chk: Option<Val>) -> Result<Option<Val>> {
+		let raw = self.inner.get(key.clone(), None).await?;
+		let val = raw.as_ref().map(|v| self.key.open(key, v)).transpose()?;
+		if val != chk {
+			return Err(Error::TransactionConditionNotMet);
+		}
+		Ok(raw)
+	}
+}
+
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for EncryptedTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	fn versioned_indexes(&self) -> bool {
+		self.inner.versioned_indexes()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		self.inner.commit().await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		self.inner.exists(key, version).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		match self.inner.get(key.clone(), version).await? {
+			Some(v) => Ok(Some(self.key.open(&key, &v)?)),
+			None => Ok(None),
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(keys = keys.sprint()))]
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		let res = self.inner.getm(keys.clone(), version).await?;
+		keys.iter().zip(res).map(|(k, v)| v.map(|v| self.key.open(k, &v)).transpose()).collect()
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		let val = self.key.seal(&key, &val)?;
+		self.inner.set(key, val, version).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		let val = self.key.seal(&key, &val)?;
+		self.inner.replace(key, val).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		let val = self.key.seal(&key, &val)?;
+		self.inner.put(key, val, version).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		// Encrypted values are never equal, so compare the plain values
+		let chk = self.check(&key, chk).await?;
+		let val = self.key.seal(&key, &val)?;
+		self.inner.putc(key, val, chk).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del(&self, key: Key) -> Result<()> {
+		self.inner.del(key).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		self.inner.delc(key, chk).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.inner.clr(key).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		let chk = self.check(&key, chk).await?;
+		self.inner.clrc(key, chk).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn count(&self, rng: Range<Key>, version: Option<u64>) -> Result<usize> {
+		self.inner.count(rng, version).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keys(rng, limit, skip, version).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keysr(rng, limit, skip, version).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scan(rng, limit, skip, version).await?;
+		self.open_all(res)
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scanr(rng, limit, skip, version).await?;
+		self.open_all(res)
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn versions(&self, key: Key, beg: u64, end: u64) -> Result<Vec<(u64, Option<Val>)>> {
+		self.inner
+			.versions(key.clone(), beg, end)
+			.await?
+			.into_iter()
+			.map(|(ts, v)| Ok((ts, v.map(|v| self.key.open(&key, &v)).transpose()?)))
+			.collect()
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+
+	async fn prune_history(&self, from: Option<Key>) -> Result<Option<Key>> {
+		self.inner.prune_history(from).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key(byte: u8) -> EncryptionKey {
+		EncryptionKey::from_bytes(&[byte; KEY_LENGTH]).unwrap()
+	}
+
+	#[test]
+	fn seal_and_open() {
+		let key = key(1);
+		let val = key.seal(b"/a", b"value").unwrap();
+		assert_ne!(val, b"value");
+		assert_eq!(key.open(b"/a", &val).unwrap(), b"value");
+		// Values are bound to their key
+		assert!(matches!(key.open(b"/b", &val), Err(Error::Decryption)));
+		// Values can only be opened with the same encryption key
+		assert!(matches!(self::key(2).open(b"/a", &val), Err(Error::Decryption)));
+		// Empty and truncated values are rejected
+		assert_eq!(key.open(b"/a", &key.seal(b"/a", b"").unwrap()).unwrap(), b"");
+		assert!(matches!(key.open(b"/a", &val[..10]), Err(Error::Decryption)));
+		// The format and the algorithm are stored ahead of the ciphertext
+		assert_eq!(val[..2], [VERSION, AES_256_GCM_HKDF_SHA256]);
+		assert_eq!(val.len(), 2 + SALT_LENGTH + b"value".len() + AES_256_GCM.tag_len());
+		// Each value is encrypted with its own key
+		assert_ne!(key.seal(b"/a", b"value").unwrap()[2..], val[2..]);
+		// Values of an unknown algorithm are rejected
+		assert!(matches!(
+			key.open(b"/a", &[&[VERSION, 2][..], &val[2..]].concat()),
+			Err(Error::Decryption)
+		));
+	}
+
+	#[test]
+	fn decode_key_material() {
+		assert!(EncryptionKey::from_base64(&STANDARD.encode([7u8; KEY_LENGTH])).is_ok());
+		assert!(matches!(
+			EncryptionKey::from_base64(&STANDARD.encode([7u8; 16])),
+			Err(Error::EncryptionKeyInvalid(_))
+		));
+		assert!(matches!(
+			EncryptionKey::from_base64("not base64!"),
+			Err(Error::EncryptionKeyInvalid(_))
+		));
+		assert_eq!(format!("{:?}", key(1)), "EncryptionKey([REDACTED])");
+	}
+}
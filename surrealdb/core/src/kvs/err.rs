@@ -70,6 +70,34 @@ pub enum Error {
 
 	#[error("The storage layer does not support compaction requests.")]
 	CompactionNotSupported,
+
+	/// The storage engine does not support encryption at rest
+	#[error("The storage engine does not support encryption at rest")]
+	EncryptionUnsupported,
+
+	/// The supplied datastore encryption key could not be loaded
+	#[error("The datastore encryption key is not valid: {0}")]
+	EncryptionKeyInvalid(String),
+
+	/// The supplied encryption key is not the key the datastore was encrypted with
+	#[error("The supplied encryption key does not match the key used to encrypt the datastore")]
+	EncryptionKeyMismatch,
+
+	/// The datastore is encrypted, but no encryption key was supplied
+	#[error("The datastore is encrypted, but no encryption key was supplied")]
+	EncryptionKeyMissing,
+
+	/// An encryption key was supplied for a datastore which is not encrypted
+	#[error(
+		"The datastore is not encrypted. Export the data and import it into a new datastore to enable encryption"
+	)]
+	NotEncrypted,
+
+	/// A value in the datastore could not be decrypted
+	#[error(
+		"Unable to decrypt a value in the datastore. If a key rotation was interrupted, run it again with the same keys"
+	)]
+	Decryption,
 }
 
 impl Error {
@@ -1,13 +1,15 @@
 use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use async_channel::Sender;
 use surrealdb_types::{SurrealValue, ToSql};
 
 use super::Transaction;
 use crate::catalog::providers::{
-	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, TableProvider,
-	UserProvider,
+	ApiProvider, AuthorisationProvider, BucketProvider, ConsumerProvider, DatabaseProvider,
+	JobProvider, KeyProvider, SinkProvider, TableProvider, UserProvider,
 };
 use crate::catalog::{DatabaseId, NamespaceId, Record, TableDefinition};
 use crate::cnf::EXPORT_BATCH_SIZE;
@@ -16,8 +18,15 @@ use crate::expr::paths::{IN, OUT};
 use crate::expr::statements::define::{DefineAccessStatement, DefineUserStatement};
 use crate::expr::{Base, DefineAnalyzerStatement};
 use crate::key::record;
-use crate::kvs::KVValue;
+use crate::kvs::{KVValue, Key};
 use crate::sql::statements::OptionStatement;
+use crate::val::{RecordId, Value};
+
+mod columns;
+mod csv;
+mod ndjson;
+#[cfg(feature = "parquet")]
+mod parquet;
 
 #[derive(Clone, Debug, SurrealValue)]
 #[surreal(crate = "surrealdb_types")]
@@ -36,6 +45,12 @@ pub struct Config {
 	pub versions: bool,
 	pub records: bool,
 	pub sequences: bool,
+	pub jobs: bool,
+	pub sinks: bool,
+	pub consumers: bool,
+	pub keys: bool,
+	pub format: ExportFormat,
+	pub flatten: FlattenPolicy,
 }
 
 impl Default for Config {
@@ -54,6 +69,106 @@ impl Default for Config {
 			versions: false,
 			records: true,
 			sequences: true,
+			jobs: true,
+			sinks: true,
+			consumers: true,
+			keys: true,
+			format: ExportFormat::default(),
+			flatten: FlattenPolicy::default(),
+		}
+	}
+}
+
+/// The format in which an export is written.
+///
+/// Only [`ExportFormat::Surql`] exports the database definitions. The other
+/// formats only export the records of the selected tables, and the columnar
+/// formats export the records of a single table.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+#[surreal(untagged)]
+pub enum ExportFormat {
+	/// A SurrealQL script which recreates the database
+	#[default]
+	#[surreal(value = "surql")]
+	Surql,
+	/// One JSON object per line for each record
+	#[surreal(value = "ndjson")]
+	Ndjson,
+	/// Comma-separated values with a header row, for a single table
+	#[surreal(value = "csv")]
+	Csv,
+	/// An Apache Parquet file, for a single table
+	#[surreal(value = "parquet")]
+	Parquet,
+}
+
+impl ExportFormat {
+	/// Whether this format exports the records of a single table
+	pub fn is_columnar(&self) -> bool {
+		matches!(self, Self::Csv | Self::Parquet)
+	}
+	/// The file extension used for this format
+	pub fn extension(&self) -> &'static str {
+		match self {
+			Self::Surql => "surql",
+			Self::Ndjson => "ndjson",
+			Self::Csv => "csv",
+			Self::Parquet => "parquet",
+		}
+	}
+	/// The media type used when serving this format over HTTP
+	pub fn content_type(&self) -> &'static str {
+		match self {
+			Self::Surql => "application/octet-stream",
+			Self::Ndjson => "application/x-ndjson",
+			Self::Csv => "text/csv",
+			Self::Parquet => "application/vnd.apache.parquet",
+		}
+	}
+}
+
+impl fmt::Display for ExportFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.extension())
+	}
+}
+
+impl FromStr for ExportFormat {
+	type Err = anyhow::Error;
+	fn from_str(s: &str) -> Result<Self> {
+		match s.to_ascii_lowercase().as_str() {
+			"surql" | "sql" => Ok(Self::Surql),
+			"ndjson" | "jsonl" => Ok(Self::Ndjson),
+			"csv" => Ok(Self::Csv),
+			"parquet" => Ok(Self::Parquet),
+			_ => Err(Error::InvalidExportFormat(s.to_owned()).into()),
+		}
+	}
+}
+
+/// How nested values are written into the columns of a columnar export.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+#[surreal(untagged)]
+pub enum FlattenPolicy {
+	/// Nested objects and arrays are written as JSON in a single column
+	#[default]
+	#[surreal(value = "json")]
+	Json,
+	/// Nested objects are expanded into one column per field, named with
+	/// the dotted path to the field. Arrays are written as JSON.
+	#[surreal(value = "columns")]
+	Columns,
+}
+
+impl FromStr for FlattenPolicy {
+	type Err = anyhow::Error;
+	fn from_str(s: &str) -> Result<Self> {
+		match s.to_ascii_lowercase().as_str() {
+			"json" => Ok(Self::Json),
+			"columns" => Ok(Self::Columns),
+			_ => Err(Error::InvalidFlattenPolicy(s.to_owned()).into()),
 		}
 	}
 }
@@ -108,8 +223,8 @@ impl TableConfig {
 	pub(crate) fn is_any(&self) -> bool {
 		matches!(self, Self::All | Self::Some(_) | Self::Exclude(_))
 	}
-	// Check if we should export a specific table
-	pub(crate) fn includes(&self, table: &str) -> bool {
+	/// Check if we should export a specific table
+	pub fn includes(&self, table: &str) -> bool {
 		match self {
 			Self::All => true,
 			Self::None => false,
@@ -127,6 +242,13 @@ impl TableConfig {
 	}
 }
 
+/// The range of keys holding the records of a table
+fn records_range(ns: NamespaceId, db: DatabaseId, table: &TableDefinition) -> Result<Range<Key>> {
+	let beg = record::prefix(ns, db, &table.name)?;
+	let end = record::suffix(ns, db, &table.name)?;
+	Ok(beg..end)
+}
+
 struct InlineCommentWriter<'a, F>(&'a mut F);
 impl<F: fmt::Write> fmt::Write for InlineCommentWriter<'_, F> {
 	fn write_str(&mut self, s: &str) -> fmt::Result {
@@ -159,7 +281,7 @@ impl<F: fmt::Display> fmt::Display for InlineCommentDisplay<F> {
 }
 
 impl Transaction {
-	/// Writes the full database contents as binary SQL.
+	/// Writes the database contents in the configured export format.
 	pub async fn export(
 		&self,
 		ns: &str,
@@ -173,13 +295,101 @@ impl Transaction {
 			})
 		})?;
 
-		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
-		self.export_metadata(&cfg, &chn, db.namespace_id, db.database_id).await?;
-		// Output TABLES
-		self.export_tables(&cfg, &chn, db.namespace_id, db.database_id).await?;
+		match cfg.format {
+			ExportFormat::Surql => {
+				// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
+				self.export_metadata(&cfg, &chn, db.namespace_id, db.database_id).await?;
+				// Output TABLES
+				self.export_tables(&cfg, &chn, db.namespace_id, db.database_id).await?;
+			}
+			ExportFormat::Ndjson => {
+				for table in self.export_selection(&cfg, db.namespace_id, db.database_id).await? {
+					self.export_ndjson(db.namespace_id, db.database_id, &table, &chn).await?;
+				}
+			}
+			ExportFormat::Csv | ExportFormat::Parquet => {
+				let mut tables =
+					self.export_selection(&cfg, db.namespace_id, db.database_id).await?;
+				ensure!(
+					tables.len() == 1,
+					Error::ExportSingleTable {
+						format: cfg.format.to_string(),
+						count: tables.len(),
+					}
+				);
+				let table = tables.remove(0);
+				if cfg.format == ExportFormat::Csv {
+					self.export_csv(db.namespace_id, db.database_id, &table, cfg.flatten, &chn)
+						.await?;
+				} else {
+					self.export_parquet(db.namespace_id, db.database_id, &table, cfg.flatten, &chn)
+						.await?;
+				}
+			}
+		}
 		Ok(())
 	}
 
+	/// Returns the tables selected by the export config, in order.
+	async fn export_selection(
+		&self,
+		cfg: &Config,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Vec<TableDefinition>> {
+		// Check if tables are included in the export config
+		if !cfg.tables.is_any() {
+			return Ok(Vec::new());
+		}
+		// Fetch all of the tables for this NS / DB
+		let tables = self.all_tb(ns, db, None).await?;
+		// Warn if any specified table names don't match existing tables
+		if let Some(names) = cfg.tables.names() {
+			let existing: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+			for name in names {
+				if !existing.contains(&name.as_str()) {
+					warn!("Table '{name}' does not exist in the database");
+				}
+			}
+		}
+		// Keep the tables which are included in the export config
+		Ok(tables.iter().filter(|t| cfg.tables.includes(&t.name)).cloned().collect())
+	}
+
+	#[cfg(not(feature = "parquet"))]
+	async fn export_parquet(
+		&self,
+		_: NamespaceId,
+		_: DatabaseId,
+		_: &TableDefinition,
+		_: FlattenPolicy,
+		_: &Sender<Vec<u8>>,
+	) -> Result<()> {
+		Err(Error::ExportFormatUnsupported(ExportFormat::Parquet.to_string()).into())
+	}
+
+	/// Scans the next batch of records of a table, with the record id
+	/// injected into each of them. Returns an empty batch once the whole
+	/// table has been scanned.
+	async fn export_records(&self, next: &mut Option<Range<Key>>) -> Result<Vec<Value>> {
+		let Some(rng) = next.take() else {
+			return Ok(Vec::new());
+		};
+		let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+		*next = batch.next;
+		let mut records = Vec::with_capacity(batch.result.len());
+		for (k, v) in batch.result {
+			let k = record::RecordKey::decode_key(&k)?;
+			let mut record = Record::kv_decode_value(v)?;
+			record.data.def(RecordId {
+				table: k.tb.into_owned(),
+				key: k.id,
+			});
+			records.push(record.data);
+		}
+		Ok(records)
+	}
+
 	async fn export_metadata(
 		&self,
 		cfg: &Config,
@@ -214,6 +424,16 @@ impl Transaction {
 			.await?;
 		}
 
+		// Output KEYS, including their key material wrapped
+		// with the master key, so that the encrypted fields of
+		// the exported records can still be decrypted once
+		// imported into a server with the same master key
+		if cfg.keys {
+			let keys = self.all_db_keys(ns, db).await?;
+			let keys = keys.iter().map(|x| x.to_export_definition());
+			self.export_section("KEYS", keys, chn).await?;
+		}
+
 		// Output PARAMS
 		if cfg.params {
 			let params = self.all_db_params(ns, db).await?;
@@ -267,6 +487,24 @@ impl Transaction {
 			self.export_section("SEQUENCES", sequences.iter(), chn).await?;
 		}
 
+		// Output JOBS
+		if cfg.jobs {
+			let jobs = self.all_db_jobs(ns, db).await?;
+			self.export_section("JOBS", jobs.iter(), chn).await?;
+		}
+
+		// Output SINKS
+		if cfg.sinks {
+			let sinks = self.all_db_sinks(ns, db).await?;
+			self.export_section("SINKS", sinks.iter(), chn).await?;
+		}
+
+		// Output CONSUMERS
+		if cfg.consumers {
+			let consumers = self.all_db_consumers(ns, db).await?;
+			self.export_section("CONSUMERS", consumers.iter(), chn).await?;
+		}
+
 		Ok(())
 	}
 
@@ -303,27 +541,8 @@ impl Transaction {
 		ns: NamespaceId,
 		db: DatabaseId,
 	) -> Result<()> {
-		// Check if tables are included in the export config
-		if !cfg.tables.is_any() {
-			return Ok(());
-		}
-		// Fetch all of the tables for this NS / DB
-		let tables = self.all_tb(ns, db, None).await?;
-		// Warn if any specified table names don't match existing tables
-		if let Some(names) = cfg.tables.names() {
-			let existing: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
-			for name in names {
-				if !existing.contains(&name.as_str()) {
-					warn!("Table '{name}' does not exist in the database");
-				}
-			}
-		}
-		// Loop over all of the tables in order
-		for table in tables.iter() {
-			// Check if this table is included in the export config
-			if !cfg.tables.includes(&table.name) {
-				continue;
-			}
+		// Loop over all of the selected tables in order
+		for table in self.export_selection(cfg, ns, db).await?.iter() {
 			// Export the table definition structure first
 			self.export_table_structure(ns, db, table, chn).await?;
 			// Then export the table data if its desired
@@ -382,9 +601,7 @@ impl Transaction {
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!("")).await?;
 
-		let beg = crate::key::record::prefix(ns, db, &table.name)?;
-		let end = crate::key::record::suffix(ns, db, &table.name)?;
-		let mut next = Some(beg..end);
+		let mut next = Some(records_range(ns, db, table)?);
 
 		while let Some(rng) = next {
 			let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
@@ -498,3 +715,149 @@ impl Transaction {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+
+	async fn populated_datastore() -> Datastore {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query(
+			"
+			CREATE person:one SET name = { first: 'Tobie', last: 'Morgan' }, age = 30;
+			CREATE person:two SET name = { first: 'Jaime' }, tags = ['a', 'b'];
+			CREATE company:one SET name = 'SurrealDB';
+			RELATE person:one->works->company:one;
+			",
+		)
+		.await;
+		ds
+	}
+
+	async fn export(ds: &Datastore, cfg: Config) -> Result<Vec<u8>> {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let (snd, rcv) = async_channel::unbounded();
+		ds.export_with_config(&ses, snd, cfg).await?.await?;
+		let mut out = Vec::new();
+		while let Ok(chunk) = rcv.try_recv() {
+			out.extend(chunk);
+		}
+		Ok(out)
+	}
+
+	#[test]
+	fn parse_format() {
+		assert_eq!("NDJSON".parse::<ExportFormat>().unwrap(), ExportFormat::Ndjson);
+		assert_eq!("parquet".parse::<ExportFormat>().unwrap(), ExportFormat::Parquet);
+		assert!("xml".parse::<ExportFormat>().is_err());
+		assert_eq!("columns".parse::<FlattenPolicy>().unwrap(), FlattenPolicy::Columns);
+	}
+
+	#[test]
+	fn config_from_value() {
+		let val =
+			crate::syn::value("{ format: 'csv', flatten: 'columns', tables: ['person'] }").unwrap();
+		let cfg = Config::from_value(val).unwrap();
+		assert_eq!(cfg.format, ExportFormat::Csv);
+		assert_eq!(cfg.flatten, FlattenPolicy::Columns);
+		assert!(cfg.tables.includes("person"));
+		assert!(cfg.users);
+	}
+
+	#[tokio::test]
+	async fn export_ndjson() {
+		let ds = populated_datastore().await;
+		let cfg = Config {
+			format: ExportFormat::Ndjson,
+			tables: TableConfig::Exclude(ExcludedTables {
+				exclude: vec!["works".to_owned()],
+			}),
+			..Default::default()
+		};
+		let out = String::from_utf8(export(&ds, cfg).await.unwrap()).unwrap();
+		let lines: Vec<serde_json::Value> =
+			out.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+		assert_eq!(
+			lines,
+			[
+				serde_json::json!({ "id": "company:one", "name": "SurrealDB" }),
+				serde_json::json!({ "id": "person:one", "name": { "first": "Tobie", "last": "Morgan" }, "age": 30 }),
+				serde_json::json!({ "id": "person:two", "name": { "first": "Jaime" }, "tags": ["a", "b"] }),
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn export_csv() {
+		let ds = populated_datastore().await;
+		let cfg = Config {
+			format: ExportFormat::Csv,
+			tables: TableConfig::Some(vec!["person".to_owned()]),
+			..Default::default()
+		};
+		let out = String::from_utf8(export(&ds, cfg.clone()).await.unwrap()).unwrap();
+		assert_eq!(
+			out,
+			"id,age,name,tags\n\
+			person:one,30,\"{\"\"first\"\":\"\"Tobie\"\",\"\"last\"\":\"\"Morgan\"\"}\",\n\
+			person:two,,\"{\"\"first\"\":\"\"Jaime\"\"}\",\"[\"\"a\"\",\"\"b\"\"]\"\n"
+		);
+		let cfg = Config {
+			flatten: FlattenPolicy::Columns,
+			..cfg
+		};
+		let out = String::from_utf8(export(&ds, cfg).await.unwrap()).unwrap();
+		assert_eq!(
+			out,
+			"id,age,name.first,name.last,tags\n\
+			person:one,30,Tobie,Morgan,\n\
+			person:two,,Jaime,,\"[\"\"a\"\",\"\"b\"\"]\"\n"
+		);
+	}
+
+	#[tokio::test]
+	async fn export_columnar_single_table() {
+		let ds = populated_datastore().await;
+		let cfg = Config {
+			format: ExportFormat::Csv,
+			..Default::default()
+		};
+		let err = export(&ds, cfg).await.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref(),
+			Some(Error::ExportSingleTable {
+				count: 3,
+				..
+			})
+		));
+	}
+
+	#[cfg(feature = "parquet")]
+	#[tokio::test]
+	async fn export_parquet() {
+		use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+		use arrow_array::{Array, Int64Array, StringArray};
+
+		let ds = populated_datastore().await;
+		let cfg = Config {
+			format: ExportFormat::Parquet,
+			flatten: FlattenPolicy::Columns,
+			tables: TableConfig::Some(vec!["person".to_owned()]),
+			..Default::default()
+		};
+		let out = bytes::Bytes::from(export(&ds, cfg).await.unwrap());
+		let reader = ParquetRecordBatchReaderBuilder::try_new(out).unwrap().build().unwrap();
+		let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(batches.len(), 1);
+		let batch = &batches[0];
+		let names: Vec<_> = batch.schema().fields().iter().map(|f| f.name().clone()).collect();
+		assert_eq!(names, ["id", "age", "name.first", "name.last", "tags"]);
+		let ids = batch.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+		assert_eq!(ids.value(1), "person:two");
+		let ages = batch.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+		assert_eq!(ages.value(0), 30);
+		assert!(ages.is_null(1));
+	}
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+
+use super::{FlattenPolicy, records_range};
+use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::kvs::Transaction;
+use crate::val::{Number, Value, convert_value_to_public_value};
+
+/// The type of the values found in a column of a columnar export
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum ColumnKind {
+	/// Only empty values were found
+	Null,
+	Bool,
+	Int,
+	Float,
+	String,
+	Datetime,
+	/// Any other value, or values of different types, written as JSON
+	Json,
+}
+
+impl ColumnKind {
+	fn of(value: &Value) -> Self {
+		match value {
+			Value::None | Value::Null => Self::Null,
+			Value::Bool(_) => Self::Bool,
+			Value::Number(Number::Int(_)) => Self::Int,
+			Value::Number(Number::Float(_)) => Self::Float,
+			Value::String(_) => Self::String,
+			Value::Datetime(_) => Self::Datetime,
+			_ => Self::Json,
+		}
+	}
+
+	fn merge(self, other: Self) -> Self {
+		match (self, other) {
+			(Self::Null, v) | (v, Self::Null) => v,
+			(Self::Int, Self::Float) | (Self::Float, Self::Int) => Self::Float,
+			(a, b) if a == b => a,
+			_ => Self::Json,
+		}
+	}
+}
+
+/// The columns of a columnar export, in the order in which they are written.
+///
+/// The `id` column always comes first, followed by the other columns in the
+/// order in which they are first found in the records of the table.
+#[derive(Debug)]
+pub(super) struct Columns {
+	columns: Vec<(String, ColumnKind)>,
+	index: HashMap<String, usize>,
+}
+
+impl Default for Columns {
+	fn default() -> Self {
+		Self {
+			columns: vec![("id".to_owned(), ColumnKind::Null)],
+			index: HashMap::from([("id".to_owned(), 0)]),
+		}
+	}
+}
+
+impl Columns {
+	/// Adds the cells of a record to the columns
+	pub(super) fn add(&mut self, cells: &[(String, Value)]) {
+		for (name, value) in cells {
+			let kind = ColumnKind::of(value);
+			match self.index.get(name) {
+				Some(&i) => self.columns[i].1 = self.columns[i].1.merge(kind),
+				None => {
+					self.index.insert(name.clone(), self.columns.len());
+					self.columns.push((name.clone(), kind));
+				}
+			}
+		}
+	}
+
+	/// Returns the name and kind of each column
+	pub(super) fn iter(&self) -> impl Iterator<Item = (&str, ColumnKind)> {
+		self.columns.iter().map(|(name, kind)| (name.as_str(), *kind))
+	}
+
+	/// Places the cells of a record into their columns, leaving the columns
+	/// which are missing from the record empty.
+	pub(super) fn row(&self, cells: Vec<(String, Value)>) -> Vec<Value> {
+		let mut row = vec![Value::None; self.columns.len()];
+		for (name, value) in cells {
+			if let Some(&i) = self.index.get(&name) {
+				row[i] = value;
+			}
+		}
+		row
+	}
+}
+
+/// Flattens a record into named cells according to the flatten policy
+pub(super) fn flatten(record: Value, policy: FlattenPolicy) -> Vec<(String, Value)> {
+	let mut cells = Vec::new();
+	if let Value::Object(object) = record {
+		for (key, value) in object.0 {
+			flatten_into(key, value, policy, &mut cells);
+		}
+	}
+	cells
+}
+
+fn flatten_into(
+	name: String,
+	value: Value,
+	policy: FlattenPolicy,
+	cells: &mut Vec<(String, Value)>,
+) {
+	match value {
+		Value::Object(object) if policy == FlattenPolicy::Columns && !object.0.is_empty() => {
+			for (key, value) in object.0 {
+				flatten_into(format!("{name}.{key}"), value, policy, cells);
+			}
+		}
+		value => cells.push((name, value)),
+	}
+}
+
+/// Converts a cell into its textual representation.
+///
+/// Strings are written as is, empty values are written as an empty string,
+/// and any other value is written as JSON.
+pub(super) fn text(value: Value) -> Result<String> {
+	Ok(match convert_value_to_public_value(value)?.into_json_value() {
+		JsonValue::Null => String::new(),
+		JsonValue::String(v) => v,
+		v => v.to_string(),
+	})
+}
+
+impl Transaction {
+	/// Scans the records of a table to find the columns of a columnar export
+	pub(super) async fn export_columns(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		table: &TableDefinition,
+		policy: FlattenPolicy,
+	) -> Result<Columns> {
+		let mut columns = Columns::default();
+		let mut next = Some(records_range(ns, db, table)?);
+		loop {
+			let records = self.export_records(&mut next).await?;
+			if records.is_empty() {
+				break;
+			}
+			for record in records {
+				columns.add(&flatten(record, policy));
+			}
+		}
+		Ok(columns)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::expression::convert_public_value_to_internal;
+	use crate::syn;
+
+	fn value(input: &str) -> Value {
+		convert_public_value_to_internal(syn::value(input).unwrap())
+	}
+
+	#[test]
+	fn flatten_policies() {
+		let record =
+			value("{ id: person:one, name: { first: 'Tobie', last: 'Morgan' }, tags: ['a'] }");
+		let names =
+			|cells: Vec<(String, Value)>| cells.into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+		assert_eq!(names(flatten(record.clone(), FlattenPolicy::Json)), ["id", "name", "tags"]);
+		assert_eq!(
+			names(flatten(record, FlattenPolicy::Columns)),
+			["id", "name.first", "name.last", "tags"]
+		);
+	}
+
+	#[test]
+	fn column_kinds() {
+		let mut columns = Columns::default();
+		columns.add(&flatten(
+			value("{ id: t:1, a: 1, b: 'x', c: true, d: NONE }"),
+			FlattenPolicy::Json,
+		));
+		columns.add(&flatten(
+			value("{ id: t:2, a: 1.5, b: 2, d: d'2024-01-01T00:00:00Z', e: [1] }"),
+			FlattenPolicy::Json,
+		));
+		let kinds = columns.iter().collect::<Vec<_>>();
+		assert_eq!(
+			kinds,
+			[
+				("id", ColumnKind::Json),
+				("a", ColumnKind::Float),
+				("b", ColumnKind::Json),
+				("c", ColumnKind::Bool),
+				("d", ColumnKind::Datetime),
+				("e", ColumnKind::Json),
+			]
+		);
+		let row = columns.row(flatten(value("{ id: t:3, c: false }"), FlattenPolicy::Json));
+		let row = row.into_iter().map(text).collect::<Result<Vec<_>>>().unwrap();
+		assert_eq!(row, ["t:3", "", "", "false", "", ""]);
+	}
+
+	#[test]
+	fn cell_text() {
+		assert_eq!(text(value("'a,b'")).unwrap(), "a,b");
+		assert_eq!(text(value("NULL")).unwrap(), "");
+		assert_eq!(text(value("{ a: [1, 2] }")).unwrap(), r#"{"a":[1,2]}"#);
+		assert_eq!(text(value("d'2024-01-01T00:00:00Z'")).unwrap(), "2024-01-01T00:00:00Z");
+	}
+}
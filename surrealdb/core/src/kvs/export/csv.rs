@@ -0,0 +1,44 @@
+use anyhow::Result;
+use async_channel::Sender;
+
+use super::columns::{flatten, text};
+use super::{FlattenPolicy, records_range};
+use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::kvs::Transaction;
+
+impl Transaction {
+	/// Writes the records of a table as comma-separated values.
+	///
+	/// The columns are found with a first scan of the table, and written in
+	/// a header row. The records are then written with a second scan, with
+	/// an empty cell for each column which is missing from a record.
+	pub(super) async fn export_csv(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		table: &TableDefinition,
+		policy: FlattenPolicy,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<()> {
+		// Output the header row
+		let columns = self.export_columns(ns, db, table, policy).await?;
+		let mut writer = csv::Writer::from_writer(Vec::new());
+		writer.write_record(columns.iter().map(|(name, _)| name))?;
+		chn.send(writer.into_inner()?).await?;
+		// Output the records
+		let mut next = Some(records_range(ns, db, table)?);
+		loop {
+			let records = self.export_records(&mut next).await?;
+			if records.is_empty() {
+				break;
+			}
+			let mut writer = csv::Writer::from_writer(Vec::new());
+			for record in records {
+				let row = columns.row(flatten(record, policy));
+				writer.write_record(row.into_iter().map(text).collect::<Result<Vec<_>>>()?)?;
+			}
+			chn.send(writer.into_inner()?).await?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use async_channel::Sender;
+
+use super::records_range;
+use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::kvs::Transaction;
+use crate::val::convert_value_to_public_value;
+
+impl Transaction {
+	/// Writes the records of a table as newline-delimited JSON.
+	///
+	/// Each record is written as a JSON object on its own line, with its
+	/// record id in the `id` field.
+	pub(super) async fn export_ndjson(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		table: &TableDefinition,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<()> {
+		let mut next = Some(records_range(ns, db, table)?);
+		loop {
+			let records = self.export_records(&mut next).await?;
+			if records.is_empty() {
+				break;
+			}
+			let mut out = Vec::new();
+			for record in records {
+				let json = convert_value_to_public_value(record)?.into_json_value();
+				serde_json::to_writer(&mut out, &json)?;
+				out.push(b'\n');
+			}
+			chn.send(out).await?;
+		}
+		Ok(())
+	}
+}
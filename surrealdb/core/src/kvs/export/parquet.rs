@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+#[cfg(not(target_family = "wasm"))]
+use anyhow::Context;
+use anyhow::Result;
+use arrow_array::{
+	ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
+	TimestampMicrosecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use async_channel::Sender;
+use parquet::arrow::ArrowWriter;
+#[cfg(not(target_family = "wasm"))]
+use tokio::task::spawn_blocking;
+
+use super::columns::{ColumnKind, Columns, flatten, text};
+use super::{FlattenPolicy, records_range};
+use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::cnf::EXPORT_PARQUET_ROW_GROUP_BYTES;
+use crate::kvs::Transaction;
+use crate::val::{Number, Value};
+
+impl Transaction {
+	/// Writes the records of a table as an Apache Parquet file.
+	///
+	/// The schema is found with a first scan of the table, and the records
+	/// are then written with a second scan. Columns holding values of
+	/// different types, or values without a Parquet equivalent, are written
+	/// as JSON strings.
+	///
+	/// A row group is written out once it buffers more than
+	/// `EXPORT_PARQUET_ROW_GROUP_BYTES`, and is sent to the channel right away,
+	/// so the memory used does not depend on the size of the table. Each
+	/// batch of records is encoded on the blocking thread pool, off the async
+	/// runtime.
+	pub(super) async fn export_parquet(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		table: &TableDefinition,
+		policy: FlattenPolicy,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<()> {
+		// Find the schema of the file
+		let columns = Arc::new(self.export_columns(ns, db, table, policy).await?);
+		let schema = Arc::new(Schema::new(
+			columns
+				.iter()
+				.map(|(name, kind)| Field::new(name, data_type(kind), true))
+				.collect::<Vec<_>>(),
+		));
+		let mut writer = ArrowWriter::try_new(Vec::new(), schema.clone(), None)?;
+		// Output the records
+		let mut next = Some(records_range(ns, db, table)?);
+		loop {
+			let records = self.export_records(&mut next).await?;
+			if records.is_empty() {
+				break;
+			}
+			#[cfg(not(target_family = "wasm"))]
+			let out = {
+				let (columns, schema) = (columns.clone(), schema.clone());
+				let (done, out) = spawn_blocking(move || {
+					let out = encode(&mut writer, &columns, schema, records, policy);
+					(writer, out)
+				})
+				.await
+				.context("Parquet encoding task join error")?;
+				writer = done;
+				out?
+			};
+			#[cfg(target_family = "wasm")]
+			let out = encode(&mut writer, &columns, schema.clone(), records, policy)?;
+			// Send the row groups which have been written so far
+			if !out.is_empty() {
+				chn.send(out).await?;
+			}
+		}
+		// Output the remaining row groups and the footer
+		#[cfg(not(target_family = "wasm"))]
+		let out = spawn_blocking(move || writer.into_inner())
+			.await
+			.context("Parquet encoding task join error")??;
+		#[cfg(target_family = "wasm")]
+		let out = writer.into_inner()?;
+		chn.send(out).await?;
+		Ok(())
+	}
+}
+
+/// Writes a batch of records, and returns the row groups which have been
+/// written so far.
+fn encode(
+	writer: &mut ArrowWriter<Vec<u8>>,
+	columns: &Columns,
+	schema: Arc<Schema>,
+	records: Vec<Value>,
+	policy: FlattenPolicy,
+) -> Result<Vec<u8>> {
+	let batch = record_batch(columns, schema, records, policy)?;
+	writer.write(&batch)?;
+	// Close the row group once it buffers too much data
+	if writer.in_progress_size() >= *EXPORT_PARQUET_ROW_GROUP_BYTES {
+		writer.flush()?;
+	}
+	Ok(std::mem::take(writer.inner_mut()))
+}
+
+fn data_type(kind: ColumnKind) -> DataType {
+	match kind {
+		ColumnKind::Bool => DataType::Boolean,
+		ColumnKind::Int => DataType::Int64,
+		ColumnKind::Float => DataType::Float64,
+		ColumnKind::Datetime => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+		ColumnKind::Null | ColumnKind::String | ColumnKind::Json => DataType::Utf8,
+	}
+}
+
+fn record_batch(
+	columns: &Columns,
+	schema: Arc<Schema>,
+	records: Vec<Value>,
+	policy: FlattenPolicy,
+) -> Result<RecordBatch> {
+	// Pivot the records into columns
+	let mut cells: Vec<Vec<Value>> =
+		columns.iter().map(|_| Vec::with_capacity(records.len())).collect();
+	for record in records {
+		for (i, value) in columns.row(flatten(record, policy)).into_iter().enumerate() {
+			cells[i].push(value);
+		}
+	}
+	// Convert each column into an array
+	let arrays = columns
+		.iter()
+		.zip(cells)
+		.map(|((_, kind), values)| array(kind, values))
+		.collect::<Result<Vec<_>>>()?;
+	Ok(RecordBatch::try_new(schema, arrays)?)
+}
+
+fn array(kind: ColumnKind, values: Vec<Value>) -> Result<ArrayRef> {
+	Ok(match kind {
+		ColumnKind::Bool => Arc::new(BooleanArray::from(
+			values
+				.into_iter()
+				.map(|v| match v {
+					Value::Bool(v) => Some(v),
+					_ => None,
+				})
+				.collect::<Vec<_>>(),
+		)),
+		ColumnKind::Int => Arc::new(Int64Array::from(
+			values
+				.into_iter()
+				.map(|v| match v {
+					Value::Number(Number::Int(v)) => Some(v),
+					_ => None,
+				})
+				.collect::<Vec<_>>(),
+		)),
+		ColumnKind::Float => Arc::new(Float64Array::from(
+			values
+				.into_iter()
+				.map(|v| match v {
+					Value::Number(Number::Int(v)) => Some(v as f64),
+					Value::Number(Number::Float(v)) => Some(v),
+					_ => None,
+				})
+				.collect::<Vec<_>>(),
+		)),
+		ColumnKind::Datetime => Arc::new(
+			TimestampMicrosecondArray::from(
+				values
+					.into_iter()
+					.map(|v| match v {
+						Value::Datetime(v) => Some(v.0.timestamp_micros()),
+						_ => None,
+					})
+					.collect::<Vec<_>>(),
+			)
+			.with_timezone("UTC"),
+		),
+		ColumnKind::Null | ColumnKind::String | ColumnKind::Json => Arc::new(StringArray::from(
+			values
+				.into_iter()
+				.map(|v| match v {
+					Value::None | Value::Null => Ok(None),
+					v => text(v).map(Some),
+				})
+				.collect::<Result<Vec<_>>>()?,
+		)),
+	})
+}
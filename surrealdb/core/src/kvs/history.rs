@@ -0,0 +1,707 @@
+//! Versioned queries for storage engines without MVCC history.
+//!
+//! When versioning is enabled on the `rocksdb` or `tikv` storage engines,
+//! every transaction is wrapped in a [`HistoryTransaction`]. On commit, each
+//! record document, table definition and field definition which was written
+//! is also written to a history keyspace, at the timestamp of the
+//! transaction, as given by the storage engine, in nanoseconds. These are the
+//! keys read by versioned queries, `SHOW HISTORY`, `SHOW VERSION`, and
+//! `INFO FOR DB` and `INFO FOR TABLE` at a version:
+//!
+//! `!h{key}{ts}` -> `{0}` if the key was deleted, or `{1}{val}` if it was set
+//!
+//! The key is escaped so that the history entries sort in the same order as
+//! the keys, followed by the versions of each key in timestamp order. A read
+//! at a version then returns, for each key, the latest entry which is not
+//! newer than the version. History is only kept from the moment versioning is
+//! enabled. Unlike on storage engines which keep every version of every key,
+//! indexes are not versioned, so versioned queries scan the records of a table
+//! rather than its indexes.
+//!
+//! Entries older than the retention period are removed by a background task,
+//! which sweeps the whole history keyspace a batch at a time, except for the
+//! latest entry of each key, which is still needed to read the key at the
+//! start of the retention window, unless the key was deleted.
+//!
+//! Keeping the history amplifies the writes of records: each record which is
+//! written is written twice, once to its key and once to the history.
+//! Deleting a range of keys also scans the range, to record the deletion of
+//! the records within it.
+#![cfg(any(feature = "kv-rocksdb", feature = "kv-tikv"))]
+
+use std::collections::BTreeMap;
+use std::mem;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use super::api::{ScanLimit, Transactable};
+use super::err::{Error, Result};
+use super::{BoxTimeStamp, BoxTimeStampImpl, Direction, Key, Val};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::key::database::tb::TableKey;
+use crate::key::debug::Sprintable;
+use crate::key::record::RecordKey;
+use crate::key::table::fd::Fd;
+
+/// The prefix of the history keyspace
+const PREFIX: &[u8] = b"!h";
+
+/// The key after every entry of the history keyspace
+const SUFFIX: &[u8] = b"!i";
+
+/// The value tag of a deleted key
+const DELETED: u8 = 0;
+
+/// The value tag of a set key
+const SET: u8 = 1;
+
+/// Encodes the start of the history entries of a key
+fn prefix(key: &[u8]) -> Key {
+	let mut k = Vec::with_capacity(PREFIX.len() + key.len() + 10);
+	k.extend_from_slice(PREFIX);
+	for &b in key {
+		k.push(b);
+		if b == 0x00 {
+			k.push(0xff);
+		}
+	}
+	k.extend_from_slice(&[0x00, 0x00]);
+	k
+}
+
+/// Encodes the history entry of a key at a version
+fn entry(key: &[u8], version: u64) -> Key {
+	let mut k = prefix(key);
+	k.extend_from_slice(&version.to_be_bytes());
+	k
+}
+
+/// Encodes the key after every history entry of a key up to a version
+fn until(key: &[u8], version: u64) -> Key {
+	let mut k = entry(key, version);
+	k.push(0x00);
+	k
+}
+
+/// Decodes a history entry into the key and version
+fn decode(k: &[u8]) -> Option<(Key, u64)> {
+	let mut bytes = k.strip_prefix(PREFIX)?.iter();
+	let mut key = Vec::new();
+	loop {
+		match (*bytes.next()?, bytes.as_slice().first()) {
+			(0x00, Some(0xff)) => {
+				bytes.next();
+				key.push(0x00);
+			}
+			(0x00, Some(0x00)) => {
+				bytes.next();
+				break;
+			}
+			(b, _) => key.push(b),
+		}
+	}
+	let version = u64::from_be_bytes(bytes.as_slice().try_into().ok()?);
+	Some((key, version))
+}
+
+/// Decodes a history value, returning `None` if the key was deleted
+fn value(v: Val) -> Option<Val> {
+	match v.split_first() {
+		Some((&SET, val)) => Some(val.to_vec()),
+		_ => None,
+	}
+}
+
+/// Returns the version of a timestamp of the storage engine, in nanoseconds,
+/// as used for versioned queries
+fn version(ts: &BoxTimeStamp) -> Result<u64> {
+	ts.as_datetime()
+		.and_then(|dt| dt.timestamp_nanos_opt())
+		.and_then(|ns| u64::try_from(ns).ok())
+		.ok_or_else(|| Error::TimestampInvalid("The timestamp is out of range".to_string()))
+}
+
+/// A write recorded in the transaction, to be added to the history on commit
+#[derive(Clone)]
+enum Write {
+	/// The key was set to a value
+	Set(Val),
+	/// The key was deleted
+	Del,
+	/// The key and all its versions were deleted
+	Clr,
+}
+
+#[derive(Default)]
+struct State {
+	/// The latest write of each key in the transaction
+	writes: BTreeMap<Key, Write>,
+	/// The previous writes overwritten since each save point
+	savepoints: Vec<Vec<(Key, Option<Write>)>>,
+}
+
+/// Checks whether the history of a key is kept
+fn is_versioned(key: &[u8]) -> bool {
+	RecordKey::is_record_key(key) || TableKey::is_table_key(key) || Fd::is_field_key(key)
+}
+
+impl State {
+	fn record(&mut self, key: Key, write: Write) {
+		if !is_versioned(&key) {
+			return;
+		}
+		let previous = self.writes.insert(key.clone(), write);
+		if let Some(savepoint) = self.savepoints.last_mut() {
+			savepoint.push((key, previous));
+		}
+	}
+}
+
+/// A transaction which keeps the history of the keys it writes, for
+/// storage engines which do not support versioned queries themselves.
+pub(super) struct HistoryTransaction {
+	/// The underlying datastore transaction
+	inner: Box<dyn Transactable>,
+	/// Version retention period in nanoseconds (0 = unlimited)
+	retention_ns: u64,
+	/// The writes of this transaction
+	state: Mutex<State>,
+}
+
+impl HistoryTransaction {
+	pub(super) fn new(inner: Box<dyn Transactable>, retention_ns: u64) -> Box<dyn Transactable> {
+		Box::new(Self {
+			inner,
+			retention_ns,
+			state: Mutex::new(State::default()),
+		})
+	}
+
+	fn record(&self, key: Key, write: Write) {
+		self.state.lock().unwrap_or_else(|e| e.into_inner()).record(key, write);
+	}
+
+	/// Writes the history entries of the keys written in this transaction.
+	async fn write_history(&self) -> Result<()> {
+		let writes = {
+			let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+			mem::take(&mut state.writes)
+		};
+		let version = version(&self.inner.timestamp().await?)?;
+		for (key, write) in writes {
+			let val = match write {
+				Write::Set(val) => {
+					let mut v = Vec::with_capacity(val.len() + 1);
+					v.push(SET);
+					v.extend(val);
+					v
+				}
+				Write::Del => vec![DELETED],
+				Write::Clr => continue,
+			};
+			self.inner.set(entry(&key, version), val, None).await?;
+		}
+		Ok(())
+	}
+
+	/// Removes a batch of the history entries which have left the retention
+	/// window, starting from a key of the history keyspace, and returns the
+	/// key from which to continue, if the keyspace has not been swept yet.
+	async fn prune(&self, from: Option<Key>) -> Result<Option<Key>> {
+		// Keep every version when there is no retention period
+		if self.retention_ns == 0 {
+			return Ok(None);
+		}
+		let now = version(&self.inner.timestamp().await?)?;
+		let Some(cutoff) = now.checked_sub(self.retention_ns) else {
+			return Ok(None);
+		};
+		let batch = *NORMAL_FETCH_SIZE;
+		let beg = from.unwrap_or_else(|| PREFIX.to_vec());
+		let res = self.inner.scan(beg..SUFFIX.to_vec(), ScanLimit::Count(batch), 0, None).await?;
+		let done = res.len() < batch as usize;
+		let last = res.last().map(|(k, _)| k.clone());
+		// The latest stale entry of the key being swept, and whether it is a
+		// deletion, which is removed once a newer stale entry is found
+		let mut stale: Option<(Key, Key, bool)> = None;
+		for (k, v) in res {
+			let Some((key, ts)) = decode(&k) else {
+				continue;
+			};
+			if let Some((cur, entry, deleted)) = stale.take() {
+				// A stale entry is no longer needed once a newer entry of
+				// the key is stale as well, or if it is a deletion
+				if deleted || (cur == key && ts < cutoff) {
+					self.inner.del(entry).await?;
+				}
+			}
+			if ts < cutoff {
+				stale = Some((key, k, v.first() != Some(&SET)));
+			}
+		}
+		match stale {
+			// Check the latest stale entry again with the next batch, which
+			// may hold newer entries of the key
+			Some((_, entry, _)) if !done => Ok(Some(entry)),
+			Some((_, entry, deleted)) => {
+				if deleted {
+					self.inner.del(entry).await?;
+				}
+				Ok(None)
+			}
+			None if done => Ok(None),
+			None => Ok(last.map(|mut k| {
+				k.push(0x00);
+				k
+			})),
+		}
+	}
+
+	/// Fetches a key at a version from the history.
+	async fn get_version(&self, key: &[u8], version: u64) -> Result<Option<Val>> {
+		let rng = prefix(key)..until(key, version);
+		let res = self.inner.scanr(rng, ScanLimit::Count(1), 0, None).await?;
+		Ok(res.into_iter().next().and_then(|(_, v)| value(v)))
+	}
+
+	/// Scans a range of keys at a version from the history.
+	async fn scan_version(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: u64,
+		dir: Direction,
+	) -> Result<Vec<(Key, Val)>> {
+		let batch = *NORMAL_FETCH_SIZE;
+		let mut out = Output {
+			res: Vec::new(),
+			bytes: 0,
+			skip,
+			limit,
+		};
+		// The key being read, and its latest value at the version
+		let mut current: Option<(Key, Option<Val>, u64)> = None;
+		let mut next = Some(prefix(&rng.start)..prefix(&rng.end));
+		while let Some(rng) = next.take() {
+			let res = match dir {
+				Direction::Forward => {
+					self.inner.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?
+				}
+				Direction::Backward => {
+					self.inner.scanr(rng.clone(), ScanLimit::Count(batch), 0, None).await?
+				}
+			};
+			// Continue after the last entry of the batch
+			if res.len() >= batch as usize
+				&& let Some((last, _)) = res.last()
+			{
+				next = Some(match dir {
+					Direction::Forward => {
+						let mut start = last.clone();
+						start.push(0x00);
+						start..rng.end
+					}
+					Direction::Backward => rng.start..last.clone(),
+				});
+			}
+			for (k, v) in res {
+				let Some((key, ts)) = decode(&k) else {
+					continue;
+				};
+				// Keep the latest version of the key which is not newer than the version
+				if let Some((cur, val, at)) = &mut current
+					&& *cur == key
+				{
+					if ts <= version && ts >= *at {
+						*val = value(v);
+						*at = ts;
+					}
+					continue;
+				}
+				// Otherwise this is the first entry of the next key
+				if let Some((cur, val, _)) = current.take()
+					&& out.push(cur, val)
+				{
+					return Ok(out.res);
+				}
+				current = Some(match ts <= version {
+					true => (key, value(v), ts),
+					false => (key, None, 0),
+				});
+			}
+		}
+		if let Some((cur, val, _)) = current {
+			out.push(cur, val);
+		}
+		Ok(out.res)
+	}
+}
+
+/// The output of a versioned scan
+struct Output {
+	res: Vec<(Key, Val)>,
+	bytes: usize,
+	skip: u32,
+	limit: ScanLimit,
+}
+
+impl Output {
+	/// Adds a key to the output, returning true once the limit is reached
+	fn push(&mut self, key: Key, val: Option<Val>) -> bool {
+		if let Some(val) = val {
+			if self.skip > 0 {
+				self.skip -= 1;
+			} else {
+				self.bytes += key.len() + val.len();
+				self.res.push((key, val));
+			}
+		}
+		match self.limit {
+			ScanLimit::Count(c) => self.res.len() >= c as usize,
+			ScanLimit::Bytes(b) => self.bytes >= b as usize,
+			ScanLimit::BytesOrCount(b, c) => {
+				self.res.len() >= c as usize || self.bytes >= b as usize
+			}
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for HistoryTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		*self.state.lock().unwrap_or_else(|e| e.into_inner()) = State::default();
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		if self.writeable() {
+			self.write_history().await?;
+		}
+		self.inner.commit().await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		match version {
+			Some(v) => Ok(self.get_version(&key, v).await?.is_some()),
+			None => self.inner.exists(key, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		match version {
+			Some(v) => self.get_version(&key, v).await,
+			None => self.inner.get(key, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(keys = keys.sprint()))]
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		match version {
+			Some(v) => {
+				let mut out = Vec::with_capacity(keys.len());
+				for key in keys {
+					out.push(self.get_version(&key, v).await?);
+				}
+				Ok(out)
+			}
+			None => self.inner.getm(keys, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.inner.set(key.clone(), val.clone(), version).await?;
+		self.record(key, Write::Set(val));
+		Ok(())
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.inner.put(key.clone(), val.clone(), version).await?;
+		self.record(key, Write::Set(val));
+		Ok(())
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		self.inner.putc(key.clone(), val.clone(), chk).await?;
+		self.record(key, Write::Set(val));
+		Ok(())
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del(&self, key: Key) -> Result<()> {
+		self.inner.del(key.clone()).await?;
+		self.record(key, Write::Del);
+		Ok(())
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.inner.delc(key.clone(), chk).await?;
+		self.record(key, Write::Del);
+		Ok(())
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn delr(&self, rng: Range<Key>) -> Result<()> {
+		// Record the deletion of the records in the range, so that the range
+		// itself can be deleted by the storage engine at once
+		let mut next = Some(rng.clone());
+		while let Some(rng) = next {
+			let res = self.inner.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = res.next;
+			for k in res.result {
+				self.record(k, Write::Del);
+			}
+		}
+		self.inner.delr(rng).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.inner.del(key.clone()).await?;
+		// Remove every version of the key, a batch at a time
+		let batch = *NORMAL_FETCH_SIZE;
+		loop {
+			let rng = prefix(&key)..until(&key, u64::MAX);
+			let res = self.inner.keys(rng, ScanLimit::Count(batch), 0, None).await?;
+			let done = res.len() < batch as usize;
+			for k in res {
+				self.inner.del(k).await?;
+			}
+			if done {
+				break;
+			}
+		}
+		self.record(key, Write::Clr);
+		Ok(())
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.inner.delc(key.clone(), chk).await?;
+		self.clr(key).await
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn count(&self, rng: Range<Key>, version: Option<u64>) -> Result<usize> {
+		match version {
+			Some(v) => {
+				let res = self
+					.scan_version(rng, ScanLimit::Count(u32::MAX), 0, v, Direction::Forward)
+					.await?;
+				Ok(res.len())
+			}
+			None => self.inner.count(rng, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		match version {
+			Some(v) => {
+				let res = self.scan_version(rng, limit, skip, v, Direction::Forward).await?;
+				Ok(res.into_iter().map(|(k, _)| k).collect())
+			}
+			None => self.inner.keys(rng, limit, skip, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		match version {
+			Some(v) => {
+				let res = self.scan_version(rng, limit, skip, v, Direction::Backward).await?;
+				Ok(res.into_iter().map(|(k, _)| k).collect())
+			}
+			None => self.inner.keysr(rng, limit, skip, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		match version {
+			Some(v) => self.scan_version(rng, limit, skip, v, Direction::Forward).await,
+			None => self.inner.scan(rng, limit, skip, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		match version {
+			Some(v) => self.scan_version(rng, limit, skip, v, Direction::Backward).await,
+			None => self.inner.scanr(rng, limit, skip, None).await,
+		}
+	}
+
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn versions(&self, key: Key, beg: u64, end: u64) -> Result<Vec<(u64, Option<Val>)>> {
+		let batch = *NORMAL_FETCH_SIZE;
+		let mut out = Vec::new();
+		let mut next = Some(entry(&key, beg)..until(&key, end));
+		while let Some(rng) = next.take() {
+			let res = self.inner.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?;
+			// Continue after the last entry of the batch
+			if res.len() >= batch as usize
+				&& let Some((last, _)) = res.last()
+			{
+				let mut start = last.clone();
+				start.push(0x00);
+				next = Some(start..rng.end);
+			}
+			for (k, v) in res {
+				if let Some((_, ts)) = decode(&k) {
+					out.push((ts, value(v)));
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await?;
+		self.state.lock().unwrap_or_else(|e| e.into_inner()).savepoints.push(Vec::new());
+		Ok(())
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await?;
+		let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+		// Keep the overwritten writes, for a rollback to the previous save point
+		if let Some(log) = state.savepoints.pop()
+			&& let Some(parent) = state.savepoints.last_mut()
+		{
+			parent.extend(log);
+		}
+		Ok(())
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await?;
+		let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(log) = state.savepoints.pop() {
+			for (key, previous) in log.into_iter().rev() {
+				match previous {
+					Some(write) => state.writes.insert(key, write),
+					None => state.writes.remove(&key),
+				};
+			}
+		}
+		Ok(())
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+
+	async fn prune_history(&self, from: Option<Key>) -> Result<Option<Key>> {
+		self.prune(from).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::{DatabaseId, NamespaceId};
+	use crate::kvs::KVKey;
+	use crate::val::{RecordIdKey, TableName};
+
+	#[test]
+	fn entries_sort_by_key_then_version() {
+		let keys: [&[u8]; 4] = [b"/a", b"/a\x00", b"/a\x00b", b"/ab"];
+		let mut entries = Vec::new();
+		for key in keys {
+			for version in [0, 1, u64::MAX] {
+				entries.push(entry(key, version));
+			}
+		}
+		let mut sorted = entries.clone();
+		sorted.sort();
+		assert_eq!(entries, sorted);
+		// The entries of each key are within the range of the key
+		for key in keys {
+			assert!(prefix(key) <= entry(key, 0));
+			assert!(entry(key, u64::MAX) < until(key, u64::MAX));
+		}
+		assert!(until(b"/a", u64::MAX) < prefix(b"/a\x00"));
+	}
+
+	#[test]
+	fn only_records_and_definitions_are_versioned() {
+		let tb = TableName::from("person");
+		let id = RecordIdKey::String("one".to_owned());
+		let record = RecordKey::new(NamespaceId(1), DatabaseId(2), &tb, id).encode_key().unwrap();
+		let table = TableKey::new(NamespaceId(1), DatabaseId(2), &tb).encode_key().unwrap();
+		let field = Fd::new(NamespaceId(1), DatabaseId(2), &tb, "name").encode_key().unwrap();
+		let mut state = State::default();
+		state.record(record.clone(), Write::Del);
+		state.record(table.clone(), Write::Del);
+		state.record(field.clone(), Write::Del);
+		state.record(b"/!fu".to_vec(), Write::Del);
+		state.record(b"/*\x00\x00\x00\x01!db".to_vec(), Write::Del);
+		let mut expected = vec![&record, &table, &field];
+		expected.sort();
+		assert_eq!(state.writes.keys().collect::<Vec<_>>(), expected);
+	}
+
+	#[test]
+	fn entries_decode() {
+		for key in [&b"/a"[..], b"/a\x00\xff", b"", b"\x00\x00"] {
+			assert_eq!(decode(&entry(key, 42)), Some((key.to_vec(), 42)));
+		}
+		assert_eq!(decode(b"/a"), None);
+		assert_eq!(decode(&prefix(b"/a")), None);
+	}
+}
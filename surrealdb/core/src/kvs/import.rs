@@ -0,0 +1,588 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+use async_channel::Sender;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use surrealdb_types::SurrealValue;
+
+use super::Datastore;
+use crate::catalog::TableType;
+use crate::catalog::providers::TableProvider;
+use crate::cnf::{IMPORT_BATCH_SIZE, IMPORT_MAX_REJECTED};
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::expr::data::Data;
+use crate::expr::idiom::Idiom;
+use crate::expr::paths::{ID, IN, OUT};
+use crate::expr::plan::{LogicalPlan, TopLevelExpr};
+use crate::expr::statements::InsertStatement;
+use crate::expr::{Expr, Kind, Literal, Output, Part};
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::Read;
+use crate::sql::expression::convert_public_value_to_internal;
+use crate::syn;
+use crate::types::PublicValue;
+use crate::val::{TableName, Value, convert_value_to_public_value};
+
+mod csv;
+mod ndjson;
+#[cfg(feature = "parquet")]
+mod parquet;
+
+/// The configuration of a bulk import of records into a table
+#[derive(Clone, Debug, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+pub struct Config {
+	/// The table into which the records are imported
+	pub table: String,
+	/// The format of the imported file
+	pub format: ImportFormat,
+}
+
+/// The format of a file which is bulk imported into a table
+#[derive(Clone, Copy, Debug, Eq, PartialEq, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+#[surreal(untagged)]
+pub enum ImportFormat {
+	/// One JSON object per line for each record
+	#[surreal(value = "ndjson")]
+	Ndjson,
+	/// Comma-separated values, with the column names in a header row
+	#[surreal(value = "csv")]
+	Csv,
+	/// An Apache Parquet file
+	#[surreal(value = "parquet")]
+	Parquet,
+}
+
+impl fmt::Display for ImportFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Ndjson => f.write_str("ndjson"),
+			Self::Csv => f.write_str("csv"),
+			Self::Parquet => f.write_str("parquet"),
+		}
+	}
+}
+
+impl FromStr for ImportFormat {
+	type Err = anyhow::Error;
+	fn from_str(s: &str) -> Result<Self> {
+		match s.to_ascii_lowercase().as_str() {
+			"ndjson" | "jsonl" => Ok(Self::Ndjson),
+			"csv" => Ok(Self::Csv),
+			"parquet" => Ok(Self::Parquet),
+			_ => Err(Error::InvalidImportFormat(s.to_owned()).into()),
+		}
+	}
+}
+
+/// The outcome of a bulk import
+#[derive(Clone, Debug, Default, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+pub struct Report {
+	/// The number of records which were imported
+	pub imported: u64,
+	/// The number of rows which could not be imported
+	pub failed: u64,
+	/// The first rows which could not be imported, up to
+	/// `SURREAL_IMPORT_MAX_REJECTED` rows. Every rejected row is sent to the
+	/// channel of rejected rows, when the import has one.
+	pub rejected: Vec<Reject>,
+}
+
+/// A row which could not be imported
+#[derive(Clone, Debug, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+pub struct Reject {
+	/// The position of the row in the file, starting from 1
+	pub row: u64,
+	/// The reason why the row was rejected
+	pub error: String,
+	/// The record decoded from the row, if it could be decoded
+	pub record: Option<PublicValue>,
+}
+
+/// A decoded row, or the reason why it could not be decoded
+pub(super) type Row = std::result::Result<Value, String>;
+
+/// Incrementally decodes the rows of an imported text file
+trait Decoder: Send {
+	/// Appends the next chunk of the file
+	fn push(&mut self, chunk: &[u8]);
+	/// Signals that the whole file has been pushed
+	fn finish(&mut self) -> Result<()>;
+	/// Returns the next complete row, if any
+	fn next(&mut self) -> Result<Option<Row>>;
+}
+
+/// Builds a record from named columns, where dotted column names are
+/// expanded into nested objects.
+fn record(cells: impl IntoIterator<Item = (String, Value)>) -> Value {
+	let mut record = Value::empty_object();
+	for (name, value) in cells {
+		let path: Vec<Part> = name.split('.').map(|v| Part::Field(v.to_owned())).collect();
+		record.put(&path, value);
+	}
+	record
+}
+
+/// Writes decoded rows into a table, in batches.
+///
+/// Each batch is written with a single INSERT statement, which is built
+/// directly rather than parsed, and which runs in its own transaction. If a
+/// batch fails, its rows are written one by one, so that only the failing
+/// rows are rejected. Every rejected row is sent to the channel of rejected
+/// rows as soon as it is rejected, while the report only keeps the first ones.
+pub(super) struct Loader<'a> {
+	ds: &'a Datastore,
+	sess: &'a Session,
+	table: TableName,
+	relation: bool,
+	fields: Vec<(Idiom, Kind)>,
+	rows: u64,
+	batch: Vec<(u64, Value)>,
+	report: Report,
+	rejects: Option<Sender<Reject>>,
+}
+
+impl<'a> Loader<'a> {
+	pub(super) async fn new(
+		ds: &'a Datastore,
+		sess: &'a Session,
+		table: &str,
+		rejects: Option<Sender<Reject>>,
+	) -> Result<Self> {
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		let table = TableName::from(table);
+		// Fetch the table and field definitions
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let tb = catch!(txn, txn.get_tb_by_name(&ns, &db, &table).await);
+		let fields = match &tb {
+			Some(tb) => catch!(
+				txn,
+				txn.all_tb_fields(tb.namespace_id, tb.database_id, &tb.name, None).await
+			),
+			None => Default::default(),
+		};
+		txn.cancel().await?;
+		// Only coerce fields which are plain paths into the record
+		let fields = fields
+			.iter()
+			.filter(|f| f.name.iter().all(|p| matches!(p, Part::Field(_))))
+			.filter_map(|f| Some((f.name.clone(), f.field_kind.clone()?)))
+			.collect();
+		Ok(Self {
+			ds,
+			sess,
+			table,
+			relation: tb.is_some_and(|tb| matches!(tb.table_type, TableType::Relation(_))),
+			fields,
+			rows: 0,
+			batch: Vec::new(),
+			report: Report::default(),
+			rejects,
+		})
+	}
+
+	/// Adds a decoded row to the current batch
+	pub(super) async fn push(&mut self, row: Row) -> Result<()> {
+		self.rows += 1;
+		match row.and_then(|record| self.prepare(record)) {
+			Ok(record) => {
+				self.batch.push((self.rows, record));
+				if self.batch.len() >= *IMPORT_BATCH_SIZE {
+					self.flush().await?;
+				}
+			}
+			Err(error) => self.reject(self.rows, error, None).await?,
+		}
+		Ok(())
+	}
+
+	/// Writes the last batch, and returns the outcome of the import
+	pub(super) async fn finish(mut self) -> Result<Report> {
+		self.flush().await?;
+		Ok(self.report)
+	}
+
+	/// Converts the record id and the edges of the record, and coerces the
+	/// fields of the record into the kinds of their field definitions.
+	fn prepare(&self, mut record: Value) -> std::result::Result<Value, String> {
+		if !record.is_object() {
+			return Err("Expected the row to be an object".to_owned());
+		}
+		// Record ids are decoded as strings
+		if let Value::String(id) = record.pick(&*ID) {
+			let id = match syn::record_id(&id) {
+				Ok(rid) if rid.table.as_str() == self.table.as_str() => {
+					convert_public_value_to_internal(PublicValue::RecordId(rid))
+				}
+				_ => Value::String(id),
+			};
+			record.put(&*ID, id);
+		}
+		// The edges of relations must be record ids
+		if self.relation {
+			for path in [&*IN, &*OUT] {
+				if let Value::String(v) = record.pick(path) {
+					let rid = syn::record_id(&v)
+						.map_err(|_| format!("Expected a record id, found `{v}`"))?;
+					record.put(path, convert_public_value_to_internal(PublicValue::RecordId(rid)));
+				}
+			}
+		}
+		// Coerce the fields which have a kind
+		for (name, kind) in &self.fields {
+			let value = record.pick(name);
+			if value.is_none() {
+				continue;
+			}
+			let value = coerce(value, kind).map_err(|e| {
+				format!("Couldn't coerce the field `{}`: {e}", name.to_raw_string())
+			})?;
+			record.put(name, value);
+		}
+		Ok(record)
+	}
+
+	async fn flush(&mut self) -> Result<()> {
+		if self.batch.is_empty() {
+			return Ok(());
+		}
+		let (rows, records): (Vec<u64>, Vec<Value>) =
+			std::mem::take(&mut self.batch).into_iter().unzip();
+		if self.insert(&records).await?.is_ok() {
+			self.report.imported += records.len() as u64;
+			return Ok(());
+		}
+		// Find the rows which caused the batch to fail
+		for (row, record) in rows.into_iter().zip(records) {
+			match self.insert(std::slice::from_ref(&record)).await? {
+				Ok(()) => self.report.imported += 1,
+				Err(error) => self.reject(row, error, Some(record)).await?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Writes records with a single INSERT statement, whose data is a literal
+	/// built from the records
+	async fn insert(&self, records: &[Value]) -> Result<std::result::Result<(), String>> {
+		let data = records.iter().cloned().map(Value::into_literal).collect();
+		let stm = InsertStatement {
+			into: Some(Expr::Table(self.table.clone())),
+			data: Data::SingleExpression(Expr::Literal(Literal::Array(data))),
+			ignore: false,
+			update: None,
+			output: Some(Output::None),
+			timeout: Expr::Literal(Literal::None),
+			relation: self.relation,
+		};
+		let plan = LogicalPlan {
+			expressions: vec![TopLevelExpr::Expr(Expr::Insert(Box::new(stm)))],
+		};
+		let mut res = self.ds.process_plan(plan, self.sess, None).await?;
+		Ok(match res.pop().map(|r| r.result) {
+			Some(Err(e)) => Err(e.to_string()),
+			_ => Ok(()),
+		})
+	}
+
+	async fn reject(&mut self, row: u64, error: String, record: Option<Value>) -> Result<()> {
+		self.report.failed += 1;
+		let reject = Reject {
+			row,
+			error,
+			record: record.and_then(|v| convert_value_to_public_value(v).ok()),
+		};
+		// Only the first rejected rows are kept, so that the report is bounded
+		if self.report.rejected.len() < *IMPORT_MAX_REJECTED {
+			self.report.rejected.push(reject.clone());
+		}
+		if let Some(rejects) = &self.rejects
+			&& rejects.send(reject).await.is_err()
+		{
+			bail!(Error::Channel("The channel of rejected rows was closed".to_owned()));
+		}
+		Ok(())
+	}
+}
+
+/// Coerces a value into a kind. Strings are also cast into the kind, and
+/// parsed as JSON, so that the cells of text formats can be converted.
+fn coerce(value: Value, kind: &Kind) -> std::result::Result<Value, String> {
+	let Value::String(text) = value else {
+		return value.coerce_to_kind(kind).map_err(|e| e.to_string());
+	};
+	let value = Value::String(text.clone());
+	match value.coerce_to_kind(kind) {
+		Ok(v) => Ok(v),
+		Err(e) => {
+			if let Ok(v) = Value::String(text.clone()).cast_to_kind(kind) {
+				return Ok(v);
+			}
+			match syn::json(&text) {
+				Ok(v) => convert_public_value_to_internal(v)
+					.coerce_to_kind(kind)
+					.map_err(|_| e.to_string()),
+				Err(_) => Err(e.to_string()),
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Decodes the rows of a file, and writes them into the table
+	pub(super) async fn import_rows<S>(
+		&self,
+		sess: &Session,
+		cfg: Config,
+		stream: S,
+		rejects: Option<Sender<Reject>>,
+	) -> Result<Report>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		let loader = Loader::new(self, sess, &cfg.table, rejects).await?;
+		match cfg.format {
+			ImportFormat::Ndjson => decode(loader, ndjson::NdjsonDecoder::default(), stream).await,
+			ImportFormat::Csv => decode(loader, csv::CsvDecoder::default(), stream).await,
+			#[cfg(feature = "parquet")]
+			ImportFormat::Parquet => parquet::import(loader, stream).await,
+			#[cfg(not(feature = "parquet"))]
+			ImportFormat::Parquet => Err(Error::ImportFormatUnsupported(cfg.format.to_string()).into()),
+		}
+	}
+}
+
+/// Decodes the rows of a text file as its chunks are read, and writes them
+/// into the table
+async fn decode<S>(mut loader: Loader<'_>, mut decoder: impl Decoder, stream: S) -> Result<Report>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	let mut stream = std::pin::pin!(stream);
+	while let Some(chunk) = stream.next().await {
+		decoder.push(&chunk?);
+		while let Some(row) = decoder.next()? {
+			loader.push(row).await?;
+		}
+	}
+	decoder.finish()?;
+	while let Some(row) = decoder.next()? {
+		loader.push(row).await?;
+	}
+	loader.finish().await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	async fn schemafull_datastore() -> Datastore {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query(
+			"
+			DEFINE TABLE person SCHEMAFULL;
+			DEFINE FIELD name ON person TYPE string;
+			DEFINE FIELD age ON person TYPE int ASSERT $value >= 0;
+			DEFINE FIELD active ON person TYPE bool DEFAULT false;
+			DEFINE FIELD born ON person TYPE option<datetime>;
+			DEFINE FIELD address ON person TYPE option<object>;
+			DEFINE FIELD address.city ON person TYPE string;
+			DEFINE TABLE knows TYPE RELATION;
+			",
+		)
+		.await;
+		ds
+	}
+
+	fn session() -> Session {
+		Session::owner().with_ns("test").with_db("test")
+	}
+
+	/// Imports a file which is split into the given chunks
+	async fn import(ds: &Datastore, table: &str, format: ImportFormat, chunks: &[&[u8]]) -> Report {
+		let cfg = Config {
+			table: table.to_owned(),
+			format,
+		};
+		let stream = futures::stream::iter(
+			chunks.iter().map(|c| Ok(Bytes::copy_from_slice(c))).collect::<Vec<_>>(),
+		);
+		ds.import_records(&session(), cfg, stream, None).await.unwrap()
+	}
+
+	fn decode(mut decoder: impl Decoder, chunks: &[&[u8]]) -> Vec<Row> {
+		let mut rows = Vec::new();
+		for chunk in chunks {
+			decoder.push(chunk);
+			while let Some(row) = decoder.next().unwrap() {
+				rows.push(row);
+			}
+		}
+		decoder.finish().unwrap();
+		while let Some(row) = decoder.next().unwrap() {
+			rows.push(row);
+		}
+		rows
+	}
+
+	fn json(row: &Row) -> serde_json::Value {
+		let value = row.clone().unwrap();
+		convert_value_to_public_value(value).unwrap().into_json_value()
+	}
+
+	#[test]
+	fn parse_format() {
+		assert_eq!("CSV".parse::<ImportFormat>().unwrap(), ImportFormat::Csv);
+		assert_eq!("jsonl".parse::<ImportFormat>().unwrap(), ImportFormat::Ndjson);
+		assert!("surql".parse::<ImportFormat>().is_err());
+	}
+
+	#[test]
+	fn decode_csv() {
+		let rows = decode(
+			csv::CsvDecoder::default(),
+			&[
+				b"\xef\xbb\xbfid,name,address.ci",
+				b"ty\r\n1,\"Morgan, \"\"Tobie\"\"\",London\n2,",
+				b",\n3\n",
+			],
+		);
+		assert_eq!(rows.len(), 3);
+		assert_eq!(
+			json(&rows[0]),
+			serde_json::json!({ "id": "1", "name": "Morgan, \"Tobie\"", "address": { "city": "London" } })
+		);
+		assert_eq!(json(&rows[1]), serde_json::json!({ "id": "2" }));
+		assert_eq!(rows[2], Err("Expected 3 columns, found 1".to_owned()));
+	}
+
+	#[test]
+	fn decode_ndjson() {
+		let rows = decode(
+			ndjson::NdjsonDecoder::default(),
+			&[b"{\"id\": 1, \"tags\": [\"a\"]}\n\n{\"id\"", b": 2}\nnot json\n[1]"],
+		);
+		assert_eq!(rows.len(), 4);
+		assert_eq!(json(&rows[0]), serde_json::json!({ "id": 1, "tags": ["a"] }));
+		assert_eq!(json(&rows[1]), serde_json::json!({ "id": 2 }));
+		assert!(rows[2].is_err());
+		assert_eq!(json(&rows[3]), serde_json::json!([1]));
+	}
+
+	#[tokio::test]
+	async fn import_csv_with_coercion() {
+		let ds = schemafull_datastore().await;
+		let report = import(
+			&ds,
+			"person",
+			ImportFormat::Csv,
+			&[b"id,name,age,active,born,address.city\n\
+			person:one,Tobie,30,true,2020-01-01T00:00:00Z,London\n\
+			two,Jaime,thirty,,,\n\
+			three,Jaime,-1,,,\n\
+			four,Jaime,4,,,Paris\n"],
+		)
+		.await;
+		assert_eq!(report.imported, 2);
+		assert_eq!(report.failed, 2);
+		let rejected: Vec<_> =
+			report.rejected.iter().map(|r| (r.row, r.record.is_some())).collect();
+		// The second row can not be coerced, and the third row fails its assertion
+		assert_eq!(rejected, [(2, false), (3, true)]);
+		assert!(report.rejected[0].error.contains("`age`"), "{}", report.rejected[0].error);
+		assert_eq!(
+			ds.test_query("SELECT * FROM person").await.into_json_value(),
+			serde_json::json!([
+				{ "id": "person:four", "name": "Jaime", "age": 4, "active": false, "address": { "city": "Paris" } },
+				{ "id": "person:one", "name": "Tobie", "age": 30, "active": true, "born": "2020-01-01T00:00:00Z", "address": { "city": "London" } },
+			])
+		);
+	}
+
+	#[tokio::test]
+	async fn import_ndjson_relations() {
+		let ds = schemafull_datastore().await;
+		let report = import(
+			&ds,
+			"knows",
+			ImportFormat::Ndjson,
+			&[b"{\"in\": \"person:one\", \"out\": \"person:two\", \"since\": 2020}\n\
+			{\"in\": \"person:one\", \"out\": \"not a record\"}\n"],
+		)
+		.await;
+		assert_eq!(report.imported, 1);
+		assert_eq!(report.rejected.len(), 1);
+		assert_eq!(report.rejected[0].row, 2);
+		assert_eq!(
+			ds.test_query("SELECT in, out, since FROM knows").await.into_json_value(),
+			serde_json::json!([{ "in": "person:one", "out": "person:two", "since": 2020 }])
+		);
+	}
+
+	#[tokio::test]
+	async fn import_streams_every_rejected_row() {
+		let ds = schemafull_datastore().await;
+		let rows = *IMPORT_MAX_REJECTED + 5;
+		let file = "not json\n".repeat(rows);
+		let cfg = Config {
+			table: "person".to_owned(),
+			format: ImportFormat::Ndjson,
+		};
+		let stream = futures::stream::iter([Ok(Bytes::from(file))]);
+		let (snd, rcv) = async_channel::unbounded();
+		let report = ds.import_records(&session(), cfg, stream, Some(snd)).await.unwrap();
+		// The report is capped, but every rejected row is sent to the channel
+		assert_eq!(report.failed, rows as u64);
+		assert_eq!(report.rejected.len(), *IMPORT_MAX_REJECTED);
+		let mut rejected = Vec::new();
+		while let Ok(reject) = rcv.try_recv() {
+			rejected.push(reject.row);
+		}
+		assert_eq!(rejected, (1..=rows as u64).collect::<Vec<_>>());
+	}
+
+	#[cfg(feature = "parquet")]
+	#[tokio::test]
+	async fn import_parquet() {
+		use crate::kvs::export;
+
+		let ds = schemafull_datastore().await;
+		ds.test_query(
+			"
+			CREATE person:one SET name = 'Tobie', age = 30, born = d'2020-01-01T00:00:00Z', address = { city: 'London' };
+			CREATE person:two SET name = 'Jaime', age = 20, active = true;
+			",
+		)
+		.await;
+		// Export the table, and import it into a new table
+		let cfg = export::Config {
+			format: export::ExportFormat::Parquet,
+			flatten: export::FlattenPolicy::Columns,
+			tables: export::TableConfig::Some(vec!["person".to_owned()]),
+			..Default::default()
+		};
+		let (snd, rcv) = async_channel::unbounded();
+		ds.export_with_config(&session(), snd, cfg).await.unwrap().await.unwrap();
+		let mut file = Vec::new();
+		while let Ok(chunk) = rcv.try_recv() {
+			file.extend(chunk);
+		}
+		let (head, tail) = file.split_at(file.len() / 2);
+		let report = import(&ds, "people", ImportFormat::Parquet, &[head, tail]).await;
+		assert_eq!(report.imported, 2);
+		assert!(report.rejected.is_empty());
+		assert_eq!(
+			ds.test_query("SELECT * OMIT id FROM people ORDER BY name").await.into_json_value(),
+			serde_json::json!([
+				{ "name": "Jaime", "age": 20, "active": true },
+				{ "name": "Tobie", "age": 30, "active": false, "born": "2020-01-01T00:00:00Z", "address": { "city": "London" } },
+			])
+		);
+	}
+}
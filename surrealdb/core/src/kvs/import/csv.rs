@@ -0,0 +1,122 @@
+use anyhow::Result;
+use csv_core::{ReadRecordResult, Reader};
+
+use super::{Decoder, Row, record};
+use crate::val::Value;
+
+/// Decodes comma-separated values, where the first row holds the names of
+/// the columns. Empty cells are left out of the record, and the other cells
+/// are decoded as strings, to be coerced into the kinds of the fields.
+pub(super) struct CsvDecoder {
+	reader: Reader,
+	buffer: Vec<u8>,
+	/// The start of the input which has not been decoded
+	pos: usize,
+	finished: bool,
+	/// The names of the columns, once the header has been decoded
+	header: Option<Vec<String>>,
+	/// The cells of the current record
+	out: Vec<u8>,
+	outlen: usize,
+	/// The end of each cell of the current record
+	ends: Vec<usize>,
+	endlen: usize,
+}
+
+impl Default for CsvDecoder {
+	fn default() -> Self {
+		Self {
+			reader: Reader::new(),
+			buffer: Vec::new(),
+			pos: 0,
+			finished: false,
+			header: None,
+			out: vec![0; 1024],
+			outlen: 0,
+			ends: vec![0; 64],
+			endlen: 0,
+		}
+	}
+}
+
+impl CsvDecoder {
+	/// Returns the cells of the record which has just been decoded
+	fn cells(&mut self) -> std::result::Result<Vec<String>, String> {
+		let mut start = 0;
+		let mut cells = Vec::with_capacity(self.endlen);
+		for &end in &self.ends[..self.endlen] {
+			let cell = std::str::from_utf8(&self.out[start..end])
+				.map_err(|_| "The row is not valid UTF-8".to_owned())?;
+			cells.push(cell.to_owned());
+			start = end;
+		}
+		self.outlen = 0;
+		self.endlen = 0;
+		Ok(cells)
+	}
+}
+
+impl Decoder for CsvDecoder {
+	fn push(&mut self, chunk: &[u8]) {
+		self.buffer.drain(..self.pos);
+		self.pos = 0;
+		self.buffer.extend_from_slice(chunk);
+	}
+
+	fn finish(&mut self) -> Result<()> {
+		self.finished = true;
+		Ok(())
+	}
+
+	fn next(&mut self) -> Result<Option<Row>> {
+		loop {
+			// An empty input signals the end of the file
+			let input = &self.buffer[self.pos..];
+			if input.is_empty() && !self.finished {
+				return Ok(None);
+			}
+			let (res, nin, nout, nend) = self.reader.read_record(
+				input,
+				&mut self.out[self.outlen..],
+				&mut self.ends[self.endlen..],
+			);
+			self.pos += nin;
+			self.outlen += nout;
+			self.endlen += nend;
+			match res {
+				ReadRecordResult::InputEmpty => continue,
+				ReadRecordResult::OutputFull => self.out.resize(self.out.len() * 2, 0),
+				ReadRecordResult::OutputEndsFull => self.ends.resize(self.ends.len() * 2, 0),
+				ReadRecordResult::End => return Ok(None),
+				ReadRecordResult::Record => {
+					let cells = self.cells();
+					let Some(header) = &self.header else {
+						// The first record holds the names of the columns
+						let mut names = cells.map_err(|e| anyhow::anyhow!(e))?;
+						if let Some(first) = names.first_mut() {
+							*first = first.trim_start_matches('\u{feff}').to_owned();
+						}
+						self.header = Some(names);
+						continue;
+					};
+					return Ok(Some(cells.and_then(|cells| {
+						if cells.len() != header.len() {
+							return Err(format!(
+								"Expected {} columns, found {}",
+								header.len(),
+								cells.len()
+							));
+						}
+						Ok(record(
+							header
+								.iter()
+								.zip(cells)
+								.filter(|(_, cell)| !cell.is_empty())
+								.map(|(name, cell)| (name.clone(), Value::String(cell))),
+						))
+					})));
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+
+use super::{Decoder, Row};
+use crate::sql::expression::convert_public_value_to_internal;
+use crate::syn;
+
+/// Decodes one JSON object per line
+#[derive(Default)]
+pub(super) struct NdjsonDecoder {
+	buffer: Vec<u8>,
+	/// The start of the first line which has not been decoded
+	pos: usize,
+	finished: bool,
+}
+
+impl Decoder for NdjsonDecoder {
+	fn push(&mut self, chunk: &[u8]) {
+		self.buffer.drain(..self.pos);
+		self.pos = 0;
+		self.buffer.extend_from_slice(chunk);
+	}
+
+	fn finish(&mut self) -> Result<()> {
+		self.finished = true;
+		Ok(())
+	}
+
+	fn next(&mut self) -> Result<Option<Row>> {
+		loop {
+			// Take the next complete line
+			let rest = &self.buffer[self.pos..];
+			let line = match rest.iter().position(|&b| b == b'\n') {
+				Some(end) => &rest[..end],
+				None if self.finished && !rest.is_empty() => rest,
+				None => return Ok(None),
+			};
+			self.pos += (line.len() + 1).min(rest.len());
+			let Ok(line) = std::str::from_utf8(line) else {
+				return Ok(Some(Err("The line is not valid UTF-8".to_owned())));
+			};
+			// Skip blank lines
+			let line = line.trim();
+			if line.is_empty() {
+				continue;
+			}
+			return Ok(Some(match syn::json(line) {
+				Ok(v) => Ok(convert_public_value_to_internal(v)),
+				Err(e) => Err(format!("Invalid JSON: {e}")),
+			}));
+		}
+	}
+}
@@ -0,0 +1,209 @@
+use std::pin::pin;
+
+#[cfg(not(target_family = "wasm"))]
+use anyhow::Context;
+use anyhow::{Result, ensure};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+	ArrowTemporalType, Date32Type, Date64Type, Float32Type, Float64Type, Int8Type, Int16Type,
+	Int32Type, Int64Type, TimestampMicrosecondType, TimestampMillisecondType,
+	TimestampNanosecondType, TimestampSecondType, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow_array::{Array, RecordBatchReader};
+use arrow_schema::{DataType, TimeUnit};
+use async_channel::Sender;
+use futures::{Stream, StreamExt};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+#[cfg(not(target_family = "wasm"))]
+use tokio::task::spawn_blocking;
+
+use super::{Loader, Report, Row, record};
+#[cfg(not(target_family = "wasm"))]
+use crate::cnf::IMPORT_BATCH_SIZE;
+use crate::cnf::IMPORT_PARQUET_MAX_SIZE;
+use crate::err::Error;
+use crate::val::{Bytes, Datetime, Object, Value};
+
+/// Imports the rows of an Apache Parquet file.
+///
+/// The footer of a Parquet file holds its schema, and the imported file is
+/// only available as a stream, so the whole file is buffered before any row
+/// is decoded. Files larger than `SURREAL_IMPORT_PARQUET_MAX_SIZE` bytes are
+/// rejected while they are buffered. The rows are then decoded on the
+/// blocking thread pool, off the async runtime, and handed to the loader
+/// through a bounded channel. Columns of a type without a SurrealQL
+/// equivalent fail the import.
+pub(super) async fn import<S>(mut loader: Loader<'_>, stream: S) -> Result<Report>
+where
+	S: Stream<Item = Result<bytes::Bytes>>,
+{
+	let mut stream = pin!(stream);
+	let limit = *IMPORT_PARQUET_MAX_SIZE;
+	let mut file = Vec::new();
+	while let Some(chunk) = stream.next().await {
+		let chunk = chunk?;
+		ensure!((file.len() + chunk.len()) as u64 <= limit, Error::ImportSizeExceeded(limit));
+		file.extend_from_slice(&chunk);
+	}
+	let file = bytes::Bytes::from(file);
+	#[cfg(not(target_family = "wasm"))]
+	let (rows, decoding) = {
+		let (snd, rcv) = async_channel::bounded(*IMPORT_BATCH_SIZE);
+		(rcv, spawn_blocking(move || decode(file, &snd)))
+	};
+	#[cfg(target_family = "wasm")]
+	let (rows, decoding) = {
+		let (snd, rcv) = async_channel::unbounded();
+		(rcv, decode(file, &snd))
+	};
+	while let Ok(row) = rows.recv().await {
+		loader.push(row).await?;
+	}
+	// The channel is closed once every row was decoded, or decoding failed
+	#[cfg(not(target_family = "wasm"))]
+	decoding.await.context("Parquet decoding task join error")??;
+	#[cfg(target_family = "wasm")]
+	decoding?;
+	loader.finish().await
+}
+
+/// Decodes the rows of a file into the channel, until every row was decoded
+/// or the channel was closed.
+fn decode(file: bytes::Bytes, rows: &Sender<Row>) -> Result<()> {
+	let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+	// Check the types of the columns up front
+	for field in reader.schema().fields() {
+		check(field.name(), field.data_type())?;
+	}
+	for batch in reader {
+		let batch = batch?;
+		for row in 0..batch.num_rows() {
+			let mut cells = Vec::with_capacity(batch.num_columns());
+			for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+				let value = cell(field.name(), column.as_ref(), row)?;
+				if !value.is_none() {
+					cells.push((field.name().clone(), value));
+				}
+			}
+			// The loader stopped, so the rest of the file is not needed
+			if rows.send_blocking(Ok(record(cells))).is_err() {
+				return Ok(());
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Checks that the values of a column can be converted
+fn check(name: &str, data_type: &DataType) -> Result<()> {
+	match data_type {
+		DataType::Null
+		| DataType::Boolean
+		| DataType::Int8
+		| DataType::Int16
+		| DataType::Int32
+		| DataType::Int64
+		| DataType::UInt8
+		| DataType::UInt16
+		| DataType::UInt32
+		| DataType::UInt64
+		| DataType::Float32
+		| DataType::Float64
+		| DataType::Utf8
+		| DataType::LargeUtf8
+		| DataType::Binary
+		| DataType::LargeBinary
+		| DataType::Date32
+		| DataType::Date64
+		| DataType::Timestamp(_, _) => Ok(()),
+		DataType::List(field) | DataType::LargeList(field) => check(name, field.data_type()),
+		DataType::Struct(fields) => {
+			fields.iter().try_for_each(|f| check(&format!("{name}.{}", f.name()), f.data_type()))
+		}
+		kind => Err(Error::ImportUnsupportedType {
+			name: name.to_owned(),
+			kind: kind.to_string(),
+		}
+		.into()),
+	}
+}
+
+/// Converts the value at a position of an array
+fn cell(name: &str, array: &dyn Array, i: usize) -> Result<Value> {
+	if array.is_null(i) {
+		return Ok(Value::None);
+	}
+	Ok(match array.data_type() {
+		DataType::Null => Value::None,
+		DataType::Boolean => Value::from(array.as_boolean().value(i)),
+		DataType::Int8 => Value::from(array.as_primitive::<Int8Type>().value(i)),
+		DataType::Int16 => Value::from(array.as_primitive::<Int16Type>().value(i)),
+		DataType::Int32 => Value::from(array.as_primitive::<Int32Type>().value(i)),
+		DataType::Int64 => Value::from(array.as_primitive::<Int64Type>().value(i)),
+		DataType::UInt8 => Value::from(array.as_primitive::<UInt8Type>().value(i)),
+		DataType::UInt16 => Value::from(array.as_primitive::<UInt16Type>().value(i)),
+		DataType::UInt32 => Value::from(array.as_primitive::<UInt32Type>().value(i)),
+		DataType::UInt64 => Value::from(array.as_primitive::<UInt64Type>().value(i)),
+		DataType::Float32 => Value::from(array.as_primitive::<Float32Type>().value(i)),
+		DataType::Float64 => Value::from(array.as_primitive::<Float64Type>().value(i)),
+		DataType::Utf8 => Value::String(array.as_string::<i32>().value(i).to_owned()),
+		DataType::LargeUtf8 => Value::String(array.as_string::<i64>().value(i).to_owned()),
+		DataType::Binary => Value::from(Bytes::from(array.as_binary::<i32>().value(i).to_vec())),
+		DataType::LargeBinary => {
+			Value::from(Bytes::from(array.as_binary::<i64>().value(i).to_vec()))
+		}
+		DataType::Date32 => datetime::<Date32Type>(array, i),
+		DataType::Date64 => datetime::<Date64Type>(array, i),
+		DataType::Timestamp(TimeUnit::Second, _) => datetime::<TimestampSecondType>(array, i),
+		DataType::Timestamp(TimeUnit::Millisecond, _) => {
+			datetime::<TimestampMillisecondType>(array, i)
+		}
+		DataType::Timestamp(TimeUnit::Microsecond, _) => {
+			datetime::<TimestampMicrosecondType>(array, i)
+		}
+		DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+			datetime::<TimestampNanosecondType>(array, i)
+		}
+		DataType::List(_) => list(name, array.as_list::<i32>().value(i).as_ref())?,
+		DataType::LargeList(_) => list(name, array.as_list::<i64>().value(i).as_ref())?,
+		DataType::Struct(fields) => {
+			let array = array.as_struct();
+			let mut object = Object::default();
+			for (field, column) in fields.iter().zip(array.columns()) {
+				let value = cell(&format!("{name}.{}", field.name()), column.as_ref(), i)?;
+				if !value.is_none() {
+					object.insert(field.name().clone(), value);
+				}
+			}
+			Value::Object(object)
+		}
+		kind => {
+			return Err(Error::ImportUnsupportedType {
+				name: name.to_owned(),
+				kind: kind.to_string(),
+			}
+			.into());
+		}
+	})
+}
+
+fn list(name: &str, array: &dyn Array) -> Result<Value> {
+	let values = (0..array.len())
+		.map(|i| match cell(name, array, i)? {
+			Value::None => Ok(Value::Null),
+			v => Ok(v),
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Value::from(values))
+}
+
+/// Converts a date or timestamp, which is stored as UTC
+fn datetime<T: ArrowTemporalType>(array: &dyn Array, i: usize) -> Value
+where
+	i64: From<T::Native>,
+{
+	match array.as_primitive::<T>().value_as_datetime(i) {
+		Some(v) => Value::from(Datetime::from(v.and_utc())),
+		None => Value::Null,
+	}
+}
@@ -4,6 +4,7 @@ use std::fmt::Debug;
 
 use anyhow::{Context, Result};
 use roaring::{RoaringBitmap, RoaringTreemap};
+use uuid::Uuid;
 
 /// KVKey is a trait that defines a key for the key-value store.
 ///
@@ -127,6 +128,18 @@ impl KVValue for u64 {
 	}
 }
 
+impl KVValue for Uuid {
+	#[inline]
+	fn kv_encode_value(&self) -> Result<Vec<u8>> {
+		Ok(self.as_bytes().to_vec())
+	}
+
+	#[inline]
+	fn kv_decode_value(bytes: Vec<u8>) -> Result<Self> {
+		Uuid::from_slice(&bytes).context("Uuid bytes must be 16 bytes")
+	}
+}
+
 impl KVValue for () {
 	fn kv_encode_value(&self) -> Result<Vec<u8>> {
 		Ok(Vec::new())
@@ -117,6 +117,11 @@ impl Transactable for Transaction {
 		self.write
 	}
 
+	/// Every version of every key is kept, including index entries
+	fn versioned_indexes(&self) -> bool {
+		true
+	}
+
 	/// Cancels the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
 	async fn cancel(&self) -> Result<()> {
@@ -14,14 +14,19 @@
 //!   database
 //! - `mem`: in-memory database
 
+pub mod backup;
 pub mod config;
 pub mod export;
+pub mod import;
+pub mod recovery;
 
 mod api;
 mod batch;
 mod clock;
 mod ds;
+mod encryption;
 mod err;
+mod history;
 mod into;
 mod key;
 mod scanner;
@@ -37,9 +42,12 @@ mod rocksdb;
 mod surrealkv;
 mod tikv;
 
+#[cfg(test)]
+mod test_support;
 #[cfg(test)]
 mod tests;
 
+pub(crate) mod audit;
 pub(crate) mod cache;
 pub(crate) mod index;
 pub(crate) mod sequences;
@@ -48,11 +56,13 @@ pub(crate) mod tasklease;
 pub(crate) mod version;
 
 pub use api::{ScanLimit, Transactable};
+pub use audit::AuditFile;
 pub(crate) use ds::TransactionFactory;
 pub use ds::requirements::{TransactionBuilderFactoryRequirements, TransactionBuilderRequirements};
 pub use ds::{
 	Datastore, DatastoreFlavor, Metric, Metrics, TransactionBuilder, TransactionBuilderFactory,
 };
+pub use encryption::EncryptionKey;
 pub use err::{Error, Result};
 pub use into::IntoBytes;
 pub(crate) use key::{KVKey, KVValue, impl_kv_key_storekey, impl_kv_value_revisioned};
@@ -65,6 +75,8 @@ pub use tr::{LockType, TransactionType, Transactor};
 pub(crate) use tx::CachePolicy;
 pub use tx::Transaction;
 
+pub use crate::cf::{Sink, SinkBatch};
+
 /// The key part of a key-value pair. An alias for [`Vec<u8>`].
 pub type Key = Vec<u8>;
 
@@ -0,0 +1,411 @@
+//! Point-in-time recovery from the changefeeds of a datastore.
+//!
+//! A recovery starts from a datastore into which a backup has been restored.
+//! The changefeed entries which were recorded after that backup are read from
+//! a more recent backup archive of the same datastore, and the record changes
+//! they contain are replayed up to a chosen point in time. This allows to
+//! recover a datastore as it was just before an unwanted change, such as a
+//! `DELETE`, without losing the changes made since the base backup.
+//!
+//! Only the tables with a changefeed can be recovered past the base backup.
+//! The namespaces and databases defined after the base backup are skipped.
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use super::LockType::Optimistic;
+use super::TransactionType::{Read, Write};
+use super::backup::Entry;
+use super::{BoxTimeStamp, BoxTimeStampImpl, Datastore, KVKey, KVValue, Key, Transaction, Val};
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseId, NamespaceId, TableDefinition};
+use crate::cf::{TableMutation, TableMutations};
+use crate::cnf::RESTORE_BATCH_SIZE;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::expr::paths::{IN, OUT};
+use crate::expr::statements::{
+	DefineStatement, DeleteStatement, InsertStatement, OptionStatement, UpsertStatement,
+};
+use crate::expr::{Data, Expr, Literal, LogicalPlan, TopLevelExpr};
+use crate::key::change;
+use crate::sql::statements::define::DefineKind;
+use crate::val::{Datetime, RecordId, TableName, Value};
+
+/// The point in time up to which a datastore is recovered.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecoveryPoint {
+	/// Recover the changes made up to and including this datetime
+	Timestamp(Datetime),
+	/// Recover the changes made up to and including this versionstamp
+	Versionstamp(u128),
+}
+
+impl RecoveryPoint {
+	/// Whether the changes made at this timestamp are recovered
+	fn includes(&self, ts: &BoxTimeStamp) -> bool {
+		match self {
+			Self::Timestamp(dt) => ts.as_datetime().is_some_and(|v| v <= dt.0),
+			Self::Versionstamp(vs) => ts.as_versionstamp() <= *vs,
+		}
+	}
+}
+
+impl FromStr for RecoveryPoint {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		if let Ok(vs) = s.parse() {
+			return Ok(Self::Versionstamp(vs));
+		}
+		match Datetime::from_str(s) {
+			Ok(dt) => Ok(Self::Timestamp(dt)),
+			Err(()) => Err(Error::InvalidRecoveryPoint(s.to_owned()).into()),
+		}
+	}
+}
+
+impl fmt::Display for RecoveryPoint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Timestamp(dt) => write!(f, "{dt}"),
+			Self::Versionstamp(vs) => write!(f, "versionstamp {vs}"),
+		}
+	}
+}
+
+/// The changesets of a database which are waiting to be replayed.
+struct Batch {
+	ns: NamespaceId,
+	db: DatabaseId,
+	/// The timestamp of the last changeset
+	ts: Vec<u8>,
+	/// The changefeed entries of the changesets
+	entries: Vec<(Key, Val)>,
+	/// The statements replaying the changesets
+	plan: Vec<TopLevelExpr>,
+	/// The number of changesets
+	count: u64,
+	/// The transaction reading the table definitions of the changesets
+	txn: Transaction,
+	/// The table definitions read by the transaction
+	tables: HashMap<TableName, Option<Arc<TableDefinition>>>,
+}
+
+/// Replays the changefeed entries of a backup archive onto a datastore.
+pub(crate) struct Recovery<'a> {
+	ds: &'a Datastore,
+	/// The changefeed versionstamp of the base backup
+	since: u128,
+	until: RecoveryPoint,
+	ts_impl: BoxTimeStampImpl,
+	/// The names of the namespaces and databases of the datastore
+	names: HashMap<(NamespaceId, DatabaseId), (String, String)>,
+	/// The databases whose changes are skipped
+	skipped: HashSet<(NamespaceId, DatabaseId)>,
+	batch: Option<Batch>,
+	count: u64,
+}
+
+impl<'a> Recovery<'a> {
+	pub(crate) async fn new(ds: &'a Datastore, since: u128, until: RecoveryPoint) -> Result<Self> {
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let names = catch!(txn, Self::names(&txn).await);
+		let ts_impl = txn.timestamp_impl();
+		txn.cancel().await?;
+		Ok(Self {
+			ds,
+			since,
+			until,
+			ts_impl,
+			names,
+			skipped: HashSet::new(),
+			batch: None,
+			count: 0,
+		})
+	}
+
+	async fn names(
+		txn: &Transaction,
+	) -> Result<HashMap<(NamespaceId, DatabaseId), (String, String)>> {
+		let mut names = HashMap::new();
+		for ns in txn.all_ns().await?.iter() {
+			for db in txn.all_db(ns.namespace_id).await?.iter() {
+				names.insert((ns.namespace_id, db.database_id), (ns.name.clone(), db.name.clone()));
+			}
+		}
+		Ok(names)
+	}
+
+	/// Replays an entry of the archive, if it is a changefeed entry
+	/// recorded between the base backup and the recovery point.
+	pub(crate) async fn push(&mut self, entry: Entry) -> Result<()> {
+		// Changefeed entries are only ever set
+		let Entry::Set(key, val) = entry else {
+			return Ok(());
+		};
+		let Ok(cf) = change::Cf::decode_key(&key) else {
+			return Ok(());
+		};
+		if change::new(cf.ns, cf.db, &cf.ts, &cf.tb).encode_key()? != key {
+			return Ok(());
+		}
+		// Check the changes were made in the recovered period
+		let ts = self.ts_impl.decode(&cf.ts)?;
+		if ts.as_versionstamp() <= self.since || !self.until.includes(&ts) {
+			return Ok(());
+		}
+		// Check the database exists in the base backup
+		if !self.names.contains_key(&(cf.ns, cf.db)) {
+			if self.skipped.insert((cf.ns, cf.db)) {
+				warn!(
+					"Skipping the changes of database {} in namespace {}, which was defined after the base backup",
+					cf.db, cf.ns
+				);
+			}
+			return Ok(());
+		}
+		// Replay the changes of each database separately
+		if self.batch.as_ref().is_some_and(|b| (b.ns, b.db) != (cf.ns, cf.db)) {
+			self.flush().await?;
+		}
+		// Each changeset is replayed in its own transaction
+		let starts = self.batch.as_ref().is_none_or(|b| b.ts != *cf.ts);
+		if starts && self.batch.as_ref().is_some_and(|b| b.count >= *RESTORE_BATCH_SIZE as u64) {
+			self.flush().await?;
+		}
+		let batch = match self.batch.take() {
+			Some(batch) => batch,
+			None => Batch {
+				ns: cf.ns,
+				db: cf.db,
+				ts: Vec::new(),
+				entries: Vec::new(),
+				plan: vec![TopLevelExpr::Option(OptionStatement {
+					name: "IMPORT".to_owned(),
+					what: true,
+				})],
+				count: 0,
+				txn: self.ds.transaction(Read, Optimistic).await?,
+				tables: HashMap::new(),
+			},
+		};
+		let batch = self.batch.insert(batch);
+		if starts {
+			if batch.count > 0 {
+				batch.plan.push(TopLevelExpr::Commit);
+			}
+			batch.plan.push(TopLevelExpr::Begin);
+			batch.ts = cf.ts.to_vec();
+			batch.count += 1;
+		}
+		// Fetch the current definition of the table
+		let tb = match batch.tables.get(cf.tb.as_ref()) {
+			Some(tb) => tb.clone(),
+			None => {
+				let txn = &batch.txn;
+				let tb = catch!(txn, txn.get_tb(cf.ns, cf.db, &cf.tb).await);
+				batch.tables.insert(cf.tb.clone().into_owned(), tb.clone());
+				tb
+			}
+		};
+		for mutation in TableMutations::kv_decode_value(val.clone())?.1 {
+			replay(&mut batch.plan, tb.as_deref(), mutation);
+		}
+		batch.entries.push((key, val));
+		Ok(())
+	}
+
+	/// Replays the pending changesets, returning the number of replayed changesets.
+	pub(crate) async fn finish(mut self) -> Result<u64> {
+		self.flush().await?;
+		Ok(self.count)
+	}
+
+	async fn flush(&mut self) -> Result<()> {
+		let Some(mut batch) = self.batch.take() else {
+			return Ok(());
+		};
+		batch.txn.cancel().await?;
+		batch.plan.push(TopLevelExpr::Commit);
+		// Replay the changesets
+		let (ns, db) = &self.names[&(batch.ns, batch.db)];
+		let ses = Session::owner().with_ns(ns).with_db(db);
+		let plan = LogicalPlan {
+			expressions: batch.plan,
+		};
+		for res in self.ds.execute_import_plan(&ses, plan).await? {
+			res.result.map_err(|e| anyhow::anyhow!(e))?;
+		}
+		// Keep the changefeed history of the replayed changesets
+		let txn = self.ds.transaction(Write, Optimistic).await?;
+		run!(
+			txn,
+			async {
+				for (key, val) in batch.entries {
+					txn.set(&key, &val, None).await?;
+				}
+				Ok(())
+			}
+			.await
+		)?;
+		self.count += batch.count;
+		Ok(())
+	}
+}
+
+/// Whether the value is a graph edge record
+fn is_edge(v: &Value) -> bool {
+	matches!(v.pick(&*IN), Value::RecordId(_)) && matches!(v.pick(&*OUT), Value::RecordId(_))
+}
+
+/// A literal of a record id
+fn record(id: RecordId) -> Expr {
+	Expr::Literal(Literal::RecordId(id.into_literal()))
+}
+
+/// Adds the statements replaying a table mutation
+fn replay(plan: &mut Vec<TopLevelExpr>, tb: Option<&TableDefinition>, mutation: TableMutation) {
+	let delete = |id: RecordId| {
+		Expr::Delete(Box::new(DeleteStatement {
+			what: vec![record(id)],
+			..Default::default()
+		}))
+	};
+	match mutation {
+		TableMutation::Set(id, v) | TableMutation::SetWithDiff(id, v, _) => {
+			// Graph edges are recreated, so that the graph keys are written
+			if is_edge(&v) && tb.is_none_or(|tb| tb.allows_relation()) {
+				plan.push(TopLevelExpr::Expr(delete(id)));
+				plan.push(TopLevelExpr::Expr(Expr::Insert(Box::new(InsertStatement {
+					into: None,
+					data: Data::SingleExpression(v.into_literal()),
+					ignore: false,
+					update: None,
+					output: None,
+					timeout: Expr::Literal(Literal::None),
+					relation: true,
+				}))));
+			} else {
+				plan.push(TopLevelExpr::Expr(Expr::Upsert(Box::new(UpsertStatement {
+					what: vec![record(id)],
+					data: Some(Data::ContentExpression(v.into_literal())),
+					..Default::default()
+				}))));
+			}
+		}
+		TableMutation::Del(id) | TableMutation::DelWithOriginal(id, _) => {
+			plan.push(TopLevelExpr::Expr(delete(id)));
+		}
+		TableMutation::Def(tb) => {
+			let mut def = tb.to_sql_definition();
+			def.kind = DefineKind::Overwrite;
+			plan.push(TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Table(
+				def.into(),
+			)))));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bytes::Bytes;
+	use futures::stream;
+	use surrealdb_types::ToSql;
+
+	use super::*;
+	use crate::kvs::backup::Header;
+
+	async fn backup(ds: &Datastore) -> (Header, Vec<u8>) {
+		let (snd, rcv) = async_channel::unbounded();
+		let (header, job) = ds.backup(None, snd).await.unwrap();
+		job.await.unwrap();
+		let mut archive = Vec::new();
+		while let Ok(chunk) = rcv.try_recv() {
+			archive.extend(chunk);
+		}
+		(header, archive)
+	}
+
+	async fn versionstamp(ds: &Datastore) -> u128 {
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let ts = txn.timestamp().await.unwrap();
+		txn.cancel().await.unwrap();
+		ts.as_versionstamp()
+	}
+
+	#[test]
+	fn parse_recovery_point() {
+		assert_eq!("42".parse::<RecoveryPoint>().unwrap(), RecoveryPoint::Versionstamp(42));
+		assert_eq!(
+			"2024-01-01T00:00:00Z".parse::<RecoveryPoint>().unwrap(),
+			RecoveryPoint::Timestamp(Datetime::from_str("2024-01-01T00:00:00Z").unwrap())
+		);
+		assert!("yesterday".parse::<RecoveryPoint>().is_err());
+	}
+
+	#[tokio::test]
+	async fn recover_before_delete() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE TABLE person CHANGEFEED 1h; DEFINE TABLE knows CHANGEFEED 1h;").await;
+		ds.test_query("CREATE person:tobie SET age = 33; CREATE person:jaime SET age = 28;").await;
+		let (base, archive) = backup(&ds).await;
+		// Changes made after the base backup
+		ds.test_query("UPDATE person:tobie SET age = 34; CREATE person:john SET age = 41;").await;
+		ds.test_query("RELATE person:tobie->knows->person:john;").await;
+		let expected = ds.test_query("SELECT *, ->knows->person AS knows FROM person").await;
+		let history = ds.test_query("SHOW CHANGES FOR TABLE person SINCE 0").await;
+		let until = versionstamp(&ds).await;
+		// The unwanted changes
+		ds.test_query("DELETE person; DELETE knows;").await;
+		let (_, changes) = backup(&ds).await;
+		// Recover the datastore just before the unwanted changes
+		let target = Datastore::new("memory").await.unwrap();
+		target.restore(stream::iter([Ok(Bytes::from(archive))])).await.unwrap();
+		let count = target
+			.recover(
+				&base,
+				stream::iter([Ok(Bytes::from(changes))]),
+				RecoveryPoint::Versionstamp(until),
+			)
+			.await
+			.unwrap();
+		assert_eq!(count, 3);
+		assert_eq!(
+			target.test_query("SELECT *, ->knows->person AS knows FROM person").await,
+			expected
+		);
+		// The replayed changes are kept in the changefeed
+		assert_eq!(target.test_query("SHOW CHANGES FOR TABLE person SINCE 0").await, history);
+	}
+
+	#[tokio::test]
+	async fn recover_requires_the_same_datastore() {
+		let ds = Datastore::new_test("memory").await;
+		ds.test_query("DEFINE TABLE person CHANGEFEED 1h; CREATE person:tobie;").await;
+		let (base, _) = backup(&ds).await;
+		ds.test_query("CREATE person:jaime").await;
+		let (_, changes) = backup(&ds).await;
+		// A datastore restored from a backup of another datastore
+		let other = Datastore::new_test("memory").await;
+		other.test_query("DEFINE TABLE person CHANGEFEED 1h; CREATE person:tobie;").await;
+		let (other_base, archive) = backup(&other).await;
+		let target = Datastore::new("memory").await.unwrap();
+		target.restore(stream::iter([Ok(Bytes::from(archive))])).await.unwrap();
+		for (base, expected) in [
+			(other_base, "the base backup from datastore"),
+			(base, "the datastore was restored from datastore"),
+		] {
+			let changes = stream::iter([Ok(Bytes::from(changes.clone()))]);
+			let until = RecoveryPoint::Versionstamp(u128::MAX);
+			let err = target.recover(&base, changes, until).await.unwrap_err();
+			assert!(err.to_string().contains(expected), "{err}");
+		}
+		// Nothing was replayed onto the target
+		let ids = target.test_query("SELECT VALUE id FROM person").await.to_sql();
+		assert!(!ids.contains("jaime"), "{ids}");
+	}
+}
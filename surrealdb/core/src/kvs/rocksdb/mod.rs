@@ -8,8 +8,8 @@ mod memory_manager;
 
 use std::ops::Range;
 use std::pin::Pin;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
 
 use background_flusher::BackgroundFlusher;
 use commit_coordinator::CommitCoordinator;
@@ -24,7 +24,9 @@ use tokio::sync::Mutex;
 use super::Direction;
 use super::api::ScanLimit;
 use super::config::{RocksDbConfig, SyncMode};
+use super::encryption::{self, EncryptedTransaction, EncryptionKey};
 use super::err::{Error, Result};
+use super::history::HistoryTransaction;
 use crate::key::debug::Sprintable;
 use crate::kvs::api::Transactable;
 use crate::kvs::ds::{Metric, Metrics};
@@ -43,6 +45,10 @@ pub struct Datastore {
 	commit_coordinator: Option<Arc<CommitCoordinator>>,
 	/// Background flusher for periodically flushing WAL to disk
 	background_flusher: Option<Arc<BackgroundFlusher>>,
+	/// The version retention period in nanoseconds, if versioning is enabled
+	history: Option<u64>,
+	/// The key used for encrypting the data at rest, if encryption is enabled
+	encryption: RwLock<Option<Arc<EncryptionKey>>>,
 }
 
 pub struct Transaction {
@@ -250,14 +256,32 @@ impl Datastore {
 		}
 		// Register the memory manager with the global allocator tracker
 		memory_manager.register_with_allocator_tracker();
-		// Return the datastore
-		Ok(Datastore {
+		// Keep a history of the changes for versioned queries
+		info!(target: TARGET, "Versioning enabled: {} with retention period: {}ns", config.versioned, config.retention_ns);
+		let history = config.versioned.then_some(config.retention_ns);
+		// Create the datastore
+		let ds = Datastore {
 			db,
 			memory_manager,
 			disk_space_manager,
 			background_flusher,
 			commit_coordinator,
-		})
+			history,
+			encryption: RwLock::new(None),
+		};
+		// Check the encryption key against the datastore
+		info!(target: TARGET, "Encryption at rest enabled: {}", config.encryption.is_some());
+		let tx = ds.raw_transaction(true).await?;
+		if let Err(e) = encryption::verify(tx, config.encryption.as_deref()).await {
+			// Stop the background tasks before returning the error
+			if let Err(e) = ds.shutdown().await {
+				warn!(target: TARGET, "Failed to shut down the datastore: {e}");
+			}
+			return Err(e);
+		}
+		*ds.encryption.write().unwrap_or_else(|e| e.into_inner()) = config.encryption;
+		// Return the datastore
+		Ok(ds)
 	}
 
 	const BLOCK_CACHE_USAGE: &str = "rocksdb.block_cache_usage";
@@ -334,6 +358,60 @@ impl Datastore {
 
 	/// Start a new transaction
 	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Box<dyn Transactable>> {
+		// Create a new transaction
+		let tx = self.raw_transaction(write).await?;
+		// Encrypt the values if encryption is enabled
+		let encryption = self.encryption.read().unwrap_or_else(|e| e.into_inner()).clone();
+		let tx = match encryption {
+			Some(key) => EncryptedTransaction::new(tx, key),
+			None => tx,
+		};
+		// Record the history of the transaction if versioning is enabled
+		Ok(match self.history {
+			Some(retention_ns) => HistoryTransaction::new(tx, retention_ns),
+			None => tx,
+		})
+	}
+
+	/// Re-encrypt the datastore with a new encryption key
+	pub(crate) async fn rotate_encryption_key(&self, key: Arc<EncryptionKey>) -> Result<()> {
+		// Only an encrypted datastore can be rotated
+		let Some(old) = self.encryption.read().unwrap_or_else(|e| e.into_inner()).clone() else {
+			return Err(Error::NotEncrypted);
+		};
+		// Re-encrypt the values in batches
+		let mut next = Some(Vec::new());
+		while let Some(beg) = next.take() {
+			let tx = self.raw_transaction(true).await?;
+			match encryption::rotate(&*tx, &old, &key, beg).await {
+				Ok(v) => next = v,
+				Err(e) => {
+					if let Err(e) = tx.cancel().await {
+						warn!(target: TARGET, "Failed to cancel the key rotation transaction: {e}");
+					}
+					return Err(e);
+				}
+			}
+			tx.commit().await?;
+		}
+		// Rotate the check value last
+		let tx = self.raw_transaction(true).await?;
+		if let Err(e) = encryption::rotate_check(&*tx, &key).await {
+			if let Err(e) = tx.cancel().await {
+				warn!(target: TARGET, "Failed to cancel the key rotation transaction: {e}");
+			}
+			return Err(e);
+		}
+		tx.commit().await?;
+		// Use the new key for any further transactions
+		info!(target: TARGET, "Rotated the datastore encryption key");
+		*self.encryption.write().unwrap_or_else(|e| e.into_inner()) = Some(key);
+		Ok(())
+	}
+
+	/// Start a new transaction on the underlying storage, which
+	/// neither encrypts the values nor records their history
+	async fn raw_transaction(&self, write: bool) -> Result<Box<dyn Transactable>> {
 		// Set the transaction options
 		let mut to = OptimisticTransactionOptions::default();
 		to.set_snapshot(true);
@@ -16,6 +16,7 @@ use tokio::sync::RwLock;
 use super::Direction;
 use super::api::ScanLimit;
 use super::config::{SurrealKvConfig, SyncMode};
+use super::encryption::{self, EncryptedTransaction, EncryptionKey};
 use super::err::{Error, Result};
 use crate::key::debug::Sprintable;
 use crate::kvs::api::Transactable;
@@ -30,6 +31,8 @@ pub struct Datastore {
 	commit_coordinator: Option<Arc<CommitCoordinator>>,
 	/// Background flusher for periodically flushing WAL when sync=<interval>
 	background_flusher: Option<Arc<BackgroundFlusher>>,
+	/// The key used for encrypting the data at rest, if encryption is enabled
+	encryption: std::sync::RwLock<Option<Arc<EncryptionKey>>>,
 }
 
 pub struct Transaction {
@@ -105,13 +108,27 @@ impl Datastore {
 			}
 		};
 
-		// Create and return the datastore
-		Ok(Datastore {
+		// Create the datastore
+		let ds = Datastore {
 			db,
 			enable_versions: config.versioned,
 			commit_coordinator,
 			background_flusher,
-		})
+			encryption: std::sync::RwLock::new(None),
+		};
+		// Check the encryption key against the datastore
+		info!(target: TARGET, "Encryption at rest enabled: {}", config.encryption.is_some());
+		let tx = ds.raw_transaction(true).await?;
+		if let Err(e) = encryption::verify(tx, config.encryption.as_deref()).await {
+			// Stop the background tasks before returning the error
+			if let Err(e) = ds.shutdown().await {
+				warn!(target: TARGET, "Failed to shut down the datastore: {e}");
+			}
+			return Err(e);
+		}
+		*ds.encryption.write().unwrap_or_else(|e| e.into_inner()) = config.encryption;
+		// Return the datastore
+		Ok(ds)
 	}
 
 	/// Shutdown the database
@@ -138,6 +155,57 @@ impl Datastore {
 
 	/// Start a new transaction
 	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Box<dyn Transactable>> {
+		// Create a new transaction
+		let tx = self.raw_transaction(write).await?;
+		// Encrypt the values if encryption is enabled
+		let encryption = self.encryption.read().unwrap_or_else(|e| e.into_inner()).clone();
+		Ok(match encryption {
+			Some(key) => EncryptedTransaction::new(tx, key),
+			None => tx,
+		})
+	}
+
+	/// Re-encrypt the datastore with a new encryption key. Only the latest
+	/// version of each key is re-encrypted, so earlier versions can no longer
+	/// be read once the key has been rotated.
+	pub(crate) async fn rotate_encryption_key(&self, key: Arc<EncryptionKey>) -> Result<()> {
+		// Only an encrypted datastore can be rotated
+		let Some(old) = self.encryption.read().unwrap_or_else(|e| e.into_inner()).clone() else {
+			return Err(Error::NotEncrypted);
+		};
+		// Re-encrypt the values in batches
+		let mut next = Some(Vec::new());
+		while let Some(beg) = next.take() {
+			let tx = self.raw_transaction(true).await?;
+			match encryption::rotate(&*tx, &old, &key, beg).await {
+				Ok(v) => next = v,
+				Err(e) => {
+					if let Err(e) = tx.cancel().await {
+						warn!(target: TARGET, "Failed to cancel the key rotation transaction: {e}");
+					}
+					return Err(e);
+				}
+			}
+			tx.commit().await?;
+		}
+		// Rotate the check value last
+		let tx = self.raw_transaction(true).await?;
+		if let Err(e) = encryption::rotate_check(&*tx, &key).await {
+			if let Err(e) = tx.cancel().await {
+				warn!(target: TARGET, "Failed to cancel the key rotation transaction: {e}");
+			}
+			return Err(e);
+		}
+		tx.commit().await?;
+		// Use the new key for any further transactions
+		info!(target: TARGET, "Rotated the datastore encryption key");
+		*self.encryption.write().unwrap_or_else(|e| e.into_inner()) = Some(key);
+		Ok(())
+	}
+
+	/// Start a new transaction on the underlying storage, which
+	/// does not encrypt the values
+	async fn raw_transaction(&self, write: bool) -> Result<Box<dyn Transactable>> {
 		// Create a new transaction
 		let mut txn = match write {
 			true => self.db.begin_with_mode(Mode::ReadWrite),
@@ -174,6 +242,11 @@ impl Transactable for Transaction {
 		self.write
 	}
 
+	/// Every version of every key is kept, including index entries
+	fn versioned_indexes(&self) -> bool {
+		true
+	}
+
 	/// Cancels the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
 	async fn cancel(&self) -> Result<()> {
@@ -708,6 +781,42 @@ impl Transactable for Transaction {
 		Ok(res)
 	}
 
+	/// Retrieve every stored version of a key.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn versions(&self, key: Key, beg: u64, end: u64) -> Result<Vec<(u64, Option<Val>)>> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Set the key range
+		let mut next = key.clone();
+		next.push(0x00);
+		// Load the inner transaction
+		let inner = self.inner.read().await;
+		// Include tombstones so we can detect deleted versions
+		let opts = HistoryOptions::new().with_tombstones(true);
+		// Create the iterator with tombstone visibility
+		let mut iter = inner.history_with_options(&key, &next, &opts)?;
+		// History entries are sorted (key ASC, timestamp DESC),
+		// so backward iteration yields the oldest versions first
+		iter.seek_last()?;
+		// Collect the versions within the timestamps
+		let mut res = Vec::new();
+		while iter.valid() {
+			let key_ref = iter.key();
+			let ts = key_ref.timestamp();
+			if ts >= beg && ts <= end {
+				match key_ref.is_tombstone() {
+					true => res.push((ts, None)),
+					false => res.push((ts, Some(iter.value()?))),
+				}
+			}
+			iter.prev()?;
+		}
+		// Return result
+		Ok(res)
+	}
+
 	/// Set a new save point on the transaction.
 	async fn new_save_point(&self) -> Result<()> {
 		self.inner.write().await.set_savepoint()?;
@@ -27,6 +27,20 @@ pub(crate) enum TaskLeaseType {
 	IndexCompaction,
 	/// Event processing
 	EventProcessing,
+	/// Deletion of expired records
+	RecordExpiry,
+	/// Scheduled jobs
+	JobScheduler,
+	/// Delivery of changefeeds to sinks
+	ChangefeedSinks,
+	/// Removal of the received result cache invalidations
+	ResultInvalidations,
+	/// Removal of abandoned multipart file uploads
+	FileUploadExpiry,
+	/// Removal of the versions which have left the retention window
+	HistoryRetention,
+	/// Removal of the audit log events which have left the retention period
+	AuditLogRetention,
 }
 
 /// Represents a distributed task lease stored in the datastore.
@@ -0,0 +1,37 @@
+//! Shared setup for the unit tests of the datastore and its users.
+use surrealdb_types::Error as TypesError;
+
+use super::Datastore;
+use crate::dbs::Session;
+use crate::types::PublicValue;
+
+impl Datastore {
+	/// Creates a datastore with a `test` namespace and database.
+	pub(crate) async fn new_test(path: &str) -> Self {
+		let ds = Self::new(path).await.unwrap();
+		ds.execute("DEFINE NAMESPACE test", &Session::owner(), None).await.unwrap();
+		ds.execute("DEFINE DATABASE test", &Session::owner().with_ns("test"), None).await.unwrap();
+		ds
+	}
+
+	/// Runs a query, and returns the result of its last statement.
+	pub(crate) async fn test_execute(
+		&self,
+		sql: &str,
+		sess: &Session,
+	) -> Result<PublicValue, TypesError> {
+		let mut res = self.execute(sql, sess, None).await.unwrap();
+		res.pop().expect("the query should contain a statement").result
+	}
+
+	/// Runs a query as the root owner within the `test` namespace and
+	/// database, failing if any of its statements fails.
+	pub(crate) async fn test_query(&self, sql: &str) -> PublicValue {
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let mut out = PublicValue::None;
+		for res in self.execute(sql, &sess, None).await.unwrap() {
+			out = res.result.unwrap();
+		}
+		out
+	}
+}
@@ -0,0 +1,103 @@
+//! Tests for encryption at rest
+//!
+//! These tests reopen the same datastore path with different keys, to check
+//! that the values can only be read with the key the datastore was encrypted
+//! with, and that a rotated key replaces the previous one.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use temp_dir::TempDir;
+use tokio_util::sync::CancellationToken;
+
+use crate::CommunityComposer;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, EncryptionKey, Error};
+
+async fn open(path: &str) -> anyhow::Result<Datastore> {
+	Datastore::new_with_factory(CommunityComposer(), path, CancellationToken::new()).await
+}
+
+async fn close(ds: Datastore) {
+	ds.shutdown().await.unwrap();
+	drop(ds);
+}
+
+fn assert_error(res: anyhow::Result<Datastore>, err: Error) {
+	match res {
+		Ok(_) => panic!("expected the datastore to fail to open with: {err}"),
+		Err(e) => assert!(format!("{e:?}").contains(&err.to_string()), "{e:?}"),
+	}
+}
+
+pub async fn encrypted(new_path: fn(&str) -> String) {
+	// Setup the key files and the datastore path
+	let dir = TempDir::new().unwrap();
+	let old = dir.path().join("old.key");
+	let new = dir.path().join("new.key");
+	std::fs::write(&old, [1u8; 32]).unwrap();
+	std::fs::write(&new, STANDARD.encode([2u8; 32])).unwrap();
+	let path = new_path(&dir.path().join("data").to_string_lossy());
+	let with_key = |key: &std::path::Path| format!("{path}?key_file={}", key.display());
+	// Create an encrypted datastore
+	let ds = open(&with_key(&old)).await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"test", &"ok".as_bytes().to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	close(ds).await;
+	// The datastore can not be opened without the key, or with another key
+	assert_error(open(&path).await, Error::EncryptionKeyMissing);
+	assert_error(open(&with_key(&new)).await, Error::EncryptionKeyMismatch);
+	// Rotate the key
+	let ds = open(&with_key(&old)).await.unwrap();
+	ds.rotate_encryption_key(EncryptionKey::from_file(&new).unwrap()).await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&"test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	tx.cancel().await.unwrap();
+	close(ds).await;
+	// Only the new key can now be used
+	assert_error(open(&with_key(&old)).await, Error::EncryptionKeyMismatch);
+	let ds = open(&with_key(&new)).await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&"test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	tx.cancel().await.unwrap();
+	close(ds).await;
+}
+
+pub async fn unencrypted(new_path: fn(&str) -> String) {
+	// Setup the key file and the datastore path
+	let dir = TempDir::new().unwrap();
+	let key = dir.path().join("data.key");
+	std::fs::write(&key, [1u8; 32]).unwrap();
+	let path = new_path(&dir.path().join("data").to_string_lossy());
+	// Create a datastore without encryption
+	let ds = open(&path).await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"test", &"ok".as_bytes().to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	// The key of an unencrypted datastore can not be rotated
+	let res = ds.rotate_encryption_key(EncryptionKey::from_file(&key).unwrap()).await;
+	assert!(format!("{:?}", res.unwrap_err()).contains(&Error::NotEncrypted.to_string()));
+	close(ds).await;
+	// An existing datastore can not be opened with a key
+	assert_error(open(&format!("{path}?key_file={}", key.display())).await, Error::NotEncrypted);
+}
+
+macro_rules! define_tests {
+	($new_path:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn encrypted() {
+			super::encryption::encrypted($new_path).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn unencrypted() {
+			super::encryption::unencrypted($new_path).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -20,6 +20,8 @@ macro_rules! include_tests {
 	};
 }
 
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod encryption;
 #[cfg(feature = "kv-rocksdb")]
 mod metrics;
 
@@ -30,9 +32,14 @@ mod multiwriter_same_keys_conflict;
 mod raw;
 #[cfg(feature = "kv-rocksdb")]
 mod read_and_deletion_only;
+#[cfg(feature = "kv-rocksdb")]
+mod retention;
 mod snapshot;
 #[cfg(feature = "kv-mem")]
 mod tx_cache_test;
+mod versioned;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-tikv", feature = "kv-surrealkv"))]
+mod versions;
 
 #[derive(Clone, Debug)]
 pub(crate) enum Kvs {
@@ -81,6 +88,18 @@ mod mem {
 		(ds, Kvs::Mem)
 	}
 
+	async fn new_versioned_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Use a versioned memory datastore instance
+		let path = "memory?versioned=true";
+		// Setup the in-memory datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id);
+		// Return the datastore
+		(ds, Kvs::Mem)
+	}
+
 	include_tests!(new_ds =>
 		raw,
 		snapshot,
@@ -88,6 +107,8 @@ mod mem {
 		multiwriter_different_keys,
 		multiwriter_same_keys_conflict,
 	);
+
+	include_tests!(new_versioned_ds => versioned);
 }
 
 #[cfg(feature = "kv-rocksdb")]
@@ -113,6 +134,36 @@ mod rocksdb {
 		(ds, Kvs::Rocksdb)
 	}
 
+	async fn new_versioned_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Setup the temporary data storage path
+		let path = TempDir::new().unwrap().path().to_string_lossy().to_string();
+		let path = format!("rocksdb:{path}?versioned=true");
+		// Setup the RocksDB datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), &path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id);
+		// Return the datastore
+		(ds, Kvs::Rocksdb)
+	}
+
+	async fn new_retention_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Setup the temporary data storage path
+		let path = TempDir::new().unwrap().path().to_string_lossy().to_string();
+		let path = format!("rocksdb:{path}?versioned=true&retention=1ms");
+		// Setup the RocksDB datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), &path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id);
+		// Return the datastore
+		(ds, Kvs::Rocksdb)
+	}
+
+	fn new_path(dir: &str) -> String {
+		format!("rocksdb:{dir}")
+	}
+
 	include_tests!(new_ds =>
 		raw,
 		snapshot,
@@ -122,6 +173,12 @@ mod rocksdb {
 		read_and_deletion_only,
 		metrics
 	);
+
+	include_tests!(new_versioned_ds => versioned, versions);
+
+	include_tests!(new_retention_ds => retention);
+
+	include_tests!(new_path => encryption);
 }
 
 #[cfg(feature = "kv-surrealkv")]
@@ -147,6 +204,23 @@ mod surrealkv {
 		(ds, Kvs::SurrealKV)
 	}
 
+	async fn new_versioned_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Setup the temporary data storage path
+		let path = TempDir::new().unwrap().path().to_string_lossy().to_string();
+		let path = format!("surrealkv:{path}?versioned=true");
+		// Setup the SurrealKV datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), &path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id);
+		// Return the datastore
+		(ds, Kvs::SurrealKV)
+	}
+
+	fn new_path(dir: &str) -> String {
+		format!("surrealkv:{dir}")
+	}
+
 	include_tests!(new_ds =>
 		raw,
 		snapshot,
@@ -154,6 +228,10 @@ mod surrealkv {
 		multiwriter_different_keys,
 		multiwriter_same_keys_conflict,
 	);
+
+	include_tests!(new_versioned_ds => versions);
+
+	include_tests!(new_path => encryption);
 }
 
 #[cfg(feature = "kv-tikv")]
@@ -181,6 +259,22 @@ mod tikv {
 		(ds, Kvs::Tikv)
 	}
 
+	async fn new_versioned_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Setup the cluster connection string
+		let path = "tikv:127.0.0.1:2379?versioned=true";
+		// Setup the TiKV datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id);
+		// Clear any previous test entries, along with their history
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.clrr(vec![0u8]..vec![0xffu8]).await.unwrap();
+		tx.commit().await.unwrap();
+		// Return the datastore
+		(ds, Kvs::Tikv)
+	}
+
 	include_tests!(new_ds =>
 		raw,
 		snapshot,
@@ -188,4 +282,6 @@ mod tikv {
 		multiwriter_different_keys,
 		multiwriter_same_keys_allow,
 	);
+
+	include_tests!(new_versioned_ds => versioned, versions);
 }
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+
+pub async fn retention(new_ds: impl CreateDs) {
+	// Create a new datastore, with a retention period of 1ms
+	let node_id = Uuid::parse_str("5d4a3cbe-8a02-4ff6-9c0d-26e1f4f6cfa1").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	// Write, overwrite and delete keys
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a1".to_vec(), None).await.unwrap();
+	tx.set(&"/test/b", &b"b1".to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a2".to_vec(), None).await.unwrap();
+	tx.del(&"/test/b").await.unwrap();
+	tx.commit().await.unwrap();
+	// Move every version out of the retention window. In tests, the
+	// timestamps move forward by a millisecond each time they are read.
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	for _ in 0..5 {
+		tx.timestamp().await.unwrap();
+	}
+	tx.cancel().await.unwrap();
+	// Nothing is removed once the sweep is cancelled
+	let canceller = CancellationToken::new();
+	canceller.cancel();
+	ds.history_retention(Duration::from_secs(1), &canceller).await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	assert_eq!(tx.versions(&"/test/a", 0, u64::MAX).await.unwrap().len(), 2);
+	tx.cancel().await.unwrap();
+	// Sweep the history
+	ds.history_retention(Duration::from_secs(1), &CancellationToken::new()).await.unwrap();
+	// The latest version of a key is kept, unless the key was deleted
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let versions = tx.versions(&"/test/a", 0, u64::MAX).await.unwrap();
+	assert_eq!(versions.into_iter().map(|(_, v)| v).collect::<Vec<_>>(), [Some(b"a2".to_vec())]);
+	assert!(tx.versions(&"/test/b", 0, u64::MAX).await.unwrap().is_empty());
+	tx.cancel().await.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn retention() {
+			super::retention::retention($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use super::{CreateDs, Kvs};
+use crate::kvs::Datastore;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::val::Datetime;
+
+/// Returns the current version, after any previous commit. The history kept
+/// for the storage engines without versioning follows their timestamps.
+async fn version(ds: &Datastore, kvs: &Kvs) -> u64 {
+	tokio::time::sleep(Duration::from_millis(5)).await;
+	let version = match kvs {
+		Kvs::Mem => Datetime::now().to_version_stamp().unwrap(),
+		_ => {
+			let tx = ds.transaction(Read, Optimistic).await.unwrap();
+			let ts = tx.timestamp().await.unwrap();
+			tx.cancel().await.unwrap();
+			Datetime::from(ts.as_datetime().unwrap()).to_version_stamp().unwrap()
+		}
+	};
+	tokio::time::sleep(Duration::from_millis(5)).await;
+	version
+}
+
+pub async fn versioned(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("a9d6bd3a-8d15-4bcc-a0c9-6c0d9e3e1c31").unwrap();
+	let (ds, kvs) = new_ds.create_ds(node_id).await;
+	// Write the first versions
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a1".to_vec(), None).await.unwrap();
+	tx.set(&"/test/b", &b"b1".to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	let v1 = version(&ds, &kvs).await;
+	// Update, delete and create keys
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a2".to_vec(), None).await.unwrap();
+	tx.del(&"/test/b").await.unwrap();
+	tx.set(&"/test/c", &b"c2".to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	let v2 = version(&ds, &kvs).await;
+	// Writes which are rolled back are not versioned
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a3".to_vec(), None).await.unwrap();
+	tx.new_save_point().await.unwrap();
+	tx.set(&"/test/d", &b"d3".to_vec(), None).await.unwrap();
+	tx.rollback_to_save_point().await.unwrap();
+	tx.commit().await.unwrap();
+	// Read the keys at each version
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	assert_eq!(tx.get(&"/test/a", None).await.unwrap(), Some(b"a3".to_vec()));
+	assert_eq!(tx.get(&"/test/a", Some(v1)).await.unwrap(), Some(b"a1".to_vec()));
+	assert_eq!(tx.get(&"/test/b", Some(v1)).await.unwrap(), Some(b"b1".to_vec()));
+	assert_eq!(tx.get(&"/test/c", Some(v1)).await.unwrap(), None);
+	assert_eq!(tx.get(&"/test/a", Some(v2)).await.unwrap(), Some(b"a2".to_vec()));
+	assert_eq!(tx.get(&"/test/b", Some(v2)).await.unwrap(), None);
+	assert_eq!(tx.get(&"/test/c", Some(v2)).await.unwrap(), Some(b"c2".to_vec()));
+	assert_eq!(tx.get(&"/test/d", None).await.unwrap(), None);
+	// Scan the keys at each version
+	let res = tx.scan("/test/".."/test0", 100, 0, Some(v1)).await.unwrap();
+	assert_eq!(
+		res,
+		vec![(b"/test/a".to_vec(), b"a1".to_vec()), (b"/test/b".to_vec(), b"b1".to_vec())]
+	);
+	let res = tx.scan("/test/".."/test0", 100, 0, Some(v2)).await.unwrap();
+	assert_eq!(
+		res,
+		vec![(b"/test/a".to_vec(), b"a2".to_vec()), (b"/test/c".to_vec(), b"c2".to_vec())]
+	);
+	let res = tx.scanr("/test/".."/test0", 100, 0, Some(v2)).await.unwrap();
+	assert_eq!(
+		res,
+		vec![(b"/test/c".to_vec(), b"c2".to_vec()), (b"/test/a".to_vec(), b"a2".to_vec())]
+	);
+	let res = tx.keys("/test/".."/test0", 1, 1, Some(v2)).await.unwrap();
+	assert_eq!(res, vec![b"/test/c".to_vec()]);
+	assert_eq!(tx.count("/test/".."/test0", Some(v1)).await.unwrap(), 2);
+	tx.cancel().await.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn versioned() {
+			super::versioned::versioned($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
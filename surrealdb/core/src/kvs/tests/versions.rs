@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+use super::CreateDs;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+
+pub async fn versions(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("4f3c8a0e-4b39-4f0b-9d41-2d6a2c1c5e7b").unwrap();
+	let (ds, _) = new_ds.create_ds(node_id).await;
+	// Set, update and delete a key in separate transactions
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a1".to_vec(), None).await.unwrap();
+	tx.set(&"/test/b", &b"b1".to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"/test/a", &b"a2".to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.del(&"/test/a").await.unwrap();
+	tx.commit().await.unwrap();
+	// List every version of the key
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let res = tx.versions(&"/test/a", 0, u64::MAX).await.unwrap();
+	let vals: Vec<_> = res.iter().map(|(_, v)| v.clone()).collect();
+	assert_eq!(vals, vec![Some(b"a1".to_vec()), Some(b"a2".to_vec()), None]);
+	assert!(res.windows(2).all(|w| w[0].0 < w[1].0));
+	// List the versions between two timestamps
+	let res = tx.versions(&"/test/a", res[1].0, res[1].0).await.unwrap();
+	assert_eq!(res.len(), 1);
+	assert_eq!(res[0].1, Some(b"a2".to_vec()));
+	// Other keys are not included
+	let res = tx.versions(&"/test/b", 0, u64::MAX).await.unwrap();
+	assert_eq!(res.len(), 1);
+	tx.cancel().await.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn versions() {
+			super::versions::versions($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -16,7 +16,9 @@ use tikv::{CheckLevel, Config, TimestampExt, TransactionClient, TransactionOptio
 use tokio::sync::RwLock;
 
 use super::api::ScanLimit;
+use super::config::TikvConfig;
 use super::err::{Error, Result};
+use super::history::HistoryTransaction;
 use super::timestamp::MAX_TIMESTAMP_BYTES;
 use super::util;
 use crate::cnf::COUNT_BATCH_SIZE;
@@ -31,6 +33,8 @@ const ESTIMATED_BYTES_PER_KEY: u32 = 128;
 const ESTIMATED_BYTES_PER_VAL: u32 = 512;
 pub struct Datastore {
 	db: Pin<Arc<TransactionClient>>,
+	/// The version retention period in nanoseconds, if versioning is enabled
+	history: Option<u64>,
 }
 
 pub struct Transaction {
@@ -58,9 +62,9 @@ struct TransactionInner {
 
 impl Datastore {
 	/// Open a new database
-	pub(crate) async fn new(path: &str) -> Result<Datastore> {
+	pub(crate) async fn new(path: &str, config: TikvConfig) -> Result<Datastore> {
 		// Configure the client and keyspace
-		let config = match *cnf::TIKV_API_VERSION {
+		let tikv_config = match *cnf::TIKV_API_VERSION {
 			2 => match *cnf::TIKV_KEYSPACE {
 				Some(ref keyspace) => {
 					info!(target: TARGET, "Connecting to keyspace with cluster API V2: {keyspace}");
@@ -78,16 +82,20 @@ impl Datastore {
 			_ => return Err(Error::Datastore("Invalid TiKV API version".into())),
 		};
 		// Set the default request timeout
-		let config = config.with_timeout(Duration::from_secs(*cnf::TIKV_REQUEST_TIMEOUT));
+		let tikv_config = tikv_config.with_timeout(Duration::from_secs(*cnf::TIKV_REQUEST_TIMEOUT));
 		// Set the max decoding message size
-		let config =
-			config.with_grpc_max_decoding_message_size(*cnf::TIKV_GRPC_MAX_DECODING_MESSAGE_SIZE);
+		let tikv_config = tikv_config
+			.with_grpc_max_decoding_message_size(*cnf::TIKV_GRPC_MAX_DECODING_MESSAGE_SIZE);
 		// Create the client with the config
-		let client = TransactionClient::new_with_config(vec![path], config);
+		let client = TransactionClient::new_with_config(vec![path], tikv_config);
+		// Keep a history of the changes for versioned queries
+		info!(target: TARGET, "Versioning enabled: {} with retention period: {}ns", config.versioned, config.retention_ns);
+		let history = config.versioned.then_some(config.retention_ns);
 		// Check for errors with the client
 		match client.await {
 			Ok(db) => Ok(Datastore {
 				db: Arc::pin(db),
+				history,
 			}),
 			Err(e) => Err(Error::Datastore(e.to_string())),
 		}
@@ -128,8 +136,8 @@ impl Datastore {
 			opt = opt.read_only();
 		}
 		// Create a new transaction
-		match self.db.begin_with_options(opt).await {
-			Ok(txn) => Ok(Box::new(Transaction {
+		let tx: Box<dyn Transactable> = match self.db.begin_with_options(opt).await {
+			Ok(txn) => Box::new(Transaction {
 				done: AtomicBool::new(false),
 				write,
 				inner: RwLock::new(TransactionInner {
@@ -138,9 +146,14 @@ impl Datastore {
 					operations: Vec::new(),
 				}),
 				db: self.db.clone(),
-			})),
-			Err(e) => Err(Error::from(e)),
-		}
+			}),
+			Err(e) => return Err(Error::from(e)),
+		};
+		// Record the history of the transaction if versioning is enabled
+		Ok(match self.history {
+			Some(retention_ns) => HistoryTransaction::new(tx, retention_ns),
+			None => tx,
+		})
 	}
 }
 
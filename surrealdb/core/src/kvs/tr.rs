@@ -88,6 +88,15 @@ impl Transactor {
 		self.inner.writeable()
 	}
 
+	/// Check if indexes can be read at a version.
+	///
+	/// If the storage engine keeps every version of every key, then
+	/// versioned queries can use indexes, and this function will return
+	/// [`true`].
+	pub fn versioned_indexes(&self) -> bool {
+		self.inner.versioned_indexes()
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -406,6 +415,27 @@ impl Transactor {
 		self.inner.scanr(beg..end, limit, skip, version).await
 	}
 
+	/// Retrieve every stored version of a key from the datastore.
+	///
+	/// This function returns the versions of the key between the two
+	/// timestamps (inclusive) in ascending order.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
+	pub async fn versions<K>(
+		&self,
+		key: K,
+		since: u64,
+		until: u64,
+	) -> Result<Vec<(u64, Option<Val>)>>
+	where
+		K: IntoBytes + Debug,
+	{
+		let key = key.into_vec();
+		if since > until {
+			return Ok(vec![]);
+		}
+		self.inner.versions(key, since, until).await
+	}
+
 	/// Count the total number of keys within a range in the datastore.
 	///
 	/// This function fetches the total count, in batches, with multiple
@@ -15,8 +15,9 @@ use uuid::Uuid;
 use super::batch::Batch;
 use super::{Key, Val, util};
 use crate::catalog::providers::{
-	ApiProvider, AuthorisationProvider, BucketProvider, CatalogProvider, DatabaseProvider,
-	NamespaceProvider, NodeProvider, RootProvider, TableProvider, UserProvider,
+	ApiProvider, AuthorisationProvider, BucketProvider, CatalogProvider, ConsumerProvider,
+	DatabaseProvider, JobProvider, KeyProvider, NamespaceProvider, NodeProvider, RootProvider,
+	SinkProvider, TableProvider, UserProvider,
 };
 use crate::catalog::{
 	self, ApiDefinition, ConfigDefinition, DatabaseDefinition, DatabaseId, DefaultConfig, IndexId,
@@ -28,6 +29,8 @@ use crate::doc::CursorRecord;
 use crate::err::Error;
 use crate::idx::planner::ScanDirection;
 use crate::key::database::sq::Sq;
+use crate::kvs::audit::{AuditEvent, AuditLog};
+use crate::kvs::cache::result::{Invalidation, ResultCache, Written};
 use crate::kvs::cache::tx::TransactionCache;
 use crate::kvs::index::{BatchId, BatchIdsCleanQueue, SharedIndexKey};
 use crate::kvs::scanner::Direction;
@@ -69,6 +72,14 @@ pub struct Transaction {
 	/// Per index, track the pending append batch for cleanup after rollback (cancel or failed
 	/// commit).
 	pending_index_batches: Mutex<HashMap<SharedIndexKey, (BatchId, BatchIdsCleanQueue)>>,
+	/// The cross transaction cache of query results
+	results: Arc<ResultCache>,
+	/// The tables written in this transaction
+	written: parking_lot::Mutex<Written>,
+	/// The audit log of this store, if audit logging is enabled
+	audit_log: Option<AuditLog>,
+	/// The events of the statements audited in this transaction
+	audited: parking_lot::Mutex<Vec<AuditEvent>>,
 }
 
 impl Deref for Transaction {
@@ -81,10 +92,12 @@ impl Deref for Transaction {
 
 impl Transaction {
 	/// Create a new query store
-	pub fn new(
+	pub(crate) fn new(
 		local: bool,
 		sequences: Sequences,
 		async_event_trigger: Arc<Notify>,
+		results: Arc<ResultCache>,
+		audit_log: Option<AuditLog>,
 		tr: Transactor,
 	) -> Transaction {
 		Transaction {
@@ -96,6 +109,10 @@ impl Transaction {
 			async_event_trigger,
 			trigger_async_event: AtomicBool::new(false),
 			pending_index_batches: Mutex::new(HashMap::new()),
+			results,
+			written: parking_lot::Mutex::new(HashMap::new()),
+			audit_log,
+			audited: parking_lot::Mutex::new(Vec::new()),
 		}
 	}
 
@@ -125,6 +142,15 @@ impl Transaction {
 		self.tr.closed()
 	}
 
+	/// Check if indexes can be read at a version.
+	///
+	/// If the storage engine keeps every version of every key, then
+	/// versioned queries can use indexes, and this function will return
+	/// [`true`].
+	pub fn versioned_indexes(&self) -> bool {
+		self.tr.versioned_indexes()
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -132,11 +158,16 @@ impl Transaction {
 	pub async fn cancel(&self) -> Result<()> {
 		// Clear any buffered changefeed entries
 		self.cf.clear();
+		// Clear the tables written
+		self.written.lock().clear();
 		// Enqueue pending index batches for deferred cleanup after rollback (cancel or failed
 		// commit).
 		self.cleanup_index_batches().await;
 		// Cancel the transaction
-		Ok(self.tr.cancel().await.map_err(Error::from)?)
+		let res = self.tr.cancel().await.map_err(Error::from);
+		// Record the audited statements, whose changes were reverted
+		self.record_audited(Some("The transaction was cancelled".to_string())).await;
+		Ok(res?)
 	}
 
 	/// Commit a transaction.
@@ -144,8 +175,14 @@ impl Transaction {
 	/// This attempts to commit all changes made within the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
 	pub async fn commit(&self) -> Result<()> {
-		// Store any buffered changefeed entries
-		if let Err(e) = self.store_changes().await {
+		// Store any buffered changefeed entries, and publish the written tables
+		let stored = match self.store_changes().await {
+			Ok(()) => self.publish_written().await,
+			Err(e) => Err(e),
+		};
+		if let Err(e) = stored {
+			// Record the audited statements with the error
+			self.record_audited(Some(e.to_string())).await;
 			// Cancel the transaction if failure
 			let _ = self.cancel().await;
 			// Return the error
@@ -155,8 +192,17 @@ impl Transaction {
 		if let Err(e) = self.tr.commit().await {
 			// Enqueue pending index batches for deferred cleanup after commit failure.
 			self.cleanup_index_batches().await;
+			// Record the audited statements with the error
+			self.record_audited(Some(e.to_string())).await;
 			anyhow::bail!(e);
 		}
+		// Record the audited statements, whose changes were applied
+		self.record_audited(None).await;
+		// Invalidate the cached results which read the written tables
+		let written = std::mem::take(&mut *self.written.lock());
+		if !written.is_empty() {
+			self.results.written(written);
+		}
 		if self.trigger_async_event.load(Ordering::Relaxed) {
 			// Notify after commit so queued events are visible to workers.
 			self.async_event_trigger.notify_one();
@@ -164,6 +210,30 @@ impl Transaction {
 		Ok(())
 	}
 
+	/// Buffer the event of an audited statement, to be recorded once this
+	/// transaction is committed or cancelled.
+	pub(crate) fn audit(&self, event: AuditEvent) {
+		if self.audit_log.is_some() {
+			self.audited.lock().push(event);
+		}
+	}
+
+	/// Record the events of the statements audited in this transaction,
+	/// failing the statements which succeeded with the given error if the
+	/// transaction did not commit.
+	async fn record_audited(&self, error: Option<String>) {
+		let Some(audit_log) = &self.audit_log else {
+			return;
+		};
+		let events = std::mem::take(&mut *self.audited.lock());
+		for mut event in events {
+			if event.error.is_none() {
+				event.error.clone_from(&error);
+			}
+			audit_log.record(event).await;
+		}
+	}
+
 	/// Enqueue pending index batches for deferred cleanup after rollback (cancel or failed commit).
 	async fn cleanup_index_batches(&self) {
 		let batches = {
@@ -599,6 +669,28 @@ impl Transaction {
 		Ok(self.tr.scanr(beg..end, limit, skip, version).await.map_err(Error::from)?)
 	}
 
+	/// Retrieve every stored version of a key from the datastore.
+	///
+	/// This function returns the versions of the key between the two
+	/// timestamps (inclusive) in ascending order, with a `None` value for
+	/// each version at which the key was deleted.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn versions<K>(
+		&self,
+		key: &K,
+		since: u64,
+		until: u64,
+	) -> Result<Vec<(u64, Option<K::ValueType>)>>
+	where
+		K: KVKey + Debug,
+	{
+		let key = key.encode_key()?;
+		let res = self.tr.versions(key, since, until).await.map_err(Error::from)?;
+		res.into_iter()
+			.map(|(ts, val)| Ok((ts, val.map(K::ValueType::kv_decode_value).transpose()?)))
+			.collect()
+	}
+
 	/// Count the total number of keys within a range in the datastore.
 	///
 	/// This function fetches the total count, in batches, with multiple
@@ -835,6 +927,24 @@ impl Transaction {
 		Ok(())
 	}
 
+	/// Publishes the tables written in this transaction to the other nodes
+	/// which share the storage engine, so that they invalidate the cached
+	/// results which read those tables.
+	async fn publish_written(&self) -> Result<()> {
+		// Local storage engines are not shared with other nodes
+		if self.local {
+			return Ok(());
+		}
+		let invalidation = {
+			let written = self.written.lock();
+			if written.is_empty() {
+				return Ok(());
+			}
+			Invalidation::written(&written)
+		};
+		self.results.publish(self, &invalidation).await
+	}
+
 	// --------------------------------------------------
 	// Cache functions
 	// --------------------------------------------------
@@ -853,6 +963,15 @@ impl Transaction {
 		self.cache.insert(qey, cache::tx::Entry::Val(record));
 	}
 
+	/// Records that a table was written in this transaction.
+	fn set_table_written(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName) {
+		let mut written = self.written.lock();
+		let tables = written.entry((ns, db)).or_default();
+		if !tables.contains(tb) {
+			tables.insert(tb.to_owned());
+		}
+	}
+
 	/// Clears all keys from the transaction cache.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub fn clear_cache(&self) {
@@ -871,6 +990,13 @@ impl Transaction {
 		self.tr.inner.compact(rng).await
 	}
 
+	/// Removes a batch of the versions which have left the retention window of
+	/// the history, returning the key from which to continue, if any.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub(crate) async fn prune_history(&self, from: Option<Key>) -> Result<Option<Key>> {
+		Ok(self.tr.inner.prune_history(from).await.map_err(Error::from)?)
+	}
+
 	/// Mark this transaction to wake the async event processor after commit.
 	pub(crate) fn trigger_async_event(&self) {
 		self.trigger_async_event.store(true, Ordering::Relaxed);
@@ -2154,6 +2280,7 @@ impl TableProvider for Transaction {
 		let key = crate::key::record::new(ns, db, tb, id);
 		self.put(&key, &record, version).await?;
 		self.set_record_cache(ns, db, tb, id, record);
+		self.set_table_written(ns, db, tb);
 		Ok(())
 	}
 
@@ -2172,6 +2299,8 @@ impl TableProvider for Transaction {
 		self.set(&key, &record, version).await?;
 		// Set the value in the cache
 		self.set_record_cache(ns, db, tb, id, record);
+		// Mark the table as written
+		self.set_table_written(ns, db, tb);
 		// Return nothing
 		Ok(())
 	}
@@ -2190,6 +2319,8 @@ impl TableProvider for Transaction {
 		// Clear the value from the cache
 		let qey = cache::tx::Lookup::Record(ns, db, tb, id);
 		self.cache.remove(qey);
+		// Mark the table as written
+		self.set_table_written(ns, db, tb);
 		// Return nothing
 		Ok(())
 	}
@@ -2827,4 +2958,204 @@ impl BucketProvider for Transaction {
 	}
 }
 
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl JobProvider for Transaction {
+	/// Retrieve all job definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_jobs(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::JobDefinition]>> {
+		let qey = cache::tx::Lookup::Jbs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_jbs(),
+			None => {
+				let beg = crate::key::database::jb::prefix(ns, db)?;
+				let end = crate::key::database::jb::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Jbs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific job definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_job(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		jb: &str,
+	) -> Result<Option<Arc<catalog::JobDefinition>>> {
+		let qey = cache::tx::Lookup::Jb(ns, db, jb);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::database::jb::new(ns, db, jb);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+				let job_def = Arc::new(val);
+				let entr = cache::tx::Entry::Any(job_def.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(job_def))
+			}
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl SinkProvider for Transaction {
+	/// Retrieve all sink definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_sinks(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::SinkDefinition]>> {
+		let qey = cache::tx::Lookup::Sks(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_sks(),
+			None => {
+				let beg = crate::key::database::sk::prefix(ns, db)?;
+				let end = crate::key::database::sk::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Sks(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific sink definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_sink(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		sk: &str,
+	) -> Result<Option<Arc<catalog::SinkDefinition>>> {
+		let qey = cache::tx::Lookup::Sk(ns, db, sk);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::database::sk::new(ns, db, sk);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+				let sink_def = Arc::new(val);
+				let entr = cache::tx::Entry::Any(sink_def.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(sink_def))
+			}
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl ConsumerProvider for Transaction {
+	/// Retrieve all consumer definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_consumers(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		let qey = cache::tx::Lookup::Css(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_css(),
+			None => {
+				let beg = crate::key::database::cs::prefix(ns, db)?;
+				let end = crate::key::database::cs::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Css(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific consumer definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_consumer(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		cs: &str,
+	) -> Result<Option<Arc<catalog::ConsumerDefinition>>> {
+		let qey = cache::tx::Lookup::Cs(ns, db, cs);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::database::cs::new(ns, db, cs);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+				let consumer_def = Arc::new(val);
+				let entr = cache::tx::Entry::Any(consumer_def.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(consumer_def))
+			}
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl KeyProvider for Transaction {
+	/// Retrieve all key definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn all_db_keys(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::KeyDefinition]>> {
+		let qey = cache::tx::Lookup::Kys(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_kys(),
+			None => {
+				let beg = crate::key::database::ky::prefix(ns, db)?;
+				let end = crate::key::database::ky::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Kys(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific key definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	async fn get_db_key(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ky: &str,
+	) -> Result<Option<Arc<catalog::KeyDefinition>>> {
+		let qey = cache::tx::Lookup::Ky(ns, db, ky);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type().map(Some),
+			None => {
+				let key = crate::key::database::ky::new(ns, db, ky);
+				let Some(val) = self.get(&key, None).await? else {
+					return Ok(None);
+				};
+				let key_def = Arc::new(val);
+				let entr = cache::tx::Entry::Any(key_def.clone());
+				self.cache.insert(qey, entr);
+				Ok(Some(key_def))
+			}
+		}
+	}
+}
+
 impl CatalogProvider for Transaction {}
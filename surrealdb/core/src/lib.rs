@@ -38,6 +38,7 @@ pub mod doc;
 mod exe;
 mod fmt;
 mod fnc;
+mod job;
 mod key;
 #[doc(hidden)]
 pub mod str;
@@ -27,6 +27,45 @@ pub struct EngineOptions {
 	///
 	/// Default: 5 seconds
 	pub event_processing_interval: Duration,
+	/// Interval for deleting the records whose `TTL` has elapsed.
+	///
+	/// Default: 5 seconds
+	pub record_expiry_interval: Duration,
+	/// Interval for checking which scheduled jobs are due.
+	///
+	/// As schedules have a granularity of one minute, this should not
+	/// exceed a minute. Default: 10 seconds
+	pub job_scheduler_interval: Duration,
+	/// Interval for delivering changefeeds to the sinks defined with
+	/// `DEFINE SINK`.
+	///
+	/// Default: 1 second
+	pub changefeed_sink_interval: Duration,
+	/// Interval for writing the buffered audit log events to the datastore.
+	///
+	/// Default: 1 second
+	pub audit_log_flush_interval: Duration,
+	/// Interval for applying the result cache invalidations published by the
+	/// other nodes of the cluster.
+	///
+	/// Default: 1 second
+	pub result_invalidation_interval: Duration,
+	/// Interval for discarding the multipart file uploads which have been idle
+	/// for longer than the upload timeout.
+	///
+	/// Default: 60 seconds
+	pub file_upload_expiry_interval: Duration,
+	/// Interval for removing the versions kept for versioned queries on the
+	/// `rocksdb` and `tikv` storage engines which have left the retention
+	/// window.
+	///
+	/// Default: 60 seconds
+	pub history_retention_interval: Duration,
+	/// Interval for removing the audit log events stored in the datastore
+	/// which have left the audit log retention period.
+	///
+	/// Default: 60 seconds
+	pub audit_log_retention_interval: Duration,
 }
 
 impl Default for EngineOptions {
@@ -38,6 +77,14 @@ impl Default for EngineOptions {
 			changefeed_gc_interval: Duration::from_secs(30),
 			index_compaction_interval: Duration::from_secs(5),
 			event_processing_interval: Duration::from_secs(5),
+			record_expiry_interval: Duration::from_secs(5),
+			job_scheduler_interval: Duration::from_secs(10),
+			changefeed_sink_interval: Duration::from_secs(1),
+			audit_log_flush_interval: Duration::from_secs(1),
+			result_invalidation_interval: Duration::from_secs(1),
+			file_upload_expiry_interval: Duration::from_secs(60),
+			history_retention_interval: Duration::from_secs(60),
+			audit_log_retention_interval: Duration::from_secs(60),
 		}
 	}
 }
@@ -69,4 +116,44 @@ impl EngineOptions {
 		self.event_processing_interval = interval;
 		self
 	}
+
+	pub fn with_record_expiry_interval(mut self, interval: Duration) -> Self {
+		self.record_expiry_interval = interval;
+		self
+	}
+
+	pub fn with_job_scheduler_interval(mut self, interval: Duration) -> Self {
+		self.job_scheduler_interval = interval;
+		self
+	}
+
+	pub fn with_changefeed_sink_interval(mut self, interval: Duration) -> Self {
+		self.changefeed_sink_interval = interval;
+		self
+	}
+
+	pub fn with_audit_log_flush_interval(mut self, interval: Duration) -> Self {
+		self.audit_log_flush_interval = interval;
+		self
+	}
+
+	pub fn with_result_invalidation_interval(mut self, interval: Duration) -> Self {
+		self.result_invalidation_interval = interval;
+		self
+	}
+
+	pub fn with_file_upload_expiry_interval(mut self, interval: Duration) -> Self {
+		self.file_upload_expiry_interval = interval;
+		self
+	}
+
+	pub fn with_history_retention_interval(mut self, interval: Duration) -> Self {
+		self.history_retention_interval = interval;
+		self
+	}
+
+	pub fn with_audit_log_retention_interval(mut self, interval: Duration) -> Self {
+		self.audit_log_retention_interval = interval;
+		self
+	}
 }
@@ -807,6 +807,7 @@ pub trait RpcProtocol {
 			timeout: Expr::Literal(Literal::None),
 			explain: None,
 			tempfiles: false,
+			cache: None,
 		};
 		let ast = Ast::single_expr(Expr::Select(Box::new(sql)));
 
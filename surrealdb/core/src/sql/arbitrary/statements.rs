@@ -126,7 +126,7 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 				}
 				cols
 			}
-			Index::Hnsw(_) | Index::FullText(_) => vec![u.arbitrary()?],
+			Index::Hnsw(_) | Index::FullText(_) | Index::Spatial(_) => vec![u.arbitrary()?],
 			Index::Count(_) => Vec::new(),
 		};
 
@@ -208,6 +208,7 @@ impl<'a> arbitrary::Arbitrary<'a> for SelectStatement {
 			timeout: u.arbitrary()?,
 			explain: u.arbitrary()?,
 			tempfiles: u.arbitrary()?,
+			cache: u.arbitrary()?,
 		})
 	}
 }
@@ -239,6 +240,7 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineAnalyzerStatement {
 			function: u.arbitrary()?,
 			tokenizers: arb_opt(u, |u| arb_vec1(u, Arbitrary::arbitrary))?,
 			filters: arb_opt(u, |u| arb_vec1(u, Arbitrary::arbitrary))?,
+			operators: u.arbitrary()?,
 			comment: u.arbitrary()?,
 		})
 	}
@@ -284,6 +286,7 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineFieldStatement {
 			permissions,
 			comment: u.arbitrary()?,
 			reference: u.arbitrary()?,
+			encrypted: u.arbitrary()?,
 		})
 	}
 }
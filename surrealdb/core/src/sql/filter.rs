@@ -1,7 +1,8 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
-use crate::fmt::QuoteStr;
+use crate::fmt::{Fmt, QuoteStr};
 use crate::sql::language::Language;
+use crate::types::PublicFile;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -13,6 +14,29 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	Stopwords(Stopwords),
+	Synonyms(Synonyms, Option<FilterStage>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Stopwords {
+	Language(Language),
+	Custom(Vec<String>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Synonyms {
+	Rules(Vec<String>),
+	File(PublicFile),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FilterStage {
+	Indexing,
+	Querying,
 }
 
 impl ToSql for Filter {
@@ -25,6 +49,31 @@ impl ToSql for Filter {
 			Self::Snowball(lang) => write_sql!(f, fmt, "SNOWBALL({lang})"),
 			Self::Uppercase => f.push_str("UPPERCASE"),
 			Self::Mapper(path) => write_sql!(f, fmt, "MAPPER({})", QuoteStr(path)),
+			Self::Stopwords(Stopwords::Language(lang)) => write_sql!(f, fmt, "STOPWORDS({lang})"),
+			Self::Stopwords(Stopwords::Custom(words)) => write_sql!(
+				f,
+				fmt,
+				"STOPWORDS([{}])",
+				Fmt::comma_separated(words.iter().map(|x| QuoteStr(x)))
+			),
+			Self::Synonyms(synonyms, stage) => {
+				f.push_str("SYNONYMS(");
+				match synonyms {
+					Synonyms::Rules(rules) => write_sql!(
+						f,
+						fmt,
+						"[{}]",
+						Fmt::comma_separated(rules.iter().map(|x| QuoteStr(x)))
+					),
+					Synonyms::File(file) => file.fmt_sql(f, fmt),
+				}
+				match stage {
+					Some(FilterStage::Indexing) => f.push_str(", INDEXING"),
+					Some(FilterStage::Querying) => f.push_str(", QUERYING"),
+					None => {}
+				}
+				f.push(')');
+			}
 		}
 	}
 }
@@ -39,6 +88,10 @@ impl From<Filter> for crate::expr::Filter {
 			Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			Filter::Uppercase => Self::Uppercase,
 			Filter::Mapper(path) => Self::Mapper(path),
+			Filter::Stopwords(words) => Self::Stopwords(words.into()),
+			Filter::Synonyms(synonyms, stage) => {
+				Self::Synonyms(synonyms.into(), stage.map(Into::into))
+			}
 		}
 	}
 }
@@ -53,6 +106,65 @@ impl From<crate::expr::Filter> for Filter {
 			crate::expr::Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			crate::expr::Filter::Uppercase => Self::Uppercase,
 			crate::expr::Filter::Mapper(path) => Self::Mapper(path),
+			crate::expr::Filter::Stopwords(words) => Self::Stopwords(words.into()),
+			crate::expr::Filter::Synonyms(synonyms, stage) => {
+				Self::Synonyms(synonyms.into(), stage.map(Into::into))
+			}
+		}
+	}
+}
+
+impl From<Stopwords> for crate::expr::filter::Stopwords {
+	fn from(v: Stopwords) -> Self {
+		match v {
+			Stopwords::Language(lang) => Self::Language(lang.into()),
+			Stopwords::Custom(words) => Self::Custom(words),
+		}
+	}
+}
+
+impl From<crate::expr::filter::Stopwords> for Stopwords {
+	fn from(v: crate::expr::filter::Stopwords) -> Self {
+		match v {
+			crate::expr::filter::Stopwords::Language(lang) => Self::Language(lang.into()),
+			crate::expr::filter::Stopwords::Custom(words) => Self::Custom(words),
+		}
+	}
+}
+
+impl From<Synonyms> for crate::expr::filter::Synonyms {
+	fn from(v: Synonyms) -> Self {
+		match v {
+			Synonyms::Rules(rules) => Self::Rules(rules),
+			// The rules are read from the file when the analyzer is defined
+			Synonyms::File(file) => Self::File(file.into(), Vec::new()),
+		}
+	}
+}
+
+impl From<crate::expr::filter::Synonyms> for Synonyms {
+	fn from(v: crate::expr::filter::Synonyms) -> Self {
+		match v {
+			crate::expr::filter::Synonyms::Rules(rules) => Self::Rules(rules),
+			crate::expr::filter::Synonyms::File(file, _) => Self::File(file.into()),
+		}
+	}
+}
+
+impl From<FilterStage> for crate::expr::filter::FilterStage {
+	fn from(v: FilterStage) -> Self {
+		match v {
+			FilterStage::Indexing => Self::Indexing,
+			FilterStage::Querying => Self::Querying,
+		}
+	}
+}
+
+impl From<crate::expr::filter::FilterStage> for FilterStage {
+	fn from(v: crate::expr::filter::FilterStage) -> Self {
+		match v {
+			crate::expr::filter::FilterStage::Indexing => Self::Indexing,
+			crate::expr::filter::FilterStage::Querying => Self::Querying,
 		}
 	}
 }
@@ -18,6 +18,8 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Spatial index for geometry based queries
+	Spatial(SpatialParams),
 }
 
 impl From<Index> for crate::catalog::Index {
@@ -28,6 +30,7 @@ impl From<Index> for crate::catalog::Index {
 			Index::Hnsw(p) => Self::Hnsw(p.into()),
 			Index::FullText(p) => Self::FullText(p.into()),
 			Index::Count(c) => Self::Count(c.map(Into::into)),
+			Index::Spatial(p) => Self::Spatial(p.into()),
 		}
 	}
 }
@@ -40,6 +43,7 @@ impl From<crate::catalog::Index> for Index {
 			crate::catalog::Index::Hnsw(p) => Self::Hnsw(p.into()),
 			crate::catalog::Index::FullText(p) => Self::FullText(p.into()),
 			crate::catalog::Index::Count(c) => Self::Count(c.map(Into::into)),
+			crate::catalog::Index::Spatial(p) => Self::Spatial(p.into()),
 		}
 	}
 }
@@ -71,6 +75,28 @@ impl From<crate::catalog::FullTextParams> for FullTextParams {
 	}
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct SpatialParams {
+	pub level: u8,
+}
+
+impl From<SpatialParams> for crate::catalog::SpatialParams {
+	fn from(v: SpatialParams) -> Self {
+		crate::catalog::SpatialParams {
+			level: v.level,
+		}
+	}
+}
+
+impl From<crate::catalog::SpatialParams> for SpatialParams {
+	fn from(v: crate::catalog::SpatialParams) -> Self {
+		Self {
+			level: v.level,
+		}
+	}
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub(crate) struct HnswParams {
@@ -242,6 +268,7 @@ impl ToSql for Index {
 					f.push_str(" HASHED_VECTOR")
 				}
 			}
+			Self::Spatial(p) => write_sql!(f, fmt, "SPATIAL LEVEL {}", p.level),
 		}
 	}
 }
@@ -1,6 +1,7 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::fmt::EscapeKwFreeIdent;
+use crate::types::PublicDuration;
 use crate::val::File;
 use crate::{catalog, expr};
 
@@ -187,3 +188,52 @@ impl ToSql for SiloExecutable {
 		)
 	}
 }
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct ModuleLimits {
+	pub fuel: Option<u64>,
+	pub memory: Option<u64>,
+	pub timeout: Option<PublicDuration>,
+}
+
+impl ModuleLimits {
+	pub(crate) fn is_empty(&self) -> bool {
+		self.fuel.is_none() && self.memory.is_none() && self.timeout.is_none()
+	}
+}
+
+impl From<catalog::ModuleLimits> for ModuleLimits {
+	fn from(limits: catalog::ModuleLimits) -> Self {
+		Self {
+			fuel: limits.fuel,
+			memory: limits.memory,
+			timeout: limits.timeout.map(Into::into),
+		}
+	}
+}
+
+impl From<ModuleLimits> for catalog::ModuleLimits {
+	fn from(limits: ModuleLimits) -> Self {
+		Self {
+			fuel: limits.fuel,
+			memory: limits.memory,
+			timeout: limits.timeout.map(Into::into),
+		}
+	}
+}
+
+impl ToSql for ModuleLimits {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("LIMITS");
+		if let Some(v) = self.fuel {
+			write_sql!(f, fmt, " FUEL {v}");
+		}
+		if let Some(v) = self.memory {
+			write_sql!(f, fmt, " MEMORY {v}");
+		}
+		if let Some(v) = self.timeout {
+			write_sql!(f, fmt, " TIMEOUT {v}");
+		}
+	}
+}
@@ -0,0 +1,45 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
+use crate::sql::Expr;
+
+/// An ACK statement for acknowledging the changes read by a changefeed
+/// consumer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AckStatement {
+	pub consumer: String,
+	// Versionstamp of the last change set read
+	// or Param resolving to the versionstamp
+	pub versionstamp: Expr,
+}
+
+impl ToSql for AckStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(
+			f,
+			fmt,
+			"ACK CONSUMER {} AT {}",
+			EscapeKwFreeIdent(&self.consumer),
+			CoverStmts(&self.versionstamp)
+		);
+	}
+}
+
+impl From<AckStatement> for crate::expr::statements::AckStatement {
+	fn from(v: AckStatement) -> Self {
+		Self {
+			consumer: v.consumer,
+			versionstamp: v.versionstamp.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::AckStatement> for AckStatement {
+	fn from(v: crate::expr::statements::AckStatement) -> Self {
+		Self {
+			consumer: v.consumer,
+			versionstamp: v.versionstamp.into(),
+		}
+	}
+}
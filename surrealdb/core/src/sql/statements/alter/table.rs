@@ -3,6 +3,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use super::AlterKind;
 use crate::fmt::{EscapeKwFreeIdent, EscapeKwIdent, QuoteStr};
 use crate::sql::{ChangeFeed, Permissions, TableType};
+use crate::types::PublicDuration;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -14,6 +15,7 @@ use crate::sql::{ChangeFeed, Permissions, TableType};
 /// - `PERMISSIONS ...`
 /// - `CHANGEFEED ...` / `DROP CHANGEFEED`
 /// - `COMMENT <string>` / `DROP COMMENT`
+/// - `TTL <duration>` / `DROP TTL`
 /// - `COMPACT` (request table keyspace compaction)
 ///
 /// Note: `COMPACT` is parsed and preserved on the expression side, however it is
@@ -25,6 +27,7 @@ pub struct AlterTableStatement {
 	pub permissions: Option<Permissions>,
 	pub changefeed: AlterKind<ChangeFeed>,
 	pub comment: AlterKind<String>,
+	pub ttl: AlterKind<PublicDuration>,
 	pub kind: Option<TableType>,
 	/// Request table‑level compaction when true.
 	pub compact: bool,
@@ -87,6 +90,12 @@ impl ToSql for AlterTableStatement {
 			AlterKind::Drop => f.push_str(" DROP CHANGEFEED"),
 			AlterKind::None => {}
 		}
+
+		match self.ttl {
+			AlterKind::Set(ref ttl) => write_sql!(f, fmt, " TTL {}", ttl),
+			AlterKind::Drop => f.push_str(" DROP TTL"),
+			AlterKind::None => {}
+		}
 		if let Some(permissions) = &self.permissions {
 			write_sql!(f, fmt, " {permissions}");
 		}
@@ -106,6 +115,7 @@ impl From<AlterTableStatement> for crate::expr::statements::alter::AlterTableSta
 			permissions: v.permissions.map(Into::into),
 			changefeed: v.changefeed.into(),
 			comment: v.comment.into(),
+			ttl: v.ttl.into(),
 			kind: v.kind.map(Into::into),
 			compact: v.compact,
 		}
@@ -121,6 +131,7 @@ impl From<crate::expr::statements::alter::AlterTableStatement> for AlterTableSta
 			permissions: v.permissions.map(Into::into),
 			changefeed: v.changefeed.into(),
 			comment: v.comment.into(),
+			ttl: v.ttl.into(),
 			kind: v.kind.map(Into::into),
 			compact: v.compact,
 		}
@@ -0,0 +1,93 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
+use crate::sql::{Block, Expr, Kind, Literal, Permission};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineAggregateStatement {
+	pub kind: DefineKind,
+	pub name: String,
+	pub args: Vec<(String, Kind)>,
+	pub returns: Option<Kind>,
+	pub state: Expr,
+	pub accumulate: Block,
+	pub merge: Block,
+	pub finalize: Block,
+	pub comment: Expr,
+	pub permissions: Permission,
+}
+
+impl ToSql for DefineAggregateStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "DEFINE AGGREGATE");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write_sql!(f, fmt, " OVERWRITE"),
+			DefineKind::IfNotExists => write_sql!(f, fmt, " IF NOT EXISTS"),
+		}
+		write_sql!(f, fmt, " fn");
+		for s in self.name.split("::") {
+			write_sql!(f, fmt, "::");
+			EscapeKwFreeIdent(s).fmt_sql(f, fmt);
+		}
+		write_sql!(f, fmt, "(");
+		for (i, (name, kind)) in self.args.iter().enumerate() {
+			if i > 0 {
+				f.push_str(", ");
+			}
+			write_sql!(f, fmt, "${}: {kind}", EscapeKwFreeIdent(name));
+		}
+		f.push(')');
+		if let Some(ref v) = self.returns {
+			write_sql!(f, fmt, " -> {v}");
+		}
+		write_sql!(f, fmt, " STATE {}", CoverStmts(&self.state));
+		write_sql!(f, fmt, " ACCUMULATE ");
+		self.accumulate.fmt_sql(f, fmt);
+		write_sql!(f, fmt, " MERGE ");
+		self.merge.fmt_sql(f, fmt);
+		write_sql!(f, fmt, " FINAL ");
+		self.finalize.fmt_sql(f, fmt);
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+		let fmt = fmt.increment();
+		write_sql!(f, fmt, " PERMISSIONS {}", self.permissions);
+	}
+}
+
+impl From<DefineAggregateStatement> for crate::expr::statements::DefineAggregateStatement {
+	fn from(v: DefineAggregateStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name,
+			args: v.args.into_iter().map(|(i, k)| (i, k.into())).collect(),
+			returns: v.returns.map(Into::into),
+			state: v.state.into(),
+			accumulate: v.accumulate.into(),
+			merge: v.merge.into(),
+			finalize: v.finalize.into(),
+			comment: v.comment.into(),
+			permissions: v.permissions.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::DefineAggregateStatement> for DefineAggregateStatement {
+	fn from(v: crate::expr::statements::DefineAggregateStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name,
+			args: v.args.into_iter().map(|(i, k)| (i, k.into())).collect(),
+			returns: v.returns.map(Into::into),
+			state: v.state.into(),
+			accumulate: v.accumulate.into(),
+			merge: v.merge.into(),
+			finalize: v.finalize.into(),
+			comment: v.comment.into(),
+			permissions: v.permissions.into(),
+		}
+	}
+}
@@ -13,6 +13,7 @@ pub(crate) struct DefineAnalyzerStatement {
 	pub function: Option<String>,
 	pub tokenizers: Option<Vec<Tokenizer>>,
 	pub filters: Option<Vec<Filter>>,
+	pub operators: bool,
 	pub comment: Expr,
 }
 
@@ -24,6 +25,7 @@ impl Default for DefineAnalyzerStatement {
 			function: None,
 			tokenizers: None,
 			filters: None,
+			operators: false,
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -52,6 +54,9 @@ impl ToSql for DefineAnalyzerStatement {
 		if let Some(v) = &self.filters {
 			write_sql!(f, sql_fmt, " FILTERS {}", Fmt::comma_separated(v.iter()));
 		}
+		if self.operators {
+			write_sql!(f, sql_fmt, " OPERATORS");
+		}
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -66,6 +71,7 @@ impl From<DefineAnalyzerStatement> for crate::expr::statements::DefineAnalyzerSt
 			function: v.function,
 			tokenizers: v.tokenizers.map(|v| v.into_iter().map(Into::into).collect()),
 			filters: v.filters.map(|v| v.into_iter().map(Into::into).collect()),
+			operators: v.operators,
 			comment: v.comment.into(),
 		}
 	}
@@ -79,6 +85,7 @@ impl From<crate::expr::statements::DefineAnalyzerStatement> for DefineAnalyzerSt
 			function: v.function,
 			tokenizers: v.tokenizers.map(|v| v.into_iter().map(Into::into).collect()),
 			filters: v.filters.map(|v| v.into_iter().map(Into::into).collect()),
+			operators: v.operators,
 			comment: v.comment.into(),
 		}
 	}
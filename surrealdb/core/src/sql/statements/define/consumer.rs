@@ -0,0 +1,72 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub table: Option<Expr>,
+	pub retain: bool,
+	pub comment: Expr,
+}
+
+impl Default for DefineConsumerStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			table: None,
+			retain: false,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl ToSql for DefineConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "DEFINE CONSUMER");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write_sql!(f, sql_fmt, " OVERWRITE"),
+			DefineKind::IfNotExists => write_sql!(f, sql_fmt, " IF NOT EXISTS"),
+		}
+		write_sql!(f, sql_fmt, " {}", CoverStmts(&self.name));
+		if let Some(ref table) = self.table {
+			write_sql!(f, sql_fmt, " ON {}", CoverStmts(table));
+		}
+		if self.retain {
+			write_sql!(f, sql_fmt, " RETAIN");
+		}
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineConsumerStatement> for crate::expr::statements::define::DefineConsumerStatement {
+	fn from(v: DefineConsumerStatement) -> Self {
+		crate::expr::statements::define::DefineConsumerStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			table: v.table.map(Into::into),
+			retain: v.retain,
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineConsumerStatement> for DefineConsumerStatement {
+	fn from(v: crate::expr::statements::define::DefineConsumerStatement) -> Self {
+		DefineConsumerStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			table: v.table.map(Into::into),
+			retain: v.retain,
+			comment: v.comment.into(),
+		}
+	}
+}
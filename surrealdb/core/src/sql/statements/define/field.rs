@@ -1,7 +1,7 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
-use crate::fmt::CoverStmts;
+use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
 use crate::sql::reference::Reference;
 use crate::sql::{Expr, Kind, Literal, Permissions};
 
@@ -57,6 +57,7 @@ pub(crate) struct DefineFieldStatement {
 	pub permissions: Permissions,
 	pub comment: Expr,
 	pub reference: Option<Reference>,
+	pub encrypted: Option<String>,
 }
 
 impl Default for DefineFieldStatement {
@@ -75,6 +76,7 @@ impl Default for DefineFieldStatement {
 			permissions: Permissions::default(),
 			comment: Expr::Literal(Literal::None),
 			reference: None,
+			encrypted: None,
 		}
 	}
 }
@@ -118,6 +120,9 @@ impl ToSql for DefineFieldStatement {
 		if let Some(ref v) = self.reference {
 			write_sql!(f, sql_fmt, " REFERENCE {v}");
 		}
+		if let Some(ref v) = self.encrypted {
+			write_sql!(f, sql_fmt, " ENCRYPTED WITH KEY {}", EscapeKwFreeIdent(v));
+		}
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -147,6 +152,7 @@ impl From<DefineFieldStatement> for crate::expr::statements::DefineFieldStatemen
 			permissions: v.permissions.into(),
 			comment: v.comment.into(),
 			reference: v.reference.map(Into::into),
+			encrypted: v.encrypted,
 		}
 	}
 }
@@ -168,6 +174,7 @@ impl From<crate::expr::statements::DefineFieldStatement> for DefineFieldStatemen
 			permissions: v.permissions.into(),
 			comment: v.comment.into(),
 			reference: v.reference.map(Into::into),
+			encrypted: v.encrypted,
 		}
 	}
 }
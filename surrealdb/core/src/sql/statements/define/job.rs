@@ -0,0 +1,73 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineJobStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub schedule: Expr,
+	pub then: Expr,
+	pub comment: Expr,
+}
+
+impl Default for DefineJobStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			schedule: Expr::Literal(Literal::None),
+			then: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl ToSql for DefineJobStatement {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "DEFINE JOB");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write_sql!(f, sql_fmt, " OVERWRITE"),
+			DefineKind::IfNotExists => write_sql!(f, sql_fmt, " IF NOT EXISTS"),
+		}
+		write_sql!(
+			f,
+			sql_fmt,
+			" {} SCHEDULE {} THEN {}",
+			CoverStmts(&self.name),
+			CoverStmts(&self.schedule),
+			CoverStmts(&self.then)
+		);
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineJobStatement> for crate::expr::statements::define::DefineJobStatement {
+	fn from(v: DefineJobStatement) -> Self {
+		crate::expr::statements::define::DefineJobStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			schedule: v.schedule.into(),
+			then: v.then.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineJobStatement> for DefineJobStatement {
+	fn from(v: crate::expr::statements::define::DefineJobStatement) -> Self {
+		DefineJobStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			schedule: v.schedule.into(),
+			then: v.then.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
@@ -0,0 +1,79 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineKeyStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub version: Option<u32>,
+	pub value: Option<Expr>,
+	pub encrypted: bool,
+	pub comment: Expr,
+}
+
+impl Default for DefineKeyStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			version: None,
+			value: None,
+			encrypted: false,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl ToSql for DefineKeyStatement {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "DEFINE KEY");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write_sql!(f, sql_fmt, " OVERWRITE"),
+			DefineKind::IfNotExists => write_sql!(f, sql_fmt, " IF NOT EXISTS"),
+		}
+		write_sql!(f, sql_fmt, " {}", CoverStmts(&self.name));
+		if let Some(version) = self.version {
+			write_sql!(f, sql_fmt, " VERSION {version}");
+		}
+		if let Some(ref value) = self.value {
+			if self.encrypted {
+				write_sql!(f, sql_fmt, " ENCRYPTED");
+			}
+			write_sql!(f, sql_fmt, " VALUE {}", CoverStmts(value));
+		}
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineKeyStatement> for crate::expr::statements::define::DefineKeyStatement {
+	fn from(v: DefineKeyStatement) -> Self {
+		crate::expr::statements::define::DefineKeyStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			version: v.version,
+			value: v.value.map(Into::into),
+			encrypted: v.encrypted,
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineKeyStatement> for DefineKeyStatement {
+	fn from(v: crate::expr::statements::define::DefineKeyStatement) -> Self {
+		DefineKeyStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			version: v.version,
+			value: v.value.map(Into::into),
+			encrypted: v.encrypted,
+			comment: v.comment.into(),
+		}
+	}
+}
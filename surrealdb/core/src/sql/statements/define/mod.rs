@@ -1,36 +1,46 @@
 mod access;
+mod aggregate;
 mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
+mod key;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 pub mod user;
 
 pub(crate) use access::DefineAccessStatement;
+pub(crate) use aggregate::DefineAggregateStatement;
 pub(crate) use analyzer::DefineAnalyzerStatement;
 pub(crate) use api::{ApiAction, DefineApiStatement};
 pub(crate) use bucket::DefineBucketStatement;
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::DefineConsumerStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
 pub(crate) use function::DefineFunctionStatement;
 pub(crate) use index::DefineIndexStatement;
+pub(crate) use job::DefineJobStatement;
+pub(crate) use key::DefineKeyStatement;
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
 pub(crate) use sequence::DefineSequenceStatement;
+pub(crate) use sink::{DefineSinkStatement, SinkTarget};
 use surrealdb_types::{SqlFormat, ToSql};
 pub(crate) use table::DefineTableStatement;
 pub(crate) use user::DefineUserStatement;
@@ -70,6 +80,7 @@ pub(crate) enum DefineStatement {
 	Namespace(DefineNamespaceStatement),
 	Database(DefineDatabaseStatement),
 	Function(DefineFunctionStatement),
+	Aggregate(DefineAggregateStatement),
 	Analyzer(DefineAnalyzerStatement),
 	Param(DefineParamStatement),
 	Table(DefineTableStatement),
@@ -86,6 +97,10 @@ pub(crate) enum DefineStatement {
 	Sequence(DefineSequenceStatement),
 	#[cfg_attr(feature = "arbitrary", arbitrary(skip))]
 	Module(DefineModuleStatement),
+	Job(DefineJobStatement),
+	Sink(DefineSinkStatement),
+	Consumer(DefineConsumerStatement),
+	Key(DefineKeyStatement),
 }
 
 impl ToSql for DefineStatement {
@@ -94,6 +109,7 @@ impl ToSql for DefineStatement {
 			Self::Namespace(v) => v.fmt_sql(f, fmt),
 			Self::Database(v) => v.fmt_sql(f, fmt),
 			Self::Function(v) => v.fmt_sql(f, fmt),
+			Self::Aggregate(v) => v.fmt_sql(f, fmt),
 			Self::User(v) => v.fmt_sql(f, fmt),
 			Self::Param(v) => v.fmt_sql(f, fmt),
 			Self::Table(v) => v.fmt_sql(f, fmt),
@@ -108,6 +124,10 @@ impl ToSql for DefineStatement {
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
+			Self::Job(v) => v.fmt_sql(f, fmt),
+			Self::Sink(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
+			Self::Key(v) => v.fmt_sql(f, fmt),
 		}
 	}
 }
@@ -118,6 +138,7 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Namespace(v) => Self::Namespace(v.into()),
 			DefineStatement::Database(v) => Self::Database(v.into()),
 			DefineStatement::Function(v) => Self::Function(v.into()),
+			DefineStatement::Aggregate(v) => Self::Aggregate(v.into()),
 			DefineStatement::Analyzer(v) => Self::Analyzer(v.into()),
 			DefineStatement::Param(v) => Self::Param(v.into()),
 			DefineStatement::Table(v) => Self::Table(v.into()),
@@ -132,6 +153,10 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			DefineStatement::Sequence(v) => Self::Sequence(v.into()),
 			DefineStatement::Module(v) => Self::Module(v.into()),
+			DefineStatement::Job(v) => Self::Job(v.into()),
+			DefineStatement::Sink(v) => Self::Sink(v.into()),
+			DefineStatement::Consumer(v) => Self::Consumer(v.into()),
+			DefineStatement::Key(v) => Self::Key(v.into()),
 		}
 	}
 }
@@ -142,6 +167,7 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Namespace(v) => Self::Namespace(v.into()),
 			crate::expr::statements::DefineStatement::Database(v) => Self::Database(v.into()),
 			crate::expr::statements::DefineStatement::Function(v) => Self::Function(v.into()),
+			crate::expr::statements::DefineStatement::Aggregate(v) => Self::Aggregate(v.into()),
 			crate::expr::statements::DefineStatement::Analyzer(v) => Self::Analyzer(v.into()),
 			crate::expr::statements::DefineStatement::Param(v) => Self::Param(v.into()),
 			crate::expr::statements::DefineStatement::Table(v) => Self::Table(v.into()),
@@ -156,6 +182,10 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::DefineStatement::Sequence(v) => Self::Sequence(v.into()),
 			crate::expr::statements::DefineStatement::Module(v) => Self::Module(v.into()),
+			crate::expr::statements::DefineStatement::Job(v) => Self::Job(v.into()),
+			crate::expr::statements::DefineStatement::Sink(v) => Self::Sink(v.into()),
+			crate::expr::statements::DefineStatement::Consumer(v) => Self::Consumer(v.into()),
+			crate::expr::statements::DefineStatement::Key(v) => Self::Key(v.into()),
 		}
 	}
 }
@@ -2,7 +2,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
 use crate::fmt::CoverStmts;
-use crate::sql::{Expr, Literal, ModuleExecutable, Permission};
+use crate::sql::{Expr, Literal, ModuleExecutable, ModuleLimits, Permission};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -10,6 +10,7 @@ pub(crate) struct DefineModuleStatement {
 	pub kind: DefineKind,
 	pub name: Option<String>,
 	pub executable: ModuleExecutable,
+	pub limits: ModuleLimits,
 	pub comment: Expr,
 	pub permissions: Permission,
 }
@@ -26,6 +27,9 @@ impl ToSql for DefineModuleStatement {
 			write_sql!(f, sql_fmt, " mod::{} AS", name);
 		}
 		write_sql!(f, sql_fmt, " {}", self.executable);
+		if !self.limits.is_empty() {
+			write_sql!(f, sql_fmt, " {}", self.limits);
+		}
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -45,6 +49,7 @@ impl From<DefineModuleStatement> for crate::expr::statements::DefineModuleStatem
 			kind: v.kind.into(),
 			name: v.name,
 			executable: v.executable.into(),
+			limits: v.limits.into(),
 			comment: v.comment.into(),
 			permissions: v.permissions.into(),
 		}
@@ -57,6 +62,7 @@ impl From<crate::expr::statements::DefineModuleStatement> for DefineModuleStatem
 			kind: v.kind.into(),
 			name: v.name,
 			executable: v.executable.into(),
+			limits: v.limits.into(),
 			comment: v.comment.into(),
 			permissions: v.permissions.into(),
 		}
@@ -0,0 +1,149 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::catalog::SinkDefinition;
+use crate::fmt::{CoverStmts, Fmt};
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineSinkStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub tables: Vec<Expr>,
+	pub target: SinkTarget,
+	pub batch: u32,
+	pub retry: u32,
+	pub comment: Expr,
+}
+
+impl Default for DefineSinkStatement {
+	fn default() -> Self {
+		Self {
+			kind: DefineKind::Default,
+			name: Expr::Literal(Literal::None),
+			tables: Vec::new(),
+			target: SinkTarget::Stdout,
+			batch: SinkDefinition::DEFAULT_BATCH,
+			retry: SinkDefinition::DEFAULT_RETRY,
+			comment: Expr::Literal(Literal::None),
+		}
+	}
+}
+
+impl ToSql for DefineSinkStatement {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "DEFINE SINK");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write_sql!(f, sql_fmt, " OVERWRITE"),
+			DefineKind::IfNotExists => write_sql!(f, sql_fmt, " IF NOT EXISTS"),
+		}
+		write_sql!(f, sql_fmt, " {}", CoverStmts(&self.name));
+		if !self.tables.is_empty() {
+			write_sql!(
+				f,
+				sql_fmt,
+				" ON {}",
+				Fmt::comma_separated(self.tables.iter().map(CoverStmts))
+			);
+		}
+		write_sql!(f, sql_fmt, " TO {} BATCH {} RETRY {}", self.target, self.batch, self.retry);
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineSinkStatement> for crate::expr::statements::define::DefineSinkStatement {
+	fn from(v: DefineSinkStatement) -> Self {
+		crate::expr::statements::define::DefineSinkStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			tables: v.tables.into_iter().map(Into::into).collect(),
+			target: v.target.into(),
+			batch: v.batch,
+			retry: v.retry,
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineSinkStatement> for DefineSinkStatement {
+	fn from(v: crate::expr::statements::define::DefineSinkStatement) -> Self {
+		DefineSinkStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			tables: v.tables.into_iter().map(Into::into).collect(),
+			target: v.target.into(),
+			batch: v.batch,
+			retry: v.retry,
+			comment: v.comment.into(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum SinkTarget {
+	Webhook {
+		url: Expr,
+		headers: Option<Expr>,
+	},
+	File(Expr),
+	Stdout,
+	Custom(Expr),
+}
+
+impl ToSql for SinkTarget {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		match self {
+			Self::Webhook {
+				url,
+				headers,
+			} => {
+				write_sql!(f, sql_fmt, "WEBHOOK {}", CoverStmts(url));
+				if let Some(headers) = headers {
+					write_sql!(f, sql_fmt, " HEADERS {}", CoverStmts(headers));
+				}
+			}
+			Self::File(path) => write_sql!(f, sql_fmt, "FILE {}", CoverStmts(path)),
+			Self::Stdout => write_sql!(f, sql_fmt, "STDOUT"),
+			Self::Custom(name) => write_sql!(f, sql_fmt, "CUSTOM {}", CoverStmts(name)),
+		}
+	}
+}
+
+impl From<SinkTarget> for crate::expr::statements::define::SinkTarget {
+	fn from(v: SinkTarget) -> Self {
+		match v {
+			SinkTarget::Webhook {
+				url,
+				headers,
+			} => Self::Webhook {
+				url: url.into(),
+				headers: headers.map(Into::into),
+			},
+			SinkTarget::File(path) => Self::File(path.into()),
+			SinkTarget::Stdout => Self::Stdout,
+			SinkTarget::Custom(name) => Self::Custom(name.into()),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::SinkTarget> for SinkTarget {
+	fn from(v: crate::expr::statements::define::SinkTarget) -> Self {
+		match v {
+			crate::expr::statements::define::SinkTarget::Webhook {
+				url,
+				headers,
+			} => Self::Webhook {
+				url: url.into(),
+				headers: headers.map(Into::into),
+			},
+			crate::expr::statements::define::SinkTarget::File(path) => Self::File(path.into()),
+			crate::expr::statements::define::SinkTarget::Stdout => Self::Stdout,
+			crate::expr::statements::define::SinkTarget::Custom(name) => Self::Custom(name.into()),
+		}
+	}
+}
@@ -4,6 +4,7 @@ use super::DefineKind;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::{Expr, Literal, Permissions, TableType, View};
+use crate::types::PublicDuration;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -18,6 +19,7 @@ pub(crate) struct DefineTableStatement {
 	pub changefeed: Option<ChangeFeed>,
 	pub comment: Expr,
 	pub table_type: TableType,
+	pub ttl: Option<PublicDuration>,
 }
 
 impl Default for DefineTableStatement {
@@ -33,6 +35,7 @@ impl Default for DefineTableStatement {
 			changefeed: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
+			ttl: None,
 		}
 	}
 }
@@ -92,6 +95,9 @@ impl ToSql for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {}", v);
 		}
+		if let Some(ref v) = self.ttl {
+			write_sql!(f, sql_fmt, " TTL {}", v);
+		}
 		if sql_fmt.is_pretty() {
 			f.push('\n');
 			let inner_fmt = sql_fmt.increment();
@@ -116,6 +122,7 @@ impl From<DefineTableStatement> for crate::expr::statements::DefineTableStatemen
 			changefeed: v.changefeed.map(Into::into),
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
+			ttl: v.ttl.map(Into::into),
 		}
 	}
 }
@@ -134,6 +141,7 @@ impl From<crate::expr::statements::DefineTableStatement> for DefineTableStatemen
 			changefeed: v.changefeed.map(Into::into),
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
+			ttl: v.ttl.map(Into::into),
 		}
 	}
 }
@@ -0,0 +1,45 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::CoverStmts;
+use crate::sql::Expr;
+
+/// A SHOW HISTORY statement for listing the stored versions of a record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HistoryStatement {
+	pub what: Expr,
+	pub since: Option<Expr>,
+	pub until: Option<Expr>,
+}
+
+impl ToSql for HistoryStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "SHOW HISTORY FOR {}", CoverStmts(&self.what));
+		if let Some(ref v) = self.since {
+			write_sql!(f, fmt, " SINCE {}", CoverStmts(v));
+		}
+		if let Some(ref v) = self.until {
+			write_sql!(f, fmt, " UNTIL {}", CoverStmts(v));
+		}
+	}
+}
+
+impl From<HistoryStatement> for crate::expr::statements::HistoryStatement {
+	fn from(v: HistoryStatement) -> Self {
+		crate::expr::statements::HistoryStatement {
+			what: v.what.into(),
+			since: v.since.map(Into::into),
+			until: v.until.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::statements::HistoryStatement> for HistoryStatement {
+	fn from(v: crate::expr::statements::HistoryStatement) -> Self {
+		HistoryStatement {
+			what: v.what.into(),
+			since: v.since.map(Into::into),
+			until: v.until.map(Into::into),
+		}
+	}
+}
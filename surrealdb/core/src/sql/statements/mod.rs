@@ -1,9 +1,11 @@
 pub(crate) mod access;
+pub(crate) mod ack;
 pub(crate) mod alter;
 pub(crate) mod create;
 pub(crate) mod define;
 pub(crate) mod delete;
 pub(crate) mod foreach;
+pub(crate) mod history;
 pub(crate) mod ifelse;
 pub(crate) mod info;
 pub(crate) mod insert;
@@ -14,6 +16,7 @@ pub(crate) mod output;
 pub(crate) mod rebuild;
 pub(crate) mod relate;
 pub(crate) mod remove;
+pub(crate) mod run;
 pub(crate) mod select;
 pub(crate) mod set;
 pub(crate) mod show;
@@ -23,6 +26,7 @@ pub(crate) mod upsert;
 pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
+pub(crate) use self::ack::AckStatement;
 pub(crate) use self::alter::{AlterStatement, AlterTableStatement};
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
@@ -32,6 +36,7 @@ pub(crate) use self::define::{
 };
 pub(crate) use self::delete::DeleteStatement;
 pub(crate) use self::foreach::ForeachStatement;
+pub(crate) use self::history::HistoryStatement;
 pub(crate) use self::ifelse::IfelseStatement;
 pub(crate) use self::info::InfoStatement;
 pub(crate) use self::insert::InsertStatement;
@@ -46,6 +51,7 @@ pub(crate) use self::remove::{
 	RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
 	RemoveStatement, RemoveTableStatement, RemoveUserStatement,
 };
+pub(crate) use self::run::RunStatement;
 pub(crate) use self::select::SelectStatement;
 pub(crate) use self::set::SetStatement;
 pub(crate) use self::show::ShowStatement;
@@ -6,12 +6,14 @@ use crate::fmt::{EscapeKwFreeIdent, EscapeKwIdent};
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum RebuildStatement {
 	Index(RebuildIndexStatement),
+	Key(RebuildKeyStatement),
 }
 
 impl ToSql for RebuildStatement {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
 		match self {
 			Self::Index(v) => v.fmt_sql(f, fmt),
+			Self::Key(v) => v.fmt_sql(f, fmt),
 		}
 	}
 }
@@ -20,6 +22,7 @@ impl From<RebuildStatement> for crate::expr::statements::rebuild::RebuildStateme
 	fn from(v: RebuildStatement) -> Self {
 		match v {
 			RebuildStatement::Index(v) => Self::Index(v.into()),
+			RebuildStatement::Key(v) => Self::Key(v.into()),
 		}
 	}
 }
@@ -28,6 +31,7 @@ impl From<crate::expr::statements::rebuild::RebuildStatement> for RebuildStateme
 	fn from(v: crate::expr::statements::rebuild::RebuildStatement) -> Self {
 		match v {
 			crate::expr::statements::rebuild::RebuildStatement::Index(v) => Self::Index(v.into()),
+			crate::expr::statements::rebuild::RebuildStatement::Key(v) => Self::Key(v.into()),
 		}
 	}
 }
@@ -81,3 +85,38 @@ impl From<crate::expr::statements::rebuild::RebuildIndexStatement> for RebuildIn
 		}
 	}
 }
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RebuildKeyStatement {
+	pub name: String,
+	pub if_exists: bool,
+}
+
+impl ToSql for RebuildKeyStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "REBUILD KEY");
+		if self.if_exists {
+			write_sql!(f, fmt, " IF EXISTS");
+		}
+		write_sql!(f, fmt, " {}", EscapeKwIdent(&self.name, &["IF"]));
+	}
+}
+
+impl From<RebuildKeyStatement> for crate::expr::statements::rebuild::RebuildKeyStatement {
+	fn from(v: RebuildKeyStatement) -> Self {
+		Self {
+			name: v.name,
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::rebuild::RebuildKeyStatement> for RebuildKeyStatement {
+	fn from(v: crate::expr::statements::rebuild::RebuildKeyStatement) -> Self {
+		Self {
+			name: v.name,
+			if_exists: v.if_exists,
+		}
+	}
+}
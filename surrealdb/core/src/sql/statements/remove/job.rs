@@ -0,0 +1,48 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct RemoveJobStatement {
+	pub name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveJobStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl ToSql for RemoveJobStatement {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "REMOVE JOB");
+		if self.if_exists {
+			write_sql!(f, sql_fmt, " IF EXISTS");
+		}
+		write_sql!(f, sql_fmt, " {}", CoverStmts(&self.name));
+	}
+}
+
+impl From<RemoveJobStatement> for crate::expr::statements::remove::RemoveJobStatement {
+	fn from(v: RemoveJobStatement) -> Self {
+		crate::expr::statements::remove::RemoveJobStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::remove::RemoveJobStatement> for RemoveJobStatement {
+	fn from(v: crate::expr::statements::remove::RemoveJobStatement) -> Self {
+		RemoveJobStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
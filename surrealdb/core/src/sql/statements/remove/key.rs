@@ -0,0 +1,48 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct RemoveKeyStatement {
+	pub name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveKeyStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl ToSql for RemoveKeyStatement {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "REMOVE KEY");
+		if self.if_exists {
+			write_sql!(f, sql_fmt, " IF EXISTS");
+		}
+		write_sql!(f, sql_fmt, " {}", CoverStmts(&self.name));
+	}
+}
+
+impl From<RemoveKeyStatement> for crate::expr::statements::remove::RemoveKeyStatement {
+	fn from(v: RemoveKeyStatement) -> Self {
+		crate::expr::statements::remove::RemoveKeyStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::remove::RemoveKeyStatement> for RemoveKeyStatement {
+	fn from(v: crate::expr::statements::remove::RemoveKeyStatement) -> Self {
+		RemoveKeyStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
@@ -4,16 +4,20 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod job;
+mod key;
 mod model;
 mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,16 +26,20 @@ pub(crate) use analyzer::RemoveAnalyzerStatement;
 pub(crate) use api::RemoveApiStatement;
 pub(crate) use bucket::RemoveBucketStatement;
 pub(crate) use config::{RemoveConfigKind, RemoveConfigStatement};
+pub(crate) use consumer::RemoveConsumerStatement;
 pub(crate) use database::RemoveDatabaseStatement;
 pub(crate) use event::RemoveEventStatement;
 pub(crate) use field::RemoveFieldStatement;
 pub(crate) use function::RemoveFunctionStatement;
 pub(crate) use index::RemoveIndexStatement;
+pub(crate) use job::RemoveJobStatement;
+pub(crate) use key::RemoveKeyStatement;
 pub(crate) use model::RemoveModelStatement;
 pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
 pub(crate) use sequence::RemoveSequenceStatement;
+pub(crate) use sink::RemoveSinkStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
 
@@ -56,6 +64,10 @@ pub(crate) enum RemoveStatement {
 	Sequence(RemoveSequenceStatement),
 	Module(RemoveModuleStatement),
 	Config(RemoveConfigStatement),
+	Job(RemoveJobStatement),
+	Sink(RemoveSinkStatement),
+	Consumer(RemoveConsumerStatement),
+	Key(RemoveKeyStatement),
 }
 
 impl ToSql for RemoveStatement {
@@ -78,6 +90,10 @@ impl ToSql for RemoveStatement {
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
 			Self::Config(v) => v.fmt_sql(f, fmt),
+			Self::Job(v) => v.fmt_sql(f, fmt),
+			Self::Sink(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
+			Self::Key(v) => v.fmt_sql(f, fmt),
 		}
 	}
 }
@@ -102,6 +118,10 @@ impl From<RemoveStatement> for crate::expr::statements::RemoveStatement {
 			RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
 			RemoveStatement::Module(v) => Self::Module(v.into()),
 			RemoveStatement::Config(v) => Self::Config(v.into()),
+			RemoveStatement::Job(v) => Self::Job(v.into()),
+			RemoveStatement::Sink(v) => Self::Sink(v.into()),
+			RemoveStatement::Consumer(v) => Self::Consumer(v.into()),
+			RemoveStatement::Key(v) => Self::Key(v.into()),
 		}
 	}
 }
@@ -126,6 +146,10 @@ impl From<crate::expr::statements::RemoveStatement> for RemoveStatement {
 			crate::expr::statements::RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
 			crate::expr::statements::RemoveStatement::Module(v) => Self::Module(v.into()),
 			crate::expr::statements::RemoveStatement::Config(v) => Self::Config(v.into()),
+			crate::expr::statements::RemoveStatement::Job(v) => Self::Job(v.into()),
+			crate::expr::statements::RemoveStatement::Sink(v) => Self::Sink(v.into()),
+			crate::expr::statements::RemoveStatement::Consumer(v) => Self::Consumer(v.into()),
+			crate::expr::statements::RemoveStatement::Key(v) => Self::Key(v.into()),
 		}
 	}
 }
@@ -0,0 +1,61 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::EscapeKwFreeIdent;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum RunStatement {
+	Job(RunJobStatement),
+}
+
+impl ToSql for RunStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Job(v) => v.fmt_sql(f, fmt),
+		}
+	}
+}
+
+impl From<RunStatement> for crate::expr::statements::run::RunStatement {
+	fn from(v: RunStatement) -> Self {
+		match v {
+			RunStatement::Job(v) => Self::Job(v.into()),
+		}
+	}
+}
+
+impl From<crate::expr::statements::run::RunStatement> for RunStatement {
+	fn from(v: crate::expr::statements::run::RunStatement) -> Self {
+		match v {
+			crate::expr::statements::run::RunStatement::Job(v) => Self::Job(v.into()),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RunJobStatement {
+	pub name: String,
+}
+
+impl ToSql for RunJobStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "RUN JOB {}", EscapeKwFreeIdent(&self.name));
+	}
+}
+
+impl From<RunJobStatement> for crate::expr::statements::run::RunJobStatement {
+	fn from(v: RunJobStatement) -> Self {
+		Self {
+			name: v.name,
+		}
+	}
+}
+
+impl From<crate::expr::statements::run::RunJobStatement> for RunJobStatement {
+	fn from(v: crate::expr::statements::run::RunJobStatement) -> Self {
+		Self {
+			name: v.name,
+		}
+	}
+}
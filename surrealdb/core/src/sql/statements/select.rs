@@ -5,6 +5,7 @@ use crate::sql::order::Ordering;
 use crate::sql::{
 	Cond, Explain, Expr, Fetchs, Fields, Groups, Limit, Literal, Splits, Start, With,
 };
+use crate::types::PublicDuration;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SelectStatement {
@@ -26,6 +27,8 @@ pub struct SelectStatement {
 	pub timeout: Expr,
 	pub explain: Option<Explain>,
 	pub tempfiles: bool,
+	/// How long the result of the statement is kept in the result cache
+	pub cache: Option<PublicDuration>,
 }
 
 impl ToSql for SelectStatement {
@@ -69,6 +72,9 @@ impl ToSql for SelectStatement {
 		if !matches!(self.timeout, Expr::Literal(Literal::None)) {
 			write_sql!(f, fmt, " TIMEOUT {}", CoverStmts(&self.timeout));
 		}
+		if let Some(ref v) = self.cache {
+			write_sql!(f, fmt, " CACHE {v}");
+		}
 		if let Some(ref v) = self.explain {
 			write_sql!(f, fmt, " {v}");
 		}
@@ -94,6 +100,7 @@ impl From<SelectStatement> for crate::expr::statements::SelectStatement {
 			timeout: v.timeout.into(),
 			explain: v.explain.map(Into::into),
 			tempfiles: v.tempfiles,
+			cache: v.cache.map(Into::into),
 		}
 	}
 }
@@ -117,6 +124,7 @@ impl From<crate::expr::statements::SelectStatement> for SelectStatement {
 			timeout: v.timeout.into(),
 			explain: v.explain.map(Into::into),
 			tempfiles: v.tempfiles,
+			cache: v.cache.map(Into::into),
 		}
 	}
 }
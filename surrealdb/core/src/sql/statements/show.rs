@@ -31,25 +31,42 @@ impl From<crate::expr::statements::show::ShowSince> for ShowSince {
 	}
 }
 
-/// A SHOW CHANGES statement for displaying changes made to a table or database.
+/// A SHOW CHANGES statement for displaying changes made to a table or database,
+/// or a SHOW AUDIT statement for displaying the audit log.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ShowStatement {
 	pub table: Option<String>,
+	/// The consumer from whose acknowledged offset the changes are read, in
+	/// which case `table` and `since` are unused.
+	pub consumer: Option<String>,
+	/// Whether the audit log is shown, in which case `table` and `consumer`
+	/// are unused, and `since` must be a timestamp, or a versionstamp of 0
+	/// when the whole audit log is shown.
+	pub audit: bool,
 	pub since: ShowSince,
 	pub limit: Option<u32>,
 }
 
 impl ToSql for ShowStatement {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
-		write_sql!(f, fmt, "SHOW CHANGES FOR");
-		match self.table {
-			Some(ref v) => write_sql!(f, fmt, " TABLE {}", EscapeKwFreeIdent(v)),
-			None => write_sql!(f, fmt, " DATABASE"),
-		}
-		match self.since {
-			ShowSince::Timestamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
-			ShowSince::Versionstamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
+		if self.audit {
+			write_sql!(f, fmt, "SHOW AUDIT");
+			if let ShowSince::Timestamp(ref v) = self.since {
+				write_sql!(f, fmt, " SINCE {}", v);
+			}
+		} else if let Some(ref v) = self.consumer {
+			write_sql!(f, fmt, "SHOW CHANGES FOR CONSUMER {}", EscapeKwFreeIdent(v));
+		} else {
+			write_sql!(f, fmt, "SHOW CHANGES FOR");
+			match self.table {
+				Some(ref v) => write_sql!(f, fmt, " TABLE {}", EscapeKwFreeIdent(v)),
+				None => write_sql!(f, fmt, " DATABASE"),
+			}
+			match self.since {
+				ShowSince::Timestamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
+				ShowSince::Versionstamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
+			}
 		}
 		if let Some(ref v) = self.limit {
 			write_sql!(f, fmt, " LIMIT {}", v)
@@ -61,6 +78,8 @@ impl From<ShowStatement> for crate::expr::statements::ShowStatement {
 	fn from(v: ShowStatement) -> Self {
 		crate::expr::statements::ShowStatement {
 			table: v.table.map(TableName::new),
+			consumer: v.consumer,
+			audit: v.audit,
 			since: v.since.into(),
 			limit: v.limit,
 		}
@@ -71,6 +90,8 @@ impl From<crate::expr::statements::ShowStatement> for ShowStatement {
 	fn from(v: crate::expr::statements::ShowStatement) -> Self {
 		ShowStatement {
 			table: v.table.map(TableName::into_string),
+			consumer: v.consumer,
+			audit: v.audit,
 			since: v.since.into(),
 			limit: v.limit,
 		}
@@ -6,14 +6,15 @@ use crate::sql::literal::ObjectEntry;
 use crate::sql::statements::access::{AccessStatementGrant, Subject};
 use crate::sql::statements::alter::AlterKind;
 use crate::sql::statements::live::LiveFields;
-use crate::sql::statements::rebuild::RebuildIndexStatement;
+use crate::sql::statements::rebuild::{RebuildIndexStatement, RebuildKeyStatement};
+use crate::sql::statements::run::{RunJobStatement, RunStatement};
 use crate::sql::statements::show::ShowSince;
 use crate::sql::statements::{
-	AccessStatement, AlterStatement, AlterTableStatement, CreateStatement, DefineStatement,
-	DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement,
-	InsertStatement, OptionStatement, OutputStatement, RebuildStatement, RelateStatement,
-	RemoveStatement, RemoveTableStatement, SelectStatement, SetStatement, ShowStatement,
-	SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AckStatement, AlterStatement, AlterTableStatement, CreateStatement,
+	DefineStatement, DefineTableStatement, DeleteStatement, ForeachStatement, HistoryStatement,
+	IfelseStatement, InfoStatement, InsertStatement, OptionStatement, OutputStatement,
+	RebuildStatement, RelateStatement, RemoveStatement, RemoveTableStatement, SelectStatement,
+	SetStatement, ShowStatement, SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, Data, Expr, Fields, Function, FunctionCall, Idiom,
@@ -133,7 +134,8 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
             Expr::Literal(Literal::Integer(3)),
         ]))))], close: None })), "IF true {\n\t1;\n\t2;\n} ELSE IF false { 3 }", "IF true {\n\n\t1;\n\t2;\n} ELSE IF false { 3 }")]
 // Expression: Select
-#[case::expr_select(Expr::Select(Box::new(SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("user".to_string())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), explain: None, tempfiles: false })), "SELECT * FROM user", "SELECT * FROM user")]
+#[case::expr_select(Expr::Select(Box::new(SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("user".to_string())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), explain: None, tempfiles: false, cache: None })), "SELECT * FROM user", "SELECT * FROM user")]
+#[case::expr_select_cache(Expr::Select(Box::new(SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("user".to_string())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), explain: None, tempfiles: false, cache: Some(PublicDuration::from_secs(30)) })), "SELECT * FROM user CACHE 30s", "SELECT * FROM user CACHE 30s")]
 // Expression: Create
 #[case::expr_create(Expr::Create(Box::new(CreateStatement { only: false, what: vec![Expr::Table("user".to_string())], data: None, output: None, timeout: Expr::Literal(Literal::None) })), "CREATE user", "CREATE user")]
 // Expression: Update
@@ -158,10 +160,15 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 )]
 // Expression: Rebuild
 #[case::expr_rebuild(Expr::Rebuild(Box::new(RebuildStatement::Index(RebuildIndexStatement { name: "idx".to_string(), what: "user".to_string(), if_exists: false, concurrently: false }))), "REBUILD INDEX idx ON user", "REBUILD INDEX idx ON user")]
+#[case::expr_rebuild_key(Expr::Rebuild(Box::new(RebuildStatement::Key(RebuildKeyStatement { name: "pii".to_string(), if_exists: false }))), "REBUILD KEY pii", "REBUILD KEY pii")]
+// Expression: Run
+#[case::expr_run(Expr::Run(Box::new(RunStatement::Job(RunJobStatement { name: "cleanup".to_string() }))), "RUN JOB cleanup", "RUN JOB cleanup")]
+// Expression: History
+#[case::expr_history(Expr::History(Box::new(HistoryStatement { what: Expr::Literal(Literal::RecordId(RecordIdLit { table: "person".to_string(), key: RecordIdKeyLit::String("tobie".to_string()) })), since: Some(Expr::Param(Param::new("since".to_string()))), until: None })), "SHOW HISTORY FOR person:tobie SINCE $since", "SHOW HISTORY FOR person:tobie SINCE $since")]
 // Expression: Upsert
 #[case::expr_upsert(Expr::Upsert(Box::new(UpsertStatement { only: false, what: vec![Expr::Table("user".to_string())], with: None, data: None, cond: None, output: None, timeout: Expr::Literal(Literal::None), explain: None })), "UPSERT user", "UPSERT user")]
 // Expression: Alter
-#[case::expr_alter(Expr::Alter(Box::new(AlterStatement::Table(AlterTableStatement { name: "user".to_string(), if_exists: false, schemafull: AlterKind::None, permissions: None, changefeed: AlterKind::None, comment: AlterKind::None, kind: None, compact: false }))), "ALTER TABLE user", "ALTER TABLE user")]
+#[case::expr_alter(Expr::Alter(Box::new(AlterStatement::Table(AlterTableStatement { name: "user".to_string(), if_exists: false, schemafull: AlterKind::None, permissions: None, changefeed: AlterKind::None, comment: AlterKind::None, ttl: AlterKind::None, kind: None, compact: false }))), "ALTER TABLE user", "ALTER TABLE user")]
 // Expression: Info
 #[case::expr_info(
 	Expr::Info(Box::new(InfoStatement::Root(false))),
@@ -239,7 +246,8 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
             version: Expr::Literal(Literal::None),
             timeout: Expr::Literal(Literal::None),
             explain: None,
-            tempfiles: false
+            tempfiles: false,
+            cache: None
         })),
         block: Block(vec![
             Expr::IfElse(Box::new(IfelseStatement {
@@ -327,7 +335,10 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 #[case::top_level_live_diff(TopLevelExpr::Live(Box::new(LiveStatement { fields: LiveFields::Diff, what: Expr::Table("user".to_string()), cond: None, fetch: None })), "LIVE SELECT DIFF FROM user", "LIVE SELECT DIFF FROM user")]
 #[case::top_level_option(TopLevelExpr::Option(OptionStatement { name: "IMPORT".to_string(), what: true }), "OPTION IMPORT", "OPTION IMPORT")]
 #[case::top_level_use(TopLevelExpr::Use(UseStatement::NsDb(Expr::Idiom(Idiom::field("ns".to_string())), Expr::Idiom(Idiom::field("db".to_string())))), "USE NS ns DB db", "USE NS ns DB db")]
-#[case::top_level_show(TopLevelExpr::Show(ShowStatement { table: Some("user".to_string()), since: ShowSince::Versionstamp(123), limit: Some(10) }), "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10", "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10")]
+#[case::top_level_show(TopLevelExpr::Show(ShowStatement { table: Some("user".to_string()), consumer: None, audit: false, since: ShowSince::Versionstamp(123), limit: Some(10) }), "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10", "SHOW CHANGES FOR TABLE user SINCE 123 LIMIT 10")]
+#[case::top_level_show_consumer(TopLevelExpr::Show(ShowStatement { table: None, consumer: Some("orders".to_string()), audit: false, since: ShowSince::Versionstamp(0), limit: Some(10) }), "SHOW CHANGES FOR CONSUMER orders LIMIT 10", "SHOW CHANGES FOR CONSUMER orders LIMIT 10")]
+#[case::top_level_show_audit(TopLevelExpr::Show(ShowStatement { table: None, consumer: None, audit: true, since: ShowSince::Versionstamp(0), limit: Some(10) }), "SHOW AUDIT LIMIT 10", "SHOW AUDIT LIMIT 10")]
+#[case::top_level_ack(TopLevelExpr::Ack(AckStatement { consumer: "orders".to_string(), versionstamp: Expr::Literal(Literal::Integer(65536)) }), "ACK CONSUMER orders AT 65536", "ACK CONSUMER orders AT 65536")]
 #[case::top_level_expr(TopLevelExpr::Expr(Expr::Literal(Literal::Integer(1))), "1", "1")]
 fn test_to_sql(#[case] v: impl ToSql, #[case] expected: &str, #[case] expected_pretty: &str) {
 	assert_eq!(v.to_sql(), expected);
@@ -0,0 +1,224 @@
+//! Module aggregates for the streaming executor.
+//!
+//! A module function which is exported as an aggregate is computed
+//! incrementally for each group of a `GROUP BY` query. The values of a group
+//! are buffered, and folded into the state of the aggregate in batches, so
+//! that the module is not instantiated for every value.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::Result;
+use surrealism_runtime::controller::{Controller, Runtime};
+use surrealism_runtime::limits::SurrealismLimits;
+
+use crate::catalog::ModuleLimits;
+use crate::ctx::FrozenContext;
+use crate::err::Error;
+use crate::exec::function::{Accumulator, AggregateFunction, Signature};
+use crate::exec::physical_expr::function::validate_return;
+use crate::expr::Kind;
+use crate::expr::module::{self, accumulate, limit_error, spawn_thread};
+use crate::surrealism::host::AggregateHost;
+use crate::types::PublicValue;
+use crate::val::Value;
+
+/// The number of values which are buffered before they are folded into the state.
+const BATCH_SIZE: usize = 1024;
+
+/// An aggregate function which is exported by a module.
+#[derive(Clone)]
+pub(crate) struct ModuleAggregate {
+	inner: Arc<ModuleAggregateInner>,
+}
+
+struct ModuleAggregateInner {
+	/// The full name of the function, such as `mod::stats::mean`
+	name: String,
+	/// The name of the function within the module
+	sub: Option<String>,
+	ctx: FrozenContext,
+	runtime: Arc<Runtime>,
+	limits: SurrealismLimits,
+	/// The kind of the values which are aggregated
+	input: Kind,
+	returns: Option<Kind>,
+}
+
+impl ModuleAggregate {
+	pub(crate) fn new(
+		name: String,
+		sub: Option<String>,
+		ctx: &FrozenContext,
+		runtime: Arc<Runtime>,
+		limits: &ModuleLimits,
+		signature: module::Signature,
+	) -> Self {
+		// The signature of an aggregate expects an array of the values to aggregate
+		let input = match signature.args.into_iter().next() {
+			Some(Kind::Array(kind, _)) => *kind,
+			_ => Kind::Any,
+		};
+		Self {
+			inner: Arc::new(ModuleAggregateInner {
+				name,
+				sub,
+				ctx: ctx.clone(),
+				runtime,
+				limits: SurrealismLimits::from(limits),
+				input,
+				returns: signature.returns,
+			}),
+		}
+	}
+
+	/// Folds values into a state.
+	fn accumulate(
+		&self,
+		state: Option<PublicValue>,
+		values: Vec<Value>,
+		extra: &[Value],
+	) -> Result<PublicValue> {
+		let inner = &self.inner;
+		let values = to_public(values)?;
+		let extra = to_public(extra.to_vec())?;
+		spawn_thread(move || async move {
+			let mut controller = inner.controller().await?;
+			accumulate(&mut controller, inner.sub.clone(), state, values, &extra).await
+		})
+		.map_err(|e| limit_error(&inner.ctx, e))
+	}
+
+	/// Folds values into a state, and computes the result of the aggregate.
+	fn finalize(
+		&self,
+		state: Option<PublicValue>,
+		values: Vec<Value>,
+		extra: &[Value],
+	) -> Result<Value> {
+		let inner = &self.inner;
+		let values = to_public(values)?;
+		let extra = to_public(extra.to_vec())?;
+		let result = spawn_thread(move || async move {
+			let mut controller = inner.controller().await?;
+			let sub = inner.sub.clone();
+			let state = accumulate(&mut controller, sub.clone(), state, values, &extra).await?;
+			controller.aggregate_finalize(sub, state).await
+		})
+		.map_err(|e| limit_error(&inner.ctx, e))?;
+		validate_return(&inner.name, inner.returns.as_ref(), result.into())
+	}
+}
+
+impl ModuleAggregateInner {
+	async fn controller(&self) -> Result<Controller> {
+		let host = Box::new(AggregateHost::new(&self.ctx));
+		self.runtime.new_controller_with_limits(host, &self.limits).await
+	}
+}
+
+impl fmt::Debug for ModuleAggregate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ModuleAggregate").field("name", &self.inner.name).finish()
+	}
+}
+
+impl AggregateFunction for ModuleAggregate {
+	/// Module aggregates are not registered by name, so they share a name.
+	fn name(&self) -> &'static str {
+		"mod"
+	}
+
+	fn create_accumulator(&self) -> Box<dyn Accumulator> {
+		self.create_accumulator_with_args(&[])
+	}
+
+	fn create_accumulator_with_args(&self, args: &[Value]) -> Box<dyn Accumulator> {
+		Box::new(ModuleAccumulator {
+			aggregate: self.clone(),
+			state: None,
+			pending: Vec::new(),
+			extra: args.to_vec(),
+		})
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().returns(self.inner.returns.clone().unwrap_or(Kind::Any))
+	}
+}
+
+/// Accumulates the values of a group for a module aggregate. The values are
+/// buffered until there are enough of them to be folded into the state.
+#[derive(Clone, Debug)]
+struct ModuleAccumulator {
+	aggregate: ModuleAggregate,
+	/// The state of the values which have been folded so far
+	state: Option<PublicValue>,
+	/// The values which have not been folded into the state yet
+	pending: Vec<Value>,
+	/// The additional arguments, which are passed with every value
+	extra: Vec<Value>,
+}
+
+impl ModuleAccumulator {
+	fn flush(&mut self) -> Result<()> {
+		let pending = std::mem::take(&mut self.pending);
+		let state = self.aggregate.accumulate(self.state.take(), pending, &self.extra)?;
+		self.state = Some(state);
+		Ok(())
+	}
+}
+
+impl Accumulator for ModuleAccumulator {
+	fn update(&mut self, value: Value) -> Result<()> {
+		let inner = &self.aggregate.inner;
+		let value =
+			value.coerce_to_kind(&inner.input).map_err(|e| Error::InvalidFunctionArguments {
+				name: inner.name.clone(),
+				message: format!("Failed to coerce argument: {e}"),
+			})?;
+		self.pending.push(value);
+		if self.pending.len() >= BATCH_SIZE {
+			self.flush()?;
+		}
+		Ok(())
+	}
+
+	fn merge(&mut self, other: Box<dyn Accumulator>) -> Result<()> {
+		let Some(other) = other.as_any().downcast_ref::<ModuleAccumulator>() else {
+			anyhow::bail!("Cannot merge a module aggregate with a different aggregate");
+		};
+		// The state of a module aggregate is opaque, so only values which
+		// have not been folded yet can be taken over from another accumulator
+		if other.state.is_some() {
+			anyhow::bail!("Cannot merge the partial state of a module aggregate");
+		}
+		self.pending.extend(other.pending.iter().cloned());
+		if self.pending.len() >= BATCH_SIZE {
+			self.flush()?;
+		}
+		Ok(())
+	}
+
+	fn finalize(&self) -> Result<Value> {
+		self.aggregate.finalize(self.state.clone(), self.pending.clone(), &self.extra)
+	}
+
+	fn reset(&mut self) {
+		self.state = None;
+		self.pending.clear();
+	}
+
+	fn clone_box(&self) -> Box<dyn Accumulator> {
+		Box::new(self.clone())
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+fn to_public(values: Vec<Value>) -> Result<Vec<PublicValue>> {
+	values.into_iter().map(|x| x.try_into()).collect()
+}
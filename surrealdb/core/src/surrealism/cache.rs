@@ -1,5 +1,5 @@
 use std::future::Future;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use anyhow::{Error, Result};
@@ -8,8 +8,10 @@ use surrealism_runtime::controller::Runtime;
 
 use crate::catalog::{DatabaseId, NamespaceId};
 
+/// The compiled runtimes of the modules. A module has a separate runtime for
+/// the definitions which limit its fuel, as only those meter the fuel.
 pub struct SurrealismCache {
-	cache: quick_cache::sync::Cache<SurrealismCacheKey, SurrealismCacheValue, Weight>,
+	cache: quick_cache::sync::Cache<(SurrealismCacheKey, bool), SurrealismCacheValue, Weight>,
 }
 
 impl SurrealismCache {
@@ -24,13 +26,16 @@ impl SurrealismCache {
 	}
 
 	pub fn remove(&self, lookup: &SurrealismCacheLookup) {
-		self.cache.remove(lookup);
+		self.cache.remove(&MeteredLookup(lookup, false));
+		self.cache.remove(&MeteredLookup(lookup, true));
 	}
 
-	/// Gets the runtime from the cache or computes it if not present using the provided function
+	/// Gets the runtime from the cache or computes it if not present using the provided function.
+	/// The `metered` runtime of a module is the one used by the definitions which limit its fuel.
 	pub async fn get_or_insert_with<F, Fut>(
 		&self,
 		lookup: &SurrealismCacheLookup<'_>,
+		metered: bool,
 		compute: F,
 	) -> Result<Arc<Runtime>>
 	where
@@ -38,7 +43,8 @@ impl SurrealismCache {
 		Fut: Future<Output = Result<Arc<Runtime>>>,
 	{
 		// This match is only needed to avoid allocating for the key in the fast path
-		let value = match self.cache.get(lookup) {
+		let lookup = MeteredLookup(lookup, metered);
+		let value = match self.cache.get(&lookup) {
 			Some(runtime) => runtime,
 			None => {
 				let compute = async {
@@ -48,7 +54,8 @@ impl SurrealismCache {
 					Result::<_, Error>::Ok(value)
 				};
 
-				self.cache.get_or_insert_async(&lookup.to_key(), compute).await?
+				let key = (lookup.0.to_key(), metered);
+				self.cache.get_or_insert_async(&key, compute).await?
 			}
 		};
 
@@ -105,6 +112,23 @@ impl Equivalent<SurrealismCacheKey> for SurrealismCacheLookup<'_> {
 	}
 }
 
+/// Looks up the runtime of a module, with or without fuel metering.
+struct MeteredLookup<'a, 'b>(&'a SurrealismCacheLookup<'b>, bool);
+
+// Hashes the same as the `(SurrealismCacheKey, bool)` key which it looks up
+impl Hash for MeteredLookup<'_, '_> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.hash(state);
+		self.1.hash(state);
+	}
+}
+
+impl Equivalent<(SurrealismCacheKey, bool)> for MeteredLookup<'_, '_> {
+	fn equivalent(&self, key: &(SurrealismCacheKey, bool)) -> bool {
+		self.0.equivalent(&key.0) && self.1 == key.1
+	}
+}
+
 #[derive(Clone)]
 pub struct SurrealismCacheValue {
 	pub(crate) runtime: Arc<Runtime>,
@@ -113,8 +137,8 @@ pub struct SurrealismCacheValue {
 #[derive(Clone)]
 pub(crate) struct Weight;
 
-impl Weighter<SurrealismCacheKey, SurrealismCacheValue> for Weight {
-	fn weight(&self, _key: &SurrealismCacheKey, _val: &SurrealismCacheValue) -> u64 {
+impl Weighter<(SurrealismCacheKey, bool), SurrealismCacheValue> for Weight {
+	fn weight(&self, _key: &(SurrealismCacheKey, bool), _val: &SurrealismCacheValue) -> u64 {
 		// For the moment all entries have the
 		// same weight, and can be evicted when
 		// necessary. In the future we will
@@ -5,6 +5,7 @@ use surrealism_runtime::config::SurrealismConfig;
 use surrealism_runtime::host::InvocationContext;
 use surrealism_runtime::kv::KVStore;
 
+use crate::ctx::cancellation::Cancellation;
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -18,6 +19,7 @@ pub(crate) struct Host {
 	pub(crate) ctx: FrozenContext,
 	pub(crate) opt: Options,
 	pub(crate) doc: Option<CursorDoc>,
+	pub(crate) cancellation: Cancellation,
 }
 
 impl Host {
@@ -27,6 +29,7 @@ impl Host {
 			ctx: ctx.clone(),
 			opt: opt.clone(),
 			doc: doc.cloned(),
+			cancellation: ctx.cancellation(),
 		}
 	}
 }
@@ -84,6 +87,10 @@ impl InvocationContext for Host {
 		todo!()
 	}
 
+	fn cancelled(&self) -> bool {
+		self.cancellation.is_done()
+	}
+
 	fn stdout(&mut self, _output: &str) -> Result<()> {
 		todo!()
 	}
@@ -134,3 +141,56 @@ impl InvocationContext for SignatureHost {
 		todo!()
 	}
 }
+
+/// The host of an aggregate which is computed incrementally by the streaming
+/// executor. The aggregate has no document or options to run queries with, but
+/// it is interrupted when the query is cancelled.
+pub(crate) struct AggregateHost {
+	cancellation: Cancellation,
+}
+
+impl AggregateHost {
+	pub(crate) fn new(ctx: &FrozenContext) -> Self {
+		Self {
+			cancellation: ctx.cancellation(),
+		}
+	}
+}
+
+#[async_trait]
+impl InvocationContext for AggregateHost {
+	async fn sql(
+		&mut self,
+		_config: &SurrealismConfig,
+		_query: String,
+		_vars: PublicObject,
+	) -> Result<PublicValue> {
+		bail!("SQL is not supported in aggregate functions")
+	}
+
+	async fn run(
+		&mut self,
+		_config: &SurrealismConfig,
+		_fnc: String,
+		_version: Option<String>,
+		_args: Vec<PublicValue>,
+	) -> Result<PublicValue> {
+		bail!("Run is not supported in aggregate functions")
+	}
+
+	fn kv(&mut self) -> Result<&dyn KVStore> {
+		bail!("KV is not supported in aggregate functions")
+	}
+
+	fn cancelled(&self) -> bool {
+		self.cancellation.is_done()
+	}
+
+	fn stdout(&mut self, _output: &str) -> Result<()> {
+		Ok(())
+	}
+
+	fn stderr(&mut self, _output: &str) -> Result<()> {
+		Ok(())
+	}
+}
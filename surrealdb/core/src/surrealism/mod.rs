@@ -1,2 +1,3 @@
+pub(crate) mod aggregate;
 pub(crate) mod cache;
 pub(crate) mod host;
@@ -62,7 +62,10 @@ pub fn could_be_reserved(s: &str) -> bool {
 pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map! {
 	// Keywords
 	UniCase::ascii("ACCESS") => TokenKind::Keyword(Keyword::Access),
+	UniCase::ascii("ACCUMULATE") => TokenKind::Keyword(Keyword::Accumulate),
+	UniCase::ascii("ACK") => TokenKind::Keyword(Keyword::Ack),
 	UniCase::ascii("AFTER") => TokenKind::Keyword(Keyword::After),
+	UniCase::ascii("AGGREGATE") => TokenKind::Keyword(Keyword::Aggregate),
 	UniCase::ascii("ALGORITHM") => TokenKind::Keyword(Keyword::Algorithm),
 	UniCase::ascii("ALL") => TokenKind::Keyword(Keyword::All),
 	UniCase::ascii("ALLINSIDE") => TokenKind::Keyword(Keyword::AllInside),
@@ -80,6 +83,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ASSERT") => TokenKind::Keyword(Keyword::Assert),
 	UniCase::ascii("ASYNC") => TokenKind::Keyword(Keyword::Async),
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
+	UniCase::ascii("AUDIT") => TokenKind::Keyword(Keyword::Audit),
 	UniCase::ascii("AUTHENTICATE") => TokenKind::Keyword(Keyword::Authenticate),
 	UniCase::ascii("AUTO") => TokenKind::Keyword(Keyword::Auto),
 	UniCase::ascii("BACKEND") => TokenKind::Keyword(Keyword::Backend),
@@ -92,6 +96,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("BREAK") => TokenKind::Keyword(Keyword::Break),
 	UniCase::ascii("BUCKET") => TokenKind::Keyword(Keyword::Bucket),
 	UniCase::ascii("BY") => TokenKind::Keyword(Keyword::By),
+	UniCase::ascii("CACHE") => TokenKind::Keyword(Keyword::Cache),
 	UniCase::ascii("CAMEL") => TokenKind::Keyword(Keyword::Camel),
 	UniCase::ascii("CANCEL") => TokenKind::Keyword(Keyword::Cancel),
 	UniCase::ascii("CAPACITY") => TokenKind::Keyword(Keyword::Capacity),
@@ -107,6 +112,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("COMPUTED") => TokenKind::Keyword(Keyword::Computed),
 	UniCase::ascii("CONCURRENTLY") => TokenKind::Keyword(Keyword::Concurrently),
 	UniCase::ascii("CONFIG") => TokenKind::Keyword(Keyword::Config),
+	UniCase::ascii("CONSUMER") => TokenKind::Keyword(Keyword::Consumer),
 	UniCase::ascii("CONTAINS") => TokenKind::Keyword(Keyword::Contains),
 	UniCase::ascii("CONTAINSALL") => TokenKind::Keyword(Keyword::ContainsAll),
 	UniCase::ascii("CONTAINSANY") => TokenKind::Keyword(Keyword::ContainsAny),
@@ -116,6 +122,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("CONTINUE") => TokenKind::Keyword(Keyword::Continue),
 	UniCase::ascii("COUNT") => TokenKind::Keyword(Keyword::Count),
 	UniCase::ascii("CREATE") => TokenKind::Keyword(Keyword::Create),
+	UniCase::ascii("CUSTOM") => TokenKind::Keyword(Keyword::Custom),
 	UniCase::ascii("DATABASE") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DB") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DEFAULT") => TokenKind::Keyword(Keyword::Default),
@@ -132,6 +139,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("EDGENGRAM") => TokenKind::Keyword(Keyword::Edgengram),
 	UniCase::ascii("EFC") => TokenKind::Keyword(Keyword::Efc),
 	UniCase::ascii("ELSE") => TokenKind::Keyword(Keyword::Else),
+	UniCase::ascii("ENCRYPTED") => TokenKind::Keyword(Keyword::Encrypted),
 	UniCase::ascii("END") => TokenKind::Keyword(Keyword::End),
 	UniCase::ascii("ENFORCED") => TokenKind::Keyword(Keyword::Enforced),
 	UniCase::ascii("EVENT") => TokenKind::Keyword(Keyword::Event),
@@ -146,11 +154,13 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("FIELD") => TokenKind::Keyword(Keyword::Field),
 	UniCase::ascii("FIELDS") => TokenKind::Keyword(Keyword::Fields),
 	UniCase::ascii("FILTERS") => TokenKind::Keyword(Keyword::Filters),
+	UniCase::ascii("FINAL") => TokenKind::Keyword(Keyword::Final),
 	UniCase::ascii("FLEX") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEXI") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEXIBLE") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FOR") => TokenKind::Keyword(Keyword::For),
 	UniCase::ascii("FROM") => TokenKind::Keyword(Keyword::From),
+	UniCase::ascii("FUEL") => TokenKind::Keyword(Keyword::Fuel),
 	UniCase::ascii("FULL") => TokenKind::Keyword(Keyword::Full),
 	UniCase::ascii("FULLTEXT") => TokenKind::Keyword(Keyword::Fulltext),
 	UniCase::ascii("FUNCTION") => TokenKind::Keyword(Keyword::Function),
@@ -161,12 +171,14 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("HASHED_VECTOR") => TokenKind::Keyword(Keyword::HashedVector),
 	UniCase::ascii("HEADERS") => TokenKind::Keyword(Keyword::Headers),
 	UniCase::ascii("HIGHLIGHTS") => TokenKind::Keyword(Keyword::Highlights),
+	UniCase::ascii("HISTORY") => TokenKind::Keyword(Keyword::History),
 	UniCase::ascii("HNSW") => TokenKind::Keyword(Keyword::Hnsw),
 	UniCase::ascii("IF") => TokenKind::Keyword(Keyword::If),
 	UniCase::ascii("IGNORE") => TokenKind::Keyword(Keyword::Ignore),
 	UniCase::ascii("IN") => TokenKind::Keyword(Keyword::In),
 	UniCase::ascii("INCLUDE") => TokenKind::Keyword(Keyword::Include),
 	UniCase::ascii("INDEX") => TokenKind::Keyword(Keyword::Index),
+	UniCase::ascii("INDEXING") => TokenKind::Keyword(Keyword::Indexing),
 	UniCase::ascii("INFO") => TokenKind::Keyword(Keyword::Info),
 	UniCase::ascii("INSERT") => TokenKind::Keyword(Keyword::Insert),
 	UniCase::ascii("INSIDE") => TokenKind::Keyword(Keyword::Inside),
@@ -174,6 +186,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("INTO") => TokenKind::Keyword(Keyword::Into),
 	UniCase::ascii("IS") => TokenKind::Keyword(Keyword::Is),
 	UniCase::ascii("ISSUER") => TokenKind::Keyword(Keyword::Issuer),
+	UniCase::ascii("JOB") => TokenKind::Keyword(Keyword::Job),
 	UniCase::ascii("JWKS") => TokenKind::Keyword(Keyword::Jwks),
 	UniCase::ascii("JWT") => TokenKind::Keyword(Keyword::Jwt),
 	UniCase::ascii("KEEP_PRUNED_CONNECTIONS") => TokenKind::Keyword(Keyword::KeepPrunedConnections),
@@ -181,7 +194,9 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("KILL") => TokenKind::Keyword(Keyword::Kill),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
+	UniCase::ascii("LEVEL") => TokenKind::Keyword(Keyword::Level),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
+	UniCase::ascii("LIMITS") => TokenKind::Keyword(Keyword::Limits),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
 	UniCase::ascii("LM") => TokenKind::Keyword(Keyword::Lm),
 	UniCase::ascii("LOWERCASE") => TokenKind::Keyword(Keyword::Lowercase),
@@ -189,6 +204,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("M0") => TokenKind::Keyword(Keyword::M0),
 	UniCase::ascii("MAPPER") => TokenKind::Keyword(Keyword::Mapper),
 	UniCase::ascii("MAXDEPTH") => TokenKind::Keyword(Keyword::MaxDepth),
+	UniCase::ascii("MEMORY") => TokenKind::Keyword(Keyword::Memory),
 	UniCase::ascii("MERGE") => TokenKind::Keyword(Keyword::Merge),
 	UniCase::ascii("MIDDLEWARE") => TokenKind::Keyword(Keyword::Middleware),
 	UniCase::ascii("ML") => TokenKind::Keyword(Keyword::ML),
@@ -209,6 +225,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("OMIT") => TokenKind::Keyword(Keyword::Omit),
 	UniCase::ascii("ON") => TokenKind::Keyword(Keyword::On),
 	UniCase::ascii("ONLY") => TokenKind::Keyword(Keyword::Only),
+	UniCase::ascii("OPERATORS") => TokenKind::Keyword(Keyword::Operators),
 	UniCase::ascii("OPTION") => TokenKind::Keyword(Keyword::Option),
 	UniCase::ascii("OR") => TokenKind::Keyword(Keyword::OrKw),
 	UniCase::ascii("ORDER") => TokenKind::Keyword(Keyword::Order),
@@ -227,6 +244,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("PREPARE") => TokenKind::Keyword(Keyword::Prepare),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
 	UniCase::ascii("PURGE") => TokenKind::Keyword(Keyword::Purge),
+	UniCase::ascii("QUERYING") => TokenKind::Keyword(Keyword::Querying),
 	UniCase::ascii("RANGE") => TokenKind::Keyword(Keyword::Range),
 	UniCase::ascii("READONLY") => TokenKind::Keyword(Keyword::Readonly),
 	UniCase::ascii("REBUILD") => TokenKind::Keyword(Keyword::Rebuild),
@@ -237,13 +255,16 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("RELATION") => TokenKind::Keyword(Keyword::Relation),
 	UniCase::ascii("REMOVE") => TokenKind::Keyword(Keyword::Remove),
 	UniCase::ascii("REPLACE") => TokenKind::Keyword(Keyword::Replace),
+	UniCase::ascii("RETAIN") => TokenKind::Keyword(Keyword::Retain),
 	UniCase::ascii("RETRY") => TokenKind::Keyword(Keyword::Retry),
 	UniCase::ascii("RETURN") => TokenKind::Keyword(Keyword::Return),
 	UniCase::ascii("REVOKE") => TokenKind::Keyword(Keyword::Revoke),
 	UniCase::ascii("REVOKED") => TokenKind::Keyword(Keyword::Revoked),
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
+	UniCase::ascii("RUN") => TokenKind::Keyword(Keyword::Run),
 	UniCase::ascii("SC") => TokenKind::Keyword(Keyword::Scope),
+	UniCase::ascii("SCHEDULE") => TokenKind::Keyword(Keyword::Schedule),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -255,12 +276,18 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SIGNIN") => TokenKind::Keyword(Keyword::Signin),
 	UniCase::ascii("SIGNUP") => TokenKind::Keyword(Keyword::Signup),
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
+	UniCase::ascii("SINK") => TokenKind::Keyword(Keyword::Sink),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
+	UniCase::ascii("SPATIAL") => TokenKind::Keyword(Keyword::Spatial),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
+	UniCase::ascii("STATE") => TokenKind::Keyword(Keyword::State),
+	UniCase::ascii("STDOUT") => TokenKind::Keyword(Keyword::Stdout),
+	UniCase::ascii("STOPWORDS") => TokenKind::Keyword(Keyword::Stopwords),
 	UniCase::ascii("STRICT") => TokenKind::Keyword(Keyword::Strict),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
+	UniCase::ascii("SYNONYMS") => TokenKind::Keyword(Keyword::Synonyms),
 	UniCase::ascii("SYSTEM") => TokenKind::Keyword(Keyword::System),
 	UniCase::ascii("TABLE") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TABLES") => TokenKind::Keyword(Keyword::Tables),
@@ -276,9 +303,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
+	UniCase::ascii("TTL") => TokenKind::Keyword(Keyword::Ttl),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
 	UniCase::ascii("UNSET") => TokenKind::Keyword(Keyword::Unset),
+	UniCase::ascii("UNTIL") => TokenKind::Keyword(Keyword::Until),
 	UniCase::ascii("UPDATE") => TokenKind::Keyword(Keyword::Update),
 	UniCase::ascii("UPPERCASE") => TokenKind::Keyword(Keyword::Uppercase),
 	UniCase::ascii("UPSERT") => TokenKind::Keyword(Keyword::Upsert),
@@ -289,6 +318,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("VALUES") => TokenKind::Keyword(Keyword::Values),
 	UniCase::ascii("VERSION") => TokenKind::Keyword(Keyword::Version),
 	UniCase::ascii("VS") => TokenKind::Keyword(Keyword::Vs),
+	UniCase::ascii("WEBHOOK") => TokenKind::Keyword(Keyword::Webhook),
 	UniCase::ascii("WHEN") => TokenKind::Keyword(Keyword::When),
 	UniCase::ascii("WHERE") => TokenKind::Keyword(Keyword::Where),
 	UniCase::ascii("WITH") => TokenKind::Keyword(Keyword::With),
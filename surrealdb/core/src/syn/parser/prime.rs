@@ -211,6 +211,11 @@ impl Parser<'_> {
 				let stmt = self.parse_select_stmt(stk).await?;
 				Expr::Select(Box::new(stmt))
 			}
+			t!("SHOW") if self.peek1().kind == t!("HISTORY") => {
+				self.pop_peek();
+				let stmt = self.parse_history_stmt(stk).await?;
+				Expr::History(Box::new(stmt))
+			}
 			t!("CREATE") => {
 				self.pop_peek();
 				let stmt = self.parse_create_stmt(stk).await?;
@@ -260,6 +265,11 @@ impl Parser<'_> {
 				let stmt = self.parse_rebuild_stmt()?;
 				Expr::Rebuild(Box::new(stmt))
 			}
+			t!("RUN") if self.peek1().kind == t!("JOB") => {
+				self.pop_peek();
+				let stmt = self.parse_run_stmt()?;
+				Expr::Run(Box::new(stmt))
+			}
 			t!("ALTER") => {
 				self.pop_peek();
 				let stmt = self.parse_alter_stmt(stk).await?;
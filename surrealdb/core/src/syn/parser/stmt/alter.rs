@@ -2,7 +2,6 @@ use reblessive::Stk;
 
 use crate::catalog::{ApiMethod, EventDefinition, EventKind};
 use crate::sql::TableType;
-use crate::sql::filter::Filter;
 use crate::sql::statements::alter::field::AlterDefault;
 use crate::sql::statements::alter::{
 	AlterAccessStatement, AlterAnalyzerStatement, AlterApiClause, AlterApiStatement,
@@ -18,6 +17,7 @@ use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, expected_whitespace, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
 use crate::syn::token::{Token, TokenKind, t};
+use crate::types::PublicDuration;
 
 impl Parser<'_> {
 	pub(crate) async fn parse_alter_stmt(&mut self, stk: &mut Stk) -> ParseResult<AlterStatement> {
@@ -144,8 +144,11 @@ impl Parser<'_> {
 							self.pop_peek();
 							res.changefeed = AlterKind::Drop;
 						}
+						_ if self.eat(t!("TTL")) => {
+							res.ttl = AlterKind::Drop;
+						}
 						_ => {
-							unexpected!(self, peek, "`COMMENT` or `CHANGEFEED`")
+							unexpected!(self, peek, "`COMMENT`, `CHANGEFEED`, or `TTL`")
 						}
 					}
 				}
@@ -192,6 +195,9 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = AlterKind::Set(self.parse_changefeed()?)
 				}
+				_ if self.eat(t!("TTL")) => {
+					res.ttl = AlterKind::Set(self.next_token_value::<PublicDuration>()?);
+				}
 				_ => break,
 			}
 		}
@@ -671,41 +677,7 @@ impl Parser<'_> {
 					self.pop_peek();
 					let mut filters = Vec::new();
 					loop {
-						let next = self.next();
-						match next.kind {
-							t!("ASCII") => filters.push(Filter::Ascii),
-							t!("LOWERCASE") => filters.push(Filter::Lowercase),
-							t!("UPPERCASE") => filters.push(Filter::Uppercase),
-							t!("EDGENGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::EdgeNgram(a, b));
-							}
-							t!("NGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Ngram(a, b));
-							}
-							t!("SNOWBALL") => {
-								let open_span = expected!(self, t!("(")).span;
-								let language = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Snowball(language));
-							}
-							t!("MAPPER") => {
-								let open_span = expected!(self, t!("(")).span;
-								let path: String = self.parse_string_lit()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Mapper(path));
-							}
-							_ => unexpected!(self, next, "a filter"),
-						}
+						filters.push(self.parse_filter()?);
 						if !self.eat(t!(",")) {
 							break;
 						}
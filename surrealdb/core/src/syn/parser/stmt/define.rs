@@ -4,8 +4,8 @@ use crate::catalog::{ApiMethod, EventDefinition, EventKind};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::base::Base;
-use crate::sql::filter::Filter;
-use crate::sql::index::{Distance, HnswParams, VectorType};
+use crate::sql::filter::{Filter, FilterStage, Stopwords, Synonyms};
+use crate::sql::index::{Distance, HnswParams, SpatialParams, VectorType};
 use crate::sql::kind::KindLiteral;
 use crate::sql::statements::define::config::api::{ApiConfig, Middleware};
 use crate::sql::statements::define::config::defaults::DefaultConfig;
@@ -13,11 +13,12 @@ use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig
 use crate::sql::statements::define::config::{ConfigInner, graphql};
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
-	ApiAction, DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement,
-	DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
+	ApiAction, DefineAccessStatement, DefineAggregateStatement, DefineAnalyzerStatement,
+	DefineApiStatement, DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement,
+	DefineDatabaseStatement, DefineDefault, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineJobStatement, DefineKeyStatement,
 	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	DefineSinkStatement, DefineStatement, DefineTableStatement, DefineUserStatement, SinkTarget,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -25,20 +26,21 @@ use crate::sql::{
 	Scoring, TableType, access_type, table_type,
 };
 #[cfg(feature = "surrealism")]
-use crate::sql::{ModuleExecutable, SiloExecutable, SurrealismExecutable};
+use crate::sql::{ModuleExecutable, ModuleLimits, SiloExecutable, SurrealismExecutable};
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
 use crate::syn::token::{Token, TokenKind, t};
-use crate::types::PublicDuration;
-#[cfg(feature = "surrealism")]
-use crate::types::PublicFile;
+use crate::types::{PublicDuration, PublicFile};
 
 impl Parser<'_> {
 	pub(crate) async fn parse_define_stmt(
 		&mut self,
 		stk: &mut Stk,
 	) -> ParseResult<DefineStatement> {
+		if self.eat(t!("AGGREGATE")) {
+			return self.parse_define_aggregate(stk).await.map(DefineStatement::Aggregate);
+		}
 		let next = self.next();
 		match next.kind {
 			t!("NAMESPACE") => {
@@ -65,6 +67,14 @@ impl Parser<'_> {
 			t!("BUCKET") => self.parse_define_bucket(stk, next).await.map(DefineStatement::Bucket),
 			t!("SEQUENCE") => self.parse_define_sequence(stk).await.map(DefineStatement::Sequence),
 			t!("MODULE") => self.parse_define_module(stk).await.map(DefineStatement::Module),
+			t!("JOB") => {
+				stk.run(|stk| self.parse_define_job(stk, next)).await.map(DefineStatement::Job)
+			}
+			t!("SINK") => {
+				stk.run(|stk| self.parse_define_sink(stk, next)).await.map(DefineStatement::Sink)
+			}
+			t!("CONSUMER") => self.parse_define_consumer(stk).await.map(DefineStatement::Consumer),
+			t!("KEY") => self.parse_define_key(stk).await.map(DefineStatement::Key),
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
 	}
@@ -151,40 +161,83 @@ impl Parser<'_> {
 		} else {
 			DefineKind::Default
 		};
-		let name = self.parse_custom_function_name()?;
-		let token = expected!(self, t!("(")).span;
-		let mut args = Vec::new();
-		loop {
-			if self.eat(t!(")")) {
-				break;
-			}
+		let (name, args, returns) = self.parse_custom_function_signature(stk).await?;
 
-			let param = self.next_token_value::<Param>()?.into_string();
-			expected!(self, t!(":"));
-			let kind = stk.run(|ctx| self.parse_inner_kind(ctx)).await?;
+		let next = expected!(self, t!("{")).span;
+		let block = self.parse_block(stk, next).await?;
 
-			args.push((param, kind));
+		let mut res = DefineFunctionStatement {
+			name,
+			args,
+			block,
+			kind,
+			returns,
+			comment: Expr::Literal(Literal::None),
+			permissions: Permission::default(),
+		};
 
-			if !self.eat(t!(",")) {
-				self.expect_closing_delimiter(t!(")"), token)?;
-				break;
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				t!("PERMISSIONS") => {
+					self.pop_peek();
+					res.permissions = stk.run(|ctx| self.parse_permission_value(ctx)).await?;
+				}
+				_ => break,
 			}
 		}
-		let returns = if self.eat(t!("->")) {
-			Some(stk.run(|ctx| self.parse_inner_kind(ctx)).await?)
+
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_aggregate(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineAggregateStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
 		} else {
-			None
+			DefineKind::Default
 		};
+		let (name, args, returns) = self.parse_custom_function_signature(stk).await?;
+
+		if !self.eat(t!("STATE")) {
+			unexpected!(self, self.peek(), "the STATE clause of the aggregate");
+		}
+		let state = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
 
+		if !self.eat(t!("ACCUMULATE")) {
+			unexpected!(self, self.peek(), "the ACCUMULATE clause of the aggregate");
+		}
 		let next = expected!(self, t!("{")).span;
-		let block = self.parse_block(stk, next).await?;
+		let accumulate = self.parse_block(stk, next).await?;
 
-		let mut res = DefineFunctionStatement {
+		expected!(self, t!("MERGE"));
+		let next = expected!(self, t!("{")).span;
+		let merge = self.parse_block(stk, next).await?;
+
+		if !self.eat(t!("FINAL")) {
+			unexpected!(self, self.peek(), "the FINAL clause of the aggregate");
+		}
+		let next = expected!(self, t!("{")).span;
+		let finalize = self.parse_block(stk, next).await?;
+
+		let mut res = DefineAggregateStatement {
+			kind,
 			name,
 			args,
-			block,
-			kind,
 			returns,
+			state,
+			accumulate,
+			merge,
+			finalize,
 			comment: Expr::Literal(Literal::None),
 			permissions: Permission::default(),
 		};
@@ -206,6 +259,38 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the name, arguments and return kind of a `fn::` function.
+	async fn parse_custom_function_signature(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<(String, Vec<(String, Kind)>, Option<Kind>)> {
+		let name = self.parse_custom_function_name()?;
+		let token = expected!(self, t!("(")).span;
+		let mut args = Vec::new();
+		loop {
+			if self.eat(t!(")")) {
+				break;
+			}
+
+			let param = self.next_token_value::<Param>()?.into_string();
+			expected!(self, t!(":"));
+			let kind = stk.run(|ctx| self.parse_inner_kind(ctx)).await?;
+
+			args.push((param, kind));
+
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!(")"), token)?;
+				break;
+			}
+		}
+		let returns = if self.eat(t!("->")) {
+			Some(stk.run(|ctx| self.parse_inner_kind(ctx)).await?)
+		} else {
+			None
+		};
+		Ok((name, args, returns))
+	}
+
 	#[cfg(not(feature = "surrealism"))]
 	pub(crate) async fn parse_define_module(
 		&mut self,
@@ -281,10 +366,17 @@ impl Parser<'_> {
 			}
 		};
 
+		let limits = if self.eat(t!("LIMITS")) {
+			self.parse_module_limits()?
+		} else {
+			ModuleLimits::default()
+		};
+
 		let mut definition = DefineModuleStatement {
 			kind,
 			name,
 			executable,
+			limits,
 			comment: Expr::Literal(Literal::None),
 			permissions: Permission::default(),
 		};
@@ -307,6 +399,26 @@ impl Parser<'_> {
 		Ok(definition)
 	}
 
+	#[cfg(feature = "surrealism")]
+	fn parse_module_limits(&mut self) -> ParseResult<ModuleLimits> {
+		let mut limits = ModuleLimits::default();
+		loop {
+			if self.eat(t!("FUEL")) {
+				limits.fuel = Some(self.next_token_value::<u64>()?);
+			} else if self.eat(t!("MEMORY")) {
+				limits.memory = Some(self.next_token_value::<u64>()?);
+			} else if self.eat(t!("TIMEOUT")) {
+				limits.timeout = Some(self.next_token_value::<PublicDuration>()?);
+			} else {
+				break;
+			}
+		}
+		if limits.is_empty() {
+			unexpected!(self, self.peek(), "`FUEL`, `MEMORY` or `TIMEOUT`");
+		}
+		Ok(limits)
+	}
+
 	pub(crate) async fn parse_define_user(
 		&mut self,
 		stk: &mut Stk,
@@ -708,6 +820,9 @@ impl Parser<'_> {
 						_ => unexpected!(self, peek, "`SELECT`"),
 					}
 				}
+				_ if self.eat(t!("TTL")) => {
+					res.ttl = Some(self.next_token_value::<PublicDuration>()?);
+				}
 				_ => break,
 			}
 		}
@@ -1002,6 +1117,11 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.computed = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
 				}
+				_ if self.eat(t!("ENCRYPTED")) => {
+					expected!(self, t!("WITH"));
+					expected!(self, t!("KEY"));
+					res.encrypted = Some(self.parse_ident()?);
+				}
 				_ => break,
 			}
 		}
@@ -1182,6 +1302,21 @@ impl Parser<'_> {
 						use_hashed_vector,
 					});
 				}
+				t!("SPATIAL") => {
+					self.pop_peek();
+					let level = if self.eat(t!("LEVEL")) {
+						let level: u8 = self.next_token_value()?;
+						if level == 0 || level > 24 {
+							bail!("Invalid value for SPATIAL parameter `LEVEL`", @self.last_span() => "`LEVEL` must be between 1 and 24")
+						}
+						level
+					} else {
+						12u8
+					};
+					res.index = Index::Spatial(SpatialParams {
+						level,
+					});
+				}
 				t!("CONCURRENTLY") => {
 					self.pop_peek();
 					res.concurrently = true;
@@ -1199,7 +1334,7 @@ impl Parser<'_> {
 					bail!("Cannot create a count index with fields", @field_span);
 				}
 			}
-			(field_span, Index::FullText(_) | Index::Hnsw(_)) => {
+			(field_span, Index::FullText(_) | Index::Hnsw(_) | Index::Spatial(_)) => {
 				if res.cols.len() != 1 {
 					if let Some(field_span) = field_span {
 						bail!("Expected one column, found {}", res.cols.len(), @field_span);
@@ -1238,6 +1373,7 @@ impl Parser<'_> {
 			function: None,
 			tokenizers: None,
 			filters: None,
+			operators: false,
 			comment: Expr::Literal(Literal::None),
 
 			kind,
@@ -1248,47 +1384,7 @@ impl Parser<'_> {
 					self.pop_peek();
 					let mut filters = Vec::new();
 					loop {
-						let next = self.next();
-						match next.kind {
-							t!("ASCII") => {
-								filters.push(Filter::Ascii);
-							}
-							t!("LOWERCASE") => {
-								filters.push(Filter::Lowercase);
-							}
-							t!("UPPERCASE") => {
-								filters.push(Filter::Uppercase);
-							}
-							t!("EDGENGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::EdgeNgram(a, b));
-							}
-							t!("NGRAM") => {
-								let open_span = expected!(self, t!("(")).span;
-								let a = self.next_token_value()?;
-								expected!(self, t!(","));
-								let b = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Ngram(a, b));
-							}
-							t!("SNOWBALL") => {
-								let open_span = expected!(self, t!("(")).span;
-								let language = self.next_token_value()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Snowball(language))
-							}
-							t!("MAPPER") => {
-								let open_span = expected!(self, t!("(")).span;
-								let path: String = self.parse_string_lit()?;
-								self.expect_closing_delimiter(t!(")"), open_span)?;
-								filters.push(Filter::Mapper(path))
-							}
-							_ => unexpected!(self, next, "a filter"),
-						}
+						filters.push(self.parse_filter()?);
 						if !self.eat(t!(",")) {
 							break;
 						}
@@ -1328,6 +1424,10 @@ impl Parser<'_> {
 					}
 					res.function = Some(ident);
 				}
+				t!("OPERATORS") => {
+					self.pop_peek();
+					res.operators = true;
+				}
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
@@ -1338,6 +1438,95 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses a single filter of an analyzer.
+	pub(crate) fn parse_filter(&mut self) -> ParseResult<Filter> {
+		if self.eat(t!("STOPWORDS")) {
+			let open_span = expected!(self, t!("(")).span;
+			let words = if self.peek_kind() == t!("[") {
+				Stopwords::Custom(self.parse_string_list()?)
+			} else {
+				Stopwords::Language(self.next_token_value()?)
+			};
+			self.expect_closing_delimiter(t!(")"), open_span)?;
+			return Ok(Filter::Stopwords(words));
+		}
+		if self.eat(t!("SYNONYMS")) {
+			let open_span = expected!(self, t!("(")).span;
+			let synonyms = if self.peek_kind() == t!("[") {
+				Synonyms::Rules(self.parse_string_list()?)
+			} else {
+				Synonyms::File(self.next_token_value::<PublicFile>()?)
+			};
+			let stage = if self.eat(t!(",")) {
+				if self.eat(t!("INDEXING")) {
+					Some(FilterStage::Indexing)
+				} else if self.eat(t!("QUERYING")) {
+					Some(FilterStage::Querying)
+				} else {
+					let next = self.next();
+					unexpected!(self, next, "`INDEXING` or `QUERYING`")
+				}
+			} else {
+				None
+			};
+			self.expect_closing_delimiter(t!(")"), open_span)?;
+			return Ok(Filter::Synonyms(synonyms, stage));
+		}
+		let next = self.next();
+		let filter = match next.kind {
+			t!("ASCII") => Filter::Ascii,
+			t!("LOWERCASE") => Filter::Lowercase,
+			t!("UPPERCASE") => Filter::Uppercase,
+			t!("EDGENGRAM") => {
+				let open_span = expected!(self, t!("(")).span;
+				let a = self.next_token_value()?;
+				expected!(self, t!(","));
+				let b = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::EdgeNgram(a, b)
+			}
+			t!("NGRAM") => {
+				let open_span = expected!(self, t!("(")).span;
+				let a = self.next_token_value()?;
+				expected!(self, t!(","));
+				let b = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::Ngram(a, b)
+			}
+			t!("SNOWBALL") => {
+				let open_span = expected!(self, t!("(")).span;
+				let language = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::Snowball(language)
+			}
+			t!("MAPPER") => {
+				let open_span = expected!(self, t!("(")).span;
+				let path: String = self.parse_string_lit()?;
+				self.expect_closing_delimiter(t!(")"), open_span)?;
+				Filter::Mapper(path)
+			}
+			_ => unexpected!(self, next, "a filter"),
+		};
+		Ok(filter)
+	}
+
+	/// Parses an array of string literals, like `["a", "b"]`.
+	fn parse_string_list(&mut self) -> ParseResult<Vec<String>> {
+		let open_span = expected!(self, t!("[")).span;
+		let mut res = Vec::new();
+		loop {
+			if self.eat(t!("]")) {
+				break;
+			}
+			res.push(self.parse_string_lit()?);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), open_span)?;
+				break;
+			}
+		}
+		Ok(res)
+	}
+
 	pub(crate) async fn parse_define_bucket(
 		&mut self,
 		stk: &mut Stk,
@@ -1426,6 +1615,222 @@ impl Parser<'_> {
 		})
 	}
 
+	pub(crate) async fn parse_define_job(
+		&mut self,
+		stk: &mut Stk,
+		token: Token,
+	) -> ParseResult<DefineJobStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		let mut res = DefineJobStatement {
+			name,
+			kind,
+			..Default::default()
+		};
+		let mut schedule = false;
+		let mut then = false;
+		loop {
+			match self.peek_kind() {
+				t!("SCHEDULE") => {
+					self.pop_peek();
+					res.schedule = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+					schedule = true;
+				}
+				t!("THEN") => {
+					self.pop_peek();
+					res.then = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+					then = true;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+		if !schedule {
+			bail!("Expected a `SCHEDULE` clause", @token.span => "`DEFINE JOB` requires a schedule");
+		}
+		if !then {
+			bail!("Expected a `THEN` clause", @token.span => "`DEFINE JOB` requires a `THEN` statement");
+		}
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_sink(
+		&mut self,
+		stk: &mut Stk,
+		token: Token,
+	) -> ParseResult<DefineSinkStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		let mut res = DefineSinkStatement {
+			name,
+			kind,
+			..Default::default()
+		};
+		let mut target = false;
+		loop {
+			match self.peek_kind() {
+				t!("ON") => {
+					self.pop_peek();
+					self.eat(t!("TABLE"));
+					res.tables = vec![stk.run(|ctx| self.parse_expr_table(ctx)).await?];
+					while self.eat(t!(",")) {
+						res.tables.push(stk.run(|ctx| self.parse_expr_table(ctx)).await?);
+					}
+				}
+				t!("TO") => {
+					self.pop_peek();
+					res.target = self.parse_sink_target(stk).await?;
+					target = true;
+				}
+				t!("BATCH") => {
+					self.pop_peek();
+					res.batch = self.next_token_value()?;
+				}
+				t!("RETRY") => {
+					self.pop_peek();
+					res.retry = self.next_token_value()?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+		if !target {
+			bail!("Expected a `TO` clause", @token.span => "`DEFINE SINK` requires a target");
+		}
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_consumer(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineConsumerStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		let mut res = DefineConsumerStatement {
+			name,
+			kind,
+			..Default::default()
+		};
+		loop {
+			match self.peek_kind() {
+				t!("ON") => {
+					self.pop_peek();
+					self.eat(t!("TABLE"));
+					res.table = Some(stk.run(|ctx| self.parse_expr_table(ctx)).await?);
+				}
+				t!("RETAIN") => {
+					self.pop_peek();
+					res.retain = true;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_key(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineKeyStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		let mut res = DefineKeyStatement {
+			name,
+			kind,
+			..Default::default()
+		};
+		loop {
+			match self.peek_kind() {
+				t!("VERSION") => {
+					self.pop_peek();
+					res.version = Some(self.next_token_value::<u32>()?);
+				}
+				t!("VALUE") => {
+					self.pop_peek();
+					res.value = Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ if self.eat(t!("ENCRYPTED")) => {
+					expected!(self, t!("VALUE"));
+					res.value = Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?);
+					res.encrypted = true;
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
+	async fn parse_sink_target(&mut self, stk: &mut Stk) -> ParseResult<SinkTarget> {
+		let target = if self.eat(t!("FILE")) {
+			SinkTarget::File(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+		} else if self.eat(t!("WEBHOOK")) {
+			let url = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+			let headers = if self.eat(t!("HEADERS")) {
+				Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+			} else {
+				None
+			};
+			SinkTarget::Webhook {
+				url,
+				headers,
+			}
+		} else if self.eat(t!("STDOUT")) {
+			SinkTarget::Stdout
+		} else if self.eat(t!("CUSTOM")) {
+			SinkTarget::Custom(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+		} else {
+			let next = self.next();
+			unexpected!(self, next, "a sink target")
+		};
+		Ok(target)
+	}
+
 	pub(crate) async fn parse_define_config(
 		&mut self,
 		stk: &mut Stk,
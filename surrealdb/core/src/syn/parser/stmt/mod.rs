@@ -8,10 +8,11 @@ use crate::sql::statements::access::{
 	AccessStatementShow, PurgeKind, Subject,
 };
 use crate::sql::statements::live::LiveFields;
-use crate::sql::statements::rebuild::RebuildIndexStatement;
+use crate::sql::statements::rebuild::{RebuildIndexStatement, RebuildKeyStatement};
+use crate::sql::statements::run::{RunJobStatement, RunStatement};
 use crate::sql::statements::show::ShowSince;
 use crate::sql::statements::{
-	ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
+	AckStatement, ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
 	OutputStatement, RebuildStatement, SetStatement, ShowStatement, SleepStatement, UseStatement,
 };
 use crate::sql::{AssignOperator, ExplainFormat, Expr, Literal, Param, TopLevelExpr};
@@ -109,10 +110,14 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_access(stk).await.map(|x| TopLevelExpr::Access(Box::new(x)))
 			}
-			t!("SHOW") => {
+			t!("SHOW") if self.peek1().kind != t!("HISTORY") => {
 				self.pop_peek();
 				self.parse_show_stmt().map(TopLevelExpr::Show)
 			}
+			t!("ACK") if self.peek1().kind == t!("CONSUMER") => {
+				self.pop_peek();
+				self.parse_ack_stmt(stk).await.map(TopLevelExpr::Ack)
+			}
 			_ => {
 				let covered = self.peek_kind() == t!("(");
 				let expr = self.parse_expr_start(stk).await?;
@@ -554,11 +559,42 @@ impl Parser<'_> {
 					concurrently,
 				})
 			}
+			t!("KEY") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.parse_ident()?;
+				RebuildStatement::Key(RebuildKeyStatement {
+					name,
+					if_exists,
+				})
+			}
 			_ => unexpected!(self, next, "a rebuild statement keyword"),
 		};
 		Ok(res)
 	}
 
+	/// Parsers a RUN statement.
+	///
+	/// # Parser State
+	/// Expects `RUN` to already be consumed.
+	pub(super) fn parse_run_stmt(&mut self) -> ParseResult<RunStatement> {
+		let next = self.next();
+		let res = match next.kind {
+			t!("JOB") => {
+				let name = self.parse_ident()?;
+				RunStatement::Job(RunJobStatement {
+					name,
+				})
+			}
+			_ => unexpected!(self, next, "a run statement keyword"),
+		};
+		Ok(res)
+	}
+
 	/// Parsers a RETURN statement.
 	///
 	/// # Parser State
@@ -605,6 +641,21 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `SHOW` to already be consumed.
 	pub(super) fn parse_show_stmt(&mut self) -> ParseResult<ShowStatement> {
+		if self.eat(t!("AUDIT")) {
+			let since = match self.eat(t!("SINCE")) {
+				true => ShowSince::Timestamp(self.next_token_value()?),
+				false => ShowSince::Versionstamp(0),
+			};
+			let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
+			return Ok(ShowStatement {
+				table: None,
+				consumer: None,
+				audit: true,
+				since,
+				limit,
+			});
+		}
+
 		expected!(self, t!("CHANGES"));
 		expected!(self, t!("FOR"));
 
@@ -615,7 +666,18 @@ impl Parser<'_> {
 				Some(table)
 			}
 			t!("DATABASE") => None,
-			_ => unexpected!(self, next, "`TABLE` or `DATABASE`"),
+			t!("CONSUMER") => {
+				let consumer = self.parse_ident()?;
+				let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
+				return Ok(ShowStatement {
+					table: None,
+					consumer: Some(consumer),
+					audit: false,
+					since: ShowSince::Versionstamp(0),
+					limit,
+				});
+			}
+			_ => unexpected!(self, next, "`TABLE`, `DATABASE` or `CONSUMER`"),
 		};
 
 		expected!(self, t!("SINCE"));
@@ -635,11 +697,28 @@ impl Parser<'_> {
 
 		Ok(ShowStatement {
 			table,
+			consumer: None,
+			audit: false,
 			since,
 			limit,
 		})
 	}
 
+	/// Parsers an ACK statement
+	///
+	/// # Parser State
+	/// Expects `ACK` to already be consumed.
+	pub(super) async fn parse_ack_stmt(&mut self, stk: &mut Stk) -> ParseResult<AckStatement> {
+		expected!(self, t!("CONSUMER"));
+		let consumer = self.parse_ident()?;
+		expected!(self, t!("AT"));
+		let versionstamp = stk.run(|stk| self.parse_expr_field(stk)).await?;
+		Ok(AckStatement {
+			consumer,
+			versionstamp,
+		})
+	}
+
 	/// Parsers a SLEEP statement
 	///
 	/// # Parser State
@@ -2,7 +2,8 @@ use reblessive::Stk;
 
 use crate::sql::statements::remove::{
 	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveConfigKind,
-	RemoveConfigStatement, RemoveModuleStatement, RemoveSequenceStatement,
+	RemoveConfigStatement, RemoveConsumerStatement, RemoveJobStatement, RemoveKeyStatement,
+	RemoveModuleStatement, RemoveSequenceStatement, RemoveSinkStatement,
 };
 use crate::sql::statements::{
 	RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
@@ -336,6 +337,62 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("JOB") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Job(RemoveJobStatement {
+					name,
+					if_exists,
+				})
+			}
+			t!("SINK") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Sink(RemoveSinkStatement {
+					name,
+					if_exists,
+				})
+			}
+			t!("CONSUMER") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Consumer(RemoveConsumerStatement {
+					name,
+					if_exists,
+				})
+			}
+			t!("KEY") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Key(RemoveKeyStatement {
+					name,
+					if_exists,
+				})
+			}
 			_ => unexpected!(self, next, "a remove statement keyword"),
 		};
 		Ok(res)
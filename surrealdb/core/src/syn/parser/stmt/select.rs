@@ -2,11 +2,12 @@ use reblessive::Stk;
 
 use super::parts::MissingKind;
 use crate::sql::order::{OrderList, Ordering};
-use crate::sql::statements::SelectStatement;
+use crate::sql::statements::{HistoryStatement, SelectStatement};
 use crate::sql::{Expr, Fields, Limit, Literal, Order, Split, Splits, Start};
 use crate::syn::parser::mac::expected;
 use crate::syn::parser::{ParseResult, Parser};
 use crate::syn::token::{Span, t};
+use crate::types::PublicDuration;
 
 impl Parser<'_> {
 	/// expects `select` to be eaten.
@@ -65,6 +66,11 @@ impl Parser<'_> {
 			Expr::Literal(Literal::None)
 		};
 		let timeout = self.try_parse_timeout(stk).await?;
+		let cache = if self.eat(t!("CACHE")) {
+			Some(self.next_token_value::<PublicDuration>()?)
+		} else {
+			None
+		};
 		let tempfiles = self.eat(t!("TEMPFILES"));
 		let explain = self.try_parse_explain()?;
 
@@ -84,10 +90,39 @@ impl Parser<'_> {
 			version,
 			timeout,
 			tempfiles,
+			cache,
 			explain,
 		})
 	}
 
+	/// Parsers a SHOW HISTORY statement.
+	///
+	/// # Parser State
+	/// Expects `SHOW` to already be consumed.
+	pub(crate) async fn parse_history_stmt(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<HistoryStatement> {
+		expected!(self, t!("HISTORY"));
+		expected!(self, t!("FOR"));
+		let what = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		let since = if self.eat(t!("SINCE")) {
+			Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+		} else {
+			None
+		};
+		let until = if self.eat(t!("UNTIL")) {
+			Some(stk.run(|ctx| self.parse_expr_field(ctx)).await?)
+		} else {
+			None
+		};
+		Ok(HistoryStatement {
+			what,
+			since,
+			until,
+		})
+	}
+
 	pub(crate) fn try_parse_split(
 		&mut self,
 		fields: &Fields,
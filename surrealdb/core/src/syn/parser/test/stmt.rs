@@ -11,7 +11,7 @@ use crate::sql::changefeed::ChangeFeed;
 use crate::sql::data::Assignment;
 use crate::sql::field::Selector;
 use crate::sql::filter::Filter;
-use crate::sql::index::{Distance, FullTextParams, HnswParams, VectorType};
+use crate::sql::index::{Distance, FullTextParams, HnswParams, SpatialParams, VectorType};
 use crate::sql::language::Language;
 use crate::sql::literal::ObjectEntry;
 use crate::sql::lookup::{LookupKind, LookupSubject};
@@ -21,24 +21,29 @@ use crate::sql::statements::access::{
 };
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement,
+	DefineAccessStatement, DefineAggregateStatement, DefineAnalyzerStatement,
+	DefineConsumerStatement, DefineDatabaseStatement, DefineDefault, DefineEventStatement,
+	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, DefineJobStatement,
+	DefineKeyStatement, DefineKind, DefineNamespaceStatement, DefineParamStatement,
+	DefineSinkStatement, DefineStatement, DefineTableStatement, SinkTarget,
 };
 use crate::sql::statements::live::LiveFields;
+use crate::sql::statements::rebuild::{RebuildKeyStatement, RebuildStatement};
 use crate::sql::statements::remove::{
-	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement,
+	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement, RemoveConsumerStatement,
+	RemoveJobStatement, RemoveKeyStatement, RemoveSinkStatement,
 };
+use crate::sql::statements::run::{RunJobStatement, RunStatement};
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
-	AccessStatement, CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement,
-	InfoStatement, InsertStatement, KillStatement, OptionStatement, OutputStatement,
-	RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement,
-	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
-	SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AckStatement, CreateStatement, DeleteStatement, ForeachStatement,
+	HistoryStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
+	OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
+	RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
+	RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
+	RemoveTableStatement, RemoveUserStatement, SelectStatement, UpdateStatement, UpsertStatement,
+	UseStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -258,6 +263,46 @@ fn parse_define_function() {
 	)
 }
 
+#[test]
+fn parse_define_aggregate() {
+	let res = syn::parse_with(
+		r#"DEFINE AGGREGATE fn::total($value: number) -> number STATE 0
+		ACCUMULATE { $state + $value }
+		MERGE { $state + $other }
+		FINAL { $state }
+		COMMENT 'test' PERMISSIONS FULL
+		"#
+		.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	let param = |name: &str| Expr::Param(Param::new(name.to_owned()));
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Aggregate(DefineAggregateStatement {
+			kind: DefineKind::Default,
+			name: "total".to_owned(),
+			args: vec![("value".to_owned(), Kind::Number)],
+			returns: Some(Kind::Number),
+			state: Expr::Literal(Literal::Integer(0)),
+			accumulate: Block(vec![Expr::Binary {
+				left: Box::new(param("state")),
+				op: BinaryOperator::Add,
+				right: Box::new(param("value")),
+			}]),
+			merge: Block(vec![Expr::Binary {
+				left: Box::new(param("state")),
+				op: BinaryOperator::Add,
+				right: Box::new(param("other")),
+			}]),
+			finalize: Block(vec![param("state")]),
+			comment: Expr::Literal(Literal::String("test".to_string())),
+			permissions: Permission::Full,
+		})))
+	)
+}
+
 #[test]
 fn parse_define_user() {
 	// Password.
@@ -1602,6 +1647,7 @@ fn parse_define_table() {
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
+			ttl: None,
 		})))
 	);
 }
@@ -1632,6 +1678,234 @@ fn parse_define_event() {
 	)
 }
 
+#[test]
+fn parse_define_job() {
+	let res = syn::parse_with(
+		r#"DEFINE JOB IF NOT EXISTS cleanup SCHEDULE "*/5 * * * *" THEN null COMMENT "tidy""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Job(DefineJobStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("cleanup".to_string())),
+			schedule: Expr::Literal(Literal::String("*/5 * * * *".to_string())),
+			then: Expr::Literal(Literal::Null),
+			comment: Expr::Literal(Literal::String("tidy".to_string())),
+		})))
+	);
+
+	syn::parse_with(r#"DEFINE JOB cleanup THEN null"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with(r#"DEFINE JOB cleanup SCHEDULE "@daily""#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
+
+#[cfg(feature = "surrealism")]
+#[test]
+fn parse_define_module() {
+	use surrealdb_types::ToSql;
+
+	use crate::sql::statements::define::DefineModuleStatement;
+	use crate::sql::{ModuleExecutable, ModuleLimits, SurrealismExecutable};
+	use crate::val::File;
+
+	let parse = |sql: &str| {
+		syn::parse_with_settings(
+			sql.as_bytes(),
+			ParserSettings::default_with_experimental(true),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+	};
+
+	let sql = r#"DEFINE MODULE mod::demo AS f"modules:/demo.surli" LIMITS FUEL 1000 MEMORY 65536 TIMEOUT 5s"#;
+	let res = parse(sql).unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Module(DefineModuleStatement {
+			kind: DefineKind::Default,
+			name: Some("demo".to_string()),
+			executable: ModuleExecutable::Surrealism(SurrealismExecutable(File::new(
+				"modules".to_string(),
+				"/demo.surli".to_string()
+			))),
+			limits: ModuleLimits {
+				fuel: Some(1000),
+				memory: Some(65536),
+				timeout: Some(PublicDuration::from_secs(5)),
+			},
+			comment: Expr::Literal(Literal::None),
+			permissions: Permission::default(),
+		})))
+	);
+	assert_eq!(res.to_sql(), format!("{sql} PERMISSIONS FULL"));
+
+	// The limits can be set in any order, and each one is optional
+	let res = parse(r#"DEFINE MODULE f"modules:/demo.surli" LIMITS TIMEOUT 5s FUEL 1000"#).unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Module(stmt) = *stmt else {
+		panic!("expected a DEFINE MODULE statement");
+	};
+	assert_eq!(
+		stmt.limits,
+		ModuleLimits {
+			fuel: Some(1000),
+			memory: None,
+			timeout: Some(PublicDuration::from_secs(5)),
+		}
+	);
+
+	// LIMITS must set at least one limit
+	parse(r#"DEFINE MODULE f"modules:/demo.surli" LIMITS"#).unwrap_err();
+	parse(r#"DEFINE MODULE f"modules:/demo.surli" LIMITS FUEL -1"#).unwrap_err();
+}
+
+#[test]
+fn parse_define_sink() {
+	let res = syn::parse_with(
+		r#"DEFINE SINK IF NOT EXISTS orders ON TABLE order, invoice TO WEBHOOK "https://example.com/hook" HEADERS { authorization: "token" } BATCH 50 RETRY 5 COMMENT "forward""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("orders".to_string())),
+			tables: vec![Expr::Table("order".to_string()), Expr::Table("invoice".to_string())],
+			target: SinkTarget::Webhook {
+				url: Expr::Literal(Literal::String("https://example.com/hook".to_string())),
+				headers: Some(Expr::Literal(Literal::Object(vec![ObjectEntry {
+					key: "authorization".to_string(),
+					value: Expr::Literal(Literal::String("token".to_string())),
+				}]))),
+			},
+			batch: 50,
+			retry: 5,
+			comment: Expr::Literal(Literal::String("forward".to_string())),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE SINK audit TO FILE "/tmp/audit.ndjson""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			name: Expr::Idiom(Idiom::field("audit".to_string())),
+			target: SinkTarget::File(Expr::Literal(Literal::String(
+				"/tmp/audit.ndjson".to_string()
+			))),
+			..Default::default()
+		})))
+	);
+
+	syn::parse_with(r#"DEFINE SINK audit ON TABLE order"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with(r#"DEFINE SINK audit TO KAFKA "topic""#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_consumer() {
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER IF NOT EXISTS orders ON TABLE order RETAIN COMMENT "billing""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("orders".to_string())),
+			table: Some(Expr::Table("order".to_string())),
+			retain: true,
+			comment: Expr::Literal(Literal::String("billing".to_string())),
+		})))
+	);
+
+	let res = syn::parse_with(r#"DEFINE CONSUMER audit"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			name: Expr::Idiom(Idiom::field("audit".to_string())),
+			..Default::default()
+		})))
+	);
+}
+
+#[test]
+fn parse_define_key() {
+	let res = syn::parse_with(
+		r#"DEFINE KEY IF NOT EXISTS pii VERSION 2 VALUE "c2VjcmV0" COMMENT "customer data""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Key(DefineKeyStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("pii".to_string())),
+			version: Some(2),
+			value: Some(Expr::Literal(Literal::String("c2VjcmV0".to_string()))),
+			encrypted: false,
+			comment: Expr::Literal(Literal::String("customer data".to_string())),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE KEY pii VERSION 3 ENCRYPTED VALUE "c2VjcmV0""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Key(DefineKeyStatement {
+			name: Expr::Idiom(Idiom::field("pii".to_string())),
+			version: Some(3),
+			value: Some(Expr::Literal(Literal::String("c2VjcmV0".to_string()))),
+			encrypted: true,
+			..Default::default()
+		})))
+	);
+
+	let res = syn::parse_with(r#"DEFINE KEY pii"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Key(DefineKeyStatement {
+			name: Expr::Idiom(Idiom::field("pii".to_string())),
+			..Default::default()
+		})))
+	);
+}
+
 #[test]
 fn parse_define_field() {
 	// General
@@ -1668,6 +1942,26 @@ fn parse_define_field() {
 				comment: Expr::Literal(Literal::None),
 				reference: None,
 				computed: None,
+				encrypted: None,
+			})))
+		)
+	}
+
+	// Encrypted field
+	{
+		let res = syn::parse_with(
+			r#"DEFINE FIELD email ON user TYPE string ENCRYPTED WITH KEY pii"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Expr::Define(Box::new(DefineStatement::Field(DefineFieldStatement {
+				name: Expr::Idiom(Idiom::field("email".to_string())),
+				what: Expr::Table("user".to_string()),
+				field_kind: Some(Kind::String),
+				encrypted: Some("pii".to_string()),
+				..Default::default()
 			})))
 		)
 	}
@@ -1754,11 +2048,31 @@ fn parse_define_index() {
 			concurrently: false
 		})))
 	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS location SPATIAL LEVEL 16"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".to_string()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field("location".to_string())]))],
+			index: Index::Spatial(SpatialParams {
+				level: 16
+			}),
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
 }
 
 #[test]
 fn parse_define_analyzer() {
-	let res = syn::parse_with(r#"DEFINE ANALYZER ana FILTERS ASCII, EDGENGRAM(1,2), NGRAM(3,4), LOWERCASE, SNOWBALL(NLD), UPPERCASE TOKENIZERS BLANK, CAMEL, CLASS, PUNCT FUNCTION fn::foo::bar"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
+	let res = syn::parse_with(r#"DEFINE ANALYZER ana FILTERS ASCII, EDGENGRAM(1,2), NGRAM(3,4), LOWERCASE, SNOWBALL(NLD), UPPERCASE TOKENIZERS BLANK, CAMEL, CLASS, PUNCT FUNCTION fn::foo::bar OPERATORS"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
 	assert_eq!(
 		res,
 		Expr::Define(Box::new(DefineStatement::Analyzer(DefineAnalyzerStatement {
@@ -1778,6 +2092,7 @@ fn parse_define_analyzer() {
 				Filter::Snowball(Language::Dutch),
 				Filter::Uppercase,
 			]),
+			operators: true,
 			comment: Expr::Literal(Literal::None),
 			function: Some("foo::bar".to_owned()),
 		}))),
@@ -1861,7 +2176,8 @@ pub fn parse_for() {
 					version: Expr::Literal(Literal::None),
 					timeout: Expr::Literal(Literal::None),
 					explain: None,
-					tempfiles: false
+					tempfiles: false,
+					cache: None,
 				}))),
 				op: BinaryOperator::Multiply,
 				right: Box::new(Expr::Literal(Literal::Integer(2)))
@@ -1983,6 +2299,8 @@ fn parse_show() {
 		res,
 		TopLevelExpr::Show(ShowStatement {
 			table: Some("foo".to_owned()),
+			consumer: None,
+			audit: false,
 			since: ShowSince::Versionstamp(1),
 			limit: Some(10)
 		})
@@ -2012,10 +2330,59 @@ fn parse_show() {
 		res,
 		TopLevelExpr::Show(ShowStatement {
 			table: None,
+			consumer: None,
+			audit: false,
 			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
 			limit: None
 		})
+	);
+
+	let res = syn::parse_with(
+		r#"SHOW CHANGES FOR CONSUMER orders LIMIT 5"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Show(ShowStatement {
+			table: None,
+			consumer: Some("orders".to_owned()),
+			audit: false,
+			since: ShowSince::Versionstamp(0),
+			limit: Some(5)
+		})
+	);
+
+	let res = syn::parse_with(
+		r#"SHOW AUDIT SINCE d"2012-04-23T18:25:43.0000511Z" LIMIT 5"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
 	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Show(ShowStatement {
+			table: None,
+			consumer: None,
+			audit: true,
+			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
+			limit: Some(5)
+		})
+	);
+}
+
+#[test]
+fn parse_ack() {
+	let res = syn::parse_with(r#"ACK CONSUMER orders AT 65536"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Ack(AckStatement {
+			consumer: "orders".to_owned(),
+			versionstamp: Expr::Literal(Literal::Integer(65536)),
+		})
+	);
 }
 
 #[test]
@@ -2199,7 +2566,8 @@ fn parse_insert_select() {
 				version: Expr::Literal(Literal::None),
 				timeout: Expr::Literal(Literal::None),
 				explain: None,
-				tempfiles: false
+				tempfiles: false,
+				cache: None,
 			}))),
 			ignore: true,
 			update: None,
@@ -2561,6 +2929,159 @@ fn parse_remove() {
 			if_exists: true,
 		})))
 	);
+
+	let res = syn::parse_with(r#"REMOVE JOB IF EXISTS cleanup"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Job(RemoveJobStatement {
+			name: Expr::Idiom(Idiom::field("cleanup".to_string())),
+			if_exists: true,
+		})))
+	);
+
+	let res = syn::parse_with(r#"REMOVE SINK orders"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Sink(RemoveSinkStatement {
+			name: Expr::Idiom(Idiom::field("orders".to_string())),
+			if_exists: false,
+		})))
+	);
+
+	let res =
+		syn::parse_with(r#"REMOVE CONSUMER IF EXISTS orders"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Consumer(RemoveConsumerStatement {
+			name: Expr::Idiom(Idiom::field("orders".to_string())),
+			if_exists: true,
+		})))
+	);
+}
+
+#[test]
+fn parse_remove_key() {
+	let res = syn::parse_with(r#"REMOVE KEY IF EXISTS pii"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Key(RemoveKeyStatement {
+			name: Expr::Idiom(Idiom::field("pii".to_string())),
+			if_exists: true,
+		})))
+	);
+}
+
+#[test]
+fn parse_rebuild_key() {
+	let res = syn::parse_with(r#"REBUILD KEY IF EXISTS pii"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Rebuild(Box::new(RebuildStatement::Key(RebuildKeyStatement {
+			name: "pii".to_string(),
+			if_exists: true,
+		})))
+	);
+}
+
+#[test]
+fn parse_run() {
+	let res = syn::parse_with(r"RUN JOB cleanup".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+
+	let expect = Expr::Run(Box::new(RunStatement::Job(RunJobStatement {
+		name: "cleanup".to_string(),
+	})));
+	assert_eq!(res, expect);
+
+	// `run` is still usable as a field name
+	let res = syn::parse_with(r"run".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, ident_field("run"));
+}
+
+#[test]
+fn parse_show_history() {
+	let res = syn::parse_with(
+		r"SHOW HISTORY FOR person:tobie SINCE $since UNTIL $until".as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	let expect = Expr::History(Box::new(HistoryStatement {
+		what: Expr::Literal(Literal::RecordId(RecordIdLit {
+			table: "person".to_owned(),
+			key: RecordIdKeyLit::String("tobie".to_owned()),
+		})),
+		since: Some(Expr::Param(Param::new("since".to_owned()))),
+		until: Some(Expr::Param(Param::new("until".to_owned()))),
+	}));
+	assert_eq!(res, expect);
+
+	// `history` is still usable as a field name
+	let res = syn::parse_with(r"SELECT history, id FROM person".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert!(matches!(res, Expr::Select(_)));
+
+	// `SELECT history FROM` selects the `history` field
+	let res = syn::parse_with(r"SELECT history FROM t".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement, found {res:?}");
+	};
+	assert_eq!(
+		stmt.fields,
+		Fields::Select(vec![Field::Single(Selector {
+			expr: ident_field("history"),
+			alias: None,
+		})])
+	);
+}
+
+#[test]
+fn parse_select_cache() {
+	let res = syn::parse_with(
+		r"SELECT count() FROM order GROUP ALL TIMEOUT 1s CACHE 30s".as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement");
+	};
+	assert_eq!(stmt.timeout, Expr::Literal(Literal::Duration(PublicDuration::from_secs(1))));
+	assert_eq!(stmt.cache, Some(PublicDuration::from_secs(30)));
+
+	// `cache` is still usable as a field name
+	let res = syn::parse_with(r"SELECT cache FROM settings".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement");
+	};
+	assert_eq!(stmt.cache, None);
 }
 
 #[test]
@@ -286,6 +286,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
+			ttl: None,
 		})))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Event(DefineEventStatement {
 			kind: DefineKind::Default,
@@ -327,6 +328,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			comment: Expr::Literal(Literal::None),
 			reference: None,
 			computed: None,
+			encrypted: None,
 		})))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
 			kind: DefineKind::Default,
@@ -371,6 +373,7 @@ fn statements() -> Vec<TopLevelExpr> {
 					Filter::Snowball(Language::Dutch),
 					Filter::Uppercase,
 				]),
+				operators: false,
 				comment: Expr::Literal(Literal::None),
 				function: Some("foo::bar".to_owned()),
 			},
@@ -427,6 +430,7 @@ fn statements() -> Vec<TopLevelExpr> {
 					timeout: Expr::Literal(Literal::None),
 					explain: None,
 					tempfiles: false,
+					cache: None,
 				}))),
 				op: BinaryOperator::Multiply,
 				right: Box::new(Expr::Literal(Literal::Integer(2))),
@@ -500,6 +504,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			version: Expr::Literal(Literal::Datetime(PublicDatetime::from(expected_datetime))),
 			timeout: Expr::Literal(Literal::None),
 			tempfiles: false,
+			cache: None,
 			explain: Some(Explain(true)),
 		}))),
 		TopLevelExpr::Expr(Expr::Select(Box::new(SelectStatement {
@@ -530,6 +535,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			version: Expr::Literal(Literal::None),
 			timeout: Expr::Literal(Literal::None),
 			tempfiles: false,
+			cache: None,
 			explain: None,
 		}))),
 		TopLevelExpr::Expr(Expr::Let(Box::new(SetStatement {
@@ -539,11 +545,15 @@ fn statements() -> Vec<TopLevelExpr> {
 		}))),
 		TopLevelExpr::Show(ShowStatement {
 			table: Some("foo".to_owned()),
+			consumer: None,
+			audit: false,
 			since: ShowSince::Versionstamp(1),
 			limit: Some(10),
 		}),
 		TopLevelExpr::Show(ShowStatement {
 			table: None,
+			consumer: None,
+			audit: false,
 			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
 			limit: None,
 		}),
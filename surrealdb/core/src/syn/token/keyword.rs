@@ -1,7 +1,7 @@
 macro_rules! keyword {
 	($($name:ident => $value:tt),* $(,)?) => {
 
-		#[repr(u8)]
+		#[repr(u16)]
 		#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 		pub enum Keyword{
 			$($name,)*
@@ -25,7 +25,10 @@ macro_rules! keyword {
 
 keyword! {
 	Access => "ACCESS",
+	Accumulate => "ACCUMULATE",
+	Ack => "ACK",
 	After => "AFTER",
+	Aggregate => "AGGREGATE",
 	Algorithm => "ALGORITHM",
 	All => "ALL",
 	Alter => "ALTER",
@@ -38,6 +41,7 @@ keyword! {
 	Assert => "ASSERT",
 	Async => "ASYNC",
 	At => "AT",
+	Audit => "AUDIT",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
 	Backend => "BACKEND",
@@ -51,6 +55,7 @@ keyword! {
 	Bm25 => "BM25",
 	Break => "BREAK",
 	By => "BY",
+	Cache => "CACHE",
 	Camel => "CAMEL",
 	Cancel => "CANCEL",
 	Cascade => "CASCADE",
@@ -63,11 +68,13 @@ keyword! {
 	Compact => "COMPACT",
 	Concurrently => "CONCURRENTLY",
 	Config => "CONFIG",
+	Consumer => "CONSUMER",
 	Content => "CONTENT",
 	Continue => "CONTINUE",
 	Computed => "COMPUTED",
 	Count => "COUNT",
 	Create => "CREATE",
+	Custom => "CUSTOM",
 	Database => "DATABASE",
 	Default => "DEFAULT",
 	Define => "DEFINE",
@@ -82,6 +89,7 @@ keyword! {
 	Edgengram => "EDGENGRAM",
 	Event => "EVENT",
 	Else => "ELSE",
+	Encrypted => "ENCRYPTED",
 	End => "END",
 	Enforced => "ENFORCED",
 	Exclude => "EXCLUDE",
@@ -95,9 +103,11 @@ keyword! {
 	Field => "FIELD",
 	Fields => "FIELDS",
 	Filters => "FILTERS",
+	Final => "FINAL",
 	Flexible => "FLEXIBLE",
 	For => "FOR",
 	From => "FROM",
+	Fuel => "FUEL",
 	Full => "FULL",
 	Fulltext => "FULLTEXT",
 	Function => "FUNCTION",
@@ -107,6 +117,7 @@ keyword! {
 	Group => "GROUP",
 	Headers => "HEADERS",
 	Highlights => "HIGHLIGHTS",
+	History => "HISTORY",
 	Hnsw => "HNSW",
 	Ignore => "IGNORE",
 	Include => "INCLUDE",
@@ -117,14 +128,18 @@ keyword! {
 	If => "IF",
 	Is => "IS",
 	Issuer => "ISSUER",
+	Job => "JOB",
 	Jwt => "JWT",
 	Jwks => "JWKS",
 	HashedVector => "HASHED_VECTOR",
+	Indexing => "INDEXING",
 	Key => "KEY",
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
 	Let => "LET",
+	Level => "LEVEL",
 	Limit => "LIMIT",
+	Limits => "LIMITS",
 	Live => "LIVE",
 	Lowercase => "LOWERCASE",
 	Lm => "LM",
@@ -132,6 +147,7 @@ keyword! {
 	M0 => "M0",
 	Mapper => "MAPPER",
 	MaxDepth => "MAXDEPTH",
+	Memory => "MEMORY",
 	Middleware => "MIDDLEWARE",
 	Merge => "MERGE",
 	Model => "MODEL",
@@ -146,6 +162,7 @@ keyword! {
 	Omit => "OMIT",
 	On => "ON",
 	Only => "ONLY",
+	Operators => "OPERATORS",
 	Option => "OPTION",
 	Order => "ORDER",
 	Original => "ORIGINAL",
@@ -161,6 +178,7 @@ keyword! {
 	Prepare => "PREPARE",
 	Punct => "PUNCT",
 	Purge => "PURGE",
+	Querying => "QUERYING",
 	Range => "RANGE",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
@@ -171,12 +189,15 @@ keyword! {
 	Relation => "RELATION",
 	Remove => "REMOVE",
 	Replace => "REPLACE",
+	Retain => "RETAIN",
 	Retry => "RETRY",
 	Return => "RETURN",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
 	Roles => "ROLES",
 	Root => "ROOT",
+	Run => "RUN",
+	Schedule => "SCHEDULE",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -188,12 +209,18 @@ keyword! {
 	Signin => "SIGNIN",
 	Signup => "SIGNUP",
 	Since => "SINCE",
+	Sink => "SINK",
 	Sleep => "SLEEP",
 	Snowball => "SNOWBALL",
+	Spatial => "SPATIAL",
 	Split => "SPLIT",
 	Start => "START",
+	State => "STATE",
+	Stdout => "STDOUT",
+	Stopwords => "STOPWORDS",
 	Strict => "STRICT",
 	Structure => "STRUCTURE",
+	Synonyms => "SYNONYMS",
 	System => "SYSTEM",
 	Table => "TABLE",
 	Tables => "TABLES",
@@ -208,9 +235,11 @@ keyword! {
 	To => "TO",
 	Transaction => "TRANSACTION",
 	True => "true",
+	Ttl => "TTL",
 	Type => "TYPE",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
+	Until => "UNTIL",
 	Update => "UPDATE",
 	Upsert => "UPSERT",
 	Uppercase => "UPPERCASE",
@@ -221,6 +250,7 @@ keyword! {
 	Values => "VALUES",
 	Version => "VERSION",
 	Vs => "VS",
+	Webhook => "WEBHOOK",
 	When => "WHEN",
 	Where => "WHERE",
 	With => "WITH",
@@ -404,7 +404,13 @@ impl fmt::Display for TokenKind {
 }
 
 /// An assertion statically checking the size of TokenKind.
-const _TOKEN_KIND_SIZE_ASSERT: [(); 2] = [(); std::mem::size_of::<TokenKind>()];
+///
+/// There are more than 256 keywords, so `Keyword` no longer fits in a byte,
+/// which widens the kind to 4 bytes. The extra bytes take the place of the
+/// padding after the kind, so a `Token` stays 12 bytes, which the second
+/// assertion checks.
+const _TOKEN_KIND_SIZE_ASSERT: [(); 4] = [(); std::mem::size_of::<TokenKind>()];
+const _TOKEN_SIZE_ASSERT: [(); 12] = [(); std::mem::size_of::<Token>()];
 
 impl TokenKind {
 	pub fn has_data(&self) -> bool {
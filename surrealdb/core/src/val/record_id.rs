@@ -452,6 +452,7 @@ impl RecordId {
 			timeout: Expr::Literal(Literal::None),
 			explain: None,
 			tempfiles: false,
+			cache: None,
 		};
 		if let Value::Object(x) = stk.run(|stk| stm.compute(stk, ctx, opt, doc)).await?.first() {
 			Ok(Some(x))
@@ -67,6 +67,7 @@ impl Value {
 							timeout: Expr::Literal(Literal::None),
 							explain: None,
 							tempfiles: false,
+							cache: None,
 						};
 						*this = stm
 							.compute(stk, ctx, opt, None)
@@ -266,6 +267,7 @@ impl Value {
 					timeout: Expr::Literal(Literal::None),
 					explain: None,
 					tempfiles: false,
+					cache: None,
 				};
 				*this = stm.compute(stk, ctx, opt, None).await?.first();
 				Ok(())
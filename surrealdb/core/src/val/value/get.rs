@@ -425,6 +425,7 @@ impl Value {
 								timeout: Expr::Literal(Literal::None),
 								explain: None,
 								tempfiles: false,
+								cache: None,
 							};
 
 							let res = stk.run(|stk| stm.compute(stk, ctx, opt, None)).await?.all();
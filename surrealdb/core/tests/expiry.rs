@@ -0,0 +1,73 @@
+mod helpers;
+use std::time::Duration;
+
+use anyhow::Result;
+use helpers::{new_ds, skip_ok};
+use surrealdb_core::dbs::Session;
+use surrealdb_core::syn;
+use surrealdb_types::{Action, RecordId, Value};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn record_expiry() -> Result<()> {
+	let dbs = new_ds("test", "test").await?.with_notifications();
+	let Some(channel) = dbs.notifications() else {
+		unreachable!("No notification channel");
+	};
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+
+	// Setup the scenario
+	let sql = "
+		DEFINE TABLE session TTL 1s;
+		DEFINE EVENT expired ON TABLE session WHEN $event = 'DELETE' THEN (
+			CREATE activity SET session = $before.id
+		);
+		CREATE session:one;
+		CREATE session:two;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	skip_ok(res, 4)?;
+
+	// Nothing is due yet
+	dbs.record_expiry(Duration::from_secs(1)).await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM session;", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, syn::value("[session:one, session:two]").unwrap());
+
+	// Listen to the deletions
+	let res = &mut dbs.execute("LIVE SELECT * FROM session;", &ses, None).await?;
+	skip_ok(res, 1)?;
+
+	// Writing a record postpones its expiry
+	sleep(Duration::from_millis(1500)).await;
+	let res = &mut dbs.execute("UPDATE session:two SET seen = true;", &ses, None).await?;
+	skip_ok(res, 1)?;
+	let tmp = channel.recv().await?;
+	assert_eq!(tmp.action, Action::Update);
+
+	// Delete the expired records
+	dbs.record_expiry(Duration::from_secs(1)).await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM session;", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, syn::value("[session:two]").unwrap());
+
+	// The table events are processed
+	let res = &mut dbs.execute("SELECT VALUE session FROM activity;", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, syn::value("[session:one]").unwrap());
+
+	// The live queries are notified
+	let tmp = channel.recv().await?;
+	assert_eq!(tmp.action, Action::Delete);
+	assert_eq!(tmp.record, Value::RecordId(RecordId::new("session".to_owned(), "one".to_owned())));
+
+	// The updated record expires later on
+	sleep(Duration::from_millis(1500)).await;
+	dbs.record_expiry(Duration::from_secs(1)).await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM session;", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, syn::value("[]").unwrap());
+
+	Ok(())
+}
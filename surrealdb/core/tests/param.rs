@@ -29,11 +29,15 @@ async fn define_global_param() -> Result<()> {
 			apis: {},
 			buckets: {},
 			configs: {},
+			consumers: {},
 			functions: {},
+			jobs: {},
+			keys: {},
 			models: {},
 			modules: {},
 			params: { test: 'DEFINE PARAM $test VALUE 12345 PERMISSIONS FULL' },
 			sequences: {},
+			sinks: {},
 			tables: {},
 			users: {},
 		}",
@@ -315,11 +315,15 @@ async fn relate_enforced() -> Result<()> {
 	apis: {},
 	buckets: {},
 	configs: {},
+	consumers: {},
 	functions: {},
+	jobs: {},
+	keys: {},
 	models: {},
 	modules: {},
 	params: {},
 	sequences: {},
+	sinks: {},
 	tables: {
 		a: 'DEFINE TABLE a TYPE ANY SCHEMALESS PERMISSIONS NONE',
 		edge: 'DEFINE TABLE edge TYPE RELATION ENFORCED SCHEMALESS PERMISSIONS NONE'
@@ -0,0 +1,96 @@
+use anyhow::{Result, bail};
+use clap::Args;
+use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::kvs::backup::{ArchiveReader, Header};
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
+use tokio::fs::File;
+use tokio::io::{self, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(help = "Database path of the datastore to back up. A running server can not be \
+		backed up through this command, unless its storage engine allows concurrent access, \
+		such as TiKV. Stop the server before backing up an embedded storage engine.")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "Path to the backup file to write. Use dash - to write into stdout.")]
+	#[arg(default_value = "-")]
+	#[arg(index = 2)]
+	file: String,
+	#[arg(
+		help = "Path to a previous backup, to only back up the changes made since it was taken. Requires a storage engine with versioning enabled."
+	)]
+	#[arg(long)]
+	since: Option<String>,
+}
+
+/// Write a backup of the datastore for the `backup` subcommand.
+///
+/// The datastore is opened directly, rather than through a running server.
+/// Embedded storage engines, such as RocksDB and SurrealKV, lock their path,
+/// so they can only be backed up while no server is running on it. There is
+/// no way yet to request a backup from a running server.
+pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	BackupCommandArguments {
+		path,
+		file,
+		since,
+	}: BackupCommandArguments,
+) -> Result<()> {
+	// Check the datastore path
+	C::path_valid(&path)?;
+	// Incremental backups start from the snapshot of the previous backup
+	let since = match since {
+		Some(previous) => Some(read_header(&previous).await?.versionstamp),
+		None => None,
+	};
+	// Open the datastore
+	let ds = Datastore::new_with_factory(composer, &path, CancellationToken::new()).await?;
+	// Prepare the output
+	let mut output: Box<dyn AsyncWrite + Unpin + Send> = if file == "-" {
+		Box::new(io::stdout())
+	} else {
+		Box::new(File::create(&file).await?)
+	};
+	// Stream the backup into the output
+	let (snd, rcv) = async_channel::bounded(1);
+	let (header, job) = ds.backup(since, snd).await?;
+	let write = async {
+		while let Ok(chunk) = rcv.recv().await {
+			output.write_all(&chunk).await?;
+		}
+		output.flush().await?;
+		Ok(())
+	};
+	let (count, ()) = tokio::try_join!(job, write)?;
+	ds.shutdown().await?;
+	info!(
+		"Backed up {count} entries at versionstamp {}{}",
+		header.versionstamp,
+		match header.since {
+			Some(since) => format!(", since versionstamp {since}"),
+			None => String::new(),
+		}
+	);
+	// Everything OK
+	Ok(())
+}
+
+/// Reads the header at the start of a backup file
+pub(super) async fn read_header(path: &str) -> Result<Header> {
+	let mut file = File::open(path).await?;
+	let mut reader = ArchiveReader::default();
+	let mut buf = vec![0; 4096];
+	loop {
+		if let Some(header) = reader.header()? {
+			return Ok(header);
+		}
+		let n = file.read(&mut buf).await?;
+		if n == 0 {
+			bail!("The backup file `{path}` is truncated");
+		}
+		reader.push(&buf[..n]);
+	}
+}
@@ -1,10 +1,13 @@
+use std::path::Path;
+
 use anyhow::Result;
 use clap::Args;
 use futures_util::StreamExt;
-use surrealdb::Connection;
-use surrealdb::engine::any::{self, connect};
+use surrealdb::engine::any::{self, Any, connect};
 use surrealdb::method::{Export, ExportConfig};
-use surrealdb_core::kvs::export::TableConfig;
+use surrealdb::{Connection, Surreal};
+use surrealdb_core::kvs::export::{ExportFormat, FlattenPolicy, TableConfig};
+use surrealdb_types::Value;
 use tokio::io::{self, AsyncWriteExt};
 
 use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
@@ -19,8 +22,14 @@ pub struct DatabaseConnectionArguments {
 	pub(crate) endpoint: String,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Clone, Debug)]
 struct ExportConfigArguments {
+	/// The format of the export: surql, ndjson, csv or parquet
+	#[arg(long, default_value = "surql")]
+	format: ExportFormat,
+	/// How nested objects are written in a csv or parquet export: json or columns
+	#[arg(long)]
+	flatten: Option<FlattenPolicy>,
 	/// Whether only specific resources should be exported
 	#[arg(long)]
 	only: bool,
@@ -64,11 +73,24 @@ struct ExportConfigArguments {
 	/// Whether configs should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	configs: Option<bool>,
+	/// Whether jobs should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	jobs: Option<bool>,
+	/// Whether changefeed sinks should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	sinks: Option<bool>,
+	/// Whether changefeed consumers should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	consumers: Option<bool>,
+	/// Whether encryption keys should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	keys: Option<bool>,
 }
 
 #[derive(Args, Debug)]
 pub struct ExportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to export. Use dash - to write into stdout.")]
+	#[arg(help = "Path to the file to export. Use dash - to write into stdout. \
+		For csv and parquet exports, a directory writes one file per table.")]
 	#[arg(default_value = "-")]
 	#[arg(index = 1)]
 	file: String,
@@ -141,6 +163,7 @@ pub async fn init(
 
 	// Export the data from the database
 	debug!("Exporting data from the database");
+	let format = config.format;
 	if file == "-" {
 		// Prepare the backup
 		let mut backup = apply_config(config, client.export(())).await?;
@@ -150,10 +173,16 @@ pub async fn init(
 		while let Some(bytes) = backup.next().await {
 			stdout.write_all(&bytes?).await?;
 		}
+	} else if format.is_columnar() && Path::new(&file).is_dir() {
+		// Write each of the selected tables into its own file
+		for table in export_tables(&client, &config).await? {
+			let path = Path::new(&file).join(format!("{table}.{}", format.extension()));
+			apply_config(config.clone(), client.export(path)).tables(vec![table]).await?;
+		}
 	} else {
 		apply_config(config, client.export(file)).await?;
 	}
-	info!("The SurrealQL file was exported successfully");
+	info!("The {format} export was written successfully");
 	// Everything OK
 	Ok(())
 }
@@ -162,7 +191,11 @@ fn apply_config<C: Connection, R>(
 	config: ExportConfigArguments,
 	export: Export<C, R>,
 ) -> Export<C, R, ExportConfig> {
-	let mut export = export.with_config();
+	let mut export = export.with_config().format(config.format);
+
+	if let Some(value) = config.flatten {
+		export = export.flatten(value);
+	}
 
 	if config.only {
 		export = export
@@ -227,5 +260,42 @@ fn apply_config<C: Connection, R>(
 		export = export.configs(value);
 	}
 
+	if let Some(value) = config.jobs {
+		export = export.jobs(value);
+	}
+
+	if let Some(value) = config.sinks {
+		export = export.sinks(value);
+	}
+
+	if let Some(value) = config.consumers {
+		export = export.consumers(value);
+	}
+
+	if let Some(value) = config.keys {
+		export = export.keys(value);
+	}
+
 	export
 }
+
+/// Lists the tables of the database which are selected by the export config
+async fn export_tables(
+	client: &Surreal<Any>,
+	config: &ExportConfigArguments,
+) -> Result<Vec<String>> {
+	let selection = match (&config.tables, &config.tables_exclude) {
+		(Some(tables), _) | (None, Some(tables)) => tables.clone(),
+		(None, None) if config.only => TableConfig::None,
+		(None, None) => TableConfig::All,
+	};
+	let info: Value = client.query("INFO FOR DB").await?.take(0)?;
+	let tables = match info {
+		Value::Object(mut info) => match info.remove("tables") {
+			Some(Value::Object(tables)) => tables.into_inner().into_keys().collect(),
+			_ => Vec::new(),
+		},
+		_ => Vec::new(),
+	};
+	Ok(tables.into_iter().filter(|table: &String| selection.includes(table)).collect())
+}
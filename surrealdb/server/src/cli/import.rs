@@ -1,6 +1,9 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 use clap::Args;
 use surrealdb::engine::any::{self, connect};
+use surrealdb::method::{ImportFormat, ImportReport};
 use surrealdb::opt::Config;
 use surrealdb::opt::capabilities::Capabilities;
 
@@ -21,6 +24,15 @@ pub struct ImportCommandArguments {
 	#[arg(help = "Path to the SurrealQL file to import")]
 	#[arg(index = 1)]
 	file: String,
+	/// Bulk import the records of an ndjson, csv or parquet file, instead of a SurrealQL file
+	#[arg(long, requires = "table")]
+	format: Option<ImportFormat>,
+	/// The table into which the records are bulk imported
+	#[arg(long, requires = "format")]
+	table: Option<String>,
+	/// The file into which the rows which could not be imported are written, as ndjson
+	#[arg(long, requires = "format")]
+	errors: Option<PathBuf>,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
@@ -32,6 +44,9 @@ pub struct ImportCommandArguments {
 pub async fn init(
 	ImportCommandArguments {
 		file,
+		format,
+		table,
+		errors,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -86,6 +101,20 @@ pub async fn init(
 
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
+	// Bulk import the records into a table
+	if let (Some(format), Some(table)) = (format, table) {
+		let mut import = client.import(file).into_table(table, format);
+		if let Some(path) = &errors {
+			import = import.errors(path);
+		}
+		let report = import.await.inspect_err(|_| {
+			error!(
+				"Surreal import failed, import might only be partially completed or have failed entirely."
+			)
+		})?;
+		log_report(report, errors);
+		return Ok(());
+	}
 	// Import the data into the database
 	client.import(file).await.inspect_err(|_| {
 		error!(
@@ -96,3 +125,28 @@ pub async fn init(
 	// All ok
 	Ok(())
 }
+
+/// Logs the report of a bulk import. Every rejected row was already written
+/// into the errors file, when there is one.
+fn log_report(report: ImportReport, errors: Option<PathBuf>) {
+	info!("Imported {} records, rejected {} rows", report.imported, report.failed);
+	if report.failed == 0 {
+		return;
+	}
+	match errors {
+		Some(path) => {
+			warn!("The rejected rows were written to '{}'", path.display());
+		}
+		None => {
+			if report.failed > report.rejected.len() as u64 {
+				warn!(
+					"Only the first {} rejected rows are reported, use --errors to write all of them",
+					report.rejected.len()
+				);
+			}
+			for reject in report.rejected {
+				warn!("Row {} was rejected: {}", reject.row, reject.error);
+			}
+		}
+	}
+}
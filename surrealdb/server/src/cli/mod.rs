@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 pub(crate) mod abstraction;
+mod backup;
 mod config;
 mod export;
 mod fix;
@@ -9,6 +10,8 @@ mod isready;
 mod ml;
 #[cfg(feature = "surrealism")]
 mod module;
+mod restore;
+mod rotate_key;
 #[cfg(feature = "cli")]
 mod sql;
 mod start;
@@ -26,6 +29,7 @@ use std::process::ExitCode;
 use std::time::Duration;
 
 use anyhow::Result;
+use backup::BackupCommandArguments;
 use clap::{Parser, Subcommand, ValueEnum};
 pub use config::{Config, ConfigCheck, ConfigCheckRequirements};
 use export::ExportCommandArguments;
@@ -35,6 +39,8 @@ use isready::IsReadyCommandArguments;
 use ml::MlCommand;
 #[cfg(feature = "surrealism")]
 use module::ModuleCommand;
+use restore::RestoreCommandArguments;
+use rotate_key::RotateKeyCommandArguments;
 use semver::Version;
 #[cfg(feature = "cli")]
 use sql::SqlCommandArguments;
@@ -161,10 +167,10 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
-	#[command(about = "Backup data to or from an existing database")]
+	#[command(about = "Backup the raw data of a datastore into a portable archive")]
 	Backup(BackupCommandArguments),
-	*/
+	#[command(about = "Restore backup archives into a datastore")]
+	Restore(RestoreCommandArguments),
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
@@ -190,6 +196,8 @@ enum Commands {
 	Validate(ValidateCommandArguments),
 	#[command(about = "Fix database storage issues")]
 	Fix(FixCommandArguments),
+	#[command(about = "Re-encrypt an encrypted datastore with a new encryption key")]
+	RotateKey(RotateKeyCommandArguments),
 	#[command(about = "Run commands in version 2 of the database for backwards compatibility")]
 	V2(V2Commands),
 }
@@ -289,6 +297,8 @@ pub async fn init<
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init::<C>(composer, args).await,
+		Commands::Backup(args) => backup::init::<C>(composer, args).await,
+		Commands::Restore(args) => restore::init::<C>(composer, args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
 		Commands::Version(args) => version::init(args).await,
@@ -301,6 +311,7 @@ pub async fn init<
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
 		Commands::Fix(args) => fix::init::<C>(args).await,
+		Commands::RotateKey(args) => rotate_key::init::<C>(composer, args).await,
 		Commands::V2(args) => v2::init(args).await,
 	};
 	// Save the flamegraph and profile
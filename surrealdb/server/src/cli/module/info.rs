@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
-use surrealism_runtime::controller::Runtime;
+use surrealism_runtime::controller::{FunctionKind, Runtime};
 use surrealism_runtime::package::SurrealismPackage;
 use surrealism_types::err::PrefixError;
 
@@ -32,7 +32,9 @@ pub async fn init(file: PathBuf) -> Result<()> {
 			.await
 			.prefix_err(|| format!("Failed to collect return type for function '{name}'"))?;
 
-		results.push((name, args, returns));
+		let kind = controller.kind(Some(name.clone()));
+
+		results.push((name, args, returns, kind));
 	}
 
 	let exports = results;
@@ -41,15 +43,21 @@ pub async fn init(file: PathBuf) -> Result<()> {
 	println!("\n{title}");
 	println!("{}\n", "=".repeat(title.len() + 2));
 
-	for (name, args, returns) in exports {
+	for (name, args, returns, kind) in exports {
 		let name = if name.is_empty() {
 			"<mod>".to_string()
 		} else {
 			format!("<mod>::{name}")
 		};
 
+		let kind = match kind {
+			Some(FunctionKind::Aggregate) => " (aggregate)",
+			Some(FunctionKind::Table) => " (table)",
+			_ => "",
+		};
+
 		println!(
-			"- {name}({}) -> {}",
+			"- {name}({}) -> {}{kind}",
 			args.iter().map(|arg| format!("{arg}")).collect::<Vec<_>>().join(", "),
 			returns
 		);
@@ -0,0 +1,87 @@
+use anyhow::{Result, ensure};
+use clap::Args;
+use futures::TryStreamExt;
+use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::kvs::recovery::RecoveryPoint;
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+use tokio_util::sync::CancellationToken;
+
+use super::backup::read_header;
+
+#[derive(Args, Debug)]
+pub struct RestoreCommandArguments {
+	#[arg(help = "Database path of the datastore to restore into")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "Paths to the backup files to restore, starting with a full backup \
+		followed by the incremental backups taken after it, in order")]
+	#[arg(index = 2, required = true, num_args = 1..)]
+	files: Vec<String>,
+	#[arg(help = "Path to a more recent backup of the same datastore, whose changefeeds \
+		are replayed on top of the restored backups")]
+	#[arg(long, requires = "until")]
+	changes: Option<String>,
+	#[arg(help = "The datetime or versionstamp up to which the changefeeds are replayed")]
+	#[arg(long, requires = "changes")]
+	until: Option<RecoveryPoint>,
+}
+
+/// Restore backups into the datastore for the `restore` subcommand.
+pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	RestoreCommandArguments {
+		path,
+		files,
+		changes,
+		until,
+	}: RestoreCommandArguments,
+) -> Result<()> {
+	// Check the datastore path
+	C::path_valid(&path)?;
+	// Check that the backups follow each other before writing anything
+	let mut previous: Option<u64> = None;
+	let mut datastore = None;
+	for file in &files {
+		let header = read_header(file).await?;
+		ensure!(
+			datastore.is_none_or(|v| v == header.datastore),
+			"The backup `{file}` was taken from a different datastore than the previous backups"
+		);
+		datastore = Some(header.datastore);
+		if let Some(since) = header.since {
+			ensure!(
+				previous.is_none_or(|v| v == since),
+				"The backup `{file}` is based on versionstamp {since}, which is not the versionstamp of the previous backup"
+			);
+		}
+		previous = Some(header.versionstamp);
+	}
+	if let Some(changes) = &changes {
+		let header = read_header(changes).await?;
+		ensure!(
+			datastore.is_none_or(|v| v == header.datastore),
+			"The backup `{changes}` was taken from a different datastore than the restored backups"
+		);
+	}
+	// Open the datastore
+	let ds = Datastore::new_with_factory(composer, &path, CancellationToken::new()).await?;
+	// Restore each backup in order
+	let mut base = None;
+	for file in &files {
+		let stream = ReaderStream::new(File::open(file).await?).map_err(anyhow::Error::from);
+		let header = ds.restore(stream).await?;
+		info!("Restored the backup `{file}` at versionstamp {}", header.versionstamp);
+		base = Some(header);
+	}
+	// Replay the changefeeds up to the recovery point
+	if let (Some(base), Some(changes), Some(until)) = (base, changes, until) {
+		let stream = ReaderStream::new(File::open(&changes).await?).map_err(anyhow::Error::from);
+		let count = ds.recover(&base, stream, until.clone()).await?;
+		info!("Replayed {count} changesets from `{changes}` up to {until}");
+	}
+	ds.shutdown().await?;
+	// Everything OK
+	Ok(())
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::kvs::{Datastore, EncryptionKey, TransactionBuilderFactory};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Args, Debug)]
+pub struct RotateKeyCommandArguments {
+	#[arg(help = "Database path of the encrypted datastore")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "Path to the file containing the current encryption key")]
+	#[arg(env = "SURREAL_KEY_FILE", long = "key-file")]
+	#[arg(value_parser = super::validator::file_exists)]
+	key_file: Option<PathBuf>,
+	#[arg(help = "Path to the file containing the new encryption key")]
+	#[arg(long = "new-key-file")]
+	#[arg(value_parser = super::validator::file_exists)]
+	new_key_file: PathBuf,
+}
+
+/// Re-encrypt a datastore with a new key for the `rotate-key` subcommand.
+///
+/// The datastore is opened directly with the current key, so no server
+/// may be running on the same path while the key is rotated.
+pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	RotateKeyCommandArguments {
+		path,
+		key_file,
+		new_key_file,
+	}: RotateKeyCommandArguments,
+) -> Result<()> {
+	// Check the datastore path
+	C::path_valid(&path)?;
+	let path = with_key_file(path, key_file.as_deref())?;
+	// Load the new key before opening the datastore
+	let key = EncryptionKey::from_file(&new_key_file)?;
+	// Open the datastore with the current key
+	let ds = Datastore::new_with_factory(composer, &path, CancellationToken::new()).await?;
+	// Re-encrypt the datastore
+	let res = ds.rotate_encryption_key(key).await;
+	ds.shutdown().await?;
+	res?;
+	info!("Rotated the datastore encryption key");
+	// Everything OK
+	Ok(())
+}
+
+/// Adds the encryption key file, if any, to the datastore path.
+pub(super) fn with_key_file(path: String, key_file: Option<&Path>) -> Result<String> {
+	let Some(key_file) = key_file else {
+		return Ok(path);
+	};
+	if !(path.starts_with("rocksdb:") || path.starts_with("surrealkv:")) {
+		bail!("Encryption at rest is only supported by the rocksdb and surrealkv storage engines");
+	}
+	let key_file = key_file.display().to_string();
+	if key_file.contains('&') {
+		bail!("The path of the encryption key file can not contain '&'");
+	}
+	let separator = match path.contains('?') {
+		true => '&',
+		false => '?',
+	};
+	Ok(format!("{path}{separator}key_file={key_file}"))
+}
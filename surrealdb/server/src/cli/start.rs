@@ -36,6 +36,12 @@ pub struct StartCommandArguments {
 	#[arg(value_parser = super::validator::key_valid)]
 	#[arg(hide = true)] // Not currently in use
 	key: Option<String>,
+	#[arg(
+		help = "Path to the file containing the key used for encrypting the data at rest. Only values are encrypted: record ids, index keys, and schema names remain in plain text"
+	)]
+	#[arg(env = "SURREAL_KEY_FILE", long = "key-file")]
+	#[arg(value_parser = super::validator::file_exists)]
+	key_file: Option<PathBuf>,
 	//
 	// Tasks
 	#[arg(
@@ -72,6 +78,62 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ASYNC_EVENT_PROCESSING_INTERVAL", long = "async-event-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "5s")]
 	event_processing_interval: Duration,
+	#[arg(
+		help = "The interval at which to delete the records whose TTL has elapsed",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_RECORD_EXPIRY_INTERVAL", long = "record-expiry-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "5s")]
+	record_expiry_interval: Duration,
+	#[arg(
+		help = "The interval at which to check which scheduled jobs are due",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_JOB_SCHEDULER_INTERVAL", long = "job-scheduler-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "10s")]
+	job_scheduler_interval: Duration,
+	#[arg(
+		help = "The interval at which to deliver changefeeds to the defined sinks",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_CHANGEFEED_SINK_INTERVAL", long = "changefeed-sink-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	changefeed_sink_interval: Duration,
+	#[arg(
+		help = "The interval at which to write the buffered audit log events to the datastore",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_AUDIT_LOG_FLUSH_INTERVAL", long = "audit-log-flush-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	audit_log_flush_interval: Duration,
+	#[arg(
+		help = "The interval at which to apply the result cache invalidations published by other nodes",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_RESULT_INVALIDATION_INTERVAL", long = "result-invalidation-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	result_invalidation_interval: Duration,
+	#[arg(
+		help = "The interval at which to discard the multipart file uploads which have been idle for too long",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_FILE_UPLOAD_EXPIRY_INTERVAL", long = "file-upload-expiry-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	file_upload_expiry_interval: Duration,
+	#[arg(
+		help = "The interval at which to remove the versions which have left the version retention period",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_HISTORY_RETENTION_INTERVAL", long = "history-retention-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	history_retention_interval: Duration,
+	#[arg(
+		help = "The interval at which to remove the audit log events which have left the audit log retention period",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_AUDIT_LOG_RETENTION_INTERVAL", long = "audit-log-retention-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	audit_log_retention_interval: Duration,
 	//
 	// Authentication
 	#[arg(
@@ -186,9 +248,18 @@ pub async fn init<
 		changefeed_gc_interval,
 		index_compaction_interval,
 		event_processing_interval,
+		record_expiry_interval,
+		job_scheduler_interval,
+		changefeed_sink_interval,
+		audit_log_flush_interval,
+		result_invalidation_interval,
+		file_upload_expiry_interval,
+		history_retention_interval,
+		audit_log_retention_interval,
 		no_banner,
 		no_identification_headers,
 		allow_origin,
+		key_file,
 		..
 	}: StartCommandArguments,
 ) -> Result<()> {
@@ -196,6 +267,8 @@ pub async fn init<
 	let _ = CryptoProvider::install_default(rustls::crypto::aws_lc_rs::default_provider());
 	// Check the path is valid
 	C::path_valid(&path)?;
+	// Encrypt the data at rest if a key file is specified
+	let path = super::rotate_key::with_key_file(path, key_file.as_deref())?;
 	// Check if we should output a banner
 	if !no_banner {
 		println!("{LOGO}");
@@ -220,7 +293,15 @@ pub async fn init<
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
 		.with_changefeed_gc_interval(changefeed_gc_interval)
 		.with_index_compaction_interval(index_compaction_interval)
-		.with_event_processing_interval(event_processing_interval);
+		.with_event_processing_interval(event_processing_interval)
+		.with_record_expiry_interval(record_expiry_interval)
+		.with_job_scheduler_interval(job_scheduler_interval)
+		.with_changefeed_sink_interval(changefeed_sink_interval)
+		.with_audit_log_flush_interval(audit_log_flush_interval)
+		.with_result_invalidation_interval(result_invalidation_interval)
+		.with_file_upload_expiry_interval(file_upload_expiry_interval)
+		.with_history_retention_interval(history_retention_interval)
+		.with_audit_log_retention_interval(audit_log_retention_interval);
 	// Configure the config
 	let Some(bind) = listen_addresses.first().copied() else {
 		return Err(anyhow::anyhow!("No listen address provided"));
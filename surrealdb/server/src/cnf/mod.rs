@@ -41,6 +41,10 @@ pub static NET_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 pub static HTTP_MAX_ML_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_ML_BODY_SIZE", usize, 4 << 30);
 
+/// The maximum HTTP body size of the HTTP /file endpoints (default: 4 GiB)
+pub static HTTP_MAX_FILE_BODY_SIZE: LazyLock<usize> =
+	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_FILE_BODY_SIZE", usize, 4 << 30);
+
 /// The maximum HTTP body size of the HTTP /sql endpoint (default: 1 MiB)
 pub static HTTP_MAX_SQL_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_SQL_BODY_SIZE", usize, 1 << 20);
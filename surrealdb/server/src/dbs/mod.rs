@@ -7,7 +7,7 @@ use clap::Args;
 use rand::Rng;
 use surrealdb::opt::capabilities::Capabilities as SdkCapabilities;
 use surrealdb_core::buc::BucketStoreProvider;
-use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
+use surrealdb_core::kvs::{AuditFile, Datastore, TransactionBuilderFactory};
 use tokio::time::{Instant, sleep, timeout};
 use tokio_util::sync::CancellationToken;
 
@@ -67,6 +67,33 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_SLOW_QUERY_LOG_PARAM_DENY", long = "slow-log-param-deny")]
 	#[arg(value_delimiter = ',', num_args = 1..)]
 	slow_log_param_deny: Vec<String>,
+	// Audit logging configuration. Authentication attempts and the statements
+	// which change the schema or the permissions are appended as JSON lines to
+	// `audit_log_file`, which is rotated when it exceeds `audit_log_max_size`,
+	// and/or stored in the datastore when `audit_log_table` is set, where root
+	// users can read them with `SHOW AUDIT`, and where they are kept for
+	// `audit_log_retention`, or forever when it is not set.
+	#[arg(help = "The path of the file to write the audit log to")]
+	#[arg(env = "SURREAL_AUDIT_LOG_FILE", long = "audit-log-file")]
+	audit_log_file: Option<PathBuf>,
+	#[arg(help = "The size in bytes above which the audit log file is rotated")]
+	#[arg(env = "SURREAL_AUDIT_LOG_MAX_SIZE", long = "audit-log-max-size")]
+	#[arg(default_value_t = 100 * 1024 * 1024)]
+	audit_log_max_size: u64,
+	#[arg(help = "The number of rotated audit log files to keep")]
+	#[arg(env = "SURREAL_AUDIT_LOG_MAX_FILES", long = "audit-log-max-files")]
+	#[arg(default_value_t = 10)]
+	audit_log_max_files: usize,
+	#[arg(help = "Whether to store the audit log in the datastore")]
+	#[arg(env = "SURREAL_AUDIT_LOG_TABLE", long = "audit-log-table")]
+	#[arg(default_value_t = false)]
+	audit_log_table: bool,
+	#[arg(
+		help = "How long to keep the audit log events stored in the datastore (default: forever)"
+	)]
+	#[arg(env = "SURREAL_AUDIT_LOG_RETENTION", long = "audit-log-retention")]
+	#[arg(value_parser = super::cli::validator::duration)]
+	audit_log_retention: Option<Duration>,
 	#[arg(help = "The default namespace for a new instance")]
 	#[arg(env = "SURREAL_DEFAULT_NAMESPACE", long = "default-namespace")]
 	default_namespace: Option<String>,
@@ -732,6 +759,11 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 		slow_log_threshold,
 		slow_log_param_allow,
 		slow_log_param_deny,
+		audit_log_file,
+		audit_log_max_size,
+		audit_log_max_files,
+		audit_log_table,
+		audit_log_retention,
 		default_namespace,
 		default_database,
 		no_defaults,
@@ -772,6 +804,21 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 	if !slow_log_param_deny.is_empty() {
 		debug!("Slow log param deny is {:?}", slow_log_param_deny);
 	}
+	if let Some(v) = &audit_log_file {
+		debug!("Audit log file is {}", v.display());
+	}
+	if audit_log_table {
+		debug!("Audit log is stored in the datastore");
+	}
+	if let Some(v) = audit_log_retention {
+		debug!("Audit log retention period is {v:?}");
+	}
+	// Setup the audit log file
+	let audit_log_file = audit_log_file.map(|path| AuditFile {
+		path,
+		max_size: audit_log_max_size,
+		max_files: audit_log_max_files,
+	});
 	// Convert the capabilities
 	let capabilities = capabilities.into();
 	// Log the specified server capabilities
@@ -784,7 +831,8 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 		.with_transaction_timeout(transaction_timeout)
 		.with_auth_enabled(!unauthenticated)
 		.with_capabilities(capabilities)
-		.with_slow_log(slow_log_threshold, slow_log_param_allow, slow_log_param_deny);
+		.with_slow_log(slow_log_threshold, slow_log_param_allow, slow_log_param_deny)
+		.with_audit_log(audit_log_file, audit_log_table, audit_log_retention)?;
 	#[cfg(storage)]
 	let dbs = dbs.with_temporary_directory(temporary_directory);
 	// Ensure the storage version is up to date to prevent corruption.
@@ -1,9 +1,10 @@
-use axum::body::Bytes;
+use axum::body::{Body, Bytes};
 use axum::extract::{DefaultBodyLimit, Path, Query};
 use axum::http::{HeaderMap, Method};
 use axum::response::IntoResponse;
 use axum::routing::any;
 use axum::{Extension, Router};
+use futures::TryStreamExt;
 use surrealdb_core::api::err::ApiError;
 use surrealdb_core::api::request::ApiRequest;
 use surrealdb_core::catalog::ApiMethod;
@@ -112,8 +113,8 @@ async fn handler(
 		path = %path,
 		"Invoking API handler"
 	);
-	let res = ds
-		.invoke_api_handler(&ns, &db, &path, &session, req)
+	let (res, stream) = ds
+		.invoke_api_handler_streaming(&ns, &db, &path, &session, req)
 		.await
 		.map_err(|e| ApiHandlerError(ResponseError(e), request_id.clone()))?;
 
@@ -122,6 +123,12 @@ async fn handler(
 		status = %res.status,
 		"API handler completed"
 	);
+	// Files returned by the handler are streamed from their bucket
+	if let Some(stream) = stream {
+		let body = Body::from_stream(stream.map_err(std::io::Error::other));
+		return Ok((res.status, res.headers, body));
+	}
+
 	let res_body = match res.body {
 		Value::None => Vec::new(),
 		Value::Bytes(x) => x.into_inner().to_vec(),
@@ -129,7 +136,7 @@ async fn handler(
 		_ => {
 			return Err(ApiHandlerError(
 				ApiError::InvalidApiResponse(
-					"HTTP API response body must be None, bytes, or string; other values are not supported".into(),
+					"HTTP API response body must be None, bytes, string, or file; other values are not supported".into(),
 				)
 				.into(),
 				request_id,
@@ -137,5 +144,5 @@ async fn handler(
 		}
 	};
 
-	Ok((res.status, res.headers, res_body))
+	Ok((res.status, res.headers, Body::from(res_body)))
 }
@@ -8,6 +8,7 @@ use axum::{Extension, Router};
 use axum_extra::TypedHeader;
 use bytes::Bytes;
 use http::StatusCode;
+use http::header::CONTENT_TYPE;
 use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
 use surrealdb_core::iam::Action::View;
@@ -86,6 +87,8 @@ async fn handle_inner(
 	db.check(&session, View, Any.on_db(&nsv, &dbv)).map_err(ResponseError)?;
 	// Create a new bounded channel
 	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Set the content type of the export format
+	let content_type = cfg.format.content_type();
 	// Start the export task
 	let task = db.export_with_config(&session, snd, cfg).await.map_err(ResponseError)?;
 	// Spawn a new database export job
@@ -99,5 +102,5 @@ async fn handle_inner(
 		}
 	});
 	// Return the chunked body
-	Ok(Response::builder().status(StatusCode::OK).body(body)?)
+	Ok(Response::builder().status(StatusCode::OK).header(CONTENT_TYPE, content_type).body(body)?)
 }
@@ -0,0 +1,167 @@
+//! This file defines the endpoints for streaming files into, and out of,
+//! buckets, including resumable multipart uploads.
+//!
+//! - `GET /file/{bucket}/{key}` streams a file from a bucket
+//! - `PUT /file/{bucket}/{key}` streams the request body into a file
+//! - `POST /file/{bucket}/{key}` starts a multipart upload, returning its id
+//! - `PUT /file/{bucket}/{key}?upload={id}&offset={n}` appends a chunk
+//! - `GET /file/{bucket}/{key}?upload={id}` returns the size of the upload
+//! - `POST /file/{bucket}/{key}?upload={id}` commits the upload
+//! - `DELETE /file/{bucket}/{key}?upload={id}` aborts the upload
+
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Path, Request};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Router};
+use axum_extra::TypedHeader;
+use axum_extra::extract::Query;
+use futures::TryStreamExt;
+use http::header::{CONTENT_TYPE, HeaderValue};
+use serde::Deserialize;
+use surrealdb_core::api::format::OCTET_STREAM;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_types::{Value, object};
+use tower_http::limit::RequestBodyLimitLayer;
+use uuid::Uuid;
+
+use super::AppState;
+use super::error::ResponseError;
+use super::headers::Accept;
+use super::output::Output;
+use crate::cnf::HTTP_MAX_FILE_BODY_SIZE;
+use crate::ntw::error::Error as NetError;
+
+/// The options of a multipart file upload
+#[derive(Default, Deserialize, Debug, Clone)]
+struct UploadOptions {
+	pub upload: Option<Uuid>,
+	pub offset: Option<u64>,
+}
+
+pub fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new()
+		.route("/file/{bucket}/{*key}", get(download).put(upload).post(multipart).delete(abort))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_FILE_BODY_SIZE))
+}
+
+/// Checks if capabilities allow querying the requested HTTP route
+fn check_route(state: &AppState) -> Result<(), ResponseError> {
+	if !state.datastore.allows_http_route(&RouteTarget::Files) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Files);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Files.to_string()).into());
+	}
+	Ok(())
+}
+
+/// Streams a file from a bucket, or returns the size of a multipart upload.
+async fn download(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	Path((bucket, key)): Path<(String, String)>,
+	Query(options): Query<UploadOptions>,
+) -> Result<Response, ResponseError> {
+	check_route(&state)?;
+	let db = &state.datastore;
+	// Return the progress of a multipart upload
+	if let Some(id) = options.upload {
+		let size = db.file_upload_size(&session, &bucket, &key, id).await.map_err(ResponseError)?;
+		return output(accept.as_deref(), status(id, size)).map(IntoResponse::into_response);
+	}
+	// Stream the file from the bucket
+	let Some(stream) = db.get_file(&session, &bucket, &key).await.map_err(ResponseError)? else {
+		return Err(NetError::NotFound(format!("/file/{bucket}/{key}")).into());
+	};
+	let body = Body::from_stream(stream.map_err(std::io::Error::other));
+	Ok(([(CONTENT_TYPE, HeaderValue::from_static(OCTET_STREAM))], body).into_response())
+}
+
+/// Streams the request body into a file, or appends it to a multipart upload.
+async fn upload(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	Path((bucket, key)): Path<(String, String)>,
+	Query(options): Query<UploadOptions>,
+	request: Request,
+) -> Result<Output, ResponseError> {
+	check_route(&state)?;
+	let db = &state.datastore;
+	let stream = request.into_body().into_data_stream().map_err(anyhow::Error::new);
+	let res = match options.upload {
+		// Append the chunk to the multipart upload
+		Some(id) => db
+			.write_file_upload(&session, &bucket, &key, id, options.offset, stream)
+			.await
+			.map(|size| status(id, size)),
+		// Write the whole file at once
+		None => db.put_file(&session, &bucket, &key, stream).await.map(|size| {
+			Value::Object(object! {
+				size: size,
+			})
+		}),
+	};
+	output(accept.as_deref(), res.map_err(ResponseError)?)
+}
+
+/// Starts a multipart upload, or commits it once all of the data was written.
+async fn multipart(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	Path((bucket, key)): Path<(String, String)>,
+	Query(options): Query<UploadOptions>,
+) -> Result<Output, ResponseError> {
+	check_route(&state)?;
+	let db = &state.datastore;
+	let res = match options.upload {
+		// Store the data of the multipart upload in the bucket
+		Some(id) => {
+			db.commit_file_upload(&session, &bucket, &key, id).await.map(|size| status(id, size))
+		}
+		// Start a new multipart upload
+		None => db.begin_file_upload(&session, &bucket, &key).await.map(|id| status(id, 0)),
+	};
+	output(accept.as_deref(), res.map_err(ResponseError)?)
+}
+
+/// Aborts a multipart upload, discarding the data which was written to it.
+async fn abort(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Path((bucket, key)): Path<(String, String)>,
+	Query(options): Query<UploadOptions>,
+) -> Result<Output, ResponseError> {
+	check_route(&state)?;
+	let id = options.upload.ok_or(NetError::Request)?;
+	state.datastore.abort_file_upload(&session, &bucket, &key, id).await.map_err(ResponseError)?;
+	Ok(Output::None)
+}
+
+/// The progress of a multipart upload
+fn status(id: Uuid, size: u64) -> Value {
+	Value::Object(object! {
+		id: id.to_string(),
+		size: size,
+	})
+}
+
+fn output(accept: Option<&Accept>, res: Value) -> Result<Output, ResponseError> {
+	match accept {
+		// Simple serialization
+		None | Some(Accept::ApplicationJson) => Ok(Output::json_value(&res)),
+		Some(Accept::ApplicationCbor) => Ok(Output::cbor(res)),
+		// Return nothing
+		Some(Accept::ApplicationOctetStream) => Ok(Output::None),
+		// Internal serialization
+		Some(Accept::ApplicationFlatbuffers) => Ok(Output::flatbuffers(&res)),
+		// An unsupported content-type was requested
+		Some(_) => Err(NetError::InvalidType.into()),
+	}
+}
@@ -1,13 +1,24 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::body::Body;
 use axum::extract::{DefaultBodyLimit, Request};
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 use axum::routing::post;
 use axum::{Extension, Router};
 use axum_extra::TypedHeader;
-use futures::TryStreamExt;
+use axum_extra::extract::Query;
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use http::StatusCode;
+use http::header::CONTENT_TYPE;
+use serde::Deserialize;
 use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
 use surrealdb_core::iam::Action::Edit;
 use surrealdb_core::iam::ResourceKind::Any;
+use surrealdb_core::kvs::Datastore;
+use surrealdb_core::kvs::import::{Config, ImportFormat};
 use surrealdb_types::SurrealValue;
 use tower_http::limit::RequestBodyLimitLayer;
 
@@ -18,6 +29,17 @@ use crate::cnf::HTTP_MAX_IMPORT_BODY_SIZE;
 use crate::ntw::error::Error as NetError;
 use crate::ntw::output::Output;
 
+/// The options of a bulk import of records into a table. When no format is
+/// specified, the request body is imported as a SurrealQL script. When
+/// `errors` is set, every rejected row is streamed back as it is rejected.
+#[derive(Default, Deserialize, Debug, Clone)]
+struct ImportOptions {
+	pub table: Option<String>,
+	pub format: Option<String>,
+	#[serde(default)]
+	pub errors: bool,
+}
+
 pub fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
@@ -32,8 +54,9 @@ async fn handler(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	Query(options): Query<ImportOptions>,
 	request: Request,
-) -> Result<impl IntoResponse, ResponseError> {
+) -> Result<Response, ResponseError> {
 	// Get the datastore reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
@@ -46,37 +69,97 @@ async fn handler(
 
 	let body_stream = request.into_body().into_data_stream().map_err(anyhow::Error::new);
 
-	// Execute the sql query in the database
-	match db.import_stream(&session, body_stream).await {
+	// Execute the import in the database
+	let res = match options.format {
+		// Bulk import the records of the file into a table
+		Some(format) => {
+			let format = format.parse::<ImportFormat>().map_err(ResponseError)?;
+			let table = options.table.ok_or(NetError::Request)?;
+			let cfg = Config {
+				table,
+				format,
+			};
+			if options.errors {
+				return import_with_rejects(db.clone(), session, cfg, body_stream);
+			}
+			db.import_records(&session, cfg, body_stream, None).await.map(|res| res.into_value())
+		}
+		// Execute the sql query in the database
+		None => db.import_stream(&session, body_stream).await.map(|res| res.into_value()),
+	};
+	match res {
 		Ok(res) => {
-			match accept.as_deref() {
+			let output = match accept.as_deref() {
 				// Simple serialization
 				None | Some(Accept::ApplicationJson) => {
 					// TODO(3.0): This code here is using the wrong serialization method which might
 					// result in some values of the code being serialized wrong.
 					//
 					// this will serialize structs differently then they should.
-					let res = res.into_value();
-					Ok(Output::json_value(&res))
+					Output::json_value(&res)
 				}
 				Some(Accept::ApplicationCbor) => {
 					// TODO(3.0): This code here is using the wrong serialization method which might
 					// result in some values of the code being serialized wrong.
-					let res = res.into_value();
-					Ok(Output::cbor(res))
+					Output::cbor(res)
 				}
 				// Return nothing
-				Some(Accept::ApplicationOctetStream) => Ok(Output::None),
+				Some(Accept::ApplicationOctetStream) => Output::None,
 				// Internal serialization
-				Some(Accept::ApplicationFlatbuffers) => {
-					let res = res.into_value();
-					Ok(Output::flatbuffers(&res))
-				}
+				Some(Accept::ApplicationFlatbuffers) => Output::flatbuffers(&res),
 				// An unsupported content-type was requested
-				Some(_) => Err(NetError::InvalidType.into()),
-			}
+				Some(_) => return Err(NetError::InvalidType.into()),
+			};
+			Ok(output.into_response())
 		}
-		// There was an error when executing the query
+		// There was an error when executing the import
 		Err(err) => Err(ResponseError(err)),
 	}
 }
+
+/// Bulk imports the records of a file, and streams every rejected row back as
+/// a line of NDJSON, as soon as it is rejected. The last line holds either the
+/// report of the import, or the error which failed it. When the client
+/// disconnects, the channel of rejected rows is closed, which stops the import.
+fn import_with_rejects<S>(
+	db: Arc<Datastore>,
+	session: Session,
+	cfg: Config,
+	stream: S,
+) -> Result<Response, ResponseError>
+where
+	S: Stream<Item = Result<Bytes>> + Send + 'static,
+{
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes>>(1);
+	let body = Body::from_stream(body_stream);
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Spawn a new bulk import job
+	tokio::spawn(async move {
+		let import = db.import_records(&session, cfg, stream, Some(snd));
+		// Process all rejected rows
+		let lines = chn.clone();
+		let forward = async move {
+			while let Ok(reject) = rcv.recv().await {
+				let line = serde_json::json!({ "reject": reject.into_value().into_json_value() });
+				if lines.send(Ok(Bytes::from(format!("{line}\n")))).await.is_err() {
+					break;
+				}
+			}
+		};
+		let (res, ()) = tokio::join!(import, forward);
+		let line = match res {
+			Ok(report) => serde_json::json!({ "report": report.into_value().into_json_value() }),
+			Err(err) => serde_json::json!({ "error": err.to_string() }),
+		};
+		if let Err(err) = chn.send(Ok(Bytes::from(format!("{line}\n")))).await {
+			tracing::warn!("Error sending the import report: {:?}", err);
+		}
+	});
+	// Return the chunked body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(CONTENT_TYPE, "application/x-ndjson")
+		.body(body)?)
+}
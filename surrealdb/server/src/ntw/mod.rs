@@ -3,6 +3,7 @@ mod auth;
 pub mod client_ip;
 pub mod error;
 pub mod export;
+pub mod file;
 #[cfg(feature = "graphql")]
 pub mod gql;
 pub(crate) mod headers;
@@ -138,6 +139,7 @@ impl RouterFactory for CommunityComposer {
 			.merge(signup::router())
 			.merge(key::router())
 			.merge(ml::router())
+			.merge(file::router())
 			.merge(api::router());
 
 		#[cfg(feature = "graphql")]
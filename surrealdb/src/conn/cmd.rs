@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use async_channel::Sender;
 use surrealdb_core::iam::token::Token;
 use surrealdb_core::kvs::export::Config as DbExportConfig;
+use surrealdb_core::kvs::import::Config as DbImportConfig;
 use uuid::Uuid;
 
 use super::MlExportConfig;
@@ -63,6 +64,11 @@ pub(crate) enum Command {
 	ImportFile {
 		path: PathBuf,
 	},
+	ImportRecords {
+		path: PathBuf,
+		config: DbImportConfig,
+		errors: Option<PathBuf>,
+	},
 	ImportMl {
 		path: PathBuf,
 	},
@@ -160,9 +160,14 @@ use std::sync::Arc;
 #[cfg(not(target_family = "wasm"))]
 use std::task::{Poll, ready};
 #[cfg(not(target_family = "wasm"))]
-use std::{future::Future, path::PathBuf};
+use std::{
+	future::Future,
+	path::{Path, PathBuf},
+};
 
 use async_channel::Sender;
+#[cfg(not(target_family = "wasm"))]
+use futures::Stream;
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use futures::StreamExt;
 #[cfg(not(target_family = "wasm"))]
@@ -185,7 +190,7 @@ use tokio::{
 	io::{self, AsyncReadExt, AsyncWriteExt},
 };
 #[cfg(not(target_family = "wasm"))]
-use tokio_util::bytes::BytesMut;
+use tokio_util::bytes::{Bytes, BytesMut};
 use uuid::Uuid;
 
 use crate::conn::Command;
@@ -502,6 +507,42 @@ async fn export_file(
 	Ok(())
 }
 
+#[cfg(not(target_family = "wasm"))]
+async fn import_stream(
+	path: &Path,
+) -> Result<impl Stream<Item = anyhow::Result<Bytes>> + use<>, crate::Error> {
+	let mut file = match OpenOptions::new().read(true).open(path).await {
+		Ok(file) => file,
+		Err(error) => {
+			return Err(crate::Error::internal(format!(
+				"Failed to open `{}`: {}",
+				path.display(),
+				error
+			)));
+		}
+	};
+
+	let mut buffer = BytesMut::with_capacity(4096);
+
+	Ok(poll_fn(move |ctx| {
+		// Doing it this way optimizes allocation.
+		// It is highly likely that the buffer we return from this stream will be
+		// dropped between calls to this function.
+		// If this is the case than instead of allocating new memory the call to reserve
+		// will instead reclaim the existing used memory.
+		if buffer.capacity() == 0 {
+			buffer.reserve(4096);
+		}
+
+		let future = pin!(file.read_buf(&mut buffer));
+		match ready!(future.poll(ctx)) {
+			Ok(0) => Poll::Ready(None),
+			Ok(_) => Poll::Ready(Some(Ok(buffer.split().freeze()))),
+			Err(e) => Poll::Ready(Some(Err(anyhow::anyhow!("{}", e)))),
+		}
+	}))
+}
+
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 async fn export_ml(
 	kvs: &Datastore,
@@ -792,6 +833,9 @@ async fn router(
 		}
 		| Command::ImportFile {
 			..
+		}
+		| Command::ImportRecords {
+			..
 		} => Err(crate::Error::internal(
 			"The protocol or storage engine does not support backups on this architecture"
 				.to_string(),
@@ -974,37 +1018,7 @@ async fn router(
 			path,
 		} => {
 			let query_result = QueryResultBuilder::started_now();
-			let file = match OpenOptions::new().read(true).open(&path).await {
-				Ok(path) => path,
-				Err(error) => {
-					return Err(crate::Error::internal(format!(
-						"Failed to open `{}`: {}",
-						path.display(),
-						error
-					)));
-				}
-			};
-
-			let mut file = pin!(file);
-			let mut buffer = BytesMut::with_capacity(4096);
-
-			let stream = poll_fn(|ctx| {
-				// Doing it this way optimizes allocation.
-				// It is highly likely that the buffer we return from this stream will be
-				// dropped between calls to this function.
-				// If this is the case than instead of allocating new memory the call to reserve
-				// will instead reclaim the existing used memory.
-				if buffer.capacity() == 0 {
-					buffer.reserve(4096);
-				}
-
-				let future = pin!(file.read_buf(&mut buffer));
-				match ready!(future.poll(ctx)) {
-					Ok(0) => Poll::Ready(None),
-					Ok(_) => Poll::Ready(Some(Ok(buffer.split().freeze()))),
-					Err(e) => Poll::Ready(Some(Err(anyhow::anyhow!("{}", e)))),
-				}
-			});
+			let stream = import_stream(&path).await?;
 
 			let responses = kvs
 				.execute_import(
@@ -1021,6 +1035,62 @@ async fn router(
 
 			Ok(vec![query_result.finish()])
 		}
+		#[cfg(not(target_family = "wasm"))]
+		Command::ImportRecords {
+			path,
+			config,
+			errors,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			let stream = import_stream(&path).await?;
+			let session = state.session.read().await.clone();
+
+			let report = match errors {
+				None => kvs.import_records(&session, config, stream, None).await,
+				Some(errors) => {
+					let mut output = match OpenOptions::new()
+						.write(true)
+						.create(true)
+						.truncate(true)
+						.open(&errors)
+						.await
+					{
+						Ok(file) => file,
+						Err(error) => {
+							return Err(crate::Error::internal(format!(
+								"Failed to open `{}`: {}",
+								errors.display(),
+								error
+							)));
+						}
+					};
+					let (snd, rcv) = crate::channel::bounded(1);
+					let import = kvs.import_records(&session, config, stream, Some(snd));
+					// Write every rejected row into the errors file as it is rejected. If
+					// writing fails, the channel is closed, which stops the import.
+					let write = async move {
+						while let Ok(reject) = rcv.recv().await {
+							let mut line = reject.into_value().into_json_value().to_string();
+							line.push('\n');
+							output.write_all(line.as_bytes()).await?;
+						}
+						output.flush().await
+					};
+					let (report, written) = tokio::join!(import, write);
+					if let Err(error) = written {
+						return Err(crate::Error::internal(format!(
+							"Failed to write `{}`: {}",
+							errors.display(),
+							error
+						)));
+					}
+					report
+				}
+			}
+			.map_err(crate::std_error_to_types_error)?;
+
+			Ok(vec![query_result.finish_with_result(Ok(report.into_value()))])
+		}
 		#[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 		Command::ImportMl {
 			path,
@@ -464,6 +464,98 @@ async fn export_bytes(request: RequestBuilder, bytes: BackupSender) -> Result<()
 
 #[cfg(not(target_family = "wasm"))]
 async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
+	let value = send_import(request, path).await?;
+
+	// Convert Value::Array to Vec<QueryResult>
+	let Value::Array(arr) = value else {
+		return Err(Error::internal("Expected array response from import".to_string()));
+	};
+
+	for val in arr.into_vec() {
+		let result = QueryResult::from_value(val)
+			.map_err(|e| Error::internal(format!("Failed to parse query result: {e}")))?;
+		result.result?;
+	}
+
+	Ok(())
+}
+
+#[cfg(not(target_family = "wasm"))]
+async fn send_import(request: RequestBuilder, path: PathBuf) -> Result<Value> {
+	let res =
+		send_file(request.header(ACCEPT, surrealdb_core::api::format::FLATBUFFERS), path).await?;
+
+	let bytes = res.bytes().await.map_err(crate::std_error_to_types_error)?;
+
+	surrealdb_core::rpc::format::flatbuffers::decode(&bytes)
+		.map_err(|x| format!("Failed to deserialize flatbuffers payload: {x:?}"))
+		.map_err(|e| {
+			crate::Error::internal(format!("The server returned an unexpected response: {e}"))
+		})
+}
+
+/// Bulk imports the records of a file, and writes every row which could not
+/// be imported into the errors file.
+///
+/// The request must ask for the rejected rows with the `errors` query
+/// parameter. The server streams each rejected row back as a line of NDJSON,
+/// and ends the response with a line holding either the report or the error
+/// which failed the import.
+#[cfg(not(target_family = "wasm"))]
+async fn send_import_records(
+	request: RequestBuilder,
+	path: PathBuf,
+	errors: PathBuf,
+) -> Result<Value> {
+	use tokio::io::AsyncWriteExt;
+
+	let res = send_file(request, path).await?;
+
+	let mut output =
+		match OpenOptions::new().write(true).create(true).truncate(true).open(&errors).await {
+			Ok(file) => file,
+			Err(error) => {
+				return Err(Error::internal(format!(
+					"Failed to open `{}`: {}",
+					errors.display(),
+					error
+				)));
+			}
+		};
+	let write_error = |error: std::io::Error| {
+		Error::internal(format!("Failed to write `{}`: {}", errors.display(), error))
+	};
+
+	let mut body = res.bytes_stream();
+	let mut buffer = Vec::new();
+	while let Some(chunk) = body.try_next().await.map_err(crate::std_error_to_types_error)? {
+		buffer.extend_from_slice(&chunk);
+		while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+			let line = buffer.drain(..=end).collect::<Vec<_>>();
+			let line = serde_json::from_slice::<serde_json::Value>(&line).map_err(|e| {
+				Error::internal(format!("The server returned an unexpected response: {e}"))
+			})?;
+			if let Some(reject) = line.get("reject") {
+				let mut line = reject.to_string();
+				line.push('\n');
+				output.write_all(line.as_bytes()).await.map_err(write_error)?;
+			} else if let Some(report) = line.get("report") {
+				output.flush().await.map_err(write_error)?;
+				return Ok(report.clone().into_value());
+			} else if let Some(error) = line.get("error").and_then(|e| e.as_str()) {
+				output.flush().await.map_err(write_error)?;
+				return Err(Error::internal(error.to_owned()));
+			}
+		}
+	}
+
+	Err(Error::internal("The server ended the import without a report".to_owned()))
+}
+
+/// Sends a file as the body of a request, and checks the status of the
+/// response
+#[cfg(not(target_family = "wasm"))]
+async fn send_file(request: RequestBuilder, path: PathBuf) -> Result<reqwest::Response> {
 	let file = match OpenOptions::new().read(true).open(&path).await {
 		Ok(path) => path,
 		Err(error) => {
@@ -471,12 +563,7 @@ async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
 		}
 	};
 
-	let res = request
-		.header(ACCEPT, surrealdb_core::api::format::FLATBUFFERS)
-		.body(file)
-		.send()
-		.await
-		.map_err(crate::std_error_to_types_error)?;
+	let res = request.body(file).send().await.map_err(crate::std_error_to_types_error)?;
 
 	if res.error_for_status_ref().is_err() {
 		let res = res.text().await.map_err(crate::std_error_to_types_error)?;
@@ -495,26 +582,7 @@ async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
 		}
 	}
 
-	let bytes = res.bytes().await.map_err(crate::std_error_to_types_error)?;
-
-	let value: Value = surrealdb_core::rpc::format::flatbuffers::decode(&bytes)
-		.map_err(|x| format!("Failed to deserialize flatbuffers payload: {x:?}"))
-		.map_err(|e| {
-			crate::Error::internal(format!("The server returned an unexpected response: {e}"))
-		})?;
-
-	// Convert Value::Array to Vec<QueryResult>
-	let Value::Array(arr) = value else {
-		return Err(Error::internal("Expected array response from import".to_string()));
-	};
-
-	for val in arr.into_vec() {
-		let result = QueryResult::from_value(val)
-			.map_err(|e| Error::internal(format!("Failed to parse query result: {e}")))?;
-		result.result?;
-	}
-
-	Ok(())
+	Ok(res)
 }
 
 pub(crate) async fn health(request: RequestBuilder) -> Result<()> {
@@ -871,6 +939,9 @@ async fn router(
 		| Command::ImportFile {
 			..
 		}
+		| Command::ImportRecords {
+			..
+		}
 		| Command::ImportMl {
 			..
 		} => {
@@ -989,6 +1060,33 @@ async fn router(
 			Ok(vec![QueryResultBuilder::instant_none()])
 		}
 		#[cfg(not(target_family = "wasm"))]
+		Command::ImportRecords {
+			path,
+			config,
+			errors,
+		} => {
+			let mut req_path = base_url.join("import").map_err(crate::std_error_to_types_error)?;
+			req_path
+				.query_pairs_mut()
+				.append_pair("table", &config.table)
+				.append_pair("format", &config.format.to_string());
+			if errors.is_some() {
+				req_path.query_pairs_mut().append_pair("errors", "true");
+			}
+			let headers = session_state.headers.read().await;
+			let auth = session_state.auth.read().await;
+			let request = client
+				.post(req_path)
+				.headers(headers.clone())
+				.auth(&auth)
+				.header(CONTENT_TYPE, "application/octet-stream");
+			let report = match errors {
+				Some(errors) => send_import_records(request, path, errors).await?,
+				None => send_import(request, path).await?,
+			};
+			Ok(vec![QueryResultBuilder::started_now().finish_with_result(Ok(report))])
+		}
+		#[cfg(not(target_family = "wasm"))]
 		Command::ImportMl {
 			path,
 		} => {
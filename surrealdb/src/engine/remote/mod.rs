@@ -258,6 +258,9 @@ impl Command {
 			| Command::ImportFile {
 				..
 			}
+			| Command::ImportRecords {
+				..
+			}
 			| Command::ExportBytesMl {
 				..
 			}
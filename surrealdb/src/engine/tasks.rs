@@ -51,8 +51,19 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task3 = spawn_task_node_membership_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task4 = spawn_task_changefeed_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task5 = spawn_task_index_compaction(dbs.clone(), canceller.clone(), opts);
-	let task6 = spawn_task_event_processing(dbs, canceller, opts);
-	Tasks(vec![task1, task2, task3, task4, task5, task6])
+	let task6 = spawn_task_event_processing(dbs.clone(), canceller.clone(), opts);
+	let task7 = spawn_task_record_expiry(dbs.clone(), canceller.clone(), opts);
+	let task8 = spawn_task_job_scheduler(dbs.clone(), canceller.clone(), opts);
+	let task9 = spawn_task_changefeed_sinks(dbs.clone(), canceller.clone(), opts);
+	let task10 = spawn_task_audit_log(dbs.clone(), canceller.clone(), opts);
+	let task11 = spawn_task_result_invalidations(dbs.clone(), canceller.clone(), opts);
+	let task12 = spawn_task_file_upload_expiry(dbs.clone(), canceller.clone(), opts);
+	let task13 = spawn_task_history_retention(dbs.clone(), canceller.clone(), opts);
+	let task14 = spawn_task_audit_log_retention(dbs, canceller, opts);
+	Tasks(vec![
+		task1, task2, task3, task4, task5, task6, task7, task8, task9, task10, task11, task12,
+		task13, task14,
+	])
 }
 
 fn spawn_task_node_membership_refresh(
@@ -264,6 +275,302 @@ fn spawn_task_event_processing(
 	}))
 }
 
+/// Spawns a background task for record expiry
+///
+/// This function creates a background task that periodically deletes the
+/// records of the tables defined with a `TTL`, once the `TTL` has elapsed
+/// since the records were last written.
+///
+/// The task runs at the interval specified by `opts.record_expiry_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the record expiry interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_record_expiry(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.record_expiry_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running record expiry every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.record_expiry(interval).await {
+						error!("Error running record expiry: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running record expiry");
+	}))
+}
+
+/// Spawns a background task for scheduled jobs
+///
+/// This function creates a background task that periodically runs the jobs
+/// defined with `DEFINE JOB` whose schedule is due.
+///
+/// The task runs at the interval specified by `opts.job_scheduler_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the job scheduler interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_job_scheduler(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.job_scheduler_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running job scheduler every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.job_scheduler(interval).await {
+						error!("Error running job scheduler: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running job scheduler");
+	}))
+}
+
+/// Spawns a background task for changefeed sinks
+///
+/// This function creates a background task that periodically delivers the
+/// changefeeds of the databases to the sinks defined with `DEFINE SINK`.
+///
+/// The task runs at the interval specified by `opts.changefeed_sink_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the changefeed sink interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_changefeed_sinks(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.changefeed_sink_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Delivering changefeeds to sinks every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.changefeed_sinks(interval).await {
+						error!("Error delivering changefeeds to sinks: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Delivering changefeeds to sinks");
+	}))
+}
+
+/// Spawns a background task for the audit log
+///
+/// This function creates a background task that periodically writes the
+/// buffered audit log events to the datastore, and writes the remaining
+/// events once the task is cancelled.
+///
+/// The task runs at the interval specified by `opts.audit_log_flush_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the audit log flush interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_audit_log(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.audit_log_flush_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Writing the audit log every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.flush_audit_log().await {
+						error!("Error writing the audit log: {e}");
+					}
+				}
+			}
+		}
+		// Write the events buffered since the last tick
+		if let Err(e) = dbs.flush_audit_log().await {
+			error!("Error writing the audit log: {e}");
+		}
+		trace!("Background task exited: Writing the audit log");
+	}))
+}
+
+/// Spawns a background task that removes the versions kept for versioned
+/// queries which have left the retention window.
+///
+/// This function creates a background task that periodically sweeps the
+/// history kept by the `rocksdb` and `tikv` storage engines when versioning
+/// is enabled with a retention period. A sweep stops between batches when the
+/// task is cancelled.
+///
+/// The task runs at the interval specified by
+/// `opts.history_retention_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the history retention interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_history_retention(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.history_retention_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running history retention every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.history_retention(interval, &canceller).await {
+						error!("Error running history retention: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running history retention");
+	}))
+}
+
+/// Spawns a background task that removes the audit log events stored in the
+/// datastore which have left the audit log retention period.
+///
+/// This function creates a background task that periodically removes the
+/// expired audit log events, when the audit log is stored in the datastore
+/// with a retention period. The events are removed in batches, and the task
+/// stops between batches when it is cancelled.
+///
+/// The task runs at the interval specified by
+/// `opts.audit_log_retention_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the audit log retention interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_audit_log_retention(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.audit_log_retention_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running audit log retention every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.audit_log_retention(interval, &canceller).await {
+						error!("Error running audit log retention: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running audit log retention");
+	}))
+}
+
 async fn interval_ticker(interval: Duration) -> IntervalStream {
 	#[cfg(not(target_family = "wasm"))]
 	use tokio::{time, time::MissedTickBehavior};
@@ -314,3 +621,101 @@ mod test {
 			.unwrap();
 	}
 }
+
+/// Spawns a background task for result cache invalidations
+///
+/// This function creates a background task that periodically applies the
+/// result cache invalidations published by the other nodes of the cluster,
+/// and removes those which every node has received.
+///
+/// The task runs at the interval specified by
+/// `opts.result_invalidation_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the result invalidation interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_result_invalidations(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.result_invalidation_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Receiving result cache invalidations every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.result_invalidations(interval).await {
+						error!("Error receiving result cache invalidations: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Receiving result cache invalidations");
+	}))
+}
+
+/// Spawns a background task for the expiry of multipart file uploads
+///
+/// This function creates a background task that periodically discards the
+/// multipart file uploads which were abandoned, once they have been idle for
+/// longer than the upload timeout.
+///
+/// The task runs at the interval specified by
+/// `opts.file_upload_expiry_interval`.
+///
+/// # Arguments
+///
+/// * `dbs` - The datastore instance
+/// * `canceller` - Token used to cancel the task when the engine is shutting down
+/// * `opts` - Engine options containing the file upload expiry interval
+///
+/// # Returns
+///
+/// * A pinned task that can be awaited
+fn spawn_task_file_upload_expiry(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.file_upload_expiry_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running file upload expiry every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.file_upload_expiry(interval).await {
+						error!("Error running file upload expiry: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running file upload expiry");
+	}))
+}
@@ -9,6 +9,7 @@ use async_channel::Receiver;
 use futures::{Stream, StreamExt};
 use semver::Version;
 use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
+pub use surrealdb_core::kvs::export::{ExportFormat, FlattenPolicy};
 
 use crate::conn::{Command, MlExportConfig};
 use crate::method::{BoxFuture, ExportConfig as Config, Model, OnceLockExt};
@@ -173,6 +174,63 @@ where
 		}
 		self
 	}
+
+	/// Whether to export jobs from the database
+	pub fn jobs(mut self, jobs: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.jobs = jobs;
+		}
+		self
+	}
+
+	/// Whether to export changefeed sinks from the database
+	pub fn sinks(mut self, sinks: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.sinks = sinks;
+		}
+		self
+	}
+
+	/// Whether to export changefeed consumers from the database
+	pub fn consumers(mut self, consumers: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.consumers = consumers;
+		}
+		self
+	}
+
+	/// Whether to export encryption keys from the database
+	pub fn keys(mut self, keys: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.keys = keys;
+		}
+		self
+	}
+
+	/// The format in which to export the database
+	///
+	/// Formats other than SurrealQL only export the records of the selected
+	/// tables, and the CSV and Parquet formats require a single table:
+	/// ```
+	/// # use surrealdb::method::ExportFormat;
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// # let target = ();
+	/// db.export(target).with_config().format(ExportFormat::Csv).tables(vec!["users"]);
+	/// ```
+	pub fn format(mut self, format: ExportFormat) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
+
+	/// How nested values are written into the columns of a CSV or Parquet export
+	pub fn flatten(mut self, flatten: FlattenPolicy) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.flatten = flatten;
+		}
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -1,7 +1,12 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use surrealdb_core::kvs::import::Config as DbImportConfig;
+pub use surrealdb_core::kvs::import::{
+	ImportFormat, Reject as ImportReject, Report as ImportReport,
+};
 
 use crate::conn::Command;
 use crate::method::{BoxFuture, Model, OnceLockExt};
@@ -30,6 +35,47 @@ where
 			import_type: PhantomData,
 		}
 	}
+
+	/// Bulk import the records of an NDJSON, CSV or Parquet file into a table
+	///
+	/// The fields of the records are coerced into the types of the fields
+	/// defined on the table. Rows which can not be imported do not fail the
+	/// import, and are returned in the report instead. A Parquet file is
+	/// buffered in memory before it is decoded, so it can be no larger than
+	/// `SURREAL_IMPORT_PARQUET_MAX_SIZE` bytes.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::ImportFormat;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let report = db.import("people.csv").into_table("person", ImportFormat::Csv).await?;
+	/// for reject in report.rejected {
+	///     eprintln!("Row {} was rejected: {}", reject.row, reject.error);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn into_table(
+		self,
+		table: impl Into<String>,
+		format: ImportFormat,
+	) -> ImportRecords<'r, C> {
+		ImportRecords {
+			client: self.client,
+			file: self.file,
+			config: DbImportConfig {
+				table: table.into(),
+				format,
+			},
+			errors: None,
+		}
+	}
 }
 
 impl<C, T> Import<'_, C, T>
@@ -85,3 +131,66 @@ where
 		})
 	}
 }
+
+/// A bulk import of records into a table
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ImportRecords<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) file: PathBuf,
+	pub(super) config: DbImportConfig,
+	pub(super) errors: Option<PathBuf>,
+}
+
+impl<C> ImportRecords<'_, C>
+where
+	C: Connection,
+{
+	/// Writes every row which could not be imported into a file, as NDJSON
+	///
+	/// The report only holds the first rejected rows, up to
+	/// `SURREAL_IMPORT_MAX_REJECTED` rows, while the file holds all of them.
+	pub fn errors(mut self, path: impl AsRef<Path>) -> Self {
+		self.errors = Some(path.as_ref().to_owned());
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> ImportRecords<'static, C> {
+		ImportRecords {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for ImportRecords<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<ImportReport>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Backup) {
+				return Err(Error::internal(
+					"The protocol or storage engine does not support backups on this architecture"
+						.to_string(),
+				));
+			}
+			router
+				.execute(
+					self.client.session_id,
+					Command::ImportRecords {
+						path: self.file,
+						config: self.config,
+						errors: self.errors,
+					},
+				)
+				.await
+		})
+	}
+}
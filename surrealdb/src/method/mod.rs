@@ -53,10 +53,10 @@ pub use commit::Commit;
 pub use content::Content;
 pub use create::Create;
 pub use delete::Delete;
-pub use export::{Backup, Export};
+pub use export::{Backup, Export, ExportFormat, FlattenPolicy};
 use futures::Future;
 pub use health::Health;
-pub use import::Import;
+pub use import::{Import, ImportFormat, ImportRecords, ImportReject, ImportReport};
 pub use insert::Insert;
 pub use invalidate::Invalidate;
 pub use live::Stream;
@@ -85,6 +85,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				| Command::ImportFile {
 					..
 				}
+				| Command::ImportRecords {
+					..
+				}
 				| Command::Attach {
 					..
 				}
@@ -9,7 +9,9 @@ use crate::{Connect, Error};
 impl<R> Connect<Db, R> {
 	/// Enable MVCC versioning on the datastore.
 	///
-	/// Supported by `SurrealKv` and `Mem` engines.
+	/// Supported by all engines except `IndxDb`. The `SurrealKv` and `Mem`
+	/// engines keep versions natively, while `RocksDb` and `TiKv` keep them in
+	/// a separate history keyspace.
 	///
 	/// # Examples
 	///
@@ -36,7 +38,7 @@ impl<R> Connect<Db, R> {
 	/// Determines how long old versions are kept before being garbage collected.
 	/// A duration of zero means unlimited retention.
 	///
-	/// Supported by `SurrealKv` and `Mem` engines. Requires `versioned()`.
+	/// Supported by all engines except `IndxDb`. Requires `versioned()`.
 	///
 	/// # Examples
 	///
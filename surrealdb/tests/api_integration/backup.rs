@@ -11,6 +11,7 @@
 // Supported by the storage engines and the HTTP protocol
 
 use futures::StreamExt as _;
+use surrealdb::method::{ExportFormat, ImportFormat};
 use surrealdb::opt::Config;
 use surrealdb::types::Value;
 use tokio::fs::remove_file;
@@ -133,6 +134,88 @@ pub async fn export_with_config(new_db: impl CreateDb) {
 	}
 }
 
+pub async fn export_formats(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(Ulid::new().to_string()).use_db(&db_name).await.unwrap();
+
+	// Insert records
+	db.query("CREATE user:1 SET name = 'User 1'; CREATE user:2 SET name = 'User 2', age = 30;")
+		.await
+		.unwrap();
+	db.query("CREATE group:1 SET name = 'Group 1'").await.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Export the user table as NDJSON
+	let res = db
+		.export(())
+		.with_config()
+		.format(ExportFormat::Ndjson)
+		.tables(vec!["user"])
+		.await
+		.unwrap();
+	let bytes = res
+		.fold(Vec::new(), |mut acc, x| async move {
+			acc.append(&mut x.unwrap());
+			acc
+		})
+		.await;
+	let lines: Vec<serde_json::Value> = String::from_utf8(bytes)
+		.unwrap()
+		.lines()
+		.map(|l| serde_json::from_str(l).unwrap())
+		.collect();
+	assert_eq!(
+		lines,
+		[
+			serde_json::json!({ "id": "user:1", "name": "User 1" }),
+			serde_json::json!({ "id": "user:2", "name": "User 2", "age": 30 }),
+		]
+	);
+
+	// Export the user table as CSV
+	let res =
+		db.export(()).with_config().format(ExportFormat::Csv).tables(vec!["user"]).await.unwrap();
+	let bytes = res
+		.fold(Vec::new(), |mut acc, x| async move {
+			acc.append(&mut x.unwrap());
+			acc
+		})
+		.await;
+	assert_eq!(
+		String::from_utf8(bytes).unwrap(),
+		"id,name,age\nuser:1,User 1,\nuser:2,User 2,30\n"
+	);
+}
+
+pub async fn import_records(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(Ulid::new().to_string()).use_db(&db_name).await.unwrap();
+	db.query("DEFINE TABLE user SCHEMAFULL; DEFINE FIELD name ON user TYPE string; DEFINE FIELD age ON user TYPE option<int>;")
+		.await
+		.unwrap();
+	drop(permit);
+
+	// Bulk import a CSV file into the user table
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file = dir.path().join("users.csv");
+	std::fs::write(&file, "id,name,age\n1,User 1,\n2,User 2,30\n3,User 3,old\n").unwrap();
+	let report = db.import(&file).into_table("user", ImportFormat::Csv).await.unwrap();
+	assert_eq!(report.imported, 2);
+	assert_eq!(report.rejected.len(), 1);
+	assert_eq!(report.rejected[0].row, 3);
+
+	// Verify that the columns were coerced into the field types
+	let mut response = db.query("SELECT name, age FROM user ORDER BY name").await.unwrap();
+	let ages: Vec<Option<i64>> = response.take("age").unwrap();
+	assert_eq!(ages, [None, Some(30)]);
+}
+
 #[cfg(feature = "ml")]
 pub async fn ml_export_import(new_db: impl CreateDb) {
 	let config = Config::new();
@@ -210,6 +293,12 @@ define_include_tests!(backup => {
 	#[tokio::test]
 	export_with_config,
 
+	#[tokio::test]
+	export_formats,
+
+	#[tokio::test]
+	import_records,
+
 	#[test_log::test(tokio::test)]
 	#[cfg(feature = "ml")]
 	ml_export_import,
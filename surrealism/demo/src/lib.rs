@@ -1,6 +1,6 @@
 use anyhow::Result;
 use surrealdb_types::SurrealValue;
-use surrealism::surrealism;
+use surrealism::{SurrealismAggregate, surrealism};
 // use surrealism::types::value::Value;
 // use surrealism::types::number::Number;
 
@@ -179,6 +179,58 @@ fn test_kv() -> Result<()> {
 	Ok(())
 }
 
+#[derive(Debug, Default, SurrealValue)]
+struct MeanState {
+	sum: f64,
+	count: i64,
+}
+
+#[surrealism(aggregate)]
+struct Mean;
+
+impl SurrealismAggregate for Mean {
+	type State = MeanState;
+	type Input = (f64,);
+	type Output = Option<f64>;
+
+	fn init() -> MeanState {
+		MeanState::default()
+	}
+
+	fn accumulate(mut state: MeanState, (value,): (f64,)) -> Result<MeanState, String> {
+		state.sum += value;
+		state.count += 1;
+		Ok(state)
+	}
+
+	fn merge(left: MeanState, right: MeanState) -> Result<MeanState, String> {
+		Ok(MeanState {
+			sum: left.sum + right.sum,
+			count: left.count + right.count,
+		})
+	}
+
+	fn finalize(state: MeanState) -> Result<Option<f64>, String> {
+		Ok((state.count > 0).then(|| state.sum / state.count as f64))
+	}
+}
+
+#[derive(Debug, SurrealValue)]
+struct Step {
+	index: i64,
+	value: i64,
+}
+
+#[surrealism(table)]
+fn steps(start: i64, count: i64) -> Vec<Step> {
+	(0..count)
+		.map(|index| Step {
+			index,
+			value: start + index,
+		})
+		.collect()
+}
+
 #[surrealism]
 fn test_io() -> Result<String> {
 	println!("This is a test message to stdout");
@@ -3,18 +3,19 @@ use quote::{format_ident, quote};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
-	Expr, ExprLit, FnArg, GenericArgument, ItemFn, Lit, Meta, MetaNameValue, PatType,
+	Expr, ExprLit, FnArg, GenericArgument, ItemFn, ItemStruct, Lit, Meta, MetaNameValue, PatType,
 	PathArguments, ReturnType, Type, TypePath, parse_macro_input,
 };
 
 #[proc_macro_attribute]
 pub fn surrealism(attr: TokenStream, item: TokenStream) -> TokenStream {
 	let args = parse_macro_input!(attr with Punctuated::<Meta, Comma>::parse_terminated);
-	let input_fn = parse_macro_input!(item as ItemFn);
 
 	let mut is_default = false;
 	let mut export_name_override: Option<String> = None;
 	let mut is_init = false;
+	let mut is_aggregate = false;
+	let mut is_table = false;
 
 	for meta in args.iter() {
 		match meta {
@@ -43,12 +44,31 @@ pub fn surrealism(attr: TokenStream, item: TokenStream) -> TokenStream {
 			Meta::Path(path) if path.is_ident("init") => {
 				is_init = true;
 			}
+			Meta::Path(path) if path.is_ident("aggregate") => {
+				is_aggregate = true;
+			}
+			Meta::Path(path) if path.is_ident("table") => {
+				is_table = true;
+			}
 			_ => panic!(
-				"Unsupported attribute: expected #[surrealism], #[surrealism(default)], #[surrealism(init)], or #[surrealism(name = \"...\")]"
+				"Unsupported attribute: expected #[surrealism], #[surrealism(default)], #[surrealism(init)], #[surrealism(aggregate)], #[surrealism(table)], or #[surrealism(name = \"...\")]"
 			),
 		}
 	}
 
+	if is_aggregate {
+		if is_default || is_init || is_table {
+			panic!("#[surrealism(aggregate)] can only be combined with `name = \"...\"`");
+		}
+		let input_struct = parse_macro_input!(item as ItemStruct);
+		return aggregate(input_struct, export_name_override);
+	}
+	if is_table && (is_default || is_init) {
+		panic!("#[surrealism(table)] can only be combined with `name = \"...\"`");
+	}
+
+	let input_fn = parse_macro_input!(item as ItemFn);
+
 	let fn_name = &input_fn.sig.ident;
 	let fn_vis = &input_fn.vis;
 	let fn_sig = &input_fn.sig;
@@ -122,6 +142,7 @@ pub fn surrealism(attr: TokenStream, item: TokenStream) -> TokenStream {
 	let export_ident = format_ident!("__sr_fnc__{}", export_suffix);
 	let args_ident = format_ident!("__sr_args__{}", export_suffix);
 	let returns_ident = format_ident!("__sr_returns__{}", export_suffix);
+	let table_ident = format_ident!("__sr_tbl__{}", export_suffix);
 
 	// DRY error handling pattern
 	let try_or_fail = |expr: proc_macro2::TokenStream, context: &str| {
@@ -297,5 +318,121 @@ pub fn surrealism(attr: TokenStream, item: TokenStream) -> TokenStream {
 		}
 	};
 
+	// Table functions are marked with an additional export
+	let expanded = if is_table {
+		quote! {
+			#expanded
+
+			#[unsafe(no_mangle)]
+			pub extern "C" fn #table_ident() -> i32 {
+				0
+			}
+		}
+	} else {
+		expanded
+	};
+
 	TokenStream::from(expanded)
 }
+
+/// Generates the exports of an aggregate function, which is implemented by
+/// the annotated struct through `surrealism::SurrealismAggregate`.
+fn aggregate(input_struct: ItemStruct, export_name_override: Option<String>) -> TokenStream {
+	let struct_name = &input_struct.ident;
+	let export_suffix =
+		export_name_override.unwrap_or_else(|| snake_case(&struct_name.to_string()));
+
+	let args_ident = format_ident!("__sr_args__{}", export_suffix);
+	let returns_ident = format_ident!("__sr_returns__{}", export_suffix);
+	let stages = [
+		(format_ident!("__sr_agg_init__{}", export_suffix), quote! { init_raw }, "Aggregate init"),
+		(
+			format_ident!("__sr_agg_accumulate__{}", export_suffix),
+			quote! { accumulate_raw },
+			"Aggregate accumulate",
+		),
+		(
+			format_ident!("__sr_agg_merge__{}", export_suffix),
+			quote! { merge_raw },
+			"Aggregate merge",
+		),
+		(
+			format_ident!("__sr_agg_finalize__{}", export_suffix),
+			quote! { finalize_raw },
+			"Aggregate finalize",
+		),
+	];
+
+	let stage_exports = stages.iter().map(|(ident, raw, context)| {
+		quote! {
+			#[unsafe(no_mangle)]
+			pub extern "C" fn #ident(ptr: u32) -> i32 {
+				let mut controller = surrealism::Controller {};
+				match surrealism::aggregate::#raw::<#struct_name>(&mut controller, ptr.into()) {
+					Ok(result) => (*result).try_into().unwrap_or_else(|_| {
+						eprintln!("Transfer error: pointer overflow");
+						-1
+					}),
+					Err(e) => {
+						eprintln!(concat!(#context, " error: {}"), e);
+						-1
+					}
+				}
+			}
+		}
+	});
+
+	let expanded = quote! {
+		#input_struct
+
+		#(#stage_exports)*
+
+		#[unsafe(no_mangle)]
+		pub extern "C" fn #args_ident() -> i32 {
+			let mut controller = surrealism::Controller {};
+			match surrealism::aggregate::args_raw::<#struct_name>(&mut controller) {
+				Ok(result) => (*result).try_into().unwrap_or_else(|_| {
+					eprintln!("Transfer error: pointer overflow");
+					-1
+				}),
+				Err(e) => {
+					eprintln!("Args error: {}", e);
+					-1
+				}
+			}
+		}
+
+		#[unsafe(no_mangle)]
+		pub extern "C" fn #returns_ident() -> i32 {
+			let mut controller = surrealism::Controller {};
+			match surrealism::aggregate::returns_raw::<#struct_name>(&mut controller) {
+				Ok(result) => (*result).try_into().unwrap_or_else(|_| {
+					eprintln!("Transfer error: pointer overflow");
+					-1
+				}),
+				Err(e) => {
+					eprintln!("Returns error: {}", e);
+					-1
+				}
+			}
+		}
+	};
+
+	TokenStream::from(expanded)
+}
+
+/// Converts a struct name such as `WeightedMean` into `weighted_mean`.
+fn snake_case(name: &str) -> String {
+	let mut out = String::with_capacity(name.len() + 4);
+	for (i, c) in name.chars().enumerate() {
+		if c.is_ascii_uppercase() {
+			if i > 0 {
+				out.push('_');
+			}
+			out.push(c.to_ascii_lowercase());
+		} else {
+			out.push(c);
+		}
+	}
+	out
+}
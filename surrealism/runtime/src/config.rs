@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use surrealism_types::err::PrefixError;
 
 use crate::capabilities::SurrealismCapabilities;
+use crate::limits::SurrealismLimits;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SurrealismConfig {
@@ -11,6 +12,8 @@ pub struct SurrealismConfig {
 	pub meta: SurrealismMeta,
 	#[serde(default)]
 	pub capabilities: SurrealismCapabilities,
+	#[serde(default)]
+	pub limits: SurrealismLimits,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
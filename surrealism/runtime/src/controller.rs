@@ -26,9 +26,19 @@
 //! });
 //! # Ok::<(), anyhow::Error>(())
 //! ```
+//!
+//! # Limits
+//!
+//! Each Controller runs within the [`SurrealismLimits`] of the package, which can be overridden
+//! with [`Runtime::new_controller_with_limits`]. Fuel is consumed as the module executes, when the
+//! runtime was created with a fuel limit (see [`Runtime::with_limits`]), the growth of its linear
+//! memory is bounded, and a background thread increments the epochs of the engines so that a
+//! module which runs past its timeout, or whose invocation context was cancelled, is interrupted.
+//! Exceeding a limit fails the call with a [`LimitError`].
 
 use std::fmt;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -40,6 +50,7 @@ use wasmtime_wasi::p1::{self, WasiP1Ctx};
 
 use crate::config::SurrealismConfig;
 use crate::host::{InvocationContext, implement_host_functions};
+use crate::limits::{LimitError, MemoryLimiter, SurrealismLimits, tick_epoch};
 use crate::package::SurrealismPackage;
 
 /// Store data for WASM execution. Each Controller has its own isolated StoreData.
@@ -47,6 +58,7 @@ pub struct StoreData {
 	pub wasi: WasiP1Ctx,
 	pub config: Arc<SurrealismConfig>,
 	pub(crate) context: Box<dyn InvocationContext>,
+	pub(crate) limiter: MemoryLimiter,
 }
 
 impl fmt::Debug for StoreData {
@@ -65,22 +77,36 @@ pub struct Runtime {
 	module: Module,
 	linker: Linker<StoreData>,
 	config: Arc<SurrealismConfig>,
+	/// Whether fuel is metered, so that it can be limited
+	fuel: bool,
 }
 
 impl Runtime {
 	/// Compile the WASM module and prepare the runtime.
 	/// This is expensive - do it once and share via `Arc<Runtime>`.
 	/// The compiled artifacts (Engine, Module, Linker) are immutable and thread-safe.
-	pub fn new(
+	pub fn new(package: SurrealismPackage) -> Result<Self> {
+		Self::with_limits(package, &SurrealismLimits::default())
+	}
+
+	/// Compile the WASM module and prepare the runtime, for controllers whose limits
+	/// are the limits of the package with any limit which is set in `overrides`
+	/// replaced. Fuel is only metered when one of them sets a fuel limit.
+	pub fn with_limits(
 		SurrealismPackage {
 			wasm,
 			config,
 		}: SurrealismPackage,
+		overrides: &SurrealismLimits,
 	) -> Result<Self> {
+		let fuel = config.limits.with_overrides(overrides).fuel.is_some();
 		// Configure engine for fast compilation in debug, optimized runtime in release
 		let mut engine_config = Config::new();
 		// Enable async support for async host functions
 		engine_config.async_support(true);
+		// Enable epochs, and fuel when it is limited, so that the execution can be bounded
+		engine_config.consume_fuel(fuel);
+		engine_config.epoch_interruption(true);
 		#[cfg(debug_assertions)]
 		{
 			// Use Winch baseline compiler for extremely fast compilation in debug builds
@@ -102,11 +128,15 @@ impl Runtime {
 		implement_host_functions(&mut linker)
 			.prefix_err(|| "failed to implement host functions")?;
 
+		// Increment the epoch until the engine is dropped
+		tick_epoch(&engine)?;
+
 		Ok(Self {
 			engine,
 			module,
 			linker,
 			config: Arc::new(config),
+			fuel,
 		})
 	}
 
@@ -115,19 +145,60 @@ impl Runtime {
 	/// Each controller has its own mutable Store, ensuring no shared mutable state.
 	/// Safe for concurrent execution: no mutable state is shared between controllers.
 	pub async fn new_controller(&self, context: Box<dyn InvocationContext>) -> Result<Controller> {
+		self.new_controller_with_limits(context, &SurrealismLimits::default()).await
+	}
+
+	/// Create a new Controller, with the limits of the package replaced by any
+	/// limit which is set in `overrides`.
+	pub async fn new_controller_with_limits(
+		&self,
+		context: Box<dyn InvocationContext>,
+		overrides: &SurrealismLimits,
+	) -> Result<Controller> {
 		let wasi_ctx = super::wasi_context::build()?;
+		let limits = self.config.limits.with_overrides(overrides);
 
 		let store_data = StoreData {
 			wasi: wasi_ctx,
 			config: self.config.clone(),
 			context,
+			limiter: MemoryLimiter {
+				memory: limits.memory,
+			},
 		};
 		let mut store = Store::new(&self.engine, store_data);
-		let instance = self
-			.linker
-			.instantiate_async(&mut store, &self.module)
-			.await
-			.prefix_err(|| "failed to instantiate WASM module")?;
+		store.limiter(|data| &mut data.limiter);
+		if let Some(fuel) = limits.fuel {
+			if !self.fuel {
+				anyhow::bail!(
+					"The runtime does not meter fuel, so it can't limit the fuel of a controller"
+				);
+			}
+			store.set_fuel(fuel)?;
+		} else if self.fuel {
+			store.set_fuel(u64::MAX)?;
+		}
+		// Check the deadline and the cancellation on every epoch
+		let deadline = limits.timeout().map(|timeout| (Instant::now() + timeout, timeout));
+		store.set_epoch_deadline(1);
+		store.epoch_deadline_callback(move |store| {
+			if store.data().context.cancelled() {
+				return Err(LimitError::Cancelled.into());
+			}
+			if let Some((deadline, timeout)) = deadline
+				&& deadline <= Instant::now()
+			{
+				return Err(LimitError::Timeout(timeout).into());
+			}
+			Ok(UpdateDeadline::Yield(1))
+		});
+		let instance =
+			self.linker.instantiate_async(&mut store, &self.module).await.map_err(|e| {
+				match limit_error(e, &limits) {
+					e if e.is::<LimitError>() => e,
+					e => anyhow::anyhow!("failed to instantiate WASM module: {e}"),
+				}
+			})?;
 		let memory = instance
 			.get_memory(&mut store, "memory")
 			.prefix_err(|| "WASM module must export 'memory'")?;
@@ -136,10 +207,33 @@ impl Runtime {
 			store,
 			instance,
 			memory,
+			limits,
 		})
 	}
 }
 
+/// Surfaces the error of a call which exceeded one of the limits as a [`LimitError`].
+fn limit_error(error: anyhow::Error, limits: &SurrealismLimits) -> anyhow::Error {
+	if let Some(e) = error.downcast_ref::<LimitError>() {
+		return e.clone().into();
+	}
+	match error.downcast_ref::<Trap>() {
+		Some(Trap::OutOfFuel) => LimitError::Fuel(limits.fuel.unwrap_or(u64::MAX)).into(),
+		_ => error,
+	}
+}
+
+/// The kind of a function which is exported by a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FunctionKind {
+	/// A function which is called once per invocation, and returns a single value.
+	Scalar,
+	/// An aggregate, which is computed with init, accumulate, merge and finalize calls.
+	Aggregate,
+	/// A function which returns a set of rows.
+	Table,
+}
+
 /// Per-execution controller. Not thread-safe - create one per concurrent call.
 /// Lightweight, created from Runtime. Each controller has its own isolated Store and Instance.
 #[derive(Debug)]
@@ -147,12 +241,16 @@ pub struct Controller {
 	pub(super) store: Store<StoreData>,
 	pub(super) instance: Instance,
 	pub(super) memory: Memory,
+	pub(super) limits: SurrealismLimits,
 }
 
 impl Controller {
 	pub async fn alloc(&mut self, len: u32) -> Result<u32> {
 		let alloc = self.instance.get_typed_func::<(u32,), i32>(&mut self.store, "__sr_alloc")?;
-		let result = alloc.call_async(&mut self.store, (len,)).await?;
+		let result = alloc
+			.call_async(&mut self.store, (len,))
+			.await
+			.map_err(|e| limit_error(e, &self.limits))?;
 		if result == -1 {
 			anyhow::bail!("Memory allocation failed");
 		}
@@ -161,7 +259,10 @@ impl Controller {
 
 	pub async fn free(&mut self, ptr: u32, len: u32) -> Result<()> {
 		let free = self.instance.get_typed_func::<(u32, u32), i32>(&mut self.store, "__sr_free")?;
-		let result = free.call_async(&mut self.store, (ptr, len)).await?;
+		let result = free
+			.call_async(&mut self.store, (ptr, len))
+			.await
+			.map_err(|e| limit_error(e, &self.limits))?;
 		if result == -1 {
 			anyhow::bail!("Memory deallocation failed");
 		}
@@ -175,7 +276,7 @@ impl Controller {
 		}
 
 		let init = self.instance.get_typed_func::<(), ()>(&mut self.store, "__sr_init")?;
-		init.call_async(&mut self.store, ()).await
+		init.call_async(&mut self.store, ()).await.map_err(|e| limit_error(e, &self.limits))
 	}
 
 	pub async fn invoke<A: Args>(
@@ -184,9 +285,62 @@ impl Controller {
 		args: A,
 	) -> Result<surrealdb_types::Value> {
 		let name = format!("__sr_fnc__{}", name.unwrap_or_default());
-		let args = AsyncTransfer::transfer(args.to_values(), self).await?;
-		let invoke = self.instance.get_typed_func::<(u32,), (i32,)>(&mut self.store, &name)?;
-		let (ptr,) = invoke.call_async(&mut self.store, (*args,)).await?;
+		self.call(&name, args.to_values()).await
+	}
+
+	/// Returns the state of an empty group of an aggregate.
+	pub async fn aggregate_init(&mut self, name: Option<String>) -> Result<surrealdb_types::Value> {
+		let name = format!("__sr_agg_init__{}", name.unwrap_or_default());
+		self.call(&name, Vec::new()).await
+	}
+
+	/// Folds the arguments for a single value into the state of an aggregate.
+	pub async fn aggregate_accumulate(
+		&mut self,
+		name: Option<String>,
+		state: surrealdb_types::Value,
+		args: Vec<surrealdb_types::Value>,
+	) -> Result<surrealdb_types::Value> {
+		let name = format!("__sr_agg_accumulate__{}", name.unwrap_or_default());
+		let mut values = Vec::with_capacity(args.len() + 1);
+		values.push(state);
+		values.extend(args);
+		self.call(&name, values).await
+	}
+
+	/// Combines two partial states of an aggregate.
+	pub async fn aggregate_merge(
+		&mut self,
+		name: Option<String>,
+		left: surrealdb_types::Value,
+		right: surrealdb_types::Value,
+	) -> Result<surrealdb_types::Value> {
+		let name = format!("__sr_agg_merge__{}", name.unwrap_or_default());
+		self.call(&name, vec![left, right]).await
+	}
+
+	/// Computes the result of an aggregate from its final state.
+	pub async fn aggregate_finalize(
+		&mut self,
+		name: Option<String>,
+		state: surrealdb_types::Value,
+	) -> Result<surrealdb_types::Value> {
+		let name = format!("__sr_agg_finalize__{}", name.unwrap_or_default());
+		self.call(&name, vec![state]).await
+	}
+
+	/// Calls an export which receives a list of values, and returns a value or an error.
+	async fn call(
+		&mut self,
+		export: &str,
+		args: Vec<surrealdb_types::Value>,
+	) -> Result<surrealdb_types::Value> {
+		let args = AsyncTransfer::transfer(args, self).await?;
+		let invoke = self.instance.get_typed_func::<(u32,), (i32,)>(&mut self.store, export)?;
+		let (ptr,) = invoke
+			.call_async(&mut self.store, (*args,))
+			.await
+			.map_err(|e| limit_error(e, &self.limits))?;
 		if ptr == -1 {
 			anyhow::bail!("WASM function returned error (-1)");
 		}
@@ -196,17 +350,43 @@ impl Controller {
 		result.map_err(|e| anyhow::anyhow!("WASM function returned error: {}", e))
 	}
 
+	/// Returns the kind of an exported function, or `None` if the module does not export it.
+	/// Fails if one of the exports of the function is not a function.
+	pub fn kind(&mut self, name: Option<String>) -> Result<Option<FunctionKind>> {
+		let name = name.unwrap_or_default();
+		let has_export = |controller: &mut Self, export: String| -> Result<bool> {
+			match controller.instance.get_export(&mut controller.store, &export) {
+				None => Ok(false),
+				Some(Extern::Func(_)) => Ok(true),
+				Some(_) => anyhow::bail!("The export {export} is not a function"),
+			}
+		};
+		if has_export(self, format!("__sr_agg_init__{name}"))? {
+			Ok(Some(FunctionKind::Aggregate))
+		} else if !has_export(self, format!("__sr_fnc__{name}"))? {
+			Ok(None)
+		} else if has_export(self, format!("__sr_tbl__{name}"))? {
+			Ok(Some(FunctionKind::Table))
+		} else {
+			Ok(Some(FunctionKind::Scalar))
+		}
+	}
+
 	pub async fn args(&mut self, name: Option<String>) -> Result<Vec<surrealdb_types::Kind>> {
 		let name = format!("__sr_args__{}", name.unwrap_or_default());
 		let args = self.instance.get_typed_func::<(), (i32,)>(&mut self.store, &name)?;
-		let (ptr,) = args.call_async(&mut self.store, ()).await?;
+		let (ptr,) =
+			args.call_async(&mut self.store, ()).await.map_err(|e| limit_error(e, &self.limits))?;
 		AsyncTransfer::receive(ptr.try_into()?, self).await
 	}
 
 	pub async fn returns(&mut self, name: Option<String>) -> Result<surrealdb_types::Kind> {
 		let name = format!("__sr_returns__{}", name.unwrap_or_default());
 		let returns = self.instance.get_typed_func::<(), (i32,)>(&mut self.store, &name)?;
-		let (ptr,) = returns.call_async(&mut self.store, ()).await?;
+		let (ptr,) = returns
+			.call_async(&mut self.store, ())
+			.await
+			.map_err(|e| limit_error(e, &self.limits))?;
 		if ptr == -1 {
 			anyhow::bail!("WASM function returned error (-1)");
 		}
@@ -217,13 +397,13 @@ impl Controller {
 		// scan the exported functions and return a list of available functions
 		let mut functions = Vec::new();
 
-		// First, collect all export names that start with __sr_fnc__
+		// First, collect all export names of functions and aggregates
 		let function_names: Vec<String> = {
 			let exports = self.instance.exports(&mut self.store);
 			exports
 				.filter_map(|export| {
 					let name = export.name();
-					if name.starts_with("__sr_fnc__") {
+					if name.starts_with("__sr_fnc__") || name.starts_with("__sr_agg_init__") {
 						Some(name.to_string())
 					} else {
 						None
@@ -238,7 +418,11 @@ impl Controller {
 				&& let ExternType::Func(_) = export.ty(&self.store)
 			{
 				// strip the prefix
-				let function_name = name.strip_prefix("__sr_fnc__").unwrap_or(&name).to_string();
+				let function_name = name
+					.strip_prefix("__sr_fnc__")
+					.or_else(|| name.strip_prefix("__sr_agg_init__"))
+					.unwrap_or(&name)
+					.to_string();
 				functions.push(function_name);
 			}
 		}
@@ -274,3 +458,152 @@ impl surrealism_types::controller::AsyncMemoryController for Controller {
 		Ok(&mut mem[start..end])
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use semver::Version;
+
+	use super::*;
+	use crate::config::SurrealismMeta;
+	use crate::kv::KVStore;
+
+	/// A module with a function whose arguments loop forever, one whose
+	/// arguments grow the memory by 16 pages, and a global exported as a function.
+	const WAT: &str = r#"
+		(module
+			(memory (export "memory") 1)
+			(func (export "__sr_fnc__spin") (param i32) (result i32) i32.const -1)
+			(func (export "__sr_args__spin") (result i32) (loop (br 0)) i32.const -1)
+			(func (export "__sr_fnc__grow") (param i32) (result i32) i32.const -1)
+			(func (export "__sr_args__grow") (result i32)
+				(drop (memory.grow (i32.const 16)))
+				i32.const -1)
+			(global (export "__sr_fnc__global") i32 (i32.const 0))
+		)
+	"#;
+
+	struct TestContext;
+
+	#[async_trait]
+	impl InvocationContext for TestContext {
+		async fn sql(
+			&mut self,
+			_config: &SurrealismConfig,
+			_query: String,
+			_vars: surrealdb_types::Object,
+		) -> Result<surrealdb_types::Value> {
+			anyhow::bail!("Queries are not supported in tests")
+		}
+
+		async fn run(
+			&mut self,
+			_config: &SurrealismConfig,
+			_fnc: String,
+			_version: Option<String>,
+			_args: Vec<surrealdb_types::Value>,
+		) -> Result<surrealdb_types::Value> {
+			anyhow::bail!("Functions are not supported in tests")
+		}
+
+		fn kv(&mut self) -> Result<&dyn KVStore> {
+			anyhow::bail!("The key-value store is not supported in tests")
+		}
+	}
+
+	fn package(limits: SurrealismLimits) -> SurrealismPackage {
+		SurrealismPackage {
+			config: SurrealismConfig {
+				meta: SurrealismMeta {
+					organisation: "surrealdb".to_string(),
+					name: "limits".to_string(),
+					version: Version::new(1, 0, 0),
+				},
+				capabilities: Default::default(),
+				limits,
+			},
+			wasm: WAT.as_bytes().to_vec(),
+		}
+	}
+
+	async fn limit_error(runtime: &Runtime, overrides: SurrealismLimits, sub: &str) -> LimitError {
+		let mut controller =
+			runtime.new_controller_with_limits(Box::new(TestContext), &overrides).await.unwrap();
+		let err = controller.args(Some(sub.to_string())).await.unwrap_err();
+		err.downcast::<LimitError>().unwrap()
+	}
+
+	#[tokio::test]
+	async fn fuel_is_only_metered_with_a_limit() {
+		let runtime = Runtime::new(package(SurrealismLimits::default())).unwrap();
+		assert!(!runtime.fuel);
+		let mut controller = runtime.new_controller(Box::new(TestContext)).await.unwrap();
+		assert_eq!(controller.kind(Some("spin".to_string())).unwrap(), Some(FunctionKind::Scalar));
+		// A runtime which doesn't meter fuel can't limit it
+		let overrides = SurrealismLimits {
+			fuel: Some(1000),
+			..Default::default()
+		};
+		let err = runtime.new_controller_with_limits(Box::new(TestContext), &overrides).await;
+		assert!(err.is_err());
+		// The limits of the package, or the overrides, enable the metering
+		let limits = SurrealismLimits {
+			fuel: Some(1000),
+			..Default::default()
+		};
+		assert!(Runtime::new(package(limits)).unwrap().fuel);
+		let runtime = Runtime::with_limits(package(SurrealismLimits::default()), &overrides);
+		assert!(runtime.unwrap().fuel);
+	}
+
+	#[tokio::test]
+	async fn kind() {
+		let runtime = Runtime::new(package(SurrealismLimits::default())).unwrap();
+		let mut controller = runtime.new_controller(Box::new(TestContext)).await.unwrap();
+		assert_eq!(controller.kind(Some("grow".to_string())).unwrap(), Some(FunctionKind::Scalar));
+		assert_eq!(controller.kind(Some("missing".to_string())).unwrap(), None);
+		let err = controller.kind(Some("global".to_string())).unwrap_err();
+		assert_eq!(err.to_string(), "The export __sr_fnc__global is not a function");
+	}
+
+	#[tokio::test]
+	async fn fuel_limit() {
+		let limits = SurrealismLimits {
+			fuel: Some(1000),
+			..Default::default()
+		};
+		let runtime = Runtime::new(package(limits)).unwrap();
+		let err = limit_error(&runtime, SurrealismLimits::default(), "spin").await;
+		assert_eq!(err, LimitError::Fuel(1000));
+		// The limit of the package can be overridden
+		let overrides = SurrealismLimits {
+			fuel: Some(2000),
+			..Default::default()
+		};
+		let err = limit_error(&runtime, overrides, "spin").await;
+		assert_eq!(err, LimitError::Fuel(2000));
+	}
+
+	#[tokio::test]
+	async fn memory_limit() {
+		let runtime = Runtime::new(package(SurrealismLimits::default())).unwrap();
+		let overrides = SurrealismLimits {
+			memory: Some(131072),
+			..Default::default()
+		};
+		let err = limit_error(&runtime, overrides, "grow").await;
+		assert_eq!(err, LimitError::Memory(131072));
+	}
+
+	#[tokio::test]
+	async fn timeout_limit() {
+		let runtime = Runtime::new(package(SurrealismLimits::default())).unwrap();
+		let overrides = SurrealismLimits {
+			timeout_ms: Some(50),
+			..Default::default()
+		};
+		let err = limit_error(&runtime, overrides, "spin").await;
+		assert_eq!(err, LimitError::Timeout(Duration::from_millis(50)));
+	}
+}
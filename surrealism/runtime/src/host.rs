@@ -133,6 +133,11 @@ pub trait InvocationContext: Send + Sync {
 
 	fn kv(&mut self) -> Result<&dyn KVStore>;
 
+	/// Whether the invocation was cancelled, in which case the WASM module is interrupted
+	fn cancelled(&self) -> bool {
+		false
+	}
+
 	/// Handle stdout output from the WASM module
 	fn stdout(&mut self, output: &str) -> Result<()> {
 		// Default implementation: print to standard output
@@ -3,5 +3,6 @@ pub mod config;
 pub mod controller;
 pub mod host;
 pub mod kv;
+pub mod limits;
 pub mod package;
 mod wasi_context;
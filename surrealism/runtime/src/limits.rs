@@ -0,0 +1,166 @@
+//! Resource limits for the execution of a WASM module.
+//!
+//! Limits are declared in the `[limits]` section of `surrealism.toml`, and can be
+//! overridden when the module is loaded into a database:
+//!
+//! ```toml
+//! [limits]
+//! fuel = 1000000000
+//! memory = 67108864
+//! timeout_ms = 5000
+//! ```
+//!
+//! - **`fuel`**: The number of WASM instructions (approximately) that a single controller may
+//!   execute before it is interrupted. Fuel is only metered by a runtime which was created with a
+//!   fuel limit, so that modules without one don't pay for the metering.
+//! - **`memory`**: The maximum size, in bytes, that the linear memory of the module may grow to.
+//! - **`timeout_ms`**: The maximum wall-clock time, in milliseconds, that a single controller may
+//!   run for. The invocation context can also cancel the execution at any time.
+
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use surrealism_types::err::PrefixError;
+use wasmtime::{Engine, EngineWeak, ResourceLimiter};
+
+/// How often the epoch of an engine is incremented, and so how often a running
+/// module checks its deadline and the cancellation of its invocation context.
+pub(crate) const EPOCH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The engines whose epoch is incremented, and whether the thread which
+/// increments them is running.
+struct EpochTicker {
+	engines: Vec<EngineWeak>,
+	running: bool,
+}
+
+static EPOCH_TICKER: Mutex<EpochTicker> = Mutex::new(EpochTicker {
+	engines: Vec::new(),
+	running: false,
+});
+
+/// Increments the epoch of an engine until it is dropped.
+///
+/// A single thread increments the epochs of all the engines, and stops once
+/// every engine has been dropped.
+pub(crate) fn tick_epoch(engine: &Engine) -> Result<()> {
+	let mut ticker = EPOCH_TICKER.lock().unwrap_or_else(PoisonError::into_inner);
+	ticker.engines.push(engine.weak());
+	if ticker.running {
+		return Ok(());
+	}
+	std::thread::Builder::new()
+		.name("surrealism-epoch".to_string())
+		.spawn(|| {
+			loop {
+				{
+					let mut ticker = EPOCH_TICKER.lock().unwrap_or_else(PoisonError::into_inner);
+					ticker.engines.retain(|weak| match weak.upgrade() {
+						Some(engine) => {
+							engine.increment_epoch();
+							true
+						}
+						None => false,
+					});
+					if ticker.engines.is_empty() {
+						ticker.running = false;
+						return;
+					}
+				}
+				std::thread::sleep(EPOCH_INTERVAL);
+			}
+		})
+		.prefix_err(|| "failed to spawn the epoch thread")?;
+	ticker.running = true;
+	Ok(())
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SurrealismLimits {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub fuel: Option<u64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub memory: Option<u64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub timeout_ms: Option<u64>,
+}
+
+impl SurrealismLimits {
+	/// Returns these limits, with any limit which is set in `overrides` replaced.
+	pub fn with_overrides(&self, overrides: &SurrealismLimits) -> SurrealismLimits {
+		SurrealismLimits {
+			fuel: overrides.fuel.or(self.fuel),
+			memory: overrides.memory.or(self.memory),
+			timeout_ms: overrides.timeout_ms.or(self.timeout_ms),
+		}
+	}
+
+	pub fn timeout(&self) -> Option<Duration> {
+		self.timeout_ms.map(Duration::from_millis)
+	}
+}
+
+/// The error returned when the execution of a module exceeded one of its limits,
+/// or was cancelled by its invocation context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitError {
+	/// The module ran out of fuel
+	Fuel(u64),
+	/// The module tried to grow its memory beyond the limit, in bytes
+	Memory(u64),
+	/// The module ran for longer than the timeout
+	Timeout(Duration),
+	/// The invocation context cancelled the execution
+	Cancelled,
+}
+
+impl fmt::Display for LimitError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			LimitError::Fuel(fuel) => {
+				write!(f, "The module exceeded its limit of {fuel} units of fuel")
+			}
+			LimitError::Memory(memory) => {
+				write!(f, "The module exceeded its memory limit of {memory} bytes")
+			}
+			LimitError::Timeout(timeout) => {
+				write!(f, "The module exceeded its timeout of {timeout:?}")
+			}
+			LimitError::Cancelled => write!(f, "The module execution was cancelled"),
+		}
+	}
+}
+
+impl std::error::Error for LimitError {}
+
+/// Bounds the growth of the linear memories of a store.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryLimiter {
+	pub(crate) memory: Option<u64>,
+}
+
+impl ResourceLimiter for MemoryLimiter {
+	fn memory_growing(
+		&mut self,
+		_current: usize,
+		desired: usize,
+		_maximum: Option<usize>,
+	) -> anyhow::Result<bool> {
+		match self.memory {
+			Some(limit) if desired as u64 > limit => Err(LimitError::Memory(limit).into()),
+			_ => Ok(true),
+		}
+	}
+
+	fn table_growing(
+		&mut self,
+		_current: usize,
+		_desired: usize,
+		_maximum: Option<usize>,
+	) -> anyhow::Result<bool> {
+		Ok(true)
+	}
+}
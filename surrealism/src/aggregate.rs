@@ -0,0 +1,123 @@
+use anyhow::Result;
+use surrealdb_types::{SurrealValue, Value};
+use surrealism_types::arg::SerializableArg;
+use surrealism_types::args::Args;
+use surrealism_types::controller::MemoryController;
+use surrealism_types::transfer::{Ptr, Transfer};
+
+/// An aggregate function which can be used in a `GROUP BY` query.
+///
+/// An aggregate is computed by creating an empty state with [`init`](Self::init),
+/// folding every value of a group into that state with [`accumulate`](Self::accumulate),
+/// and turning the final state into the result with [`finalize`](Self::finalize).
+/// When a group is computed in parts, the partial states are combined with
+/// [`merge`](Self::merge).
+///
+/// The state is passed between the host and the module as a value, so it must
+/// implement [`SurrealValue`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[surrealism(aggregate)]
+/// struct Total;
+///
+/// impl SurrealismAggregate for Total {
+///     type State = i64;
+///     type Input = (i64,);
+///     type Output = i64;
+///
+///     fn init() -> i64 {
+///         0
+///     }
+///
+///     fn accumulate(state: i64, (value,): (i64,)) -> Result<i64, String> {
+///         Ok(state + value)
+///     }
+///
+///     fn merge(left: i64, right: i64) -> Result<i64, String> {
+///         Ok(left + right)
+///     }
+///
+///     fn finalize(state: i64) -> Result<i64, String> {
+///         Ok(state)
+///     }
+/// }
+/// ```
+pub trait SurrealismAggregate: 'static {
+	/// The intermediate state of the aggregate.
+	type State: SurrealValue;
+	/// The arguments of the aggregate. The first argument is the value which is
+	/// aggregated, and any further arguments are passed unchanged for each value.
+	type Input: Args;
+	/// The result of the aggregate.
+	type Output: SurrealValue;
+
+	/// Creates the state of an empty group.
+	fn init() -> Self::State;
+
+	/// Folds the arguments for a single value into the state.
+	fn accumulate(state: Self::State, input: Self::Input) -> Result<Self::State, String>;
+
+	/// Combines two partial states of the same group.
+	fn merge(left: Self::State, right: Self::State) -> Result<Self::State, String>;
+
+	/// Computes the result of the aggregate from its final state.
+	fn finalize(state: Self::State) -> Result<Self::Output, String>;
+}
+
+/// Transfers the argument kinds of the aggregate.
+pub fn args_raw<T: SurrealismAggregate>(controller: &mut dyn MemoryController) -> Result<Ptr> {
+	T::Input::kinds().transfer(controller)
+}
+
+/// Transfers the return kind of the aggregate.
+pub fn returns_raw<T: SurrealismAggregate>(controller: &mut dyn MemoryController) -> Result<Ptr> {
+	T::Output::kind_of().transfer(controller)
+}
+
+/// Transfers the state of an empty group.
+pub fn init_raw<T: SurrealismAggregate>(
+	controller: &mut dyn MemoryController,
+	args: Ptr,
+) -> Result<Ptr> {
+	Vec::<Value>::receive(args, controller)?;
+	Ok::<_, String>(SerializableArg::from(T::init())).transfer(controller)
+}
+
+/// Receives `[state, ...input]` and transfers the new state.
+pub fn accumulate_raw<T: SurrealismAggregate>(
+	controller: &mut dyn MemoryController,
+	args: Ptr,
+) -> Result<Ptr> {
+	let mut args = Vec::<Value>::receive(args, controller)?.into_iter();
+	let state = receive_state::<T>(args.next())?;
+	let input = T::Input::from_values(args.collect()).map_err(|e| anyhow::anyhow!("{}", e))?;
+	T::accumulate(state, input).map(SerializableArg::from).transfer(controller)
+}
+
+/// Receives `[left, right]` and transfers the merged state.
+pub fn merge_raw<T: SurrealismAggregate>(
+	controller: &mut dyn MemoryController,
+	args: Ptr,
+) -> Result<Ptr> {
+	let mut args = Vec::<Value>::receive(args, controller)?.into_iter();
+	let left = receive_state::<T>(args.next())?;
+	let right = receive_state::<T>(args.next())?;
+	T::merge(left, right).map(SerializableArg::from).transfer(controller)
+}
+
+/// Receives `[state]` and transfers the result of the aggregate.
+pub fn finalize_raw<T: SurrealismAggregate>(
+	controller: &mut dyn MemoryController,
+	args: Ptr,
+) -> Result<Ptr> {
+	let mut args = Vec::<Value>::receive(args, controller)?.into_iter();
+	let state = receive_state::<T>(args.next())?;
+	T::finalize(state).map(SerializableArg::from).transfer(controller)
+}
+
+fn receive_state<T: SurrealismAggregate>(value: Option<Value>) -> Result<T::State> {
+	let value = value.ok_or_else(|| anyhow::anyhow!("Missing aggregate state"))?;
+	T::State::from_value(value).map_err(|e| anyhow::anyhow!("Invalid aggregate state: {}", e))
+}
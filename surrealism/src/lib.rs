@@ -1,8 +1,10 @@
+pub mod aggregate;
 pub mod controller;
 pub mod err;
 pub mod imports;
 pub mod memory;
 pub mod registry;
+pub use aggregate::SurrealismAggregate;
 pub use controller::Controller;
 pub use imports::{kv, run, sql};
 pub use registry::SurrealismFunction;
@@ -5515,6 +5515,325 @@ mod graphql_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn connections_and_aggregates() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+
+					DEFINE TABLE person SCHEMAFULL;
+					DEFINE FIELD name ON person TYPE string;
+					DEFINE FIELD team ON person TYPE string;
+					DEFINE FIELD age ON person TYPE int;
+
+					CREATE person:a SET name = "Alice", team = "red", age = 30;
+					CREATE person:b SET name = "Bob", team = "red", age = 20;
+					CREATE person:c SET name = "Carol", team = "blue", age = 40;
+					CREATE person:d SET name = "Dave", team = "blue", age = 50;
+					CREATE person:e SET name = "Eve", team = "red", age = 10;
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// Page forwards through the connection two records at a time
+		let mut after = serde_json::Value::Null;
+		let mut names = Vec::new();
+		for page in 0..3 {
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"query": r#"query($after: String) {
+							personConnection(first: 2, after: $after, order: {asc: age}) {
+								totalCount
+								edges { cursor node { name } }
+								pageInfo { hasNextPage hasPreviousPage endCursor }
+							}
+						}"#,
+						"variables": { "after": after }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "connection query failed: {:?}", body["errors"]);
+			let conn = &body["data"]["personConnection"];
+			assert_eq!(conn["totalCount"], 5);
+			for edge in conn["edges"].as_array().unwrap() {
+				names.push(edge["node"]["name"].as_str().unwrap().to_string());
+			}
+			assert_eq!(conn["pageInfo"]["hasNextPage"], page < 2);
+			assert_eq!(conn["pageInfo"]["hasPreviousPage"], page > 0);
+			after = conn["pageInfo"]["endCursor"].clone();
+		}
+		assert_eq!(names, ["Eve", "Bob", "Alice", "Carol", "Dave"]);
+
+		// A cursor is only accepted for the order it was returned with
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						personConnection(first: 1, order: {asc: name}) {
+							pageInfo { endCursor }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "connection query failed: {:?}", body["errors"]);
+			let cursor = body["data"]["personConnection"]["pageInfo"]["endCursor"].clone();
+			let oversized = "A".repeat(10_000);
+			for after in [cursor, json!(oversized)] {
+				let res = client
+					.post(gql_url)
+					.body(
+						json!({
+							"query": r#"query($after: String) {
+								personConnection(first: 2, after: $after, order: {asc: age}) {
+									nodes { name }
+								}
+							}"#,
+							"variables": { "after": after }
+						})
+						.to_string(),
+					)
+					.send()
+					.await?;
+				assert_eq!(res.status(), 200);
+				let body = res.json::<serde_json::Value>().await?;
+				assert!(
+					body["errors"][0]["message"].as_str().unwrap().contains("Invalid cursor"),
+					"expected the cursor to be rejected, got: {body:?}"
+				);
+			}
+		}
+
+		// Page backwards from the end of the connection
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						personConnection(last: 2, order: {asc: age}, filter: {team: {eq: "red"}}) {
+							totalCount
+							nodes { name }
+							pageInfo { hasNextPage hasPreviousPage }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "connection query failed: {:?}", body["errors"]);
+			let conn = &body["data"]["personConnection"];
+			assert_eq!(conn["totalCount"], 3);
+			assert_eq!(conn["nodes"], json!([{"name": "Bob"}, {"name": "Alice"}]));
+			assert_eq!(conn["pageInfo"]["hasNextPage"], false);
+			assert_eq!(conn["pageInfo"]["hasPreviousPage"], true);
+		}
+
+		// Aggregate the records grouped by team
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						personAggregate(groupBy: [team]) {
+							count
+							group
+							sum { age }
+							avg { age }
+							min { age }
+							max { age }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "aggregate query failed: {:?}", body["errors"]);
+			let groups = body["data"]["personAggregate"].as_array().unwrap();
+			assert_eq!(groups.len(), 2);
+			let blue = groups.iter().find(|g| g["group"]["team"] == "blue").unwrap();
+			assert_eq!(blue["count"], 2);
+			assert_eq!(blue["sum"]["age"], 90);
+			assert_eq!(blue["avg"]["age"].as_f64(), Some(45.0));
+			assert_eq!(blue["min"]["age"], 40);
+			assert_eq!(blue["max"]["age"], 50);
+			let red = groups.iter().find(|g| g["group"]["team"] == "red").unwrap();
+			assert_eq!(red["count"], 3);
+			assert_eq!(red["sum"]["age"], 60);
+			assert_eq!(red["min"]["age"], 10);
+			assert_eq!(red["max"]["age"], 30);
+		}
+
+		// Without groupBy all of the matching records form a single group
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						personAggregate(where: {age: {gt: 15}}) { count max { age } }
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "aggregate query failed: {:?}", body["errors"]);
+			assert_eq!(body["data"]["personAggregate"], json!([{"count": 4, "max": {"age": 50}}]));
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn fulltext_and_vector_search() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+
+					DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+
+					DEFINE TABLE article SCHEMAFULL;
+					DEFINE FIELD title ON article TYPE string;
+					DEFINE FIELD embedding ON article TYPE array<float>;
+					DEFINE INDEX article_title ON article FIELDS title FULLTEXT ANALYZER simple BM25 HIGHLIGHTS;
+					DEFINE INDEX article_embedding ON article FIELDS embedding HNSW DIMENSION 2 DIST EUCLIDEAN;
+
+					CREATE article:one SET title = "Graph databases", embedding = [1.0, 1.0];
+					CREATE article:two SET title = "Vector search in databases", embedding = [5.0, 5.0];
+					CREATE article:three SET title = "Cooking pasta", embedding = [10.0, 10.0];
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// Full-text search with scores and highlights
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						article(search: {title: {query: "databases"}}, order: {asc: title}) {
+							id
+							_score
+							_search { title { score highlight } }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "full-text search failed: {:?}", body["errors"]);
+			let articles = body["data"]["article"].as_array().unwrap();
+			assert_eq!(articles.len(), 2);
+			assert_eq!(articles[0]["id"], "article:one");
+			assert_eq!(articles[0]["_search"]["title"]["highlight"], "Graph <b>databases</b>");
+			assert_eq!(articles[1]["id"], "article:two");
+			for article in articles {
+				assert!(article["_score"].as_f64().unwrap() > 0.0);
+				assert_eq!(article["_score"], article["_search"]["title"]["score"]);
+			}
+		}
+
+		// Nearest neighbour search, ordered by distance
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						article(nearest: {embedding: {vector: [4.0, 4.0], k: 2}}) {
+							id
+							_distance
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "nearest search failed: {:?}", body["errors"]);
+			let articles = body["data"]["article"].as_array().unwrap();
+			assert_eq!(articles.len(), 2);
+			assert_eq!(articles[0]["id"], "article:two");
+			assert_eq!(articles[1]["id"], "article:one");
+			assert!(articles[0]["_distance"].as_f64() < articles[1]["_distance"].as_f64());
+		}
+
+		// Fields without a search index are rejected
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{ article(nearest: {title: {vector: [1.0], k: 1}}) { id } }"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(!body["errors"].is_null());
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn subscriptions_live_query_stream() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_without_auth().await.unwrap();